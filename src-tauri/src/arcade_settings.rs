@@ -0,0 +1,48 @@
+//! ClaudeArcade's own preferences, in ~/.claude/arcade_settings.json
+//! Claude Code never reads this file; it holds what the app itself does in the background.
+
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+use crate::commands::context_guard::ContextGuardSettings;
+use crate::commands::usage_window::UsageLimits;
+use crate::http::NetworkSettings;
+use crate::notifications::NotificationSettings;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ArcadeSettings {
+    pub notifications: NotificationSettings,
+    /// Where the latest Claude Code release is looked up; the npm registry when unset.
+    /// Must answer with JSON holding a `version` field.
+    pub claude_release_url: Option<String>,
+    /// WSL `.claude` folders (`\\wsl$\<distro>\home\<user>\.claude`) scanned alongside this machine's
+    pub wsl_config_dirs: Vec<String>,
+    /// Plan or limits the 5-hour usage window is measured against
+    pub usage_limits: UsageLimits,
+    /// Proxy, timeouts, retries and offline mode for network features
+    pub network: NetworkSettings,
+    /// Accept events from hooks and scripts on a localhost port
+    pub event_listener: bool,
+    /// Have Claude Code send its OpenTelemetry metrics to the event listener
+    pub otel_metrics: bool,
+    /// Thresholds of the context guard hooks
+    pub context_guard: ContextGuardSettings,
+}
+
+fn settings_path() -> Option<PathBuf> {
+    crate::paths::claude_dir().map(|d| d.join("arcade_settings.json"))
+}
+
+/// Saved settings, or the defaults when there are none (or they can't be read)
+pub fn load_arcade_settings() -> ArcadeSettings {
+    settings_path()
+        .and_then(|path| crate::store::SETTINGS.load(&path))
+        .unwrap_or_default()
+}
+
+pub fn save_arcade_settings(settings: &ArcadeSettings) -> Result<(), String> {
+    let path = settings_path().ok_or("Could not find home directory")?;
+    let content = crate::store::SETTINGS.to_json(settings)?;
+    crate::paths::atomic_write(&path, content).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
@@ -0,0 +1,162 @@
+//! Append-only log of the changes ClaudeArcade makes to Claude config, in ~/.claude/arcade_audit.jsonl
+//! Writes go through the watcher's `write_expected`, which records them once they land; removals are recorded by their commands.
+
+use serde::{Deserialize, Serialize};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use crate::scanner::integrity::{checksum_tree, sha256_hex};
+
+/// Size at which the log is moved to arcade_audit.1.jsonl and a new one started
+const MAX_AUDIT_BYTES: u64 = 5 * 1024 * 1024;
+
+/// One change to one file or folder
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditEntry {
+    /// RFC3339
+    pub timestamp: String,
+    /// Command that made the change, from its tracing span; "unknown" outside one
+    pub command: String,
+    pub target: String,
+    /// SHA-256 of the content before (of its files' checksums for a folder), None when it didn't exist
+    pub before_hash: Option<String>,
+    /// SHA-256 of the content after, None when it was removed
+    pub after_hash: Option<String>,
+}
+
+/// Which entries `get_audit_log` returns; unset fields match everything
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct AuditFilter {
+    pub command: Option<String>,
+    /// Part of the target path
+    pub target: Option<String>,
+    /// RFC3339; only entries at or after this time
+    pub since: Option<String>,
+    /// 200 when unset
+    pub limit: Option<usize>,
+}
+
+fn audit_path() -> Option<PathBuf> {
+    crate::paths::claude_dir().map(|d| d.join("arcade_audit.jsonl"))
+}
+
+fn current_command() -> String {
+    tracing::Span::current()
+        .metadata()
+        .map(|m| m.name().to_string())
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+fn content_hash(path: &Path) -> Option<String> {
+    if path.is_dir() {
+        let files = serde_json::to_vec(&checksum_tree(path)).ok()?;
+        return Some(sha256_hex(&files));
+    }
+    fs::read(path).ok().map(|content| sha256_hex(&content))
+}
+
+fn append(entry: &AuditEntry) {
+    let Some(path) = audit_path() else {
+        return;
+    };
+    if fs::metadata(&path).is_ok_and(|m| m.len() > MAX_AUDIT_BYTES) {
+        let _ = fs::rename(&path, path.with_extension("1.jsonl"));
+    }
+    let Ok(line) = serde_json::to_string(entry) else {
+        return;
+    };
+    let written = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .and_then(|mut file| writeln!(file, "{}", line));
+    if let Err(e) = written {
+        tracing::warn!("Failed to append to {}: {}", path.display(), e);
+    }
+}
+
+fn record(target: &Path, before_hash: Option<String>, after_hash: Option<String>) {
+    if before_hash == after_hash {
+        return;
+    }
+    append(&AuditEntry {
+        timestamp: chrono::Utc::now().to_rfc3339(),
+        command: current_command(),
+        target: target.to_string_lossy().to_string(),
+        before_hash,
+        after_hash,
+    });
+}
+
+/// Hash of what is at `path` now, taken before a write to pass to `record_write` after it
+pub fn current_hash(path: &Path) -> Option<String> {
+    content_hash(path)
+}
+
+/// Record that `content` was written to `path` over what `before_hash` hashed. Unchanged content isn't recorded.
+pub fn record_write(path: &Path, before_hash: Option<String>, content: &[u8]) {
+    record(path, before_hash, Some(sha256_hex(content)));
+}
+
+/// Record that the file or folder at `path` is about to be removed
+pub fn record_removal(path: &Path) {
+    if path.exists() {
+        record(path, content_hash(path), None);
+    }
+}
+
+/// Entries matching `filter`, newest first
+pub fn read_audit_log(filter: &AuditFilter) -> Vec<AuditEntry> {
+    let Some(path) = audit_path() else {
+        return Vec::new();
+    };
+    let mut content = fs::read_to_string(path.with_extension("1.jsonl")).unwrap_or_default();
+    content.push_str(&fs::read_to_string(&path).unwrap_or_default());
+
+    let since = filter.since.as_deref().and_then(|s| chrono::DateTime::parse_from_rfc3339(s).ok());
+    let target = filter.target.as_ref().map(|t| t.to_lowercase());
+    content
+        .lines()
+        .rev()
+        .filter_map(|line| serde_json::from_str::<AuditEntry>(line).ok())
+        .filter(|entry| filter.command.as_ref().is_none_or(|c| *c == entry.command))
+        .filter(|entry| target.as_ref().is_none_or(|t| entry.target.to_lowercase().contains(t)))
+        .filter(|entry| {
+            since.is_none_or(|since| {
+                chrono::DateTime::parse_from_rfc3339(&entry.timestamp).is_ok_and(|at| at >= since)
+            })
+        })
+        .take(filter.limit.unwrap_or(200))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TempDir;
+    use crate::paths::ConfigPaths;
+
+    #[test]
+    fn records_changes_and_filters_them() {
+        let root = TempDir::new("audit");
+        let claude_md = root.join(".claude/CLAUDE.md");
+        fs::create_dir_all(root.join(".claude")).unwrap();
+
+        let entries = ConfigPaths::under(&root).scoped(|| {
+            crate::watcher::write_expected(&claude_md, "Be brief").unwrap();
+            crate::watcher::write_expected(&claude_md, "Be brief").unwrap();
+            // A write that fails leaves no entry
+            assert!(crate::watcher::write_expected(root.join(".claude/missing/CLAUDE.md"), "Be brief").is_err());
+            record_removal(&claude_md);
+            read_audit_log(&AuditFilter { target: Some("claude.md".to_string()), ..Default::default() })
+        });
+
+        assert_eq!(entries.len(), 2);
+        assert!(entries[0].after_hash.is_none());
+        assert_eq!(entries[1].before_hash, None);
+        assert_eq!(entries[1].after_hash, entries[0].before_hash);
+    }
+}
@@ -0,0 +1,15 @@
+//! Run blocking filesystem and process work on Tauri's blocking thread pool
+//! Commands await this so a slow disk or network home never stalls the invoke handlers.
+
+/// Run `f` off the async workers and wait for its result. It runs inside the caller's
+/// tracing span, so its logs are attributed to the command.
+pub async fn run_blocking<T, F>(f: F) -> Result<T, String>
+where
+    F: FnOnce() -> T + Send + 'static,
+    T: Send + 'static,
+{
+    let span = tracing::Span::current();
+    tauri::async_runtime::spawn_blocking(move || span.in_scope(f))
+        .await
+        .map_err(|e| format!("Background task failed: {}", e))
+}
@@ -4,6 +4,12 @@
 use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::PathBuf;
+use crate::blocking::run_blocking;
+use crate::error::ArcadeError;
+use crate::paths::{check_name, check_writable};
+use crate::scanner::frontmatter::Frontmatter;
+use crate::secrets::check_secrets;
+use crate::trash::move_to_trash;
 
 /// Agent configuration data
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -28,9 +34,8 @@ pub struct AgentData {
 
 /// Get the global agents directory (~/.claude/agents/)
 fn get_global_agents_dir() -> PathBuf {
-    dirs::home_dir()
+    crate::paths::claude_dir()
         .unwrap_or_default()
-        .join(".claude")
         .join("agents")
 }
 
@@ -58,14 +63,12 @@ fn parse_agent_file(content: &str) -> Option<AgentConfig> {
     }
 
     // Parse frontmatter
-    let after_first = &content[3..];
-    let end_pos = after_first.find("---")?;
-    let yaml_content = &after_first[..end_pos].trim();
-    let body = after_first[end_pos + 3..].trim();
+    let doc = Frontmatter::parse(content);
+    let body = doc.body().trim();
 
     #[derive(Deserialize)]
     #[serde(rename_all = "kebab-case")]
-    struct Frontmatter {
+    struct AgentFrontmatter {
         name: Option<String>,
         description: Option<String>,
         tools: Option<String>,  // Comma-separated in YAML
@@ -74,7 +77,7 @@ fn parse_agent_file(content: &str) -> Option<AgentConfig> {
         skills: Option<String>,  // Comma-separated in YAML
     }
 
-    let fm: Frontmatter = serde_yaml::from_str(yaml_content).ok()?;
+    let fm: AgentFrontmatter = doc.deserialize()?;
 
     // Parse comma-separated tools
     let tools = fm.tools.map(|t| {
@@ -137,45 +140,92 @@ fn generate_agent_content(config: &AgentConfig) -> String {
     lines.join("\n")
 }
 
-/// List all agents (global and project)
-#[tauri::command]
-pub fn list_agents(project_path: Option<String>) -> Vec<AgentData> {
-    let mut agents = Vec::new();
-
-    // Scan global agents
-    let global_dir = get_global_agents_dir();
-    if global_dir.exists() {
-        if let Ok(entries) = fs::read_dir(&global_dir) {
-            for entry in entries.flatten() {
-                let path = entry.path();
-                if path.is_file() && path.extension().map_or(false, |e| e == "md") {
-                    if let Some(agent) = read_agent_at_path(&path, true) {
-                        agents.push(agent);
-                    }
-                }
+/// A list field as written, either a YAML list or a comma-separated line
+fn list_field(doc: &Frontmatter, key: &str) -> Vec<String> {
+    match doc.get(key) {
+        Some(serde_yaml::Value::Sequence(items)) => items.iter().filter_map(|i| i.as_str().map(String::from)).collect(),
+        Some(serde_yaml::Value::String(line)) => {
+            line.split(',').map(|s| s.trim().to_string()).filter(|s| !s.is_empty()).collect()
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// An existing agent file with `config` applied: fields the app doesn't manage, their order and
+/// an unchanged prompt stay as written
+fn update_agent_content(existing: &str, config: &AgentConfig) -> Result<String, String> {
+    let mut doc = Frontmatter::parse(existing);
+    let text_fields = [
+        ("name", Some(config.name.as_str())),
+        ("description", Some(config.description.as_str())),
+        ("model", config.model.as_deref()),
+        ("permission-mode", config.permission_mode.as_deref()),
+    ];
+    for (key, value) in text_fields {
+        match value.filter(|v| !v.is_empty()) {
+            Some(value) => doc.set(key, value)?,
+            None => {
+                doc.remove(key);
+            }
+        }
+    }
+    for (key, values) in [("tools", &config.tools), ("skills", &config.skills)] {
+        match values.as_ref().filter(|v| !v.is_empty()) {
+            Some(values) if *values == list_field(&doc, key) => {}
+            Some(values) => doc.set(key, values.join(", "))?,
+            None => {
+                doc.remove(key);
             }
         }
     }
+    if doc.body().trim() != config.system_prompt.trim() {
+        doc.set_body(format!("\n{}", config.system_prompt));
+    }
+    Ok(doc.to_string())
+}
 
-    // Scan project agents
-    if let Some(ref project) = project_path {
-        let project_dir = get_project_agents_dir(project);
-        if project_dir.exists() {
-            if let Ok(entries) = fs::read_dir(&project_dir) {
+/// List all agents (global and project)
+#[tauri::command]
+pub async fn list_agents(project_path: Option<String>) -> Result<Vec<AgentData>, String> {
+    run_blocking(move || {
+        let mut agents = Vec::new();
+
+        // Scan global agents
+        let global_dir = get_global_agents_dir();
+        if global_dir.exists() {
+            if let Ok(entries) = fs::read_dir(&global_dir) {
                 for entry in entries.flatten() {
                     let path = entry.path();
                     if path.is_file() && path.extension().map_or(false, |e| e == "md") {
-                        if let Some(agent) = read_agent_at_path(&path, false) {
+                        if let Some(agent) = read_agent_at_path(&path, true) {
                             agents.push(agent);
                         }
                     }
                 }
             }
         }
-    }
 
-    agents.sort_by(|a, b| a.config.name.to_lowercase().cmp(&b.config.name.to_lowercase()));
-    agents
+        // Scan project agents
+        if let Some(ref project) = project_path {
+            let project_dir = get_project_agents_dir(project);
+            if project_dir.exists() {
+                if let Ok(entries) = fs::read_dir(&project_dir) {
+                    for entry in entries.flatten() {
+                        let path = entry.path();
+                        if path.is_file() && path.extension().map_or(false, |e| e == "md") {
+                            if let Some(agent) = read_agent_at_path(&path, false) {
+                                agents.push(agent);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        agents.sort_by(|a, b| a.config.name.to_lowercase().cmp(&b.config.name.to_lowercase()));
+        agents
+    })
+    .await
 }
 
 /// Read an agent from a file path
@@ -196,96 +246,127 @@ fn read_agent_at_path(path: &PathBuf, is_global: bool) -> Option<AgentData> {
 
 /// Get a single agent by ID
 #[tauri::command]
-pub fn get_agent(agent_id: String, is_global: bool, project_path: Option<String>) -> Result<AgentData, String> {
-    let file_path = if is_global {
-        get_global_agents_dir().join(format!("{}.md", agent_id))
-    } else {
-        let project = project_path.ok_or("Project path required for project agents")?;
-        get_project_agents_dir(&project).join(format!("{}.md", agent_id))
-    };
-
-    read_agent_at_path(&file_path, is_global)
-        .ok_or_else(|| format!("Agent '{}' not found", agent_id))
+pub async fn get_agent(agent_id: String, is_global: bool, project_path: Option<String>) -> Result<AgentData, ArcadeError> {
+    run_blocking(move || {
+        check_name(&agent_id)?;
+        let file_path = if is_global {
+            get_global_agents_dir().join(format!("{}.md", agent_id))
+        } else {
+            let project = project_path.ok_or("Project path required for project agents")?;
+            get_project_agents_dir(&project).join(format!("{}.md", agent_id))
+        };
+
+        read_agent_at_path(&file_path, is_global)
+            .ok_or_else(|| ArcadeError::not_found("Agent", &agent_id))
+    })
+    .await?
 }
 
 /// Create or update an agent
 #[tauri::command]
-pub fn save_agent(
+#[tracing::instrument(skip(config), err)]
+pub async fn save_agent(
     agent_id: String,
     config: AgentConfig,
     is_global: bool,
     project_path: Option<String>,
 ) -> Result<AgentData, String> {
-    let dir = if is_global {
-        get_global_agents_dir()
-    } else {
-        let project = project_path.ok_or("Project path required for project agents")?;
-        get_project_agents_dir(&project)
-    };
-
-    // Ensure directory exists
-    fs::create_dir_all(&dir).map_err(|e| format!("Failed to create agents directory: {}", e))?;
-
-    let file_path = dir.join(format!("{}.md", agent_id));
-    let content = generate_agent_content(&config);
-
-    fs::write(&file_path, &content).map_err(|e| format!("Failed to write agent file: {}", e))?;
-
-    Ok(AgentData {
-        id: agent_id,
-        file_path: file_path.to_string_lossy().to_string(),
-        is_global,
-        config,
+    run_blocking(move || {
+        check_name(&agent_id)?;
+        let dir = if is_global {
+            get_global_agents_dir()
+        } else {
+            let project = project_path.as_deref().ok_or("Project path required for project agents")?;
+            get_project_agents_dir(project)
+        };
+
+        // Ensure directory exists
+        fs::create_dir_all(&dir).map_err(|e| format!("Failed to create agents directory: {}", e))?;
+
+        let file_path = dir.join(format!("{}.md", agent_id));
+        check_writable(&file_path, project_path.as_deref())?;
+        let content = match fs::read_to_string(&file_path) {
+            Ok(existing) => update_agent_content(&existing, &config)?,
+            Err(_) => generate_agent_content(&config),
+        };
+
+        crate::watcher::write_expected(&file_path, &content).map_err(|e| format!("Failed to write agent file: {}", e))?;
+
+        Ok(AgentData {
+            id: agent_id,
+            file_path: file_path.to_string_lossy().to_string(),
+            is_global,
+            config,
+        })
     })
+    .await?
 }
 
 /// Delete an agent
 #[tauri::command]
-pub fn delete_agent(agent_id: String, is_global: bool, project_path: Option<String>) -> Result<(), String> {
-    let file_path = if is_global {
-        get_global_agents_dir().join(format!("{}.md", agent_id))
-    } else {
-        let project = project_path.ok_or("Project path required for project agents")?;
-        get_project_agents_dir(&project).join(format!("{}.md", agent_id))
-    };
-
-    if file_path.exists() {
-        fs::remove_file(&file_path).map_err(|e| format!("Failed to delete agent: {}", e))?;
-    }
+#[tracing::instrument(err)]
+pub async fn delete_agent(agent_id: String, is_global: bool, project_path: Option<String>) -> Result<(), String> {
+    run_blocking(move || {
+        check_name(&agent_id)?;
+        let file_path = if is_global {
+            get_global_agents_dir().join(format!("{}.md", agent_id))
+        } else {
+            let project = project_path.as_deref().ok_or("Project path required for project agents")?;
+            get_project_agents_dir(project).join(format!("{}.md", agent_id))
+        };
+
+        if file_path.exists() {
+            check_writable(&file_path, project_path.as_deref())?;
+            move_to_trash(&file_path, "agent").map_err(|e| format!("Failed to delete agent: {}", e))?;
+        }
 
-    Ok(())
+        Ok(())
+    })
+    .await?
 }
 
 /// Get raw agent content (for editing)
 #[tauri::command]
-pub fn get_agent_content(agent_id: String, is_global: bool, project_path: Option<String>) -> Result<String, String> {
-    let file_path = if is_global {
-        get_global_agents_dir().join(format!("{}.md", agent_id))
-    } else {
-        let project = project_path.ok_or("Project path required")?;
-        get_project_agents_dir(&project).join(format!("{}.md", agent_id))
-    };
-
-    fs::read_to_string(&file_path).map_err(|e| format!("Failed to read agent: {}", e))
+pub async fn get_agent_content(agent_id: String, is_global: bool, project_path: Option<String>) -> Result<String, String> {
+    run_blocking(move || {
+        check_name(&agent_id)?;
+        let file_path = if is_global {
+            get_global_agents_dir().join(format!("{}.md", agent_id))
+        } else {
+            let project = project_path.ok_or("Project path required")?;
+            get_project_agents_dir(&project).join(format!("{}.md", agent_id))
+        };
+
+        fs::read_to_string(&file_path).map_err(|e| format!("Failed to read agent: {}", e))
+    })
+    .await?
 }
 
-/// Save raw agent content
+/// Save raw agent content, refusing likely credentials unless `allow_secrets`
 #[tauri::command]
-pub fn save_agent_content(
+#[tracing::instrument(skip(content), err)]
+pub async fn save_agent_content(
     agent_id: String,
     content: String,
     is_global: bool,
     project_path: Option<String>,
-) -> Result<(), String> {
-    let dir = if is_global {
-        get_global_agents_dir()
-    } else {
-        let project = project_path.ok_or("Project path required")?;
-        get_project_agents_dir(&project)
-    };
-
-    fs::create_dir_all(&dir).map_err(|e| format!("Failed to create directory: {}", e))?;
-
-    let file_path = dir.join(format!("{}.md", agent_id));
-    fs::write(&file_path, content).map_err(|e| format!("Failed to write agent: {}", e))
+    allow_secrets: Option<bool>,
+) -> Result<(), ArcadeError> {
+    run_blocking(move || {
+        check_name(&agent_id)?;
+        check_secrets(&content, allow_secrets)?;
+        let dir = if is_global {
+            get_global_agents_dir()
+        } else {
+            let project = project_path.as_deref().ok_or("Project path required")?;
+            get_project_agents_dir(project)
+        };
+
+        fs::create_dir_all(&dir).map_err(|e| format!("Failed to create directory: {}", e))?;
+
+        let file_path = dir.join(format!("{}.md", agent_id));
+        check_writable(&file_path, project_path.as_deref())?;
+        crate::watcher::write_expected(&file_path, content).map_err(|e| ArcadeError::io(&file_path, e))
+    })
+    .await?
 }
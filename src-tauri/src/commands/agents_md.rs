@@ -0,0 +1,229 @@
+//! AGENTS.md export: a project's CLAUDE.md memory rendered for tools that read AGENTS.md
+//! Projects can opt into staying in sync, so the file is regenerated whenever their CLAUDE.md changes.
+
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::scanner::plugin::claude_config_dir;
+use crate::blocking::run_blocking;
+
+/// First line of every generated AGENTS.md; a file without it was written by hand
+pub(crate) const GENERATED_MARKER: &str = "<!-- Generated by ClaudeArcade from CLAUDE.md";
+
+/// Claude Code follows @imports this deep
+const MAX_IMPORT_DEPTH: usize = 5;
+
+/// What goes into AGENTS.md
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct AgentsMdOptions {
+    /// Also include ~/.claude/CLAUDE.md, which is personal rather than the team's
+    pub include_user_memory: bool,
+    /// Also include CLAUDE.local.md, which is usually git-ignored
+    pub include_local: bool,
+    /// Regenerate AGENTS.md whenever the project's CLAUDE.md changes
+    pub keep_in_sync: bool,
+    /// Replace an AGENTS.md that wasn't generated by ClaudeArcade
+    pub overwrite: bool,
+}
+
+/// Outcome of an export
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AgentsMdExport {
+    pub path: String,
+    /// CLAUDE.md files the content came from, in order
+    pub sources: Vec<String>,
+    /// Files pulled in through @imports
+    pub imports: Vec<String>,
+    pub keep_in_sync: bool,
+}
+
+/// Projects kept in sync, in ~/.claude/arcade_agents_md.json
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AgentsMdSyncStore {
+    projects: HashMap<String, AgentsMdOptions>,
+}
+
+fn sync_store_path() -> Option<PathBuf> {
+    claude_config_dir().map(|d| d.join("arcade_agents_md.json"))
+}
+
+fn load_sync_store() -> AgentsMdSyncStore {
+    sync_store_path()
+        .and_then(|path| crate::store::AGENTS_MD.load(&path))
+        .unwrap_or_default()
+}
+
+fn save_sync_store(store: &AgentsMdSyncStore) -> Result<(), String> {
+    let path = sync_store_path().ok_or("Could not find home directory")?;
+    let content = crate::store::AGENTS_MD.to_json(store)?;
+    crate::paths::atomic_write(&path, content).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+/// CLAUDE.md files that make up a project's memory, in the order Claude Code loads them
+fn memory_sources(project_root: &Path, options: &AgentsMdOptions) -> Vec<PathBuf> {
+    let mut sources = Vec::new();
+    if options.include_user_memory {
+        sources.extend(claude_config_dir().map(|d| d.join("CLAUDE.md")));
+    }
+    sources.push(project_root.join("CLAUDE.md"));
+    sources.push(project_root.join(".claude").join("CLAUDE.md"));
+    if options.include_local {
+        sources.push(project_root.join("CLAUDE.local.md"));
+    }
+    sources.into_iter().filter(|p| p.is_file()).collect()
+}
+
+/// Path an `@import` refers to, relative to the importing file (or home for `@~/`)
+fn import_target(reference: &str, base_dir: &Path) -> Option<PathBuf> {
+    let path = match reference.strip_prefix("~/") {
+        Some(rest) => crate::paths::home_dir()?.join(rest),
+        None => base_dir.join(reference),
+    };
+    path.is_file().then_some(path)
+}
+
+/// File content with lines that are only an `@path` import (optionally a list item) replaced
+/// by the imported file. Imports inside code fences and inline mentions are left alone.
+fn expand_imports(path: &Path, depth: usize, seen: &mut HashSet<PathBuf>, imports: &mut Vec<String>) -> String {
+    let Ok(content) = fs::read_to_string(path) else {
+        return String::new();
+    };
+    let base_dir = path.parent().unwrap_or(Path::new("."));
+    let mut out = Vec::new();
+    let mut in_fence = false;
+
+    for line in content.lines() {
+        let trimmed = line.trim();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_fence = !in_fence;
+        }
+        let reference = trimmed.trim_start_matches(['-', '*']).trim().strip_prefix('@');
+        let target = reference
+            .filter(|r| !in_fence && depth < MAX_IMPORT_DEPTH && !r.contains(char::is_whitespace))
+            .and_then(|r| import_target(r, base_dir));
+        match target {
+            Some(target) if seen.insert(target.canonicalize().unwrap_or(target.clone())) => {
+                imports.push(target.to_string_lossy().to_string());
+                out.push(expand_imports(&target, depth + 1, seen, imports).trim_end().to_string());
+            }
+            _ => out.push(line.to_string()),
+        }
+    }
+    out.join("\n")
+}
+
+/// AGENTS.md content for a project, with the CLAUDE.md files and imports it was built from
+fn render_agents_md(project_root: &Path, options: &AgentsMdOptions) -> (String, Vec<String>, Vec<String>) {
+    let sources = memory_sources(project_root, options);
+    let mut seen: HashSet<PathBuf> = sources.iter().map(|p| p.canonicalize().unwrap_or(p.clone())).collect();
+    let mut imports = Vec::new();
+
+    let mut out = format!("{}. Edit CLAUDE.md instead; changes here are overwritten. -->\n", GENERATED_MARKER);
+    for source in &sources {
+        let body = expand_imports(source, 0, &mut seen, &mut imports);
+        if body.trim().is_empty() {
+            continue;
+        }
+        let label = source.strip_prefix(project_root).unwrap_or(source).to_string_lossy().to_string();
+        out.push_str(&format!("\n<!-- Source: {} -->\n\n{}\n", label, body.trim()));
+    }
+
+    let sources = sources.iter().map(|p| p.to_string_lossy().to_string()).collect();
+    (out, sources, imports)
+}
+
+fn is_generated(path: &Path) -> bool {
+    fs::read_to_string(path).is_ok_and(|content| content.starts_with(GENERATED_MARKER))
+}
+
+fn write_agents_md(project_root: &Path, options: &AgentsMdOptions) -> Result<AgentsMdExport, String> {
+    let path = project_root.join("AGENTS.md");
+    if path.exists() && !options.overwrite && !is_generated(&path) {
+        return Err("AGENTS.md already exists and wasn't generated by ClaudeArcade; export with overwrite to replace it".to_string());
+    }
+
+    let (content, sources, imports) = render_agents_md(project_root, options);
+    if sources.is_empty() {
+        return Err("This project has no CLAUDE.md to export".to_string());
+    }
+    crate::watcher::write_expected(&path, &content).map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+
+    Ok(AgentsMdExport {
+        path: path.to_string_lossy().to_string(),
+        sources,
+        imports,
+        keep_in_sync: options.keep_in_sync,
+    })
+}
+
+/// Regenerate AGENTS.md for a project kept in sync. Called by the project watcher when
+/// CLAUDE.md changes; a hand-written AGENTS.md that replaced ours is left alone.
+pub(crate) fn refresh_synced_agents_md(project_path: &str) {
+    let store = load_sync_store();
+    let Some(options) = store.projects.get(project_path) else {
+        return;
+    };
+    let root = Path::new(project_path);
+    if !is_generated(&root.join("AGENTS.md")) {
+        return;
+    }
+    let options = AgentsMdOptions { overwrite: false, ..options.clone() };
+    if let Err(e) = write_agents_md(root, &options) {
+        tracing::error!("Failed to refresh AGENTS.md for {}: {}", project_path, e);
+    }
+}
+
+/// Render a project's CLAUDE.md memory (with @imports inlined) into AGENTS.md. With
+/// `keep_in_sync` the file is regenerated whenever CLAUDE.md changes while the project is watched;
+/// exporting without it stops syncing.
+#[tauri::command]
+pub async fn export_agents_md(project_path: String, options: Option<AgentsMdOptions>) -> Result<AgentsMdExport, String> {
+    run_blocking(move || {
+        let options = options.unwrap_or_default();
+        let root = PathBuf::from(&project_path);
+        if !root.is_dir() {
+            return Err(format!("Project not found: {}", project_path));
+        }
+
+        let export = write_agents_md(&root, &options)?;
+
+        let mut store = load_sync_store();
+        let was_synced = store.projects.contains_key(&project_path);
+        if options.keep_in_sync {
+            store.projects.insert(project_path, AgentsMdOptions { overwrite: false, ..options });
+            save_sync_store(&store)?;
+        } else if was_synced {
+            store.projects.remove(&project_path);
+            save_sync_store(&store)?;
+        }
+
+        Ok(export)
+    })
+    .await?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TempDir;
+
+    #[test]
+    fn inlines_imports_outside_code_fences() {
+        let dir = TempDir::new("agents-md");
+        fs::create_dir_all(dir.join("docs")).unwrap();
+        fs::write(dir.join("docs/style.md"), "Use tabs.").unwrap();
+        fs::write(dir.join("CLAUDE.md"), "# Rules\n- @docs/style.md\n```\n@docs/style.md\n```\nSee @docs/style.md").unwrap();
+
+        let (content, sources, imports) = render_agents_md(&dir, &AgentsMdOptions::default());
+
+        assert!(content.starts_with(GENERATED_MARKER));
+        assert_eq!(sources.len(), 1);
+        assert_eq!(imports.len(), 1);
+        assert!(content.contains("# Rules\nUse tabs.\n```\n@docs/style.md\n```\nSee @docs/style.md"));
+    }
+}
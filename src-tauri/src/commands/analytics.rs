@@ -1,7 +1,11 @@
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::fs;
 use std::path::PathBuf;
-use chrono::{DateTime, Datelike, Local, NaiveDate, Weekday};
+use std::sync::{Mutex, MutexGuard};
+use chrono::{DateTime, Datelike, Local, NaiveDate};
+use crate::blocking::run_blocking;
+use super::arcade_hooks::{apply_session_events, hooks_installed, take_session_events, HookSession};
 
 /// Usage data for a single day
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -12,6 +16,29 @@ pub struct DailyUsage {
     pub estimated_tokens: u64,       // Rough token estimate
     pub active_minutes: u32,         // Minutes with activity
     pub tools_used: u32,             // Number of tool calls
+    // Exact figures from Claude Code's OpenTelemetry metrics, when they are received
+    #[serde(default)]
+    pub input_tokens: u64,
+    #[serde(default)]
+    pub output_tokens: u64,
+    #[serde(default)]
+    pub cache_read_tokens: u64,
+    #[serde(default)]
+    pub cache_creation_tokens: u64,
+    #[serde(default)]
+    pub cost_usd: f64,
+    #[serde(default)]
+    pub edits_accepted: u32,
+    #[serde(default)]
+    pub edits_rejected: u32,
+}
+
+impl DailyUsage {
+    /// Tokens reported by Claude Code's metrics, or the estimate on days without them
+    pub fn total_tokens(&self) -> u64 {
+        let exact = self.input_tokens + self.output_tokens + self.cache_read_tokens + self.cache_creation_tokens;
+        if exact > 0 { exact } else { self.estimated_tokens }
+    }
 }
 
 /// Weekly summary
@@ -53,237 +80,319 @@ pub struct SessionData {
 pub struct AnalyticsData {
     pub daily_usage: Vec<DailyUsage>,
     pub current_session: Option<SessionData>,
+    /// Sessions the arcade hooks saw start, by Claude Code session id
+    #[serde(default)]
+    pub hook_sessions: HashMap<String, HookSession>,
 }
 
 fn get_analytics_path() -> PathBuf {
-    dirs::home_dir()
+    crate::paths::claude_dir()
         .unwrap_or_default()
-        .join(".claude")
         .join("arcade_analytics.json")
 }
 
-fn load_analytics() -> AnalyticsData {
+/// Held across every load-change-save of analytics: hook events, OTLP exports and the app's own
+/// commands arrive at the same time and would otherwise overwrite each other's counts
+static ANALYTICS_LOCK: Mutex<()> = Mutex::new(());
+
+pub(crate) fn lock_analytics() -> MutexGuard<'static, ()> {
+    ANALYTICS_LOCK.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+pub(crate) fn load_analytics() -> AnalyticsData {
     let path = get_analytics_path();
-    if path.exists() {
-        if let Ok(content) = fs::read_to_string(&path) {
-            if let Ok(data) = serde_json::from_str(&content) {
-                return data;
-            }
-        }
-    }
-    AnalyticsData::default()
+    crate::store::ANALYTICS.load(&path).unwrap_or_default()
 }
 
-fn save_analytics(data: &AnalyticsData) -> Result<(), String> {
+pub(crate) fn save_analytics(data: &AnalyticsData) -> Result<(), String> {
     let path = get_analytics_path();
     if let Some(parent) = path.parent() {
         fs::create_dir_all(parent).map_err(|e| e.to_string())?;
     }
-    let content = serde_json::to_string_pretty(data).map_err(|e| e.to_string())?;
-    fs::write(&path, content).map_err(|e| e.to_string())?;
+    let content = crate::store::ANALYTICS.to_json(data)?;
+    crate::paths::atomic_write(&path, content).map_err(|e| e.to_string())?;
+    super::statusline::refresh_statusline_analytics(data);
     Ok(())
 }
 
+/// Fold in the session hook events recorded since the last read; true when anything changed
+fn fold_session_events(data: &mut AnalyticsData) -> bool {
+    let events = take_session_events().unwrap_or_default();
+    apply_session_events(data, &events, Local::now().timestamp())
+}
+
+/// Analytics with the session hook events recorded since the last read folded in
+fn load_synced_analytics() -> AnalyticsData {
+    let _lock = lock_analytics();
+    let mut data = load_analytics();
+    if fold_session_events(&mut data) {
+        if let Err(e) = save_analytics(&data) {
+            tracing::warn!("Failed to save session hook events: {}", e);
+        }
+    }
+    data
+}
+
+/// Days of usage kept, enough for a year-over-year comparison
+const RETENTION_DAYS: i64 = 400;
+
+/// Drop days past the retention window and put the rest in date order; returns how many went
+fn compact_daily_usage(data: &mut AnalyticsData, today: NaiveDate) -> usize {
+    let cutoff = (today - chrono::Duration::days(RETENTION_DAYS)).format("%Y-%m-%d").to_string();
+    let before = data.daily_usage.len();
+    // Dates are YYYY-MM-DD, so they compare as strings
+    data.daily_usage.retain(|d| d.date >= cutoff);
+    data.daily_usage.sort_by(|a, b| a.date.cmp(&b.date));
+    before - data.daily_usage.len()
+}
+
+/// Fold in pending hook events, close sessions gone quiet and drop old days, for the scheduled
+/// compaction job. Returns a summary of what it did.
+pub(crate) fn compact_analytics() -> Result<String, String> {
+    let _lock = lock_analytics();
+    let mut data = load_analytics();
+    fold_session_events(&mut data);
+    let removed = compact_daily_usage(&mut data, Local::now().date_naive());
+    save_analytics(&data)?;
+    Ok(format!("Kept {} days, dropped {}", data.daily_usage.len(), removed))
+}
+
 fn today_string() -> String {
     Local::now().format("%Y-%m-%d").to_string()
 }
 
 fn get_or_create_today(data: &mut AnalyticsData) -> &mut DailyUsage {
-    let today = today_string();
+    get_or_create_day(data, Local::now().timestamp())
+}
+
+/// The entry for the local day containing `timestamp` (Unix seconds)
+pub(crate) fn get_or_create_day(data: &mut AnalyticsData, timestamp: i64) -> &mut DailyUsage {
+    let day = DateTime::from_timestamp(timestamp, 0)
+        .map(|t| t.with_timezone(&Local).format("%Y-%m-%d").to_string())
+        .unwrap_or_else(today_string);
 
-    // Find or create today's entry
-    if !data.daily_usage.iter().any(|d| d.date == today) {
+    // Find or create the day's entry
+    if !data.daily_usage.iter().any(|d| d.date == day) {
         data.daily_usage.push(DailyUsage {
-            date: today.clone(),
+            date: day.clone(),
             ..Default::default()
         });
     }
 
-    data.daily_usage.iter_mut().find(|d| d.date == today).unwrap()
+    data.daily_usage.iter_mut().find(|d| d.date == day).unwrap()
 }
 
 /// Start a new session
 #[tauri::command]
-pub fn start_session() -> Result<String, String> {
-    let mut data = load_analytics();
-
-    let session_id = uuid::Uuid::new_v4().to_string();
-    let now = chrono::Local::now().timestamp();
-
-    data.current_session = Some(SessionData {
-        session_id: session_id.clone(),
-        start_time: now,
-        messages: 0,
-        tokens: 0,
-        tools: 0,
-    });
+pub async fn start_session() -> Result<String, String> {
+    run_blocking(move || {
+        let _lock = lock_analytics();
+        let mut data = load_analytics();
+
+        let session_id = uuid::Uuid::new_v4().to_string();
+        let now = chrono::Local::now().timestamp();
+
+        data.current_session = Some(SessionData {
+            session_id: session_id.clone(),
+            start_time: now,
+            messages: 0,
+            tokens: 0,
+            tools: 0,
+        });
 
-    // Increment today's session count
-    let today = get_or_create_today(&mut data);
-    today.sessions += 1;
+        // Increment today's session count, unless the session hooks will count it
+        if !hooks_installed() {
+            get_or_create_today(&mut data).sessions += 1;
+        }
 
-    save_analytics(&data)?;
-    Ok(session_id)
+        save_analytics(&data)?;
+        Ok(session_id)
+    })
+    .await?
 }
 
 /// Record a message in the current session
 #[tauri::command]
-pub fn record_message(estimated_tokens: u64, tool_calls: u32) -> Result<(), String> {
-    let mut data = load_analytics();
-
-    if let Some(session) = data.current_session.as_mut() {
-        session.messages += 1;
-        session.tokens += estimated_tokens;
-        session.tools += tool_calls;
-    }
+pub async fn record_message(estimated_tokens: u64, tool_calls: u32) -> Result<(), String> {
+    run_blocking(move || {
+        let _lock = lock_analytics();
+        let mut data = load_analytics();
+
+        if let Some(session) = data.current_session.as_mut() {
+            session.messages += 1;
+            session.tokens += estimated_tokens;
+            session.tools += tool_calls;
+        }
 
-    let today = get_or_create_today(&mut data);
-    today.messages += 1;
-    today.estimated_tokens += estimated_tokens;
-    today.tools_used += tool_calls;
+        let today = get_or_create_today(&mut data);
+        today.messages += 1;
+        today.estimated_tokens += estimated_tokens;
+        today.tools_used += tool_calls;
 
-    save_analytics(&data)?;
-    Ok(())
+        save_analytics(&data)?;
+        Ok(())
+    })
+    .await?
 }
 
 /// Record active time
 #[tauri::command]
-pub fn record_activity(minutes: u32) -> Result<(), String> {
-    let mut data = load_analytics();
-
-    let today = get_or_create_today(&mut data);
-    today.active_minutes += minutes;
-
-    save_analytics(&data)?;
-    Ok(())
+pub async fn record_activity(minutes: u32) -> Result<(), String> {
+    run_blocking(move || {
+        let _lock = lock_analytics();
+        let mut data = load_analytics();
+
+        let today = get_or_create_today(&mut data);
+        today.active_minutes += minutes;
+
+        save_analytics(&data)?;
+        Ok(())
+    })
+    .await?
 }
 
 /// End the current session
 #[tauri::command]
-pub fn end_session() -> Result<(), String> {
-    let mut data = load_analytics();
-    data.current_session = None;
-    save_analytics(&data)?;
-    Ok(())
+pub async fn end_session() -> Result<(), String> {
+    run_blocking(move || {
+        let _lock = lock_analytics();
+        let mut data = load_analytics();
+        data.current_session = None;
+        save_analytics(&data)?;
+        Ok(())
+    })
+    .await?
 }
 
 /// Get usage data for the past N days
 #[tauri::command]
-pub fn get_daily_usage(days: u32) -> Vec<DailyUsage> {
-    let data = load_analytics();
-    let today = Local::now();
-
-    let mut result: Vec<DailyUsage> = Vec::new();
-
-    for i in 0..days {
-        let date = today - chrono::Duration::days(i as i64);
-        let date_str = date.format("%Y-%m-%d").to_string();
-
-        if let Some(usage) = data.daily_usage.iter().find(|d| d.date == date_str) {
-            result.push(usage.clone());
-        } else {
-            result.push(DailyUsage {
-                date: date_str,
-                ..Default::default()
-            });
+pub async fn get_daily_usage(days: u32) -> Result<Vec<DailyUsage>, String> {
+    run_blocking(move || {
+        let data = load_synced_analytics();
+        let today = Local::now();
+
+        let mut result: Vec<DailyUsage> = Vec::new();
+
+        for i in 0..days {
+            let date = today - chrono::Duration::days(i as i64);
+            let date_str = date.format("%Y-%m-%d").to_string();
+
+            if let Some(usage) = data.daily_usage.iter().find(|d| d.date == date_str) {
+                result.push(usage.clone());
+            } else {
+                result.push(DailyUsage {
+                    date: date_str,
+                    ..Default::default()
+                });
+            }
         }
-    }
 
-    result
+        result
+    })
+    .await
 }
 
 /// Get weekly summary
 #[tauri::command]
-pub fn get_weekly_summary() -> WeeklySummary {
-    let data = load_analytics();
-    let today = Local::now();
-
-    // Find Monday of current week
-    let days_since_monday = today.weekday().num_days_from_monday() as i64;
-    let monday = today - chrono::Duration::days(days_since_monday);
-    let sunday = monday + chrono::Duration::days(6);
-
-    let week_start = monday.format("%Y-%m-%d").to_string();
-    let week_end = sunday.format("%Y-%m-%d").to_string();
-
-    let mut summary = WeeklySummary {
-        week_start,
-        week_end,
-        total_sessions: 0,
-        total_messages: 0,
-        total_tokens: 0,
-        total_minutes: 0,
-        total_tools: 0,
-        daily_breakdown: Vec::new(),
-    };
-
-    for i in 0..7 {
-        let date = monday + chrono::Duration::days(i);
-        let date_str = date.format("%Y-%m-%d").to_string();
-
-        let usage = if let Some(u) = data.daily_usage.iter().find(|d| d.date == date_str) {
-            u.clone()
-        } else {
-            DailyUsage {
-                date: date_str,
-                ..Default::default()
-            }
+pub async fn get_weekly_summary() -> Result<WeeklySummary, String> {
+    run_blocking(move || {
+        let data = load_synced_analytics();
+        let today = Local::now();
+
+        // Find Monday of current week
+        let days_since_monday = today.weekday().num_days_from_monday() as i64;
+        let monday = today - chrono::Duration::days(days_since_monday);
+        let sunday = monday + chrono::Duration::days(6);
+
+        let week_start = monday.format("%Y-%m-%d").to_string();
+        let week_end = sunday.format("%Y-%m-%d").to_string();
+
+        let mut summary = WeeklySummary {
+            week_start,
+            week_end,
+            total_sessions: 0,
+            total_messages: 0,
+            total_tokens: 0,
+            total_minutes: 0,
+            total_tools: 0,
+            daily_breakdown: Vec::new(),
         };
 
-        summary.total_sessions += usage.sessions;
-        summary.total_messages += usage.messages;
-        summary.total_tokens += usage.estimated_tokens;
-        summary.total_minutes += usage.active_minutes;
-        summary.total_tools += usage.tools_used;
-        summary.daily_breakdown.push(usage);
-    }
+        for i in 0..7 {
+            let date = monday + chrono::Duration::days(i);
+            let date_str = date.format("%Y-%m-%d").to_string();
 
-    summary
-}
+            let usage = if let Some(u) = data.daily_usage.iter().find(|d| d.date == date_str) {
+                u.clone()
+            } else {
+                DailyUsage {
+                    date: date_str,
+                    ..Default::default()
+                }
+            };
 
-/// Get monthly summary
-#[tauri::command]
-pub fn get_monthly_summary() -> MonthlySummary {
-    let data = load_analytics();
-    let today = Local::now();
-    let month_str = today.format("%Y-%m").to_string();
-
-    // Get all days in current month
-    let first_day = NaiveDate::from_ymd_opt(today.year(), today.month(), 1).unwrap();
-    let last_day = if today.month() == 12 {
-        NaiveDate::from_ymd_opt(today.year() + 1, 1, 1).unwrap()
-    } else {
-        NaiveDate::from_ymd_opt(today.year(), today.month() + 1, 1).unwrap()
-    } - chrono::Duration::days(1);
-
-    let mut summary = MonthlySummary {
-        month: month_str,
-        total_sessions: 0,
-        total_messages: 0,
-        total_tokens: 0,
-        total_minutes: 0,
-        weekly_breakdown: Vec::new(),
-    };
-
-    // Aggregate all days in the month
-    let mut current = first_day;
-    while current <= last_day {
-        let date_str = current.format("%Y-%m-%d").to_string();
-        if let Some(usage) = data.daily_usage.iter().find(|d| d.date == date_str) {
             summary.total_sessions += usage.sessions;
             summary.total_messages += usage.messages;
-            summary.total_tokens += usage.estimated_tokens;
+            summary.total_tokens += usage.total_tokens();
             summary.total_minutes += usage.active_minutes;
+            summary.total_tools += usage.tools_used;
+            summary.daily_breakdown.push(usage);
+        }
+
+        summary
+    })
+    .await
+}
+
+/// Get monthly summary
+#[tauri::command]
+pub async fn get_monthly_summary() -> Result<MonthlySummary, String> {
+    run_blocking(move || {
+        let data = load_synced_analytics();
+        let today = Local::now();
+        let month_str = today.format("%Y-%m").to_string();
+
+        // Get all days in current month
+        let first_day = NaiveDate::from_ymd_opt(today.year(), today.month(), 1).unwrap();
+        let last_day = if today.month() == 12 {
+            NaiveDate::from_ymd_opt(today.year() + 1, 1, 1).unwrap()
+        } else {
+            NaiveDate::from_ymd_opt(today.year(), today.month() + 1, 1).unwrap()
+        } - chrono::Duration::days(1);
+
+        let mut summary = MonthlySummary {
+            month: month_str,
+            total_sessions: 0,
+            total_messages: 0,
+            total_tokens: 0,
+            total_minutes: 0,
+            weekly_breakdown: Vec::new(),
+        };
+
+        // Aggregate all days in the month
+        let mut current = first_day;
+        while current <= last_day {
+            let date_str = current.format("%Y-%m-%d").to_string();
+            if let Some(usage) = data.daily_usage.iter().find(|d| d.date == date_str) {
+                summary.total_sessions += usage.sessions;
+                summary.total_messages += usage.messages;
+                summary.total_tokens += usage.total_tokens();
+                summary.total_minutes += usage.active_minutes;
+            }
+            current += chrono::Duration::days(1);
         }
-        current += chrono::Duration::days(1);
-    }
 
-    summary
+        summary
+    })
+    .await
 }
 
 /// Get current session data
 #[tauri::command]
-pub fn get_current_session() -> Option<SessionData> {
-    let data = load_analytics();
-    data.current_session
+pub async fn get_current_session() -> Result<Option<SessionData>, String> {
+    run_blocking(move || {
+        let data = load_analytics();
+        data.current_session
+    })
+    .await
 }
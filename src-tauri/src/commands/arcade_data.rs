@@ -0,0 +1,239 @@
+//! Moving ClaudeArcade's own data (analytics, usage history, recent projects, settings, groups,
+//! spellbooks, jobs, onboarding) to another machine as one versioned JSON bundle
+//! Caches, install checksums, the event endpoint's token and machine-bound setup (profiles, sync
+//! clone) stay behind. Loadouts and achievements live in the frontend's storage, so it hands them
+//! over on export and gets them back on import.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+
+use crate::blocking::run_blocking;
+use crate::error::ArcadeError;
+use crate::paths::ConfigPaths;
+use crate::store::{self, DataFile};
+use crate::trash::move_to_trash;
+
+/// Identifies an arcade data bundle
+pub const ARCADE_DATA_FORMAT: &str = "claudearcade-data";
+
+/// Bumped when the bundle layout changes incompatibly
+pub const ARCADE_DATA_VERSION: u32 = 1;
+
+/// An arcade data file a bundle carries
+struct Portable {
+    file: &'static DataFile,
+    /// Kept in the default Claude folder whichever profile is active
+    default_dir: bool,
+}
+
+/// What a bundle carries. Caches, install checksums and machine-bound files stay out.
+const DATA_FILES: [Portable; 10] = [
+    Portable { file: &store::ANALYTICS, default_dir: false },
+    Portable { file: &store::USAGE, default_dir: false },
+    Portable { file: &store::PROJECTS, default_dir: false },
+    Portable { file: &store::SETTINGS, default_dir: false },
+    Portable { file: &store::MCP_GROUPS, default_dir: false },
+    Portable { file: &store::SPELLBOOKS, default_dir: false },
+    Portable { file: &store::STATUSLINE, default_dir: false },
+    Portable { file: &store::AGENTS_MD, default_dir: false },
+    Portable { file: &store::JOBS, default_dir: false },
+    Portable { file: &store::ONBOARDING, default_dir: true },
+];
+
+fn portable(name: &str) -> Option<&'static Portable> {
+    DATA_FILES.iter().find(|p| p.file.name == name)
+}
+
+/// One file in a bundle, its data unwrapped from the file's envelope
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BundledFile {
+    pub schema_version: u32,
+    pub content: Value,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArcadeDataBundle {
+    pub format: String,
+    pub version: u32,
+    /// RFC3339
+    pub exported_at: String,
+    pub app_version: String,
+    /// By file name
+    pub files: BTreeMap<String, BundledFile>,
+    /// The frontend's own stores (loadouts, achievements, ...), passed through untouched
+    #[serde(default)]
+    pub frontend_state: Option<Value>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArcadeDataExportResult {
+    pub path: String,
+    pub files: Vec<String>,
+    pub warnings: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArcadeDataImportResult {
+    pub imported: Vec<String>,
+    pub skipped: Vec<String>,
+    /// For the frontend to restore into its stores
+    pub frontend_state: Option<Value>,
+    pub warnings: Vec<String>,
+}
+
+fn data_path(portable: &Portable) -> Option<PathBuf> {
+    let dir = if portable.default_dir { ConfigPaths::resolve_default().map(|p| p.claude_dir) } else { crate::paths::claude_dir() };
+    dir.map(|d| d.join(portable.file.name))
+}
+
+fn build_bundle(frontend_state: Option<Value>, warnings: &mut Vec<String>) -> ArcadeDataBundle {
+    let mut files = BTreeMap::new();
+    for portable in &DATA_FILES {
+        let file = portable.file;
+        let Some(path) = data_path(portable).filter(|p| p.exists()) else {
+            continue;
+        };
+        match fs::read_to_string(&path).map_err(|e| e.to_string()).and_then(|c| file.parse::<Value>(&c)) {
+            Ok((content, _)) => {
+                files.insert(file.name.to_string(), BundledFile { schema_version: file.version(), content });
+            }
+            Err(e) => warnings.push(format!("Left out {}: {}", file.name, e)),
+        }
+    }
+    ArcadeDataBundle {
+        format: ARCADE_DATA_FORMAT.to_string(),
+        version: ARCADE_DATA_VERSION,
+        exported_at: chrono::Utc::now().to_rfc3339(),
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        files,
+        frontend_state,
+    }
+}
+
+/// Check a bundle is one this version understands; files it can't take are reported, not fatal
+fn check_bundle(bundle: &ArcadeDataBundle, warnings: &mut Vec<String>) -> Result<(), ArcadeError> {
+    if bundle.format != ARCADE_DATA_FORMAT {
+        return Err(ArcadeError::invalid_input(format!("Not an arcade data bundle (format '{}')", bundle.format)));
+    }
+    if bundle.version > ARCADE_DATA_VERSION {
+        return Err(ArcadeError::invalid_input(format!(
+            "Arcade data bundle version {} is newer than this app supports ({}); update ClaudeArcade",
+            bundle.version, ARCADE_DATA_VERSION
+        )));
+    }
+    for (name, file) in &bundle.files {
+        match portable(name) {
+            None => warnings.push(format!("{} isn't arcade data this version knows; it will be skipped", name)),
+            Some(known) if file.schema_version > known.file.version() => warnings.push(format!(
+                "{} is schema version {}, newer than this app supports ({}); it will be skipped",
+                name,
+                file.schema_version,
+                known.file.version()
+            )),
+            Some(_) => {}
+        }
+    }
+    Ok(())
+}
+
+/// Bundle the arcade's data into one JSON file at `dest` (a file, or a folder to write
+/// `claudearcade-data-<date>.json` into). `frontend_state` is stored as given.
+#[tauri::command]
+#[tracing::instrument(skip(frontend_state), err)]
+pub async fn export_arcade_data(dest: String, frontend_state: Option<Value>) -> Result<ArcadeDataExportResult, ArcadeError> {
+    run_blocking(move || {
+        let mut warnings = Vec::new();
+        let bundle = build_bundle(frontend_state, &mut warnings);
+
+        let mut path = PathBuf::from(dest);
+        if path.is_dir() {
+            path = path.join(format!("claudearcade-data-{}.json", chrono::Local::now().format("%Y-%m-%d")));
+        }
+        let content = serde_json::to_string_pretty(&bundle).map_err(|e| e.to_string())?;
+        crate::paths::atomic_write(&path, content).map_err(|e| ArcadeError::io(&path, e))?;
+
+        Ok(ArcadeDataExportResult { path: path.to_string_lossy().to_string(), files: bundle.files.into_keys().collect(), warnings })
+    })
+    .await?
+}
+
+/// Restore arcade data from a bundle, replacing the current files; the ones replaced go to the
+/// trash. Files from older versions are migrated. `files` limits it to some file names. The
+/// frontend's part is returned for it to restore.
+#[tauri::command]
+#[tracing::instrument(err)]
+pub async fn import_arcade_data(path: String, files: Option<Vec<String>>) -> Result<ArcadeDataImportResult, ArcadeError> {
+    run_blocking(move || {
+        let content = fs::read_to_string(&path).map_err(|e| ArcadeError::io(&path, e))?;
+        let bundle: ArcadeDataBundle =
+            serde_json::from_str(&content).map_err(|e| ArcadeError::invalid_input(format!("Not a valid arcade data bundle: {}", e)))?;
+        let mut warnings = Vec::new();
+        check_bundle(&bundle, &mut warnings)?;
+
+        let (mut imported, mut skipped) = (Vec::new(), Vec::new());
+        for (name, file) in bundle.files {
+            let known = portable(&name).filter(|p| file.schema_version <= p.file.version());
+            let wanted = files.as_ref().is_none_or(|f| f.contains(&name));
+            let (Some(known), true) = (known, wanted) else {
+                skipped.push(name);
+                continue;
+            };
+            let data = match known.file.migrate(file.schema_version, file.content) {
+                Ok(data) => data,
+                Err(e) => {
+                    warnings.push(e);
+                    skipped.push(name);
+                    continue;
+                }
+            };
+            let content = known.file.to_json(&data)?;
+            let target = data_path(known).ok_or("Could not find home directory")?;
+            if target.exists() {
+                move_to_trash(&target, "arcadeData")?;
+            }
+            if let Some(parent) = target.parent() {
+                fs::create_dir_all(parent).map_err(|e| ArcadeError::io(parent, e))?;
+            }
+            crate::watcher::write_expected(&target, content).map_err(|e| ArcadeError::io(&target, e))?;
+            imported.push(name);
+        }
+        Ok(ArcadeDataImportResult { imported, skipped, frontend_state: bundle.frontend_state, warnings })
+    })
+    .await?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn rejects_bundles_from_newer_versions() {
+        let file = |schema_version| BundledFile { schema_version, content: json!({}) };
+        let mut bundle = ArcadeDataBundle {
+            format: ARCADE_DATA_FORMAT.to_string(),
+            version: ARCADE_DATA_VERSION,
+            exported_at: String::new(),
+            app_version: String::new(),
+            files: BTreeMap::from([
+                ("arcade_analytics.json".to_string(), file(1)),
+                ("arcade_projects.json".to_string(), file(99)),
+                ("arcade_integrity.json".to_string(), file(1)),
+            ]),
+            frontend_state: None,
+        };
+        let mut warnings = Vec::new();
+        assert!(check_bundle(&bundle, &mut warnings).is_ok());
+        assert_eq!(warnings.len(), 2);
+
+        bundle.version = ARCADE_DATA_VERSION + 1;
+        assert!(check_bundle(&bundle, &mut Vec::new()).is_err());
+    }
+}
@@ -0,0 +1,234 @@
+//! Session hooks that record Claude Code sessions started outside the app
+//! The hook script only appends a line to arcade_session_events.jsonl; analytics reads and clears
+//! that file when it is next asked for usage, so the hooks never wait on the app.
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::analytics::{get_or_create_day, AnalyticsData};
+use crate::blocking::run_blocking;
+use crate::error::ArcadeError;
+use crate::scanner::settings::{read_settings_full, update_settings_field};
+
+const SCRIPT_NAME: &str = "arcade_session_hook.sh";
+
+/// Hook events and the argument the script gets for each. Stop fires after every response, so
+/// a session killed before SessionEnd still has a duration.
+const HOOK_EVENTS: [(&str, &str); 3] = [("SessionStart", "start"), ("Stop", "activity"), ("SessionEnd", "end")];
+
+/// Sessions silent for this long are closed at their last activity
+const STALE_SESSION_SECS: i64 = 12 * 60 * 60;
+
+/// A session the hooks reported as started but not yet ended
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HookSession {
+    /// Unix seconds
+    pub started_at: i64,
+    pub last_activity: i64,
+}
+
+/// Result of `install_arcade_hooks`
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArcadeHooksInstall {
+    pub script_path: String,
+    pub events: Vec<String>,
+}
+
+/// One line the hook script appends
+#[derive(Debug, Deserialize)]
+struct SessionEvent {
+    event: String,
+    at: i64,
+    #[serde(default)]
+    hook: Value,
+}
+
+fn script_path() -> Option<PathBuf> {
+    crate::paths::claude_dir().map(|d| d.join(SCRIPT_NAME))
+}
+
+fn events_path() -> Option<PathBuf> {
+    crate::paths::claude_dir().map(|d| d.join("arcade_session_events.jsonl"))
+}
+
+/// Whether the hooks are installed, so the app doesn't count its own sessions a second time
+pub(crate) fn hooks_installed() -> bool {
+    let Some(script) = script_path().filter(|p| p.exists()) else {
+        return false;
+    };
+    let marker = script.to_string_lossy().replace('\\', "/");
+    read_settings_full()
+        .ok()
+        .and_then(|s| s.hooks)
+        .is_some_and(|hooks| hooks.to_string().contains(&marker))
+}
+
+fn script(events_path: &Path) -> String {
+    format!(
+        "#!/bin/sh\n# Installed by ClaudeArcade; records session starts and ends for its analytics.\n\
+         # $1 is start, activity or end; Claude Code sends the session details as JSON on stdin.\n\
+         payload=$(tr -d '\\n')\n\
+         printf '{{\"event\":\"%s\",\"at\":%s,\"hook\":%s}}\\n' \"$1\" \"$(date +%s)\" \"${{payload:-null}}\" >> \"{}\"\n",
+        events_path.to_string_lossy().replace('\\', "/")
+    )
+}
+
+/// `hooks` with every group running `script_name` replaced by one group per (event, command).
+/// Other hooks are kept; events left with no groups are dropped.
+pub(crate) fn with_script_hooks(hooks: Option<Value>, script_name: &str, commands: &[(&str, String)]) -> Value {
+    let mut hooks = match hooks {
+        Some(Value::Object(map)) => map,
+        _ => serde_json::Map::new(),
+    };
+    for groups in hooks.values_mut() {
+        if let Value::Array(groups) = groups {
+            groups.retain(|g| !g.to_string().contains(script_name));
+        }
+    }
+    hooks.retain(|_, groups| !groups.as_array().is_some_and(|g| g.is_empty()));
+
+    for (event, command) in commands {
+        let groups = hooks.entry(*event).or_insert_with(|| Value::Array(Vec::new()));
+        if !groups.is_array() {
+            *groups = Value::Array(Vec::new());
+        }
+        if let Value::Array(groups) = groups {
+            groups.push(json!({ "hooks": [{ "type": "command", "command": command }] }));
+        }
+    }
+    Value::Object(hooks)
+}
+
+/// `hooks` with the arcade entries for each event replaced by `command <arg>`
+fn with_arcade_hooks(hooks: Option<Value>, command: &str) -> Value {
+    let commands: Vec<(&str, String)> = HOOK_EVENTS.iter().map(|(event, arg)| (*event, format!("{} {}", command, arg))).collect();
+    with_script_hooks(hooks, SCRIPT_NAME, &commands)
+}
+
+/// Fold hook events into analytics: a session per start on the day it began, and its length as
+/// active minutes once it ends (or goes quiet). Returns whether anything changed.
+pub(crate) fn apply_session_events(data: &mut AnalyticsData, lines: &str, now: i64) -> bool {
+    let mut changed = false;
+    for event in lines.lines().filter_map(|l| serde_json::from_str::<SessionEvent>(l).ok()) {
+        if let Some(id) = event.hook.get("session_id").and_then(|s| s.as_str()) {
+            apply_session_event(data, &event.event, id, event.at);
+            changed = true;
+        }
+    }
+    close_stale_sessions(data, now) || changed
+}
+
+/// One start, activity or end of the session `id` at `at` (Unix seconds)
+pub(crate) fn apply_session_event(data: &mut AnalyticsData, event: &str, id: &str, at: i64) {
+    match event {
+        // SessionStart also fires on resume and after /clear; only the first counts
+        "start" if !data.hook_sessions.contains_key(id) => {
+            get_or_create_day(data, at).sessions += 1;
+            data.hook_sessions.insert(id.to_string(), HookSession { started_at: at, last_activity: at });
+        }
+        "activity" | "end" => {
+            let session = data
+                .hook_sessions
+                .entry(id.to_string())
+                .or_insert(HookSession { started_at: at, last_activity: at });
+            session.last_activity = session.last_activity.max(at);
+            if event == "end" {
+                close_session(data, id);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn close_stale_sessions(data: &mut AnalyticsData, now: i64) -> bool {
+    let stale: Vec<String> = data
+        .hook_sessions
+        .iter()
+        .filter(|(_, s)| now - s.last_activity > STALE_SESSION_SECS)
+        .map(|(id, _)| id.clone())
+        .collect();
+    for id in &stale {
+        close_session(data, id);
+    }
+    !stale.is_empty()
+}
+
+fn close_session(data: &mut AnalyticsData, id: &str) {
+    if let Some(session) = data.hook_sessions.remove(id) {
+        let minutes = ((session.last_activity - session.started_at).max(0) / 60) as u32;
+        get_or_create_day(data, session.started_at).active_minutes += minutes;
+    }
+}
+
+/// Take the events the hooks wrote since the last call. The file is renamed before reading, so
+/// lines appended meanwhile land in a new file for next time.
+pub(crate) fn take_session_events() -> Option<String> {
+    let path = events_path()?;
+    let taken = path.with_extension("jsonl.reading");
+    fs::rename(&path, &taken).ok()?;
+    let content = fs::read_to_string(&taken).ok();
+    let _ = fs::remove_file(&taken);
+    content
+}
+
+/// Write the session hook script and add SessionStart, Stop and SessionEnd hooks running it to
+/// settings.json, so sessions started from any terminal show up in analytics. Other hooks on
+/// those events are kept; running it again only refreshes the arcade ones.
+#[tauri::command]
+#[tracing::instrument(err)]
+pub async fn install_arcade_hooks() -> Result<ArcadeHooksInstall, ArcadeError> {
+    run_blocking(move || {
+        let (Some(script_path), Some(events_path)) = (script_path(), events_path()) else {
+            return Err(ArcadeError::Other { message: "Could not find home directory".to_string() });
+        };
+        let content = script(&events_path);
+        crate::watcher::write_expected(&script_path, content).map_err(|e| ArcadeError::io(&script_path, e))?;
+
+        let command = format!("sh \"{}\"", script_path.to_string_lossy().replace('\\', "/"));
+        let hooks = with_arcade_hooks(read_settings_full()?.hooks, &command);
+        update_settings_field("hooks", hooks)?;
+
+        Ok(ArcadeHooksInstall {
+            script_path: script_path.to_string_lossy().to_string(),
+            events: HOOK_EVENTS.iter().map(|(event, _)| event.to_string()).collect(),
+        })
+    })
+    .await?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn records_hook_sessions() {
+        let hooks = with_arcade_hooks(
+            Some(json!({ "SessionStart": [{ "hooks": [{ "type": "command", "command": "echo hi" }] }] })),
+            "sh \"/home/me/.claude/arcade_session_hook.sh\"",
+        );
+        let again = with_arcade_hooks(Some(hooks.clone()), "sh \"/home/me/.claude/arcade_session_hook.sh\"");
+        assert_eq!(hooks, again);
+        assert_eq!(hooks["SessionStart"].as_array().unwrap().len(), 2);
+
+        let start = 1_767_225_600; // 2026-01-01T00:00:00Z
+        let lines = [
+            format!(r#"{{"event":"start","at":{},"hook":{{"session_id":"a"}}}}"#, start),
+            format!(r#"{{"event":"start","at":{},"hook":{{"session_id":"a","source":"resume"}}}}"#, start + 60),
+            format!(r#"{{"event":"activity","at":{},"hook":{{"session_id":"a"}}}}"#, start + 600),
+            format!(r#"{{"event":"end","at":{},"hook":{{"session_id":"a"}}}}"#, start + 1500),
+            format!(r#"{{"event":"start","at":{},"hook":{{"session_id":"b"}}}}"#, start + 1600),
+        ]
+        .join("\n");
+        let mut data = AnalyticsData::default();
+        assert!(apply_session_events(&mut data, &lines, start + 2000));
+
+        let sessions: u32 = data.daily_usage.iter().map(|d| d.sessions).sum();
+        let minutes: u32 = data.daily_usage.iter().map(|d| d.active_minutes).sum();
+        assert_eq!((sessions, minutes), (2, 25));
+        assert!(data.hook_sessions.contains_key("b"));
+    }
+}
@@ -0,0 +1,8 @@
+use crate::audit::{read_audit_log, AuditEntry, AuditFilter};
+use crate::blocking::run_blocking;
+
+/// Changes ClaudeArcade made to Claude config, newest first
+#[tauri::command]
+pub async fn get_audit_log(filter: Option<AuditFilter>) -> Result<Vec<AuditEntry>, String> {
+    run_blocking(move || read_audit_log(&filter.unwrap_or_default())).await
+}
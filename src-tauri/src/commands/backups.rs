@@ -0,0 +1,15 @@
+use crate::scanner::backups::{list_backups, restore_backup, SettingsBackup};
+use crate::blocking::run_blocking;
+
+/// List settings backups taken before ClaudeArcade's writes, newest first
+#[tauri::command]
+pub async fn list_settings_backups() -> Result<Vec<SettingsBackup>, String> {
+    run_blocking(move || list_backups(None)).await
+}
+
+/// Restore a settings backup over the file it was taken from
+#[tauri::command]
+#[tracing::instrument(err)]
+pub async fn restore_settings_backup(id: String) -> Result<SettingsBackup, String> {
+    run_blocking(move || restore_backup(&id)).await?
+}
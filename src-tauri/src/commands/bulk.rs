@@ -0,0 +1,439 @@
+//! Equip, unequip, enable, disable or delete many items in one call
+//! All settings.json changes are written together or not at all; deletions go to the trash one by one.
+//! Claude Code only honours `disabledMcpjsonServers` for a project's .mcp.json, so a disabled user
+//! MCP server is taken out of settings.json and parked in arcade_mcp_parked.json until it is enabled.
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use tauri::State;
+
+use super::equipment::{calculate_context_stats, context_stats_with, load_warning, permission_warnings, plugin_conflicts};
+use super::inventory::{cached_or_scan, memory_cached_scan, InventoryCacheState};
+use super::inventory_export::item_scope;
+use super::statusline::refresh_statusline_load;
+use crate::blocking::run_blocking;
+use crate::error::ArcadeError;
+use crate::paths::check_writable;
+use crate::scanner::settings::{modify_settings, read_mcp_servers, read_settings};
+use crate::trash::move_to_trash;
+use crate::types::{ContextStats, InventoryItem, ItemSource};
+
+/// Prefix of MCP server ids in operations, followed by the server name
+pub(crate) const MCP_PREFIX: &str = "mcp_";
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum BulkAction {
+    Equip,
+    Unequip,
+    Enable,
+    Disable,
+    Delete,
+}
+
+impl BulkAction {
+    fn turns_on(self) -> bool {
+        matches!(self, BulkAction::Equip | BulkAction::Enable)
+    }
+}
+
+/// One action on an inventory item, or on an MCP server as `mcp_<name>`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BulkOperation {
+    pub action: BulkAction,
+    pub item_id: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BulkItemResult {
+    pub item_id: String,
+    pub action: BulkAction,
+    pub success: bool,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BulkApplyResult {
+    /// One per operation, in order
+    pub results: Vec<BulkItemResult>,
+    /// Context load after every operation was applied
+    pub context_stats: ContextStats,
+    pub warnings: Vec<String>,
+    /// Clashes between equipped plugins and other plugins or the user's config
+    pub conflicts: Vec<String>,
+    /// Nothing was written; results and stats are what applying would lead to
+    pub dry_run: bool,
+}
+
+/// A change to ~/.claude/settings.json
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum SettingsEdit {
+    Plugin { id: String, enabled: bool },
+    /// Moves the server's entry between `mcpServers` and the parked servers, since Claude Code
+    /// starts every server left in `mcpServers`
+    McpEnabled { name: String, enabled: bool },
+    /// Drops the server from `mcpServers` and the parked servers
+    McpRemove { name: String },
+}
+
+/// Disabled user MCP servers by name, with the config they had in settings.json
+pub(crate) type ParkedMcpServers = Map<String, Value>;
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+struct ParkedFile {
+    servers: ParkedMcpServers,
+}
+
+fn parked_path() -> Option<PathBuf> {
+    crate::paths::claude_dir().map(|d| d.join(crate::store::MCP_PARKED.name))
+}
+
+/// MCP servers disabled by the arcade, to be put back into settings.json when enabled
+pub(crate) fn load_parked_mcp() -> ParkedMcpServers {
+    parked_path()
+        .and_then(|path| crate::store::MCP_PARKED.load::<ParkedFile>(&path))
+        .unwrap_or_default()
+        .servers
+}
+
+fn save_parked_mcp(servers: &ParkedMcpServers) -> Result<(), ArcadeError> {
+    let path = parked_path().ok_or("Could not find home directory")?;
+    let content = crate::store::MCP_PARKED.to_json(&ParkedFile { servers: servers.clone() })?;
+    crate::paths::atomic_write(&path, content).map_err(|e| ArcadeError::io(&path, e))
+}
+
+/// Apply `edits` to settings.json in one write, parking and restoring MCP server configs.
+/// Parked configs are saved before settings.json loses them, so a failed write loses nothing;
+/// a server left in both places stays enabled.
+pub(crate) fn write_settings_edits(edits: &[&SettingsEdit]) -> Result<(), ArcadeError> {
+    let before = load_parked_mcp();
+    let mut parked = before.clone();
+    modify_settings(|settings| {
+        let mut after = before.clone();
+        for edit in edits {
+            apply_edit(settings, edit, &mut after)?;
+        }
+        let mut kept = before.clone();
+        kept.extend(after.clone());
+        save_parked_mcp(&kept).map_err(String::from)?;
+        parked = after;
+        Ok(())
+    })?;
+    save_parked_mcp(&parked)
+}
+
+enum Planned {
+    Settings(SettingsEdit),
+    Trash(PathBuf, &'static str),
+}
+
+/// Whether an operation changes settings.json, and so joins its all-or-nothing write
+fn settings_bound(op: &BulkOperation, items: &HashMap<&str, &InventoryItem>) -> bool {
+    op.item_id.starts_with(MCP_PREFIX) || items.get(op.item_id.as_str()).is_some_and(|i| matches!(i.source, ItemSource::Plugin))
+}
+
+fn plan(
+    op: &BulkOperation,
+    items: &HashMap<&str, &InventoryItem>,
+    mcp_servers: &[String],
+    project_path: Option<&str>,
+) -> Result<Planned, String> {
+    if let Some(name) = op.item_id.strip_prefix(MCP_PREFIX) {
+        if !mcp_servers.iter().any(|s| s == name) {
+            return Err(format!("MCP server not found: {}", name));
+        }
+        let name = name.to_string();
+        return Ok(Planned::Settings(match op.action {
+            BulkAction::Delete => SettingsEdit::McpRemove { name },
+            action => SettingsEdit::McpEnabled { name, enabled: action.turns_on() },
+        }));
+    }
+
+    let item = items.get(op.item_id.as_str()).ok_or_else(|| format!("Item not found: {}", op.item_id))?;
+    match (&item.source, op.action) {
+        (ItemSource::Plugin, BulkAction::Delete) => Err(format!("Remove the plugin {} with uninstall_plugin", item.name)),
+        (ItemSource::Plugin, _) if item.source_path.is_empty() => Err(format!("{} is not installed", item.name)),
+        (ItemSource::Plugin, action) => Ok(Planned::Settings(SettingsEdit::Plugin { id: item.id.clone(), enabled: action.turns_on() })),
+        (ItemSource::Command | ItemSource::Subagent | ItemSource::Skill, BulkAction::Delete) => {
+            if !matches!(item_scope(item).as_str(), "user" | "project") {
+                return Err(format!("{} comes with a plugin or Claude Code and can't be deleted", item.name));
+            }
+            let path = PathBuf::from(&item.source_path);
+            check_writable(&path, project_path).map_err(|e| e.to_string())?;
+            let kind = match item.source {
+                ItemSource::Command => "command",
+                ItemSource::Subagent => "agent",
+                _ => "skill",
+            };
+            Ok(Planned::Trash(path, kind))
+        }
+        (ItemSource::Command | ItemSource::Subagent | ItemSource::Skill, _) => {
+            Err(format!("{} is always available to Claude; it can only be deleted", item.name))
+        }
+        _ => Err(format!("{} can't be changed in bulk", item.name)),
+    }
+}
+
+/// Add `name` to the string list at `key`, or take it out, dropping the list once empty
+fn set_listed(map: &mut Map<String, Value>, key: &str, name: &str, listed: bool) {
+    let list = map.entry(key).or_insert_with(|| Value::Array(Vec::new()));
+    let Some(names) = list.as_array_mut() else {
+        return;
+    };
+    names.retain(|n| n.as_str() != Some(name));
+    if listed {
+        names.push(Value::String(name.to_string()));
+    }
+    if names.is_empty() {
+        map.remove(key);
+    }
+}
+
+pub(crate) fn apply_edit(settings: &mut Value, edit: &SettingsEdit, parked: &mut ParkedMcpServers) -> Result<(), String> {
+    let map = settings.as_object_mut().ok_or("settings.json is not an object")?;
+    match edit {
+        SettingsEdit::Plugin { id, enabled } => {
+            let plugins = map.entry("enabledPlugins").or_insert_with(|| Value::Object(Map::new()));
+            let plugins = plugins.as_object_mut().ok_or("enabledPlugins is not an object")?;
+            // Disabled plugins are left out, as disable_plugin does
+            if *enabled {
+                plugins.insert(id.clone(), Value::Bool(true));
+            } else {
+                plugins.remove(id);
+            }
+        }
+        SettingsEdit::McpEnabled { name, enabled } => {
+            // Listed by earlier versions, which had no effect on these servers
+            set_listed(map, "disabledMcpjsonServers", name, false);
+            let servers = map.entry("mcpServers").or_insert_with(|| Value::Object(Map::new()));
+            let servers = servers.as_object_mut().ok_or("mcpServers is not an object")?;
+            if *enabled {
+                if let Some(config) = parked.remove(name) {
+                    servers.entry(name.clone()).or_insert(config);
+                }
+            } else if let Some(config) = servers.remove(name) {
+                parked.insert(name.clone(), config);
+            }
+            if servers.is_empty() {
+                map.remove("mcpServers");
+            }
+        }
+        SettingsEdit::McpRemove { name } => {
+            if let Some(Value::Object(servers)) = map.get_mut("mcpServers") {
+                servers.remove(name);
+            }
+            parked.remove(name);
+            set_listed(map, "disabledMcpjsonServers", name, false);
+        }
+    }
+    Ok(())
+}
+
+/// Run `operations` against the scanned `items`; see `bulk_apply`
+pub(crate) fn apply_operations(
+    items: &[InventoryItem],
+    operations: Vec<BulkOperation>,
+    project_path: Option<&str>,
+    dry_run: bool,
+) -> BulkApplyResult {
+    let items: HashMap<&str, &InventoryItem> = items.iter().map(|i| (i.id.as_str(), i)).collect();
+    let servers = read_mcp_servers();
+    let mcp_servers: Vec<String> = match &servers {
+        Ok(servers) => servers.keys().chain(load_parked_mcp().keys()).cloned().collect(),
+        Err(_) => Vec::new(),
+    };
+
+    let planned: Vec<Result<Planned, String>> = operations
+        .iter()
+        .map(|op| plan(op, &items, &mcp_servers, project_path))
+        .collect();
+    let mut errors: Vec<Option<String>> = planned.iter().map(|p| p.as_ref().err().cloned()).collect();
+
+    let in_settings: Vec<usize> = (0..operations.len()).filter(|&i| settings_bound(&operations[i], &items)).collect();
+    // Planned against a settings.json that couldn't be read, nothing in it can be trusted
+    if let Err(e) = &servers {
+        for &i in &in_settings {
+            errors[i] = Some(e.clone());
+        }
+    }
+    let blocked = in_settings.iter().find_map(|&i| {
+        errors[i].as_ref().map(|e| format!("Not applied because {} failed: {}", operations[i].item_id, e))
+    });
+    let edits: Vec<&SettingsEdit> = planned
+        .iter()
+        .filter_map(|p| match p {
+            Ok(Planned::Settings(edit)) => Some(edit),
+            _ => None,
+        })
+        .collect();
+    let written = match blocked {
+        Some(e) => Err(e),
+        None if edits.is_empty() || dry_run => Ok(()),
+        None => write_settings_edits(&edits).map_err(String::from),
+    };
+    if let Err(e) = written {
+        for &i in &in_settings {
+            errors[i].get_or_insert_with(|| e.clone());
+        }
+    }
+
+    for (i, planned) in planned.iter().enumerate() {
+        if let Ok(Planned::Trash(path, kind)) = planned {
+            if dry_run {
+                continue;
+            }
+            if let Err(e) = move_to_trash(path, kind) {
+                errors[i] = Some(e.to_string());
+            }
+        }
+    }
+
+    let plugin_changes: Vec<(String, bool)> = planned
+        .iter()
+        .zip(&errors)
+        .filter_map(|(planned, error)| match (planned, error) {
+            (Ok(Planned::Settings(SettingsEdit::Plugin { id, enabled })), None) => Some((id.clone(), *enabled)),
+            _ => None,
+        })
+        .collect();
+    let context_stats = if dry_run {
+        context_stats_with(&plugin_changes)
+    } else {
+        let stats = calculate_context_stats();
+        refresh_statusline_load(&stats);
+        stats
+    };
+
+    let mut enabled: Vec<String> = read_settings().unwrap_or_default().enabled_plugins.into_iter().filter(|(_, on)| *on).map(|(id, _)| id).collect();
+    for (id, on) in &plugin_changes {
+        enabled.retain(|e| e != id);
+        if *on {
+            enabled.push(id.clone());
+        }
+    }
+    let mut warnings: Vec<String> = load_warning(&context_stats).into_iter().collect();
+    let mut conflicts = Vec::new();
+    // Equipped plugins, checked against deny rules and each other as equip_item does
+    for (id, _) in plugin_changes.iter().filter(|(_, on)| *on) {
+        for warning in permission_warnings(id) {
+            if !warnings.contains(&warning) {
+                warnings.push(warning);
+            }
+        }
+        for conflict in plugin_conflicts(id, &enabled) {
+            if !conflicts.contains(&conflict) {
+                conflicts.push(conflict);
+            }
+        }
+    }
+
+    let results = operations
+        .into_iter()
+        .zip(errors)
+        .map(|(op, error)| BulkItemResult { item_id: op.item_id, action: op.action, success: error.is_none(), error })
+        .collect();
+    BulkApplyResult { results, context_stats, warnings, conflicts, dry_run }
+}
+
+/// Apply many item actions at once, e.g. disabling every unused MCP server. Settings changes are
+/// written in one go: if any of them is invalid or the write fails, none are applied. Deletions move
+/// each file to the trash independently. Returns per-item results and the resulting context load.
+/// With `dry_run`, nothing is written and the result previews the outcome, as for applying a loadout.
+#[tauri::command]
+#[tracing::instrument(skip(state, operations), fields(count = operations.len()), err)]
+pub async fn bulk_apply(
+    state: State<'_, InventoryCacheState>,
+    operations: Vec<BulkOperation>,
+    project_path: Option<String>,
+    dry_run: Option<bool>,
+) -> Result<BulkApplyResult, String> {
+    let cached = memory_cached_scan(&state, &project_path)?;
+    run_blocking(move || {
+        let scan = cached_or_scan(cached, project_path.as_deref());
+        apply_operations(&scan.items, operations, project_path.as_deref(), dry_run.unwrap_or(false))
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TempDir;
+    use crate::paths::ConfigPaths;
+    use serde_json::json;
+    use std::fs;
+
+    #[test]
+    fn applies_settings_edits() {
+        let mut settings = json!({
+            "enabledPlugins": { "review@market": true },
+            "mcpServers": { "github": { "command": "gh-mcp" }, "slack": { "command": "slack-mcp" } },
+            "disabledMcpjsonServers": ["github"],
+        });
+        let mut parked = ParkedMcpServers::new();
+        let edits = [
+            SettingsEdit::Plugin { id: "review@market".to_string(), enabled: false },
+            SettingsEdit::Plugin { id: "lint@market".to_string(), enabled: true },
+            SettingsEdit::McpEnabled { name: "github".to_string(), enabled: false },
+            SettingsEdit::McpRemove { name: "slack".to_string() },
+        ];
+        for edit in &edits {
+            apply_edit(&mut settings, edit, &mut parked).unwrap();
+        }
+        assert_eq!(settings["enabledPlugins"], json!({ "lint@market": true }));
+        assert_eq!(settings["mcpServers"], json!({}));
+        assert!(settings.get("disabledMcpjsonServers").is_none());
+        assert_eq!(Value::Object(parked.clone()), json!({ "github": { "command": "gh-mcp" } }));
+
+        apply_edit(&mut settings, &SettingsEdit::McpEnabled { name: "github".to_string(), enabled: true }, &mut parked).unwrap();
+        assert_eq!(settings["mcpServers"], json!({ "github": { "command": "gh-mcp" } }));
+        assert!(parked.is_empty());
+    }
+
+    #[test]
+    fn disabled_mcp_servers_leave_settings_and_come_back() {
+        let root = TempDir::new("bulk");
+        let settings_path = root.join(".claude/settings.json");
+        fs::create_dir_all(settings_path.parent().unwrap()).unwrap();
+        fs::write(&settings_path, r#"{"mcpServers":{"github":{"command":"gh-mcp","args":["--stdio"]}}}"#).unwrap();
+        let op = |action| BulkOperation { action, item_id: format!("{}github", MCP_PREFIX) };
+        let read = || serde_json::from_str::<Value>(&fs::read_to_string(&settings_path).unwrap()).unwrap();
+
+        let (disabled, parked, enabled) = ConfigPaths::under(&root).scoped(|| {
+            let result = apply_operations(&[], vec![op(BulkAction::Disable)], None, false);
+            assert!(result.results[0].success, "{:?}", result.results[0].error);
+            let disabled = read();
+            let parked = load_parked_mcp();
+            // Parked servers are still known to a later enable
+            let result = apply_operations(&[], vec![op(BulkAction::Enable)], None, false);
+            assert!(result.results[0].success, "{:?}", result.results[0].error);
+            (disabled, parked, (read(), load_parked_mcp()))
+        });
+
+        assert!(disabled.get("mcpServers").is_none());
+        assert_eq!(parked["github"], json!({ "command": "gh-mcp", "args": ["--stdio"] }));
+        assert_eq!(enabled.0["mcpServers"]["github"], json!({ "command": "gh-mcp", "args": ["--stdio"] }));
+        assert!(enabled.0.get("disabledMcpjsonServers").is_none());
+        assert!(enabled.1.is_empty());
+    }
+
+    #[test]
+    fn unreadable_settings_block_settings_edits() {
+        let root = TempDir::new("bulk-broken");
+        let settings_path = root.join(".claude/settings.json");
+        fs::create_dir_all(settings_path.parent().unwrap()).unwrap();
+        fs::write(&settings_path, r#"{"mcpServers":{"github":{"command":"gh-mcp"}"#).unwrap();
+
+        let op = BulkOperation { action: BulkAction::Disable, item_id: format!("{}github", MCP_PREFIX) };
+        let result = ConfigPaths::under(&root).scoped(|| apply_operations(&[], vec![op], None, true));
+
+        assert!(!result.results[0].success);
+        assert!(result.results[0].error.as_deref().is_some_and(|e| e.contains("Failed to parse")));
+    }
+}
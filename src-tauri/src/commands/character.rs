@@ -0,0 +1,743 @@
+//! Character files: a whole global setup (CLAUDE.md, commands, skills, agents, hooks, MCP servers
+//! and loadouts) bundled into one versioned `.arcade` JSON document that can be shared or moved.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+use crate::scanner::frontmatter::Frontmatter;
+use crate::scanner::plugin::claude_config_dir;
+use crate::scanner::settings::{install_mcp_server_value, read_settings_full, update_settings_field};
+use crate::blocking::run_blocking;
+
+/// Identifies a character file
+pub const CHARACTER_FORMAT: &str = "claudearcade-character";
+
+/// Bumped when the archive layout changes incompatibly
+pub const CHARACTER_VERSION: u32 = 1;
+
+/// Parts of a setup a character file can carry
+pub const CHARACTER_COMPONENTS: [&str; 7] = ["claudeMd", "commands", "skills", "agents", "hooks", "mcpServers", "loadouts"];
+
+/// Files larger than this are left out of a character file
+const MAX_FILE_BYTES: u64 = 512 * 1024;
+
+/// Stands in for a scrubbed secret
+pub const REDACTED: &str = "<REDACTED>";
+
+/// What to put in a character file
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CharacterExportOptions {
+    pub name: Option<String>,
+    pub author: Option<String>,
+    pub description: Option<String>,
+    /// Components to include; all of them when None
+    pub components: Option<Vec<String>>,
+    /// Saved loadouts (builds), which live in the app's own storage
+    pub loadouts: Option<Value>,
+}
+
+/// One component in a character file and the items it holds
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CharacterComponent {
+    pub kind: String,
+    pub items: Vec<String>,
+}
+
+/// Describes a character file; checked before anything is imported
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CharacterManifest {
+    pub format: String,
+    pub version: u32,
+    pub name: String,
+    pub author: Option<String>,
+    pub description: Option<String>,
+    /// RFC 3339
+    pub created_at: String,
+    pub app_version: String,
+    pub components: Vec<CharacterComponent>,
+    /// Values replaced by placeholders, e.g. "mcpServers.github.env.GITHUB_TOKEN"
+    pub scrubbed: Vec<String>,
+}
+
+/// A config file, stored by its path relative to ~/.claude
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CharacterFile {
+    pub component: String,
+    pub path: String,
+    pub content: String,
+}
+
+/// The whole `.arcade` document
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CharacterArchive {
+    pub manifest: CharacterManifest,
+    #[serde(default)]
+    pub files: Vec<CharacterFile>,
+    /// settings.json `hooks`
+    #[serde(default)]
+    pub hooks: Option<Value>,
+    /// settings.json `mcpServers`, secrets scrubbed
+    #[serde(default)]
+    pub mcp_servers: BTreeMap<String, Value>,
+    #[serde(default)]
+    pub loadouts: Option<Value>,
+}
+
+/// Where a character file was written and what went in
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CharacterExportResult {
+    pub path: String,
+    pub manifest: CharacterManifest,
+    pub warnings: Vec<String>,
+}
+
+/// Names of env vars, headers and flags that usually hold credentials
+fn is_secret_name(name: &str) -> bool {
+    let name = name.to_ascii_lowercase();
+    ["token", "key", "secret", "password", "passwd", "auth", "credential"]
+        .iter()
+        .any(|word| name.contains(word))
+}
+
+/// Values that look like credentials whatever they're called
+fn is_secret_value(value: &str) -> bool {
+    ["sk-", "ghp_", "gho_", "github_pat_", "glpat-", "xoxb-", "xoxp-", "AKIA", "Bearer "]
+        .iter()
+        .any(|prefix| value.starts_with(prefix))
+}
+
+/// Replace credentials in an MCP server config: env vars and headers become `${NAME}`
+/// references, secret arguments become placeholders
+pub fn scrub_mcp_server(name: &str, config: &mut Value, scrubbed: &mut Vec<String>) {
+    for field in ["env", "headers"] {
+        if let Some(map) = config.get_mut(field).and_then(|v| v.as_object_mut()) {
+            for (key, value) in map.iter_mut() {
+                let secret = is_secret_name(key) || value.as_str().is_some_and(is_secret_value);
+                if secret && value.as_str() != Some(&format!("${{{}}}", key)) {
+                    *value = Value::String(format!("${{{}}}", key));
+                    scrubbed.push(format!("mcpServers.{}.{}.{}", name, field, key));
+                }
+            }
+        }
+    }
+
+    if let Some(args) = config.get_mut("args").and_then(|v| v.as_array_mut()) {
+        let mut previous_flag_secret = false;
+        for (index, arg) in args.iter_mut().enumerate() {
+            let Some(text) = arg.as_str().map(String::from) else {
+                continue;
+            };
+            let replacement = match text.split_once('=') {
+                Some((flag, value)) if flag.starts_with('-') && is_secret_name(flag) && !value.is_empty() => {
+                    Some(format!("{}={}", flag, REDACTED))
+                }
+                _ if previous_flag_secret || is_secret_value(&text) => Some(REDACTED.to_string()),
+                _ => None,
+            };
+            previous_flag_secret = text.starts_with('-') && !text.contains('=') && is_secret_name(&text);
+            if let Some(replacement) = replacement {
+                *arg = Value::String(replacement);
+                scrubbed.push(format!("mcpServers.{}.args[{}]", name, index));
+            }
+        }
+    }
+}
+
+/// Text files under a ~/.claude folder, as character files
+fn collect_files(claude_dir: &Path, folder: &str, component: &str, warnings: &mut Vec<String>) -> Vec<CharacterFile> {
+    let mut files = Vec::new();
+    for entry in WalkDir::new(claude_dir.join(folder)).sort_by_file_name().into_iter().filter_map(|e| e.ok()) {
+        let path = entry.path();
+        if !path.is_file() || path.components().any(|c| c.as_os_str() == ".git") {
+            continue;
+        }
+        let relative = path.strip_prefix(claude_dir).unwrap_or(path).to_string_lossy().replace('\\', "/");
+        if entry.metadata().map(|m| m.len()).unwrap_or(0) > MAX_FILE_BYTES {
+            warnings.push(format!("Skipped {}: larger than {} KB", relative, MAX_FILE_BYTES / 1024));
+            continue;
+        }
+        match fs::read_to_string(path) {
+            Ok(content) => files.push(CharacterFile { component: component.to_string(), path: relative, content }),
+            Err(_) => warnings.push(format!("Skipped {}: not a text file", relative)),
+        }
+    }
+    files
+}
+
+/// Item names for the manifest: top-level entries under the component folder
+fn component_items(files: &[CharacterFile], component: &str) -> Vec<String> {
+    let mut items: Vec<String> = files
+        .iter()
+        .filter(|f| f.component == component)
+        .filter_map(|f| f.path.split('/').nth(1))
+        .map(|name| name.trim_end_matches(".md").to_string())
+        .collect();
+    items.dedup();
+    items
+}
+
+/// Gather the selected parts of the global setup into an archive
+pub(crate) fn build_character(options: &CharacterExportOptions, warnings: &mut Vec<String>) -> Result<CharacterArchive, String> {
+    let claude_dir = claude_config_dir().ok_or("Could not find home directory")?;
+    let selected: Vec<&str> = match &options.components {
+        Some(components) => {
+            if let Some(unknown) = components.iter().find(|c| !CHARACTER_COMPONENTS.contains(&c.as_str())) {
+                return Err(format!("Unknown component '{}'. Expected any of: {}", unknown, CHARACTER_COMPONENTS.join(", ")));
+            }
+            components.iter().map(|c| c.as_str()).collect()
+        }
+        None => CHARACTER_COMPONENTS.to_vec(),
+    };
+    let settings = read_settings_full()?;
+
+    let mut files = Vec::new();
+    let mut components = Vec::new();
+    let mut scrubbed = Vec::new();
+    let mut hooks = None;
+    let mut mcp_servers = BTreeMap::new();
+    let mut loadouts = None;
+
+    for component in &selected {
+        let items = match *component {
+            "claudeMd" => match fs::read_to_string(claude_dir.join("CLAUDE.md")) {
+                Ok(content) => {
+                    files.push(CharacterFile { component: "claudeMd".to_string(), path: "CLAUDE.md".to_string(), content });
+                    vec!["CLAUDE.md".to_string()]
+                }
+                Err(_) => Vec::new(),
+            },
+            "commands" | "skills" | "agents" => {
+                files.extend(collect_files(&claude_dir, component, component, warnings));
+                component_items(&files, component)
+            }
+            "hooks" => {
+                hooks = settings.hooks.clone().filter(|h| h.as_object().is_some_and(|m| !m.is_empty()));
+                let events: Vec<String> = hooks.iter().flat_map(|h| h.as_object().into_iter().flatten().map(|(k, _)| k.clone())).collect();
+                if !events.is_empty() {
+                    warnings.push("Hooks run shell commands; scripts they reference are not included".to_string());
+                }
+                events
+            }
+            "mcpServers" => {
+                for (name, config) in settings.mcp_servers.clone().unwrap_or_default() {
+                    let mut config = config;
+                    scrub_mcp_server(&name, &mut config, &mut scrubbed);
+                    mcp_servers.insert(name, config);
+                }
+                mcp_servers.keys().cloned().collect()
+            }
+            "loadouts" => {
+                loadouts = options.loadouts.clone();
+                match &loadouts {
+                    Some(Value::Array(builds)) => builds
+                        .iter()
+                        .filter_map(|b| b.get("name").and_then(|n| n.as_str()).map(String::from))
+                        .collect(),
+                    _ => Vec::new(),
+                }
+            }
+            _ => Vec::new(),
+        };
+        if !items.is_empty() {
+            components.push(CharacterComponent { kind: component.to_string(), items });
+        }
+    }
+
+    Ok(CharacterArchive {
+        manifest: CharacterManifest {
+            format: CHARACTER_FORMAT.to_string(),
+            version: CHARACTER_VERSION,
+            name: options.name.clone().unwrap_or_else(|| "My Character".to_string()),
+            author: options.author.clone(),
+            description: options.description.clone(),
+            created_at: chrono::Utc::now().to_rfc3339(),
+            app_version: env!("CARGO_PKG_VERSION").to_string(),
+            components,
+            scrubbed,
+        },
+        files,
+        hooks,
+        mcp_servers,
+        loadouts,
+    })
+}
+
+/// Bundle the global setup into a `.arcade` character file at `dest` (a file, or a folder to
+/// write `<name>.arcade` into). MCP credentials are replaced by placeholders.
+#[tauri::command]
+pub async fn export_character(dest: String, options: Option<CharacterExportOptions>) -> Result<CharacterExportResult, String> {
+    run_blocking(move || {
+        let options = options.unwrap_or_default();
+        let mut warnings = Vec::new();
+        let archive = build_character(&options, &mut warnings)?;
+
+        let mut path = PathBuf::from(dest);
+        if path.is_dir() {
+            let stem: String = archive
+                .manifest
+                .name
+                .chars()
+                .map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '_' { c } else { '-' })
+                .collect();
+            path = path.join(format!("{}.arcade", stem));
+        }
+        let content = serde_json::to_string_pretty(&archive).map_err(|e| e.to_string())?;
+        crate::paths::atomic_write(&path, content).map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+
+        Ok(CharacterExportResult {
+            path: path.to_string_lossy().to_string(),
+            manifest: archive.manifest,
+            warnings,
+        })
+    })
+    .await?
+}
+
+/// One thing a character file would add, and whether it clashes with the current setup
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CharacterImportItem {
+    /// "<component>:<name>", used to pick a resolution per item
+    pub id: String,
+    pub component: String,
+    pub name: String,
+    /// Path under ~/.claude, or the settings.json key
+    pub target: String,
+    pub conflict: bool,
+    /// Already present with the same content; importing changes nothing
+    pub identical: bool,
+}
+
+/// What importing a character file would do
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CharacterImportPreview {
+    pub manifest: CharacterManifest,
+    pub items: Vec<CharacterImportItem>,
+    pub warnings: Vec<String>,
+}
+
+/// How to resolve conflicts: "skip", "overwrite" or "rename". Rename keeps both copies;
+/// for CLAUDE.md and hooks, which can't be renamed, it appends to what is there.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CharacterImportStrategy {
+    /// For conflicts without an override; "skip" when None
+    pub default: Option<String>,
+    /// Per-item choices, keyed by item id
+    #[serde(default)]
+    pub overrides: HashMap<String, String>,
+    /// Components to import; all in the file when None
+    pub components: Option<Vec<String>>,
+}
+
+/// What an import did
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CharacterImportResult {
+    pub manifest: CharacterManifest,
+    pub imported: Vec<String>,
+    pub skipped: Vec<String>,
+    /// Item id and the name it was imported as
+    pub renamed: Vec<(String, String)>,
+    /// Loadouts from the file, for the app to add to its saved builds
+    pub loadouts: Option<Value>,
+    pub warnings: Vec<String>,
+}
+
+/// Read a character file and check it is one this version understands
+fn read_character(path: &str) -> Result<CharacterArchive, String> {
+    let content = fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", path, e))?;
+    let archive: CharacterArchive =
+        serde_json::from_str(&content).map_err(|e| format!("Not a valid character file: {}", e))?;
+
+    let manifest = &archive.manifest;
+    if manifest.format != CHARACTER_FORMAT {
+        return Err(format!("Not a character file (format '{}')", manifest.format));
+    }
+    if manifest.version > CHARACTER_VERSION {
+        return Err(format!(
+            "Character file version {} is newer than this app supports ({}); update ClaudeArcade",
+            manifest.version, CHARACTER_VERSION
+        ));
+    }
+    for file in &archive.files {
+        let folder_ok = match file.component.as_str() {
+            "claudeMd" => file.path == "CLAUDE.md",
+            "commands" | "skills" | "agents" => file.path.starts_with(&format!("{}/", file.component)),
+            _ => false,
+        };
+        let escapes = Path::new(&file.path)
+            .components()
+            .any(|c| !matches!(c, std::path::Component::Normal(_)));
+        if !folder_ok || escapes {
+            return Err(format!("Character file has an unsafe path: {}", file.path));
+        }
+    }
+    Ok(archive)
+}
+
+/// Name an item is grouped under: skills are folders, everything else is one file
+fn file_item_name(file: &CharacterFile) -> String {
+    match file.component.as_str() {
+        "skills" => file.path.split('/').nth(1).unwrap_or_default().to_string(),
+        "claudeMd" => "CLAUDE.md".to_string(),
+        _ => file.path.split_once('/').map(|(_, rest)| rest).unwrap_or(&file.path).to_string(),
+    }
+}
+
+/// Path under ~/.claude an item occupies
+fn file_item_target(component: &str, name: &str) -> String {
+    match component {
+        "claudeMd" => "CLAUDE.md".to_string(),
+        _ => format!("{}/{}", component, name),
+    }
+}
+
+/// Every item in the archive with its conflict state
+fn preview_items(archive: &CharacterArchive, claude_dir: &Path) -> Result<Vec<CharacterImportItem>, String> {
+    let settings = read_settings_full()?;
+    let mut items: Vec<CharacterImportItem> = Vec::new();
+
+    for file in &archive.files {
+        let name = file_item_name(file);
+        let id = format!("{}:{}", file.component, name);
+        let on_disk = fs::read_to_string(claude_dir.join(&file.path)).ok();
+        let differs = on_disk.as_deref().is_some_and(|c| c != file.content);
+        match items.iter_mut().find(|i| i.id == id) {
+            // Later files of the same skill folder
+            Some(item) => {
+                item.conflict |= differs;
+                item.identical &= on_disk.is_some() && !differs;
+            }
+            None => {
+                let target = file_item_target(&file.component, &name);
+                let exists = claude_dir.join(&target).exists();
+                items.push(CharacterImportItem {
+                    id,
+                    component: file.component.clone(),
+                    name,
+                    target,
+                    conflict: differs || (exists && on_disk.is_none()),
+                    identical: on_disk.is_some() && !differs,
+                });
+            }
+        }
+    }
+
+    let current_hooks = settings.hooks.unwrap_or(Value::Null);
+    for (event, matchers) in archive.hooks.iter().flat_map(|h| h.as_object().into_iter().flatten()) {
+        let existing = current_hooks.get(event);
+        items.push(CharacterImportItem {
+            id: format!("hooks:{}", event),
+            component: "hooks".to_string(),
+            name: event.clone(),
+            target: format!("hooks.{}", event),
+            conflict: existing.is_some_and(|e| e != matchers),
+            identical: existing == Some(matchers),
+        });
+    }
+
+    let current_servers = settings.mcp_servers.unwrap_or_default();
+    for (name, config) in &archive.mcp_servers {
+        let existing = current_servers.get(name);
+        items.push(CharacterImportItem {
+            id: format!("mcpServers:{}", name),
+            component: "mcpServers".to_string(),
+            name: name.clone(),
+            target: format!("mcpServers.{}", name),
+            conflict: existing.is_some_and(|e| e != config),
+            identical: existing == Some(config),
+        });
+    }
+
+    Ok(items)
+}
+
+/// Warnings every preview and import should show
+fn manifest_warnings(manifest: &CharacterManifest) -> Vec<String> {
+    manifest
+        .scrubbed
+        .iter()
+        .map(|key| format!("{} was scrubbed on export; fill it in after importing", key))
+        .collect()
+}
+
+/// Check a character file and list what importing it would add or clash with
+#[tauri::command]
+pub async fn preview_character_import(path: String) -> Result<CharacterImportPreview, String> {
+    run_blocking(move || {
+        let archive = read_character(&path)?;
+        let claude_dir = claude_config_dir().ok_or("Could not find home directory")?;
+        Ok(CharacterImportPreview {
+            items: preview_items(&archive, &claude_dir)?,
+            warnings: manifest_warnings(&archive.manifest),
+            manifest: archive.manifest,
+        })
+    })
+    .await?
+}
+
+/// First free "<name>-imported[-n]" among taken names
+pub(crate) fn renamed(name: &str, taken: impl Fn(&str) -> bool) -> String {
+    let (stem, extension) = match name.rsplit_once('.') {
+        Some((stem, ext)) if !stem.is_empty() => (stem, format!(".{}", ext)),
+        _ => (name, String::new()),
+    };
+    (1..)
+        .map(|n| match n {
+            1 => format!("{}-imported{}", stem, extension),
+            n => format!("{}-imported-{}{}", stem, n, extension),
+        })
+        .find(|candidate| !taken(candidate))
+        .unwrap_or_default()
+}
+
+/// Point a renamed agent or skill's frontmatter `name` at its new name
+pub(crate) fn rename_frontmatter(content: &str, new_name: &str) -> String {
+    let mut doc = Frontmatter::parse(content);
+    if !doc.has_frontmatter() || doc.get("name").is_none() || doc.set("name", new_name).is_err() {
+        return content.to_string();
+    }
+    doc.to_string()
+}
+
+pub(crate) fn write_claude_file(path: &Path, content: &str) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+    }
+    crate::watcher::write_expected(path, content).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+/// Import a character file. Items that clash are resolved per `strategy`; files already in
+/// place but not in the archive are kept.
+#[tauri::command]
+#[tracing::instrument(skip(strategy), err)]
+pub async fn import_character(path: String, strategy: Option<CharacterImportStrategy>) -> Result<CharacterImportResult, String> {
+    run_blocking(move || import_archive(&path, strategy.unwrap_or_default())).await?
+}
+
+fn import_archive(path: &str, strategy: CharacterImportStrategy) -> Result<CharacterImportResult, String> {
+    let archive = read_character(path)?;
+    let claude_dir = claude_config_dir().ok_or("Could not find home directory")?;
+    let items = preview_items(&archive, &claude_dir)?;
+
+    let selected = |component: &str| strategy.components.as_ref().is_none_or(|c| c.iter().any(|s| s == component));
+    let choice = |item: &CharacterImportItem| -> String {
+        if !item.conflict {
+            return "overwrite".to_string();
+        }
+        strategy
+            .overrides
+            .get(&item.id)
+            .or(strategy.default.as_ref())
+            .cloned()
+            .unwrap_or_else(|| "skip".to_string())
+    };
+
+    let mut result = CharacterImportResult {
+        manifest: archive.manifest.clone(),
+        imported: Vec::new(),
+        skipped: Vec::new(),
+        renamed: Vec::new(),
+        loadouts: None,
+        warnings: manifest_warnings(&archive.manifest),
+    };
+
+    for item in &items {
+        if !selected(&item.component) || item.identical {
+            result.skipped.push(item.id.clone());
+            continue;
+        }
+        let choice = choice(item);
+        if !["skip", "overwrite", "rename"].contains(&choice.as_str()) {
+            return Err(format!("Unknown resolution '{}' for {}", choice, item.id));
+        }
+        if choice == "skip" {
+            result.skipped.push(item.id.clone());
+            continue;
+        }
+
+        match item.component.as_str() {
+            "hooks" => {
+                let mut hooks = read_settings_full()?.hooks.unwrap_or_else(|| Value::Object(Default::default()));
+                let incoming = archive.hooks.as_ref().and_then(|h| h.get(&item.name)).cloned().unwrap_or(Value::Null);
+                let merged = match (choice.as_str(), hooks.get(&item.name)) {
+                    ("rename", Some(Value::Array(existing))) => {
+                        let mut merged = existing.clone();
+                        merged.extend(incoming.as_array().into_iter().flatten().cloned());
+                        Value::Array(merged)
+                    }
+                    _ => incoming,
+                };
+                if let Some(map) = hooks.as_object_mut() {
+                    map.insert(item.name.clone(), merged);
+                }
+                update_settings_field("hooks", hooks)?;
+            }
+            "mcpServers" => {
+                let mut name = item.name.clone();
+                if choice == "rename" {
+                    let existing = read_settings_full()?.mcp_servers.unwrap_or_default();
+                    name = renamed(&item.name, |candidate| existing.contains_key(candidate));
+                    result.renamed.push((item.id.clone(), name.clone()));
+                }
+                let config = archive.mcp_servers.get(&item.name).cloned().unwrap_or(Value::Null);
+                install_mcp_server_value(&name, config)?;
+            }
+            component => {
+                let files = archive.files.iter().filter(|f| f.component == component && file_item_name(f) == item.name);
+                let new_name = (choice == "rename" && component != "claudeMd").then(|| {
+                    renamed(&item.name, |candidate| claude_dir.join(file_item_target(component, candidate)).exists())
+                });
+                for file in files {
+                    let target = match &new_name {
+                        Some(new_name) => {
+                            let inner = file.path.strip_prefix(&file_item_target(component, &item.name)).unwrap_or_default();
+                            claude_dir.join(format!("{}{}", file_item_target(component, new_name), inner))
+                        }
+                        None => claude_dir.join(&file.path),
+                    };
+                    let content = match &new_name {
+                        Some(new_name) => rename_frontmatter(&file.content, new_name.trim_end_matches(".md")),
+                        // CLAUDE.md can't be renamed; keep both by appending
+                        None if choice == "rename" => {
+                            let existing = fs::read_to_string(&target).unwrap_or_default();
+                            format!("{}\n\n{}", existing.trim_end(), file.content)
+                        }
+                        None => file.content.clone(),
+                    };
+                    write_claude_file(&target, &content)?;
+                }
+                if let Some(new_name) = new_name {
+                    result.renamed.push((item.id.clone(), new_name));
+                }
+            }
+        }
+        result.imported.push(item.id.clone());
+    }
+
+    if selected("loadouts") {
+        result.loadouts = archive.loadouts;
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::paths::ConfigPaths;
+    use crate::test_support::TempDir;
+
+    #[test]
+    fn scrubs_mcp_credentials() {
+        let mut config = serde_json::json!({
+            "command": "npx",
+            "args": ["server", "--api-key", "abc123", "--token=xyz", "--port", "3000"],
+            "env": { "GITHUB_TOKEN": "ghp_secret", "NODE_ENV": "production" }
+        });
+        let mut scrubbed = Vec::new();
+        scrub_mcp_server("github", &mut config, &mut scrubbed);
+
+        assert_eq!(config["env"]["GITHUB_TOKEN"], "${GITHUB_TOKEN}");
+        assert_eq!(config["env"]["NODE_ENV"], "production");
+        assert_eq!(config["args"], serde_json::json!(["server", "--api-key", REDACTED, "--token=<REDACTED>", "--port", "3000"]));
+        assert_eq!(scrubbed.len(), 3);
+    }
+
+    fn archive(files: &[(&str, &str, &str)], mcp_servers: serde_json::Value) -> String {
+        serde_json::json!({
+            "manifest": {
+                "format": CHARACTER_FORMAT, "version": CHARACTER_VERSION, "name": "Reviewer", "author": null,
+                "description": null, "createdAt": "", "appVersion": "", "components": [], "scrubbed": [],
+            },
+            "files": files.iter().map(|(component, path, content)| serde_json::json!({
+                "component": component, "path": path, "content": content,
+            })).collect::<Vec<_>>(),
+            "mcpServers": mcp_servers,
+        })
+        .to_string()
+    }
+
+    #[test]
+    fn imports_into_an_empty_setup() {
+        let root = TempDir::new("character-import");
+        let file = root.join("reviewer.arcade");
+        fs::write(&file, archive(
+            &[("commands", "commands/review.md", "Review the diff"), ("skills", "skills/pdf/SKILL.md", "---\nname: pdf\n---\n")],
+            serde_json::json!({ "github": { "command": "gh-mcp" } }),
+        ))
+        .unwrap();
+
+        let result = ConfigPaths::under(&root)
+            .scoped(|| import_archive(&file.to_string_lossy(), CharacterImportStrategy::default()))
+            .unwrap();
+
+        assert_eq!(result.imported, vec!["commands:review.md", "skills:pdf", "mcpServers:github"]);
+        assert_eq!(fs::read_to_string(root.join(".claude/commands/review.md")).unwrap(), "Review the diff");
+        assert!(root.join(".claude/skills/pdf/SKILL.md").is_file());
+        let settings: Value = serde_json::from_str(&fs::read_to_string(root.join(".claude/settings.json")).unwrap()).unwrap();
+        assert_eq!(settings["mcpServers"]["github"]["command"], "gh-mcp");
+
+        fs::write(&file, archive(&[("commands", "commands/../../.bashrc", "echo")], serde_json::json!({}))).unwrap();
+        let escaped = ConfigPaths::under(&root).scoped(|| import_archive(&file.to_string_lossy(), CharacterImportStrategy::default()));
+        assert!(escaped.is_err());
+        assert!(!root.join(".bashrc").exists());
+    }
+
+    #[test]
+    fn resolves_name_conflicts_per_item() {
+        let root = TempDir::new("character-conflicts");
+        fs::create_dir_all(root.join(".claude/commands")).unwrap();
+        fs::create_dir_all(root.join(".claude/agents")).unwrap();
+        fs::write(root.join(".claude/commands/review.md"), "Mine").unwrap();
+        fs::write(root.join(".claude/commands/ship.md"), "Ship it").unwrap();
+        fs::write(root.join(".claude/agents/helper.md"), "---\nname: helper\n---\nMine").unwrap();
+        let file = root.join("team.arcade");
+        fs::write(&file, archive(
+            &[
+                ("commands", "commands/review.md", "Theirs"),
+                ("commands", "commands/ship.md", "Ship it"),
+                ("agents", "agents/helper.md", "---\nname: helper\n---\nTheirs"),
+            ],
+            serde_json::json!({}),
+        ))
+        .unwrap();
+        let path = file.to_string_lossy().to_string();
+
+        let (preview, result) = ConfigPaths::under(&root).scoped(|| {
+            let claude_dir = claude_config_dir().unwrap();
+            let preview = preview_items(&read_character(&path).unwrap(), &claude_dir).unwrap();
+            let strategy = CharacterImportStrategy {
+                overrides: HashMap::from([("agents:helper.md".to_string(), "rename".to_string())]),
+                ..Default::default()
+            };
+            (preview, import_archive(&path, strategy).unwrap())
+        });
+
+        let state: Vec<(&str, bool, bool)> = preview.iter().map(|i| (i.id.as_str(), i.conflict, i.identical)).collect();
+        assert_eq!(state, vec![("commands:review.md", true, false), ("commands:ship.md", false, true), ("agents:helper.md", true, false)]);
+
+        // Conflicts are skipped unless an override says otherwise; identical items change nothing
+        assert_eq!(result.skipped, vec!["commands:review.md", "commands:ship.md"]);
+        assert_eq!(result.renamed, vec![("agents:helper.md".to_string(), "helper-imported.md".to_string())]);
+        assert_eq!(fs::read_to_string(root.join(".claude/commands/review.md")).unwrap(), "Mine");
+        assert!(fs::read_to_string(root.join(".claude/agents/helper.md")).unwrap().ends_with("Mine"));
+        let imported = fs::read_to_string(root.join(".claude/agents/helper-imported.md")).unwrap();
+        assert!(imported.contains("name: helper-imported") && imported.ends_with("Theirs"));
+
+        let overwrite = CharacterImportStrategy { default: Some("overwrite".to_string()), ..Default::default() };
+        ConfigPaths::under(&root).scoped(|| import_archive(&path, overwrite)).unwrap();
+        assert_eq!(fs::read_to_string(root.join(".claude/commands/review.md")).unwrap(), "Theirs");
+    }
+}
@@ -1,62 +1,80 @@
 use std::fs;
 use std::path::PathBuf;
+use crate::blocking::run_blocking;
+use crate::error::ArcadeError;
+use crate::secrets::check_secrets;
 
 /// Get the path to the global CLAUDE.md file
 fn global_claude_md_path() -> Option<PathBuf> {
-    dirs::home_dir().map(|h| h.join(".claude").join("CLAUDE.md"))
+    crate::paths::claude_dir().map(|d| d.join("CLAUDE.md"))
 }
 
 /// Read the global CLAUDE.md file
 #[tauri::command]
 pub async fn read_global_claude_md() -> Result<String, String> {
-    let path = global_claude_md_path().ok_or("Could not find home directory")?;
+    run_blocking(move || {
+        let path = global_claude_md_path().ok_or("Could not find home directory")?;
 
-    if !path.exists() {
-        return Ok(String::new());
-    }
+        if !path.exists() {
+            return Ok(String::new());
+        }
 
-    fs::read_to_string(&path).map_err(|e| format!("Failed to read CLAUDE.md: {}", e))
+        fs::read_to_string(&path).map_err(|e| format!("Failed to read CLAUDE.md: {}", e))
+    })
+    .await?
 }
 
-/// Write to the global CLAUDE.md file
+/// Write to the global CLAUDE.md file. Content that looks like it holds credentials is refused
+/// unless `allow_secrets` confirms it.
 #[tauri::command]
-pub async fn write_global_claude_md(content: String) -> Result<(), String> {
-    let path = global_claude_md_path().ok_or("Could not find home directory")?;
+#[tracing::instrument(skip(content), err)]
+pub async fn write_global_claude_md(content: String, allow_secrets: Option<bool>) -> Result<(), ArcadeError> {
+    run_blocking(move || {
+        check_secrets(&content, allow_secrets)?;
+        let path = global_claude_md_path().ok_or("Could not find home directory")?;
 
-    // Ensure parent directory exists
-    if let Some(parent) = path.parent() {
-        fs::create_dir_all(parent).map_err(|e| e.to_string())?;
-    }
+        // Ensure parent directory exists
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+        }
 
-    // Write atomically via temp file
-    let temp_path = path.with_extension("md.tmp");
-    fs::write(&temp_path, &content).map_err(|e| format!("Failed to write CLAUDE.md: {}", e))?;
-    fs::rename(&temp_path, &path).map_err(|e| format!("Failed to save CLAUDE.md: {}", e))?;
+        crate::watcher::write_expected(&path, &content).map_err(|e| format!("Failed to save CLAUDE.md: {}", e))?;
 
-    Ok(())
+        Ok(())
+    })
+    .await?
 }
 
 /// Read a project-specific CLAUDE.md file
 #[tauri::command]
 pub async fn read_project_claude_md(project_path: String) -> Result<String, String> {
-    let path = PathBuf::from(&project_path).join("CLAUDE.md");
+    run_blocking(move || {
+        let path = PathBuf::from(&project_path).join("CLAUDE.md");
 
-    if !path.exists() {
-        return Ok(String::new());
-    }
+        if !path.exists() {
+            return Ok(String::new());
+        }
 
-    fs::read_to_string(&path).map_err(|e| format!("Failed to read CLAUDE.md: {}", e))
+        fs::read_to_string(&path).map_err(|e| format!("Failed to read CLAUDE.md: {}", e))
+    })
+    .await?
 }
 
-/// Write to a project-specific CLAUDE.md file
+/// Write to a project-specific CLAUDE.md file, refusing likely credentials unless `allow_secrets`
 #[tauri::command]
-pub async fn write_project_claude_md(project_path: String, content: String) -> Result<(), String> {
-    let path = PathBuf::from(&project_path).join("CLAUDE.md");
+#[tracing::instrument(skip(content), err)]
+pub async fn write_project_claude_md(
+    project_path: String,
+    content: String,
+    allow_secrets: Option<bool>,
+) -> Result<(), ArcadeError> {
+    run_blocking(move || {
+        check_secrets(&content, allow_secrets)?;
+        let path = PathBuf::from(&project_path).join("CLAUDE.md");
 
-    // Write atomically via temp file
-    let temp_path = path.with_extension("md.tmp");
-    fs::write(&temp_path, &content).map_err(|e| format!("Failed to write CLAUDE.md: {}", e))?;
-    fs::rename(&temp_path, &path).map_err(|e| format!("Failed to save CLAUDE.md: {}", e))?;
+        crate::watcher::write_expected(&path, &content).map_err(|e| format!("Failed to save CLAUDE.md: {}", e))?;
 
-    Ok(())
+        Ok(())
+    })
+    .await?
 }
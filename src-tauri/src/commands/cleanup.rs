@@ -0,0 +1,363 @@
+//! Suggestions for trimming the inventory: unused items, duplicate skills, empty CLAUDE.md sections
+//! and MCP servers that can't start. Each names the tokens it would save and an action id for
+//! `apply_cleanup_action`.
+
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tauri::State;
+
+use super::bulk::{apply_operations, BulkAction, BulkOperation, MCP_PREFIX};
+use super::character::write_claude_file;
+use super::equipment::calculate_context_stats;
+use super::inventory::{cached_or_scan, memory_cached_scan, InventoryCacheState};
+use super::inventory_export::{item_scope, variant_name};
+use crate::blocking::run_blocking;
+use crate::paths::check_writable;
+use crate::scanner::plugin_contents::MCP_SERVER_WEIGHT;
+use crate::scanner::prerequisites::find_binary;
+use crate::scanner::settings::read_mcp_servers;
+use crate::scanner::skills::find_skill_md;
+use crate::scanner::usage::ingest_usage;
+use crate::scanner::weight::estimate_tokens;
+use crate::types::{ContextStats, InventoryItem, ItemSource};
+
+const DEFAULT_UNUSED_DAYS: u32 = 30;
+
+/// Action id prefix for removing an empty CLAUDE.md section: `removeSection:<item id>:<line>`
+const REMOVE_SECTION: &str = "removeSection";
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CleanupSuggestion {
+    /// unused, duplicate, emptySection or failingMcp
+    pub kind: String,
+    /// Inventory item id, or `mcp_<name>` for an MCP server
+    pub item_id: String,
+    pub name: String,
+    pub reason: String,
+    pub token_savings: u32,
+    /// `<bulk action>:<item id>` (e.g. `disable:mcp_github`) or `removeSection:<item id>:<line>`
+    pub action_id: String,
+}
+
+/// A heading with nothing under it before the next heading of the same or a higher level
+#[derive(Debug, Clone, PartialEq)]
+struct EmptySection {
+    /// 1-based
+    line: usize,
+    heading: String,
+}
+
+fn heading_level(line: &str) -> Option<usize> {
+    let level = line.chars().take_while(|c| *c == '#').count();
+    (level > 0 && level <= 6 && line[level..].starts_with(' ')).then_some(level)
+}
+
+/// Headings of `content` with no text of their own, ignoring `#` lines inside code fences
+fn empty_sections(content: &str) -> Vec<EmptySection> {
+    let mut in_fence = false;
+    let mut headings = Vec::new();
+    // (line index, heading level) of every line that has content, headings included
+    let mut lines = Vec::new();
+    for (i, line) in content.lines().enumerate() {
+        let trimmed = line.trim();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_fence = !in_fence;
+        }
+        if trimmed.is_empty() {
+            continue;
+        }
+        let level = if in_fence { None } else { heading_level(line) };
+        lines.push((i, level));
+        if level.is_some() {
+            headings.push(lines.len() - 1);
+        }
+    }
+
+    headings
+        .into_iter()
+        .filter_map(|at| {
+            let (i, level) = lines[at];
+            let level = level?;
+            let empty = match lines.get(at + 1) {
+                None => true,
+                Some((_, Some(next))) => *next <= level,
+                Some((_, None)) => false,
+            };
+            empty.then(|| EmptySection { line: i + 1, heading: content.lines().nth(i).unwrap_or_default().to_string() })
+        })
+        .collect()
+}
+
+/// `content` without the heading at `line` and the blank lines after it, when it is still empty
+fn remove_section(content: &str, line: usize) -> Option<String> {
+    empty_sections(content).iter().find(|s| s.line == line)?;
+    let lines: Vec<&str> = content.lines().collect();
+    let end = (line..lines.len()).find(|&i| !lines[i].trim().is_empty()).unwrap_or(lines.len());
+    let mut kept: Vec<&str> = lines[..line - 1].to_vec();
+    kept.extend(&lines[end..]);
+    let mut result = kept.join("\n");
+    if content.ends_with('\n') {
+        result.push('\n');
+    }
+    Some(result)
+}
+
+fn modified_within(path: &str, window: Duration) -> bool {
+    fs::metadata(path)
+        .and_then(|m| m.modified())
+        .is_ok_and(|modified| modified.elapsed().is_ok_and(|age| age < window))
+}
+
+fn days_window(days: u32) -> Duration {
+    Duration::from_secs(u64::from(days) * 24 * 60 * 60)
+}
+
+/// Milliseconds since the Unix epoch at `window` ago, comparable with usage timestamps
+fn cutoff_millis(window: Duration) -> u64 {
+    SystemTime::now()
+        .checked_sub(window)
+        .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+        .map_or(0, |d| d.as_millis() as u64)
+}
+
+fn bulk_action_id(action: BulkAction, item_id: &str) -> String {
+    format!("{}:{}", variant_name(&action), item_id)
+}
+
+pub(crate) fn deletable(item: &InventoryItem) -> bool {
+    matches!(item.source, ItemSource::Command | ItemSource::Subagent | ItemSource::Skill)
+        && matches!(item_scope(item).as_str(), "user" | "project")
+}
+
+/// Order copies of the same item best-to-keep first: ones that can't be deleted (plugin copies),
+/// then the most used
+pub(crate) fn keep_order(a: &InventoryItem, b: &InventoryItem) -> Ordering {
+    let runs = |item: &InventoryItem| item.status.as_ref().and_then(|s| s.run_count).unwrap_or(0);
+    deletable(a).cmp(&deletable(b)).then(runs(b).cmp(&runs(a))).then(a.id.cmp(&b.id))
+}
+
+fn suggestion(kind: &str, item: &InventoryItem, reason: String, token_savings: u32, action_id: String) -> CleanupSuggestion {
+    CleanupSuggestion {
+        kind: kind.to_string(),
+        item_id: item.id.clone(),
+        name: item.name.clone(),
+        reason,
+        token_savings,
+        action_id,
+    }
+}
+
+/// Skills with the same name or the same SKILL.md; the most used copy (or one that can't be
+/// deleted) is kept and the others suggested for deletion
+fn duplicate_skills(items: &[InventoryItem]) -> Vec<CleanupSuggestion> {
+    let skills: Vec<&InventoryItem> = items.iter().filter(|i| matches!(i.source, ItemSource::Skill)).collect();
+    let content_hash = |item: &InventoryItem| {
+        let file = find_skill_md(Path::new(&item.source_path))?;
+        fs::read(file).ok().map(|content| format!("{:x}", Sha256::digest(content)))
+    };
+
+    let mut groups: HashMap<String, Vec<&InventoryItem>> = HashMap::new();
+    for skill in &skills {
+        groups.entry(format!("name:{}", skill.name.to_lowercase())).or_default().push(skill);
+        if let Some(hash) = content_hash(skill) {
+            groups.entry(format!("content:{}", hash)).or_default().push(skill);
+        }
+    }
+
+    let mut suggestions = Vec::new();
+    let mut seen = HashSet::new();
+    let mut keys: Vec<&String> = groups.keys().collect();
+    keys.sort();
+    for key in keys {
+        let mut group = groups[key].clone();
+        if group.len() < 2 {
+            continue;
+        }
+        group.sort_by(|a, b| keep_order(a, b));
+        let kept = group[0];
+        for duplicate in &group[1..] {
+            if deletable(duplicate) && duplicate.id != kept.id && seen.insert(duplicate.id.clone()) {
+                let same = if key.starts_with("content:") { "the same content as" } else { "the same name as" };
+                suggestions.push(suggestion(
+                    "duplicate",
+                    duplicate,
+                    format!("Has {} {} ({})", same, kept.name, item_scope(kept)),
+                    duplicate.token_weight,
+                    bulk_action_id(BulkAction::Delete, &duplicate.id),
+                ));
+            }
+        }
+    }
+    suggestions
+}
+
+fn unused_items(items: &[InventoryItem], days: u32) -> Vec<CleanupSuggestion> {
+    let window = days_window(days);
+    let cutoff_ms = cutoff_millis(window);
+
+    items
+        .iter()
+        .filter(|item| item.enabled && item.token_weight > 0)
+        .filter_map(|item| {
+            let status = item.status.as_ref()?;
+            // Items without tracked usage (hooks, CLAUDE.md, ...) have no run count
+            status.run_count?;
+            if status.last_used.is_some_and(|t| t >= cutoff_ms) || modified_within(&item.source_path, window) {
+                return None;
+            }
+            let action = match item.source {
+                ItemSource::Plugin => BulkAction::Unequip,
+                _ if deletable(item) => BulkAction::Delete,
+                _ => return None,
+            };
+            let reason = match status.last_used {
+                Some(_) => format!("Not used in the last {} days", days),
+                None => "Never used in a recorded session".to_string(),
+            };
+            Some(suggestion("unused", item, reason, item.token_weight, bulk_action_id(action, &item.id)))
+        })
+        .collect()
+}
+
+/// MCP servers whose command isn't installed (so they fail every session) or that go unused
+fn mcp_suggestions(days: u32) -> Vec<CleanupSuggestion> {
+    let usage = ingest_usage();
+    let cutoff_ms = cutoff_millis(days_window(days));
+
+    // Disabled servers are parked outside settings.json, so every one left here starts
+    let mut servers: Vec<_> = read_mcp_servers().unwrap_or_default().into_iter().collect();
+    servers.sort_by(|a, b| a.0.cmp(&b.0));
+    servers
+        .into_iter()
+        .filter_map(|(name, config)| {
+            let id = format!("{}{}", MCP_PREFIX, name);
+            let (kind, reason) = if find_binary(&config.command).is_none() {
+                ("failingMcp", format!("`{}` is not installed, so the server fails to start every session", config.command))
+            } else if usage.get(&format!("mcp:{}", name)).and_then(|u| u.last_used).is_none_or(|t| t < cutoff_ms) {
+                ("unused", format!("No tools used in the last {} days", days))
+            } else {
+                return None;
+            };
+            Some(CleanupSuggestion {
+                kind: kind.to_string(),
+                action_id: bulk_action_id(BulkAction::Disable, &id),
+                item_id: id,
+                name,
+                reason,
+                token_savings: MCP_SERVER_WEIGHT,
+            })
+        })
+        .collect()
+}
+
+fn claude_md_suggestions(items: &[InventoryItem]) -> Vec<CleanupSuggestion> {
+    let mut suggestions = Vec::new();
+    for item in items.iter().filter(|i| matches!(i.source, ItemSource::ClaudeMd)) {
+        let Ok(content) = fs::read_to_string(&item.source_path) else {
+            continue;
+        };
+        for section in empty_sections(&content) {
+            suggestions.push(suggestion(
+                "emptySection",
+                item,
+                format!("\"{}\" on line {} has nothing under it", section.heading.trim(), section.line),
+                estimate_tokens(&section.heading),
+                format!("{}:{}:{}", REMOVE_SECTION, item.id, section.line),
+            ));
+        }
+    }
+    suggestions
+}
+
+/// Items worth removing, biggest savings first. `unused_days` (default 30) is how long without use
+/// counts as unused; items edited within that time are left alone.
+#[tauri::command]
+pub async fn get_cleanup_suggestions(
+    state: State<'_, InventoryCacheState>,
+    project_path: Option<String>,
+    unused_days: Option<u32>,
+) -> Result<Vec<CleanupSuggestion>, String> {
+    let cached = memory_cached_scan(&state, &project_path)?;
+    run_blocking(move || {
+        let items = cached_or_scan(cached, project_path.as_deref()).items;
+        let days = unused_days.unwrap_or(DEFAULT_UNUSED_DAYS).max(1);
+
+        // An item can qualify twice (an unused duplicate); the first reason wins
+        let mut seen = HashSet::new();
+        let mut suggestions: Vec<CleanupSuggestion> = mcp_suggestions(days)
+            .into_iter()
+            .chain(duplicate_skills(&items))
+            .chain(unused_items(&items, days))
+            .chain(claude_md_suggestions(&items))
+            .filter(|s| seen.insert(s.action_id.clone()))
+            .collect();
+        suggestions.sort_by(|a, b| b.token_savings.cmp(&a.token_savings).then(a.item_id.cmp(&b.item_id)));
+        suggestions
+    })
+    .await
+}
+
+/// Carry out a suggestion by its action id; returns the context load afterwards
+#[tauri::command]
+#[tracing::instrument(skip(state), err)]
+pub async fn apply_cleanup_action(
+    state: State<'_, InventoryCacheState>,
+    action_id: String,
+    project_path: Option<String>,
+) -> Result<ContextStats, String> {
+    let cached = memory_cached_scan(&state, &project_path)?;
+    run_blocking(move || {
+        let items = cached_or_scan(cached, project_path.as_deref()).items;
+        let (action, target) = action_id.split_once(':').ok_or_else(|| format!("Unknown cleanup action: {}", action_id))?;
+
+        if action == REMOVE_SECTION {
+            let (item_id, line) = target.rsplit_once(':').ok_or_else(|| format!("Unknown cleanup action: {}", action_id))?;
+            let line: usize = line.parse().map_err(|_| format!("Unknown cleanup action: {}", action_id))?;
+            let item = items
+                .iter()
+                .find(|i| i.id == item_id && matches!(i.source, ItemSource::ClaudeMd))
+                .ok_or_else(|| format!("Item not found: {}", item_id))?;
+            let path = Path::new(&item.source_path);
+            check_writable(path, project_path.as_deref()).map_err(|e| e.to_string())?;
+            let content = fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+            let updated = remove_section(&content, line)
+                .ok_or_else(|| format!("Line {} of {} is no longer an empty section", line, path.display()))?;
+            write_claude_file(path, &updated)?;
+            return Ok(calculate_context_stats());
+        }
+
+        let action: BulkAction = serde_json::from_value(serde_json::Value::String(action.to_string()))
+            .map_err(|_| format!("Unknown cleanup action: {}", action_id))?;
+        let operation = BulkOperation { action, item_id: target.to_string() };
+        let result = apply_operations(&items, vec![operation], project_path.as_deref(), false);
+        match result.results.into_iter().find_map(|r| r.error) {
+            Some(error) => Err(error),
+            None => Ok(result.context_stats),
+        }
+    })
+    .await?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_and_removes_empty_sections() {
+        let content = "# Project\n\n## Style\n\n## Testing\nRun cargo test.\n\n```sh\n# not a heading\n```\n## Notes\n";
+        let empty = empty_sections(content);
+        assert_eq!(empty.iter().map(|s| (s.line, s.heading.as_str())).collect::<Vec<_>>(), vec![(3, "## Style"), (11, "## Notes")]);
+
+        assert_eq!(
+            remove_section(content, 3).unwrap(),
+            "# Project\n\n## Testing\nRun cargo test.\n\n```sh\n# not a heading\n```\n## Notes\n"
+        );
+        assert!(remove_section(content, 5).is_none());
+    }
+}
@@ -0,0 +1,137 @@
+//! Installed Claude Code version, and whether a newer release is out
+//! Many "feature X doesn't work" reports come down to a stale CLI, so the UI surfaces it.
+
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager, State};
+
+use crate::arcade_settings::load_arcade_settings;
+use crate::blocking::run_blocking;
+use crate::error::ArcadeError;
+use crate::http::get_json_cached;
+use crate::process::ProcessRequest;
+use crate::scanner::prerequisites::find_binary;
+
+/// Latest published version of the CLI, used unless arcade settings name another endpoint
+const DEFAULT_RELEASE_URL: &str = "https://registry.npmjs.org/@anthropic-ai/claude-code/latest";
+
+/// Installed and latest Claude Code versions
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClaudeVersionInfo {
+    /// None when the CLI isn't installed or didn't report a version
+    pub installed: Option<String>,
+    pub binary_path: Option<String>,
+    /// None when the release endpoint couldn't be reached
+    pub latest: Option<String>,
+    pub update_available: bool,
+    /// RFC3339
+    pub checked_at: String,
+    pub error: Option<String>,
+}
+
+/// The last check, so the version is only looked up once per run unless refreshed
+pub struct ClaudeVersionState(pub Mutex<Option<ClaudeVersionInfo>>);
+
+/// The version number from `claude --version` output, e.g. "1.0.83 (Claude Code)"
+pub(crate) fn parse_claude_version(output: &str) -> Option<String> {
+    output
+        .split_whitespace()
+        .find(|word| word.split('.').count() >= 2 && word.split('.').all(|part| part.parse::<u32>().is_ok()))
+        .map(String::from)
+}
+
+/// Run `claude --version` with the binary at `binary`
+pub(crate) fn claude_binary_version(binary: &Path) -> Option<String> {
+    let output = ProcessRequest::new(binary).arg("--version").timeout(Duration::from_secs(10)).run().ok()?;
+    parse_claude_version(&output.stdout_text())
+}
+
+/// Whether `latest` is a higher dotted version than `installed`
+fn is_newer(latest: &str, installed: &str) -> bool {
+    let parts = |v: &str| -> Vec<u32> { v.split('.').map(|p| p.parse().unwrap_or(0)).collect() };
+    parts(latest) > parts(installed)
+}
+
+/// The latest version, served from the last successful check when the registry can't be reached
+async fn latest_release() -> Result<String, ArcadeError> {
+    #[derive(Deserialize)]
+    struct Release {
+        version: String,
+    }
+
+    let url = load_arcade_settings().claude_release_url.unwrap_or_else(|| DEFAULT_RELEASE_URL.to_string());
+    let (release, _) = get_json_cached::<Release>(&url).await?;
+    Ok(release.version)
+}
+
+/// The installed Claude Code version and the latest release. Cached after the first call;
+/// `refresh` checks again. Emits `claude-update-available` when the CLI is behind.
+#[tauri::command]
+pub async fn get_claude_version(
+    app_handle: AppHandle,
+    state: State<'_, ClaudeVersionState>,
+    refresh: Option<bool>,
+) -> Result<ClaudeVersionInfo, ArcadeError> {
+    if !refresh.unwrap_or(false) {
+        let cached = state.0.lock().map_err(|e| format!("Lock error: {}", e))?.clone();
+        if let Some(cached) = cached {
+            return Ok(cached);
+        }
+    }
+    refresh_claude_version(&app_handle).await
+}
+
+/// Look up the installed and latest versions again and cache them, emitting
+/// `claude-update-available` when the CLI is behind
+pub(crate) async fn refresh_claude_version(app_handle: &AppHandle) -> Result<ClaudeVersionInfo, ArcadeError> {
+    let (installed, binary_path) = run_blocking(|| {
+        let binary = find_binary("claude");
+        let version = binary.as_deref().and_then(claude_binary_version);
+        (version, binary.map(|b| b.to_string_lossy().to_string()))
+    })
+    .await?;
+
+    let (latest, error) = match latest_release().await {
+        Ok(latest) => (Some(latest), None),
+        Err(e) => {
+            tracing::warn!("Failed to look up the latest Claude Code release: {}", e);
+            (None, Some(e.to_string()))
+        }
+    };
+
+    let update_available = matches!((&latest, &installed), (Some(latest), Some(installed)) if is_newer(latest, installed));
+    let info = ClaudeVersionInfo {
+        installed,
+        binary_path,
+        latest,
+        update_available,
+        checked_at: chrono::Utc::now().to_rfc3339(),
+        error,
+    };
+
+    let state = app_handle.state::<ClaudeVersionState>();
+    *state.0.lock().map_err(|e| format!("Lock error: {}", e))? = Some(info.clone());
+    if update_available {
+        let _ = app_handle.emit("claude-update-available", &info);
+    }
+    Ok(info)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_and_compares_versions() {
+        assert_eq!(parse_claude_version("1.0.83 (Claude Code)\n").as_deref(), Some("1.0.83"));
+        assert_eq!(parse_claude_version("claude 2.1.0").as_deref(), Some("2.1.0"));
+        assert_eq!(parse_claude_version("command not found"), None);
+
+        assert!(is_newer("1.0.100", "1.0.83"));
+        assert!(!is_newer("1.0.83", "1.0.83"));
+        assert!(!is_newer("0.9.0", "1.0.0"));
+    }
+}
@@ -0,0 +1,106 @@
+//! What Companions (subagents) did: every Task call handed to them, with its outcome and cost
+//! Subagents run in their own context window, so their tokens never count against the main session.
+
+use serde::{Deserialize, Serialize};
+
+use super::sessions::title_from;
+use crate::scanner::transcripts::{
+    content_blocks, entry_timestamp, entry_type, list_transcript_files, read_transcript_entries,
+    structured_tool_results, task_outcome, tool_results, TaskOutcome,
+};
+use crate::scanner::usage::is_task_tool;
+use crate::blocking::run_blocking;
+
+/// One task handed to a companion
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CompanionTask {
+    pub session_id: String,
+    pub tool_use_id: String,
+    pub timestamp: Option<String>,
+    /// Short description Claude gave the task
+    pub description: Option<String>,
+    /// First line of the prompt, shortened
+    pub prompt: Option<String>,
+    pub outcome: TaskOutcome,
+}
+
+/// Everything a companion has done across sessions
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CompanionActivity {
+    pub agent_id: String,
+    /// subagent_type Claude uses to call it
+    pub agent_name: String,
+    /// Newest first
+    pub tasks: Vec<CompanionTask>,
+    pub tasks_completed: u32,
+    pub tasks_failed: u32,
+    /// Tokens used in the companion's isolated context, over all completed tasks
+    pub isolated_tokens: u64,
+    pub average_task_tokens: Option<u64>,
+    pub last_used: Option<String>,
+}
+
+/// subagent_type for an inventory id like "subagent_user_code-reviewer"
+fn agent_name(agent_id: &str) -> &str {
+    agent_id
+        .strip_prefix("subagent_user_")
+        .or_else(|| agent_id.strip_prefix("subagent_project_"))
+        .unwrap_or(agent_id)
+}
+
+/// Task calls to a companion across all session transcripts, with outcomes and isolated-context tokens.
+/// `agent_id` is the inventory id or the plain subagent_type.
+#[tauri::command]
+pub async fn get_companion_activity(agent_id: String) -> Result<CompanionActivity, String> {
+    run_blocking(move || {
+        let name = agent_name(&agent_id).to_string();
+        let mut tasks = Vec::new();
+
+        for path in list_transcript_files(None) {
+            let entries = read_transcript_entries(&path);
+            let session_id = path.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default();
+            let mut results = None;
+
+            for entry in entries.iter().filter(|e| entry_type(e) == "assistant") {
+                for block in content_blocks(entry) {
+                    let tool = block.get("name").and_then(|n| n.as_str()).unwrap_or_default();
+                    let input = block.get("input");
+                    let field = |key: &str| input.and_then(|i| i.get(key)).and_then(|v| v.as_str());
+                    if !is_task_tool(tool) || field("subagent_type") != Some(name.as_str()) {
+                        continue;
+                    }
+
+                    // Results are only gathered for transcripts that used this companion
+                    let (plain, structured) = results.get_or_insert_with(|| (tool_results(&entries), structured_tool_results(&entries)));
+                    let id = block.get("id").and_then(|v| v.as_str()).unwrap_or_default().to_string();
+                    tasks.push(CompanionTask {
+                        session_id: session_id.clone(),
+                        timestamp: entry_timestamp(entry).map(|t| t.to_rfc3339()),
+                        description: field("description").map(String::from),
+                        prompt: field("prompt").map(title_from),
+                        outcome: task_outcome(plain.get(&id), structured.get(&id)),
+                        tool_use_id: id,
+                    });
+                }
+            }
+        }
+
+        tasks.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
+        let completed: Vec<&CompanionTask> = tasks.iter().filter(|t| t.outcome.status == "completed").collect();
+        let isolated_tokens: u64 = completed.iter().map(|t| t.outcome.total_tokens).sum();
+
+        Ok(CompanionActivity {
+            agent_id,
+            agent_name: name,
+            tasks_completed: completed.len() as u32,
+            tasks_failed: tasks.iter().filter(|t| t.outcome.status == "error").count() as u32,
+            isolated_tokens,
+            average_task_tokens: (!completed.is_empty()).then(|| isolated_tokens / completed.len() as u64),
+            last_used: tasks.first().and_then(|t| t.timestamp.clone()),
+            tasks,
+        })
+    })
+    .await?
+}
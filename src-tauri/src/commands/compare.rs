@@ -0,0 +1,174 @@
+//! Side-by-side comparison of two inventory items, for choosing which of two similar ones to equip
+//! Items come from the cached scan; tools and content are read from their files.
+
+use serde::Serialize;
+use std::path::Path;
+use tauri::State;
+
+use super::inventory::{cached_or_scan, memory_cached_scan, InventoryCacheState};
+use super::inventory_export::{item_scope, variant_name};
+use crate::blocking::run_blocking;
+use crate::error::ArcadeError;
+use crate::scanner::limits::read_capped;
+use crate::scanner::plugin_contents::frontmatter_tools;
+use crate::scanner::skills::find_skill_md;
+use crate::types::{InventoryItem, ItemSource};
+
+/// Longer files are compared without a line diff; the table grows with the product of their lengths
+const MAX_DIFF_LINES: usize = 2000;
+
+/// One side of a comparison
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ComparedItem {
+    pub item: InventoryItem,
+    /// user, project, plugin or builtin
+    pub scope: String,
+    /// `allowed-tools` (commands, skills) or `tools` (agents); empty when unrestricted
+    pub allowed_tools: Vec<String>,
+    pub run_count: u32,
+    /// Milliseconds since the Unix epoch
+    pub last_used: Option<u64>,
+}
+
+/// A line of the content diff
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiffLine {
+    /// "same", "removed" (only in the first item) or "added" (only in the second)
+    pub kind: &'static str,
+    pub text: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ItemComparison {
+    pub a: ComparedItem,
+    pub b: ComparedItem,
+    pub same_type: bool,
+    /// Token weight of b minus a
+    pub token_difference: i64,
+    pub shared_tools: Vec<String>,
+    pub only_a_tools: Vec<String>,
+    pub only_b_tools: Vec<String>,
+    /// Line diff of the two files, for commands, agents, skills and CLAUDE.md files of the same type
+    pub content_diff: Option<Vec<DiffLine>>,
+}
+
+/// The markdown file an item is defined in, when it has one of its own
+fn content_file(item: &InventoryItem) -> Option<String> {
+    if !matches!(item.source, ItemSource::Command | ItemSource::Subagent | ItemSource::Skill | ItemSource::ClaudeMd) {
+        return None;
+    }
+    let path = Path::new(&item.source_path);
+    let file = if path.is_dir() { find_skill_md(path)? } else { path.to_path_buf() };
+    read_capped(&file).ok()
+}
+
+fn compared(item: InventoryItem, content: Option<&str>) -> ComparedItem {
+    let status = item.status.clone().unwrap_or_default();
+    ComparedItem {
+        scope: item_scope(&item),
+        allowed_tools: content.map(frontmatter_tools).unwrap_or_default(),
+        run_count: status.run_count.unwrap_or(0),
+        last_used: status.last_used,
+        item,
+    }
+}
+
+/// Longest-common-subsequence line diff; None when either side is too long
+fn line_diff(a: &str, b: &str) -> Option<Vec<DiffLine>> {
+    let a: Vec<&str> = a.lines().collect();
+    let b: Vec<&str> = b.lines().collect();
+    if a.len() > MAX_DIFF_LINES || b.len() > MAX_DIFF_LINES {
+        return None;
+    }
+
+    // common[i][j]: length of the common subsequence of a[i..] and b[j..]
+    let mut common = vec![vec![0u32; b.len() + 1]; a.len() + 1];
+    for i in (0..a.len()).rev() {
+        for j in (0..b.len()).rev() {
+            common[i][j] = if a[i] == b[j] { common[i + 1][j + 1] + 1 } else { common[i + 1][j].max(common[i][j + 1]) };
+        }
+    }
+
+    let line = |kind, text: &str| DiffLine { kind, text: text.to_string() };
+    let (mut i, mut j) = (0, 0);
+    let mut diff = Vec::new();
+    while i < a.len() || j < b.len() {
+        if i < a.len() && j < b.len() && a[i] == b[j] {
+            diff.push(line("same", a[i]));
+            i += 1;
+            j += 1;
+        } else if i < a.len() && (j == b.len() || common[i + 1][j] >= common[i][j + 1]) {
+            diff.push(line("removed", a[i]));
+            i += 1;
+        } else {
+            diff.push(line("added", b[j]));
+            j += 1;
+        }
+    }
+    Some(diff)
+}
+
+fn compare(a: InventoryItem, b: InventoryItem) -> ItemComparison {
+    let same_type = variant_name(&a.source) == variant_name(&b.source) && variant_name(&a.item_type) == variant_name(&b.item_type);
+    let content_a = content_file(&a);
+    let content_b = content_file(&b);
+    let content_diff = match (&content_a, &content_b) {
+        (Some(x), Some(y)) if same_type => line_diff(x, y),
+        _ => None,
+    };
+
+    let a = compared(a, content_a.as_deref());
+    let b = compared(b, content_b.as_deref());
+    let split = |from: &[String], other: &[String]| -> Vec<String> { from.iter().filter(|t| !other.contains(t)).cloned().collect() };
+    ItemComparison {
+        same_type,
+        token_difference: i64::from(b.item.token_weight) - i64::from(a.item.token_weight),
+        shared_tools: a.allowed_tools.iter().filter(|t| b.allowed_tools.contains(t)).cloned().collect(),
+        only_a_tools: split(&a.allowed_tools, &b.allowed_tools),
+        only_b_tools: split(&b.allowed_tools, &a.allowed_tools),
+        content_diff,
+        a,
+        b,
+    }
+}
+
+/// Compare two inventory items by id: weight, tools, scope, rarity, usage and, for two items of
+/// the same type, their content line by line
+#[tauri::command]
+pub async fn compare_items(
+    state: State<'_, InventoryCacheState>,
+    id_a: String,
+    id_b: String,
+    project_path: Option<String>,
+) -> Result<ItemComparison, ArcadeError> {
+    let cached = memory_cached_scan(&state, &project_path)?;
+    run_blocking(move || {
+        let mut items = cached_or_scan(cached, project_path.as_deref()).items;
+        let mut take = |id: &str| {
+            let index = items.iter().position(|i| i.id == id).ok_or_else(|| ArcadeError::not_found("Item", id))?;
+            Ok::<_, ArcadeError>(items.swap_remove(index))
+        };
+        let a = take(&id_a)?;
+        // Comparing an item with itself is allowed, if not very useful
+        let b = if id_a == id_b { a.clone() } else { take(&id_b)? };
+        Ok(compare(a, b))
+    })
+    .await?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn diffs_lines() {
+        let kinds = |diff: Vec<DiffLine>| diff.into_iter().map(|l| format!("{} {}", l.kind, l.text)).collect::<Vec<_>>();
+        assert_eq!(
+            kinds(line_diff("intro\nreview the diff\nbe brief", "intro\nreview the whole file\nbe brief\nsign off").unwrap()),
+            vec!["same intro", "removed review the diff", "added review the whole file", "same be brief", "added sign off"]
+        );
+    }
+}
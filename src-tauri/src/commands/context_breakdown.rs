@@ -0,0 +1,110 @@
+//! Where the context budget goes: equipped token weight by slot, source and scope
+//! Feeds the treemap view; everything enabled in the scan counts, not only plugins.
+
+use serde::Serialize;
+use std::collections::BTreeMap;
+use tauri::State;
+
+use super::equipment::context_stats_for_tokens;
+use super::inventory::{cached_or_scan, memory_cached_scan, InventoryCacheState};
+use super::inventory_export::{item_scope, variant_name};
+use crate::blocking::run_blocking;
+use crate::types::{ContextStats, InventoryItem};
+
+const HEAVIEST_COUNT: usize = 10;
+
+/// Equipped items sharing a slot, source or scope
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WeightGroup {
+    pub key: String,
+    pub tokens: u32,
+    pub item_count: usize,
+    /// Fraction of the context budget
+    pub share: f64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContextBreakdown {
+    pub stats: ContextStats,
+    /// By item type (helm, ring, spell, ...)
+    pub by_slot: Vec<WeightGroup>,
+    /// By where the item comes from (plugin, skill, command, ...)
+    pub by_source: Vec<WeightGroup>,
+    /// user, project, plugin or builtin
+    pub by_scope: Vec<WeightGroup>,
+    /// Heaviest equipped items, heaviest first
+    pub heaviest: Vec<InventoryItem>,
+}
+
+/// Groups by `key`, heaviest first
+fn group_by(items: &[&InventoryItem], budget: u32, key: impl Fn(&InventoryItem) -> String) -> Vec<WeightGroup> {
+    let mut groups: BTreeMap<String, (u32, usize)> = BTreeMap::new();
+    for item in items {
+        let group = groups.entry(key(item)).or_default();
+        group.0 += item.token_weight;
+        group.1 += 1;
+    }
+    let mut groups: Vec<WeightGroup> = groups
+        .into_iter()
+        .map(|(key, (tokens, item_count))| WeightGroup { key, tokens, item_count, share: tokens as f64 / budget.max(1) as f64 })
+        .collect();
+    groups.sort_by_key(|g| std::cmp::Reverse(g.tokens));
+    groups
+}
+
+fn breakdown(items: Vec<InventoryItem>) -> ContextBreakdown {
+    let equipped: Vec<&InventoryItem> = items.iter().filter(|i| i.enabled && i.token_weight > 0).collect();
+    let stats = context_stats_for_tokens(equipped.iter().map(|i| i.token_weight).sum());
+    let budget = stats.total_budget;
+
+    let mut heaviest = equipped.clone();
+    heaviest.sort_by(|a, b| b.token_weight.cmp(&a.token_weight).then(a.name.cmp(&b.name)));
+    ContextBreakdown {
+        by_slot: group_by(&equipped, budget, |i| variant_name(&i.item_type)),
+        by_source: group_by(&equipped, budget, |i| variant_name(&i.source)),
+        by_scope: group_by(&equipped, budget, item_scope),
+        heaviest: heaviest.into_iter().take(HEAVIEST_COUNT).cloned().collect(),
+        stats,
+    }
+}
+
+/// Equipped token weight for a project grouped by slot type, source and scope, with the ten
+/// heaviest items
+#[tauri::command]
+pub async fn get_context_breakdown(
+    state: State<'_, InventoryCacheState>,
+    project_path: Option<String>,
+) -> Result<ContextBreakdown, String> {
+    let cached = memory_cached_scan(&state, &project_path)?;
+    run_blocking(move || breakdown(cached_or_scan(cached, project_path.as_deref()).items)).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{test_item, ItemSource, ItemType};
+
+    fn item(id: &str, item_type: ItemType, source: ItemSource, token_weight: u32) -> InventoryItem {
+        InventoryItem { item_type, source, token_weight, ..test_item(id) }
+    }
+
+    #[test]
+    fn groups_equipped_weight() {
+        let items = [
+            item("skill_user_pdf", ItemType::Spell, ItemSource::Skill, 100),
+            item("skill_project_lint", ItemType::Spell, ItemSource::Skill, 300),
+            item("cmd_user_review", ItemType::Ring, ItemSource::Command, 50),
+        ];
+        let equipped: Vec<&InventoryItem> = items.iter().collect();
+
+        let by_scope = group_by(&equipped, 1000, item_scope);
+        assert_eq!(by_scope.iter().map(|g| (g.key.as_str(), g.tokens, g.item_count)).collect::<Vec<_>>(), vec![
+            ("project", 300, 1),
+            ("user", 150, 2),
+        ]);
+        assert_eq!(by_scope[0].share, 0.3);
+        assert_eq!(group_by(&equipped, 1000, |i| variant_name(&i.item_type))[0].key, "spell");
+    }
+}
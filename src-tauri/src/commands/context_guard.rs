@@ -0,0 +1,218 @@
+//! Context guard: hooks that warn, or stop Claude, when a session's context is nearly full
+//! The app keeps each watched session's context use in arcade_context/<session id> (a whole
+//! percent) as transcripts and status line events come in; the hook script only reads that file,
+//! so a prompt never waits on the app. Sessions the app hasn't seen are let through.
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use super::arcade_hooks::with_script_hooks;
+use crate::arcade_settings::{load_arcade_settings, save_arcade_settings};
+use crate::blocking::run_blocking;
+use crate::error::ArcadeError;
+use crate::scanner::settings::{read_settings_full, update_settings_field};
+use crate::scanner::transcripts::is_valid_session_id;
+
+const SCRIPT_NAME: &str = "arcade_context_guard.sh";
+
+/// Estimates older than this belong to sessions long gone
+const STALE_ESTIMATE: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// When the guard steps in, in percent of the context window
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ContextGuardSettings {
+    /// Prompts sent above this get a warning added for Claude
+    pub warn_percent: u32,
+    /// Prompts and tool calls above this are refused; 0 never refuses
+    pub block_percent: u32,
+}
+
+impl Default for ContextGuardSettings {
+    fn default() -> Self {
+        // Where the dumbzone starts
+        ContextGuardSettings { warn_percent: 50, block_percent: 0 }
+    }
+}
+
+/// Result of `install_context_guard`
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContextGuardInstall {
+    pub script_path: String,
+    pub events: Vec<String>,
+    pub settings: ContextGuardSettings,
+}
+
+fn script_path() -> Option<PathBuf> {
+    crate::paths::claude_dir().map(|d| d.join(SCRIPT_NAME))
+}
+
+fn estimates_dir() -> Option<PathBuf> {
+    crate::paths::claude_dir().map(|d| d.join("arcade_context"))
+}
+
+fn script(estimates_dir: &Path) -> String {
+    format!(
+        "#!/bin/sh\n# Installed by ClaudeArcade; warns or stops Claude when the session's context is nearly full.\n\
+         # $1 is prompt or tool, $2 the percent to warn at and $3 the percent to block at (0 never blocks).\n\
+         payload=$(tr -d '\\n')\n\
+         id=$(printf '%s' \"$payload\" | sed -n 's/.*\"session_id\" *: *\"\\([A-Za-z0-9_-]*\\)\".*/\\1/p')\n\
+         [ -n \"$id\" ] || exit 0\n\
+         percent=$(cat \"{dir}/$id\" 2>/dev/null)\n\
+         [ -n \"$percent\" ] || exit 0\n\
+         # Slash commands such as /compact and /clear are how to get out, so they always go through\n\
+         printf '%s' \"$payload\" | grep -q '\"prompt\" *: *\"/' && exit 0\n\
+         if [ \"$3\" -gt 0 ] && [ \"$percent\" -ge \"$3\" ]; then\n\
+         \x20 echo \"ClaudeArcade context guard: the context is $percent% full (limit $3%). Run /compact or /clear first.\" >&2\n\
+         \x20 exit 2\n\
+         fi\n\
+         if [ \"$1\" = prompt ] && [ \"$percent\" -ge \"$2\" ]; then\n\
+         \x20 echo \"Note from ClaudeArcade: this session's context is $percent% full. Answers get less reliable from here; keep replies focused, and suggest /compact if the task is far from done.\"\n\
+         fi\n\
+         exit 0\n",
+        dir = estimates_dir.to_string_lossy().replace('\\', "/")
+    )
+}
+
+fn hook_commands(script_path: &Path, settings: &ContextGuardSettings) -> Vec<(&'static str, String)> {
+    let command = format!("sh \"{}\"", script_path.to_string_lossy().replace('\\', "/"));
+    [("UserPromptSubmit", "prompt"), ("PreToolUse", "tool")]
+        .into_iter()
+        .map(|(event, arg)| (event, format!("{} {} {} {}", command, arg, settings.warn_percent, settings.block_percent)))
+        .collect()
+}
+
+/// Session ids become file names, so anything but the characters they're made of is refused
+fn estimate_path(session_id: &str) -> Option<PathBuf> {
+    estimates_dir().filter(|_| is_valid_session_id(session_id)).map(|d| d.join(session_id))
+}
+
+fn guard_installed() -> bool {
+    script_path().is_some_and(|p| p.exists())
+}
+
+fn remove_stale_estimates(dir: &Path) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    let cutoff = SystemTime::now() - STALE_ESTIMATE;
+    for entry in entries.flatten() {
+        if entry.metadata().and_then(|m| m.modified()).is_ok_and(|t| t < cutoff) {
+            let _ = fs::remove_file(entry.path());
+        }
+    }
+}
+
+/// Keep a session's context use (0.0-1.0 of the window) for the guard. Does nothing unless the
+/// guard is installed.
+pub(crate) fn record_context_use(session_id: &str, load_percentage: f64) {
+    let Some(path) = estimate_path(session_id).filter(|_| guard_installed()) else {
+        return;
+    };
+    if !path.exists() {
+        if let Some(dir) = path.parent() {
+            let _ = fs::create_dir_all(dir);
+            remove_stale_estimates(dir);
+        }
+    }
+    let percent = (load_percentage * 100.0).round().max(0.0) as u32;
+    if let Err(e) = crate::paths::atomic_write(&path, percent.to_string()) {
+        tracing::warn!("Failed to record context use for {}: {}", session_id, e);
+    }
+}
+
+/// Forget a session's context use once it has been compacted
+pub(crate) fn clear_context_use(session_id: &str) {
+    if let Some(path) = estimate_path(session_id) {
+        let _ = fs::remove_file(path);
+    }
+}
+
+fn check_thresholds(settings: &ContextGuardSettings) -> Result<(), ArcadeError> {
+    if !(1..=100).contains(&settings.warn_percent) || settings.block_percent > 100 {
+        return Err(ArcadeError::invalid_input("Thresholds are percentages from 1 to 100"));
+    }
+    if settings.block_percent > 0 && settings.block_percent < settings.warn_percent {
+        return Err(ArcadeError::invalid_input("The block threshold can't be below the warning threshold"));
+    }
+    Ok(())
+}
+
+/// Write the context guard script and add UserPromptSubmit and PreToolUse hooks running it to
+/// settings.json. Thresholds not given keep their saved values; running it again applies new ones.
+#[tauri::command]
+#[tracing::instrument(err)]
+pub async fn install_context_guard(
+    warn_percent: Option<u32>,
+    block_percent: Option<u32>,
+) -> Result<ContextGuardInstall, ArcadeError> {
+    run_blocking(move || {
+        let (Some(script_path), Some(estimates_dir)) = (script_path(), estimates_dir()) else {
+            return Err(ArcadeError::Other { message: "Could not find home directory".to_string() });
+        };
+        let mut arcade_settings = load_arcade_settings();
+        let settings = ContextGuardSettings {
+            warn_percent: warn_percent.unwrap_or(arcade_settings.context_guard.warn_percent),
+            block_percent: block_percent.unwrap_or(arcade_settings.context_guard.block_percent),
+        };
+        check_thresholds(&settings)?;
+
+        let content = script(&estimates_dir);
+        crate::watcher::write_expected(&script_path, content).map_err(|e| ArcadeError::io(&script_path, e))?;
+        fs::create_dir_all(&estimates_dir).map_err(|e| ArcadeError::io(&estimates_dir, e))?;
+
+        let commands = hook_commands(&script_path, &settings);
+        update_settings_field("hooks", with_script_hooks(read_settings_full()?.hooks, SCRIPT_NAME, &commands))?;
+
+        arcade_settings.context_guard = settings.clone();
+        save_arcade_settings(&arcade_settings)?;
+        Ok(ContextGuardInstall {
+            script_path: script_path.to_string_lossy().to_string(),
+            events: commands.iter().map(|(event, _)| event.to_string()).collect(),
+            settings,
+        })
+    })
+    .await?
+}
+
+/// Take the context guard hooks out of settings.json and delete the script
+#[tauri::command]
+#[tracing::instrument(err)]
+pub async fn uninstall_context_guard() -> Result<(), ArcadeError> {
+    run_blocking(move || {
+        update_settings_field("hooks", with_script_hooks(read_settings_full()?.hooks, SCRIPT_NAME, &[]))?;
+        if let Some(path) = script_path().filter(|p| p.exists()) {
+            fs::remove_file(&path).map_err(|e| ArcadeError::io(&path, e))?;
+        }
+        if let Some(dir) = estimates_dir() {
+            let _ = fs::remove_dir_all(dir);
+        }
+        Ok(())
+    })
+    .await?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn installs_and_removes_guard_hooks() {
+        let settings = ContextGuardSettings { warn_percent: 50, block_percent: 85 };
+        let commands = hook_commands(Path::new("/home/me/.claude/arcade_context_guard.sh"), &settings);
+        assert_eq!(commands[0].1, "sh \"/home/me/.claude/arcade_context_guard.sh\" prompt 50 85");
+
+        let theirs = json!({ "PreToolUse": [{ "matcher": "Bash", "hooks": [{ "type": "command", "command": "lint" }] }] });
+        let installed = with_script_hooks(Some(theirs.clone()), SCRIPT_NAME, &commands);
+        assert_eq!(installed["PreToolUse"].as_array().unwrap().len(), 2);
+        assert_eq!(with_script_hooks(Some(installed), SCRIPT_NAME, &[]), theirs);
+
+        assert!(estimate_path("0f6c2a7e-5d7b-4c59-9b0e-1d2f3a4b5c6d").is_some());
+        assert!(estimate_path("../settings.json").is_none());
+        assert!(check_thresholds(&ContextGuardSettings { warn_percent: 60, block_percent: 40 }).is_err());
+    }
+}
@@ -0,0 +1,217 @@
+//! Turn a slash command into a skill or flatten a simple skill into a slash command
+//! The new file is written before the original is removed, so a failed conversion loses nothing.
+
+use serde::{Deserialize, Serialize};
+use serde_yaml::{Mapping, Value};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::character::write_claude_file;
+use crate::scanner::plugin::claude_config_dir;
+use crate::scanner::skills::find_skill_md;
+use crate::blocking::run_blocking;
+use crate::error::ArcadeError;
+use crate::paths::check_name;
+use crate::trash::move_to_trash;
+
+/// Command frontmatter keys a skill understands too
+const SHARED_KEYS: [&str; 3] = ["description", "allowed-tools", "model"];
+
+/// Outcome of a conversion
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConvertResult {
+    /// Inventory id of the new item
+    pub item_id: String,
+    pub path: String,
+    /// Things that behave differently after conversion
+    pub warnings: Vec<String>,
+}
+
+/// Split YAML frontmatter from the body; content without frontmatter has an empty mapping
+fn split_yaml_frontmatter(content: &str) -> Result<(Mapping, String), String> {
+    let trimmed = content.trim_start_matches('\u{feff}');
+    let Some(rest) = trimmed.strip_prefix("---") else {
+        return Ok((Mapping::new(), content.to_string()));
+    };
+    let Some(end) = rest.find("\n---") else {
+        return Ok((Mapping::new(), content.to_string()));
+    };
+    let frontmatter = match serde_yaml::from_str::<Value>(&rest[..end]) {
+        Ok(Value::Mapping(mapping)) => mapping,
+        Ok(Value::Null) => Mapping::new(),
+        _ => return Err("Frontmatter isn't valid YAML; fix it before converting".to_string()),
+    };
+    let body = rest[end + 4..].trim_start_matches('-').trim_start_matches(['\r', '\n']).to_string();
+    Ok((frontmatter, body))
+}
+
+fn join_frontmatter(frontmatter: &Mapping, body: &str) -> Result<String, String> {
+    let yaml = serde_yaml::to_string(frontmatter).map_err(|e| e.to_string())?;
+    Ok(format!("---\n{}---\n\n{}", yaml, body.trim_start()))
+}
+
+/// First line of prose, for skills whose source has no description
+fn first_line(body: &str) -> Option<String> {
+    body.lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(String::from)
+}
+
+/// SKILL.md for a command: name and description up front (skills need both to be picked
+/// up), with the command's tools and model carried over
+fn command_to_skill(name: &str, content: &str, warnings: &mut Vec<String>) -> Result<String, String> {
+    let (frontmatter, body) = split_yaml_frontmatter(content)?;
+    let mut skill = Mapping::new();
+    skill.insert("name".into(), name.into());
+    if !frontmatter.contains_key("description") {
+        let description = first_line(&body).unwrap_or_else(|| format!("Converted from the /{} command", name));
+        skill.insert("description".into(), description.into());
+    }
+    for key in SHARED_KEYS {
+        if let Some(value) = frontmatter.get(key) {
+            skill.insert(key.into(), value.clone());
+        }
+    }
+
+    for key in frontmatter.keys().filter_map(|k| k.as_str()) {
+        if key != "name" && !SHARED_KEYS.contains(&key) {
+            warnings.push(format!("Dropped `{}`, which skills don't support", key));
+        }
+    }
+    if body.contains("$ARGUMENTS") || (1..=9).any(|n| body.contains(&format!("${}", n))) {
+        warnings.push("Skills aren't given arguments; $ARGUMENTS placeholders were left in place".to_string());
+    }
+    if body.lines().any(|line| line.trim_start().starts_with("!`")) {
+        warnings.push("Skills don't run !`command` lines before loading; they were left in place".to_string());
+    }
+    warnings.push("Claude now decides when to load this; it is no longer a /command".to_string());
+
+    join_frontmatter(&skill, &body)
+}
+
+/// Command file for a skill's SKILL.md; the name comes from the file name instead
+fn skill_to_command(content: &str) -> Result<String, String> {
+    let (frontmatter, body) = split_yaml_frontmatter(content)?;
+    let mut command = frontmatter;
+    command.shift_remove("name");
+    if command.is_empty() {
+        return Ok(body);
+    }
+    join_frontmatter(&command, &body)
+}
+
+/// The ~/.claude or <project>/.claude folder for a scope
+pub(crate) fn scope_root(scope: &str, project_path: Option<&str>) -> Result<PathBuf, String> {
+    match scope {
+        "user" => claude_config_dir().ok_or_else(|| "Could not find home directory".to_string()),
+        "project" => project_path
+            .map(|p| Path::new(p).join(".claude"))
+            .ok_or_else(|| "Project items need a project path".to_string()),
+        _ => Err(format!("{} items can't be converted", scope)),
+    }
+}
+
+fn convert_command(scope: &str, name: &str, root: &Path) -> Result<ConvertResult, ArcadeError> {
+    let source = root.join("commands").join(format!("{}.md", name));
+    let content = fs::read_to_string(&source).map_err(|e| ArcadeError::io(&source, e))?;
+    let target = root.join("skills").join(name).join("SKILL.md");
+    if target.parent().is_some_and(|dir| dir.exists()) {
+        return Err(ArcadeError::Conflict { message: format!("A skill named {} already exists", name) });
+    }
+
+    let mut warnings = Vec::new();
+    write_claude_file(&target, &command_to_skill(name, &content, &mut warnings)?)?;
+    move_to_trash(&source, "command").map_err(|e| format!("Converted, but failed to remove {}: {}", source.display(), e))?;
+
+    Ok(ConvertResult {
+        item_id: format!("skill_{}_{}", scope, name),
+        path: target.to_string_lossy().to_string(),
+        warnings,
+    })
+}
+
+fn convert_skill(scope: &str, name: &str, root: &Path) -> Result<ConvertResult, ArcadeError> {
+    let skill_dir = root.join("skills").join(name);
+    let skill_md = find_skill_md(&skill_dir).ok_or_else(|| ArcadeError::not_found("SKILL.md", skill_dir.to_string_lossy()))?;
+
+    // Scripts and references would be orphaned in a single command file
+    let extra: Vec<String> = fs::read_dir(&skill_dir)
+        .map_err(|e| ArcadeError::io(&skill_dir, e))?
+        .filter_map(|e| e.ok())
+        .filter(|e| e.path() != skill_md)
+        .map(|e| e.file_name().to_string_lossy().to_string())
+        .collect();
+    if !extra.is_empty() {
+        return Err(ArcadeError::invalid_input(format!(
+            "Only skills with just a SKILL.md can become commands; {} also has {}",
+            name,
+            extra.join(", ")
+        )));
+    }
+
+    let target = root.join("commands").join(format!("{}.md", name));
+    if target.exists() {
+        return Err(ArcadeError::Conflict { message: format!("A command named /{} already exists", name) });
+    }
+    let content = fs::read_to_string(&skill_md).map_err(|e| ArcadeError::io(&skill_md, e))?;
+
+    write_claude_file(&target, &skill_to_command(&content)?)?;
+    move_to_trash(&skill_dir, "skill").map_err(|e| format!("Converted, but failed to remove {}: {}", skill_dir.display(), e))?;
+
+    Ok(ConvertResult {
+        item_id: format!("cmd_{}_{}", scope, name),
+        path: target.to_string_lossy().to_string(),
+        warnings: vec![format!("Claude no longer loads this on its own; run it as /{}", name)],
+    })
+}
+
+/// Convert a user or project slash command into a skill (`target_type` "skill") or a skill
+/// made of just a SKILL.md into a command ("command"). The original is removed only once the
+/// new file is written.
+#[tauri::command]
+pub async fn convert_item(item_id: String, target_type: String, project_path: Option<String>) -> Result<ConvertResult, ArcadeError> {
+    run_blocking(move || {
+        let (kind, rest) = item_id
+            .split_once('_')
+            .ok_or_else(|| ArcadeError::not_found("Item", &item_id))?;
+        let (scope, name) = rest.split_once('_').ok_or_else(|| ArcadeError::not_found("Item", &item_id))?;
+        check_name(name)?;
+
+        match (kind, target_type.as_str()) {
+            ("cmd", "skill") => {
+                let root = scope_root(scope, project_path.as_deref())?;
+                convert_command(scope, name, &root)
+            }
+            ("skill", "command") => {
+                let root = scope_root(scope, project_path.as_deref())?;
+                convert_skill(scope, name, &root)
+            }
+            ("cmd", "command") | ("skill", "skill") => {
+                Err(ArcadeError::invalid_input(format!("{} is already a {}", item_id, target_type)))
+            }
+            ("cmd" | "skill", _) => Err(ArcadeError::invalid_input(format!("Unsupported target type: {}", target_type))),
+            _ => Err(ArcadeError::invalid_input(format!("Only commands and skills can be converted, not {}", item_id))),
+        }
+    })
+    .await?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn converts_command_frontmatter_to_skill() {
+        let command = "---\ndescription: Review the diff\nallowed-tools: Bash(git diff:*)\nargument-hint: [file]\n---\n\nReview $ARGUMENTS\n";
+        let mut warnings = Vec::new();
+        let skill = command_to_skill("review", command, &mut warnings).unwrap();
+        assert!(skill.starts_with("---\nname: review\ndescription: Review the diff\nallowed-tools: Bash(git diff:*)\n---\n\nReview"));
+        assert!(warnings.iter().any(|w| w.contains("argument-hint")));
+
+        let back = skill_to_command(&skill).unwrap();
+        assert!(back.starts_with("---\ndescription: Review the diff\n"));
+        assert!(!back.contains("name:"));
+    }
+}
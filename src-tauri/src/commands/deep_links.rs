@@ -0,0 +1,13 @@
+//! Hands claudearcade:// links the app received to the frontend, which asks before acting on them
+
+use tauri::State;
+
+use crate::deep_link::{DeepLinkRequest, DeepLinkState};
+
+/// Links waiting for confirmation, oldest first; each is returned once. The frontend calls this
+/// at startup and whenever `deep-link` fires, so a link that launched the app isn't lost.
+#[tauri::command]
+pub fn take_pending_deep_links(state: State<'_, DeepLinkState>) -> Result<Vec<DeepLinkRequest>, String> {
+    let mut pending = state.0.lock().map_err(|e| format!("Lock error: {}", e))?;
+    Ok(std::mem::take(&mut *pending))
+}
@@ -0,0 +1,395 @@
+//! How companions, skills and commands refer to each other, for the UI to draw as a graph
+//! References come from an agent's `skills` frontmatter and from prompt text: `@agent-name`,
+//! `subagent_type: name`, a backticked name next to the word agent or skill, and `/command`.
+//! Those explicit forms are reported as dangling when nothing answers to them. Bare mentions of
+//! distinctive names (with a dash, underscore or colon) only count when the target exists.
+
+use serde::Serialize;
+use serde_yaml::Value;
+use std::collections::HashSet;
+use std::path::Path;
+use tauri::State;
+
+use super::inventory::{cached_or_scan, memory_cached_scan, InventoryCacheState};
+use super::inventory_export::item_scope;
+use crate::blocking::run_blocking;
+use crate::scanner::frontmatter::Frontmatter;
+use crate::scanner::limits::read_capped;
+use crate::scanner::plugin::installed_plugin_paths;
+use crate::scanner::plugin_contents::{plugin_components, read_plugin_manifest};
+use crate::scanner::skills::find_skill_md;
+use crate::scanner::slash_commands::get_builtin_commands;
+use crate::types::{InventoryItem, ItemSource};
+
+/// Agents Claude Code ships with, which have no file to find
+const BUILTIN_AGENTS: [&str; 5] = ["general-purpose", "explore", "plan", "statusline-setup", "output-style-setup"];
+
+const AGENT_WORDS: [&str; 6] = ["agent", "agents", "subagent", "subagents", "companion", "companions"];
+const SKILL_WORDS: [&str; 2] = ["skill", "skills"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum NodeKind {
+    Agent,
+    Skill,
+    Command,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DelegationNode {
+    /// Inventory item id; plugin parts are `<plugin id>:<kind>:<name>`
+    pub id: String,
+    /// The name prompts use for it
+    pub name: String,
+    pub kind: NodeKind,
+    /// user, project, plugin or builtin
+    pub scope: String,
+    pub path: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DelegationEdge {
+    pub from: String,
+    /// Node referred to; None when dangling
+    pub to: Option<String>,
+    /// The name as written
+    pub target: String,
+    pub target_kind: NodeKind,
+    /// preloads (frontmatter `skills`), delegates, uses or runs
+    pub relation: String,
+    pub dangling: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DelegationGraph {
+    pub nodes: Vec<DelegationNode>,
+    pub edges: Vec<DelegationEdge>,
+    pub dangling_count: usize,
+}
+
+/// A reference found in a file; explicit ones are dangling when unresolved
+#[derive(Debug, Clone, PartialEq)]
+struct Reference {
+    kind: NodeKind,
+    name: String,
+    preloaded: bool,
+    explicit: bool,
+}
+
+/// A node with the text it is read from
+struct Source {
+    node: DelegationNode,
+    /// Other names it answers to, e.g. `plugin:name`
+    aliases: Vec<String>,
+    content: Option<String>,
+}
+
+fn is_name_char(c: char) -> bool {
+    c.is_alphanumeric() || matches!(c, '-' | '_' | ':')
+}
+
+/// The name at the start of `text`
+fn leading_name(text: &str) -> &str {
+    let end = text.find(|c: char| !is_name_char(c)).unwrap_or(text.len());
+    text[..end].trim_end_matches(':')
+}
+
+fn reference(kind: NodeKind, name: &str, explicit: bool) -> Option<Reference> {
+    (!name.is_empty()).then(|| Reference { kind, name: name.to_string(), preloaded: false, explicit })
+}
+
+/// `skills:` in frontmatter, as a list or comma-separated
+fn preloaded_skills(frontmatter: &Frontmatter) -> Vec<Reference> {
+    let names: Vec<String> = match frontmatter.get("skills") {
+        Some(Value::Sequence(list)) => list.iter().filter_map(|v| v.as_str()).map(String::from).collect(),
+        Some(Value::String(list)) => list.split(',').map(|s| s.trim().to_string()).collect(),
+        _ => Vec::new(),
+    };
+    names
+        .iter()
+        .filter_map(|name| reference(NodeKind::Skill, name, true))
+        .map(|r| Reference { preloaded: true, ..r })
+        .collect()
+}
+
+/// Explicit references in prompt text
+fn explicit_references(body: &str) -> Vec<Reference> {
+    let mut found = Vec::new();
+    for (at, _) in body.match_indices("@agent-") {
+        found.extend(reference(NodeKind::Agent, leading_name(&body[at + "@agent-".len()..]), true));
+    }
+    for (at, _) in body.match_indices("subagent_type") {
+        let value = body[at + "subagent_type".len()..].trim_start_matches([' ', ':', '=', '"', '\'']);
+        found.extend(reference(NodeKind::Agent, leading_name(value), true));
+    }
+
+    // Inline code sits at odd positions between backticks
+    let segments: Vec<&str> = body.split('`').collect();
+    for (i, code) in segments.iter().enumerate().filter(|(i, _)| i % 2 == 1) {
+        let code = code.trim();
+        if let Some(command) = code.strip_prefix('/') {
+            if !command.is_empty() && command.chars().all(is_name_char) {
+                found.extend(reference(NodeKind::Command, command, true));
+            }
+            continue;
+        }
+        if code.is_empty() || !code.chars().all(is_name_char) {
+            continue;
+        }
+        let before = segments[i - 1].split_whitespace().next_back().unwrap_or_default().to_lowercase();
+        let after = segments.get(i + 1).and_then(|s| s.split_whitespace().next()).unwrap_or_default();
+        let after = after.trim_end_matches(|c: char| !c.is_alphanumeric()).to_lowercase();
+        let near = |words: &[&str]| words.contains(&before.as_str()) || words.contains(&after.as_str());
+        if near(&AGENT_WORDS) {
+            found.extend(reference(NodeKind::Agent, code, true));
+        } else if near(&SKILL_WORDS) {
+            found.extend(reference(NodeKind::Skill, code, true));
+        }
+    }
+    found
+}
+
+/// `/command` written in prose; only counted when such a command exists
+fn loose_commands(body: &str) -> Vec<Reference> {
+    let mut found = Vec::new();
+    let mut previous = ' ';
+    for (at, c) in body.char_indices() {
+        if c == '/' && (previous.is_whitespace() || previous == '(') {
+            let rest = &body[at + 1..];
+            let name = leading_name(rest);
+            // Paths go on with another / or a file extension
+            let after = &rest[name.len()..];
+            let path = after.starts_with('/') || after.strip_prefix('.').is_some_and(|ext| ext.starts_with(char::is_alphanumeric));
+            if !path {
+                found.extend(reference(NodeKind::Command, name, false));
+            }
+        }
+        previous = c;
+    }
+    found
+}
+
+/// Every reference in a file, frontmatter ones first
+fn references(content: &str) -> Vec<Reference> {
+    let frontmatter = Frontmatter::parse(content);
+    let body = frontmatter.body();
+    let mut found = preloaded_skills(&frontmatter);
+    found.extend(explicit_references(body));
+    found.extend(loose_commands(body));
+    found
+}
+
+/// The name a skill, agent or command is called by: frontmatter `name`, else its file or folder
+fn handle(kind: NodeKind, path: &Path, content: Option<&str>) -> String {
+    let declared = content
+        .filter(|_| kind != NodeKind::Command)
+        .and_then(|c| Frontmatter::parse(c).get("name"))
+        .and_then(|v| v.as_str().map(String::from));
+    let from_path = match kind {
+        NodeKind::Skill if path.is_file() => path.parent().and_then(|p| p.file_name()),
+        NodeKind::Skill => path.file_name(),
+        _ => path.file_stem(),
+    };
+    declared.unwrap_or_else(|| from_path.map(|n| n.to_string_lossy().to_string()).unwrap_or_default())
+}
+
+fn item_source(item: &InventoryItem) -> Option<Source> {
+    let kind = match item.source {
+        ItemSource::Subagent => NodeKind::Agent,
+        ItemSource::Skill => NodeKind::Skill,
+        ItemSource::Command => NodeKind::Command,
+        _ => return None,
+    };
+    let scope = item_scope(item);
+    if scope == "builtin" {
+        let name = item.name.trim_start_matches('/').to_string();
+        let node = DelegationNode { id: item.id.clone(), name, kind, scope, path: None };
+        return Some(Source { node, aliases: Vec::new(), content: None });
+    }
+    let path = Path::new(&item.source_path);
+    let file = if kind == NodeKind::Skill { find_skill_md(path) } else { Some(path.to_path_buf()) };
+    let content = file.as_deref().and_then(|f| read_capped(f).ok());
+    let node = DelegationNode {
+        id: item.id.clone(),
+        name: handle(kind, path, content.as_deref()),
+        kind,
+        scope,
+        path: Some(item.source_path.clone()),
+    };
+    Some(Source { node, aliases: Vec::new(), content })
+}
+
+/// Commands, agents and skills of the enabled plugins, answering to `plugin:name` as well
+fn plugin_sources(items: &[InventoryItem]) -> Vec<Source> {
+    let paths = installed_plugin_paths();
+    let mut sources = Vec::new();
+    for plugin in items.iter().filter(|i| matches!(i.source, ItemSource::Plugin) && i.enabled) {
+        let Some(root) = paths.get(&plugin.id) else {
+            continue;
+        };
+        let plugin_name = plugin.id.split('@').next().unwrap_or(&plugin.id);
+        let mut ignored = Vec::new();
+        let manifest = read_plugin_manifest(root, &mut ignored);
+        for component in plugin_components(root, &manifest, &mut ignored) {
+            let kind = match component.kind.as_str() {
+                "agent" => NodeKind::Agent,
+                "skill" => NodeKind::Skill,
+                "command" => NodeKind::Command,
+                _ => continue,
+            };
+            let path = component.path.as_ref().map(|p| root.join(p));
+            let content = path.as_deref().and_then(|p| read_capped(p).ok());
+            let name = match kind {
+                NodeKind::Command => component.name.clone(),
+                _ => path.as_deref().map_or(component.name.clone(), |p| handle(kind, p, content.as_deref())),
+            };
+            let node = DelegationNode {
+                id: format!("{}:{}:{}", plugin.id, component.kind, component.name),
+                name: name.clone(),
+                kind,
+                scope: "plugin".to_string(),
+                path: path.map(|p| p.to_string_lossy().to_string()),
+            };
+            sources.push(Source { node, aliases: vec![format!("{}:{}", plugin_name, name)], content });
+        }
+    }
+    sources
+}
+
+/// The node a reference from `from` resolves to: the same scope first, then project over user
+/// over plugin, the order Claude Code picks between same-named items
+fn resolve<'a>(sources: &'a [Source], from: &Source, reference: &Reference) -> Option<&'a Source> {
+    let rank = |s: &Source| {
+        let scope_rank = ["project", "user", "plugin", "builtin"].iter().position(|r| *r == s.node.scope).unwrap_or(4);
+        (s.node.scope != from.node.scope, scope_rank)
+    };
+    sources
+        .iter()
+        .filter(|s| s.node.kind == reference.kind && s.node.id != from.node.id)
+        .filter(|s| {
+            s.node.name.eq_ignore_ascii_case(&reference.name) || s.aliases.iter().any(|a| a.eq_ignore_ascii_case(&reference.name))
+        })
+        .min_by_key(|s| rank(s))
+}
+
+fn relation(reference: &Reference) -> &'static str {
+    match reference.kind {
+        _ if reference.preloaded => "preloads",
+        NodeKind::Agent => "delegates",
+        NodeKind::Skill => "uses",
+        NodeKind::Command => "runs",
+    }
+}
+
+fn build_graph(sources: Vec<Source>) -> DelegationGraph {
+    // Distinctive names are also picked up bare, outside backticks
+    let distinctive: Vec<(NodeKind, String)> = sources
+        .iter()
+        .filter(|s| s.node.kind != NodeKind::Command && s.node.name.contains(['-', '_', ':']))
+        .map(|s| (s.node.kind, s.node.name.clone()))
+        .collect();
+
+    let mut edges = Vec::new();
+    for source in &sources {
+        let Some(content) = &source.content else {
+            continue;
+        };
+        let mut found = references(content);
+        let body = Frontmatter::parse(content).body().to_lowercase();
+        for (kind, name) in &distinctive {
+            let lower = name.to_lowercase();
+            let bare = body.match_indices(&lower).any(|(at, _)| {
+                let before = body[..at].chars().next_back();
+                let after = body[at + lower.len()..].chars().next();
+                !before.is_some_and(is_name_char) && !after.is_some_and(|c| is_name_char(c) || c == '/')
+            });
+            if bare {
+                found.extend(reference(*kind, name, false));
+            }
+        }
+
+        let mut seen: HashSet<(NodeKind, String)> = HashSet::new();
+        for reference in found {
+            let target = resolve(&sources, source, &reference);
+            let builtin_agent = reference.kind == NodeKind::Agent && BUILTIN_AGENTS.contains(&reference.name.to_lowercase().as_str());
+            let dangling = target.is_none() && reference.explicit && !builtin_agent;
+            if target.is_some_and(|t| t.node.id == source.node.id) || (target.is_none() && !dangling) {
+                continue;
+            }
+            if !seen.insert((reference.kind, reference.name.to_lowercase())) {
+                continue;
+            }
+            edges.push(DelegationEdge {
+                from: source.node.id.clone(),
+                to: target.map(|t| t.node.id.clone()),
+                target: reference.name.clone(),
+                target_kind: reference.kind,
+                relation: relation(&reference).to_string(),
+                dangling,
+            });
+        }
+    }
+    let dangling_count = edges.iter().filter(|e| e.dangling).count();
+    DelegationGraph { nodes: sources.into_iter().map(|s| s.node).collect(), edges, dangling_count }
+}
+
+/// Which companions, skills and commands refer to which, with references to ones that don't
+/// exist flagged as dangling
+#[tauri::command]
+pub async fn get_delegation_graph(
+    state: State<'_, InventoryCacheState>,
+    project_path: Option<String>,
+) -> Result<DelegationGraph, String> {
+    let cached = memory_cached_scan(&state, &project_path)?;
+    run_blocking(move || {
+        let items = cached_or_scan(cached, project_path.as_deref()).items;
+        let mut sources: Vec<Source> = items.iter().chain(&get_builtin_commands()).filter_map(item_source).collect();
+        sources.extend(plugin_sources(&items));
+        build_graph(sources)
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn source(id: &str, name: &str, kind: NodeKind, scope: &str, content: Option<&str>) -> Source {
+        let node = DelegationNode { id: id.to_string(), name: name.to_string(), kind, scope: scope.to_string(), path: None };
+        Source { node, aliases: Vec::new(), content: content.map(String::from) }
+    }
+
+    #[test]
+    fn links_references_and_flags_dangling_ones() {
+        let lead = "---\nname: lead\nskills: [pdf, slides]\n---\n\
+                    Hand reviews to the `reviewer` agent and tests to @agent-test-runner.\n\
+                    Run /compact when the context is full, and never touch /etc/hosts.\n\
+                    Ask the code-simplifier for a second pass.";
+        let sources = vec![
+            source("subagent_user_lead", "lead", NodeKind::Agent, "user", Some(lead)),
+            source("subagent_user_reviewer", "reviewer", NodeKind::Agent, "user", Some("Review the diff.")),
+            source("subagent_project_code-simplifier", "code-simplifier", NodeKind::Agent, "project", Some("Simplify.")),
+            source("skill_user_pdf", "pdf", NodeKind::Skill, "user", Some("Read PDFs.")),
+            source("builtin_compact", "compact", NodeKind::Command, "builtin", None),
+        ];
+
+        let graph = build_graph(sources);
+        let edges: Vec<(&str, Option<&str>, &str, bool)> = graph
+            .edges
+            .iter()
+            .map(|e| (e.target.as_str(), e.to.as_deref(), e.relation.as_str(), e.dangling))
+            .collect();
+        assert_eq!(edges, vec![
+            ("pdf", Some("skill_user_pdf"), "preloads", false),
+            ("slides", None, "preloads", true),
+            ("test-runner", None, "delegates", true),
+            ("reviewer", Some("subagent_user_reviewer"), "delegates", false),
+            ("compact", Some("builtin_compact"), "runs", false),
+            ("code-simplifier", Some("subagent_project_code-simplifier"), "delegates", false),
+        ]);
+        assert_eq!(graph.dangling_count, 2);
+    }
+}
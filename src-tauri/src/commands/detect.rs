@@ -2,6 +2,9 @@ use std::fs;
 use std::path::Path;
 use serde::{Deserialize, Serialize};
 
+use crate::scanner::settings::parse_settings_str;
+use crate::blocking::run_blocking;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ProjectInfo {
@@ -12,6 +15,31 @@ pub struct ProjectInfo {
     pub has_typescript: bool,
     pub has_eslint: bool,
     pub has_prettier: bool,
+    /// Framework -> version, where a manifest pins one
+    pub framework_versions: std::collections::HashMap<String, String>,
+    /// Devcontainer / Docker setup, when the project has one
+    pub container: Option<ContainerInfo>,
+}
+
+/// Container setup detected in a project, with what the UI needs to suggest
+/// running Claude inside it
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ContainerInfo {
+    pub devcontainer_path: Option<String>,
+    pub dockerfile_path: Option<String>,
+    pub compose_files: Vec<String>,
+    /// Where the project is mounted inside the devcontainer
+    pub workspace_folder: Option<String>,
+    /// Mounts declared in devcontainer.json
+    pub mounts: Vec<String>,
+    /// Whether the devcontainer already installs Claude Code
+    pub has_claude_feature: bool,
+    /// Mount to add so the container sees the host's ~/.claude config
+    pub suggested_config_mount: Option<String>,
+    /// Settings to use for Claude inside the container
+    pub recommended_settings: serde_json::Value,
+    pub notes: Vec<String>,
 }
 
 impl Default for ProjectInfo {
@@ -24,12 +52,14 @@ impl Default for ProjectInfo {
             has_typescript: false,
             has_eslint: false,
             has_prettier: false,
+            framework_versions: std::collections::HashMap::new(),
+            container: None,
         }
     }
 }
 
-#[tauri::command]
-pub fn detect_project_type(path: String) -> Result<ProjectInfo, String> {
+/// Languages, frameworks and tooling found in a project folder
+pub(crate) fn project_info(path: String) -> Result<ProjectInfo, String> {
     let project_path = Path::new(&path);
 
     if !project_path.exists() {
@@ -71,6 +101,21 @@ pub fn detect_project_type(path: String) -> Result<ProjectInfo, String> {
         info.languages.push("go".to_string());
     }
 
+    detect_jvm(project_path, &mut info);
+    detect_dotnet(project_path, &mut info);
+    detect_php(project_path, &mut info);
+    detect_elixir(project_path, &mut info);
+    detect_swift(project_path, &mut info);
+
+    // Terraform and notebooks often live in a subfolder (infra/, notebooks/)
+    if has_file_with_extension(project_path, "tf", 1) {
+        push_unique(&mut info.languages, "terraform");
+    }
+    if has_file_with_extension(project_path, "ipynb", 1) {
+        push_unique(&mut info.languages, "jupyter");
+        push_unique(&mut info.frameworks, "notebooks");
+    }
+
     // Check for tsconfig.json (TypeScript)
     if project_path.join("tsconfig.json").exists() {
         info.has_typescript = true;
@@ -99,9 +144,147 @@ pub fn detect_project_type(path: String) -> Result<ProjectInfo, String> {
         || project_path.join("__tests__").exists()
         || project_path.join("spec").exists();
 
+    info.container = detect_container(project_path);
+
     Ok(info)
 }
 
+#[tauri::command]
+pub async fn detect_project_type(path: String) -> Result<ProjectInfo, String> {
+    run_blocking(move || project_info(path)).await?
+}
+
+/// Devcontainer feature that installs Claude Code
+const CLAUDE_DEVCONTAINER_FEATURE: &str = "devcontainer-features/claude-code";
+
+/// Whether the devcontainer cuts the container off the network: `--network none` in runArgs,
+/// or a firewall script run at start like the one in Claude Code's reference devcontainer
+fn network_isolated(config: &serde_json::Value) -> bool {
+    let run_args: Vec<&str> = config
+        .get("runArgs")
+        .and_then(|v| v.as_array())
+        .map(|args| args.iter().filter_map(|a| a.as_str()).collect())
+        .unwrap_or_default();
+    let no_network = run_args.iter().any(|a| *a == "--network=none" || *a == "--net=none")
+        || run_args.windows(2).any(|pair| matches!(pair[0], "--network" | "--net") && pair[1] == "none");
+    let firewall = ["postStartCommand", "postCreateCommand"]
+        .iter()
+        .filter_map(|key| config.get(*key))
+        .any(|command| command.to_string().contains("init-firewall"));
+    no_network || firewall
+}
+
+fn detect_container(project_path: &Path) -> Option<ContainerInfo> {
+    let devcontainer = [
+        ".devcontainer/devcontainer.json",
+        ".devcontainer.json",
+    ]
+    .iter()
+    .map(|p| project_path.join(p))
+    .find(|p| p.is_file());
+
+    let dockerfile = ["Dockerfile", ".devcontainer/Dockerfile"]
+        .iter()
+        .map(|p| project_path.join(p))
+        .find(|p| p.is_file());
+
+    let compose_files: Vec<String> = [
+        "docker-compose.yml",
+        "docker-compose.yaml",
+        "compose.yml",
+        "compose.yaml",
+        ".devcontainer/docker-compose.yml",
+    ]
+    .iter()
+    .filter(|p| project_path.join(p).is_file())
+    .map(|p| p.to_string())
+    .collect();
+
+    if devcontainer.is_none() && dockerfile.is_none() && compose_files.is_empty() {
+        return None;
+    }
+
+    let mut info = ContainerInfo {
+        devcontainer_path: devcontainer.as_ref().map(|p| p.to_string_lossy().to_string()),
+        dockerfile_path: dockerfile.as_ref().map(|p| p.to_string_lossy().to_string()),
+        compose_files,
+        ..Default::default()
+    };
+
+    // devcontainer.json allows comments and trailing commas
+    let config = devcontainer
+        .and_then(|p| fs::read_to_string(p).ok())
+        .and_then(|content| parse_settings_str(&content).ok());
+
+    if let Some(config) = &config {
+        let project_name = project_path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        info.workspace_folder = Some(
+            config.get("workspaceFolder")
+                .and_then(|v| v.as_str())
+                .map(String::from)
+                .unwrap_or_else(|| format!("/workspaces/{}", project_name)),
+        );
+
+        info.mounts = config.get("mounts")
+            .and_then(|m| m.as_array())
+            .map(|mounts| {
+                mounts.iter()
+                    .filter_map(|m| match m {
+                        serde_json::Value::String(s) => Some(s.clone()),
+                        serde_json::Value::Object(o) => Some(format!(
+                            "source={},target={},type={}",
+                            o.get("source").and_then(|v| v.as_str()).unwrap_or_default(),
+                            o.get("target").and_then(|v| v.as_str()).unwrap_or_default(),
+                            o.get("type").and_then(|v| v.as_str()).unwrap_or("bind"),
+                        )),
+                        _ => None,
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        info.has_claude_feature = config.get("features")
+            .and_then(|f| f.as_object())
+            .is_some_and(|features| features.keys().any(|k| k.contains(CLAUDE_DEVCONTAINER_FEATURE)));
+
+        if !info.mounts.iter().any(|m| m.contains(".claude")) {
+            let user = config.get("remoteUser").and_then(|v| v.as_str()).unwrap_or("node");
+            let home = if user == "root" { "/root".to_string() } else { format!("/home/{}", user) };
+            info.suggested_config_mount = Some(format!(
+                "source=${{localEnv:HOME}}/.claude,target={}/.claude,type=bind",
+                home
+            ));
+        }
+
+        if !info.has_claude_feature {
+            info.notes.push(format!(
+                "Add the ghcr.io/anthropics/{} feature to install Claude Code in the container",
+                CLAUDE_DEVCONTAINER_FEATURE
+            ));
+        }
+        info.recommended_settings = serde_json::json!({
+            "permissions": { "defaultMode": "acceptEdits" }
+        });
+        // Skipping prompts is only reasonable when nothing can leave the container; leave it to the user
+        if network_isolated(config) {
+            info.notes.push(
+                "The container's network is restricted, so you could opt in to bypassPermissions inside it".to_string(),
+            );
+        }
+    } else {
+        // A plain Dockerfile doesn't isolate a host session, so stay conservative
+        info.recommended_settings = serde_json::json!({
+            "permissions": { "defaultMode": "acceptEdits" }
+        });
+        info.notes.push("No devcontainer.json found; add one to run Claude Code inside the container".to_string());
+    }
+
+    Some(info)
+}
+
 fn detect_node_package_manager(project_path: &Path) -> String {
     if project_path.join("pnpm-lock.yaml").exists() {
         "pnpm".to_string()
@@ -123,6 +306,15 @@ fn parse_package_json(content: &str, info: &mut ProjectInfo) {
     }
 
     if let Ok(pkg) = serde_json::from_str::<PackageJson>(content) {
+        // Record versions of the frameworks we recognise
+        for deps in pkg.dependencies.iter().chain(pkg.dev_dependencies.iter()) {
+            for (framework, dep) in NODE_FRAMEWORK_DEPS {
+                if let Some(version) = deps.get(dep) {
+                    info.framework_versions.insert(framework.to_string(), clean_version(version));
+                }
+            }
+        }
+
         let all_deps: Vec<&String> = pkg
             .dependencies
             .iter()
@@ -232,3 +424,269 @@ fn parse_pyproject(content: &str, info: &mut ProjectInfo) {
         info.has_tests = true;
     }
 }
+
+/// Node frameworks and the package that identifies them
+const NODE_FRAMEWORK_DEPS: [(&str, &str); 7] = [
+    ("react", "react"),
+    ("nextjs", "next"),
+    ("vue", "vue"),
+    ("svelte", "svelte"),
+    ("angular", "@angular/core"),
+    ("express", "express"),
+    ("tailwind", "tailwindcss"),
+];
+
+fn push_unique(list: &mut Vec<String>, value: &str) {
+    if !list.iter().any(|v| v == value) {
+        list.push(value.to_string());
+    }
+}
+
+/// Strip range operators from a version requirement ("^18.2.0" -> "18.2.0")
+fn clean_version(version: &str) -> String {
+    version.trim_start_matches(|c: char| !c.is_ascii_digit()).to_string()
+}
+
+/// Whether a file with this extension exists in the directory, or up to `depth` levels below
+fn has_file_with_extension(dir: &Path, extension: &str, depth: usize) -> bool {
+    let entries = match fs::read_dir(dir) {
+        Ok(e) => e,
+        Err(_) => return false,
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let hidden_or_vendored = path.file_name()
+            .and_then(|n| n.to_str())
+            .is_some_and(|n| n.starts_with('.') || n == "node_modules" || n == "target");
+        if path.is_file() && path.extension().is_some_and(|e| e == extension) {
+            return true;
+        }
+        if depth > 0 && path.is_dir() && !hidden_or_vendored && has_file_with_extension(&path, extension, depth - 1) {
+            return true;
+        }
+    }
+
+    false
+}
+
+/// Folders below `src/` searched for sources when the standard layout isn't used
+const JVM_SOURCE_DEPTH: usize = 6;
+
+/// Java / Kotlin via Maven or Gradle (Groovy or Kotlin DSL). The language comes from the sources
+/// and the Kotlin plugin, not from the build script's DSL: a build.gradle.kts often builds Java.
+fn detect_jvm(project_path: &Path, info: &mut ProjectInfo) {
+    let gradle_kts = project_path.join("build.gradle.kts");
+    let gradle = project_path.join("build.gradle");
+    let pom = project_path.join("pom.xml");
+    let is_gradle = gradle_kts.exists() || gradle.exists();
+    if !is_gradle && !pom.exists() {
+        return;
+    }
+
+    let build_files = [gradle_kts, gradle, pom.clone()]
+        .iter()
+        .filter_map(|p| fs::read_to_string(p).ok())
+        .collect::<Vec<_>>()
+        .join("\n");
+    let src = project_path.join("src");
+    let kotlin = project_path.join("src/main/kotlin").is_dir()
+        || build_files.contains("org.jetbrains.kotlin")
+        || build_files.contains("kotlin(\"")
+        || has_file_with_extension(&src, "kt", JVM_SOURCE_DEPTH);
+    let java = project_path.join("src/main/java").is_dir() || has_file_with_extension(&src, "java", JVM_SOURCE_DEPTH);
+
+    if java || !kotlin {
+        push_unique(&mut info.languages, "java");
+    }
+    if kotlin {
+        push_unique(&mut info.languages, "kotlin");
+    }
+    if is_gradle {
+        push_unique(&mut info.frameworks, "gradle");
+        info.package_manager.get_or_insert_with(|| "gradle".to_string());
+    }
+    if pom.exists() {
+        push_unique(&mut info.frameworks, "maven");
+        info.package_manager.get_or_insert_with(|| "maven".to_string());
+    }
+
+    if build_files.contains("org.springframework.boot") {
+        push_unique(&mut info.frameworks, "spring-boot");
+    }
+    if build_files.contains("com.android.application") || build_files.contains("com.android.library") {
+        push_unique(&mut info.frameworks, "android");
+    }
+    if project_path.join("src/test").is_dir() {
+        info.has_tests = true;
+    }
+}
+
+/// C# / .NET via solution or project files
+fn detect_dotnet(project_path: &Path, info: &mut ProjectInfo) {
+    let has_project = has_file_with_extension(project_path, "sln", 0)
+        || has_file_with_extension(project_path, "csproj", 1)
+        || has_file_with_extension(project_path, "fsproj", 1);
+    if !has_project {
+        return;
+    }
+
+    if has_file_with_extension(project_path, "fsproj", 1) {
+        push_unique(&mut info.languages, "fsharp");
+    } else {
+        push_unique(&mut info.languages, "csharp");
+    }
+    push_unique(&mut info.frameworks, "dotnet");
+    info.package_manager.get_or_insert_with(|| "nuget".to_string());
+
+    // global.json pins the SDK version
+    if let Some(version) = fs::read_to_string(project_path.join("global.json"))
+        .ok()
+        .and_then(|c| serde_json::from_str::<serde_json::Value>(&c).ok())
+        .and_then(|v| v.get("sdk")?.get("version")?.as_str().map(String::from))
+    {
+        info.framework_versions.insert("dotnet".to_string(), version);
+    }
+}
+
+/// PHP via composer.json (Laravel, Symfony)
+fn detect_php(project_path: &Path, info: &mut ProjectInfo) {
+    let composer = match fs::read_to_string(project_path.join("composer.json")) {
+        Ok(c) => c,
+        Err(_) => return,
+    };
+
+    push_unique(&mut info.languages, "php");
+    info.package_manager.get_or_insert_with(|| "composer".to_string());
+
+    let json: serde_json::Value = serde_json::from_str(&composer).unwrap_or_default();
+    let requires: Vec<(&String, &serde_json::Value)> = ["require", "require-dev"]
+        .iter()
+        .filter_map(|k| json.get(*k).and_then(|v| v.as_object()))
+        .flat_map(|m| m.iter())
+        .collect();
+
+    for (package, version) in requires {
+        let framework = match package.as_str() {
+            "laravel/framework" => "laravel",
+            "symfony/framework-bundle" => "symfony",
+            "phpunit/phpunit" | "pestphp/pest" => {
+                info.has_tests = true;
+                continue;
+            }
+            _ => continue,
+        };
+        push_unique(&mut info.frameworks, framework);
+        if let Some(version) = version.as_str() {
+            info.framework_versions.insert(framework.to_string(), clean_version(version));
+        }
+    }
+}
+
+/// Elixir via mix.exs (Phoenix)
+fn detect_elixir(project_path: &Path, info: &mut ProjectInfo) {
+    let mix = match fs::read_to_string(project_path.join("mix.exs")) {
+        Ok(c) => c,
+        Err(_) => return,
+    };
+
+    push_unique(&mut info.languages, "elixir");
+    info.package_manager.get_or_insert_with(|| "mix".to_string());
+
+    // Dependencies look like {:phoenix, "~> 1.7.0"}
+    if let Some(start) = mix.find("{:phoenix,") {
+        push_unique(&mut info.frameworks, "phoenix");
+        let version = mix[start..]
+            .split('"')
+            .nth(1)
+            .map(clean_version);
+        if let Some(version) = version {
+            info.framework_versions.insert("phoenix".to_string(), version);
+        }
+    }
+    if project_path.join("test").is_dir() {
+        info.has_tests = true;
+    }
+}
+
+/// Swift via SwiftPM or an Xcode project
+fn detect_swift(project_path: &Path, info: &mut ProjectInfo) {
+    let package_swift = project_path.join("Package.swift");
+    let has_xcode = fs::read_dir(project_path)
+        .map(|entries| {
+            entries.flatten().any(|e| {
+                e.path().extension().is_some_and(|ext| ext == "xcodeproj" || ext == "xcworkspace")
+            })
+        })
+        .unwrap_or(false);
+
+    if !package_swift.exists() && !has_xcode {
+        return;
+    }
+
+    push_unique(&mut info.languages, "swift");
+    if package_swift.exists() {
+        push_unique(&mut info.frameworks, "swiftpm");
+        info.package_manager.get_or_insert_with(|| "swiftpm".to_string());
+
+        // First line is "// swift-tools-version:5.9"
+        if let Some(version) = fs::read_to_string(&package_swift)
+            .ok()
+            .and_then(|c| c.lines().next().and_then(|l| l.split(':').nth(1)).map(|v| v.trim().to_string()))
+        {
+            info.framework_versions.insert("swift-tools".to_string(), version);
+        }
+    }
+    if has_xcode {
+        push_unique(&mut info.frameworks, "xcode");
+    }
+    if project_path.join("Tests").is_dir() {
+        info.has_tests = true;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TempDir;
+    use serde_json::json;
+
+    #[test]
+    fn only_restricted_networks_count_as_isolated() {
+        assert!(!network_isolated(&json!({ "runArgs": ["--cap-add=NET_ADMIN"] })));
+        assert!(network_isolated(&json!({ "runArgs": ["--network", "none"] })));
+        assert!(network_isolated(&json!({ "postStartCommand": "sudo /usr/local/bin/init-firewall.sh" })));
+    }
+
+    fn jvm_project(name: &str, files: &[(&str, &str)]) -> ProjectInfo {
+        let root = TempDir::new(&format!("detect-{}", name));
+        for (path, content) in files {
+            let path = root.join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, content).unwrap();
+        }
+        project_info(root.to_string_lossy().to_string()).unwrap()
+    }
+
+    #[test]
+    fn tells_java_from_kotlin_by_sources() {
+        let maven = jvm_project("maven", &[("pom.xml", "<project/>"), ("src/main/java/com/example/App.java", "class App {}")]);
+        assert_eq!((maven.languages, maven.package_manager.as_deref()), (vec!["java".to_string()], Some("maven")));
+
+        let groovy = jvm_project("groovy", &[("build.gradle", "plugins { id 'java' }"), ("src/main/java/App.java", "class App {}")]);
+        assert_eq!((groovy.languages, groovy.package_manager.as_deref()), (vec!["java".to_string()], Some("gradle")));
+
+        let kts_java = jvm_project(
+            "kts-java",
+            &[("build.gradle.kts", "plugins { java; application }"), ("src/main/java/com/example/App.java", "class App {}")],
+        );
+        assert_eq!(kts_java.languages, vec!["java".to_string()]);
+        assert!(kts_java.frameworks.contains(&"gradle".to_string()));
+
+        let kotlin = jvm_project(
+            "kotlin",
+            &[("build.gradle.kts", "plugins { kotlin(\"jvm\") version \"2.0.0\" }"), ("src/main/kotlin/App.kt", "fun main() {}")],
+        );
+        assert_eq!(kotlin.languages, vec!["kotlin".to_string()]);
+    }
+}
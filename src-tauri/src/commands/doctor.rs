@@ -0,0 +1,233 @@
+//! Health check of the Claude Code install and its config, like `claude doctor` for what the app manages
+//! Each finding says what is wrong and, where the app can't fix it itself, what to run.
+
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::Path;
+
+use super::cli_version::claude_binary_version;
+use super::git::{git_succeeds, PERSONAL_FILES};
+use crate::blocking::run_blocking;
+use crate::error::ArcadeError;
+use crate::scanner::plugin::installed_plugin_paths;
+use crate::scanner::prerequisites::{find_binary, install_hint};
+use crate::scanner::settings::{read_mcp_servers, read_settings, settings_error, settings_path};
+
+/// Runtimes MCP servers are usually launched with
+const MCP_RUNTIMES: [&str; 4] = ["node", "npx", "uv", "uvx"];
+
+/// What to do about a finding. `command` is a Tauri command the UI can run with `args`;
+/// otherwise `hint` says what the user has to do.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DoctorFix {
+    pub label: String,
+    pub command: Option<String>,
+    pub args: Option<Value>,
+    pub hint: Option<String>,
+}
+
+/// One check's result
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DoctorFinding {
+    /// claudeBinary, mcpRuntime, claudeDirWritable, settingsValid, orphanedPlugin or gitignore
+    pub check: String,
+    /// ok, warning or error
+    pub severity: String,
+    pub message: String,
+    pub fix: Option<DoctorFix>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DoctorReport {
+    pub claude_version: Option<String>,
+    pub findings: Vec<DoctorFinding>,
+    pub errors: usize,
+    pub warnings: usize,
+}
+
+fn finding(check: &str, severity: &str, message: impl Into<String>, fix: Option<DoctorFix>) -> DoctorFinding {
+    DoctorFinding { check: check.to_string(), severity: severity.to_string(), message: message.into(), fix }
+}
+
+fn hint(label: &str, hint: impl Into<String>) -> Option<DoctorFix> {
+    Some(DoctorFix { label: label.to_string(), command: None, args: None, hint: Some(hint.into()) })
+}
+
+fn check_claude_binary(findings: &mut Vec<DoctorFinding>) -> Option<String> {
+    let Some(binary) = find_binary("claude") else {
+        findings.push(finding(
+            "claudeBinary",
+            "error",
+            "The claude CLI was not found on PATH",
+            hint("Install Claude Code", "npm install -g @anthropic-ai/claude-code"),
+        ));
+        return None;
+    };
+    let version = claude_binary_version(&binary);
+    match &version {
+        Some(version) => findings.push(finding("claudeBinary", "ok", format!("claude {} at {}", version, binary.display()), None)),
+        None => findings.push(finding(
+            "claudeBinary",
+            "warning",
+            format!("{} did not report a version", binary.display()),
+            hint("Reinstall Claude Code", "npm install -g @anthropic-ai/claude-code"),
+        )),
+    }
+    version
+}
+
+fn check_mcp_runtimes(findings: &mut Vec<DoctorFinding>) {
+    // A settings.json that doesn't parse is reported by check_settings
+    let servers = read_mcp_servers().unwrap_or_default();
+    for runtime in MCP_RUNTIMES {
+        let users: BTreeSet<&str> = servers.iter().filter(|(_, s)| s.command == runtime).map(|(id, _)| id.as_str()).collect();
+        if find_binary(runtime).is_some() {
+            findings.push(finding("mcpRuntime", "ok", format!("{} is available", runtime), None));
+        } else if !users.is_empty() {
+            let users: Vec<&str> = users.into_iter().collect();
+            findings.push(finding(
+                "mcpRuntime",
+                "error",
+                format!("{} is missing, and MCP servers need it: {}", runtime, users.join(", ")),
+                hint(&format!("Install {}", runtime), install_hint(runtime).unwrap_or_default()),
+            ));
+        } else {
+            findings.push(finding(
+                "mcpRuntime",
+                "warning",
+                format!("{} is not installed; MCP servers that run through it won't start", runtime),
+                hint(&format!("Install {}", runtime), install_hint(runtime).unwrap_or_default()),
+            ));
+        }
+    }
+}
+
+fn check_claude_dir(findings: &mut Vec<DoctorFinding>) {
+    let Some(dir) = crate::paths::claude_dir() else {
+        findings.push(finding("claudeDirWritable", "error", "Could not find home directory", None));
+        return;
+    };
+    let probe = dir.join(".arcade_doctor_probe");
+    let writable = fs::create_dir_all(&dir).and_then(|_| fs::write(&probe, b"")).is_ok();
+    let _ = fs::remove_file(&probe);
+    if writable {
+        findings.push(finding("claudeDirWritable", "ok", format!("{} is writable", dir.display()), None));
+    } else {
+        findings.push(finding(
+            "claudeDirWritable",
+            "error",
+            format!("{} is not writable, so no config changes can be saved", dir.display()),
+            hint("Fix permissions", format!("chmod -R u+w \"{}\"", dir.display())),
+        ));
+    }
+}
+
+fn check_settings(findings: &mut Vec<DoctorFinding>) {
+    match settings_error() {
+        Some(error) => {
+            let fix = match &error {
+                ArcadeError::ParseError { path, line: Some(line), .. } => {
+                    hint("Fix the syntax error", format!("Edit {} at line {}", path, line))
+                }
+                _ => hint("Make settings.json readable", "Check the file's permissions, or remove it if it isn't a file"),
+            };
+            findings.push(finding("settingsValid", "error", error.to_string(), fix));
+        }
+        None => {
+            let path = settings_path().map(|p| p.display().to_string()).unwrap_or_default();
+            findings.push(finding("settingsValid", "ok", format!("{} parses", path), None));
+        }
+    }
+}
+
+fn check_orphaned_plugins(findings: &mut Vec<DoctorFinding>) {
+    let installed = installed_plugin_paths();
+    let mut enabled: Vec<String> = read_settings().unwrap_or_default().enabled_plugins.into_keys().collect();
+    enabled.sort();
+
+    let before = findings.len();
+    for plugin_id in enabled.iter().filter(|id| !installed.contains_key(*id)) {
+        findings.push(finding(
+            "orphanedPlugin",
+            "warning",
+            format!("{} is listed in enabledPlugins but isn't installed", plugin_id),
+            Some(DoctorFix {
+                label: "Remove from enabledPlugins".to_string(),
+                command: Some("unequip_item".to_string()),
+                args: Some(json!({ "itemId": plugin_id })),
+                hint: None,
+            }),
+        ));
+    }
+    let mut missing: Vec<(&String, &std::path::PathBuf)> = installed.iter().filter(|(_, path)| !path.exists()).collect();
+    missing.sort();
+    for (plugin_id, path) in missing {
+        findings.push(finding(
+            "orphanedPlugin",
+            "warning",
+            format!("{} is installed at {}, which no longer exists", plugin_id, path.display()),
+            Some(DoctorFix {
+                label: "Uninstall".to_string(),
+                command: Some("uninstall_plugin".to_string()),
+                args: Some(json!({ "pluginId": plugin_id })),
+                hint: None,
+            }),
+        ));
+    }
+    if findings.len() == before {
+        findings.push(finding("orphanedPlugin", "ok", "Every enabled plugin is installed", None));
+    }
+}
+
+fn check_gitignore(project: &Path, findings: &mut Vec<DoctorFinding>) {
+    if !git_succeeds(project, &["rev-parse", "--is-inside-work-tree"]) {
+        return;
+    }
+    for file in PERSONAL_FILES {
+        if !project.join(file).exists() {
+            continue;
+        }
+        let tracked = git_succeeds(project, &["ls-files", "--error-unmatch", file]);
+        if !tracked && git_succeeds(project, &["check-ignore", "-q", file]) {
+            findings.push(finding("gitignore", "ok", format!("{} is gitignored", file), None));
+        } else {
+            let fix = if tracked {
+                format!("git rm --cached {} && echo {} >> .gitignore", file, file)
+            } else {
+                format!("echo {} >> .gitignore", file)
+            };
+            findings.push(finding(
+                "gitignore",
+                "warning",
+                format!("{} is personal but {}", file, if tracked { "committed" } else { "not gitignored" }),
+                hint("Ignore it", fix),
+            ));
+        }
+    }
+}
+
+/// Check the claude CLI, MCP runtimes, ~/.claude, settings.json, plugin entries and (for a project)
+/// that personal files are gitignored
+#[tauri::command]
+pub async fn run_doctor(project_path: Option<String>) -> Result<DoctorReport, String> {
+    run_blocking(move || {
+        let mut findings = Vec::new();
+        let claude_version = check_claude_binary(&mut findings);
+        check_mcp_runtimes(&mut findings);
+        check_claude_dir(&mut findings);
+        check_settings(&mut findings);
+        check_orphaned_plugins(&mut findings);
+        if let Some(project) = project_path.as_deref() {
+            check_gitignore(Path::new(project), &mut findings);
+        }
+
+        let count = |severity: &str| findings.iter().filter(|f| f.severity == severity).count();
+        DoctorReport { claude_version, errors: count("error"), warnings: count("warning"), findings }
+    })
+    .await
+}
@@ -0,0 +1,212 @@
+//! Near-identical skills, commands and agents across scopes, as left behind by copying between
+//! projects. Bodies (frontmatter aside) are compared with whitespace and case ignored: equal
+//! hashes are exact copies, and otherwise the overlap of three-word runs tells edited copies
+//! apart from items that only look alike.
+
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::path::Path;
+use tauri::State;
+
+use super::cleanup::{deletable, keep_order};
+use super::inventory::{cached_or_scan, memory_cached_scan, InventoryCacheState};
+use super::inventory_export::item_scope;
+use crate::blocking::run_blocking;
+use crate::scanner::frontmatter::Frontmatter;
+use crate::scanner::skills::find_skill_md;
+use crate::types::{InventoryItem, ItemSource};
+
+/// Share of three-word runs two bodies need in common to count as copies of each other
+const DEFAULT_THRESHOLD: f64 = 0.85;
+
+/// Shorter bodies only match exactly; a few words in common says nothing
+const MIN_FUZZY_WORDS: usize = 20;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DuplicateMember {
+    pub item: InventoryItem,
+    pub scope: String,
+    /// How much of the body it shares with the canonical item, 0.0-1.0
+    pub similarity: f64,
+    /// The app can delete it (user and project commands, agents and skills)
+    pub deletable: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DuplicateCluster {
+    /// The item suggested to keep
+    pub canonical: String,
+    /// Canonical item first
+    pub items: Vec<DuplicateMember>,
+    /// Every member has exactly the same body
+    pub exact: bool,
+    /// Tokens saved by deleting the deletable copies
+    pub token_savings: u32,
+}
+
+struct Body {
+    hash: String,
+    shingles: HashSet<u64>,
+    words: usize,
+}
+
+fn read_body(item: &InventoryItem) -> Option<String> {
+    let path = Path::new(&item.source_path);
+    let file = if path.is_dir() { find_skill_md(path)? } else { path.to_path_buf() };
+    fs::read_to_string(file).ok()
+}
+
+fn body(content: &str) -> Option<Body> {
+    let normalized = Frontmatter::parse(content).body().to_lowercase();
+    let words: Vec<&str> = normalized.split_whitespace().collect();
+    if words.is_empty() {
+        return None;
+    }
+    let shingles = words
+        .windows(3.min(words.len()))
+        .map(|run| {
+            let mut hasher = DefaultHasher::new();
+            run.hash(&mut hasher);
+            hasher.finish()
+        })
+        .collect();
+    Some(Body { hash: format!("{:x}", Sha256::digest(words.join(" "))), shingles, words: words.len() })
+}
+
+fn similarity(a: &Body, b: &Body) -> f64 {
+    if a.hash == b.hash {
+        return 1.0;
+    }
+    if a.words < MIN_FUZZY_WORDS || b.words < MIN_FUZZY_WORDS {
+        return 0.0;
+    }
+    let common = a.shingles.intersection(&b.shingles).count();
+    common as f64 / (a.shingles.len() + b.shingles.len() - common) as f64
+}
+
+fn root(parents: &mut [usize], i: usize) -> usize {
+    let mut i = i;
+    while parents[i] != i {
+        parents[i] = parents[parents[i]];
+        i = parents[i];
+    }
+    i
+}
+
+fn clusters(entries: Vec<(InventoryItem, Body)>, threshold: f64) -> Vec<DuplicateCluster> {
+    let mut parents: Vec<usize> = (0..entries.len()).collect();
+    for i in 0..entries.len() {
+        for j in i + 1..entries.len() {
+            let (a, b) = (&entries[i].1, &entries[j].1);
+            // Overlap can't exceed the smaller set's share of the larger
+            let sizes = a.shingles.len().min(b.shingles.len()) as f64 / a.shingles.len().max(b.shingles.len()) as f64;
+            if a.hash != b.hash && sizes < threshold {
+                continue;
+            }
+            if similarity(a, b) >= threshold {
+                let (x, y) = (root(&mut parents, i), root(&mut parents, j));
+                parents[x] = y;
+            }
+        }
+    }
+
+    let mut groups: HashMap<usize, Vec<usize>> = HashMap::new();
+    for i in 0..entries.len() {
+        groups.entry(root(&mut parents, i)).or_default().push(i);
+    }
+    let mut result: Vec<DuplicateCluster> = groups
+        .into_values()
+        .filter(|members| members.len() > 1)
+        .map(|mut members| {
+            members.sort_by(|&a, &b| keep_order(&entries[a].0, &entries[b].0));
+            let canonical = &entries[members[0]];
+            let items: Vec<DuplicateMember> = members
+                .iter()
+                .map(|&i| {
+                    let (item, body) = &entries[i];
+                    DuplicateMember {
+                        item: item.clone(),
+                        scope: item_scope(item),
+                        similarity: similarity(&canonical.1, body),
+                        deletable: deletable(item),
+                    }
+                })
+                .collect();
+            DuplicateCluster {
+                canonical: canonical.0.id.clone(),
+                exact: members.iter().all(|&i| entries[i].1.hash == canonical.1.hash),
+                token_savings: items[1..].iter().filter(|m| m.deletable).map(|m| m.item.token_weight).sum(),
+                items,
+            }
+        })
+        .collect();
+    result.sort_by(|a, b| b.token_savings.cmp(&a.token_savings).then(a.canonical.cmp(&b.canonical)));
+    result
+}
+
+/// Clusters of skills, commands and agents whose bodies are the same or nearly so, wherever they
+/// live, each with the copy suggested to keep. `threshold` is the share of the body two items
+/// need in common (default 0.85).
+#[tauri::command]
+pub async fn find_duplicates(
+    state: State<'_, InventoryCacheState>,
+    project_path: Option<String>,
+    threshold: Option<f64>,
+) -> Result<Vec<DuplicateCluster>, String> {
+    let threshold = threshold.unwrap_or(DEFAULT_THRESHOLD);
+    if threshold <= 0.0 || threshold > 1.0 {
+        return Err("The threshold is a share between 0 and 1".to_string());
+    }
+    let cached = memory_cached_scan(&state, &project_path)?;
+    run_blocking(move || {
+        let entries = cached_or_scan(cached, project_path.as_deref())
+            .items
+            .into_iter()
+            .filter(|i| matches!(i.source, ItemSource::Skill | ItemSource::Command | ItemSource::Subagent))
+            .filter_map(|item| {
+                let body = body(&read_body(&item)?)?;
+                Some((item, body))
+            })
+            .collect();
+        clusters(entries, threshold)
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{test_item, ItemType};
+
+    fn item(id: &str, source: ItemSource) -> InventoryItem {
+        InventoryItem { item_type: ItemType::Spell, source, token_weight: 1000, ..test_item(id) }
+    }
+
+    #[test]
+    fn clusters_near_identical_bodies() {
+        let text = "Review the staged diff for bugs, missing tests and unclear names. Point at the exact lines, \
+                    suggest a fix for each problem, and finish with a short summary of what must change first.";
+        let edited = format!("---\nname: review\n---\n{}  Keep it brief.", text.to_uppercase());
+        let entries = vec![
+            (item("cmd_project_review", ItemSource::Command), body(text).unwrap()),
+            (item("skill_user_review", ItemSource::Skill), body(&edited).unwrap()),
+            (item("cmd_plugin-tools_review", ItemSource::Command), body(text).unwrap()),
+            (item("cmd_user_deploy", ItemSource::Command), body("Deploy the app to staging and run the smoke tests.").unwrap()),
+        ];
+
+        let found = clusters(entries, DEFAULT_THRESHOLD);
+        assert_eq!(found.len(), 1);
+        let cluster = &found[0];
+        // The plugin copy can't be deleted, so it is the one to keep
+        assert_eq!(cluster.canonical, "cmd_plugin-tools_review");
+        assert_eq!(cluster.items.len(), 3);
+        assert!(!cluster.exact);
+        assert_eq!(cluster.token_savings, 2000);
+        assert!(cluster.items.iter().all(|m| m.similarity >= DEFAULT_THRESHOLD));
+    }
+}
@@ -1,17 +1,35 @@
 use crate::scanner::{enable_plugin, disable_plugin, scan_plugins};
+use crate::scanner::settings::{read_mcp_servers, read_settings};
+use crate::scanner::permissions::{collect_rules, denying_rule, PermissionScope};
+use crate::scanner::plugin::{claude_config_dir, installed_plugin_path, installed_plugin_paths};
+use crate::scanner::plugin_contents::{plugin_components, read_plugin_manifest};
 use crate::types::{EquipmentSlot, EquipResult, ContextStats};
+use crate::blocking::run_blocking;
+use std::path::Path;
+use super::statusline::refresh_statusline_load;
 
 /// Calculate context stats from current enabled plugins
-fn calculate_context_stats() -> ContextStats {
+pub(crate) fn calculate_context_stats() -> ContextStats {
+    context_stats_with(&[])
+}
+
+/// Context stats as they would be with `changes` (plugin id, enabled) applied, later entries winning
+pub(crate) fn context_stats_with(changes: &[(String, bool)]) -> ContextStats {
     let result = scan_plugins();
 
     let equipped_tokens: u32 = result.items
         .iter()
-        .filter(|item| item.enabled)
+        .filter(|item| changes.iter().rev().find(|(id, _)| *id == item.id).map_or(item.enabled, |(_, enabled)| *enabled))
         .map(|item| item.token_weight)
         .sum();
 
-    let total_budget: u32 = 200_000;
+    context_stats_for_tokens(equipped_tokens)
+}
+
+/// Build context stats (load and health) for a given number of equipped tokens,
+/// against the context window of the model in settings.json
+pub(crate) fn context_stats_for_tokens(equipped_tokens: u32) -> ContextStats {
+    let total_budget = super::models::context_budget(read_settings().ok().and_then(|s| s.model).as_deref());
     let load_percentage = equipped_tokens as f64 / total_budget as f64;
 
     let status = if load_percentage < 0.25 {
@@ -31,41 +49,144 @@ fn calculate_context_stats() -> ContextStats {
     }
 }
 
-/// Equip an item (enable a plugin)
+/// Warning for a context load in the heavy or dumbzone range
+pub(crate) fn load_warning(stats: &ContextStats) -> Option<String> {
+    match stats.status.as_str() {
+        "heavy" => Some("Context is getting heavy. Consider unequipping some items.".to_string()),
+        "dumbzone" => Some("DUMBZONE! Claude's performance will degrade significantly.".to_string()),
+        _ => None,
+    }
+}
+
+/// Warnings for tools a plugin's commands, agents, skills and MCP servers need but the user's
+/// permissions deny, naming the rule and the file to remove it from
+pub(crate) fn permission_warnings(plugin_id: &str) -> Vec<String> {
+    let Some(root) = installed_plugin_path(plugin_id) else {
+        return Vec::new();
+    };
+    let mut ignored = Vec::new();
+    let manifest = read_plugin_manifest(&root, &mut ignored);
+    let rules = collect_rules(&PermissionScope::ALL, None).rules;
+
+    let mut warnings = Vec::new();
+    for component in plugin_components(&root, &manifest, &mut ignored) {
+        for tool in &component.tools {
+            if let Some(rule) = denying_rule(tool, &rules) {
+                let warning = format!(
+                    "The {} \"{}\" uses {}, but \"{}\" in {} denies it. Remove that deny rule for it to work.",
+                    component.kind, component.name, tool, rule.rule, rule.source_path
+                );
+                if !warnings.contains(&warning) {
+                    warnings.push(warning);
+                }
+            }
+        }
+    }
+    warnings
+}
+
+/// Commands, agents, skills and MCP servers of a plugin that clash with those of the other plugins
+/// in `enabled` or the user's own config. Plugin commands stay reachable as `/plugin:name`, but
+/// the plain name goes to only one of them.
+pub(crate) fn plugin_conflicts(plugin_id: &str, enabled: &[String]) -> Vec<String> {
+    let paths = installed_plugin_paths();
+    let Some(root) = paths.get(plugin_id) else {
+        return Vec::new();
+    };
+    let mut ignored = Vec::new();
+    let components = |root: &Path, ignored: &mut Vec<String>| {
+        let manifest = read_plugin_manifest(root, ignored);
+        plugin_components(root, &manifest, ignored)
+    };
+    let own = components(root, &mut ignored);
+
+    let mut conflicts = Vec::new();
+    for other_id in enabled.iter().filter(|id| *id != plugin_id) {
+        let Some(other_root) = paths.get(other_id) else {
+            continue;
+        };
+        for other in components(other_root, &mut ignored) {
+            if other.kind != "hook" && own.iter().any(|c| c.kind == other.kind && c.name == other.name) {
+                conflicts.push(format!("The {} \"{}\" is also provided by {}", other.kind, other.name, other_id));
+            }
+        }
+    }
+
+    let user_dir = claude_config_dir();
+    let mcp_servers = read_mcp_servers().unwrap_or_default();
+    for component in &own {
+        let user_path = user_dir.as_ref().and_then(|dir| match component.kind.as_str() {
+            "command" => Some(dir.join("commands").join(format!("{}.md", component.name))),
+            "agent" => Some(dir.join("agents").join(format!("{}.md", component.name))),
+            "skill" => Some(dir.join("skills").join(&component.name)),
+            _ => None,
+        });
+        if user_path.is_some_and(|p| p.exists()) || (component.kind == "mcpServer" && mcp_servers.contains_key(&component.name)) {
+            conflicts.push(format!("The {} \"{}\" is also in your own config", component.kind, component.name));
+        }
+    }
+    conflicts
+}
+
+/// Equip an item (enable a plugin). With `dry_run`, nothing is written and the result shows the
+/// context load, warnings and conflicts equipping would bring.
 #[tauri::command]
+#[tracing::instrument(skip(_slot), err)]
 pub async fn equip_item(
     item_id: String,
     _slot: EquipmentSlot,
+    dry_run: Option<bool>,
 ) -> Result<EquipResult, String> {
-    // Enable the plugin in settings.json
-    enable_plugin(&item_id)?;
-
-    // Calculate new context stats
-    let new_context_stats = calculate_context_stats();
+    let dry_run = dry_run.unwrap_or(false);
+    run_blocking(move || {
+        let new_context_stats = if dry_run {
+            // Equipping would only write settings.json, which accepts any id; the preview checks it
+            if installed_plugin_path(&item_id).is_none() {
+                return Err(format!("Plugin not found: {}", item_id));
+            }
+            context_stats_with(&[(item_id.clone(), true)])
+        } else {
+            // Enable the plugin in settings.json
+            enable_plugin(&item_id)?;
+            let stats = calculate_context_stats();
+            refresh_statusline_load(&stats);
+            stats
+        };
 
-    // Generate warnings if entering heavy/dumbzone
-    let mut warnings = Vec::new();
-    if new_context_stats.status == "heavy" {
-        warnings.push("Context is getting heavy. Consider unequipping some items.".to_string());
-    } else if new_context_stats.status == "dumbzone" {
-        warnings.push("DUMBZONE! Claude's performance will degrade significantly.".to_string());
-    }
+        // Generate warnings if entering heavy/dumbzone
+        let mut warnings: Vec<String> = load_warning(&new_context_stats).into_iter().collect();
+        warnings.extend(permission_warnings(&item_id));
 
-    Ok(EquipResult {
-        success: true,
-        new_context_stats,
-        warnings,
+        let mut enabled: Vec<String> = read_settings()?.enabled_plugins.into_iter().filter(|(_, on)| *on).map(|(id, _)| id).collect();
+        // A preview checks against the plugins enabled once this one is
+        if !enabled.contains(&item_id) {
+            enabled.push(item_id.clone());
+        }
+        Ok(EquipResult {
+            success: true,
+            new_context_stats,
+            warnings,
+            conflicts: plugin_conflicts(&item_id, &enabled),
+            dry_run,
+        })
     })
+    .await?
 }
 
 /// Unequip an item (disable a plugin)
 #[tauri::command]
+#[tracing::instrument(err)]
 pub async fn unequip_item(
     item_id: String,
 ) -> Result<ContextStats, String> {
-    // Disable the plugin in settings.json
-    disable_plugin(&item_id)?;
+    run_blocking(move || {
+        // Disable the plugin in settings.json
+        disable_plugin(&item_id)?;
 
-    // Return new context stats
-    Ok(calculate_context_stats())
+        // Return new context stats
+        let stats = calculate_context_stats();
+        refresh_statusline_load(&stats);
+        Ok(stats)
+    })
+    .await?
 }
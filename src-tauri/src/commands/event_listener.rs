@@ -0,0 +1,101 @@
+use serde::Serialize;
+use tauri::{AppHandle, State};
+
+use crate::arcade_settings::{load_arcade_settings, save_arcade_settings, ArcadeSettings};
+use crate::blocking::run_blocking;
+use crate::event_listener::{start, stop, EventListenerState};
+use crate::otel::{foreign_exporter, remove_export_env};
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EventListenerStatus {
+    pub enabled: bool,
+    /// Claude Code's OpenTelemetry metrics are sent to the listener
+    pub otel_metrics: bool,
+    /// Set while listening
+    pub port: Option<u16>,
+    /// ~/.claude/arcade_endpoint.json, where scripts find the port and token
+    pub endpoint_file: Option<String>,
+}
+
+fn status(state: &EventListenerState, settings: &ArcadeSettings) -> Result<EventListenerStatus, String> {
+    let port = state.0.lock().map_err(|e| format!("Lock error: {}", e))?.as_ref().map(|l| l.port);
+    Ok(EventListenerStatus {
+        enabled: settings.event_listener,
+        otel_metrics: settings.otel_metrics,
+        port,
+        endpoint_file: port
+            .and(crate::paths::claude_dir())
+            .map(|d| d.join("arcade_endpoint.json").to_string_lossy().to_string()),
+    })
+}
+
+/// Whether the localhost event endpoint is on, and its port
+#[tauri::command]
+pub async fn get_event_listener_status(state: State<'_, EventListenerState>) -> Result<EventListenerStatus, String> {
+    let settings = run_blocking(load_arcade_settings).await?;
+    status(&state, &settings)
+}
+
+/// Turn the localhost event endpoint on or off; it stays that way across restarts.
+/// Turning it on again picks a new port and token. While it is off, Claude Code isn't told to
+/// send metrics to it.
+#[tauri::command]
+#[tracing::instrument(skip(app_handle, state), err)]
+pub async fn set_event_listener_enabled(
+    app_handle: AppHandle,
+    state: State<'_, EventListenerState>,
+    enabled: bool,
+) -> Result<EventListenerStatus, String> {
+    let settings = run_blocking(move || {
+        let mut settings = load_arcade_settings();
+        settings.event_listener = enabled;
+        save_arcade_settings(&settings)?;
+        if !enabled && settings.otel_metrics {
+            remove_export_env()?;
+        }
+        Ok::<_, String>(settings)
+    })
+    .await??;
+
+    if enabled {
+        start(app_handle).await?;
+    } else {
+        stop(&state)?;
+    }
+    status(&state, &settings)
+}
+
+/// Have Claude Code send its OpenTelemetry metrics (exact tokens, cost, edit decisions) to the
+/// event listener, turning the listener on if needed. Applies to Claude Code sessions started
+/// afterwards. Refused when settings.json already exports metrics somewhere else.
+#[tauri::command]
+#[tracing::instrument(skip(app_handle, state), err)]
+pub async fn set_otel_metrics_enabled(
+    app_handle: AppHandle,
+    state: State<'_, EventListenerState>,
+    enabled: bool,
+) -> Result<EventListenerStatus, String> {
+    let settings = run_blocking(move || {
+        if enabled {
+            if let Some(endpoint) = foreign_exporter()? {
+                return Err(format!("Claude Code already exports metrics to {}", endpoint));
+            }
+        }
+        let mut settings = load_arcade_settings();
+        settings.otel_metrics = enabled;
+        settings.event_listener |= enabled;
+        save_arcade_settings(&settings)?;
+        if !enabled {
+            remove_export_env()?;
+        }
+        Ok(settings)
+    })
+    .await??;
+
+    // Starting (again) writes the exporter env with the current port and token
+    if enabled {
+        start(app_handle).await?;
+    }
+    status(&state, &settings)
+}
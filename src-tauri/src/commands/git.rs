@@ -0,0 +1,193 @@
+//! Git metadata for projects, read through the git CLI
+//! Includes the tracked/ignored state of Claude config files so the UI can flag risky setups.
+
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use crate::blocking::run_blocking;
+use crate::process::ProcessRequest;
+
+/// A worktree attached to the repository
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GitWorktree {
+    pub path: String,
+    pub head: Option<String>,
+    pub branch: Option<String>,
+}
+
+/// Git state of one Claude config file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClaudeFileGitStatus {
+    /// Path relative to the project root
+    pub path: String,
+    pub exists: bool,
+    pub tracked: bool,
+    pub ignored: bool,
+    /// Has uncommitted changes (or is untracked and not ignored)
+    pub uncommitted: bool,
+}
+
+/// Git metadata for a project
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct GitInfo {
+    pub is_repo: bool,
+    pub branch: Option<String>,
+    pub head_commit: Option<String>,
+    pub dirty: bool,
+    pub changed_files: u32,
+    pub remote_url: Option<String>,
+    pub worktrees: Vec<GitWorktree>,
+    pub claude_files: Vec<ClaudeFileGitStatus>,
+    pub warnings: Vec<String>,
+}
+
+/// Claude config files whose git state is reported
+const CLAUDE_FILES: [&str; 6] = [
+    "CLAUDE.md",
+    "CLAUDE.local.md",
+    ".claude/CLAUDE.md",
+    ".claude/settings.json",
+    ".claude/settings.local.json",
+    ".mcp.json",
+];
+
+/// Personal files that should never be committed
+pub(crate) const PERSONAL_FILES: [&str; 2] = ["CLAUDE.local.md", ".claude/settings.local.json"];
+
+/// Run git in a directory, returning trimmed stdout on success
+pub(crate) fn git(project: &Path, args: &[&str]) -> Option<String> {
+    let output = ProcessRequest::new("git").arg("-C").arg(project).args(args).run().ok()?;
+
+    if output.success() {
+        Some(output.stdout_text().trim_end().to_string())
+    } else {
+        None
+    }
+}
+
+/// Run git and report only whether it succeeded
+pub(crate) fn git_succeeds(project: &Path, args: &[&str]) -> bool {
+    git(project, args).is_some()
+}
+
+fn parse_worktrees(porcelain: &str) -> Vec<GitWorktree> {
+    let mut worktrees = Vec::new();
+
+    for block in porcelain.split("\n\n").filter(|b| !b.trim().is_empty()) {
+        let mut worktree = GitWorktree { path: String::new(), head: None, branch: None };
+        for line in block.lines() {
+            if let Some(path) = line.strip_prefix("worktree ") {
+                worktree.path = path.to_string();
+            } else if let Some(head) = line.strip_prefix("HEAD ") {
+                worktree.head = Some(head.to_string());
+            } else if let Some(branch) = line.strip_prefix("branch ") {
+                worktree.branch = Some(branch.trim_start_matches("refs/heads/").to_string());
+            }
+        }
+        if !worktree.path.is_empty() {
+            worktrees.push(worktree);
+        }
+    }
+
+    worktrees
+}
+
+/// Paths changed according to `git status --porcelain`, which are relative to the repository root
+/// whatever folder git runs in. Lines are "XY path" (renames are "XY old -> new").
+fn changed_paths(porcelain: &str) -> Vec<String> {
+    porcelain
+        .lines()
+        .filter(|l| l.len() > 3)
+        .map(|l| {
+            let path = &l[3..];
+            path.rsplit(" -> ").next().unwrap_or(path).trim_matches('"').to_string()
+        })
+        .collect()
+}
+
+/// Whether a project file is among the changed paths. `prefix` is the project's folder inside the
+/// repository (`git rev-parse --show-prefix`, empty or ending in `/`); a changed path ending in `/` is
+/// an untracked folder and covers everything under it.
+fn is_changed(changed: &[String], prefix: &str, file: &str) -> bool {
+    let path = format!("{}{}", prefix, file);
+    changed.iter().any(|c| *c == path || (c.ends_with('/') && path.starts_with(c.as_str())))
+}
+
+/// Get branch, dirty state, remote, worktrees and Claude file status for a project
+#[tauri::command]
+pub async fn get_git_info(project_path: String) -> Result<GitInfo, String> {
+    run_blocking(move || {
+        let project = Path::new(&project_path);
+        if !project.exists() {
+            return Err(format!("Project path does not exist: {}", project_path));
+        }
+
+        if !git_succeeds(project, &["rev-parse", "--is-inside-work-tree"]) {
+            return Ok(GitInfo::default());
+        }
+
+        let mut info = GitInfo {
+            is_repo: true,
+            branch: git(project, &["symbolic-ref", "--short", "-q", "HEAD"]).filter(|b| !b.is_empty()),
+            head_commit: git(project, &["rev-parse", "--short", "HEAD"]),
+            remote_url: git(project, &["remote", "get-url", "origin"])
+                .or_else(|| {
+                    // Fall back to whichever remote comes first
+                    git(project, &["remote"])
+                        .and_then(|remotes| remotes.lines().next().map(String::from))
+                        .and_then(|remote| git(project, &["remote", "get-url", &remote]))
+                }),
+            worktrees: git(project, &["worktree", "list", "--porcelain"])
+                .map(|out| parse_worktrees(&out))
+                .unwrap_or_default(),
+            ..Default::default()
+        };
+
+        let changed = changed_paths(&git(project, &["status", "--porcelain"]).unwrap_or_default());
+        // The project may be a folder inside the repository
+        let prefix = git(project, &["rev-parse", "--show-prefix"]).unwrap_or_default();
+        info.changed_files = changed.len() as u32;
+        info.dirty = !changed.is_empty();
+
+        for file in CLAUDE_FILES {
+            let exists = project.join(file).exists();
+            let tracked = git_succeeds(project, &["ls-files", "--error-unmatch", file]);
+            let ignored = !tracked && git_succeeds(project, &["check-ignore", "-q", file]);
+            let uncommitted = exists && !ignored && is_changed(&changed, &prefix, file);
+
+            if exists && PERSONAL_FILES.contains(&file) && !ignored {
+                info.warnings.push(format!("{} is not gitignored and could be committed", file));
+            } else if uncommitted && tracked {
+                info.warnings.push(format!("{} has uncommitted changes", file));
+            }
+
+            info.claude_files.push(ClaudeFileGitStatus {
+                path: file.to_string(),
+                exists,
+                tracked,
+                ignored,
+                uncommitted,
+            });
+        }
+
+        Ok(info)
+    })
+    .await?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_changes_by_whole_path_from_the_repo_root() {
+        let changed = changed_paths(" M app/CLAUDE.md\n?? app/.claude/\n M .claude-foo/settings.json\nR  old.md -> app/.mcp.json\n");
+        assert!(is_changed(&changed, "app/", "CLAUDE.md"));
+        assert!(is_changed(&changed, "app/", ".claude/settings.json"));
+        assert!(is_changed(&changed, "app/", ".mcp.json"));
+        assert!(!is_changed(&changed, "", "CLAUDE.md"));
+        assert!(!is_changed(&changed, "", ".claude/settings.json"));
+    }
+}
@@ -0,0 +1,116 @@
+//! Try a hook command before trusting it with real sessions
+//! The command gets a sample payload for its event on stdin, the way Claude Code runs hooks, and
+//! its exit code is read the same way: 0 goes ahead, 2 blocks, anything else is a non-blocking error.
+
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::time::Duration;
+
+use crate::blocking::run_blocking;
+use crate::error::ArcadeError;
+use crate::process::ProcessRequest;
+
+/// Claude Code's own default for hooks without a timeout
+const DEFAULT_HOOK_TIMEOUT_SECS: u64 = 60;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HookTestResult {
+    /// success, blocked, error, timedOut or cancelled
+    pub outcome: String,
+    pub exit_code: Option<i32>,
+    pub stdout: String,
+    pub stderr: String,
+    /// Stdout parsed, when the hook answered with JSON (decision, reason, ...)
+    pub json_output: Option<Value>,
+    pub truncated: bool,
+    pub duration_ms: u64,
+}
+
+/// What Claude Code sends for `event`, with made-up but plausible values
+fn sample_payload(event: &str, cwd: &str) -> Value {
+    let mut payload = json!({
+        "session_id": "arcade-hook-test",
+        "transcript_path": "",
+        "cwd": cwd,
+        "hook_event_name": event,
+    });
+    let extra = match event {
+        "PreToolUse" => json!({ "tool_name": "Bash", "tool_input": { "command": "ls" } }),
+        "PostToolUse" => json!({ "tool_name": "Bash", "tool_input": { "command": "ls" }, "tool_response": { "stdout": "" } }),
+        "UserPromptSubmit" => json!({ "prompt": "Hello from ClaudeArcade" }),
+        "Notification" => json!({ "message": "Claude needs your permission to use Bash" }),
+        "Stop" | "SubagentStop" => json!({ "stop_hook_active": false }),
+        "PreCompact" => json!({ "trigger": "manual", "custom_instructions": "" }),
+        "SessionStart" => json!({ "source": "startup" }),
+        "SessionEnd" => json!({ "reason": "other" }),
+        _ => json!({}),
+    };
+    if let (Some(payload), Value::Object(extra)) = (payload.as_object_mut(), extra) {
+        payload.extend(extra);
+    }
+    payload
+}
+
+fn shell(command: &str) -> ProcessRequest {
+    if cfg!(windows) {
+        ProcessRequest::new("cmd").args(["/C", command])
+    } else {
+        ProcessRequest::new("sh").args(["-c", command])
+    }
+}
+
+/// Run a hook command once with a sample `event` payload, merged with `payload` when given.
+/// It runs in the project (or home) folder with CLAUDE_PROJECT_DIR set, and can be cancelled
+/// from `cancel_process` as `hook-test`.
+#[tauri::command]
+#[tracing::instrument(skip(payload), err)]
+pub async fn test_hook(
+    command: String,
+    event: String,
+    payload: Option<Value>,
+    project_path: Option<String>,
+    timeout_secs: Option<u64>,
+) -> Result<HookTestResult, ArcadeError> {
+    run_blocking(move || {
+        if command.trim().is_empty() {
+            return Err(ArcadeError::invalid_input("The hook command is empty"));
+        }
+        let cwd = project_path
+            .clone()
+            .or_else(|| crate::paths::home_dir().map(|h| h.to_string_lossy().to_string()))
+            .ok_or("Could not find home directory")?;
+        let mut input = sample_payload(&event, &cwd);
+        if let (Some(input), Some(Value::Object(overrides))) = (input.as_object_mut(), payload) {
+            input.extend(overrides);
+        }
+
+        let output = shell(&command)
+            .current_dir(&cwd)
+            .envs([("CLAUDE_PROJECT_DIR".to_string(), cwd.clone())])
+            .stdin(input.to_string())
+            .timeout(Duration::from_secs(timeout_secs.unwrap_or(DEFAULT_HOOK_TIMEOUT_SECS)))
+            .id("hook-test")
+            .run()
+            .map_err(|e| ArcadeError::Other { message: format!("Failed to start the hook: {}", e) })?;
+
+        let outcome = match output.exit_code {
+            _ if output.cancelled => "cancelled",
+            _ if output.timed_out => "timedOut",
+            Some(0) => "success",
+            Some(2) => "blocked",
+            _ => "error",
+        };
+        let stdout = output.stdout_text();
+        Ok(HookTestResult {
+            outcome: outcome.to_string(),
+            exit_code: output.exit_code,
+            json_output: serde_json::from_str(stdout.trim()).ok().filter(Value::is_object),
+            stdout,
+            stderr: output.stderr_text(),
+            truncated: output.truncated,
+            duration_ms: output.duration.as_millis() as u64,
+        })
+    })
+    .await?
+}
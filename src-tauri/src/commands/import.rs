@@ -0,0 +1,804 @@
+//! Bring rules, personas and MCP servers from other AI coding tools into Claude's config
+//! Every import is planned first (what would be created, with a diff) and only written when applied.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+use super::agents_md::GENERATED_MARKER;
+use crate::scanner::plugin::claude_config_dir;
+use crate::scanner::settings::{install_mcp_server_config, read_mcp_servers, MCPServerConfig};
+use crate::blocking::run_blocking;
+
+/// Tools `import_from_tool` understands; "agents-md" covers Codex and other AGENTS.md readers
+pub const IMPORT_TOOLS: [&str; 6] = ["cursor", "cline", "roo", "windsurf", "agents-md", "gemini"];
+
+/// CLAUDE.md tiers imported rules can go to
+pub const MEMORY_TIERS: [&str; 4] = ["project", "projectClaude", "local", "user"];
+
+/// One file an import creates or extends
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportChange {
+    /// Relative to the project root, or absolute for ~/.claude/CLAUDE.md; MCP servers go to
+    /// ~/.claude/settings.json
+    pub path: String,
+    /// "claudeMd", "command", "agent" or "mcpServer"
+    pub kind: String,
+    /// MCP server name, for mcpServer changes
+    pub name: Option<String>,
+    /// "create", "append", "merge" (into settings) or "skip" (already imported or the file exists)
+    pub action: String,
+    /// Files in the other tool's config this came from
+    pub sources: Vec<String>,
+    /// Text written (create) or added to the end (append)
+    pub content: String,
+    /// Unified diff against the file as it is now
+    pub diff: String,
+}
+
+/// What importing from another tool would do, or did
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportPlan {
+    pub tool: String,
+    pub project_path: String,
+    pub changes: Vec<ImportChange>,
+    pub warnings: Vec<String>,
+    /// False for a preview
+    pub applied: bool,
+}
+
+/// An AGENTS.md or GEMINI.md that could be merged into CLAUDE.md
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MemoryImportCandidate {
+    /// "agents-md" or "gemini", for `import_from_tool`
+    pub tool: String,
+    pub source: String,
+    /// Tier the content goes to unless another is picked
+    pub suggested_tier: String,
+    /// CLAUDE.md in that tier already has the section
+    pub already_imported: bool,
+}
+
+/// A rule from another tool, before it is mapped onto Claude's config
+#[derive(Default)]
+struct ImportedRule {
+    title: String,
+    /// File name for a command, when the rule is only used on request
+    command_name: Option<String>,
+    description: Option<String>,
+    globs: Vec<String>,
+    body: String,
+    source: String,
+    /// Came from the tool's user-level config, so it belongs in ~/.claude/CLAUDE.md
+    user_level: bool,
+}
+
+/// A persona from another tool (e.g. a Roo mode), imported as a subagent
+struct ImportedAgent {
+    name: String,
+    description: String,
+    /// None inherits every tool
+    tools: Option<Vec<String>>,
+    body: String,
+    source: String,
+}
+
+/// An MCP server definition from another tool
+struct ImportedMcpServer {
+    name: String,
+    config: Value,
+    source: String,
+}
+
+/// Everything read from another tool's config
+#[derive(Default)]
+struct ImportSource {
+    rules: Vec<ImportedRule>,
+    agents: Vec<ImportedAgent>,
+    mcp_servers: Vec<ImportedMcpServer>,
+}
+
+fn relative_path(root: &Path, path: &Path) -> String {
+    path.strip_prefix(root).unwrap_or(path).to_string_lossy().replace('\\', "/")
+}
+
+/// Lowercase, dash-separated file name stem
+fn slugify(name: &str) -> String {
+    let slug: String = name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_lowercase() } else { '-' })
+        .collect();
+    slug.split('-').filter(|s| !s.is_empty()).collect::<Vec<_>>().join("-")
+}
+
+/// Title-case a file stem ("react-style" -> "React Style")
+fn title_from_stem(stem: &str) -> String {
+    stem.split(['-', '_', ' '])
+        .filter(|w| !w.is_empty())
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().collect::<String>() + chars.as_str(),
+                None => String::new(),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Split `key: value` frontmatter from the body. Cursor writes values like `globs: *.ts`
+/// that aren't valid YAML, so lines are read as plain text.
+fn split_frontmatter(content: &str) -> (Vec<(String, String)>, String) {
+    let trimmed = content.trim_start_matches('\u{feff}');
+    let Some(rest) = trimmed.strip_prefix("---") else {
+        return (Vec::new(), content.to_string());
+    };
+    let Some(end) = rest.find("\n---") else {
+        return (Vec::new(), content.to_string());
+    };
+
+    let fields = rest[..end]
+        .lines()
+        .filter_map(|line| line.split_once(':'))
+        .map(|(key, value)| (key.trim().to_string(), value.trim().trim_matches(['"', '\'']).to_string()))
+        .collect();
+    let body = rest[end + 4..].trim_start_matches(['-']).trim().to_string();
+    (fields, body)
+}
+
+/// Globs written as `a, b`, `["a", "b"]` or a single pattern
+fn parse_globs(value: &str) -> Vec<String> {
+    value
+        .trim_matches(['[', ']'])
+        .split(',')
+        .map(|g| g.trim().trim_matches(['"', '\'']).to_string())
+        .filter(|g| !g.is_empty())
+        .collect()
+}
+
+/// Rules from `.cursorrules` and `.cursor/rules/**/*.mdc`
+fn cursor_rules(root: &Path, warnings: &mut Vec<String>) -> Vec<ImportedRule> {
+    let mut rules = Vec::new();
+
+    rules.extend(single_rules_file(root, ".cursorrules", "Cursor Rules"));
+
+    for path in rule_files(&root.join(".cursor").join("rules"), &["mdc", "md"]) {
+        let source = relative_path(root, &path);
+        let content = match fs::read_to_string(&path) {
+            Ok(content) => content,
+            Err(e) => {
+                warnings.push(format!("Failed to read {}: {}", source, e));
+                continue;
+            }
+        };
+        let (fields, body) = split_frontmatter(&content);
+        if body.is_empty() {
+            warnings.push(format!("{} has no rule text; skipped", source));
+            continue;
+        }
+        let field = |key: &str| fields.iter().find(|(k, _)| k == key).map(|(_, v)| v.clone()).filter(|v| !v.is_empty());
+
+        let stem = path.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default();
+        let always_apply = field("alwaysApply").is_some_and(|v| v == "true");
+        let globs = field("globs").map(|g| parse_globs(&g)).unwrap_or_default();
+        let description = field("description");
+
+        // Rules Cursor only pulls in when asked for become commands; the rest are always context
+        let on_request = !always_apply && globs.is_empty() && description.is_some();
+        rules.push(ImportedRule {
+            title: title_from_stem(&stem),
+            command_name: on_request.then(|| slugify(&stem)),
+            description,
+            globs,
+            body,
+            source,
+            user_level: false,
+        });
+    }
+
+    rules
+}
+
+/// Markdown or text files in a rules folder, sorted for a stable order
+fn rule_files(dir: &Path, extensions: &[&str]) -> Vec<PathBuf> {
+    let mut files: Vec<_> = WalkDir::new(dir)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .map(|e| e.into_path())
+        .filter(|p| p.is_file() && p.extension().is_some_and(|e| extensions.iter().any(|x| e == *x)))
+        .collect();
+    files.sort();
+    files
+}
+
+/// A single always-on rules file (`.clinerules`, `.windsurfrules`, ...)
+fn single_rules_file(root: &Path, file_name: &str, title: &str) -> Option<ImportedRule> {
+    let content = fs::read_to_string(root.join(file_name)).ok()?;
+    (!content.trim().is_empty()).then(|| ImportedRule {
+        title: title.to_string(),
+        body: content.trim().to_string(),
+        source: file_name.to_string(),
+        ..Default::default()
+    })
+}
+
+/// Every file in a rules folder as an always-on section titled after the file
+fn folder_rules(root: &Path, dir: &Path, warnings: &mut Vec<String>) -> Vec<ImportedRule> {
+    rule_files(dir, &["md", "txt"])
+        .into_iter()
+        .filter_map(|path| {
+            let source = relative_path(root, &path);
+            match fs::read_to_string(&path) {
+                Ok(content) if !content.trim().is_empty() => Some(ImportedRule {
+                    title: title_from_stem(&path.file_stem()?.to_string_lossy()),
+                    body: split_frontmatter(&content).1,
+                    source,
+                    ..Default::default()
+                }),
+                Ok(_) => None,
+                Err(e) => {
+                    warnings.push(format!("Failed to read {}: {}", source, e));
+                    None
+                }
+            }
+        })
+        .collect()
+}
+
+/// Cline: `.clinerules` is either one file or a folder of rule files
+fn cline_source(root: &Path, warnings: &mut Vec<String>) -> ImportSource {
+    let path = root.join(".clinerules");
+    let rules = if path.is_dir() {
+        folder_rules(root, &path, warnings)
+    } else {
+        single_rules_file(root, ".clinerules", "Cline Rules").into_iter().collect()
+    };
+    ImportSource { rules, ..Default::default() }
+}
+
+/// Claude Code tools for Roo's tool groups
+fn roo_group_tools(group: &str) -> &'static [&'static str] {
+    match group {
+        "read" => &["Read", "Grep", "Glob"],
+        "edit" => &["Edit", "Write", "NotebookEdit"],
+        "command" => &["Bash"],
+        "browser" => &["WebFetch", "WebSearch"],
+        _ => &[],
+    }
+}
+
+/// Roo: `.roorules` and `.roo/rules/` become CLAUDE.md sections, custom modes in `.roomodes`
+/// become subagents (with `.roo/rules-<mode>/` appended), and `.roo/mcp.json` servers are added
+fn roo_source(root: &Path, warnings: &mut Vec<String>) -> ImportSource {
+    let mut source = ImportSource::default();
+    source.rules.extend(single_rules_file(root, ".roorules", "Roo Rules"));
+    source.rules.extend(folder_rules(root, &root.join(".roo").join("rules"), warnings));
+
+    // .roomodes is YAML or JSON (which YAML also reads)
+    let modes: Value = match fs::read_to_string(root.join(".roomodes")) {
+        Ok(content) => serde_yaml::from_str(&content).unwrap_or_else(|e| {
+            warnings.push(format!("Failed to parse .roomodes: {}", e));
+            Value::Null
+        }),
+        Err(_) => Value::Null,
+    };
+    for mode in modes.get("customModes").and_then(|m| m.as_array()).into_iter().flatten() {
+        let field = |key: &str| mode.get(key).and_then(|v| v.as_str()).map(str::trim).filter(|v| !v.is_empty());
+        let Some(slug) = field("slug") else {
+            warnings.push(".roomodes has a mode without a slug; skipped".to_string());
+            continue;
+        };
+
+        let mut body = field("roleDefinition").unwrap_or_default().to_string();
+        if let Some(instructions) = field("customInstructions") {
+            body.push_str("\n\n");
+            body.push_str(instructions);
+        }
+        for rule in folder_rules(root, &root.join(".roo").join(format!("rules-{}", slug)), warnings) {
+            body.push_str(&format!("\n\n## {}\n\n{}", rule.title, rule.body));
+        }
+
+        // Groups are names or [name, options]; "mcp" means every tool, so inherit them all
+        let groups: Vec<&str> = mode
+            .get("groups")
+            .and_then(|g| g.as_array())
+            .into_iter()
+            .flatten()
+            .filter_map(|g| g.as_str().or_else(|| g.get(0).and_then(|n| n.as_str())))
+            .collect();
+        let tools = (!groups.contains(&"mcp")).then(|| {
+            groups.iter().flat_map(|g| roo_group_tools(g)).map(|t| t.to_string()).collect::<Vec<_>>()
+        });
+
+        source.agents.push(ImportedAgent {
+            name: slugify(slug),
+            description: field("whenToUse")
+                .or(field("description"))
+                .or(field("name"))
+                .unwrap_or(slug)
+                .to_string(),
+            tools,
+            body: body.trim().to_string(),
+            source: ".roomodes".to_string(),
+        });
+    }
+
+    source.mcp_servers = mcp_servers_from_file(&root.join(".roo").join("mcp.json"), ".roo/mcp.json", warnings);
+    source
+}
+
+/// Windsurf: `.windsurfrules` and `.windsurf/rules/` (trigger: always_on, glob, model_decision
+/// or manual), plus the MCP servers in ~/.codeium/windsurf/mcp_config.json
+fn windsurf_source(root: &Path, warnings: &mut Vec<String>) -> ImportSource {
+    let mut source = ImportSource::default();
+    source.rules.extend(single_rules_file(root, ".windsurfrules", "Windsurf Rules"));
+
+    for path in rule_files(&root.join(".windsurf").join("rules"), &["md"]) {
+        let relative = relative_path(root, &path);
+        let Ok(content) = fs::read_to_string(&path) else {
+            warnings.push(format!("Failed to read {}", relative));
+            continue;
+        };
+        let (fields, body) = split_frontmatter(&content);
+        if body.is_empty() {
+            continue;
+        }
+        let field = |key: &str| fields.iter().find(|(k, _)| k == key).map(|(_, v)| v.clone()).filter(|v| !v.is_empty());
+        let stem = path.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default();
+        let trigger = field("trigger").unwrap_or_else(|| "always_on".to_string());
+        let on_request = trigger == "manual" || trigger == "model_decision";
+
+        source.rules.push(ImportedRule {
+            title: title_from_stem(&stem),
+            command_name: on_request.then(|| slugify(&stem)),
+            description: field("description").or_else(|| on_request.then(|| title_from_stem(&stem))),
+            globs: if trigger == "glob" { field("globs").map(|g| parse_globs(&g)).unwrap_or_default() } else { Vec::new() },
+            body,
+            source: relative,
+            user_level: false,
+        });
+    }
+
+    if let Some(config) = crate::paths::home_dir().map(|h| h.join(".codeium").join("windsurf").join("mcp_config.json")) {
+        source.mcp_servers = mcp_servers_from_file(&config, "~/.codeium/windsurf/mcp_config.json", warnings);
+    }
+    source
+}
+
+/// Markdown headings one level deeper, so a whole file nests under its import section
+fn demote_headings(body: &str) -> String {
+    let mut in_fence = false;
+    body.lines()
+        .map(|line| {
+            let trimmed = line.trim_start();
+            if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+                in_fence = !in_fence;
+            }
+            let is_heading = trimmed.starts_with('#') && trimmed.trim_start_matches('#').starts_with(' ');
+            if !in_fence && is_heading && !trimmed.starts_with("######") {
+                format!("#{}", trimmed)
+            } else {
+                line.to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// A memory file another agent reads (AGENTS.md, GEMINI.md), imported whole as one section
+fn memory_file_rule(path: &Path, source: &str, user_level: bool, warnings: &mut Vec<String>) -> Option<ImportedRule> {
+    let content = fs::read_to_string(path).ok()?;
+    if content.starts_with(GENERATED_MARKER) {
+        warnings.push(format!("Skipped {}: ClaudeArcade generated it from CLAUDE.md", source));
+        return None;
+    }
+    (!content.trim().is_empty()).then(|| ImportedRule {
+        title: format!("Imported from {}", source),
+        body: demote_headings(content.trim()),
+        source: source.to_string(),
+        user_level,
+        ..Default::default()
+    })
+}
+
+/// AGENTS.md: the project's file, plus Codex's user-level ~/.codex/AGENTS.md
+fn agents_md_source(root: &Path, warnings: &mut Vec<String>) -> ImportSource {
+    let mut source = ImportSource::default();
+    source.rules.extend(memory_file_rule(&root.join("AGENTS.md"), "AGENTS.md", false, warnings));
+    if let Some(home) = crate::paths::home_dir() {
+        let codex = home.join(".codex").join("AGENTS.md");
+        source.rules.extend(memory_file_rule(&codex, "~/.codex/AGENTS.md", true, warnings));
+    }
+    source
+}
+
+/// Gemini CLI: GEMINI.md in the project and ~/.gemini, and MCP servers from its settings.json
+fn gemini_source(root: &Path, warnings: &mut Vec<String>) -> ImportSource {
+    let mut source = ImportSource::default();
+    source.rules.extend(memory_file_rule(&root.join("GEMINI.md"), "GEMINI.md", false, warnings));
+    source.mcp_servers = mcp_servers_from_file(&root.join(".gemini").join("settings.json"), ".gemini/settings.json", warnings);
+
+    if let Some(gemini_dir) = crate::paths::home_dir().map(|h| h.join(".gemini")) {
+        source.rules.extend(memory_file_rule(&gemini_dir.join("GEMINI.md"), "~/.gemini/GEMINI.md", true, warnings));
+        // A project server wins over a user one with the same name
+        for server in mcp_servers_from_file(&gemini_dir.join("settings.json"), "~/.gemini/settings.json", warnings) {
+            if !source.mcp_servers.iter().any(|s| s.name == server.name) {
+                source.mcp_servers.push(server);
+            }
+        }
+    }
+    source
+}
+
+/// `{ "mcpServers": { ... } }` from another tool. Only command-based servers carry over;
+/// remote ones need their transport checked by hand.
+fn mcp_servers_from_file(path: &Path, label: &str, warnings: &mut Vec<String>) -> Vec<ImportedMcpServer> {
+    let Ok(content) = fs::read_to_string(path) else {
+        return Vec::new();
+    };
+    let config: Value = match serde_json::from_str(&content) {
+        Ok(config) => config,
+        Err(e) => {
+            warnings.push(format!("Failed to parse {}: {}", label, e));
+            return Vec::new();
+        }
+    };
+
+    let mut servers = Vec::new();
+    for (name, server) in config.get("mcpServers").and_then(|s| s.as_object()).into_iter().flatten() {
+        if server.get("disabled").and_then(|d| d.as_bool()).unwrap_or(false) {
+            continue;
+        }
+        if server.get("command").and_then(|c| c.as_str()).is_none() {
+            warnings.push(format!("MCP server '{}' in {} is remote; add it by hand", name, label));
+            continue;
+        }
+        servers.push(ImportedMcpServer { name: name.clone(), config: server.clone(), source: label.to_string() });
+    }
+    servers
+}
+
+/// `+` lines for text added after `existing`
+fn addition_diff(path: &str, existing: Option<&str>, added: &str) -> String {
+    let old_lines = existing.map(|e| e.lines().count()).unwrap_or(0);
+    let new_lines: Vec<&str> = added.lines().collect();
+    let mut diff = match existing {
+        Some(_) => format!("--- a/{}\n+++ b/{}\n", path, path),
+        None => format!("--- /dev/null\n+++ b/{}\n", path),
+    };
+    diff.push_str(&format!("@@ -{},0 +{},{} @@\n", old_lines, old_lines + 1, new_lines.len()));
+    for line in new_lines {
+        diff.push('+');
+        diff.push_str(line);
+        diff.push('\n');
+    }
+    diff
+}
+
+/// CLAUDE.md file for a memory tier: relative to the project, or absolute for the user's
+fn tier_path(tier: &str) -> Result<String, String> {
+    match tier {
+        "project" => Ok("CLAUDE.md".to_string()),
+        "projectClaude" => Ok(".claude/CLAUDE.md".to_string()),
+        "local" => Ok("CLAUDE.local.md".to_string()),
+        "user" => claude_config_dir()
+            .map(|d| d.join("CLAUDE.md").to_string_lossy().to_string())
+            .ok_or_else(|| "Could not find home directory".to_string()),
+        _ => Err(format!("Unknown memory tier '{}'. Expected one of: {}", tier, MEMORY_TIERS.join(", "))),
+    }
+}
+
+/// Map rules onto CLAUDE.md sections and project commands. Sections go to `tier` when given,
+/// otherwise project rules go to CLAUDE.md and user-level ones to ~/.claude/CLAUDE.md.
+fn plan_rules(root: &Path, rules: Vec<ImportedRule>, tier: Option<&str>) -> Result<Vec<ImportChange>, String> {
+    let mut changes = Vec::new();
+    let mut targets: Vec<(String, Vec<ImportedRule>)> = Vec::new();
+
+    for rule in rules {
+        if let Some(name) = &rule.command_name {
+            let relative = format!(".claude/commands/{}.md", name);
+            let exists = root.join(&relative).exists();
+            let description = rule.description.clone().unwrap_or_default().replace('\n', " ");
+            let content = format!("---\ndescription: {}\n---\n\n{}\n", yaml_string(&description), rule.body);
+            changes.push(ImportChange {
+                name: None,
+                diff: if exists { String::new() } else { addition_diff(&relative, None, &content) },
+                action: if exists { "skip" } else { "create" }.to_string(),
+                path: relative,
+                kind: "command".to_string(),
+                sources: vec![rule.source],
+                content,
+            });
+            continue;
+        }
+
+        let target = match tier {
+            Some(tier) => tier_path(tier)?,
+            None if rule.user_level => tier_path("user")?,
+            None => tier_path("project")?,
+        };
+        match targets.iter_mut().find(|(path, _)| *path == target) {
+            Some((_, rules)) => rules.push(rule),
+            None => targets.push((target, vec![rule])),
+        }
+    }
+
+    for (target, rules) in targets {
+        changes.extend(plan_sections(root, &target, rules));
+    }
+    Ok(changes)
+}
+
+/// Append rules to one CLAUDE.md as `## title` sections, skipping ones imported before
+fn plan_sections(root: &Path, target: &str, rules: Vec<ImportedRule>) -> Vec<ImportChange> {
+    let mut changes = Vec::new();
+    let existing = fs::read_to_string(root.join(target)).ok();
+
+    let mut sections = String::new();
+    let mut section_sources = Vec::new();
+    let mut skipped_sources = Vec::new();
+
+    for rule in rules {
+        // A section with the same heading means it was imported before
+        let heading = format!("## {}", rule.title);
+        let already = existing.as_deref().is_some_and(|e| e.lines().any(|l| l.trim() == heading))
+            || sections.lines().any(|l| l == heading);
+        if already {
+            skipped_sources.push(rule.source);
+            continue;
+        }
+
+        sections.push_str(&format!("\n{}\n\n", heading));
+        if !rule.globs.is_empty() {
+            let globs: Vec<String> = rule.globs.iter().map(|g| format!("`{}`", g)).collect();
+            sections.push_str(&format!("_Applies to files matching {}_\n\n", globs.join(", ")));
+        }
+        sections.push_str(&rule.body);
+        sections.push('\n');
+        section_sources.push(rule.source);
+    }
+
+    if !skipped_sources.is_empty() {
+        changes.push(ImportChange {
+            path: target.to_string(),
+            kind: "claudeMd".to_string(),
+            name: None,
+            action: "skip".to_string(),
+            sources: skipped_sources,
+            content: String::new(),
+            diff: String::new(),
+        });
+    }
+    if !sections.is_empty() {
+        let content = match &existing {
+            Some(text) if !text.trim().is_empty() => {
+                let separator = if text.ends_with('\n') { "" } else { "\n" };
+                format!("{}{}", separator, sections)
+            }
+            _ => format!("# CLAUDE.md\n{}", sections),
+        };
+        changes.push(ImportChange {
+            path: target.to_string(),
+            kind: "claudeMd".to_string(),
+            name: None,
+            action: if existing.is_some() { "append" } else { "create" }.to_string(),
+            sources: section_sources,
+            diff: addition_diff(target, existing.as_deref(), &content),
+            content,
+        });
+    }
+
+    changes
+}
+
+/// A YAML double-quoted scalar (JSON string syntax is valid YAML)
+fn yaml_string(text: &str) -> String {
+    serde_json::to_string(text).unwrap_or_default()
+}
+
+/// Map personas onto project subagents
+fn plan_agents(root: &Path, agents: Vec<ImportedAgent>) -> Vec<ImportChange> {
+    agents
+        .into_iter()
+        .map(|agent| {
+            let relative = format!(".claude/agents/{}.md", agent.name);
+            let exists = root.join(&relative).exists();
+            let mut content = format!("---\nname: {}\ndescription: {}\n", agent.name, yaml_string(&agent.description));
+            if let Some(tools) = &agent.tools {
+                content.push_str(&format!("tools: {}\n", tools.join(", ")));
+            }
+            content.push_str(&format!("---\n\n{}\n", agent.body));
+            ImportChange {
+                diff: if exists { String::new() } else { addition_diff(&relative, None, &content) },
+                action: if exists { "skip" } else { "create" }.to_string(),
+                path: relative,
+                kind: "agent".to_string(),
+                name: Some(agent.name),
+                sources: vec![agent.source],
+                content,
+            }
+        })
+        .collect()
+}
+
+/// Map MCP servers onto ~/.claude/settings.json entries; servers already configured are kept
+fn plan_mcp_servers(servers: Vec<ImportedMcpServer>) -> Result<Vec<ImportChange>, String> {
+    if servers.is_empty() {
+        return Ok(Vec::new());
+    }
+    let existing: HashMap<String, MCPServerConfig> = read_mcp_servers()?;
+    Ok(servers
+        .into_iter()
+        .map(|server| {
+            let exists = existing.contains_key(&server.name);
+            let entry = serde_json::json!({ &server.name: server.config });
+            let content = serde_json::to_string_pretty(&entry).unwrap_or_default();
+            ImportChange {
+                path: "~/.claude/settings.json".to_string(),
+                kind: "mcpServer".to_string(),
+                action: if exists { "skip" } else { "merge" }.to_string(),
+                diff: if exists { String::new() } else { addition_diff("~/.claude/settings.json (mcpServers)", None, &content) },
+                name: Some(server.name),
+                sources: vec![server.source],
+                content,
+            }
+        })
+        .collect())
+}
+
+/// Write the planned changes
+fn apply_changes(root: &Path, changes: &[ImportChange]) -> Result<(), String> {
+    for change in changes {
+        if change.kind == "mcpServer" {
+            if change.action == "merge" {
+                let entry: HashMap<String, MCPServerConfig> = serde_json::from_str(&change.content)
+                    .map_err(|e| format!("Invalid MCP server config for {}: {}", change.name.as_deref().unwrap_or_default(), e))?;
+                for (name, config) in entry {
+                    install_mcp_server_config(&name, config)?;
+                }
+            }
+            continue;
+        }
+
+        let path = root.join(&change.path);
+        let content = match change.action.as_str() {
+            "create" => change.content.clone(),
+            "append" => fs::read_to_string(&path).unwrap_or_default() + &change.content,
+            _ => continue,
+        };
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+        }
+        crate::watcher::write_expected(&path, &content).map_err(|e| format!("Failed to write {}: {}", change.path, e))?;
+    }
+    Ok(())
+}
+
+fn import_plan(tool: &str, project_path: &str, tier: Option<&str>) -> Result<ImportPlan, String> {
+    let root = Path::new(project_path);
+    if !root.is_dir() {
+        return Err(format!("Project not found: {}", project_path));
+    }
+
+    let mut warnings = Vec::new();
+    let source = match tool {
+        "cursor" => ImportSource { rules: cursor_rules(root, &mut warnings), ..Default::default() },
+        "cline" => cline_source(root, &mut warnings),
+        "roo" => roo_source(root, &mut warnings),
+        "windsurf" => windsurf_source(root, &mut warnings),
+        "agents-md" => agents_md_source(root, &mut warnings),
+        "gemini" => gemini_source(root, &mut warnings),
+        _ => return Err(format!("Unknown tool '{}'. Expected one of: {}", tool, IMPORT_TOOLS.join(", "))),
+    };
+    if source.rules.is_empty() && source.agents.is_empty() && source.mcp_servers.is_empty() {
+        warnings.push(format!("No {} configuration found", tool));
+    }
+
+    let mut changes = plan_rules(root, source.rules, tier)?;
+    changes.extend(plan_agents(root, source.agents));
+    changes.extend(plan_mcp_servers(source.mcp_servers)?);
+
+    Ok(ImportPlan {
+        tool: tool.to_string(),
+        project_path: project_path.to_string(),
+        changes,
+        warnings,
+        applied: false,
+    })
+}
+
+/// Preview importing another tool's rules, personas and MCP servers into Claude's config.
+/// `tool` is cursor, cline, roo, windsurf, agents-md or gemini; `tier` (project, projectClaude,
+/// local or user) picks the CLAUDE.md that rules go to. Nothing is written.
+#[tauri::command]
+pub async fn import_from_tool(tool: String, project_path: String, tier: Option<String>) -> Result<ImportPlan, String> {
+    run_blocking(move || import_plan(&tool, &project_path, tier.as_deref())).await?
+}
+
+/// Write an import plan; existing files, sections and MCP servers are left alone
+fn apply_import(tool: String, project_path: String, tier: Option<String>) -> Result<ImportPlan, String> {
+    let mut plan = import_plan(&tool, &project_path, tier.as_deref())?;
+    apply_changes(Path::new(&project_path), &plan.changes)?;
+    plan.applied = true;
+    Ok(plan)
+}
+
+/// Apply what `import_from_tool` previewed. Existing files, sections and MCP servers are left alone.
+#[tauri::command]
+#[tracing::instrument(err)]
+pub async fn apply_tool_import(tool: String, project_path: String, tier: Option<String>) -> Result<ImportPlan, String> {
+    run_blocking(move || apply_import(tool, project_path, tier)).await?
+}
+
+/// Detect AGENTS.md and GEMINI.md files that could be merged into CLAUDE.md, each with the
+/// tier it would go to by default. Pass the tool to `import_from_tool` to preview the diff.
+#[tauri::command]
+pub async fn detect_memory_imports(project_path: String) -> Result<Vec<MemoryImportCandidate>, String> {
+    run_blocking(move || {
+        let root = Path::new(&project_path);
+        if !root.is_dir() {
+            return Err(format!("Project not found: {}", project_path));
+        }
+
+        let mut candidates = Vec::new();
+        for tool in ["agents-md", "gemini"] {
+            let mut warnings = Vec::new();
+            let source = match tool {
+                "agents-md" => agents_md_source(root, &mut warnings),
+                _ => gemini_source(root, &mut warnings),
+            };
+            for rule in source.rules {
+                let tier = if rule.user_level { "user" } else { "project" };
+                let heading = format!("## {}", rule.title);
+                let already_imported = fs::read_to_string(root.join(tier_path(tier)?))
+                    .is_ok_and(|existing| existing.lines().any(|l| l.trim() == heading));
+                candidates.push(MemoryImportCandidate {
+                    tool: tool.to_string(),
+                    source: rule.source,
+                    suggested_tier: tier.to_string(),
+                    already_imported,
+                });
+            }
+        }
+        Ok(candidates)
+    })
+    .await?
+}
+
+/// Preview importing a project's Cursor rules: always-on and glob rules become CLAUDE.md
+/// sections, rules Cursor applies on request become project commands
+#[tauri::command]
+pub async fn preview_cursor_import(project_path: String) -> Result<ImportPlan, String> {
+    run_blocking(move || import_plan("cursor", &project_path, None)).await?
+}
+
+/// Import a project's Cursor rules as previewed. Existing commands and sections are left alone.
+#[tauri::command]
+pub async fn apply_cursor_import(project_path: String) -> Result<ImportPlan, String> {
+    apply_tool_import("cursor".to_string(), project_path, None).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_cursor_frontmatter_that_is_not_yaml() {
+        let (fields, body) = split_frontmatter("---\ndescription: React style\nglobs: *.tsx, src/**/*.ts\nalwaysApply: false\n---\nUse hooks.\n");
+        assert_eq!(body, "Use hooks.");
+        let globs = fields.iter().find(|(k, _)| k == "globs").map(|(_, v)| parse_globs(v)).unwrap();
+        assert_eq!(globs, vec!["*.tsx".to_string(), "src/**/*.ts".to_string()]);
+    }
+
+    #[test]
+    fn nests_imported_memory_headings() {
+        let body = demote_headings("# Project\n```sh\n# not a heading\n```\n###### Deep");
+        assert_eq!(body, "## Project\n```sh\n# not a heading\n```\n###### Deep");
+    }
+}
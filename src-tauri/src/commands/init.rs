@@ -0,0 +1,234 @@
+//! Project starter kits: scaffold `.claude/` for a fresh repo in one step
+//! Settings, CLAUDE.md and starter commands are tailored to the detected stack.
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+use super::detect::{project_info, ProjectInfo};
+use crate::scanner::settings::write_settings_at;
+use crate::blocking::run_blocking;
+
+/// What `init_project_claude` created and left alone
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InitProjectResult {
+    pub kit: String,
+    pub languages: Vec<String>,
+    /// Paths created, relative to the project root
+    pub created: Vec<String>,
+    /// Paths that already existed and were not overwritten
+    pub skipped: Vec<String>,
+}
+
+/// Starter kits, smallest first
+pub const STARTER_KITS: [&str; 3] = ["minimal", "standard", "full"];
+
+/// Build, test and lint commands for the detected stack
+fn stack_commands(info: &ProjectInfo) -> Vec<(&'static str, String)> {
+    let mut commands = Vec::new();
+    let has = |lang: &str| info.languages.iter().any(|l| l == lang);
+
+    if has("rust") {
+        commands.push(("Build", "cargo build".to_string()));
+        commands.push(("Test", "cargo test".to_string()));
+        commands.push(("Lint", "cargo clippy --all-targets -- -D warnings".to_string()));
+        commands.push(("Format", "cargo fmt".to_string()));
+    }
+    if has("javascript") || has("typescript") {
+        let pm = info.package_manager.clone().unwrap_or_else(|| "npm".to_string());
+        commands.push(("Install", format!("{} install", pm)));
+        commands.push(("Build", format!("{} run build", pm)));
+        if info.has_tests {
+            commands.push(("Test", format!("{} test", pm)));
+        }
+        if info.has_eslint {
+            commands.push(("Lint", format!("{} run lint", pm)));
+        }
+    }
+    if has("python") {
+        commands.push(("Test", "pytest".to_string()));
+        commands.push(("Lint", "ruff check .".to_string()));
+    }
+    if has("go") {
+        commands.push(("Build", "go build ./...".to_string()));
+        commands.push(("Test", "go test ./...".to_string()));
+        commands.push(("Lint", "go vet ./...".to_string()));
+    }
+
+    commands
+}
+
+/// Recommended permissions for the detected stack: its own tooling is allowed,
+/// secrets are denied
+fn recommended_permissions(info: &ProjectInfo) -> serde_json::Value {
+    let mut allow: Vec<String> = vec![
+        "Bash(git status)".to_string(),
+        "Bash(git diff:*)".to_string(),
+        "Bash(git log:*)".to_string(),
+    ];
+
+    for (_, command) in stack_commands(info) {
+        // Keep the subcommand (and script name for `<pm> run x`) so the rule stays narrow
+        let words: Vec<&str> = command
+            .split_whitespace()
+            .take_while(|w| !w.starts_with('-') && !w.starts_with('.'))
+            .collect();
+        let prefix = match words.as_slice() {
+            [tool, "run", script, ..] => format!("{} run {}", tool, script),
+            [tool, sub, ..] => format!("{} {}", tool, sub),
+            [tool] => tool.to_string(),
+            [] => continue,
+        };
+        let rule = format!("Bash({}:*)", prefix);
+        if !allow.contains(&rule) {
+            allow.push(rule);
+        }
+    }
+
+    serde_json::json!({
+        "allow": allow,
+        "deny": [
+            "Read(./.env)",
+            "Read(./.env.*)",
+            "Read(./secrets/**)",
+        ],
+    })
+}
+
+/// CLAUDE.md tailored to the stack
+fn claude_md_template(project_name: &str, info: &ProjectInfo) -> String {
+    let mut content = format!("# {}\n\n", project_name);
+
+    content.push_str("## Stack\n\n");
+    if info.languages.is_empty() {
+        content.push_str("- (describe the languages and frameworks used)\n");
+    }
+    for language in &info.languages {
+        content.push_str(&format!("- Language: {}\n", language));
+    }
+    for framework in &info.frameworks {
+        content.push_str(&format!("- Framework: {}\n", framework));
+    }
+
+    content.push_str("\n## Commands\n\n");
+    let commands = stack_commands(info);
+    if commands.is_empty() {
+        content.push_str("- (list how to build, test and lint)\n");
+    }
+    for (label, command) in commands {
+        content.push_str(&format!("- {}: `{}`\n", label, command));
+    }
+
+    content.push_str("\n## Conventions\n\n");
+    content.push_str("- Follow the existing code style and patterns in this repository\n");
+    content.push_str("- Run the tests before considering a change done\n");
+
+    content
+}
+
+/// Starter slash commands: (file name, content)
+fn starter_commands(kit: &str, info: &ProjectInfo) -> Vec<(&'static str, String)> {
+    if kit == "minimal" {
+        return Vec::new();
+    }
+
+    let test_command = stack_commands(info)
+        .into_iter()
+        .find(|(label, _)| *label == "Test")
+        .map(|(_, c)| c)
+        .unwrap_or_else(|| "the project's test suite".to_string());
+
+    let mut commands = vec![
+        ("review.md", "---\ndescription: Review the current changes\n---\n\nReview the uncommitted changes (`git diff`). Point out bugs, missing tests and anything that doesn't match the conventions in CLAUDE.md.\n".to_string()),
+        ("test.md", format!("---\ndescription: Run the tests and fix failures\n---\n\nRun `{}`. If anything fails, find the cause and fix it, then run the tests again.\n", test_command)),
+    ];
+
+    if kit == "full" {
+        commands.push(("commit.md", "---\ndescription: Write a commit for the staged changes\n---\n\nLook at `git diff --staged` and write a concise commit message describing what changed and why. $ARGUMENTS\n".to_string()));
+        commands.push(("explain.md", "---\ndescription: Explain a file or symbol\nargument-hint: <path or symbol>\n---\n\nExplain how $ARGUMENTS works, what calls it and what it depends on.\n".to_string()));
+    }
+
+    commands
+}
+
+/// Write a file unless it exists, recording which happened
+fn write_new_file(root: &Path, relative: &str, content: &str, result: &mut InitProjectResult) -> Result<(), String> {
+    let path = root.join(relative);
+    if path.exists() {
+        result.skipped.push(relative.to_string());
+        return Ok(());
+    }
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+    }
+    crate::watcher::write_expected(&path, content).map_err(|e| format!("Failed to write {}: {}", relative, e))?;
+    result.created.push(relative.to_string());
+    Ok(())
+}
+
+/// Scaffold `.claude/` (settings, CLAUDE.md, starter commands) for a project.
+/// Existing files are never overwritten.
+#[tauri::command]
+pub async fn init_project_claude(project_path: String, kit: String) -> Result<InitProjectResult, String> {
+    run_blocking(move || {
+        if !STARTER_KITS.contains(&kit.as_str()) {
+            return Err(format!("Unknown starter kit '{}'. Expected one of: {}", kit, STARTER_KITS.join(", ")));
+        }
+
+        let info = project_info(project_path.clone())?;
+        let root = Path::new(&project_path);
+        let project_name = root
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| "Project".to_string());
+
+        let mut result = InitProjectResult {
+            kit: kit.clone(),
+            languages: info.languages.clone(),
+            created: Vec::new(),
+            skipped: Vec::new(),
+        };
+
+        // settings.json with the recommended permissions block
+        let settings_relative = ".claude/settings.json";
+        let settings_path = root.join(settings_relative);
+        if settings_path.exists() {
+            result.skipped.push(settings_relative.to_string());
+        } else {
+            let settings = serde_json::json!({ "permissions": recommended_permissions(&info) });
+            write_settings_at(&settings_path, &settings)?;
+            result.created.push(settings_relative.to_string());
+        }
+
+        write_new_file(root, "CLAUDE.md", &claude_md_template(&project_name, &info), &mut result)?;
+
+        for (file_name, content) in starter_commands(&kit, &info) {
+            write_new_file(root, &format!(".claude/commands/{}", file_name), &content, &mut result)?;
+        }
+
+        // Keep personal config out of git
+        let gitignore = root.join(".gitignore");
+        if gitignore.exists() {
+            let existing = fs::read_to_string(&gitignore).unwrap_or_default();
+            let missing: Vec<&str> = [".claude/settings.local.json", "CLAUDE.local.md"]
+                .into_iter()
+                .filter(|entry| !existing.lines().any(|l| l.trim() == *entry))
+                .collect();
+            if !missing.is_empty() {
+                let mut updated = existing.clone();
+                if !updated.is_empty() && !updated.ends_with('\n') {
+                    updated.push('\n');
+                }
+                updated.push_str(&missing.join("\n"));
+                updated.push('\n');
+                crate::paths::atomic_write(&gitignore, updated).map_err(|e| format!("Failed to update .gitignore: {}", e))?;
+                result.created.push(".gitignore".to_string());
+            }
+        }
+
+        Ok(result)
+    })
+    .await?
+}
@@ -0,0 +1,10 @@
+use crate::scanner::integrity::{verify_install, IntegrityReport};
+use crate::blocking::run_blocking;
+use crate::error::ArcadeError;
+
+/// Compare an installed skill or plugin with the checksums recorded when ClaudeArcade
+/// installed it, listing files that were changed, removed or added since
+#[tauri::command]
+pub async fn verify_item_integrity(item_id: String) -> Result<IntegrityReport, ArcadeError> {
+    run_blocking(move || verify_install(&item_id).ok_or_else(|| ArcadeError::not_found("Install record", item_id))).await?
+}
@@ -1,50 +1,344 @@
-use std::time::Instant;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::sync::Mutex;
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+use tauri::{AppHandle, Emitter, State};
 use crate::scanner::{
     scan_plugins, scan_slash_commands, scan_skills,
     scan_hooks, scan_subagents, scan_claudemd
 };
-use crate::types::{ScanResult, InventoryItem};
+use crate::scanner::index::{
+    load_indexed_inventory, save_indexed_inventory, section_fingerprint, IndexedInventory, IndexedSection, SECTIONS,
+};
+use crate::scanner::usage::{apply_item_usage, ingest_usage};
+use crate::types::{ScanMetrics, ScanResult, SectionMetrics, InventoryItem};
+use crate::blocking::run_blocking;
+use crate::paths::ConfigPaths;
+use super::onboarding::{complete_step, OnboardingStep};
+
+/// A full scan should finish within this; slower scans are logged and flagged in their metrics
+const SCAN_BUDGET_MS: u64 = 2000;
+const SECTION_BUDGET_MS: u64 = 500;
+
+/// Metrics of this many recent scans are kept for `get_scan_metrics`
+const METRICS_HISTORY: usize = 20;
+
+/// The latest scan, kept so the frontend can render before a fresh scan finishes
+#[derive(Default)]
+pub struct InventoryCache {
+    pub project_path: Option<String>,
+    pub result: Option<ScanResult>,
+    /// Oldest first
+    pub metrics: Vec<ScanMetrics>,
+}
+
+pub struct InventoryCacheState(pub Mutex<InventoryCache>);
 
-/// Scan for all available plugins, skills, MCPs, hooks, subagents, and CLAUDE.md files
+/// What changed between two scans, emitted as `inventory-diff`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InventoryDiff {
+    pub project_path: Option<String>,
+    pub added: Vec<InventoryItem>,
+    /// Ids of items that are gone
+    pub removed: Vec<String>,
+    pub changed: Vec<InventoryItem>,
+}
+
+impl InventoryDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+/// Items added, removed or changed in any field since `previous`, matched by id
+fn diff_items(previous: &[InventoryItem], current: &[InventoryItem], project_path: Option<String>) -> InventoryDiff {
+    let before: HashMap<&str, serde_json::Value> = previous
+        .iter()
+        .map(|item| (item.id.as_str(), serde_json::to_value(item).unwrap_or_default()))
+        .collect();
+    let mut diff = InventoryDiff { project_path, added: Vec::new(), removed: Vec::new(), changed: Vec::new() };
+
+    for item in current {
+        match before.get(item.id.as_str()) {
+            None => diff.added.push(item.clone()),
+            Some(old) if *old != serde_json::to_value(item).unwrap_or_default() => diff.changed.push(item.clone()),
+            Some(_) => {}
+        }
+    }
+    let now: HashSet<&str> = current.iter().map(|item| item.id.as_str()).collect();
+    diff.removed = previous
+        .iter()
+        .filter(|item| !now.contains(item.id.as_str()))
+        .map(|item| item.id.clone())
+        .collect();
+    diff
+}
+
+/// Items from one scanner, emitted as `inventory-batch` while a streaming scan continues
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InventoryBatch {
+    pub project_path: Option<String>,
+    /// plugins, commands, skills, hooks, agents or claudeMd
+    pub section: String,
+    pub items: Vec<InventoryItem>,
+}
+
+/// Scan for all available plugins, skills, MCPs, hooks, subagents, and CLAUDE.md files.
+/// The result replaces the cached scan, and `inventory-diff` is emitted when items changed since it.
+/// With `stream`, each scanner's items are also emitted as an `inventory-batch` as soon as they're ready.
 #[tauri::command]
-pub async fn scan_inventory(project_path: Option<String>) -> Result<ScanResult, String> {
-    let start = Instant::now();
-    let mut all_items: Vec<InventoryItem> = Vec::new();
-    let mut errors: Vec<String> = Vec::new();
+#[tracing::instrument(skip(app_handle, state), err)]
+pub async fn scan_inventory(
+    app_handle: AppHandle,
+    state: State<'_, InventoryCacheState>,
+    project_path: Option<String>,
+    stream: Option<bool>,
+) -> Result<ScanResult, String> {
+    let scan_path = project_path.clone();
+    let batch_handle = app_handle.clone();
+    let result = run_blocking(move || {
+        if !stream.unwrap_or(false) {
+            return scan_all(scan_path.as_deref());
+        }
+        scan_all_streaming(scan_path.as_deref(), |section, items| {
+            let _ = batch_handle.emit("inventory-batch", InventoryBatch {
+                project_path: scan_path.clone(),
+                section: section.to_string(),
+                items: items.to_vec(),
+            });
+        })
+    })
+    .await?;
 
-    // Get project path as &str for scanner functions
-    let project_path_ref = project_path.as_deref();
+    let mut cache = state.0.lock().map_err(|e| format!("Lock error: {}", e))?;
+    if let Some(previous) = &cache.result {
+        let diff = diff_items(&previous.items, &result.items, project_path.clone());
+        if !diff.is_empty() {
+            let _ = app_handle.emit("inventory-diff", diff);
+        }
+    }
+    if let Some(metrics) = &result.metrics {
+        cache.metrics.push(metrics.clone());
+        let excess = cache.metrics.len().saturating_sub(METRICS_HISTORY);
+        cache.metrics.drain(..excess);
+    }
+    cache.project_path = project_path;
+    cache.result = Some(result.clone());
+    complete_step(&app_handle, OnboardingStep::FirstScanCompleted);
 
-    // Scan plugins (MCPs, frameworks)
-    let plugin_result = scan_plugins();
-    all_items.extend(plugin_result.items);
-    errors.extend(plugin_result.errors);
+    Ok(result)
+}
 
-    // Scan slash commands (~/.claude/commands/, .claude/commands/)
-    let commands = scan_slash_commands(project_path_ref);
-    all_items.extend(commands);
+/// The last scan result for a project, for rendering while a fresh scan runs. Falls back to
+/// the on-disk index, so it has data right after startup too.
+#[tauri::command]
+pub async fn get_cached_inventory(
+    state: State<'_, InventoryCacheState>,
+    project_path: Option<String>,
+) -> Result<Option<ScanResult>, String> {
+    if let Some(result) = memory_cached_scan(&state, &project_path)? {
+        return Ok(Some(result));
+    }
+    run_blocking(move || load_indexed_inventory(project_path.as_deref()).map(|index| index.to_scan_result())).await
+}
 
-    // Scan skills (~/.claude/skills/)
-    let skills = scan_skills(project_path_ref);
-    all_items.extend(skills);
+/// The in-memory scan, if it is for this project
+pub(crate) fn memory_cached_scan(state: &InventoryCacheState, project_path: &Option<String>) -> Result<Option<ScanResult>, String> {
+    let cache = state.0.lock().map_err(|e| format!("Lock error: {}", e))?;
+    Ok(cache.result.clone().filter(|_| cache.project_path == *project_path))
+}
 
-    // Scan hooks (from settings.json)
-    let hooks = scan_hooks(project_path_ref);
-    all_items.extend(hooks);
+/// The in-memory scan when given, else the on-disk index; scans only when neither exists
+pub(crate) fn cached_or_scan(cached: Option<ScanResult>, project_path: Option<&str>) -> ScanResult {
+    cached
+        .or_else(|| load_indexed_inventory(project_path).map(|index| index.to_scan_result()))
+        .unwrap_or_else(|| scan_all(project_path))
+}
 
-    // Scan subagents (~/.claude/agents/, .claude/agents/)
-    let subagents = scan_subagents(project_path_ref);
-    all_items.extend(subagents);
+/// Timing, file counts and index hits of recent scans this session, oldest first
+#[tauri::command]
+pub fn get_scan_metrics(state: State<'_, InventoryCacheState>) -> Result<Vec<ScanMetrics>, String> {
+    let cache = state.0.lock().map_err(|e| format!("Lock error: {}", e))?;
+    Ok(cache.metrics.clone())
+}
+
+/// One scanner's items and errors
+fn scan_section(section: &str, project_path_ref: Option<&str>) -> (Vec<InventoryItem>, Vec<String>) {
+    match section {
+        // Plugins (MCPs, frameworks)
+        "plugins" => {
+            let result = scan_plugins();
+            (result.items, result.errors)
+        }
+        // Slash commands (~/.claude/commands/, .claude/commands/)
+        "commands" => (scan_slash_commands(project_path_ref), Vec::new()),
+        // Skills (~/.claude/skills/, .claude/skills/)
+        "skills" => (scan_skills(project_path_ref), Vec::new()),
+        // Hooks (from settings.json)
+        "hooks" => (scan_hooks(project_path_ref), Vec::new()),
+        // Subagents (~/.claude/agents/, .claude/agents/)
+        "agents" => (scan_subagents(project_path_ref), Vec::new()),
+        // CLAUDE.md files (various locations)
+        "claudeMd" => (scan_claudemd(project_path_ref), Vec::new()),
+        _ => (Vec::new(), Vec::new()),
+    }
+}
+
+/// Sections a WSL config folder is scanned for; plugin install paths there are Linux paths
+const WSL_SECTIONS: [&str; 5] = ["commands", "skills", "hooks", "agents", "claudeMd"];
+
+/// User-level items of each WSL config folder turned on in arcade settings, tagged with their
+/// environment. Not indexed: the `\\wsl$` share is slow to fingerprint and these rarely change.
+fn scan_wsl_items() -> (Vec<InventoryItem>, Vec<String>) {
+    let mut items = Vec::new();
+    let mut errors = Vec::new();
+    for (environment, paths) in crate::wsl::enabled_roots() {
+        paths.scoped(|| {
+            for section in WSL_SECTIONS {
+                let (found, section_errors) = scan_section(section, None);
+                items.extend(found.into_iter().map(|mut item| {
+                    item.id = format!("{}:{}", environment, item.id);
+                    item.environment = Some(environment.clone());
+                    item
+                }));
+                errors.extend(section_errors.into_iter().map(|e| format!("{}: {}", environment, e)));
+            }
+        });
+    }
+    (items, errors)
+}
+
+/// Every inventory item with usage applied; shared by scan_inventory and exports.
+/// Sections whose files haven't changed since the indexed scan reuse the indexed items.
+pub(crate) fn scan_all(project_path_ref: Option<&str>) -> ScanResult {
+    scan_all_streaming(project_path_ref, |_, _| {})
+}
+
+/// `scan_all`, handing each section's items to `on_section` as soon as they're ready
+#[tracing::instrument(level = "debug", skip(on_section))]
+fn scan_all_streaming(project_path_ref: Option<&str>, mut on_section: impl FnMut(&str, &[InventoryItem])) -> ScanResult {
+    let start = Instant::now();
+    let paths = ConfigPaths::resolve().map(|p| p.with_project(project_path_ref));
+    let previous = load_indexed_inventory(project_path_ref).unwrap_or_default();
+    // Run counts and last use, attributed from session transcripts
+    let usage = ingest_usage();
+    let usage_ms = start.elapsed().as_millis() as u64;
+    let mut index = IndexedInventory::default();
+    let mut sections = Vec::new();
+
+    for section in SECTIONS {
+        let section_start = Instant::now();
+        let (fingerprint, file_count) = paths.as_ref().map(|p| section_fingerprint(section, p)).unwrap_or_default();
+        let cached = previous.sections.get(section).filter(|indexed| indexed.fingerprint == fingerprint);
+        let (mut items, errors) = match cached {
+            Some(indexed) => (indexed.items.clone(), indexed.errors.clone()),
+            None => scan_section(section, project_path_ref),
+        };
+        apply_item_usage(&mut items, &usage);
+        on_section(section, &items);
 
-    // Scan CLAUDE.md files (various locations)
-    let claudemd = scan_claudemd(project_path_ref);
-    all_items.extend(claudemd);
+        sections.push(SectionMetrics {
+            section: section.to_string(),
+            duration_ms: section_start.elapsed().as_millis() as u64,
+            item_count: items.len(),
+            file_count,
+            cache_hit: cached.is_some(),
+        });
+        index.sections.insert(section.to_string(), IndexedSection { fingerprint, items, errors });
+    }
 
     let duration = start.elapsed();
+    index.scan_duration_ms = duration.as_millis() as u64;
+    index.saved_at = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_millis() as u64).unwrap_or(0);
+    let mut result = index.to_scan_result();
+    let (wsl_items, wsl_errors) = scan_wsl_items();
+    result.items.extend(wsl_items);
+    result.errors.extend(wsl_errors);
+    // MCP groups, as composite trinkets
+    result.items.extend(super::mcp_groups::group_items());
+    let metrics = scan_metrics(index.saved_at, index.scan_duration_ms, usage_ms, sections);
 
-    Ok(ScanResult {
-        items: all_items,
-        errors,
-        scan_duration_ms: duration.as_millis() as u64,
-    })
+    for error in &result.errors {
+        tracing::warn!("Scan error: {}", error);
+    }
+    if !metrics.over_budget.is_empty() {
+        tracing::warn!("Slow scan ({} ms): {} over budget", metrics.total_ms, metrics.over_budget.join(", "));
+    }
+    tracing::debug!(items = result.items.len(), ms = metrics.total_ms, hit_rate = metrics.cache_hit_rate, "Inventory scanned");
+    if let Err(e) = save_indexed_inventory(project_path_ref, index) {
+        tracing::warn!("Failed to save inventory index: {}", e);
+    }
+
+    result.metrics = Some(metrics);
+    result
+}
+
+/// Metrics for a scan, flagging sections (and "total", "usage") that blew their time budget
+fn scan_metrics(scanned_at: u64, total_ms: u64, usage_ms: u64, sections: Vec<SectionMetrics>) -> ScanMetrics {
+    let mut over_budget: Vec<String> = sections
+        .iter()
+        .filter(|s| s.duration_ms > SECTION_BUDGET_MS)
+        .map(|s| s.section.clone())
+        .collect();
+    if usage_ms > SECTION_BUDGET_MS {
+        over_budget.push("usage".to_string());
+    }
+    if total_ms > SCAN_BUDGET_MS {
+        over_budget.push("total".to_string());
+    }
+    let hits = sections.iter().filter(|s| s.cache_hit).count();
+    ScanMetrics {
+        scanned_at,
+        total_ms,
+        usage_ms,
+        cache_hit_rate: if sections.is_empty() { 0.0 } else { hits as f32 / sections.len() as f32 },
+        sections,
+        over_budget,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TempDir;
+    use crate::types::test_item;
+
+    fn item(id: &str, enabled: bool) -> InventoryItem {
+        InventoryItem { token_weight: 10, enabled, ..test_item(id) }
+    }
+
+    #[test]
+    fn diffs_items_by_id() {
+        let previous = [item("cmd_user_a", true), item("cmd_user_b", true)];
+        let current = [item("cmd_user_a", false), item("cmd_user_c", true)];
+        let diff = diff_items(&previous, &current, None);
+        assert_eq!(diff.added[0].id, "cmd_user_c");
+        assert_eq!(diff.removed, vec!["cmd_user_b".to_string()]);
+        assert_eq!(diff.changed[0].id, "cmd_user_a");
+        assert!(diff_items(&current, &current, None).is_empty());
+    }
+
+    #[test]
+    fn scans_and_indexes_an_overridden_config_root() {
+        let root = TempDir::new("scan");
+        std::fs::create_dir_all(root.join(".claude/commands")).unwrap();
+        std::fs::write(root.join(".claude/commands/review.md"), "Review the diff").unwrap();
+
+        let (first, second, third) = ConfigPaths::under(&root).scoped(|| {
+            let first = scan_all(None);
+            std::fs::write(root.join(".claude/commands/ship.md"), "Ship it").unwrap();
+            (first, scan_all(None), scan_all(None))
+        });
+        let indexed = root.join(".claude/arcade_index.json").exists();
+
+        assert!(first.items.iter().any(|i| i.id == "cmd_user_review"));
+        assert_eq!(second.items.len(), first.items.len() + 1);
+        assert!(indexed);
+        let commands = |result: &ScanResult| result.metrics.as_ref().unwrap().sections[1].clone();
+        assert!(!commands(&second).cache_hit);
+        assert_eq!(commands(&second).file_count, 3);
+        assert_eq!(third.metrics.unwrap().cache_hit_rate, 1.0);
+    }
 }
@@ -0,0 +1,199 @@
+//! Inventory report as JSON or CSV: every installed item with its token weight and usage
+//! Meant for team audits of what loads into each member's context.
+
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+use super::inventory::scan_all;
+use crate::types::{InventoryItem, ItemSource};
+use crate::blocking::run_blocking;
+
+/// One row of the report
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InventoryReportRow {
+    pub id: String,
+    pub name: String,
+    pub item_type: String,
+    pub rarity: String,
+    pub source: String,
+    /// user, project, plugin or builtin
+    pub scope: String,
+    pub token_weight: u32,
+    pub enabled: bool,
+    pub version: Option<String>,
+    pub author: Option<String>,
+    pub source_path: String,
+    pub run_count: u32,
+    /// RFC 3339
+    pub last_used: Option<String>,
+    pub tasks_completed: Option<u32>,
+    /// Average tokens a companion uses in its isolated context per task
+    pub isolated_context_usage: Option<u32>,
+    pub description: String,
+}
+
+/// The JSON report
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InventoryReport {
+    pub generated_at: String,
+    pub project_path: Option<String>,
+    pub item_count: usize,
+    /// Token weight of enabled items, i.e. what every session starts with
+    pub enabled_token_weight: u64,
+    pub items: Vec<InventoryReportRow>,
+}
+
+const CSV_COLUMNS: [&str; 16] = [
+    "id", "name", "type", "rarity", "source", "scope", "tokenWeight", "enabled", "version", "author",
+    "sourcePath", "runCount", "lastUsed", "tasksCompleted", "isolatedContextUsage", "description",
+];
+
+/// camelCase name an enum serializes to
+pub(crate) fn variant_name<T: Serialize>(value: &T) -> String {
+    serde_json::to_value(value).ok().and_then(|v| v.as_str().map(String::from)).unwrap_or_default()
+}
+
+/// Scope from the id the scanners give each item ("cmd_user_x", "claudemd_project-root_x", ...)
+pub(crate) fn item_scope(item: &InventoryItem) -> String {
+    if item.id.starts_with("builtin_") {
+        return "builtin".to_string();
+    }
+    if matches!(item.source, ItemSource::Plugin) {
+        return "plugin".to_string();
+    }
+    let scope = item.id.split('_').nth(1).unwrap_or_default();
+    match scope.split('-').next().unwrap_or_default() {
+        "user" => "user",
+        "project" => "project",
+        "plugin" => "plugin",
+        _ => "unknown",
+    }
+    .to_string()
+}
+
+fn report_row(item: &InventoryItem) -> InventoryReportRow {
+    let status = item.status.clone().unwrap_or_default();
+    InventoryReportRow {
+        id: item.id.clone(),
+        name: item.name.clone(),
+        item_type: variant_name(&item.item_type),
+        rarity: variant_name(&item.rarity),
+        source: variant_name(&item.source),
+        scope: item_scope(item),
+        token_weight: item.token_weight,
+        enabled: item.enabled,
+        version: item.version.clone(),
+        author: item.author.clone(),
+        source_path: item.source_path.clone(),
+        run_count: status.run_count.unwrap_or(0),
+        last_used: status
+            .last_used
+            .and_then(|ms| chrono::DateTime::from_timestamp_millis(ms as i64))
+            .map(|t| t.to_rfc3339()),
+        tasks_completed: status.tasks_completed,
+        isolated_context_usage: status.isolated_context_usage,
+        description: item.description.clone(),
+    }
+}
+
+/// Quote a CSV field when needed. Cells a spreadsheet would run as a formula get a leading quote.
+fn csv_field(value: &str) -> String {
+    let value = if value.starts_with(['=', '+', '-', '@']) {
+        format!("'{}", value)
+    } else {
+        value.to_string()
+    };
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value
+    }
+}
+
+fn render_csv(rows: &[InventoryReportRow]) -> String {
+    let mut out = CSV_COLUMNS.join(",");
+    out.push('\n');
+    for row in rows {
+        let optional = |value: Option<u32>| value.map(|v| v.to_string()).unwrap_or_default();
+        let fields = [
+            row.id.clone(),
+            row.name.clone(),
+            row.item_type.clone(),
+            row.rarity.clone(),
+            row.source.clone(),
+            row.scope.clone(),
+            row.token_weight.to_string(),
+            row.enabled.to_string(),
+            row.version.clone().unwrap_or_default(),
+            row.author.clone().unwrap_or_default(),
+            row.source_path.clone(),
+            row.run_count.to_string(),
+            row.last_used.clone().unwrap_or_default(),
+            optional(row.tasks_completed),
+            optional(row.isolated_context_usage),
+            row.description.clone(),
+        ];
+        let line: Vec<String> = fields.iter().map(|f| csv_field(f)).collect();
+        out.push_str(&line.join(","));
+        out.push('\n');
+    }
+    out
+}
+
+/// Write every installed inventory item with type, rarity, scope, token weight, enabled state
+/// and usage to `dest` as "json" or "csv". Marketplace plugins that aren't installed are left out.
+/// A folder `dest` gets a dated file name. Returns the written path.
+#[tauri::command]
+pub async fn export_inventory(format: String, dest: String, project_path: Option<String>) -> Result<String, String> {
+    run_blocking(move || {
+        let extension = match format.as_str() {
+            "json" => "json",
+            "csv" => "csv",
+            _ => return Err(format!("Unsupported export format: {}", format)),
+        };
+
+        let scan = scan_all(project_path.as_deref());
+        let rows: Vec<InventoryReportRow> = scan
+            .items
+            .iter()
+            .filter(|item| !(matches!(item.source, ItemSource::Plugin) && item.source_path.is_empty()))
+            .map(report_row)
+            .collect();
+
+        let content = if extension == "csv" {
+            render_csv(&rows)
+        } else {
+            let report = InventoryReport {
+                generated_at: chrono::Utc::now().to_rfc3339(),
+                project_path,
+                item_count: rows.len(),
+                enabled_token_weight: rows.iter().filter(|r| r.enabled).map(|r| r.token_weight as u64).sum(),
+                items: rows,
+            };
+            serde_json::to_string_pretty(&report).map_err(|e| e.to_string())?
+        };
+
+        let mut dest = PathBuf::from(dest);
+        if dest.is_dir() {
+            dest = dest.join(format!("inventory-{}.{}", chrono::Local::now().format("%Y-%m-%d"), extension));
+        }
+        crate::paths::atomic_write(&dest, content).map_err(|e| format!("Failed to write {}: {}", dest.display(), e))?;
+
+        Ok(dest.to_string_lossy().to_string())
+    })
+    .await?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escapes_csv_fields() {
+        assert_eq!(csv_field("plain"), "plain");
+        assert_eq!(csv_field("a, \"b\""), "\"a, \"\"b\"\"\"");
+        assert_eq!(csv_field("=HYPERLINK(1)"), "'=HYPERLINK(1)");
+    }
+}
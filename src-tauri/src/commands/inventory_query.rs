@@ -0,0 +1,155 @@
+//! Filter, sort and page the inventory on the Rust side so the UI only receives the visible page
+//! Queries run on the cached scan (or the on-disk index) and only scan when neither exists.
+
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use tauri::State;
+
+use super::inventory::{cached_or_scan, memory_cached_scan, InventoryCacheState};
+use super::inventory_export::{item_scope, variant_name};
+use crate::blocking::run_blocking;
+use crate::types::InventoryItem;
+
+const DEFAULT_PAGE_SIZE: usize = 50;
+const MAX_PAGE_SIZE: usize = 500;
+
+const RARITY_ORDER: [&str; 5] = ["common", "uncommon", "rare", "epic", "legendary"];
+
+/// Which items to return; empty lists and unset fields match everything
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct InventoryFilter {
+    /// Item types as serialized ("spell", "ring", ...)
+    pub item_types: Vec<String>,
+    pub rarities: Vec<String>,
+    /// user, project, plugin or builtin
+    pub scopes: Vec<String>,
+    pub sources: Vec<String>,
+    pub enabled: Option<bool>,
+    /// Case-insensitive match on name, description and id
+    pub text: Option<String>,
+}
+
+/// name (default), type, rarity, tokenWeight, runCount or lastUsed
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct InventorySort {
+    pub field: Option<String>,
+    pub descending: bool,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct InventoryPage {
+    pub offset: usize,
+    /// 50 when unset, at most 500
+    pub limit: Option<usize>,
+}
+
+/// One page of matching items
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InventoryQueryResult {
+    pub items: Vec<InventoryItem>,
+    /// Items matching the filter, across all pages
+    pub total: usize,
+    pub offset: usize,
+    pub limit: usize,
+    /// Token weight of the enabled matching items
+    pub enabled_token_weight: u64,
+}
+
+fn matches(item: &InventoryItem, filter: &InventoryFilter, text: &Option<String>) -> bool {
+    let any = |wanted: &[String], value: String| wanted.is_empty() || wanted.iter().any(|w| w.eq_ignore_ascii_case(&value));
+    any(&filter.item_types, variant_name(&item.item_type))
+        && any(&filter.rarities, variant_name(&item.rarity))
+        && any(&filter.scopes, item_scope(item))
+        && any(&filter.sources, variant_name(&item.source))
+        && filter.enabled.is_none_or(|enabled| item.enabled == enabled)
+        && text.as_ref().is_none_or(|text| {
+            [&item.name, &item.description, &item.id].iter().any(|field| field.to_lowercase().contains(text))
+        })
+}
+
+fn rarity_rank(item: &InventoryItem) -> usize {
+    let rarity = variant_name(&item.rarity);
+    RARITY_ORDER.iter().position(|r| *r == rarity).unwrap_or(0)
+}
+
+fn compare(a: &InventoryItem, b: &InventoryItem, field: &str) -> Ordering {
+    let status = |item: &InventoryItem| item.status.clone().unwrap_or_default();
+    let by_field = match field {
+        "type" => variant_name(&a.item_type).cmp(&variant_name(&b.item_type)),
+        "rarity" => rarity_rank(a).cmp(&rarity_rank(b)),
+        "tokenWeight" => a.token_weight.cmp(&b.token_weight),
+        "runCount" => status(a).run_count.cmp(&status(b).run_count),
+        "lastUsed" => status(a).last_used.cmp(&status(b).last_used),
+        _ => Ordering::Equal,
+    };
+    // Name breaks ties so pages are stable
+    by_field.then_with(|| a.name.to_lowercase().cmp(&b.name.to_lowercase())).then_with(|| a.id.cmp(&b.id))
+}
+
+fn query_items(items: Vec<InventoryItem>, filter: &InventoryFilter, sort: &InventorySort, page: &InventoryPage) -> InventoryQueryResult {
+    let text = filter.text.as_ref().map(|t| t.trim().to_lowercase()).filter(|t| !t.is_empty());
+    let mut matching: Vec<InventoryItem> = items.into_iter().filter(|item| matches(item, filter, &text)).collect();
+
+    let field = sort.field.as_deref().unwrap_or("name");
+    matching.sort_by(|a, b| {
+        let ordering = compare(a, b, field);
+        if sort.descending { ordering.reverse() } else { ordering }
+    });
+
+    let limit = page.limit.unwrap_or(DEFAULT_PAGE_SIZE).clamp(1, MAX_PAGE_SIZE);
+    InventoryQueryResult {
+        total: matching.len(),
+        enabled_token_weight: matching.iter().filter(|i| i.enabled).map(|i| i.token_weight as u64).sum(),
+        items: matching.into_iter().skip(page.offset).take(limit).collect(),
+        offset: page.offset,
+        limit,
+    }
+}
+
+/// One page of the inventory matching `filter`, ordered by `sort`
+#[tauri::command]
+pub async fn query_inventory(
+    state: State<'_, InventoryCacheState>,
+    project_path: Option<String>,
+    filter: Option<InventoryFilter>,
+    sort: Option<InventorySort>,
+    page: Option<InventoryPage>,
+) -> Result<InventoryQueryResult, String> {
+    let cached = memory_cached_scan(&state, &project_path)?;
+    run_blocking(move || {
+        let scan = cached_or_scan(cached, project_path.as_deref());
+        query_items(scan.items, &filter.unwrap_or_default(), &sort.unwrap_or_default(), &page.unwrap_or_default())
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{test_item, ItemRarity};
+
+    fn item(id: &str, name: &str, rarity: ItemRarity, token_weight: u32) -> InventoryItem {
+        InventoryItem { name: name.to_string(), rarity, token_weight, ..test_item(id) }
+    }
+
+    #[test]
+    fn filters_sorts_and_pages() {
+        let items = vec![
+            item("cmd_user_review", "Review", ItemRarity::Rare, 300),
+            item("cmd_project_deploy", "Deploy", ItemRarity::Common, 100),
+            item("cmd_user_release", "Release", ItemRarity::Epic, 200),
+        ];
+        let filter = InventoryFilter { scopes: vec!["user".to_string()], ..Default::default() };
+        let sort = InventorySort { field: Some("tokenWeight".to_string()), descending: true };
+        let page = InventoryPage { offset: 1, limit: Some(1) };
+
+        let result = query_items(items, &filter, &sort, &page);
+        assert_eq!(result.total, 2);
+        assert_eq!(result.enabled_token_weight, 500);
+        assert_eq!(result.items[0].id, "cmd_user_release");
+    }
+}
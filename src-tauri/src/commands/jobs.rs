@@ -0,0 +1,45 @@
+//! Scheduled background jobs: list them, run one now, or change its schedule
+
+use tauri::AppHandle;
+
+use crate::blocking::run_blocking;
+use crate::error::ArcadeError;
+use crate::scheduler::{job_info, load_jobs, run_job, update_record, JobInfo, MIN_INTERVAL_MINUTES};
+
+/// Every job with its schedule, last run and next run
+#[tauri::command]
+pub async fn list_jobs() -> Result<Vec<JobInfo>, String> {
+    run_blocking(|| load_jobs().into_iter().map(job_info).collect()).await
+}
+
+/// Run a job now, whatever its schedule; the next scheduled run counts from this one
+#[tauri::command]
+#[tracing::instrument(skip(app_handle), err)]
+pub async fn run_job_now(app_handle: AppHandle, job_id: String) -> Result<JobInfo, ArcadeError> {
+    run_job(&app_handle, &job_id).await
+}
+
+/// Turn a job on or off, or change how often it runs
+#[tauri::command]
+#[tracing::instrument(err)]
+pub async fn update_job(
+    job_id: String,
+    enabled: Option<bool>,
+    interval_minutes: Option<u32>,
+) -> Result<JobInfo, ArcadeError> {
+    if interval_minutes.is_some_and(|m| m < MIN_INTERVAL_MINUTES) {
+        return Err(ArcadeError::invalid_input(format!("Jobs run at most every {} minutes", MIN_INTERVAL_MINUTES)));
+    }
+    run_blocking(move || {
+        let record = update_record(&job_id, |record| {
+            if let Some(enabled) = enabled {
+                record.enabled = enabled;
+            }
+            if let Some(minutes) = interval_minutes {
+                record.interval_minutes = minutes;
+            }
+        })?;
+        Ok(job_info(record))
+    })
+    .await?
+}
@@ -0,0 +1,112 @@
+//! In-app diagnostics: recent lines from the arcade log files and the live log level
+//! Lines are read back from the files the `logging` module writes, newest file first.
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use tracing::level_filters::LevelFilter;
+
+use crate::blocking::run_blocking;
+use crate::logging::{current_level, log_dir, parse_level, set_level};
+
+const DEFAULT_LINES: usize = 200;
+const MAX_LINES: usize = 5000;
+
+/// One log record; continuation lines of a multi-line message are folded into it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LogLine {
+    pub timestamp: String,
+    /// ERROR, WARN, INFO, DEBUG or TRACE
+    pub level: String,
+    /// Spans, module and message as written
+    pub message: String,
+}
+
+/// Parse a log file into records
+fn parse_log(content: &str) -> Vec<LogLine> {
+    let mut records: Vec<LogLine> = Vec::new();
+    for line in content.lines() {
+        let (timestamp, rest) = line.split_once(' ').unwrap_or((line, ""));
+        let (level, message) = rest.trim_start().split_once(' ').unwrap_or((rest.trim_start(), ""));
+        let is_record = timestamp.len() >= 20 && timestamp.as_bytes()[4] == b'-' && !level.is_empty() && parse_level(level).is_ok();
+        if is_record {
+            records.push(LogLine {
+                timestamp: timestamp.to_string(),
+                level: level.to_string(),
+                message: message.to_string(),
+            });
+        } else if let Some(last) = records.last_mut() {
+            last.message.push('\n');
+            last.message.push_str(line);
+        }
+    }
+    records
+}
+
+fn recent_logs(min_level: LevelFilter, lines: usize) -> Result<Vec<LogLine>, String> {
+    let Some(dir) = log_dir().filter(|d| d.is_dir()) else {
+        return Ok(Vec::new());
+    };
+    let mut files: Vec<_> = fs::read_dir(&dir)
+        .map_err(|e| format!("Failed to read {}: {}", dir.display(), e))?
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| p.extension().is_some_and(|ext| ext == "log"))
+        .collect();
+    // Names carry the date, so the newest sorts last
+    files.sort();
+
+    let mut recent = Vec::new();
+    for file in files.iter().rev() {
+        let Ok(content) = fs::read_to_string(file) else {
+            continue;
+        };
+        let mut records: Vec<LogLine> = parse_log(&content)
+            .into_iter()
+            .filter(|r| parse_level(&r.level).is_ok_and(|level| level <= min_level))
+            .collect();
+        records.append(&mut recent);
+        recent = records;
+        if recent.len() >= lines {
+            break;
+        }
+    }
+    let skip = recent.len().saturating_sub(lines);
+    Ok(recent.split_off(skip))
+}
+
+/// The last `lines` log records (200 by default) at `level` or more severe (default: all
+/// that were logged), oldest first
+#[tauri::command]
+pub async fn get_recent_logs(level: Option<String>, lines: Option<usize>) -> Result<Vec<LogLine>, String> {
+    let min_level = match level {
+        Some(level) => parse_level(&level)?,
+        None => LevelFilter::TRACE,
+    };
+    let lines = lines.unwrap_or(DEFAULT_LINES).min(MAX_LINES);
+    run_blocking(move || recent_logs(min_level, lines)).await?
+}
+
+/// Set the level logged from now on ("error", "warn", "info", "debug", "trace" or "off").
+/// Returns the level now in effect.
+#[tauri::command]
+pub fn set_log_level(level: String) -> Result<String, String> {
+    set_level(parse_level(&level)?)?;
+    tracing::info!("Log level set to {}", level);
+    Ok(current_level().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_records_and_continuation_lines() {
+        let content = "2026-01-02T03:04:05.000000Z  INFO claudearcade_lib::watcher: Watching\n\
+                       2026-01-02T03:04:06.000000Z ERROR scan: claudearcade_lib::scanner: Failed\nsecond line\n";
+        let records = parse_log(content);
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].level, "INFO");
+        assert_eq!(records[1].message, "scan: claudearcade_lib::scanner: Failed\nsecond line");
+    }
+}
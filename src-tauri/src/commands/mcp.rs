@@ -1,7 +1,14 @@
-use crate::scanner::settings::{install_mcp_server as settings_install, remove_mcp_server as settings_remove, read_mcp_servers};
+use crate::scanner::settings::{install_mcp_server as settings_install, remove_mcp_server as settings_remove, read_mcp_servers, MCPServerConfig};
 use serde::Serialize;
+use serde_json::{json, Value};
 use std::collections::HashMap;
-use std::process::Command;
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+use crate::blocking::run_blocking;
+use crate::process::ProcessRequest;
+use crate::scanner::prerequisites::find_binary;
+use tauri::AppHandle;
 
 #[derive(Debug, Serialize)]
 pub struct MCPServerInfo {
@@ -22,80 +29,149 @@ pub enum MCPStatus {
 
 /// Get all installed MCP servers
 #[tauri::command]
-pub fn get_mcp_servers() -> HashMap<String, MCPServerInfo> {
-    read_mcp_servers()
-        .into_iter()
-        .map(|(id, config)| {
-            (id, MCPServerInfo {
-                command: config.command,
-                args: config.args,
+pub async fn get_mcp_servers() -> Result<HashMap<String, MCPServerInfo>, String> {
+    run_blocking(move || {
+        Ok(read_mcp_servers()?
+            .into_iter()
+            .map(|(id, config)| {
+                (id, MCPServerInfo {
+                    command: config.command,
+                    args: config.args,
+                })
             })
-        })
-        .collect()
+            .collect())
+    })
+    .await?
 }
 
 /// Install an MCP server
 #[tauri::command]
-pub fn install_mcp_server(
+#[tracing::instrument(err)]
+pub async fn install_mcp_server(
     server_id: String,
     command: String,
     args: Vec<String>,
 ) -> Result<(), String> {
-    settings_install(&server_id, &command, args)
+    run_blocking(move || settings_install(&server_id, &command, args)).await?
 }
 
 /// Remove an MCP server
 #[tauri::command]
-pub fn remove_mcp_server(server_id: String) -> Result<(), String> {
-    settings_remove(&server_id)
+#[tracing::instrument(err)]
+pub async fn remove_mcp_server(server_id: String) -> Result<(), String> {
+    run_blocking(move || settings_remove(&server_id)).await?
 }
 
-/// Check if a command exists on the system
-fn command_exists(cmd: &str) -> bool {
-    #[cfg(target_os = "windows")]
-    {
-        Command::new("where")
-            .arg(cmd)
-            .output()
-            .map(|output| output.status.success())
-            .unwrap_or(false)
+/// Long enough for `npx` to fetch a server the first time
+const PROBE_TIMEOUT: Duration = Duration::from_secs(20);
+
+/// The frontend polls every 30 seconds; starting every server that often would be a load of its own
+const PROBE_TTL: Duration = Duration::from_secs(5 * 60);
+
+/// Last probe per server: (its config when probed, when, status)
+static PROBES: Mutex<Vec<(String, String, Instant, &'static str)>> = Mutex::new(Vec::new());
+
+/// `probe_server`, answered from the last probe while it is recent and the config is unchanged
+fn cached_probe(id: &str, config: &MCPServerConfig) -> &'static str {
+    let fingerprint = format!("{:?}", config);
+    let recent = PROBES.lock().ok().and_then(|probes| {
+        probes
+            .iter()
+            .find(|(name, seen, at, _)| name == id && *seen == fingerprint && at.elapsed() < PROBE_TTL)
+            .map(|(_, _, _, status)| *status)
+    });
+    if let Some(status) = recent {
+        return status;
     }
+    let status = probe_server(id, config);
+    if let Ok(mut probes) = PROBES.lock() {
+        probes.retain(|(name, ..)| name != id);
+        probes.push((id.to_string(), fingerprint, Instant::now(), status));
+    }
+    status
+}
 
-    #[cfg(not(target_os = "windows"))]
-    {
-        Command::new("which")
-            .arg(cmd)
-            .output()
-            .map(|output| output.status.success())
-            .unwrap_or(false)
+/// Start a stdio MCP server and send it `initialize`: "connected" when it answers,
+/// "disconnected" when its command is missing or it fails, "unknown" when it didn't answer in time.
+/// The command is resolved like `find_binary` rather than with `which`/`where`, so PATHEXT on
+/// Windows and the install folders a GUI app's PATH misses count.
+fn probe_server(id: &str, config: &MCPServerConfig) -> &'static str {
+    let Some(binary) = find_binary(&config.command) else {
+        return "disconnected";
+    };
+    let initialize = json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "initialize",
+        "params": {
+            "protocolVersion": "2024-11-05",
+            "capabilities": {},
+            "clientInfo": { "name": "ClaudeArcade", "version": env!("CARGO_PKG_VERSION") },
+        },
+    });
+    let output = ProcessRequest::new(binary)
+        .args(&config.args)
+        .envs(config.env.clone().unwrap_or_default())
+        .stdin(format!("{}\n", initialize))
+        .until("\"result\"")
+        .timeout(PROBE_TIMEOUT)
+        .max_output(64 * 1024)
+        .id(format!("mcp-probe:{}", id))
+        .run();
+    match output {
+        Ok(output) if output.stdout_text().lines().any(answers_initialize) => "connected",
+        Ok(output) if output.timed_out || output.cancelled => "unknown",
+        _ => "disconnected",
     }
 }
 
+/// A JSON-RPC result for the `initialize` request
+fn answers_initialize(line: &str) -> bool {
+    serde_json::from_str::<Value>(line).is_ok_and(|v| v["id"] == 1 && v.get("result").is_some())
+}
+
 /// Check MCP server status
 /// Returns a map of server_id -> status (connected/disconnected/unknown)
-/// Note: Since MCP servers are spawned on-demand by Claude Code, we can only
-/// check if the command is available, not if it's actually running.
+/// Each configured server is started and asked to initialize, as Claude Code would, then stopped;
+/// probes run side by side within the process service's limit and are reused for a few minutes.
+/// Each can be cancelled as `mcp-probe:<server id>`.
 #[tauri::command]
-pub fn check_mcp_status(server_ids: Vec<String>) -> HashMap<String, String> {
-    let servers = read_mcp_servers();
+pub async fn check_mcp_status(app_handle: AppHandle, server_ids: Vec<String>) -> Result<HashMap<String, String>, String> {
+    run_blocking(move || {
+        let servers = read_mcp_servers()?;
+
+        Ok(thread::scope(|scope| {
+            let probes: Vec<_> = server_ids
+                .into_iter()
+                .map(|id| {
+                    let probe = servers.get_key_value(&id).map(|(name, config)| scope.spawn(move || cached_probe(name, config)));
+                    (id, probe)
+                })
+                .collect();
+            probes
+                .into_iter()
+                .map(|(id, probe)| {
+                    // Not configured: disconnected
+                    let status = probe.and_then(|p| p.join().ok()).unwrap_or("disconnected");
+                    if servers.contains_key(&id) {
+                        crate::notifications::mcp_probed(&app_handle, &id, status != "disconnected");
+                    }
+                    (id, status.to_string())
+                })
+                .collect()
+        }))
+    })
+    .await?
+}
 
-    server_ids
-        .into_iter()
-        .map(|id| {
-            let status = if let Some(config) = servers.get(&id) {
-                // Check if the command exists
-                if command_exists(&config.command) {
-                    // Command exists, mark as "unknown" (could be connected when Claude uses it)
-                    "unknown"
-                } else {
-                    // Command doesn't exist, definitely disconnected
-                    "disconnected"
-                }
-            } else {
-                // Server not configured
-                "disconnected"
-            };
-            (id, status.to_string())
-        })
-        .collect()
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recognizes_initialize_answers() {
+        assert!(answers_initialize(r#"{"jsonrpc":"2.0","id":1,"result":{"protocolVersion":"2024-11-05"}}"#));
+        assert!(!answers_initialize(r#"{"jsonrpc":"2.0","id":1,"error":{"code":-32600}}"#));
+        assert!(!answers_initialize("Starting server on stdio..."));
+    }
 }
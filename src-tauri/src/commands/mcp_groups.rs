@@ -0,0 +1,232 @@
+//! MCP groups: named sets of MCP servers ("work mode": github, linear, slack) switched together
+//! Each group shows up in the inventory as one composite trinket. The list lives in
+//! arcade_mcp_groups.json; membership is by server name, so a group outlives removing a server.
+
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+use super::bulk::{load_parked_mcp, write_settings_edits, SettingsEdit};
+use crate::blocking::run_blocking;
+use crate::error::ArcadeError;
+use crate::scanner::plugin_contents::MCP_SERVER_WEIGHT;
+use crate::scanner::settings::read_mcp_servers;
+use crate::types::{InventoryItem, ItemRarity, ItemSource, ItemType};
+
+/// Prefix of a group's inventory item id, followed by the group id
+pub(crate) const GROUP_PREFIX: &str = "mcpgroup_";
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct McpGroup {
+    pub id: String,
+    pub name: String,
+    pub servers: Vec<String>,
+}
+
+/// A group as the frontend shows it
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct McpGroupInfo {
+    #[serde(flatten)]
+    pub group: McpGroup,
+    /// Every member is configured and none is disabled
+    pub enabled: bool,
+    /// Members neither in settings.json nor disabled there
+    pub missing: Vec<String>,
+    pub token_weight: u32,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+struct McpGroupsFile {
+    groups: Vec<McpGroup>,
+}
+
+fn groups_path() -> Option<PathBuf> {
+    crate::paths::claude_dir().map(|d| d.join("arcade_mcp_groups.json"))
+}
+
+fn load_groups() -> McpGroupsFile {
+    groups_path()
+        .and_then(|path| crate::store::MCP_GROUPS.load(&path))
+        .unwrap_or_default()
+}
+
+fn save_groups(file: &McpGroupsFile) -> Result<(), ArcadeError> {
+    let path = groups_path().ok_or("Could not find home directory")?;
+    let content = crate::store::MCP_GROUPS.to_json(file)?;
+    crate::paths::atomic_write(&path, content).map_err(|e| ArcadeError::io(&path, e))
+}
+
+/// Lowercase letters, digits and dashes, for ids made from names
+pub(crate) fn slug(name: &str) -> String {
+    let slug: String = name.trim().to_lowercase().chars().map(|c| if c.is_ascii_alphanumeric() { c } else { '-' }).collect();
+    slug.split('-').filter(|s| !s.is_empty()).collect::<Vec<_>>().join("-")
+}
+
+fn group_info(group: &McpGroup, configured: &[String], disabled: &[String]) -> McpGroupInfo {
+    let missing: Vec<String> = group.servers.iter().filter(|s| !configured.contains(s)).cloned().collect();
+    McpGroupInfo {
+        enabled: missing.is_empty() && !group.servers.iter().any(|s| disabled.contains(s)),
+        token_weight: group.servers.len() as u32 * MCP_SERVER_WEIGHT,
+        missing,
+        group: group.clone(),
+    }
+}
+
+/// Every configured server, enabled or parked, and the parked (disabled) ones
+fn server_state() -> Result<(Vec<String>, Vec<String>), String> {
+    let disabled: Vec<String> = load_parked_mcp().keys().cloned().collect();
+    let mut configured: Vec<String> = read_mcp_servers()?.into_keys().collect();
+    for name in &disabled {
+        if !configured.contains(name) {
+            configured.push(name.clone());
+        }
+    }
+    Ok((configured, disabled))
+}
+
+fn all_group_info() -> Result<Vec<McpGroupInfo>, String> {
+    let (configured, disabled) = server_state()?;
+    Ok(load_groups().groups.iter().map(|g| group_info(g, &configured, &disabled)).collect())
+}
+
+/// Each group as a composite trinket, for the inventory scan; none while settings.json is broken,
+/// which the plugin scan reports
+pub(crate) fn group_items() -> Vec<InventoryItem> {
+    all_group_info()
+        .unwrap_or_default()
+        .into_iter()
+        .map(|info| InventoryItem {
+            id: format!("{}{}", GROUP_PREFIX, info.group.id),
+            name: info.group.name.clone(),
+            description: format!("MCP group: {}", info.group.servers.join(", ")),
+            item_type: ItemType::Trinket,
+            rarity: ItemRarity::Uncommon,
+            source: ItemSource::Mcp,
+            source_path: groups_path().map(|p| p.to_string_lossy().to_string()).unwrap_or_default(),
+            token_weight: info.token_weight,
+            enabled: info.enabled,
+            version: None,
+            author: None,
+            status: None,
+            environment: None,
+        })
+        .collect()
+}
+
+fn find_group(id: &str) -> Result<McpGroup, ArcadeError> {
+    let id = id.strip_prefix(GROUP_PREFIX).unwrap_or(id);
+    load_groups().groups.into_iter().find(|g| g.id == id).ok_or_else(|| ArcadeError::not_found("MCP group", id))
+}
+
+/// Turn every configured member on or off in one settings.json write
+fn set_group_enabled(id: &str, enabled: bool) -> Result<McpGroupInfo, ArcadeError> {
+    let group = find_group(id)?;
+    let (configured, _) = server_state()?;
+    if enabled {
+        if let Some(missing) = group.servers.iter().find(|s| !configured.contains(s)) {
+            return Err(ArcadeError::not_found("MCP server", missing.clone()));
+        }
+    }
+    let edits: Vec<SettingsEdit> = group
+        .servers
+        .iter()
+        .filter(|s| configured.contains(s))
+        .map(|name| SettingsEdit::McpEnabled { name: name.clone(), enabled })
+        .collect();
+    write_settings_edits(&edits.iter().collect::<Vec<_>>())?;
+
+    let (configured, disabled) = server_state()?;
+    Ok(group_info(&group, &configured, &disabled))
+}
+
+/// Every MCP group, with whether it is on
+#[tauri::command]
+pub async fn list_mcp_groups() -> Result<Vec<McpGroupInfo>, String> {
+    run_blocking(all_group_info).await?
+}
+
+/// Create a named group of configured MCP servers
+#[tauri::command]
+#[tracing::instrument(err)]
+pub async fn create_mcp_group(name: String, servers: Vec<String>) -> Result<McpGroupInfo, ArcadeError> {
+    run_blocking(move || {
+        let id = slug(&name);
+        if id.is_empty() {
+            return Err(ArcadeError::invalid_input(format!("Invalid group name: {}", name)));
+        }
+        if servers.is_empty() {
+            return Err(ArcadeError::invalid_input("An MCP group needs at least one server"));
+        }
+        let (configured, disabled) = server_state()?;
+        if let Some(unknown) = servers.iter().find(|s| !configured.contains(s)) {
+            return Err(ArcadeError::not_found("MCP server", unknown.clone()));
+        }
+
+        let mut file = load_groups();
+        if file.groups.iter().any(|g| g.id == id) {
+            return Err(ArcadeError::Conflict { message: format!("An MCP group named {} already exists", name) });
+        }
+        let mut members: Vec<String> = Vec::new();
+        for server in servers {
+            if !members.contains(&server) {
+                members.push(server);
+            }
+        }
+        let group = McpGroup { id, name: name.trim().to_string(), servers: members };
+        file.groups.push(group.clone());
+        save_groups(&file)?;
+
+        Ok(group_info(&group, &configured, &disabled))
+    })
+    .await?
+}
+
+/// Delete a group; its servers are left as they are
+#[tauri::command]
+#[tracing::instrument(err)]
+pub async fn delete_mcp_group(group_id: String) -> Result<(), ArcadeError> {
+    run_blocking(move || {
+        let group = find_group(&group_id)?;
+        let mut file = load_groups();
+        file.groups.retain(|g| g.id != group.id);
+        save_groups(&file)
+    })
+    .await?
+}
+
+/// Enable every server in the group at once. Fails without changing anything when a member
+/// is no longer configured.
+#[tauri::command]
+#[tracing::instrument(err)]
+pub async fn enable_mcp_group(group_id: String) -> Result<McpGroupInfo, ArcadeError> {
+    run_blocking(move || set_group_enabled(&group_id, true)).await?
+}
+
+/// Disable every server in the group at once
+#[tauri::command]
+#[tracing::instrument(err)]
+pub async fn disable_mcp_group(group_id: String) -> Result<McpGroupInfo, ArcadeError> {
+    run_blocking(move || set_group_enabled(&group_id, false)).await?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reports_group_state() {
+        assert_eq!(slug(" Work Mode! "), "work-mode");
+
+        let group = McpGroup { id: "work".to_string(), name: "Work".to_string(), servers: vec!["github".to_string(), "linear".to_string()] };
+        let configured = vec!["github".to_string(), "linear".to_string(), "slack".to_string()];
+        let info = group_info(&group, &configured, &[]);
+        assert!(info.enabled);
+        assert_eq!(info.token_weight, 2 * MCP_SERVER_WEIGHT);
+
+        assert!(!group_info(&group, &configured, &["linear".to_string()]).enabled);
+        let missing = group_info(&group, &configured[..1], &[]);
+        assert_eq!((missing.enabled, missing.missing), (false, vec!["linear".to_string()]));
+    }
+}
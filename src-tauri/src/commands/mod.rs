@@ -9,6 +9,60 @@ pub mod analytics;
 pub mod permissions;
 pub mod agents;
 pub mod project;
+pub mod watcher;
+pub mod backups;
+pub mod settings;
+pub mod git;
+pub mod init;
+pub mod templates;
+pub mod registry;
+pub mod plugins;
+pub mod sessions;
+pub mod session_export;
+pub mod todos;
+pub mod companions;
+pub mod import;
+pub mod character;
+pub mod sync;
+pub mod inventory_export;
+pub mod convert;
+pub mod agents_md;
+pub mod share;
+pub mod logs;
+pub mod inventory_query;
+pub mod integrity;
+pub mod audit;
+pub mod notifications;
+pub mod statusline;
+pub mod output_styles;
+pub mod search;
+pub mod doctor;
+pub mod cli_version;
+pub mod deep_links;
+pub mod wsl;
+pub mod profiles;
+pub mod slash_commands;
+pub mod usage_window;
+pub mod models;
+pub mod network;
+pub mod trash;
+pub mod compare;
+pub mod bulk;
+pub mod cleanup;
+pub mod context_breakdown;
+pub mod arcade_hooks;
+pub mod event_listener;
+pub mod mcp_groups;
+pub mod spellbooks;
+pub mod recommend;
+pub mod context_guard;
+pub mod onboarding;
+pub mod duplicates;
+pub mod processes;
+pub mod hooks;
+pub mod delegation;
+pub mod jobs;
+pub mod arcade_data;
 
 pub use inventory::*;
 pub use equipment::*;
@@ -21,3 +75,57 @@ pub use analytics::*;
 pub use permissions::*;
 pub use agents::*;
 pub use project::*;
+pub use watcher::*;
+pub use backups::*;
+pub use settings::*;
+pub use git::*;
+pub use init::*;
+pub use templates::*;
+pub use registry::*;
+pub use plugins::*;
+pub use sessions::*;
+pub use session_export::*;
+pub use todos::*;
+pub use companions::*;
+pub use import::*;
+pub use character::*;
+pub use sync::*;
+pub use inventory_export::*;
+pub use convert::*;
+pub use agents_md::*;
+pub use share::*;
+pub use logs::*;
+pub use inventory_query::*;
+pub use integrity::*;
+pub use audit::*;
+pub use notifications::*;
+pub use statusline::*;
+pub use output_styles::*;
+pub use search::*;
+pub use doctor::*;
+pub use cli_version::*;
+pub use deep_links::*;
+pub use wsl::*;
+pub use profiles::*;
+pub use slash_commands::*;
+pub use usage_window::*;
+pub use models::*;
+pub use network::*;
+pub use trash::*;
+pub use compare::*;
+pub use bulk::*;
+pub use cleanup::*;
+pub use context_breakdown::*;
+pub use arcade_hooks::*;
+pub use event_listener::*;
+pub use mcp_groups::*;
+pub use spellbooks::*;
+pub use recommend::*;
+pub use context_guard::*;
+pub use onboarding::*;
+pub use duplicates::*;
+pub use processes::*;
+pub use hooks::*;
+pub use delegation::*;
+pub use jobs::*;
+pub use arcade_data::*;
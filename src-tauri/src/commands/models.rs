@@ -0,0 +1,116 @@
+//! The `model` Claude Code starts sessions with, globally or for one project
+//! Each model comes with the context budget the load meter measures against.
+
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::blocking::run_blocking;
+use crate::error::ArcadeError;
+use crate::paths::check_writable;
+use crate::scanner::permissions::PermissionScope;
+use crate::scanner::settings::{read_settings_at, update_settings_field, write_settings_at};
+
+/// Aliases Claude Code resolves to its current models
+const MODEL_ALIASES: [&str; 6] = ["default", "sonnet", "opus", "haiku", "sonnet[1m]", "opusplan"];
+
+const DEFAULT_CONTEXT_BUDGET: u32 = 200_000;
+const LONG_CONTEXT_BUDGET: u32 = 1_000_000;
+
+/// The model in effect and where it is set
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModelSetting {
+    /// None when no settings file picks one (Claude Code's own default)
+    pub model: Option<String>,
+    /// enterprise, local, project or user
+    pub scope: Option<PermissionScope>,
+    pub context_budget: u32,
+    pub aliases: Vec<String>,
+}
+
+/// Context window of a model alias or id; `[1m]` variants get the long context window
+pub(crate) fn context_budget(model: Option<&str>) -> u32 {
+    match model {
+        Some(model) if model.ends_with("[1m]") => LONG_CONTEXT_BUDGET,
+        _ => DEFAULT_CONTEXT_BUDGET,
+    }
+}
+
+/// A known alias, or a full model id such as `claude-sonnet-4-5-20250929` (optionally `[1m]`)
+fn check_model(model: &str) -> Result<(), ArcadeError> {
+    let id = model.strip_suffix("[1m]").unwrap_or(model);
+    let is_model_id = id.starts_with("claude-") && id.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '.' | '@'));
+    if MODEL_ALIASES.contains(&model) || is_model_id {
+        Ok(())
+    } else {
+        Err(ArcadeError::invalid_input(format!(
+            "Unknown model '{}': use one of {} or a claude-* model id",
+            model,
+            MODEL_ALIASES.join(", ")
+        )))
+    }
+}
+
+/// The model sessions start with: the highest-precedence settings file that sets `model`
+#[tauri::command]
+pub async fn get_default_model(project_path: Option<String>) -> Result<ModelSetting, String> {
+    run_blocking(move || {
+        let found = PermissionScope::ALL.into_iter().find_map(|scope| {
+            let path = scope.settings_path(project_path.as_deref())?;
+            let model = read_settings_at(&path)?.get("model")?.as_str()?.to_string();
+            Some((model, scope))
+        });
+        let (model, scope) = found.unzip();
+        ModelSetting {
+            context_budget: context_budget(model.as_deref()),
+            model,
+            scope,
+            aliases: MODEL_ALIASES.iter().map(|a| a.to_string()).collect(),
+        }
+    })
+    .await
+}
+
+/// Set `model` in ~/.claude/settings.json, or for one project in its personal settings.local.json.
+/// None removes it, so the next scope down (or Claude Code's default) applies.
+#[tauri::command]
+#[tracing::instrument(err)]
+pub async fn set_default_model(model: Option<String>, project_path: Option<String>) -> Result<(), ArcadeError> {
+    if let Some(model) = model.as_deref() {
+        check_model(model)?;
+    }
+    run_blocking(move || {
+        let value = model.map_or(Value::Null, Value::String);
+        let Some(project) = project_path.as_deref() else {
+            return update_settings_field("model", value).map(|_| ());
+        };
+
+        let path = PermissionScope::Local.settings_path(Some(project)).ok_or("Project path required")?;
+        check_writable(&path, Some(project))?;
+        let mut settings = read_settings_at(&path).unwrap_or_else(|| Value::Object(Default::default()));
+        let map = settings.as_object_mut().ok_or_else(|| format!("{} is not a JSON object", path.display()))?;
+        if value.is_null() {
+            map.remove("model");
+        } else {
+            map.insert("model".to_string(), value);
+        }
+        write_settings_at(&path, &settings).map_err(ArcadeError::from)
+    })
+    .await?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accepts_aliases_and_model_ids() {
+        assert!(check_model("opus").is_ok());
+        assert!(check_model("claude-sonnet-4-5-20250929[1m]").is_ok());
+        assert!(check_model("gpt-4").is_err());
+        assert!(check_model("claude-x; rm -rf").is_err());
+
+        assert_eq!(context_budget(Some("sonnet[1m]")), 1_000_000);
+        assert_eq!(context_budget(None), 200_000);
+    }
+}
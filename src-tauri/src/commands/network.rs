@@ -0,0 +1,25 @@
+use crate::arcade_settings::{load_arcade_settings, save_arcade_settings};
+use crate::blocking::run_blocking;
+use crate::error::ArcadeError;
+use crate::http::NetworkSettings;
+
+/// Proxy, timeout, retry and offline settings for downloads and update checks
+#[tauri::command]
+pub async fn get_network_settings() -> Result<NetworkSettings, String> {
+    run_blocking(|| load_arcade_settings().network).await
+}
+
+/// Change the network settings; a proxy that isn't a valid URL is refused
+#[tauri::command]
+pub async fn set_network_settings(settings: NetworkSettings) -> Result<(), ArcadeError> {
+    if let Some(proxy) = settings.proxy.as_deref() {
+        reqwest::Proxy::all(proxy).map_err(|e| ArcadeError::invalid_input(format!("Invalid proxy {}: {}", proxy, e)))?;
+    }
+    run_blocking(move || {
+        let mut arcade = load_arcade_settings();
+        arcade.network = settings;
+        save_arcade_settings(&arcade)
+    })
+    .await??;
+    Ok(())
+}
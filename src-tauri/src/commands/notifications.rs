@@ -0,0 +1,20 @@
+use crate::arcade_settings::{load_arcade_settings, save_arcade_settings};
+use crate::blocking::run_blocking;
+use crate::notifications::NotificationSettings;
+
+/// Which OS notifications are turned on
+#[tauri::command]
+pub async fn get_notification_settings() -> Result<NotificationSettings, String> {
+    run_blocking(|| load_arcade_settings().notifications).await
+}
+
+/// Turn OS notifications on or off per kind
+#[tauri::command]
+pub async fn set_notification_settings(settings: NotificationSettings) -> Result<(), String> {
+    run_blocking(move || {
+        let mut arcade = load_arcade_settings();
+        arcade.notifications = settings;
+        save_arcade_settings(&arcade)
+    })
+    .await?
+}
@@ -0,0 +1,197 @@
+//! First-run setup steps, kept so the frontend wizard can resume where it left off
+//! Steps the backend can see for itself are detected when the status is asked for, or marked by
+//! the command that completes them; the rest are reported by the frontend. Each newly completed
+//! step is emitted as `onboarding-step-completed` with the updated status.
+//! The state lives in the default Claude folder's arcade_onboarding.json, and is only written
+//! once that folder exists, so it can't make the config dir step look done.
+
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use tauri::{AppHandle, Emitter};
+
+use crate::blocking::run_blocking;
+use crate::paths::ConfigPaths;
+use crate::scanner::prerequisites::find_binary;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum OnboardingStep {
+    ClaudeInstalled,
+    ConfigDirFound,
+    FirstScanCompleted,
+    FirstLoadoutSaved,
+}
+
+/// In the order the wizard walks them
+const STEPS: [OnboardingStep; 4] = [
+    OnboardingStep::ClaudeInstalled,
+    OnboardingStep::ConfigDirFound,
+    OnboardingStep::FirstScanCompleted,
+    OnboardingStep::FirstLoadoutSaved,
+];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CompletedStep {
+    step: OnboardingStep,
+    /// RFC3339
+    at: String,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+struct OnboardingFile {
+    completed: Vec<CompletedStep>,
+    dismissed: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StepStatus {
+    pub step: OnboardingStep,
+    pub done: bool,
+    pub completed_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OnboardingStatus {
+    pub steps: Vec<StepStatus>,
+    /// The first step not done yet; None once all are
+    pub current: Option<OnboardingStep>,
+    pub finished: bool,
+    /// The user closed the wizard before finishing
+    pub dismissed: bool,
+}
+
+/// Payload of `onboarding-step-completed`
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OnboardingStepCompleted {
+    pub step: OnboardingStep,
+    pub status: OnboardingStatus,
+}
+
+fn onboarding_path() -> Option<PathBuf> {
+    ConfigPaths::resolve_default().map(|p| p.claude_dir.join("arcade_onboarding.json"))
+}
+
+fn load_onboarding() -> OnboardingFile {
+    onboarding_path()
+        .and_then(|path| crate::store::ONBOARDING.load(&path))
+        .unwrap_or_default()
+}
+
+fn save_onboarding(file: &OnboardingFile) -> Result<(), String> {
+    let path = onboarding_path().ok_or("Could not find home directory")?;
+    if !path.parent().is_some_and(|d| d.is_dir()) {
+        return Err("The Claude folder doesn't exist yet".to_string());
+    }
+    let content = crate::store::ONBOARDING.to_json(file)?;
+    crate::paths::atomic_write(&path, content).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+fn status(file: &OnboardingFile) -> OnboardingStatus {
+    let steps: Vec<StepStatus> = STEPS
+        .iter()
+        .map(|&step| {
+            let completed = file.completed.iter().find(|c| c.step == step);
+            StepStatus { step, done: completed.is_some(), completed_at: completed.map(|c| c.at.clone()) }
+        })
+        .collect();
+    let current = steps.iter().find(|s| !s.done).map(|s| s.step);
+    OnboardingStatus { steps, current, finished: current.is_none(), dismissed: file.dismissed }
+}
+
+/// Steps the backend can check for itself
+fn detect(step: OnboardingStep) -> bool {
+    match step {
+        OnboardingStep::ClaudeInstalled => find_binary("claude").is_some(),
+        OnboardingStep::ConfigDirFound => ConfigPaths::resolve_default().is_some_and(|p| p.claude_dir.is_dir()),
+        OnboardingStep::FirstScanCompleted | OnboardingStep::FirstLoadoutSaved => false,
+    }
+}
+
+/// Record `steps` as done; returns the ones that weren't already
+fn record(file: &mut OnboardingFile, steps: &[OnboardingStep]) -> Vec<OnboardingStep> {
+    let mut newly = Vec::new();
+    for &step in steps {
+        if !file.completed.iter().any(|c| c.step == step) {
+            file.completed.push(CompletedStep { step, at: chrono::Utc::now().to_rfc3339() });
+            newly.push(step);
+        }
+    }
+    newly
+}
+
+/// Record the steps, save, and emit an event per newly completed one
+fn complete(app: &AppHandle, steps: &[OnboardingStep]) -> OnboardingStatus {
+    let mut file = load_onboarding();
+    let newly = record(&mut file, steps);
+    if !newly.is_empty() {
+        if let Err(e) = save_onboarding(&file) {
+            tracing::debug!("Onboarding progress not saved: {}", e);
+        }
+    }
+    let status = status(&file);
+    for step in newly {
+        let _ = app.emit("onboarding-step-completed", OnboardingStepCompleted { step, status: status.clone() });
+    }
+    status
+}
+
+/// Mark a step done from the command that completes it; cheap once it is
+pub(crate) fn complete_step(app: &AppHandle, step: OnboardingStep) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let _ = run_blocking(move || complete(&app, &[step])).await;
+    });
+}
+
+/// Setup steps and which are done, checking again for the ones the backend can detect
+#[tauri::command]
+pub async fn get_onboarding_status(app_handle: AppHandle) -> Result<OnboardingStatus, String> {
+    run_blocking(move || {
+        let detected: Vec<OnboardingStep> = STEPS.into_iter().filter(|&s| detect(s)).collect();
+        complete(&app_handle, &detected)
+    })
+    .await
+}
+
+/// Mark a step done from the frontend, e.g. `firstLoadoutSaved`
+#[tauri::command]
+pub async fn complete_onboarding_step(app_handle: AppHandle, step: OnboardingStep) -> Result<OnboardingStatus, String> {
+    run_blocking(move || complete(&app_handle, &[step])).await
+}
+
+/// Hide the wizard before it is finished, or bring it back
+#[tauri::command]
+pub async fn set_onboarding_dismissed(dismissed: bool) -> Result<OnboardingStatus, String> {
+    run_blocking(move || {
+        let mut file = load_onboarding();
+        file.dismissed = dismissed;
+        save_onboarding(&file)?;
+        Ok(status(&file))
+    })
+    .await?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tracks_steps_in_order() {
+        let mut file = OnboardingFile::default();
+        assert_eq!(status(&file).current, Some(OnboardingStep::ClaudeInstalled));
+
+        let newly = record(&mut file, &[OnboardingStep::ClaudeInstalled, OnboardingStep::FirstScanCompleted]);
+        assert_eq!(newly.len(), 2);
+        assert!(record(&mut file, &[OnboardingStep::ClaudeInstalled]).is_empty());
+
+        let status = status(&file);
+        assert_eq!(status.current, Some(OnboardingStep::ConfigDirFound));
+        assert!(!status.finished);
+        assert!(status.steps[2].done);
+    }
+}
@@ -0,0 +1,232 @@
+//! Commands for managing output styles (~/.claude/output-styles/ and .claude/output-styles/)
+//! A style replaces part of Claude Code's system prompt; `outputStyle` in settings.json picks the active one.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fs;
+use std::path::{Path, PathBuf};
+use crate::blocking::run_blocking;
+use crate::error::ArcadeError;
+use crate::paths::{check_name, check_writable};
+use crate::scanner::settings::{read_settings_full, update_settings_field};
+use crate::secrets::check_secrets;
+use crate::trash::move_to_trash;
+
+/// Styles Claude Code ships with; they have no file
+const BUILT_IN_STYLES: [&str; 3] = ["default", "Explanatory", "Learning"];
+
+/// An output style file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OutputStyle {
+    /// File name without `.md`
+    pub id: String,
+    /// What `outputStyle` refers to it by: the frontmatter name, or the id
+    pub name: String,
+    pub description: String,
+    /// Keep Claude Code's coding instructions alongside the style
+    pub keep_coding_instructions: bool,
+    pub file_path: String,
+    pub is_global: bool,
+    pub is_active: bool,
+    pub prompt: String,
+}
+
+fn get_global_styles_dir() -> PathBuf {
+    crate::paths::claude_dir()
+        .unwrap_or_default()
+        .join("output-styles")
+}
+
+fn get_project_styles_dir(project_path: &str) -> PathBuf {
+    PathBuf::from(project_path).join(".claude").join("output-styles")
+}
+
+fn styles_dir(is_global: bool, project_path: Option<&str>) -> Result<PathBuf, String> {
+    if is_global {
+        Ok(get_global_styles_dir())
+    } else {
+        let project = project_path.ok_or("Project path required for project output styles")?;
+        Ok(get_project_styles_dir(project))
+    }
+}
+
+fn active_style() -> Option<String> {
+    read_settings_full().ok().and_then(|s| s.output_style)
+}
+
+fn read_style_at_path(path: &Path, is_global: bool, active: Option<&str>) -> Option<OutputStyle> {
+    let content = fs::read_to_string(path).ok()?;
+    let id = path.file_stem()?.to_str()?.to_string();
+
+    #[derive(Deserialize, Default)]
+    #[serde(rename_all = "kebab-case")]
+    struct Frontmatter {
+        name: Option<String>,
+        description: Option<String>,
+        keep_coding_instructions: Option<bool>,
+    }
+
+    let trimmed = content.trim_start();
+    let (frontmatter, prompt) = match trimmed.strip_prefix("---").and_then(|rest| rest.split_once("\n---")) {
+        Some((yaml, body)) => (serde_yaml::from_str::<Frontmatter>(yaml).unwrap_or_default(), body.trim()),
+        None => (Frontmatter::default(), trimmed.trim()),
+    };
+    let name = frontmatter.name.unwrap_or_else(|| id.clone());
+
+    Some(OutputStyle {
+        is_active: active == Some(name.as_str()),
+        description: frontmatter.description.unwrap_or_default(),
+        keep_coding_instructions: frontmatter.keep_coding_instructions.unwrap_or(false),
+        file_path: path.to_string_lossy().to_string(),
+        prompt: prompt.to_string(),
+        id,
+        name,
+        is_global,
+    })
+}
+
+fn styles_in(dir: &Path, is_global: bool, active: Option<&str>) -> Vec<OutputStyle> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    entries
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| p.is_file() && p.extension().is_some_and(|e| e == "md"))
+        .filter_map(|p| read_style_at_path(&p, is_global, active))
+        .collect()
+}
+
+fn generate_style_content(name: &str, description: &str, keep_coding_instructions: bool, prompt: &str) -> String {
+    let mut lines = vec!["---".to_string(), format!("name: {}", name)];
+    if !description.is_empty() {
+        lines.push(format!("description: {}", description));
+    }
+    if keep_coding_instructions {
+        lines.push("keep-coding-instructions: true".to_string());
+    }
+    lines.push("---".to_string());
+    lines.push(String::new());
+    lines.push(prompt.to_string());
+    lines.join("\n")
+}
+
+/// List output styles (global and project), marking the active one
+#[tauri::command]
+pub async fn list_output_styles(project_path: Option<String>) -> Result<Vec<OutputStyle>, String> {
+    run_blocking(move || {
+        let active = active_style();
+        let mut styles = styles_in(&get_global_styles_dir(), true, active.as_deref());
+        if let Some(project) = project_path.as_deref() {
+            styles.extend(styles_in(&get_project_styles_dir(project), false, active.as_deref()));
+        }
+        styles.sort_by_key(|s| s.name.to_lowercase());
+        styles
+    })
+    .await
+}
+
+/// Create a new output style; fails if one with this id already exists
+#[tauri::command]
+#[tracing::instrument(skip(prompt), err)]
+pub async fn create_output_style(
+    style_id: String,
+    name: Option<String>,
+    description: Option<String>,
+    keep_coding_instructions: Option<bool>,
+    prompt: String,
+    is_global: bool,
+    project_path: Option<String>,
+) -> Result<OutputStyle, ArcadeError> {
+    run_blocking(move || {
+        check_name(&style_id)?;
+        let dir = styles_dir(is_global, project_path.as_deref())?;
+        let file_path = dir.join(format!("{}.md", style_id));
+        if file_path.exists() {
+            return Err(ArcadeError::Conflict { message: format!("Output style '{}' already exists", style_id) });
+        }
+        check_writable(&file_path, project_path.as_deref())?;
+
+        let name = name.filter(|n| !n.trim().is_empty()).unwrap_or_else(|| style_id.clone());
+        let content = generate_style_content(
+            &name,
+            description.as_deref().unwrap_or_default(),
+            keep_coding_instructions.unwrap_or(false),
+            &prompt,
+        );
+
+        fs::create_dir_all(&dir).map_err(|e| ArcadeError::io(&dir, e))?;
+        crate::watcher::write_expected(&file_path, &content).map_err(|e| ArcadeError::io(&file_path, e))?;
+
+        read_style_at_path(&file_path, is_global, active_style().as_deref())
+            .ok_or_else(|| ArcadeError::not_found("Output style", &style_id))
+    })
+    .await?
+}
+
+/// Save raw output style content (frontmatter and prompt), refusing likely credentials unless `allow_secrets`
+#[tauri::command]
+#[tracing::instrument(skip(content), err)]
+pub async fn save_output_style_content(
+    style_id: String,
+    content: String,
+    is_global: bool,
+    project_path: Option<String>,
+    allow_secrets: Option<bool>,
+) -> Result<(), ArcadeError> {
+    run_blocking(move || {
+        check_name(&style_id)?;
+        check_secrets(&content, allow_secrets)?;
+        let dir = styles_dir(is_global, project_path.as_deref())?;
+        fs::create_dir_all(&dir).map_err(|e| ArcadeError::io(&dir, e))?;
+
+        let file_path = dir.join(format!("{}.md", style_id));
+        check_writable(&file_path, project_path.as_deref())?;
+        crate::watcher::write_expected(&file_path, content).map_err(|e| ArcadeError::io(&file_path, e))
+    })
+    .await?
+}
+
+/// Delete an output style. If it was the active one, `outputStyle` is reset to the default.
+#[tauri::command]
+#[tracing::instrument(err)]
+pub async fn delete_output_style(style_id: String, is_global: bool, project_path: Option<String>) -> Result<(), ArcadeError> {
+    run_blocking(move || {
+        check_name(&style_id)?;
+        let file_path = styles_dir(is_global, project_path.as_deref())?.join(format!("{}.md", style_id));
+        let Some(style) = read_style_at_path(&file_path, is_global, active_style().as_deref()) else {
+            return Err(ArcadeError::not_found("Output style", &style_id));
+        };
+
+        check_writable(&file_path, project_path.as_deref())?;
+        move_to_trash(&file_path, "outputStyle")?;
+
+        if style.is_active {
+            update_settings_field("outputStyle", Value::Null)?;
+        }
+        Ok(())
+    })
+    .await?
+}
+
+/// Set `outputStyle` in the user settings.json. `None` (or "default") goes back to Claude Code's default style.
+#[tauri::command]
+#[tracing::instrument(err)]
+pub async fn set_active_output_style(name: Option<String>, project_path: Option<String>) -> Result<(), ArcadeError> {
+    run_blocking(move || {
+        let name = name.filter(|n| n != "default");
+        if let Some(name) = name.as_deref() {
+            let mut known = styles_in(&get_global_styles_dir(), true, None);
+            if let Some(project) = project_path.as_deref() {
+                known.extend(styles_in(&get_project_styles_dir(project), false, None));
+            }
+            if !BUILT_IN_STYLES.contains(&name) && !known.iter().any(|s| s.name == name) {
+                return Err(ArcadeError::not_found("Output style", name));
+            }
+        }
+        update_settings_field("outputStyle", name.map(Value::String).unwrap_or(Value::Null))?;
+        Ok(())
+    })
+    .await?
+}
@@ -1,13 +1,309 @@
-use crate::scanner::settings::{read_permissions, write_permissions, PermissionsConfig};
+use std::collections::HashMap;
+use std::path::Path;
+use std::time::SystemTime;
+use chrono::{Duration, Utc};
+use serde::Serialize;
 
-/// Get all permissions from settings
+use crate::scanner::permissions::{
+    collect_rules, effective_permissions, evaluate, EffectivePermissions, MergedPermissions,
+    PermissionDecision, PermissionScope, RuleBehavior,
+};
+use crate::scanner::transcripts::{
+    list_transcript_files, modified_time, read_transcript_entries, tool_results, tool_uses,
+};
+use crate::scanner::settings::{
+    read_permissions, settings_revision, write_permissions, set_default_mode, set_additional_directories,
+    set_disable_bypass_permissions_mode, AtRevision, PermissionsConfig,
+};
+use crate::blocking::run_blocking;
+use crate::error::ArcadeError;
+
+/// Get all permissions from settings, with the settings revision to save them against
+#[tauri::command]
+pub async fn get_permissions() -> Result<AtRevision<PermissionsConfig>, String> {
+    run_blocking(|| {
+        let revision = settings_revision();
+        Ok(AtRevision { value: read_permissions()?, revision })
+    })
+    .await?
+}
+
+/// Set permissions in settings. With the `revision` they were read at, a settings.json changed
+/// since then fails with a settings conflict instead of being overwritten. Returns the new revision.
+#[tauri::command]
+#[tracing::instrument(skip(permissions), err)]
+pub async fn set_permissions(permissions: PermissionsConfig, revision: Option<String>) -> Result<String, ArcadeError> {
+    run_blocking(move || {
+        write_permissions(&permissions, revision.as_deref())?;
+        Ok(settings_revision())
+    })
+    .await?
+}
+
+/// Set the default permission mode (None clears it)
+#[tauri::command]
+pub async fn set_permission_default_mode(mode: Option<String>) -> Result<(), String> {
+    run_blocking(move || set_default_mode(mode.as_deref())).await?
+}
+
+/// Add a directory Claude is allowed to access outside the project
+#[tauri::command]
+pub async fn add_additional_directory(path: String) -> Result<Vec<String>, String> {
+    run_blocking(move || {
+        let path = path.trim().to_string();
+        if path.is_empty() {
+            return Err("Directory path cannot be empty".to_string());
+        }
+
+        let mut directories = read_permissions()?.additional_directories.unwrap_or_default();
+        if !directories.contains(&path) {
+            directories.push(path);
+        }
+
+        set_additional_directories(directories.clone())?;
+        Ok(directories)
+    })
+    .await?
+}
+
+/// Remove a directory from the additional directories list
 #[tauri::command]
-pub fn get_permissions() -> PermissionsConfig {
-    read_permissions()
+pub async fn remove_additional_directory(path: String) -> Result<Vec<String>, String> {
+    run_blocking(move || {
+        let mut directories = read_permissions()?.additional_directories.unwrap_or_default();
+        directories.retain(|d| d != &path);
+
+        set_additional_directories(directories.clone())?;
+        Ok(directories)
+    })
+    .await?
+}
+
+/// Prevent (or allow) sessions from entering bypassPermissions mode
+#[tauri::command]
+pub async fn set_bypass_permissions_disabled(disabled: bool) -> Result<(), String> {
+    run_blocking(move || set_disable_bypass_permissions_mode(disabled)).await?
+}
+
+/// Check what Claude Code would do with a tool call given the merged rules.
+/// `scopes` limits which settings files are consulted (defaults to all of them).
+#[tauri::command]
+pub async fn test_permission(
+    tool_name: String,
+    tool_input: serde_json::Value,
+    scopes: Option<Vec<String>>,
+    project_path: Option<String>,
+) -> Result<PermissionDecision, String> {
+    run_blocking(move || {
+        let scopes = match scopes {
+            Some(names) => names
+                .iter()
+                .map(|name| PermissionScope::parse(name).ok_or_else(|| format!("Unknown permission scope '{}'", name)))
+                .collect::<Result<Vec<_>, _>>()?,
+            None => PermissionScope::ALL.to_vec(),
+        };
+
+        let merged = collect_rules(&scopes, project_path.as_deref());
+        let project_root = project_path.as_deref().map(Path::new);
+
+        Ok(evaluate(&tool_name, &tool_input, &merged, project_root))
+    })
+    .await?
+}
+
+/// Per-tool counts from the permission audit
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ToolAuditEntry {
+    pub tool_name: String,
+    pub requests: u32,
+    pub auto_approved: u32,
+    pub prompted: u32,
+    pub approved_at_prompt: u32,
+    pub rejected_at_prompt: u32,
+    pub blocked_by_deny: u32,
+}
+
+/// How often a configured rule decided a tool call
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RuleHitCount {
+    pub rule: String,
+    pub behavior: RuleBehavior,
+    pub scope: PermissionScope,
+    pub hits: u32,
+}
+
+/// An ask-prompt the user has approved every time - a candidate for the allow list
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AllowCandidate {
+    pub suggested_rule: String,
+    pub approvals: u32,
+    pub example: String,
+}
+
+/// Summary of permission activity over recent sessions
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PermissionAudit {
+    pub days: u32,
+    pub sessions_scanned: u32,
+    pub total_tool_calls: u32,
+    pub tools: Vec<ToolAuditEntry>,
+    pub rule_hits: Vec<RuleHitCount>,
+    pub allow_candidates: Vec<AllowCandidate>,
+}
+
+/// Approvals needed before an always-approved prompt is suggested for the allow list
+const ALLOW_CANDIDATE_MIN_APPROVALS: u32 = 3;
+
+/// Suggest an allow rule that would cover a prompted tool call
+fn suggest_rule(tool_name: &str, tool_input: &serde_json::Value, cwd: Option<&str>) -> String {
+    match tool_name {
+        "Bash" => {
+            let command = tool_input.get("command").and_then(|v| v.as_str()).unwrap_or_default();
+            let words: Vec<&str> = command.split_whitespace().take(2).collect();
+            let prefix = match words.as_slice() {
+                [first, second] if second.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+                    && !second.starts_with('-') => format!("{} {}", first, second),
+                [first, ..] => first.to_string(),
+                [] => return "Bash".to_string(),
+            };
+            format!("Bash({}:*)", prefix)
+        }
+        "WebFetch" => {
+            let url = tool_input.get("url").and_then(|v| v.as_str()).unwrap_or_default();
+            let host = url.split("://").nth(1).unwrap_or(url).split('/').next().unwrap_or_default();
+            if host.is_empty() {
+                "WebFetch".to_string()
+            } else {
+                format!("WebFetch(domain:{})", host)
+            }
+        }
+        "Edit" | "MultiEdit" | "Write" | "NotebookEdit" => {
+            let file = tool_input.get("file_path")
+                .or_else(|| tool_input.get("notebook_path"))
+                .and_then(|v| v.as_str())
+                .unwrap_or_default();
+            let relative = cwd.and_then(|root| file.strip_prefix(root)).map(|p| p.trim_start_matches('/'));
+            match relative.and_then(|p| p.split('/').next()).filter(|_| relative.is_some_and(|r| r.contains('/'))) {
+                Some(dir) => format!("Edit(/{}/**)", dir),
+                None => "Edit".to_string(),
+            }
+        }
+        _ => tool_name.to_string(),
+    }
+}
+
+/// Summarize permission prompts, auto-approvals and rule hits from recent transcripts.
+/// Decisions are reconstructed by replaying tool calls against the current rules.
+#[tauri::command]
+pub async fn get_permission_audit(days: u32, project_path: Option<String>) -> Result<PermissionAudit, String> {
+    run_blocking(move || {
+        let cutoff = Utc::now() - Duration::days(days as i64);
+        let cutoff_time = SystemTime::now() - std::time::Duration::from_secs(days as u64 * 86_400);
+
+        let mut audit = PermissionAudit { days, ..Default::default() };
+        let mut tools: HashMap<String, ToolAuditEntry> = HashMap::new();
+        let mut rule_hits: HashMap<(String, PermissionScope), RuleHitCount> = HashMap::new();
+        // suggested rule -> (approvals, rejections, example)
+        let mut prompts: HashMap<String, (u32, u32, String)> = HashMap::new();
+        let mut merged_by_cwd: HashMap<Option<String>, MergedPermissions> = HashMap::new();
+
+        for file in list_transcript_files(project_path.as_deref()) {
+            // Skip transcripts untouched since before the window
+            if modified_time(&file).is_some_and(|t| t < cutoff_time) {
+                continue;
+            }
+
+            let entries = read_transcript_entries(&file);
+            let results = tool_results(&entries);
+            let uses: Vec<_> = tool_uses(&entries)
+                .into_iter()
+                .filter(|u| u.timestamp.is_none_or(|t| t >= cutoff))
+                .collect();
+            if uses.is_empty() {
+                continue;
+            }
+            audit.sessions_scanned += 1;
+
+            for tool_use in uses {
+                audit.total_tool_calls += 1;
+
+                let cwd = tool_use.cwd.clone().or_else(|| project_path.clone());
+                let merged = merged_by_cwd
+                    .entry(cwd.clone())
+                    .or_insert_with(|| collect_rules(&PermissionScope::ALL, cwd.as_deref()));
+                let decision = evaluate(&tool_use.name, &tool_use.input, merged, cwd.as_deref().map(Path::new));
+
+                if let Some(rule) = &decision.matched_rule {
+                    rule_hits
+                        .entry((rule.rule.clone(), rule.scope))
+                        .or_insert_with(|| RuleHitCount {
+                            rule: rule.rule.clone(),
+                            behavior: rule.behavior,
+                            scope: rule.scope,
+                            hits: 0,
+                        })
+                        .hits += 1;
+                }
+
+                let stats = tools.entry(tool_use.name.clone()).or_insert_with(|| ToolAuditEntry {
+                    tool_name: tool_use.name.clone(),
+                    ..Default::default()
+                });
+                stats.requests += 1;
+
+                match decision.behavior {
+                    RuleBehavior::Allow => stats.auto_approved += 1,
+                    RuleBehavior::Deny => stats.blocked_by_deny += 1,
+                    RuleBehavior::Ask => {
+                        stats.prompted += 1;
+                        let rejected = results.get(&tool_use.id).is_some_and(|r| r.was_rejected());
+                        if rejected {
+                            stats.rejected_at_prompt += 1;
+                        } else {
+                            stats.approved_at_prompt += 1;
+                        }
+
+                        // Only default prompts are allow candidates; explicit ask rules are deliberate
+                        if decision.matched_rule.is_none() {
+                            let suggestion = suggest_rule(&tool_use.name, &tool_use.input, cwd.as_deref());
+                            let entry = prompts.entry(suggestion).or_insert_with(|| {
+                                (0, 0, tool_use.input.to_string().chars().take(200).collect())
+                            });
+                            if rejected {
+                                entry.1 += 1;
+                            } else {
+                                entry.0 += 1;
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        audit.tools = tools.into_values().collect();
+        audit.tools.sort_by_key(|t| std::cmp::Reverse(t.requests));
+
+        audit.rule_hits = rule_hits.into_values().collect();
+        audit.rule_hits.sort_by_key(|r| std::cmp::Reverse(r.hits));
+
+        audit.allow_candidates = prompts
+            .into_iter()
+            .filter(|(_, (approvals, rejections, _))| *rejections == 0 && *approvals >= ALLOW_CANDIDATE_MIN_APPROVALS)
+            .map(|(suggested_rule, (approvals, _, example))| AllowCandidate { suggested_rule, approvals, example })
+            .collect();
+        audit.allow_candidates.sort_by_key(|c| std::cmp::Reverse(c.approvals));
+
+        Ok(audit)
+    })
+    .await?
 }
 
-/// Set permissions in settings
+/// Get the merged permissions from every settings scope with the file each rule came from
 #[tauri::command]
-pub fn set_permissions(permissions: PermissionsConfig) -> Result<(), String> {
-    write_permissions(&permissions)
+pub async fn get_effective_permissions(project_path: Option<String>) -> Result<EffectivePermissions, String> {
+    run_blocking(move || effective_permissions(project_path.as_deref())).await
 }
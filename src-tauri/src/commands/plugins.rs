@@ -0,0 +1,926 @@
+//! Plugin management: install, update and inspect plugins from marketplace sources
+//! Mirrors Claude Code's layout: files in plugins/cache/<marketplace>/<plugin>/<version>, tracked in installed_plugins.json.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use walkdir::WalkDir;
+
+use crate::scanner::disable_plugin;
+use crate::scanner::integrity::{forget_install, listing_changed, record_install};
+use crate::scanner::plugin::{
+    installed_plugin_path, load_marketplace, plugins_dir, read_installed_plugins_raw, read_marketplace_catalog,
+    write_installed_plugins, PluginMetadata, PluginSource,
+};
+use crate::scanner::plugin_contents::{
+    plugin_components, read_plugin_manifest, total_weight, PluginComponent, PluginWeight,
+};
+use crate::scanner::marketplace::MarketplaceDiagnostic;
+use crate::scanner::prerequisites::{check_prerequisites, PluginPrerequisite};
+use crate::blocking::run_blocking;
+use crate::process::ProcessRequest;
+
+/// Outcome of installing a plugin
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PluginInstallResult {
+    pub plugin_id: String,
+    pub version: String,
+    pub install_path: String,
+    /// Commit the files were taken from, when the source is a git checkout
+    pub git_commit_sha: Option<String>,
+    /// Problems worth showing that didn't stop the install
+    #[serde(default)]
+    pub warnings: Vec<String>,
+}
+
+/// Split "name@marketplace", rejecting anything that could escape the plugins folder
+fn split_plugin_id(plugin_id: &str) -> Result<(&str, &str), String> {
+    let (name, marketplace) = plugin_id
+        .split_once('@')
+        .ok_or_else(|| format!("Plugin id must look like name@marketplace: {}", plugin_id))?;
+
+    let is_safe = |part: &str| !part.is_empty() && part != "." && part != ".." && !part.contains(['/', '\\']);
+    if !is_safe(name) || !is_safe(marketplace) {
+        return Err(format!("Invalid plugin id: {}", plugin_id));
+    }
+    Ok((name, marketplace))
+}
+
+/// Clones and pulls of large marketplaces take a while; anything longer is a hung credential prompt
+const GIT_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// Run git, returning trimmed stdout or stderr as the error
+pub(crate) fn run_git(dir: Option<&Path>, args: &[&str]) -> Result<String, String> {
+    let mut command = ProcessRequest::new("git");
+    if let Some(dir) = dir {
+        command = command.arg("-C").arg(dir);
+    }
+    let output = command
+        .args(args)
+        .timeout(GIT_TIMEOUT)
+        .run()
+        .map_err(|e| format!("Failed to run git: {}", e))?;
+
+    if output.success() {
+        Ok(output.stdout_text().trim().to_string())
+    } else if output.timed_out {
+        Err(format!("git {} timed out", args.first().unwrap_or(&"")))
+    } else {
+        Err(output.stderr_text().trim().to_string())
+    }
+}
+
+/// Copy a directory tree, leaving out git metadata
+fn copy_dir(from: &Path, to: &Path) -> Result<(), String> {
+    for entry in WalkDir::new(from)
+        .into_iter()
+        .filter_entry(|e| e.file_name() != ".git")
+        .filter_map(|e| e.ok())
+    {
+        let relative = entry.path().strip_prefix(from).map_err(|e| e.to_string())?;
+        let destination = to.join(relative);
+        if entry.file_type().is_dir() {
+            fs::create_dir_all(&destination).map_err(|e| format!("Failed to create {}: {}", destination.display(), e))?;
+        } else if entry.file_type().is_file() {
+            fs::copy(entry.path(), &destination).map_err(|e| format!("Failed to copy {}: {}", relative.display(), e))?;
+        }
+    }
+    Ok(())
+}
+
+/// Catalog values passed to git must not be read as options (`--upload-pack=...` runs a command)
+fn check_git_arg<'a>(value: &'a str, what: &str) -> Result<&'a str, String> {
+    if value.starts_with('-') {
+        return Err(format!("Plugin source has an invalid {}: {}", what, value));
+    }
+    Ok(value)
+}
+
+/// Clone URL for a remote plugin source
+fn remote_url(source: &str, repo: Option<&str>, url: Option<&str>) -> Result<String, String> {
+    let url = match source {
+        "github" => repo.map(|r| format!("https://github.com/{}.git", r)),
+        "git" | "url" => url.map(String::from),
+        other => return Err(format!("Unsupported plugin source type: {}", other)),
+    }
+    .ok_or_else(|| format!("Plugin source '{}' is missing its repository", source))?;
+    check_git_arg(&url, "repository")?;
+    Ok(url)
+}
+
+/// Where a version of a plugin is installed; the version comes from third-party catalogs and
+/// manifests, so it has to be a single folder name inside the plugin's cache
+fn install_dir(plugin_cache: &Path, version: &str) -> Result<PathBuf, String> {
+    crate::paths::check_name(version).map_err(|_| format!("Plugin has an invalid version: {}", version))?;
+    Ok(plugin_cache.join(version))
+}
+
+/// Fetch a plugin's files into `staging`, returning the commit they came from
+fn fetch_plugin_source(source: &PluginSource, marketplace_dir: &Path, staging: &Path) -> Result<Option<String>, String> {
+    match source {
+        PluginSource::Path(relative) => {
+            // Catalogs are third-party content; never read outside the marketplace checkout
+            if Path::new(relative).is_absolute() || relative.split(['/', '\\']).any(|part| part == "..") {
+                return Err(format!("Plugin source path leaves the marketplace: {}", relative));
+            }
+            let from = marketplace_dir.join(relative);
+            if !from.is_dir() {
+                return Err(format!("Plugin source not found in marketplace: {}", relative));
+            }
+            copy_dir(&from, staging)?;
+            Ok(run_git(Some(marketplace_dir), &["rev-parse", "HEAD"]).ok())
+        }
+        PluginSource::Remote { source, repo, url, git_ref } => {
+            let clone_url = remote_url(source, repo.as_deref(), url.as_deref())?;
+
+            let staging_str = staging.to_string_lossy().to_string();
+            let mut args = vec!["clone", "--depth", "1"];
+            if let Some(git_ref) = git_ref {
+                args.extend(["--branch", check_git_arg(git_ref, "ref")?]);
+            }
+            args.extend(["--", clone_url.as_str(), staging_str.as_str()]);
+            run_git(None, &args).map_err(|e| format!("Failed to clone {}: {}", clone_url, e))?;
+
+            Ok(run_git(Some(staging), &["rev-parse", "HEAD"]).ok())
+        }
+    }
+}
+
+/// Where a catalog entry's files come from; entries without a source live in ./plugins/<name>
+fn source_of(metadata: &PluginMetadata, name: &str) -> PluginSource {
+    metadata
+        .source
+        .clone()
+        .unwrap_or_else(|| PluginSource::Path(format!("./plugins/{}", name)))
+}
+
+/// Version declared in the plugin's own .claude-plugin/plugin.json
+fn manifest_version(plugin_dir: &Path) -> Option<String> {
+    let content = fs::read_to_string(plugin_dir.join(".claude-plugin").join("plugin.json")).ok()?;
+    let manifest: Value = serde_json::from_str(&content).ok()?;
+    manifest.get("version").and_then(|v| v.as_str()).map(String::from)
+}
+
+/// Fetch a plugin from its marketplace into the plugin cache and record it in installed_plugins.json
+fn install_from_marketplace(plugin_id: &str) -> Result<PluginInstallResult, String> {
+    let (name, marketplace) = split_plugin_id(plugin_id)?;
+    let plugins = plugins_dir().ok_or("Could not find home directory")?;
+
+    // Fail before fetching anything if installed_plugins.json can't be updated
+    let mut installed = read_installed_plugins_raw()?;
+
+    let catalog = read_marketplace_catalog();
+    let metadata = catalog
+        .get(plugin_id)
+        .ok_or_else(|| format!("Plugin not found in marketplace '{}': {}", marketplace, name))?;
+    let source = source_of(metadata, name);
+    let mut warnings = Vec::new();
+    if listing_changed(plugin_id, &metadata.entry_digest) {
+        warnings.push(format!("The marketplace entry for {} changed since it was listed; review what was installed", name));
+    }
+
+    let plugin_cache = plugins.join("cache").join(marketplace).join(name);
+    let staging = plugin_cache.join(".installing.tmp");
+    if staging.exists() {
+        fs::remove_dir_all(&staging).map_err(|e| format!("Failed to clear previous install attempt: {}", e))?;
+    }
+    fs::create_dir_all(&plugin_cache).map_err(|e| format!("Failed to create plugin cache: {}", e))?;
+
+    let marketplace_dir = plugins.join("marketplaces").join(marketplace);
+    let marketplace_commit_sha = run_git(Some(&marketplace_dir), &["rev-parse", "HEAD"]).ok();
+    let git_commit_sha = match fetch_plugin_source(&source, &marketplace_dir, &staging) {
+        Ok(sha) => sha,
+        Err(e) => {
+            let _ = fs::remove_dir_all(&staging);
+            return Err(e);
+        }
+    };
+
+    let version = metadata
+        .version
+        .clone()
+        .or_else(|| manifest_version(&staging))
+        .or_else(|| git_commit_sha.as_ref().map(|sha| sha.chars().take(12).collect()))
+        .unwrap_or_else(|| "unknown".to_string());
+
+    let install_path = match install_dir(&plugin_cache, &version) {
+        Ok(path) => path,
+        Err(e) => {
+            let _ = fs::remove_dir_all(&staging);
+            return Err(e);
+        }
+    };
+    if install_path.exists() {
+        fs::remove_dir_all(&install_path).map_err(|e| format!("Failed to replace existing install: {}", e))?;
+    }
+    fs::rename(&staging, &install_path).map_err(|e| format!("Failed to move plugin into place: {}", e))?;
+    let install_path_str = install_path.to_string_lossy().to_string();
+
+    // Keep the original install date when reinstalling
+    let now = chrono::Utc::now().to_rfc3339();
+    let installed_at = installed
+        .get("plugins")
+        .and_then(|p| p.get(plugin_id))
+        .and_then(|entries| entries.get(0))
+        .and_then(|e| e.get("installedAt"))
+        .and_then(|v| v.as_str())
+        .map(String::from)
+        .unwrap_or_else(|| now.clone());
+
+    let entry = serde_json::json!({
+        "scope": "user",
+        "installPath": install_path_str,
+        "version": version,
+        "installedAt": installed_at,
+        "lastUpdated": now,
+        "gitCommitSha": git_commit_sha,
+        "marketplaceCommitSha": marketplace_commit_sha,
+        "isLocal": false,
+    });
+
+    if let Value::Object(ref mut file) = installed {
+        let plugins_map = file
+            .entry("plugins")
+            .or_insert_with(|| Value::Object(serde_json::Map::new()));
+        if let Value::Object(map) = plugins_map {
+            map.insert(plugin_id.to_string(), Value::Array(vec![entry]));
+        }
+    }
+    write_installed_plugins(&installed)?;
+    if let Err(e) = record_install(plugin_id, &install_path, metadata.signature.clone()) {
+        warnings.push(format!("Installed, but failed to record checksums: {}", e));
+    }
+
+    Ok(PluginInstallResult {
+        plugin_id: plugin_id.to_string(),
+        version,
+        install_path: install_path_str,
+        git_commit_sha,
+        warnings,
+    })
+}
+
+/// Install a plugin from its marketplace into the plugin cache and record it in installed_plugins.json.
+/// Reinstalling replaces the cached files.
+#[tauri::command]
+#[tracing::instrument(err)]
+pub async fn install_plugin(plugin_id: String) -> Result<PluginInstallResult, String> {
+    run_blocking(move || install_from_marketplace(&plugin_id)).await?
+}
+
+/// Marketplace name used for plugins linked from a local folder
+pub const LOCAL_MARKETPLACE: &str = "local";
+
+/// Register a local plugin folder through a local marketplace
+fn link_plugin_folder(path: String) -> Result<PluginInstallResult, String> {
+    let root = fs::canonicalize(&path).map_err(|_| format!("Folder does not exist: {}", path))?;
+    if !root.is_dir() {
+        return Err(format!("Not a folder: {}", path));
+    }
+
+    let manifest_content = fs::read_to_string(root.join(".claude-plugin").join("plugin.json"))
+        .map_err(|_| "Not a plugin folder: missing .claude-plugin/plugin.json".to_string())?;
+    let manifest: Value = serde_json::from_str(&manifest_content)
+        .map_err(|e| format!("Failed to parse .claude-plugin/plugin.json: {}", e))?;
+
+    let name = manifest
+        .get("name")
+        .and_then(|n| n.as_str())
+        .map(String::from)
+        .or_else(|| root.file_name().map(|n| n.to_string_lossy().to_string()))
+        .ok_or("Plugin has no name")?;
+    let plugin_id = format!("{}@{}", name, LOCAL_MARKETPLACE);
+    split_plugin_id(&plugin_id)?;
+
+    let mut installed = read_installed_plugins_raw()?;
+    let existing = installed_entry(&installed, &plugin_id);
+    if existing.as_ref().is_some_and(|e| !e.get("isLocal").and_then(|v| v.as_bool()).unwrap_or(false)) {
+        return Err(format!("{} is already installed from a marketplace", plugin_id));
+    }
+
+    let version = entry_str(&manifest, "version").unwrap_or_else(|| "dev".to_string());
+    let install_path = root.to_string_lossy().to_string();
+    let now = chrono::Utc::now().to_rfc3339();
+    let installed_at = existing
+        .as_ref()
+        .and_then(|e| entry_str(e, "installedAt"))
+        .unwrap_or_else(|| now.clone());
+
+    let entry = serde_json::json!({
+        "scope": "user",
+        "installPath": install_path,
+        "version": version,
+        "installedAt": installed_at,
+        "lastUpdated": now,
+        "isLocal": true,
+    });
+    if let Value::Object(ref mut file) = installed {
+        let plugins_map = file
+            .entry("plugins")
+            .or_insert_with(|| Value::Object(serde_json::Map::new()));
+        if let Value::Object(map) = plugins_map {
+            map.insert(plugin_id.clone(), Value::Array(vec![entry]));
+        }
+    }
+    write_installed_plugins(&installed)?;
+
+    Ok(PluginInstallResult {
+        plugin_id,
+        version,
+        install_path,
+        git_commit_sha: None,
+        warnings: Vec::new(),
+    })
+}
+
+/// Register a local plugin folder as an installed plugin (isLocal) so it shows up in the
+/// inventory while it's being developed. The folder is used in place and watched for edits;
+/// `uninstall_plugin` unlinks it without touching the files.
+#[tauri::command]
+pub async fn link_local_plugin(path: String) -> Result<PluginInstallResult, String> {
+    run_blocking(move || link_plugin_folder(path)).await?
+}
+
+/// Components `create_plugin` can scaffold
+pub const PLUGIN_COMPONENTS: [&str; 5] = ["commands", "agents", "skills", "hooks", "mcp"];
+
+/// What `create_plugin` generated
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreatePluginResult {
+    pub plugin_path: String,
+    /// Set when the plugin was added to a marketplace or linked locally
+    pub plugin_id: Option<String>,
+    /// Files created, relative to the plugin folder
+    pub files: Vec<String>,
+}
+
+/// Starter files for a new plugin: (relative path, content)
+fn plugin_skeleton(name: &str, description: &str, components: &[String]) -> Vec<(String, String)> {
+    let has = |c: &str| components.iter().any(|x| x == c);
+    let manifest = serde_json::json!({
+        "name": name,
+        "version": "0.1.0",
+        "description": description,
+    });
+    let mut files = vec![
+        (".claude-plugin/plugin.json".to_string(), serde_json::to_string_pretty(&manifest).unwrap_or_default()),
+        ("README.md".to_string(), format!("# {}\n\n{}\n", name, description)),
+    ];
+
+    if has("commands") {
+        files.push(("commands/hello.md".to_string(), format!(
+            "---\ndescription: Example command from {}\nargument-hint: [name]\n---\n\nGreet $ARGUMENTS and explain what the {} plugin does.\n",
+            name, name
+        )));
+    }
+    if has("agents") {
+        files.push((format!("agents/{}-helper.md", name), format!(
+            "---\nname: {}-helper\ndescription: Use this agent when the user asks for help with {}.\n---\n\nYou are a focused helper. Describe the agent's role, the tools it should use and what it should return.\n",
+            name, name
+        )));
+    }
+    if has("skills") {
+        files.push((format!("skills/{}/SKILL.md", name), format!(
+            "---\nname: {}\ndescription: Describe when Claude should use this skill.\n---\n\n# {}\n\nStep-by-step instructions for the skill go here.\n",
+            name, name
+        )));
+    }
+    if has("hooks") {
+        files.push(("hooks/hooks.json".to_string(), serde_json::to_string_pretty(&serde_json::json!({ "hooks": {} })).unwrap_or_default()));
+    }
+    if has("mcp") {
+        files.push((".mcp.json".to_string(), serde_json::to_string_pretty(&serde_json::json!({ "mcpServers": {} })).unwrap_or_default()));
+    }
+
+    files
+}
+
+/// Add a plugin entry to a marketplace's catalog
+fn add_to_marketplace_catalog(marketplace_dir: &Path, name: &str, description: &str) -> Result<(), String> {
+    let catalog_path = marketplace_dir.join(".claude-plugin").join("marketplace.json");
+    let content = fs::read_to_string(&catalog_path).map_err(|_| "Marketplace has no .claude-plugin/marketplace.json".to_string())?;
+    let mut catalog: Value = serde_json::from_str(&content).map_err(|e| format!("Failed to parse marketplace.json: {}", e))?;
+
+    let plugins = catalog
+        .get_mut("plugins")
+        .and_then(|p| p.as_array_mut())
+        .ok_or("marketplace.json has no plugins list")?;
+    if plugins.iter().any(|p| p.get("name").and_then(|n| n.as_str()) == Some(name)) {
+        return Err(format!("Marketplace already lists a plugin named {}", name));
+    }
+    plugins.push(serde_json::json!({
+        "name": name,
+        "source": format!("./plugins/{}", name),
+        "description": description,
+        "version": "0.1.0",
+    }));
+
+    let content = serde_json::to_string_pretty(&catalog).map_err(|e| e.to_string())?;
+    crate::watcher::write_expected(&catalog_path, content).map_err(|e| format!("Failed to write marketplace.json: {}", e))
+}
+
+/// Generate a plugin skeleton. `location` is either the name of an installed marketplace
+/// (the plugin goes into its plugins/ folder and catalog) or a local folder to create the
+/// plugin in, which `register` links as a local plugin.
+#[tauri::command]
+pub async fn create_plugin(
+    name: String,
+    location: String,
+    components: Vec<String>,
+    description: Option<String>,
+    register: Option<bool>,
+) -> Result<CreatePluginResult, String> {
+    run_blocking(move || {
+        if name.is_empty() || !name.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-') {
+            return Err("Plugin name must be kebab-case (lowercase letters, numbers and '-')".to_string());
+        }
+        if let Some(unknown) = components.iter().find(|c| !PLUGIN_COMPONENTS.contains(&c.as_str())) {
+            return Err(format!("Unknown component '{}'. Expected any of: {}", unknown, PLUGIN_COMPONENTS.join(", ")));
+        }
+        let description = description.unwrap_or_else(|| format!("The {} plugin", name));
+
+        let marketplace_dir = plugins_dir()
+            .map(|d| d.join("marketplaces").join(&location))
+            .filter(|d| !matches!(location.as_str(), "" | "." | "..") && !location.contains(['/', '\\']) && d.is_dir());
+        let plugin_path = match &marketplace_dir {
+            Some(dir) => dir.join("plugins").join(&name),
+            None => {
+                let parent = PathBuf::from(&location);
+                if !parent.is_dir() {
+                    return Err(format!("Not a marketplace or an existing folder: {}", location));
+                }
+                parent.join(&name)
+            }
+        };
+        if plugin_path.exists() {
+            return Err(format!("{} already exists", plugin_path.display()));
+        }
+
+        let mut files = Vec::new();
+        for (relative, content) in plugin_skeleton(&name, &description, &components) {
+            let path = plugin_path.join(&relative);
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent).map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+            }
+            crate::paths::atomic_write(&path, content).map_err(|e| format!("Failed to write {}: {}", relative, e))?;
+            files.push(relative);
+        }
+
+        let plugin_path_str = plugin_path.to_string_lossy().to_string();
+        let plugin_id = match &marketplace_dir {
+            Some(dir) => {
+                add_to_marketplace_catalog(dir, &name, &description)?;
+                Some(format!("{}@{}", name, location))
+            }
+            None if register.unwrap_or(false) => Some(link_plugin_folder(plugin_path_str.clone())?.plugin_id),
+            None => None,
+        };
+
+        Ok(CreatePluginResult {
+            plugin_path: plugin_path_str,
+            plugin_id,
+            files,
+        })
+    })
+    .await?
+}
+
+/// Uninstall a plugin: disable it, drop it from installed_plugins.json and delete its cached files.
+/// Locally linked plugin folders are never deleted.
+#[tauri::command]
+#[tracing::instrument(err)]
+pub async fn uninstall_plugin(plugin_id: String) -> Result<(), String> {
+    run_blocking(move || {
+        split_plugin_id(&plugin_id)?;
+        let cache_dir = plugins_dir().ok_or("Could not find home directory")?.join("cache");
+
+        let mut installed = read_installed_plugins_raw()?;
+        let removed = installed
+            .get_mut("plugins")
+            .and_then(|p| p.as_object_mut())
+            .and_then(|map| map.remove(&plugin_id))
+            .ok_or_else(|| format!("Plugin is not installed: {}", plugin_id))?;
+
+        disable_plugin(&plugin_id)?;
+        write_installed_plugins(&installed)?;
+        forget_install(&plugin_id)?;
+
+        for entry in removed.as_array().into_iter().flatten() {
+            let Some(install_path) = entry.get("installPath").and_then(|p| p.as_str()) else {
+                continue;
+            };
+            let install_path = Path::new(install_path);
+            if !install_path.starts_with(&cache_dir) || !install_path.exists() {
+                continue;
+            }
+            crate::audit::record_removal(install_path);
+            fs::remove_dir_all(install_path).map_err(|e| format!("Failed to remove {}: {}", install_path.display(), e))?;
+
+            // Drop the now-empty plugin folder above the version folder
+            if let Some(parent) = install_path.parent().filter(|p| *p != cache_dir) {
+                if fs::read_dir(parent).map(|mut d| d.next().is_none()).unwrap_or(false) {
+                    let _ = fs::remove_dir(parent);
+                }
+            }
+        }
+
+        Ok(())
+    })
+    .await?
+}
+
+/// What a plugin actually contributes, read from its manifest and folders
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PluginDetails {
+    pub plugin_id: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub version: Option<String>,
+    pub installed: bool,
+    /// Folder the details were read from (the install path, or the marketplace copy)
+    pub root_path: String,
+    pub components: Vec<PluginComponent>,
+    pub weight: PluginWeight,
+    pub warnings: Vec<String>,
+}
+
+/// Folder holding a plugin's files: its install path, or its folder inside the marketplace
+fn plugin_root(plugin_id: &str) -> Option<(PathBuf, bool)> {
+    if let Some(path) = installed_plugin_path(plugin_id).filter(|p| p.is_dir()) {
+        return Some((path, true));
+    }
+
+    let (name, marketplace) = split_plugin_id(plugin_id).ok()?;
+    let catalog = read_marketplace_catalog();
+    let relative = match source_of(catalog.get(plugin_id)?, name) {
+        PluginSource::Path(relative) => relative,
+        // Remote sources aren't available until installed
+        PluginSource::Remote { .. } => return None,
+    };
+    if relative.split(['/', '\\']).any(|part| part == "..") {
+        return None;
+    }
+    let path = plugins_dir()?.join("marketplaces").join(marketplace).join(relative);
+    path.is_dir().then_some((path, false))
+}
+
+/// Enumerate the commands, agents, skills, hooks and MCP servers a plugin contributes,
+/// with per-component token weights. Works for installed plugins and for marketplace
+/// plugins whose files ship inside the marketplace.
+#[tauri::command]
+pub async fn get_plugin_details(plugin_id: String) -> Result<PluginDetails, String> {
+    run_blocking(move || {
+        let (root, installed) = plugin_root(&plugin_id)
+            .ok_or_else(|| format!("Plugin files not available (install it first): {}", plugin_id))?;
+        let mut warnings = Vec::new();
+
+        let manifest = read_plugin_manifest(&root, &mut warnings);
+        let components = plugin_components(&root, &manifest, &mut warnings);
+        let weight = total_weight(&components);
+        let catalog_entry = read_marketplace_catalog().remove(&plugin_id);
+
+        Ok(PluginDetails {
+            name: entry_str(&manifest, "name")
+                .or_else(|| catalog_entry.as_ref().map(|m| m.name.clone()))
+                .unwrap_or_else(|| plugin_id.split('@').next().unwrap_or(&plugin_id).to_string()),
+            description: entry_str(&manifest, "description").or_else(|| catalog_entry.as_ref().map(|m| m.description.clone())),
+            version: entry_str(&manifest, "version").or_else(|| catalog_entry.and_then(|m| m.version)),
+            plugin_id,
+            installed,
+            root_path: root.to_string_lossy().to_string(),
+            components,
+            weight,
+            warnings,
+        })
+    })
+    .await?
+}
+
+/// Check that the binaries a plugin's LSP servers, MCP servers and hooks run exist on this machine
+#[tauri::command]
+pub async fn check_plugin_prerequisites(plugin_id: String) -> Result<Vec<PluginPrerequisite>, String> {
+    run_blocking(move || {
+        let catalog_entry = read_marketplace_catalog().remove(&plugin_id);
+        let root = plugin_root(&plugin_id).map(|(root, _)| root);
+        if root.is_none() && catalog_entry.is_none() {
+            return Err(format!("Plugin not found: {}", plugin_id));
+        }
+
+        let manifest = root
+            .as_deref()
+            .map(|r| read_plugin_manifest(r, &mut Vec::new()))
+            .unwrap_or(Value::Null);
+        let catalog_lsp = catalog_entry.as_ref().and_then(|m| m.lsp_servers.as_ref());
+
+        Ok(check_prerequisites(root.as_deref(), &manifest, catalog_lsp))
+    })
+    .await?
+}
+
+/// Installed vs latest version of one plugin
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PluginUpdateInfo {
+    pub plugin_id: String,
+    pub installed_version: String,
+    pub latest_version: Option<String>,
+    pub installed_commit: Option<String>,
+    pub latest_commit: Option<String>,
+    pub update_available: bool,
+}
+
+/// Result of checking every installed plugin for updates
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PluginUpdateCheck {
+    pub plugins: Vec<PluginUpdateInfo>,
+    /// Marketplaces that couldn't be refreshed, plugins no longer listed, ...
+    pub warnings: Vec<String>,
+}
+
+/// What changed when a plugin was updated
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PluginUpdateResult {
+    pub plugin_id: String,
+    pub previous_version: String,
+    pub version: String,
+    /// Contributed files, relative to the plugin root
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+    pub modified: Vec<String>,
+    /// Marketplace commits touching the plugin since the installed one ("<sha> <subject>")
+    pub commits: Vec<String>,
+    /// Latest section of the plugin's CHANGELOG.md, if it has one
+    pub changelog: Option<String>,
+}
+
+/// The first installed_plugins.json entry for a plugin
+fn installed_entry(installed: &Value, plugin_id: &str) -> Option<Value> {
+    installed.get("plugins")?.get(plugin_id)?.get(0).cloned()
+}
+
+fn entry_str(entry: &Value, key: &str) -> Option<String> {
+    entry.get(key).and_then(|v| v.as_str()).map(String::from)
+}
+
+/// Pull every git-backed marketplace so catalogs reflect what's published
+pub(crate) fn refresh_marketplaces(warnings: &mut Vec<String>) {
+    let Some(marketplaces) = plugins_dir().map(|d| d.join("marketplaces")) else {
+        return;
+    };
+    for entry in fs::read_dir(marketplaces).into_iter().flatten().filter_map(|e| e.ok()) {
+        let dir = entry.path();
+        if dir.join(".git").exists() {
+            if let Err(e) = run_git(Some(&dir), &["pull", "--ff-only", "--quiet"]) {
+                warnings.push(format!("Failed to refresh marketplace {}: {}", entry.file_name().to_string_lossy(), e));
+            }
+        }
+    }
+}
+
+/// Latest commit for a plugin's source: the last marketplace commit touching its folder,
+/// or the remote's head for plugins in their own repository
+fn latest_commit(source: &PluginSource, marketplace_dir: &Path) -> Option<String> {
+    match source {
+        PluginSource::Path(relative) => {
+            run_git(Some(marketplace_dir), &["log", "-1", "--format=%H", "--", relative]).ok()
+        }
+        PluginSource::Remote { source, repo, url, git_ref } => {
+            let url = remote_url(source, repo.as_deref(), url.as_deref()).ok()?;
+            let reference = check_git_arg(git_ref.as_deref().unwrap_or("HEAD"), "ref").ok()?;
+            let output = run_git(None, &["ls-remote", "--", &url, reference]).ok()?;
+            output.split_whitespace().next().map(String::from)
+        }
+    }
+    .filter(|sha| !sha.is_empty())
+}
+
+/// Whether the installed commit already contains the latest changes
+fn commit_is_current(source: &PluginSource, marketplace_dir: &Path, installed: &str, latest: &str) -> bool {
+    match source {
+        // The installed sha is the marketplace HEAD at install time, which may postdate the plugin's last change
+        PluginSource::Path(_) => {
+            installed == latest
+                || run_git(Some(marketplace_dir), &["merge-base", "--is-ancestor", latest, installed]).is_ok()
+        }
+        PluginSource::Remote { .. } => installed == latest,
+    }
+}
+
+/// Refresh marketplace catalogs and compare each installed plugin against the latest published version
+#[tauri::command]
+pub async fn check_plugin_updates() -> Result<PluginUpdateCheck, String> {
+    run_blocking(move || {
+        let plugins = plugins_dir().ok_or("Could not find home directory")?;
+        let mut warnings = Vec::new();
+        refresh_marketplaces(&mut warnings);
+
+        let installed = read_installed_plugins_raw()?;
+        let catalog = read_marketplace_catalog();
+        let mut results = Vec::new();
+
+        for plugin_id in installed.get("plugins").and_then(|p| p.as_object()).into_iter().flat_map(|m| m.keys()) {
+            let Some(entry) = installed_entry(&installed, plugin_id) else {
+                continue;
+            };
+            if entry.get("isLocal").and_then(|v| v.as_bool()).unwrap_or(false) {
+                continue;
+            }
+            let Ok((name, marketplace)) = split_plugin_id(plugin_id) else {
+                continue;
+            };
+            let Some(metadata) = catalog.get(plugin_id) else {
+                warnings.push(format!("{} is no longer listed in marketplace {}", plugin_id, marketplace));
+                continue;
+            };
+
+            let source = source_of(metadata, name);
+            let marketplace_dir = plugins.join("marketplaces").join(marketplace);
+            let installed_version = entry_str(&entry, "version").unwrap_or_else(|| "unknown".to_string());
+            let installed_commit = entry_str(&entry, "gitCommitSha");
+            let latest_commit = latest_commit(&source, &marketplace_dir);
+
+            let version_changed = metadata.version.as_ref().is_some_and(|v| *v != installed_version);
+            let commit_changed = match (&installed_commit, &latest_commit) {
+                (Some(installed), Some(latest)) => !commit_is_current(&source, &marketplace_dir, installed, latest),
+                _ => false,
+            };
+
+            results.push(PluginUpdateInfo {
+                plugin_id: plugin_id.clone(),
+                installed_version,
+                latest_version: metadata.version.clone(),
+                installed_commit,
+                latest_commit,
+                update_available: version_changed || commit_changed,
+            });
+        }
+
+        results.sort_by(|a, b| b.update_available.cmp(&a.update_available).then(a.plugin_id.cmp(&b.plugin_id)));
+        Ok(PluginUpdateCheck { plugins: results, warnings })
+    })
+    .await?
+}
+
+fn relative_path(root: &Path, path: &Path) -> String {
+    path.strip_prefix(root).unwrap_or(path).to_string_lossy().replace('\\', "/")
+}
+
+/// Content hash of every file under a plugin folder, keyed by relative path
+fn snapshot_files(root: &Path) -> HashMap<String, u64> {
+    WalkDir::new(root)
+        .into_iter()
+        .filter_entry(|e| e.file_name() != ".git")
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .filter_map(|e| {
+            let content = fs::read(e.path()).ok()?;
+            let mut hasher = DefaultHasher::new();
+            content.hash(&mut hasher);
+            Some((relative_path(root, e.path()), hasher.finish()))
+        })
+        .collect()
+}
+
+/// The newest section of a CHANGELOG.md: from the first "## " heading to the next
+fn latest_changelog_section(plugin_dir: &Path) -> Option<String> {
+    let content = fs::read_to_string(plugin_dir.join("CHANGELOG.md")).ok()?;
+    let start = content.find("## ")?;
+    let rest = &content[start..];
+    let end = rest[3..].find("\n## ").map(|i| i + 3).unwrap_or(rest.len());
+    Some(rest[..end].trim().to_string())
+}
+
+/// Pull the latest version of an installed plugin and report what changed
+#[tauri::command]
+#[tracing::instrument(err)]
+pub async fn update_plugin(plugin_id: String) -> Result<PluginUpdateResult, String> {
+    run_blocking(move || {
+        let (name, marketplace) = split_plugin_id(&plugin_id)?;
+        let plugins = plugins_dir().ok_or("Could not find home directory")?;
+
+        let entry = installed_entry(&read_installed_plugins_raw()?, &plugin_id)
+            .ok_or_else(|| format!("Plugin is not installed: {}", plugin_id))?;
+        if entry.get("isLocal").and_then(|v| v.as_bool()).unwrap_or(false) {
+            return Err(format!("{} is a local plugin; edit its folder directly", plugin_id));
+        }
+        let previous_version = entry_str(&entry, "version").unwrap_or_else(|| "unknown".to_string());
+        let previous_commit = entry_str(&entry, "gitCommitSha");
+        let previous_path = entry_str(&entry, "installPath").map(PathBuf::from);
+        let before = previous_path.as_deref().map(snapshot_files).unwrap_or_default();
+
+        let installed = install_from_marketplace(&plugin_id)?;
+        let install_path = PathBuf::from(&installed.install_path);
+
+        // A new version lands in its own folder; drop the old one
+        if let Some(previous) = previous_path.filter(|p| *p != install_path && p.starts_with(plugins.join("cache"))) {
+            let _ = fs::remove_dir_all(previous);
+        }
+
+        let after = snapshot_files(&install_path);
+        let mut added: Vec<String> = after.keys().filter(|k| !before.contains_key(*k)).cloned().collect();
+        let mut removed: Vec<String> = before.keys().filter(|k| !after.contains_key(*k)).cloned().collect();
+        let mut modified: Vec<String> = after
+            .iter()
+            .filter(|(k, hash)| before.get(*k).is_some_and(|old| old != *hash))
+            .map(|(k, _)| k.clone())
+            .collect();
+        added.sort();
+        removed.sort();
+        modified.sort();
+
+        let mut commits = Vec::new();
+        let catalog = read_marketplace_catalog();
+        if let (Some(metadata), Some(previous_commit)) = (catalog.get(&plugin_id), previous_commit) {
+            if let PluginSource::Path(relative) = source_of(metadata, name) {
+                let marketplace_dir = plugins.join("marketplaces").join(marketplace);
+                let range = format!("{}..HEAD", previous_commit);
+                if let Ok(log) = run_git(Some(&marketplace_dir), &["log", "--format=%h %s", &range, "--", &relative]) {
+                    commits = log.lines().map(String::from).collect();
+                }
+            }
+        }
+
+        Ok(PluginUpdateResult {
+            plugin_id,
+            previous_version,
+            version: installed.version,
+            added,
+            removed,
+            modified,
+            commits,
+            changelog: latest_changelog_section(&install_path),
+        })
+    })
+    .await?
+}
+
+/// Result of validating one marketplace catalog
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MarketplaceValidation {
+    pub name: String,
+    pub catalog_path: String,
+    pub valid: bool,
+    /// Plugins that load despite any diagnostics
+    pub plugin_count: usize,
+    pub diagnostics: Vec<MarketplaceDiagnostic>,
+}
+
+/// Validate an installed marketplace's catalog and list every problem found
+#[tauri::command]
+pub async fn validate_marketplace(name: String) -> Result<MarketplaceValidation, String> {
+    run_blocking(move || {
+        if name.is_empty() || name == "." || name == ".." || name.contains(['/', '\\']) {
+            return Err(format!("Invalid marketplace name: {}", name));
+        }
+        let marketplace_dir = plugins_dir().ok_or("Could not find home directory")?.join("marketplaces").join(&name);
+        if !marketplace_dir.is_dir() {
+            return Err(format!("Marketplace not found: {}", name));
+        }
+
+        let (plugin_count, diagnostics) = match load_marketplace(&marketplace_dir) {
+            Ok((plugins, diagnostics)) => (plugins.len(), diagnostics),
+            Err(diagnostic) => (0, vec![diagnostic]),
+        };
+
+        Ok(MarketplaceValidation {
+            catalog_path: marketplace_dir
+                .join(".claude-plugin")
+                .join("marketplace.json")
+                .to_string_lossy()
+                .to_string(),
+            valid: !diagnostics.iter().any(|d| d.is_error()),
+            name,
+            plugin_count,
+            diagnostics,
+        })
+    })
+    .await?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn catalog_values_stay_in_their_place() {
+        let cache = Path::new("/home/me/.claude/plugins/cache/market/linter");
+        assert_eq!(install_dir(cache, "1.2.0").unwrap(), cache.join("1.2.0"));
+        assert!(install_dir(cache, "../../..").is_err());
+        assert!(install_dir(cache, "..").is_err() && install_dir(cache, "1.0/../../x").is_err());
+
+        assert!(remote_url("git", None, Some("--upload-pack=touch /tmp/pwned")).is_err());
+        assert!(remote_url("github", Some("acme/linter"), None).is_ok());
+        assert!(check_git_arg("-b", "ref").is_err() && check_git_arg("main", "ref").is_ok());
+    }
+}
@@ -0,0 +1,31 @@
+//! External programs the app is running: list them and cancel one
+//! Only runs given an id show up, such as MCP probes (`mcp-probe:<server>`) and hook tests.
+
+use serde::Serialize;
+
+use crate::process::{cancel, running_processes};
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RunningProcessInfo {
+    pub id: String,
+    pub program: String,
+    /// Including time spent waiting for a slot
+    pub running_ms: u64,
+}
+
+/// Runs with an id that haven't finished
+#[tauri::command]
+pub fn list_processes() -> Result<Vec<RunningProcessInfo>, String> {
+    Ok(running_processes()
+        .into_iter()
+        .map(|p| RunningProcessInfo { id: p.id, program: p.program, running_ms: p.started.elapsed().as_millis() as u64 })
+        .collect())
+}
+
+/// Stop the runs with this id; false when none was running
+#[tauri::command]
+#[tracing::instrument]
+pub fn cancel_process(id: String) -> Result<bool, String> {
+    Ok(cancel(&id))
+}
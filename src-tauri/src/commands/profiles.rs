@@ -0,0 +1,141 @@
+//! Config profiles: several Claude folders (work, personal, ...) with one active at a time
+//! Activating a profile points every scanner and command at its folder, as CLAUDE_CONFIG_DIR would.
+//! The list lives in the default folder's arcade_profiles.json, so it is found whichever profile is active.
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use tauri::{AppHandle, Emitter, State};
+
+use super::inventory::{scan_inventory, InventoryCacheState};
+use crate::blocking::run_blocking;
+use crate::error::ArcadeError;
+use crate::paths::{check_name, set_profile_dir, ConfigPaths};
+use crate::types::ScanResult;
+
+/// Name of the profile that uses the default Claude folder
+const DEFAULT_PROFILE: &str = "default";
+
+/// A named Claude config folder
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigProfile {
+    pub name: String,
+    pub claude_dir: String,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub is_active: bool,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+struct ProfilesFile {
+    /// None while the default profile is active
+    active: Option<String>,
+    profiles: Vec<ConfigProfile>,
+}
+
+fn profiles_path() -> Option<PathBuf> {
+    ConfigPaths::resolve_default().map(|p| p.claude_dir.join("arcade_profiles.json"))
+}
+
+fn load_profiles() -> ProfilesFile {
+    profiles_path()
+        .and_then(|path| crate::store::PROFILES.load(&path))
+        .unwrap_or_default()
+}
+
+fn save_profiles(file: &ProfilesFile) -> Result<(), String> {
+    let path = profiles_path().ok_or("Could not find home directory")?;
+    let content = crate::store::PROFILES.to_json(file)?;
+    crate::paths::atomic_write(&path, content).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+/// The default profile first, then the created ones, with the active one marked
+fn all_profiles(file: &ProfilesFile) -> Vec<ConfigProfile> {
+    let default_dir = ConfigPaths::resolve_default().map(|p| p.claude_dir.to_string_lossy().to_string()).unwrap_or_default();
+    let active = file.active.as_deref().unwrap_or(DEFAULT_PROFILE);
+    std::iter::once(ConfigProfile { name: DEFAULT_PROFILE.to_string(), claude_dir: default_dir, is_active: false })
+        .chain(file.profiles.iter().cloned())
+        .map(|profile| ConfigProfile { is_active: profile.name == active, ..profile })
+        .collect()
+}
+
+/// Switch to the profile saved as active last run; called once at startup, before the watcher starts
+pub(crate) fn restore_active_profile() {
+    let file = load_profiles();
+    let Some(active) = file.active.as_deref() else {
+        return;
+    };
+    match file.profiles.iter().find(|p| p.name == active) {
+        Some(profile) => set_profile_dir(Some(PathBuf::from(&profile.claude_dir))),
+        None => tracing::warn!("Active profile {} no longer exists, using the default", active),
+    }
+}
+
+/// Every config profile, the default one first
+#[tauri::command]
+pub async fn list_profiles() -> Result<Vec<ConfigProfile>, String> {
+    run_blocking(|| all_profiles(&load_profiles())).await
+}
+
+/// Add a profile using `claude_dir`, or a new folder at ~/.claude-profiles/<name>.
+/// The folder is created when missing; the profile isn't activated.
+#[tauri::command]
+#[tracing::instrument(err)]
+pub async fn create_profile(name: String, claude_dir: Option<String>) -> Result<ConfigProfile, ArcadeError> {
+    check_name(&name)?;
+    run_blocking(move || {
+        let mut file = load_profiles();
+        if name == DEFAULT_PROFILE || file.profiles.iter().any(|p| p.name == name) {
+            return Err(ArcadeError::Conflict { message: format!("A profile named {} already exists", name) });
+        }
+        let dir = match claude_dir {
+            Some(dir) => PathBuf::from(dir),
+            None => ConfigPaths::resolve_default()
+                .ok_or_else(|| ArcadeError::from("Could not find home directory"))?
+                .home
+                .join(".claude-profiles")
+                .join(&name),
+        };
+        fs::create_dir_all(&dir).map_err(|e| ArcadeError::io(&dir, e))?;
+
+        let profile = ConfigProfile { name, claude_dir: dir.to_string_lossy().to_string(), is_active: false };
+        file.profiles.push(profile.clone());
+        save_profiles(&file)?;
+        Ok(profile)
+    })
+    .await?
+}
+
+/// Make `name` the active profile, restart the config watcher on its folder and rescan.
+/// Emits `profile-changed` with the profile, then `inventory-diff` as any rescan does.
+#[tauri::command]
+#[tracing::instrument(skip(app_handle, state), err)]
+pub async fn activate_profile(
+    app_handle: AppHandle,
+    state: State<'_, InventoryCacheState>,
+    name: String,
+    project_path: Option<String>,
+) -> Result<ScanResult, ArcadeError> {
+    let profile = run_blocking(move || {
+        let mut file = load_profiles();
+        let profile = all_profiles(&file)
+            .into_iter()
+            .find(|p| p.name == name)
+            .ok_or_else(|| ArcadeError::not_found("profile", &name))?;
+
+        let is_default = profile.name == DEFAULT_PROFILE;
+        file.active = (!is_default).then(|| profile.name.clone());
+        save_profiles(&file)?;
+        set_profile_dir((!is_default).then(|| PathBuf::from(&profile.claude_dir)));
+        Ok::<_, ArcadeError>(ConfigProfile { is_active: true, ..profile })
+    })
+    .await??;
+
+    tracing::info!("Switched to profile {} ({})", profile.name, profile.claude_dir);
+    if let Err(e) = crate::watcher::start_watcher(app_handle.clone()) {
+        tracing::error!("Failed to restart file watcher: {}", e);
+    }
+    let _ = app_handle.emit("profile-changed", &profile);
+    Ok(scan_inventory(app_handle, state, project_path, None).await?)
+}
@@ -3,7 +3,13 @@
 
 use serde::{Deserialize, Serialize};
 use std::fs;
+use std::panic::{catch_unwind, AssertUnwindSafe};
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use super::equipment::{calculate_context_stats, context_stats_for_tokens};
+use crate::types::ContextStats;
+use crate::blocking::run_blocking;
 
 /// Summary of Claude-specific items found in a project
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -185,9 +191,8 @@ fn estimate_tokens(claude_dir: &PathBuf) -> u32 {
     total_chars / 4
 }
 
-/// Scan a project's .claude folder and return metadata
-#[tauri::command]
-pub fn scan_project_claude_items(path: String) -> Result<ProjectScanResult, String> {
+/// Claude config, health and token estimate for one project
+fn scan_project_items(path: String) -> Result<ProjectScanResult, String> {
     let project_path = PathBuf::from(&path);
 
     if !project_path.exists() {
@@ -247,3 +252,128 @@ pub fn scan_project_claude_items(path: String) -> Result<ProjectScanResult, Stri
         has_gemfile: project_path.join("Gemfile").exists(),
     })
 }
+
+/// Scan a project's .claude folder and return metadata
+#[tauri::command]
+pub async fn scan_project_claude_items(path: String) -> Result<ProjectScanResult, String> {
+    run_blocking(move || scan_project_items(path)).await?
+}
+
+/// One project in the multi-project dashboard
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectOverview {
+    pub path: String,
+    pub name: String,
+    pub scan: Option<ProjectScanResult>,
+    pub error: Option<String>,
+    /// Commands + skills + subagents + hooks + MCP servers
+    pub item_count: u32,
+    /// Project tokens on top of the globally equipped plugins
+    pub context_stats: ContextStats,
+}
+
+/// Aggregate view across many projects
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectsDashboard {
+    pub projects: Vec<ProjectOverview>,
+    pub total_project_tokens: u32,
+    pub total_items: u32,
+    /// Number of projects per health status (healthy / heavy / dumbzone)
+    pub health_counts: std::collections::HashMap<String, u32>,
+    pub scan_duration_ms: u64,
+}
+
+fn overview_for(path: &str, global_tokens: u32) -> ProjectOverview {
+    let name = PathBuf::from(path)
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| path.to_string());
+
+    match scan_project_items(path.to_string()) {
+        Ok(scan) => {
+            let items = &scan.claude_items;
+            let item_count = items.command_count + items.skill_count + items.subagent_count
+                + items.hook_count + items.mcp_count;
+            let context_stats = context_stats_for_tokens(global_tokens + items.total_token_estimate);
+            ProjectOverview {
+                path: path.to_string(),
+                name,
+                scan: Some(scan),
+                error: None,
+                item_count,
+                context_stats,
+            }
+        }
+        Err(e) => ProjectOverview {
+            path: path.to_string(),
+            name,
+            scan: None,
+            error: Some(e),
+            item_count: 0,
+            context_stats: context_stats_for_tokens(global_tokens),
+        },
+    }
+}
+
+/// Scan many project roots at once for a fleet overview
+#[tauri::command]
+pub async fn scan_projects(paths: Vec<String>) -> Result<ProjectsDashboard, String> {
+    run_blocking(move || {
+        let start = std::time::Instant::now();
+
+        // Plugins are global, so their load is shared by every project
+        let global_tokens = calculate_context_stats().equipped;
+
+        // A few workers take the next path until none are left, so a long list doesn't mean as many threads
+        let workers = std::thread::available_parallelism().map_or(4, |n| n.get()).min(paths.len());
+        let next = AtomicUsize::new(0);
+        let mut scanned: Vec<(usize, ProjectOverview)> = std::thread::scope(|scope| {
+            let handles: Vec<_> = (0..workers)
+                .map(|_| {
+                    scope.spawn(|| {
+                        let mut done = Vec::new();
+                        loop {
+                            let index = next.fetch_add(1, Ordering::Relaxed);
+                            let Some(path) = paths.get(index) else {
+                                break;
+                            };
+                            let overview = catch_unwind(AssertUnwindSafe(|| overview_for(path, global_tokens)))
+                                .unwrap_or_else(|_| ProjectOverview {
+                                    path: path.clone(),
+                                    name: path.clone(),
+                                    scan: None,
+                                    error: Some("Project scan panicked".to_string()),
+                                    item_count: 0,
+                                    context_stats: context_stats_for_tokens(global_tokens),
+                                });
+                            done.push((index, overview));
+                        }
+                        done
+                    })
+                })
+                .collect();
+            handles.into_iter().flat_map(|handle| handle.join().unwrap_or_default()).collect()
+        });
+        scanned.sort_by_key(|(index, _)| *index);
+        let projects: Vec<ProjectOverview> = scanned.into_iter().map(|(_, overview)| overview).collect();
+
+        let mut health_counts = std::collections::HashMap::new();
+        for project in projects.iter().filter(|p| p.scan.is_some()) {
+            *health_counts.entry(project.context_stats.status.clone()).or_insert(0) += 1;
+        }
+
+        ProjectsDashboard {
+            total_project_tokens: projects.iter()
+                .filter_map(|p| p.scan.as_ref())
+                .map(|s| s.claude_items.total_token_estimate)
+                .sum(),
+            total_items: projects.iter().map(|p| p.item_count).sum(),
+            health_counts,
+            projects,
+            scan_duration_ms: start.elapsed().as_millis() as u64,
+        }
+    })
+    .await
+}
@@ -1,4 +1,5 @@
 use crate::pty::PtyManager;
+use crate::scanner::transcripts::{entry_cwd, find_transcript, read_transcript_entries};
 use std::sync::Mutex;
 use tauri::{AppHandle, State};
 
@@ -16,6 +17,29 @@ pub fn pty_spawn(
     manager.spawn(app_handle, cols, rows, cwd)
 }
 
+/// Start Claude Code in a terminal. With `resume_session`, it continues that session
+/// in the directory it ran in unless `cwd` says otherwise.
+#[tauri::command]
+pub fn pty_spawn_claude(
+    app_handle: AppHandle,
+    state: State<'_, PtyState>,
+    cols: u16,
+    rows: u16,
+    cwd: Option<String>,
+    resume_session: Option<String>,
+) -> Result<String, String> {
+    let cwd = match (&resume_session, cwd) {
+        (Some(session_id), cwd) => {
+            let transcript = find_transcript(session_id).ok_or_else(|| format!("Session not found: {}", session_id))?;
+            cwd.or_else(|| read_transcript_entries(&transcript).iter().find_map(entry_cwd).map(String::from))
+        }
+        (None, cwd) => cwd,
+    };
+
+    let manager = state.0.lock().map_err(|e| format!("Lock error: {}", e))?;
+    manager.spawn_claude(app_handle, cols, rows, cwd, resume_session)
+}
+
 #[tauri::command]
 pub fn pty_write(state: State<'_, PtyState>, id: String, data: String) -> Result<(), String> {
     let manager = state.0.lock().map_err(|e| format!("Lock error: {}", e))?;
@@ -0,0 +1,188 @@
+//! Pre-flight loadout advice from the prompt about to be sent
+//! The prompt is matched against keyword rules per kind of task; items whose name or description
+//! share those keywords are the relevant ones. No model is involved, so this is instant and
+//! only as good as the words people put in item descriptions.
+
+use serde::Serialize;
+use tauri::State;
+
+use super::equipment::context_stats_for_tokens;
+use super::inventory::{cached_or_scan, memory_cached_scan, InventoryCacheState};
+use crate::blocking::run_blocking;
+use crate::types::{ContextStats, InventoryItem, ItemSource};
+
+/// Kinds of task and the word stems that point to them, in the prompt and in item descriptions
+const TASK_RULES: &[(&str, &[&str])] = &[
+    ("debugging", &["debug", "bug", "error", "crash", "exception", "stack", "trace", "fix", "broken", "fail", "panic", "regression"]),
+    ("docs", &["doc", "readme", "changelog", "guide", "tutorial", "explain", "comment", "markdown", "writ"]),
+    ("frontend", &["frontend", "ui", "css", "html", "react", "vue", "svelte", "component", "layout", "style", "tailwind", "browser", "page"]),
+    ("testing", &["test", "spec", "coverage", "mock", "jest", "pytest", "vitest", "e2e", "playwright"]),
+    ("backend", &["api", "endpoint", "server", "backend", "route", "rest", "graphql", "request", "handler"]),
+    ("database", &["database", "sql", "query", "migration", "schema", "postgres", "mysql", "sqlite", "mongo", "table"]),
+    ("devops", &["deploy", "docker", "kubernetes", "ci", "pipeline", "terraform", "infra", "release", "build"]),
+    ("review", &["review", "refactor", "cleanup", "lint", "pr", "diff", "commit", "git"]),
+    ("security", &["security", "vulnerab", "auth", "secret", "xss", "injection", "cve", "permission"]),
+    ("data", &["csv", "excel", "spreadsheet", "pdf", "chart", "plot", "analy", "dataset", "notebook"]),
+];
+
+/// Equipped items lighter than this aren't worth taking off
+const HEAVY_ITEM_TOKENS: u32 = 2000;
+
+const MAX_SUGGESTIONS: usize = 5;
+
+/// A kind of task the prompt looks like
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskMatch {
+    pub task: String,
+    /// Prompt words that matched
+    pub keywords: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ItemAdvice {
+    pub item: InventoryItem,
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PromptRecommendation {
+    /// Empty when nothing in the prompt gave the task away; there is no advice then
+    pub tasks: Vec<TaskMatch>,
+    /// Unequipped items that would help
+    pub equip: Vec<ItemAdvice>,
+    /// Heavy equipped items with nothing to do with the task, heaviest first
+    pub unequip: Vec<ItemAdvice>,
+    /// Context load with the advice followed
+    pub context_stats: ContextStats,
+}
+
+fn words(text: &str) -> Vec<String> {
+    text.to_lowercase().split(|c: char| !c.is_alphanumeric()).filter(|w| !w.is_empty()).map(String::from).collect()
+}
+
+fn matches_stem(word: &str, stem: &str) -> bool {
+    // Short stems ("ui", "ci", "pr") only match whole words, or they'd match half the dictionary
+    if stem.len() <= 3 {
+        word == stem || word.strip_suffix('s') == Some(stem)
+    } else {
+        word.starts_with(stem)
+    }
+}
+
+/// Tasks the prompt matches, strongest first
+fn classify(prompt: &str) -> Vec<TaskMatch> {
+    let words = words(prompt);
+    let mut tasks: Vec<TaskMatch> = TASK_RULES
+        .iter()
+        .filter_map(|(task, stems)| {
+            let mut keywords: Vec<String> = Vec::new();
+            for word in words.iter().filter(|w| stems.iter().any(|s| matches_stem(w, s))) {
+                if !keywords.contains(word) {
+                    keywords.push(word.clone());
+                }
+            }
+            (!keywords.is_empty()).then(|| TaskMatch { task: task.to_string(), keywords })
+        })
+        .collect();
+    tasks.sort_by_key(|t| std::cmp::Reverse(t.keywords.len()));
+    tasks
+}
+
+/// The matched tasks an item's name, id and description speak to
+fn relevant_tasks(item: &InventoryItem, tasks: &[TaskMatch]) -> Vec<String> {
+    let words = words(&format!("{} {} {}", item.name, item.id, item.description));
+    tasks
+        .iter()
+        .filter(|t| {
+            let stems = TASK_RULES.iter().find(|(task, _)| *task == t.task).map_or(&[][..], |(_, s)| *s);
+            words.iter().any(|w| stems.iter().any(|s| matches_stem(w, s)))
+        })
+        .map(|t| t.task.clone())
+        .collect()
+}
+
+/// Items the app can turn on and off: plugins, skills and MCP groups
+fn toggleable(item: &InventoryItem) -> bool {
+    matches!(item.source, ItemSource::Plugin | ItemSource::Skill | ItemSource::Mcp)
+        && !item.source_path.is_empty()
+        && item.environment.is_none()
+}
+
+fn recommend(prompt: &str, items: Vec<InventoryItem>) -> PromptRecommendation {
+    let tasks = classify(prompt);
+    let equipped_tokens: u32 = items.iter().filter(|i| i.enabled).map(|i| i.token_weight).sum();
+    if tasks.is_empty() {
+        return PromptRecommendation { tasks, equip: Vec::new(), unequip: Vec::new(), context_stats: context_stats_for_tokens(equipped_tokens) };
+    }
+
+    let mut equip: Vec<(usize, ItemAdvice)> = Vec::new();
+    let mut unequip: Vec<ItemAdvice> = Vec::new();
+    for item in items.into_iter().filter(toggleable) {
+        let relevant = relevant_tasks(&item, &tasks);
+        if !item.enabled && !relevant.is_empty() {
+            let reason = format!("Fits {}", relevant.join(", "));
+            equip.push((relevant.len(), ItemAdvice { item, reason }));
+        } else if item.enabled && relevant.is_empty() && item.token_weight >= HEAVY_ITEM_TOKENS {
+            let reason = format!("{} tokens with nothing to do with {}", item.token_weight, tasks[0].task);
+            unequip.push(ItemAdvice { item, reason });
+        }
+    }
+    equip.sort_by(|(a, x), (b, y)| b.cmp(a).then(x.item.token_weight.cmp(&y.item.token_weight)));
+    let equip: Vec<ItemAdvice> = equip.into_iter().take(MAX_SUGGESTIONS).map(|(_, advice)| advice).collect();
+    unequip.sort_by_key(|a| std::cmp::Reverse(a.item.token_weight));
+    unequip.truncate(MAX_SUGGESTIONS);
+
+    let after = equipped_tokens + equip.iter().map(|a| a.item.token_weight).sum::<u32>()
+        - unequip.iter().map(|a| a.item.token_weight).sum::<u32>();
+    PromptRecommendation { tasks, equip, unequip, context_stats: context_stats_for_tokens(after) }
+}
+
+/// Guess what kind of task a prompt is (debugging, docs, frontend work, ...) from its wording, and
+/// suggest unequipped items that would help and heavy equipped ones that won't
+#[tauri::command]
+pub async fn recommend_for_prompt(
+    state: State<'_, InventoryCacheState>,
+    prompt_text: String,
+    project_path: Option<String>,
+) -> Result<PromptRecommendation, String> {
+    let cached = memory_cached_scan(&state, &project_path)?;
+    run_blocking(move || recommend(&prompt_text, cached_or_scan(cached, project_path.as_deref()).items)).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{test_item, ItemType};
+
+    fn item(id: &str, description: &str, enabled: bool, token_weight: u32) -> InventoryItem {
+        InventoryItem {
+            description: description.to_string(),
+            item_type: ItemType::Spell,
+            source: ItemSource::Skill,
+            source_path: format!("/home/me/.claude/skills/{}", id),
+            token_weight,
+            enabled,
+            ..test_item(id)
+        }
+    }
+
+    #[test]
+    fn advises_from_the_prompt() {
+        let tasks = classify("The login page crashes with a stack trace after my CSS change");
+        assert_eq!(tasks.iter().map(|t| t.task.as_str()).collect::<Vec<_>>(), vec!["debugging", "frontend"]);
+        assert!(classify("hello there").is_empty());
+
+        let items = vec![
+            item("skill_user_frontend-design", "Build polished UI components", false, 3000),
+            item("skill_user_pdf", "Fill and merge PDF forms", true, 5000),
+            item("skill_user_tiny", "Spreadsheet helper", true, 500),
+        ];
+        let advice = recommend("Fix the broken button component", items);
+        assert_eq!(advice.equip.iter().map(|a| a.item.id.as_str()).collect::<Vec<_>>(), vec!["skill_user_frontend-design"]);
+        assert_eq!(advice.unequip.iter().map(|a| a.item.id.as_str()).collect::<Vec<_>>(), vec!["skill_user_pdf"]);
+        assert_eq!(advice.context_stats.equipped, 3500);
+    }
+}
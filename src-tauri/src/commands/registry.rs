@@ -0,0 +1,219 @@
+//! Backend-side projects registry with tags, pinning and notes
+//! Persisted in ~/.claude/arcade_projects.json so it survives frontend storage resets.
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard};
+use crate::blocking::run_blocking;
+
+/// A project the user has opened, with their own metadata
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectEntry {
+    pub path: String,
+    pub name: String,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub pinned: bool,
+    #[serde(default)]
+    pub notes: String,
+    /// Milliseconds since epoch
+    pub created_at: i64,
+    pub last_opened: i64,
+    #[serde(default)]
+    pub open_count: u32,
+}
+
+/// Filters for `list_recent_projects`
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectFilter {
+    /// Only projects carrying every one of these tags
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub pinned_only: bool,
+    /// Case-insensitive match on name, path or notes
+    #[serde(default)]
+    pub query: Option<String>,
+    #[serde(default)]
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+struct ProjectRegistry {
+    projects: Vec<ProjectEntry>,
+}
+
+fn get_registry_path() -> PathBuf {
+    crate::paths::claude_dir()
+        .unwrap_or_default()
+        .join("arcade_projects.json")
+}
+
+/// Held across every load-change-save of the registry: opening a project while its tags are
+/// being edited would otherwise lose one of the two updates
+static REGISTRY_LOCK: Mutex<()> = Mutex::new(());
+
+fn lock_registry() -> MutexGuard<'static, ()> {
+    REGISTRY_LOCK.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+fn load_registry() -> ProjectRegistry {
+    let path = get_registry_path();
+    crate::store::PROJECTS.load(&path).unwrap_or_default()
+}
+
+fn save_registry(registry: &ProjectRegistry) -> Result<(), String> {
+    let path = get_registry_path();
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    let content = crate::store::PROJECTS.to_json(registry)?;
+    crate::paths::atomic_write(&path, content).map_err(|e| e.to_string())
+}
+
+fn now_millis() -> i64 {
+    chrono::Utc::now().timestamp_millis()
+}
+
+/// Find a project by path, creating its entry if needed
+fn entry_for<'a>(registry: &'a mut ProjectRegistry, path: &str) -> &'a mut ProjectEntry {
+    let index = match registry.projects.iter().position(|p| p.path == path) {
+        Some(i) => i,
+        None => {
+            let now = now_millis();
+            registry.projects.push(ProjectEntry {
+                path: path.to_string(),
+                name: Path::new(path)
+                    .file_name()
+                    .map(|n| n.to_string_lossy().to_string())
+                    .unwrap_or_else(|| path.to_string()),
+                tags: Vec::new(),
+                pinned: false,
+                notes: String::new(),
+                created_at: now,
+                last_opened: now,
+                open_count: 0,
+            });
+            registry.projects.len() - 1
+        }
+    };
+    &mut registry.projects[index]
+}
+
+/// Record that a project was opened
+#[tauri::command]
+pub async fn record_project_opened(path: String) -> Result<ProjectEntry, String> {
+    run_blocking(move || {
+        let _lock = lock_registry();
+        let mut registry = load_registry();
+        let entry = entry_for(&mut registry, &path);
+        entry.last_opened = now_millis();
+        entry.open_count += 1;
+        let entry = entry.clone();
+        save_registry(&registry)?;
+        Ok(entry)
+    })
+    .await?
+}
+
+/// List known projects, pinned first then most recently opened
+#[tauri::command]
+pub async fn list_recent_projects(filter: Option<ProjectFilter>) -> Result<Vec<ProjectEntry>, String> {
+    run_blocking(move || {
+        let filter = filter.unwrap_or_default();
+        let query = filter.query.as_deref().map(|q| q.to_lowercase()).filter(|q| !q.is_empty());
+
+        let mut projects: Vec<ProjectEntry> = load_registry()
+            .projects
+            .into_iter()
+            .filter(|p| !filter.pinned_only || p.pinned)
+            .filter(|p| filter.tags.iter().all(|t| p.tags.contains(t)))
+            .filter(|p| {
+                query.as_deref().is_none_or(|q| {
+                    p.name.to_lowercase().contains(q)
+                        || p.path.to_lowercase().contains(q)
+                        || p.notes.to_lowercase().contains(q)
+                })
+            })
+            .collect();
+
+        projects.sort_by(|a, b| b.pinned.cmp(&a.pinned).then(b.last_opened.cmp(&a.last_opened)));
+
+        if let Some(limit) = filter.limit {
+            projects.truncate(limit);
+        }
+        projects
+    })
+    .await
+}
+
+/// Update a project's tags, pin state, notes or display name (unset fields are left alone)
+#[tauri::command]
+pub async fn update_project_meta(
+    path: String,
+    name: Option<String>,
+    tags: Option<Vec<String>>,
+    pinned: Option<bool>,
+    notes: Option<String>,
+) -> Result<ProjectEntry, String> {
+    run_blocking(move || {
+        let _lock = lock_registry();
+        let mut registry = load_registry();
+        let entry = entry_for(&mut registry, &path);
+
+        if let Some(name) = name.filter(|n| !n.trim().is_empty()) {
+            entry.name = name.trim().to_string();
+        }
+        if let Some(tags) = tags {
+            let mut cleaned: Vec<String> = tags.iter()
+                .map(|t| t.trim().to_string())
+                .filter(|t| !t.is_empty())
+                .collect();
+            cleaned.sort();
+            cleaned.dedup();
+            entry.tags = cleaned;
+        }
+        if let Some(pinned) = pinned {
+            entry.pinned = pinned;
+        }
+        if let Some(notes) = notes {
+            entry.notes = notes;
+        }
+
+        let entry = entry.clone();
+        save_registry(&registry)?;
+        Ok(entry)
+    })
+    .await?
+}
+
+/// Remove a project from the registry (files are untouched)
+#[tauri::command]
+pub async fn remove_project(path: String) -> Result<(), String> {
+    run_blocking(move || {
+        let _lock = lock_registry();
+        let mut registry = load_registry();
+        registry.projects.retain(|p| p.path != path);
+        save_registry(&registry)
+    })
+    .await?
+}
+
+/// All tags in use, with how many projects carry each
+#[tauri::command]
+pub async fn list_project_tags() -> Result<Vec<(String, u32)>, String> {
+    run_blocking(move || {
+        let mut counts: std::collections::BTreeMap<String, u32> = std::collections::BTreeMap::new();
+        for project in load_registry().projects {
+            for tag in project.tags {
+                *counts.entry(tag).or_insert(0) += 1;
+            }
+        }
+        counts.into_iter().collect()
+    })
+    .await
+}
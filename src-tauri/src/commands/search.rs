@@ -0,0 +1,181 @@
+//! One ranked search across everything the app knows about, for a Cmd+K palette
+//! Inventory items come from the cached scan (or the on-disk index); MCP servers, recent sessions
+//! and settings keys are read directly, since they're small.
+
+use serde::Serialize;
+use serde_json::Value;
+use tauri::State;
+
+use super::inventory::{memory_cached_scan, scan_all, InventoryCacheState};
+use super::inventory_export::variant_name;
+use super::sessions::summarize_session;
+use crate::blocking::run_blocking;
+use crate::scanner::index::load_indexed_inventory;
+use crate::scanner::settings::{read_mcp_servers, read_settings_at, settings_path};
+use crate::scanner::transcripts::{list_transcript_files, modified_time};
+
+const DEFAULT_LIMIT: usize = 30;
+
+/// Newest transcripts searched; older sessions are left to the session browser
+const RECENT_SESSIONS: usize = 30;
+
+/// One hit, in the order the palette shows them
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchResult {
+    /// The inventory item's source (plugin, command, skill, hook, subagent, claudeMd), or mcp, session or setting
+    pub kind: String,
+    /// Item id, MCP server id, session id or dotted settings key
+    pub id: String,
+    pub title: String,
+    pub subtitle: String,
+    pub score: u32,
+}
+
+/// Score `text` against a lowercase `query`: an exact match beats a prefix, a prefix beats a substring,
+/// and a substring beats the query's characters appearing in order. None when they don't appear at all.
+fn fuzzy_score(query: &str, text: &str) -> Option<u32> {
+    let text = text.to_lowercase();
+    if text == query {
+        return Some(1000);
+    }
+    if text.starts_with(query) {
+        return Some(800);
+    }
+    if let Some(position) = text.find(query) {
+        // Matches at a word start rank above ones mid-word
+        let at_word = text[..position].ends_with(|c: char| !c.is_alphanumeric());
+        return Some(if at_word { 700 } else { 600 } - position.min(100) as u32);
+    }
+
+    let mut score = 0u32;
+    let mut run = 0u32;
+    let mut previous: Option<char> = None;
+    let mut wanted = query.chars().peekable();
+    for c in text.chars() {
+        if wanted.peek() == Some(&c) {
+            wanted.next();
+            run += 1;
+            score += 2 * run + if previous.is_none_or(|p| !p.is_alphanumeric()) { 5 } else { 0 };
+        } else {
+            run = 0;
+        }
+        previous = Some(c);
+    }
+    wanted.peek().is_none().then_some(score.min(500))
+}
+
+/// Best score over a title and its secondary text, which counts for less
+fn best_score(query: &str, title: &str, secondary: &[&str]) -> Option<u32> {
+    let title_score = fuzzy_score(query, title);
+    let secondary_score = secondary.iter().filter_map(|s| fuzzy_score(query, s)).max().map(|s| s / 2);
+    title_score.max(secondary_score)
+}
+
+/// Every key in settings.json as a dotted path, with its value shortened
+fn settings_keys(value: &Value, prefix: &str, keys: &mut Vec<(String, String)>) {
+    let Some(map) = value.as_object() else {
+        return;
+    };
+    for (key, value) in map {
+        let path = if prefix.is_empty() { key.clone() } else { format!("{}.{}", prefix, key) };
+        if value.is_object() {
+            settings_keys(value, &path, keys);
+        }
+        let shown: String = value.to_string().chars().take(80).collect();
+        keys.push((path, shown));
+    }
+}
+
+fn search(query: &str, items: Vec<crate::types::InventoryItem>, project_path: Option<&str>) -> Vec<SearchResult> {
+    let mut results = Vec::new();
+
+    for item in items {
+        if let Some(score) = best_score(query, &item.name, &[&item.id, &item.description]) {
+            results.push(SearchResult {
+                kind: variant_name(&item.source),
+                id: item.id,
+                title: item.name,
+                subtitle: item.description,
+                score,
+            });
+        }
+    }
+
+    for (id, server) in read_mcp_servers().unwrap_or_default() {
+        let command = format!("{} {}", server.command, server.args.join(" "));
+        if let Some(score) = best_score(query, &id, &[&command]) {
+            results.push(SearchResult { kind: "mcp".to_string(), title: id.clone(), id, subtitle: command, score });
+        }
+    }
+
+    let mut files = list_transcript_files(project_path);
+    files.sort_by_key(|f| std::cmp::Reverse(modified_time(f)));
+    for file in files.iter().take(RECENT_SESSIONS) {
+        let session = summarize_session(file);
+        let title = session.title.clone().or(session.summary.clone()).unwrap_or_else(|| session.session_id.clone());
+        let secondary = [session.summary.as_deref().unwrap_or_default(), session.session_id.as_str()];
+        if let Some(score) = best_score(query, &title, &secondary) {
+            results.push(SearchResult {
+                kind: "session".to_string(),
+                subtitle: session.project_path.clone().unwrap_or_default(),
+                id: session.session_id,
+                title,
+                score,
+            });
+        }
+    }
+
+    let mut keys = Vec::new();
+    if let Some(settings) = settings_path().and_then(|path| read_settings_at(&path)) {
+        settings_keys(&settings, "", &mut keys);
+    }
+    for (key, shown) in keys {
+        if let Some(score) = best_score(query, &key, &[]) {
+            results.push(SearchResult { kind: "setting".to_string(), id: key.clone(), title: key, subtitle: shown, score });
+        }
+    }
+
+    results.sort_by(|a, b| b.score.cmp(&a.score).then_with(|| a.title.to_lowercase().cmp(&b.title.to_lowercase())));
+    results
+}
+
+/// Fuzzy search over inventory items, MCP servers, recent sessions and settings keys, best match first
+#[tauri::command]
+pub async fn global_search(
+    state: State<'_, InventoryCacheState>,
+    query: String,
+    project_path: Option<String>,
+    limit: Option<usize>,
+) -> Result<Vec<SearchResult>, String> {
+    let query = query.trim().to_lowercase();
+    if query.is_empty() {
+        return Ok(Vec::new());
+    }
+    let cached = memory_cached_scan(&state, &project_path)?;
+    run_blocking(move || {
+        let scan = cached
+            .or_else(|| load_indexed_inventory(project_path.as_deref()).map(|index| index.to_scan_result()))
+            .unwrap_or_else(|| scan_all(project_path.as_deref()));
+        let mut results = search(&query, scan.items, project_path.as_deref());
+        results.truncate(limit.unwrap_or(DEFAULT_LIMIT));
+        results
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ranks_exact_prefix_substring_then_subsequence() {
+        let score = |text: &str| fuzzy_score("rev", text);
+        assert_eq!(score("Rev"), Some(1000));
+        assert!(score("Review") > score("code-review"));
+        assert!(score("code-review") > score("preview"));
+        assert!(score("preview") > score("Release Events"));
+        assert!(score("Release Events").is_some());
+        assert_eq!(score("Deploy"), None);
+    }
+}
@@ -0,0 +1,201 @@
+//! Render a session transcript as Markdown or standalone HTML
+//! Tool calls collapse into <details> blocks so a long debugging session stays readable.
+
+use std::path::PathBuf;
+
+use super::sessions::{parse_turns, summarize_entries, SessionSummary, TranscriptToolCall, TranscriptTurn};
+use crate::scanner::transcripts::{find_transcript, read_transcript_entries};
+use crate::blocking::run_blocking;
+use crate::error::ArcadeError;
+
+/// Heading for a turn, e.g. "Assistant (subagent) · 2025-01-01T10:00:00Z"
+fn turn_heading(turn: &TranscriptTurn) -> String {
+    let mut heading = match turn.role.as_str() {
+        "user" => "User".to_string(),
+        "assistant" => "Assistant".to_string(),
+        other => other.to_string(),
+    };
+    if turn.is_sidechain {
+        heading.push_str(" (subagent)");
+    }
+    if let Some(timestamp) = &turn.timestamp {
+        heading.push_str(" · ");
+        heading.push_str(timestamp);
+    }
+    heading
+}
+
+fn tool_input_text(call: &TranscriptToolCall) -> String {
+    serde_json::to_string_pretty(&call.input).unwrap_or_default()
+}
+
+/// Metadata lines shown under the title
+fn summary_lines(summary: &SessionSummary) -> Vec<(&'static str, String)> {
+    let mut lines = vec![("Session", summary.session_id.clone())];
+    if let Some(project) = &summary.project_path {
+        lines.push(("Project", project.clone()));
+    }
+    if let Some(branch) = &summary.git_branch {
+        lines.push(("Branch", branch.clone()));
+    }
+    if let (Some(start), Some(end)) = (&summary.started_at, &summary.ended_at) {
+        lines.push(("Time", format!("{} – {}", start, end)));
+    }
+    if let Some(model) = &summary.model {
+        lines.push(("Model", model.clone()));
+    }
+    let usage = &summary.usage;
+    lines.push((
+        "Tokens",
+        format!(
+            "{} in, {} out, {} cache write, {} cache read",
+            usage.input_tokens, usage.output_tokens, usage.cache_creation_input_tokens, usage.cache_read_input_tokens
+        ),
+    ));
+    lines
+}
+
+fn session_title(summary: &SessionSummary) -> String {
+    summary
+        .summary
+        .clone()
+        .or_else(|| summary.title.clone())
+        .unwrap_or_else(|| format!("Session {}", summary.session_id))
+}
+
+/// A fenced code block whose fence is longer than any backtick run in the content
+fn code_block(content: &str, language: &str) -> String {
+    let longest_run = content
+        .split(|c| c != '`')
+        .map(|run| run.len())
+        .max()
+        .unwrap_or(0);
+    let fence = "`".repeat((longest_run + 1).max(3));
+    format!("{}{}\n{}\n{}\n", fence, language, content.trim_end(), fence)
+}
+
+fn render_markdown(summary: &SessionSummary, turns: &[TranscriptTurn]) -> String {
+    let mut out = format!("# {}\n\n", session_title(summary));
+    for (label, value) in summary_lines(summary) {
+        out.push_str(&format!("- **{}:** {}\n", label, value));
+    }
+
+    for turn in turns.iter().filter(|t| !t.is_meta) {
+        out.push_str(&format!("\n---\n\n## {}\n\n", turn_heading(turn)));
+        if let Some(thinking) = &turn.thinking {
+            out.push_str("<details>\n<summary>Thinking</summary>\n\n");
+            out.push_str(thinking.trim());
+            out.push_str("\n\n</details>\n\n");
+        }
+        if !turn.text.is_empty() {
+            out.push_str(turn.text.trim());
+            out.push_str("\n\n");
+        }
+        for call in &turn.tool_calls {
+            let status = if call.is_error { " (error)" } else { "" };
+            out.push_str(&format!("<details>\n<summary>Tool: {}{}</summary>\n\n", call.name, status));
+            out.push_str(&code_block(&tool_input_text(call), "json"));
+            if let Some(output) = &call.output {
+                out.push_str("\nResult:\n\n");
+                out.push_str(&code_block(output, ""));
+            }
+            out.push_str("\n</details>\n\n");
+        }
+    }
+
+    out
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+const HTML_STYLE: &str = "body{font-family:-apple-system,BlinkMacSystemFont,'Segoe UI',sans-serif;max-width:900px;margin:2rem auto;padding:0 1rem;color:#1f2328;background:#fff}\
+h1{font-size:1.5rem}dl{display:grid;grid-template-columns:max-content 1fr;gap:.25rem 1rem;font-size:.9rem;color:#57606a}dt{font-weight:600}dd{margin:0}\
+.turn{border-top:1px solid #d0d7de;padding:1rem 0}.turn h2{font-size:1rem;margin:0 0 .5rem}.user h2{color:#0969da}.assistant h2{color:#8250df}.sidechain{margin-left:1.5rem}\
+.text{white-space:pre-wrap;word-wrap:break-word}details{margin:.5rem 0;border:1px solid #d0d7de;border-radius:6px;padding:.25rem .75rem}\
+summary{cursor:pointer;font-family:monospace}details.error summary{color:#cf222e}pre{background:#f6f8fa;padding:.75rem;overflow-x:auto;font-size:.85rem}";
+
+fn render_html(summary: &SessionSummary, turns: &[TranscriptTurn]) -> String {
+    let title = escape_html(&session_title(summary));
+    let mut out = format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n<style>{}</style>\n</head>\n<body>\n<h1>{}</h1>\n<dl>\n",
+        title, HTML_STYLE, title
+    );
+    for (label, value) in summary_lines(summary) {
+        out.push_str(&format!("<dt>{}</dt><dd>{}</dd>\n", label, escape_html(&value)));
+    }
+    out.push_str("</dl>\n");
+
+    for turn in turns.iter().filter(|t| !t.is_meta) {
+        let sidechain = if turn.is_sidechain { " sidechain" } else { "" };
+        out.push_str(&format!(
+            "<section class=\"turn {}{}\">\n<h2>{}</h2>\n",
+            escape_html(&turn.role),
+            sidechain,
+            escape_html(&turn_heading(turn))
+        ));
+        if let Some(thinking) = &turn.thinking {
+            out.push_str(&format!(
+                "<details><summary>Thinking</summary><div class=\"text\">{}</div></details>\n",
+                escape_html(thinking.trim())
+            ));
+        }
+        if !turn.text.is_empty() {
+            out.push_str(&format!("<div class=\"text\">{}</div>\n", escape_html(turn.text.trim())));
+        }
+        for call in &turn.tool_calls {
+            let (class, status) = if call.is_error { (" class=\"error\"", " (error)") } else { ("", "") };
+            out.push_str(&format!(
+                "<details{}><summary>Tool: {}{}</summary><pre>{}</pre>",
+                class,
+                escape_html(&call.name),
+                status,
+                escape_html(&tool_input_text(call))
+            ));
+            if let Some(output) = &call.output {
+                out.push_str(&format!("<p>Result:</p><pre>{}</pre>", escape_html(output.trim_end())));
+            }
+            out.push_str("</details>\n");
+        }
+        out.push_str("</section>\n");
+    }
+
+    out.push_str("</body>\n</html>\n");
+    out
+}
+
+/// Render a session to Markdown ("markdown"/"md") or standalone HTML ("html") and write it to `dest`.
+/// A folder `dest` gets a file named after the session. Returns the written path.
+#[tauri::command]
+pub async fn export_session(session_id: String, format: String, dest: String) -> Result<String, ArcadeError> {
+    run_blocking(move || {
+        let extension = match format.as_str() {
+            "markdown" | "md" => "md",
+            "html" => "html",
+            _ => return Err(ArcadeError::invalid_input(format!("Unsupported export format: {}", format))),
+        };
+
+        let path = find_transcript(&session_id).ok_or_else(|| ArcadeError::not_found("Session", &session_id))?;
+        let entries = read_transcript_entries(&path);
+        let summary = summarize_entries(&path, &entries);
+        let turns = parse_turns(&entries);
+        let content = if extension == "html" {
+            render_html(&summary, &turns)
+        } else {
+            render_markdown(&summary, &turns)
+        };
+
+        let mut dest = PathBuf::from(dest);
+        if dest.is_dir() {
+            dest = dest.join(format!("{}.{}", session_id, extension));
+        }
+        crate::paths::atomic_write(&dest, content).map_err(|e| ArcadeError::io(&dest, e))?;
+
+        Ok(dest.to_string_lossy().to_string())
+    })
+    .await?
+}
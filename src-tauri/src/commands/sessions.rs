@@ -0,0 +1,501 @@
+//! Past Claude Code sessions per project, read from the transcripts Claude Code keeps
+//! in ~/.claude/projects/<encoded-path>/<session-id>.jsonl
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+use std::sync::Mutex;
+use tauri::{AppHandle, State};
+
+use super::equipment::context_stats_for_tokens;
+use crate::tailer::SessionTailer;
+use crate::scanner::transcripts::{
+    block_text, content_blocks, entry_cwd, entry_message_id, entry_model, entry_timestamp, entry_type,
+    entry_usage, find_transcript, list_transcript_files, modified_time, prompt_text, read_transcript_entries,
+    TokenUsage,
+};
+use crate::blocking::run_blocking;
+use crate::error::ArcadeError;
+
+/// Overview of one session transcript
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionSummary {
+    pub session_id: String,
+    pub transcript_path: String,
+    /// Working directory the session ran in
+    pub project_path: Option<String>,
+    /// Claude Code's own summary of the session, when it wrote one
+    pub summary: Option<String>,
+    /// First prompt, shortened
+    pub title: Option<String>,
+    /// RFC 3339 timestamps of the first and last entries
+    pub started_at: Option<String>,
+    pub ended_at: Option<String>,
+    /// Prompts typed plus assistant responses
+    pub message_count: u32,
+    pub prompt_count: u32,
+    pub tool_call_count: u32,
+    /// Most used model
+    pub model: Option<String>,
+    pub models: Vec<String>,
+    pub usage: TokenUsage,
+    pub git_branch: Option<String>,
+    pub size_bytes: u64,
+}
+
+/// Longest title kept from the first prompt
+const TITLE_CHARS: usize = 120;
+
+/// Shorten text to a single-line title
+pub(crate) fn title_from(text: &str) -> String {
+    let line = text.lines().find(|l| !l.trim().is_empty()).unwrap_or_default().trim();
+    if line.chars().count() > TITLE_CHARS {
+        format!("{}…", line.chars().take(TITLE_CHARS).collect::<String>())
+    } else {
+        line.to_string()
+    }
+}
+
+/// Read a transcript and total up its metadata
+pub(crate) fn summarize_session(path: &Path) -> SessionSummary {
+    summarize_entries(path, &read_transcript_entries(path))
+}
+
+/// Total up the metadata of already-read transcript entries
+pub(crate) fn summarize_entries(path: &Path, entries: &[Value]) -> SessionSummary {
+    let mut summary = SessionSummary {
+        session_id: path.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default(),
+        transcript_path: path.to_string_lossy().to_string(),
+        project_path: entries.iter().find_map(entry_cwd).map(String::from),
+        summary: None,
+        title: None,
+        started_at: None,
+        ended_at: None,
+        message_count: 0,
+        prompt_count: 0,
+        tool_call_count: 0,
+        model: None,
+        models: Vec::new(),
+        usage: TokenUsage::default(),
+        git_branch: None,
+        size_bytes: fs::metadata(path).map(|m| m.len()).unwrap_or(0),
+    };
+
+    let mut seen_messages: HashSet<String> = HashSet::new();
+    let mut model_counts: HashMap<String, u32> = HashMap::new();
+    let mut first = None;
+    let mut last = None;
+
+    for entry in entries {
+        if let Some(timestamp) = entry_timestamp(entry) {
+            first = first.or(Some(timestamp));
+            last = Some(timestamp);
+        }
+        if summary.git_branch.is_none() {
+            summary.git_branch = entry.get("gitBranch").and_then(|b| b.as_str()).filter(|b| !b.is_empty()).map(String::from);
+        }
+
+        match entry_type(entry) {
+            "summary" => {
+                summary.summary = entry.get("summary").and_then(|s| s.as_str()).map(String::from);
+            }
+            "user" => {
+                if let Some(text) = prompt_text(entry) {
+                    summary.prompt_count += 1;
+                    summary.message_count += 1;
+                    // Slash commands and hook output are wrapped in tags; they make poor titles
+                    if summary.title.is_none() && !text.trim_start().starts_with('<') {
+                        summary.title = Some(title_from(&text));
+                    }
+                }
+            }
+            "assistant" => {
+                summary.tool_call_count += content_blocks(entry)
+                    .iter()
+                    .filter(|b| b.get("type").and_then(|t| t.as_str()) == Some("tool_use"))
+                    .count() as u32;
+
+                // Responses span several entries with the same message id; count them once
+                let is_new = match entry_message_id(entry) {
+                    Some(id) => seen_messages.insert(id.to_string()),
+                    None => true,
+                };
+                if !is_new {
+                    continue;
+                }
+                summary.message_count += 1;
+                if let Some(usage) = entry_usage(entry) {
+                    summary.usage.add(&usage);
+                }
+                if let Some(model) = entry_model(entry) {
+                    *model_counts.entry(model.to_string()).or_insert(0) += 1;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    summary.started_at = first.map(|t| t.to_rfc3339());
+    summary.ended_at = last.map(|t| t.to_rfc3339());
+    summary.model = model_counts.iter().max_by_key(|(_, count)| **count).map(|(model, _)| model.clone());
+    summary.models = model_counts.into_keys().collect();
+    summary.models.sort();
+
+    summary
+}
+
+/// List a project's past sessions, newest first. `limit` caps how many transcripts are read.
+#[tauri::command]
+pub async fn list_session_transcripts(project_path: String, limit: Option<usize>) -> Result<Vec<SessionSummary>, String> {
+    run_blocking(move || {
+        let mut files = list_transcript_files(Some(&project_path));
+        files.sort_by_key(|f| std::cmp::Reverse(modified_time(f)));
+        if let Some(limit) = limit {
+            files.truncate(limit);
+        }
+
+        let mut sessions: Vec<SessionSummary> = files.iter().map(|f| summarize_session(f)).collect();
+        // Transcripts that only hold a summary line or snapshots aren't real sessions
+        sessions.retain(|s| s.message_count > 0);
+        sessions.sort_by(|a, b| b.ended_at.cmp(&a.ended_at));
+
+        Ok(sessions)
+    })
+    .await?
+}
+
+/// Sessions shown for resuming when no limit is given
+const RESUMABLE_SESSIONS: usize = 10;
+
+/// Recent sessions of a project that `claude --resume` can pick up, newest first
+#[tauri::command]
+pub async fn list_resumable_sessions(project_path: String, limit: Option<usize>) -> Result<Vec<SessionSummary>, String> {
+    run_blocking(move || {
+        let limit = limit.unwrap_or(RESUMABLE_SESSIONS);
+        let mut files = list_transcript_files(Some(&project_path));
+        files.sort_by_key(|f| std::cmp::Reverse(modified_time(f)));
+
+        // Read newest first and stop once enough sessions had a prompt to continue from
+        let mut sessions = Vec::new();
+        for file in files {
+            if sessions.len() >= limit {
+                break;
+            }
+            let session = summarize_session(&file);
+            if session.prompt_count > 0 {
+                sessions.push(session);
+            }
+        }
+        sessions.sort_by(|a, b| b.ended_at.cmp(&a.ended_at));
+
+        Ok(sessions)
+    })
+    .await?
+}
+
+/// A tool call made during a turn, with its result once it arrived
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TranscriptToolCall {
+    pub id: String,
+    pub name: String,
+    pub input: Value,
+    pub output: Option<String>,
+    pub is_error: bool,
+}
+
+/// One turn of a conversation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TranscriptTurn {
+    pub uuid: Option<String>,
+    /// "user", "assistant" or "system"
+    pub role: String,
+    pub timestamp: Option<String>,
+    pub text: String,
+    pub thinking: Option<String>,
+    pub tool_calls: Vec<TranscriptToolCall>,
+    pub usage: Option<TokenUsage>,
+    pub model: Option<String>,
+    /// Written by a subagent rather than the main conversation
+    pub is_sidechain: bool,
+    /// Injected by Claude Code (caveats, command output) rather than typed
+    pub is_meta: bool,
+}
+
+/// A parsed session, ready for a conversation viewer
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionTranscript {
+    pub summary: SessionSummary,
+    pub turns: Vec<TranscriptTurn>,
+}
+
+fn entry_flag(entry: &Value, key: &str) -> bool {
+    entry.get(key).and_then(|v| v.as_bool()).unwrap_or(false)
+}
+
+fn new_turn(entry: &Value, role: &str) -> TranscriptTurn {
+    TranscriptTurn {
+        uuid: entry.get("uuid").and_then(|u| u.as_str()).map(String::from),
+        role: role.to_string(),
+        timestamp: entry_timestamp(entry).map(|t| t.to_rfc3339()),
+        text: String::new(),
+        thinking: None,
+        tool_calls: Vec::new(),
+        usage: None,
+        model: None,
+        is_sidechain: entry_flag(entry, "isSidechain"),
+        is_meta: entry_flag(entry, "isMeta"),
+    }
+}
+
+fn append_text(target: &mut String, text: &str) {
+    if text.trim().is_empty() {
+        return;
+    }
+    if !target.is_empty() {
+        target.push_str("\n\n");
+    }
+    target.push_str(text);
+}
+
+/// Turn transcript entries into conversation turns. Assistant responses written as several
+/// entries are merged, and tool results are attached to the call that produced them.
+pub(crate) fn parse_turns(entries: &[Value]) -> Vec<TranscriptTurn> {
+    let mut turns: Vec<TranscriptTurn> = Vec::new();
+    // tool_use id -> (turn index, call index)
+    let mut calls: HashMap<String, (usize, usize)> = HashMap::new();
+    let mut last_message_id: Option<String> = None;
+
+    for entry in entries {
+        match entry_type(entry) {
+            "assistant" => {
+                let message_id = entry_message_id(entry).map(String::from);
+                let continues = message_id.is_some() && message_id == last_message_id && turns.last().is_some_and(|t| t.role == "assistant");
+                if !continues {
+                    let mut turn = new_turn(entry, "assistant");
+                    turn.usage = entry_usage(entry);
+                    turn.model = entry_model(entry).map(String::from);
+                    turns.push(turn);
+                }
+                last_message_id = message_id;
+
+                let index = turns.len() - 1;
+                let turn = &mut turns[index];
+                for block in content_blocks(entry) {
+                    match block.get("type").and_then(|t| t.as_str()) {
+                        Some("text") => append_text(&mut turn.text, block.get("text").and_then(|t| t.as_str()).unwrap_or_default()),
+                        Some("thinking") => {
+                            let thinking = block.get("thinking").and_then(|t| t.as_str()).unwrap_or_default();
+                            append_text(turn.thinking.get_or_insert_with(String::new), thinking);
+                        }
+                        Some("tool_use") => {
+                            let id = block.get("id").and_then(|v| v.as_str()).unwrap_or_default().to_string();
+                            calls.insert(id.clone(), (index, turn.tool_calls.len()));
+                            turn.tool_calls.push(TranscriptToolCall {
+                                id,
+                                name: block.get("name").and_then(|v| v.as_str()).unwrap_or_default().to_string(),
+                                input: block.get("input").cloned().unwrap_or(Value::Null),
+                                output: None,
+                                is_error: false,
+                            });
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            "user" => {
+                last_message_id = None;
+                for block in content_blocks(entry) {
+                    if block.get("type").and_then(|t| t.as_str()) != Some("tool_result") {
+                        continue;
+                    }
+                    let id = block.get("tool_use_id").and_then(|v| v.as_str()).unwrap_or_default();
+                    if let Some(&(turn, call)) = calls.get(id) {
+                        let call = &mut turns[turn].tool_calls[call];
+                        call.output = Some(block_text(block.get("content")));
+                        call.is_error = block.get("is_error").and_then(|v| v.as_bool()).unwrap_or(false);
+                    }
+                }
+                if let Some(text) = prompt_text(entry) {
+                    let mut turn = new_turn(entry, "user");
+                    turn.text = text;
+                    turns.push(turn);
+                }
+            }
+            "system" => {
+                last_message_id = None;
+                // Older versions put the text in "content", newer ones may use a message
+                let text = entry
+                    .get("content")
+                    .and_then(|c| c.as_str())
+                    .map(String::from)
+                    .unwrap_or_else(|| block_text(entry.get("message").and_then(|m| m.get("content"))));
+                if !text.trim().is_empty() {
+                    let mut turn = new_turn(entry, "system");
+                    turn.text = text;
+                    turn.is_meta = true;
+                    turns.push(turn);
+                }
+            }
+            // summary, file-history-snapshot and future entry types carry no conversation
+            _ => {}
+        }
+    }
+
+    turns
+}
+
+/// Parse a session transcript into turns with tool calls, results and token usage
+#[tauri::command]
+pub async fn get_session_transcript(session_id: String) -> Result<SessionTranscript, ArcadeError> {
+    run_blocking(move || {
+        let path = find_transcript(&session_id).ok_or_else(|| ArcadeError::not_found("Session", &session_id))?;
+        let entries = read_transcript_entries(&path);
+
+        Ok(SessionTranscript {
+            summary: summarize_entries(&path, &entries),
+            turns: parse_turns(&entries),
+        })
+    })
+    .await?
+}
+
+/// Context window size after one assistant response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContextTimelinePoint {
+    /// Position among the session's assistant responses
+    pub turn: u32,
+    pub timestamp: Option<String>,
+    pub context_tokens: u64,
+    pub output_tokens: u64,
+    pub load_percentage: f64,
+    /// healthy, heavy or dumbzone
+    pub status: String,
+    pub model: Option<String>,
+}
+
+/// A point where Claude Code compacted the conversation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContextCompaction {
+    /// Responses before the compaction; it sits between `turn` and `turn + 1`
+    pub after_turn: u32,
+    pub timestamp: Option<String>,
+    /// "auto" or "manual"
+    pub trigger: Option<String>,
+    /// Context size just before compacting
+    pub pre_tokens: Option<u64>,
+}
+
+/// How a session's context window filled up over time
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionContextTimeline {
+    pub session_id: String,
+    pub total_budget: u32,
+    pub points: Vec<ContextTimelinePoint>,
+    pub compactions: Vec<ContextCompaction>,
+    pub peak_tokens: u64,
+    /// When the context first reached the dumbzone
+    pub dumbzone_entered_at: Option<String>,
+}
+
+/// Context size per assistant response. Subagent turns run in their own context and are skipped.
+pub(crate) fn context_timeline(session_id: &str, entries: &[Value]) -> SessionContextTimeline {
+    let mut points: Vec<ContextTimelinePoint> = Vec::new();
+    let mut compactions = Vec::new();
+    let mut last_message: Option<String> = None;
+
+    for entry in entries {
+        if entry.get("subtype").and_then(|s| s.as_str()) == Some("compact_boundary") {
+            let metadata = entry.get("compactMetadata");
+            compactions.push(ContextCompaction {
+                after_turn: points.len() as u32,
+                timestamp: entry_timestamp(entry).map(|t| t.to_rfc3339()),
+                trigger: metadata.and_then(|m| m.get("trigger")).and_then(|t| t.as_str()).map(String::from),
+                pre_tokens: metadata.and_then(|m| m.get("preTokens")).and_then(|t| t.as_u64()),
+            });
+            continue;
+        }
+        if entry_type(entry) != "assistant" || entry_flag(entry, "isSidechain") {
+            continue;
+        }
+        let Some(usage) = entry_usage(entry) else {
+            continue;
+        };
+
+        let stats = context_stats_for_tokens(usage.context_tokens().min(u32::MAX as u64) as u32);
+        let point = ContextTimelinePoint {
+            turn: points.len() as u32 + 1,
+            timestamp: entry_timestamp(entry).map(|t| t.to_rfc3339()),
+            context_tokens: usage.context_tokens(),
+            output_tokens: usage.output_tokens,
+            load_percentage: stats.load_percentage,
+            status: stats.status,
+            model: entry_model(entry).map(String::from),
+        };
+
+        // Later entries of the same response carry the final output count
+        let message_id = entry_message_id(entry).map(String::from);
+        match points.last_mut() {
+            Some(last) if message_id.is_some() && message_id == last_message => {
+                *last = ContextTimelinePoint { turn: last.turn, timestamp: last.timestamp.take(), ..point };
+            }
+            _ => points.push(point),
+        }
+        last_message = message_id;
+    }
+
+    SessionContextTimeline {
+        session_id: session_id.to_string(),
+        total_budget: context_stats_for_tokens(0).total_budget,
+        peak_tokens: points.iter().map(|p| p.context_tokens).max().unwrap_or(0),
+        dumbzone_entered_at: points.iter().find(|p| p.status == "dumbzone").and_then(|p| p.timestamp.clone()),
+        points,
+        compactions,
+    }
+}
+
+/// Per-response context size of a session, with compactions, from the usage Claude Code logged
+#[tauri::command]
+pub async fn get_session_context_timeline(session_id: String) -> Result<SessionContextTimeline, ArcadeError> {
+    run_blocking(move || {
+        let path = find_transcript(&session_id).ok_or_else(|| ArcadeError::not_found("Session", &session_id))?;
+        Ok(context_timeline(&session_id, &read_transcript_entries(&path)))
+    })
+    .await?
+}
+
+pub struct SessionTailerState(pub Mutex<SessionTailer>);
+
+/// Follow the active project's newest transcript, emitting `session-event` for new activity
+#[tauri::command]
+pub fn start_session_tail(
+    app_handle: AppHandle,
+    state: State<'_, SessionTailerState>,
+    project_path: String,
+) -> Result<(), String> {
+    let mut tailer = state.0.lock().map_err(|e| format!("Lock error: {}", e))?;
+    tailer.start(app_handle, &project_path)
+}
+
+/// Stop following session transcripts
+#[tauri::command]
+pub fn stop_session_tail(state: State<'_, SessionTailerState>) -> Result<(), String> {
+    let mut tailer = state.0.lock().map_err(|e| format!("Lock error: {}", e))?;
+    tailer.stop();
+    Ok(())
+}
+
+/// Project whose sessions are being followed, if any
+#[tauri::command]
+pub fn get_session_tail_project(state: State<'_, SessionTailerState>) -> Result<Option<String>, String> {
+    let tailer = state.0.lock().map_err(|e| format!("Lock error: {}", e))?;
+    Ok(tailer.active_project())
+}
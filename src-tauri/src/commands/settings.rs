@@ -0,0 +1,151 @@
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+use crate::scanner::effective_settings::{effective_settings, EffectiveSettings};
+use crate::scanner::settings::{
+    read_settings_at_revision, read_settings_full, settings_error, update_settings_field_at, AtRevision,
+    ClaudeSettingsFull,
+};
+use crate::blocking::run_blocking;
+use crate::error::ArcadeError;
+use crate::secrets::{check_secrets, scan_secrets};
+
+/// Get the full settings.json document and its revision. A file that doesn't parse fails with its position.
+#[tauri::command]
+pub async fn get_settings() -> Result<AtRevision<ClaudeSettingsFull>, ArcadeError> {
+    run_blocking(|| {
+        if let Some(error) = settings_error() {
+            return Err(error);
+        }
+        read_settings_at_revision()
+    })
+    .await?
+}
+
+/// Update one settings field by dotted key (null removes it). A value that looks like it holds
+/// credentials (an `env` token, say) is refused unless `allow_secrets` confirms it. With the
+/// `revision` from `get_settings`, a settings.json changed since then fails with a settings conflict.
+#[tauri::command]
+#[tracing::instrument(skip(value), err)]
+pub async fn update_settings_field(
+    key: String,
+    value: serde_json::Value,
+    allow_secrets: Option<bool>,
+    revision: Option<String>,
+) -> Result<AtRevision<ClaudeSettingsFull>, ArcadeError> {
+    run_blocking(move || {
+        let written = serde_json::to_string_pretty(&serde_json::json!({ &key: &value })).unwrap_or_default();
+        check_secrets(&written, allow_secrets)?;
+        update_settings_field_at(revision.as_deref(), &key, value)?;
+        read_settings_at_revision()
+    })
+    .await?
+}
+
+/// Get the parse or read error in settings.json, if the file is currently broken
+#[tauri::command]
+pub async fn get_settings_error() -> Result<Option<ArcadeError>, String> {
+    run_blocking(settings_error).await
+}
+
+/// Get the settings Claude Code actually runs with for a project, merged across
+/// enterprise, local, project and user files, with the file each value came from
+#[tauri::command]
+pub async fn get_effective_settings(project_path: Option<String>) -> Result<EffectiveSettings, String> {
+    run_blocking(move || effective_settings(project_path.as_deref())).await
+}
+
+/// One variable of the settings.json `env` block
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EnvVar {
+    pub name: String,
+    pub value: String,
+    /// The value looks like a credential kept in plain text
+    pub is_secret: bool,
+    /// Exactly `${NAME}` or `$NAME`, taken from the login environment: kept as written and never
+    /// treated as a secret
+    pub placeholder: bool,
+}
+
+/// Whether the whole value is a reference to another variable; anything around it could hide a credential
+fn is_placeholder(value: &str) -> bool {
+    let name = value
+        .strip_prefix("${")
+        .and_then(|v| v.strip_suffix('}'))
+        .or_else(|| value.strip_prefix('$').filter(|v| !v.starts_with('{')));
+    name.is_some_and(|name| check_env_name(name).is_ok())
+}
+
+fn check_env_name(name: &str) -> Result<(), ArcadeError> {
+    let mut chars = name.chars();
+    let valid = chars.next().is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if valid {
+        Ok(())
+    } else {
+        Err(ArcadeError::invalid_input(format!("'{}' is not a valid environment variable name", name)))
+    }
+}
+
+fn env_vars(env: BTreeMap<String, String>) -> Vec<EnvVar> {
+    env.into_iter()
+        .map(|(name, value)| {
+            let placeholder = is_placeholder(&value);
+            let is_secret = !placeholder && !scan_secrets(&format!("{}={}", name, value)).is_empty();
+            EnvVar { name, value, is_secret, placeholder }
+        })
+        .collect()
+}
+
+/// The `env` block Claude Code sets for every session, sorted by name
+#[tauri::command]
+pub async fn get_settings_env() -> Result<Vec<EnvVar>, ArcadeError> {
+    run_blocking(|| {
+        let env = read_settings_full()?.env.unwrap_or_default();
+        Ok(env_vars(env.into_iter().collect()))
+    })
+    .await?
+}
+
+/// Replace the `env` block (an empty one removes it). Values that look like credentials are refused
+/// unless `allow_secrets` confirms them; placeholders always pass. `revision` is as for `update_settings_field`.
+#[tauri::command]
+#[tracing::instrument(skip(vars), err)]
+pub async fn set_settings_env(
+    vars: BTreeMap<String, String>,
+    allow_secrets: Option<bool>,
+    revision: Option<String>,
+) -> Result<Vec<EnvVar>, ArcadeError> {
+    run_blocking(move || {
+        for name in vars.keys() {
+            check_env_name(name)?;
+        }
+        let plain: String = vars
+            .iter()
+            .filter(|(_, value)| !is_placeholder(value))
+            .map(|(name, value)| format!("{}={}\n", name, value))
+            .collect();
+        check_secrets(&plain, allow_secrets)?;
+
+        let value = if vars.is_empty() { serde_json::Value::Null } else { serde_json::json!(vars) };
+        let env = update_settings_field_at(revision.as_deref(), "env", value)?.env.unwrap_or_default();
+        Ok(env_vars(env.into_iter().collect()))
+    })
+    .await?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_whole_references_are_placeholders() {
+        assert!(is_placeholder("${HTTPS_PROXY}"));
+        assert!(is_placeholder("$ANTHROPIC_API_KEY"));
+        assert!(!is_placeholder("$"));
+        assert!(!is_placeholder("${HTTPS_PROXY"));
+        assert!(!is_placeholder("$TOKEN sk-ant-REDACTED"));
+        assert!(!is_placeholder("keychain:anthropic"));
+    }
+}
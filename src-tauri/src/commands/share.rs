@@ -0,0 +1,271 @@
+//! Share one command, agent or skill as a compact text payload that can be pasted in chat
+//! The payload is deflated JSON in URL-safe base64 behind an `arcade-item:` prefix.
+
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use flate2::read::{DeflateDecoder, DeflateEncoder};
+use flate2::Compression;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::Read;
+use std::path::{Component, Path};
+use walkdir::WalkDir;
+
+use super::character::{rename_frontmatter, renamed, write_claude_file};
+use super::convert::scope_root;
+use crate::paths::check_name;
+use crate::injection::{scan_for_injection, InjectionReport};
+use crate::blocking::run_blocking;
+
+/// Start of every payload, with the format version
+const SHARE_PREFIX: &str = "arcade-item:1:";
+
+/// Skills bigger than this don't belong in a chat message
+const MAX_SHARED_BYTES: usize = 256 * 1024;
+
+/// One file of a shared item, relative to the item (a skill folder, or just the file name)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SharedFile {
+    pub path: String,
+    pub content: String,
+}
+
+/// A command, agent or skill as carried in a payload
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SharedItem {
+    /// "command", "agent" or "skill"
+    pub kind: String,
+    pub name: String,
+    pub files: Vec<SharedFile>,
+}
+
+/// Result of `share_item`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SharePayload {
+    pub payload: String,
+    pub kind: String,
+    pub name: String,
+    pub file_count: usize,
+}
+
+/// What a payload holds and whether it clashes with an existing item
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SharedItemPreview {
+    pub item: SharedItem,
+    pub conflict: bool,
+    /// Name it would be imported under when it clashes
+    pub import_name: String,
+    /// Prompt-injection score of the item's files
+    pub risk: InjectionReport,
+}
+
+/// Result of `import_shared_item`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SharedItemImport {
+    pub item_id: String,
+    pub path: String,
+    /// Set when the name was taken and the item was imported under another
+    pub renamed_from: Option<String>,
+}
+
+/// Folder an item kind lives in, and the inventory id prefix it gets
+fn kind_layout(kind: &str) -> Result<(&'static str, &'static str), String> {
+    match kind {
+        "command" => Ok(("commands", "cmd")),
+        "agent" => Ok(("agents", "subagent")),
+        "skill" => Ok(("skills", "skill")),
+        _ => Err(format!("Only commands, agents and skills can be shared, not {}", kind)),
+    }
+}
+
+/// Where an item named `name` lives under a .claude folder
+fn item_path(root: &Path, kind: &str, name: &str) -> Result<std::path::PathBuf, String> {
+    let (folder, _) = kind_layout(kind)?;
+    Ok(match kind {
+        "skill" => root.join(folder).join(name),
+        _ => root.join(folder).join(format!("{}.md", name)),
+    })
+}
+
+fn read_shared_files(kind: &str, path: &Path) -> Result<Vec<SharedFile>, String> {
+    if kind != "skill" {
+        let content = fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        let file_name = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+        return Ok(vec![SharedFile { path: file_name, content }]);
+    }
+
+    let mut files = Vec::new();
+    for entry in WalkDir::new(path).sort_by_file_name().into_iter().filter_map(|e| e.ok()) {
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let relative = entry.path().strip_prefix(path).unwrap_or(entry.path()).to_string_lossy().replace('\\', "/");
+        let content = fs::read_to_string(entry.path())
+            .map_err(|_| format!("{} isn't text, so this skill can't be shared as a payload", relative))?;
+        files.push(SharedFile { path: relative, content });
+    }
+    Ok(files)
+}
+
+fn encode_payload(item: &SharedItem) -> Result<String, String> {
+    let json = serde_json::to_vec(item).map_err(|e| e.to_string())?;
+    let mut compressed = Vec::new();
+    DeflateEncoder::new(json.as_slice(), Compression::best())
+        .read_to_end(&mut compressed)
+        .map_err(|e| e.to_string())?;
+    Ok(format!("{}{}", SHARE_PREFIX, URL_SAFE_NO_PAD.encode(compressed)))
+}
+
+/// Decode and check a payload; whitespace from chat line wrapping is ignored
+fn decode_payload(payload: &str) -> Result<SharedItem, String> {
+    let compact: String = payload.chars().filter(|c| !c.is_whitespace()).collect();
+    let encoded = compact.strip_prefix(SHARE_PREFIX).ok_or_else(|| {
+        if compact.starts_with("arcade-item:") {
+            "This item was shared from a newer ClaudeArcade; update to import it".to_string()
+        } else {
+            "Not a ClaudeArcade item".to_string()
+        }
+    })?;
+    let compressed = URL_SAFE_NO_PAD.decode(encoded).map_err(|_| "The shared item is damaged or incomplete".to_string())?;
+
+    // Bounded so a crafted payload can't inflate without limit
+    let mut json = Vec::new();
+    DeflateDecoder::new(compressed.as_slice())
+        .take(MAX_SHARED_BYTES as u64 * 2)
+        .read_to_end(&mut json)
+        .map_err(|_| "The shared item is damaged or incomplete".to_string())?;
+    let item: SharedItem = serde_json::from_slice(&json).map_err(|e| format!("Not a valid shared item: {}", e))?;
+
+    kind_layout(&item.kind)?;
+    check_name(&item.name)?;
+    let single_file = format!("{}.md", item.name);
+    for file in &item.files {
+        let safe = Path::new(&file.path).components().all(|c| matches!(c, Component::Normal(_)));
+        let expected = item.kind == "skill" || file.path == single_file;
+        if !safe || !expected {
+            return Err(format!("Shared item has an unsafe path: {}", file.path));
+        }
+    }
+    if item.files.is_empty() || (item.kind != "skill" && item.files.len() != 1) {
+        return Err("Shared item has no usable content".to_string());
+    }
+    Ok(item)
+}
+
+/// Name the item would be imported under in `root`, and whether its own name is taken
+fn import_name(root: &Path, item: &SharedItem) -> Result<(String, bool), String> {
+    let conflict = item_path(root, &item.kind, &item.name)?.exists();
+    if !conflict {
+        return Ok((item.name.clone(), false));
+    }
+    let name = renamed(&item.name, |candidate| item_path(root, &item.kind, candidate).is_ok_and(|p| p.exists()));
+    Ok((name, true))
+}
+
+/// Pack a user or project command, agent or skill into a payload teammates can paste into
+/// `import_shared_item`
+#[tauri::command]
+pub async fn share_item(item_id: String, project_path: Option<String>) -> Result<SharePayload, String> {
+    run_blocking(move || {
+        let (prefix, rest) = item_id.split_once('_').ok_or_else(|| format!("Unknown item: {}", item_id))?;
+        let (scope, name) = rest.split_once('_').ok_or_else(|| format!("Unknown item: {}", item_id))?;
+        let kind = match prefix {
+            "cmd" => "command",
+            "subagent" => "agent",
+            "skill" => "skill",
+            _ => return Err(format!("Only commands, agents and skills can be shared, not {}", item_id)),
+        };
+        check_name(name)?;
+        let root = scope_root(scope, project_path.as_deref())?;
+        let path = item_path(&root, kind, name)?;
+        if !path.exists() {
+            return Err(format!("Item not found: {}", item_id));
+        }
+
+        let files = read_shared_files(kind, &path)?;
+        let size: usize = files.iter().map(|f| f.content.len()).sum();
+        if size > MAX_SHARED_BYTES {
+            return Err(format!("{} is {} KB; items over {} KB can't be shared this way", name, size / 1024, MAX_SHARED_BYTES / 1024));
+        }
+
+        let item = SharedItem { kind: kind.to_string(), name: name.to_string(), files };
+        Ok(SharePayload {
+            payload: encode_payload(&item)?,
+            kind: item.kind,
+            name: item.name,
+            file_count: item.files.len(),
+        })
+    })
+    .await?
+}
+
+/// Show what a payload contains before importing it. `scope` is "user" (default) or "project".
+#[tauri::command]
+pub async fn preview_shared_item(payload: String, scope: Option<String>, project_path: Option<String>) -> Result<SharedItemPreview, String> {
+    run_blocking(move || {
+        let item = decode_payload(&payload)?;
+        let root = scope_root(scope.as_deref().unwrap_or("user"), project_path.as_deref())?;
+        let (import_name, conflict) = import_name(&root, &item)?;
+        let risk = scan_for_injection(item.files.iter().map(|f| (f.path.as_str(), f.content.as_str())));
+        Ok(SharedItemPreview { item, conflict, import_name, risk })
+    })
+    .await?
+}
+
+/// Import a shared item into the user's (default) or the project's .claude folder. An item
+/// whose name is taken is imported under a new name rather than replacing the existing one.
+#[tauri::command]
+pub async fn import_shared_item(payload: String, scope: Option<String>, project_path: Option<String>) -> Result<SharedItemImport, String> {
+    run_blocking(move || {
+        let item = decode_payload(&payload)?;
+        let scope = scope.unwrap_or_else(|| "user".to_string());
+        let root = scope_root(&scope, project_path.as_deref())?;
+        let (name, conflict) = import_name(&root, &item)?;
+        let target = item_path(&root, &item.kind, &name)?;
+
+        for file in &item.files {
+            let (path, content) = match item.kind.as_str() {
+                "skill" if file.path.eq_ignore_ascii_case("SKILL.md") && conflict => {
+                    (target.join(&file.path), rename_frontmatter(&file.content, &name))
+                }
+                "skill" => (target.join(&file.path), file.content.clone()),
+                "agent" if conflict => (target.clone(), rename_frontmatter(&file.content, &name)),
+                _ => (target.clone(), file.content.clone()),
+            };
+            write_claude_file(&path, &content)?;
+        }
+
+        let (_, id_prefix) = kind_layout(&item.kind)?;
+        Ok(SharedItemImport {
+            item_id: format!("{}_{}_{}", id_prefix, scope, name),
+            path: target.to_string_lossy().to_string(),
+            renamed_from: conflict.then_some(item.name),
+        })
+    })
+    .await?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn payload_round_trips_and_rejects_unsafe_paths() {
+        let item = SharedItem {
+            kind: "command".to_string(),
+            name: "review".to_string(),
+            files: vec![SharedFile { path: "review.md".to_string(), content: "Review the diff".to_string() }],
+        };
+        let payload = encode_payload(&item).unwrap();
+        let wrapped = format!("{}\n{}", &payload[..20], &payload[20..]);
+        assert_eq!(decode_payload(&wrapped).unwrap().files[0].content, "Review the diff");
+
+        let escaping = SharedItem { files: vec![SharedFile { path: "../x.md".to_string(), content: String::new() }], ..item };
+        assert!(decode_payload(&encode_payload(&escaping).unwrap()).is_err());
+    }
+}
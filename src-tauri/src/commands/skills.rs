@@ -1,6 +1,13 @@
 use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::PathBuf;
+use crate::blocking::run_blocking;
+use crate::error::ArcadeError;
+use crate::http::send_with_retry;
+use crate::scanner::integrity::{forget_install, record_install};
+use crate::injection::{check_injection_risk, scan_for_injection, InjectionReport};
+use crate::paths::{check_name, check_writable};
+use crate::trash::move_to_trash;
 
 /// Represents an installed skill
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -13,13 +20,15 @@ pub struct InstalledSkill {
     pub is_global: bool,
     pub repo_url: String,
     pub version: Option<String>,
+    /// Prompt-injection score of the downloaded files; only set by `download_skill`
+    #[serde(default)]
+    pub risk: Option<InjectionReport>,
 }
 
 /// Get the global skills directory
 fn get_global_skills_dir() -> PathBuf {
-    dirs::home_dir()
+    crate::paths::claude_dir()
         .unwrap_or_default()
-        .join(".claude")
         .join("skills")
 }
 
@@ -32,40 +41,43 @@ fn get_project_skills_dir(project_path: &str) -> PathBuf {
 
 /// List all installed skills (both global and project-specific)
 #[tauri::command]
-pub fn list_installed_skills(project_path: Option<String>) -> Vec<InstalledSkill> {
-    let mut skills = Vec::new();
-
-    // Scan global skills
-    let global_dir = get_global_skills_dir();
-    if global_dir.exists() {
-        if let Ok(entries) = fs::read_dir(&global_dir) {
-            for entry in entries.flatten() {
-                if entry.path().is_dir() {
-                    if let Some(skill) = read_skill_metadata(&entry.path(), true) {
-                        skills.push(skill);
+pub async fn list_installed_skills(project_path: Option<String>) -> Result<Vec<InstalledSkill>, String> {
+    run_blocking(move || {
+        let mut skills = Vec::new();
+
+        // Scan global skills
+        let global_dir = get_global_skills_dir();
+        if global_dir.exists() {
+            if let Ok(entries) = fs::read_dir(&global_dir) {
+                for entry in entries.flatten() {
+                    if entry.path().is_dir() {
+                        if let Some(skill) = read_skill_metadata(&entry.path(), true) {
+                            skills.push(skill);
+                        }
                     }
                 }
             }
         }
-    }
 
-    // Scan project skills if path provided
-    if let Some(path) = project_path {
-        let project_dir = get_project_skills_dir(&path);
-        if project_dir.exists() {
-            if let Ok(entries) = fs::read_dir(&project_dir) {
-                for entry in entries.flatten() {
-                    if entry.path().is_dir() {
-                        if let Some(skill) = read_skill_metadata(&entry.path(), false) {
-                            skills.push(skill);
+        // Scan project skills if path provided
+        if let Some(path) = project_path {
+            let project_dir = get_project_skills_dir(&path);
+            if project_dir.exists() {
+                if let Ok(entries) = fs::read_dir(&project_dir) {
+                    for entry in entries.flatten() {
+                        if entry.path().is_dir() {
+                            if let Some(skill) = read_skill_metadata(&entry.path(), false) {
+                                skills.push(skill);
+                            }
                         }
                     }
                 }
             }
         }
-    }
 
-    skills
+        skills
+    })
+    .await
 }
 
 /// Read skill metadata from a skill directory
@@ -88,6 +100,7 @@ fn read_skill_metadata(skill_path: &PathBuf, is_global: bool) -> Option<Installe
                     is_global,
                     repo_url: format!("https://github.com/anthropics/skills/tree/main/skills/{}", skill_id),
                     version: meta.version,
+                    risk: None,
                 });
             }
         }
@@ -118,6 +131,7 @@ fn read_skill_metadata(skill_path: &PathBuf, is_global: bool) -> Option<Installe
         is_global,
         repo_url: format!("https://github.com/anthropics/skills/tree/main/skills/{}", skill_id),
         version: None,
+        risk: None,
     })
 }
 
@@ -129,24 +143,26 @@ struct SkillMetadata {
     version: Option<String>,
 }
 
-/// Download and install a skill from GitHub
+/// Download and install a skill from GitHub. A skill that scores high for prompt injection is
+/// refused with its report, and installed only once `accept_risk` confirms it.
 #[tauri::command]
+#[tracing::instrument(skip(skill_name), err)]
 pub async fn download_skill(
     skill_id: String,
     skill_name: String,
     is_global: bool,
     project_path: Option<String>,
-) -> Result<InstalledSkill, String> {
+    accept_risk: Option<bool>,
+) -> Result<InstalledSkill, ArcadeError> {
     // Determine target directory
+    check_name(&skill_id)?;
     let target_dir = if is_global {
         get_global_skills_dir().join(&skill_id)
     } else {
-        let project = project_path.ok_or("Project path required for project-specific skills")?;
-        get_project_skills_dir(&project).join(&skill_id)
+        let project = project_path.as_deref().ok_or_else(|| ArcadeError::invalid_input("Project path required for project-specific skills"))?;
+        get_project_skills_dir(project).join(&skill_id)
     };
-
-    // Create directory if it doesn't exist
-    fs::create_dir_all(&target_dir).map_err(|e| format!("Failed to create skill directory: {}", e))?;
+    check_writable(&target_dir, project_path.as_deref())?;
 
     // Fetch skill files from GitHub API
     let api_url = format!(
@@ -154,31 +170,47 @@ pub async fn download_skill(
         skill_id
     );
 
-    let client = reqwest::Client::new();
-    let response = client
-        .get(&api_url)
-        .header("Accept", "application/vnd.github.v3+json")
-        .header("User-Agent", "ClaudeArcade")
-        .send()
-        .await
-        .map_err(|e| format!("Failed to fetch skill from GitHub: {}", e))?;
-
-    if !response.status().is_success() {
-        return Err(format!("GitHub API error: {}", response.status()));
+    let client = crate::http::client()?;
+    let response = send_with_retry(|| client.get(&api_url).header("Accept", "application/vnd.github.v3+json")).await?;
+
+    if let Some(error) = github_error(&response, &skill_id) {
+        return Err(error);
     }
 
-    let contents: Vec<GitHubContent> = response
-        .json()
-        .await
-        .map_err(|e| format!("Failed to parse GitHub response: {}", e))?;
+    let contents: Vec<GitHubContent> = response.json().await?;
 
-    // Download each file
+    // Download every file before writing any, so the content can be scored first
+    let mut files = Vec::new();
     for item in contents {
         if item.content_type == "file" {
-            download_file(&client, &item.download_url.unwrap_or_default(), &target_dir.join(&item.name)).await?;
+            // File names come from the API response, so they are checked like ids
+            check_name(&item.name)?;
+            let content = download_file(&client, &item.download_url.unwrap_or_default()).await?;
+            files.push((item.name, content));
         }
     }
 
+    let risk = scan_for_injection(
+        files
+            .iter()
+            .filter(|(name, _)| name.ends_with(".md"))
+            .map(|(name, content)| (name.as_str(), std::str::from_utf8(content).unwrap_or_default())),
+    );
+    check_injection_risk(&risk, accept_risk)?;
+
+    // Only the fetch is async; writing and hashing the files runs on the blocking pool
+    let item_id = skill_item_id(&skill_id, is_global);
+    let dir = target_dir.clone();
+    run_blocking(move || {
+        fs::create_dir_all(&dir).map_err(|e| ArcadeError::io(&dir, e))?;
+        for (name, content) in &files {
+            let path = dir.join(name);
+            crate::paths::atomic_write(&path, content).map_err(|e| ArcadeError::io(&path, e))?;
+        }
+        record_install(&item_id, &dir, None)
+    })
+    .await??;
+
     // Return the installed skill info
     Ok(InstalledSkill {
         id: skill_id.clone(),
@@ -189,9 +221,37 @@ pub async fn download_skill(
         is_global,
         repo_url: format!("https://github.com/anthropics/skills/tree/main/skills/{}", skill_id),
         version: None,
+        risk: Some(risk),
     })
 }
 
+/// Error for a failed GitHub API response. Unauthenticated requests get 60 an hour, so an
+/// exhausted limit is reported with the time until it resets.
+fn github_error(response: &reqwest::Response, skill_id: &str) -> Option<ArcadeError> {
+    let status = response.status();
+    if status.is_success() {
+        return None;
+    }
+    let header = |name: &str| {
+        response
+            .headers()
+            .get(name)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.parse::<u64>().ok())
+    };
+    let exhausted = header("x-ratelimit-remaining") == Some(0);
+    if status == reqwest::StatusCode::TOO_MANY_REQUESTS || (status == reqwest::StatusCode::FORBIDDEN && exhausted) {
+        let retry_after_secs = header("retry-after").or_else(|| {
+            header("x-ratelimit-reset").map(|reset| reset.saturating_sub(chrono::Utc::now().timestamp().max(0) as u64))
+        });
+        return Some(ArcadeError::NetworkRateLimited { retry_after_secs });
+    }
+    if status == reqwest::StatusCode::NOT_FOUND {
+        return Some(ArcadeError::not_found("Skill", skill_id));
+    }
+    Some(ArcadeError::Network { message: format!("GitHub API error: {}", status) })
+}
+
 #[derive(Debug, Deserialize)]
 struct GitHubContent {
     name: String,
@@ -200,64 +260,69 @@ struct GitHubContent {
     download_url: Option<String>,
 }
 
-async fn download_file(client: &reqwest::Client, url: &str, path: &PathBuf) -> Result<(), String> {
+async fn download_file(client: &reqwest::Client, url: &str) -> Result<Vec<u8>, ArcadeError> {
     if url.is_empty() {
-        return Ok(());
+        return Ok(Vec::new());
     }
 
-    let response = client
-        .get(url)
-        .header("User-Agent", "ClaudeArcade")
-        .send()
-        .await
-        .map_err(|e| format!("Failed to download file: {}", e))?;
-
-    let content = response
-        .bytes()
-        .await
-        .map_err(|e| format!("Failed to read file content: {}", e))?;
-
-    fs::write(path, content).map_err(|e| format!("Failed to write file: {}", e))?;
+    let response = send_with_retry(|| client.get(url)).await?.error_for_status()?;
 
-    Ok(())
+    Ok(response.bytes().await?.to_vec())
 }
 
 /// Remove an installed skill
 #[tauri::command]
-pub fn remove_skill(skill_id: String, is_global: bool, project_path: Option<String>) -> Result<(), String> {
-    let skill_dir = if is_global {
-        get_global_skills_dir().join(&skill_id)
-    } else {
-        let project = project_path.ok_or("Project path required for project-specific skills")?;
-        get_project_skills_dir(&project).join(&skill_id)
-    };
-
-    if skill_dir.exists() {
-        fs::remove_dir_all(&skill_dir).map_err(|e| format!("Failed to remove skill: {}", e))?;
-    }
+#[tracing::instrument(err)]
+pub async fn remove_skill(skill_id: String, is_global: bool, project_path: Option<String>) -> Result<(), ArcadeError> {
+    run_blocking(move || {
+        check_name(&skill_id)?;
+        let skill_dir = if is_global {
+            get_global_skills_dir().join(&skill_id)
+        } else {
+            let project = project_path.as_deref().ok_or_else(|| ArcadeError::invalid_input("Project path required for project-specific skills"))?;
+            get_project_skills_dir(project).join(&skill_id)
+        };
+
+        if skill_dir.exists() {
+            check_writable(&skill_dir, project_path.as_deref())?;
+            move_to_trash(&skill_dir, "skill")?;
+        }
+        forget_install(&skill_item_id(&skill_id, is_global))?;
 
-    Ok(())
+        Ok(())
+    })
+    .await?
 }
 
 /// Get skill content (for reading/displaying)
 #[tauri::command]
-pub fn get_skill_content(skill_id: String, is_global: bool, project_path: Option<String>) -> Result<String, String> {
-    let skill_dir = if is_global {
-        get_global_skills_dir().join(&skill_id)
-    } else {
-        let project = project_path.ok_or("Project path required")?;
-        get_project_skills_dir(&project).join(&skill_id)
-    };
-
-    let md_path = skill_dir.join("skill.md");
-    if md_path.exists() {
-        fs::read_to_string(&md_path).map_err(|e| format!("Failed to read skill: {}", e))
-    } else {
-        Err("Skill file not found".to_string())
-    }
+pub async fn get_skill_content(skill_id: String, is_global: bool, project_path: Option<String>) -> Result<String, ArcadeError> {
+    run_blocking(move || {
+        check_name(&skill_id)?;
+        let skill_dir = if is_global {
+            get_global_skills_dir().join(&skill_id)
+        } else {
+            let project = project_path.ok_or_else(|| ArcadeError::invalid_input("Project path required"))?;
+            get_project_skills_dir(&project).join(&skill_id)
+        };
+
+        let md_path = skill_dir.join("skill.md");
+        if md_path.exists() {
+            fs::read_to_string(&md_path).map_err(|e| ArcadeError::io(&md_path, e))
+        } else {
+            Err(ArcadeError::not_found("Skill", skill_id))
+        }
+    })
+    .await?
 }
 
 // Helper functions
+
+/// Inventory id of a skill, which its checksums are recorded under
+fn skill_item_id(skill_id: &str, is_global: bool) -> String {
+    format!("skill_{}_{}", if is_global { "user" } else { "project" }, skill_id)
+}
+
 fn format_skill_name(name: &str) -> String {
     name.split('-')
         .map(|word| {
@@ -0,0 +1,30 @@
+//! Slash command details: usage, allowed tools and per-command model overrides
+
+use crate::blocking::run_blocking;
+use crate::error::ArcadeError;
+use crate::paths::check_name;
+use crate::scanner::slash_commands::{
+    get_project_commands_dir, get_user_commands_dir, read_command_details, SlashCommandDetails,
+};
+
+/// Get one slash command by name (the file name without .md)
+#[tauri::command]
+pub async fn get_slash_command(
+    command_name: String,
+    is_global: bool,
+    project_path: Option<String>,
+) -> Result<SlashCommandDetails, ArcadeError> {
+    run_blocking(move || {
+        check_name(&command_name)?;
+        let dir = if is_global {
+            get_user_commands_dir().ok_or("Could not find home directory")?
+        } else {
+            let project = project_path.ok_or("Project path required for project commands")?;
+            get_project_commands_dir(&project)
+        };
+
+        read_command_details(&dir.join(format!("{}.md", command_name)))
+            .ok_or_else(|| ArcadeError::not_found("Command", &command_name))
+    })
+    .await?
+}
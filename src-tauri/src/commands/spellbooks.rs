@@ -0,0 +1,237 @@
+//! Spellbooks: named skill collections per project, so context can be themed per kind of task
+//! Equipping one moves its skills back into `skills/` and, when asked, every other skill into
+//! `skills-disabled/`, where Claude Code doesn't load it. User skills are shared by every
+//! project, so putting them away affects other projects too.
+//! Definitions live in arcade_spellbooks.json, keyed by project path ("" outside a project).
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::equipment::context_stats_for_tokens;
+use super::mcp_groups::slug;
+use crate::blocking::run_blocking;
+use crate::error::ArcadeError;
+use crate::paths::check_writable;
+use crate::scanner::skills::{scan_skills, skill_folders};
+use crate::types::{ContextStats, InventoryItem};
+
+/// A named set of skills, by skill item id (`skill_user_pdf`, `skill_project_lint`)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Spellbook {
+    pub id: String,
+    pub name: String,
+    pub skills: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ProjectSpellbooks {
+    pub spellbooks: Vec<Spellbook>,
+    /// The spellbook equipped last
+    pub active: Option<String>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+struct SpellbooksFile {
+    projects: HashMap<String, ProjectSpellbooks>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EquipSpellbookResult {
+    /// Skill ids moved back into use
+    pub enabled: Vec<String>,
+    /// Skill ids put away
+    pub disabled: Vec<String>,
+    /// Skills in the spellbook that no longer exist, and moves that failed
+    pub errors: Vec<String>,
+    /// Load of the skills now in use
+    pub context_stats: ContextStats,
+}
+
+fn spellbooks_path() -> Option<PathBuf> {
+    crate::paths::claude_dir().map(|d| d.join("arcade_spellbooks.json"))
+}
+
+fn load_spellbooks() -> SpellbooksFile {
+    spellbooks_path()
+        .and_then(|path| crate::store::SPELLBOOKS.load(&path))
+        .unwrap_or_default()
+}
+
+fn save_spellbooks(file: &SpellbooksFile) -> Result<(), ArcadeError> {
+    let path = spellbooks_path().ok_or("Could not find home directory")?;
+    let content = crate::store::SPELLBOOKS.to_json(file)?;
+    crate::paths::atomic_write(&path, content).map_err(|e| ArcadeError::io(&path, e))
+}
+
+fn project_key(project_path: Option<&str>) -> String {
+    project_path.unwrap_or_default().to_string()
+}
+
+/// Which skills to move: (item, enable) for each skill whose state has to change
+fn plan_moves<'a>(skills: &'a [InventoryItem], book: &Spellbook, disable_others: bool) -> Vec<(&'a InventoryItem, bool)> {
+    skills
+        .iter()
+        .filter_map(|skill| {
+            let wanted = book.skills.contains(&skill.id);
+            match (wanted, skill.enabled) {
+                (true, false) => Some((skill, true)),
+                (false, true) if disable_others => Some((skill, false)),
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+/// Move a skill folder between `skills/` and `skills-disabled/`
+fn move_skill(skill_dir: &Path, enable: bool, project_path: Option<&str>) -> Result<(), ArcadeError> {
+    let (active, disabled) = skill_folders(skill_dir).ok_or("Invalid skill path")?;
+    let name = skill_dir.file_name().ok_or("Invalid skill path")?;
+    let target = if enable { active } else { disabled }.join(name);
+    check_writable(skill_dir, project_path)?;
+    check_writable(&target, project_path)?;
+    if target.exists() {
+        return Err(ArcadeError::Conflict { message: format!("{} already exists", target.display()) });
+    }
+    if let Some(parent) = target.parent() {
+        fs::create_dir_all(parent).map_err(|e| ArcadeError::io(parent, e))?;
+    }
+    fs::rename(skill_dir, &target).map_err(|e| ArcadeError::io(skill_dir, e))
+}
+
+/// The project's spellbooks and the one equipped last
+#[tauri::command]
+pub async fn list_spellbooks(project_path: Option<String>) -> Result<ProjectSpellbooks, String> {
+    run_blocking(move || load_spellbooks().projects.remove(&project_key(project_path.as_deref())).unwrap_or_default()).await
+}
+
+/// Create a spellbook, or replace the skills of the one with the same name
+#[tauri::command]
+#[tracing::instrument(err)]
+pub async fn save_spellbook(
+    name: String,
+    skill_ids: Vec<String>,
+    project_path: Option<String>,
+) -> Result<Spellbook, ArcadeError> {
+    run_blocking(move || {
+        let id = slug(&name);
+        if id.is_empty() {
+            return Err(ArcadeError::invalid_input(format!("Invalid spellbook name: {}", name)));
+        }
+        let known: Vec<String> = scan_skills(project_path.as_deref()).into_iter().map(|s| s.id).collect();
+        if let Some(unknown) = skill_ids.iter().find(|id| !known.contains(id)) {
+            return Err(ArcadeError::not_found("Skill", unknown.clone()));
+        }
+
+        let mut skills: Vec<String> = Vec::new();
+        for skill in skill_ids {
+            if !skills.contains(&skill) {
+                skills.push(skill);
+            }
+        }
+        let book = Spellbook { id, name: name.trim().to_string(), skills };
+
+        let mut file = load_spellbooks();
+        let project = file.projects.entry(project_key(project_path.as_deref())).or_default();
+        match project.spellbooks.iter_mut().find(|b| b.id == book.id) {
+            Some(existing) => *existing = book.clone(),
+            None => project.spellbooks.push(book.clone()),
+        }
+        save_spellbooks(&file)?;
+        Ok(book)
+    })
+    .await?
+}
+
+/// Delete a spellbook; its skills are left as they are
+#[tauri::command]
+#[tracing::instrument(err)]
+pub async fn delete_spellbook(collection_id: String, project_path: Option<String>) -> Result<(), ArcadeError> {
+    run_blocking(move || {
+        let mut file = load_spellbooks();
+        let project = file
+            .projects
+            .get_mut(&project_key(project_path.as_deref()))
+            .filter(|p| p.spellbooks.iter().any(|b| b.id == collection_id))
+            .ok_or_else(|| ArcadeError::not_found("Spellbook", collection_id.clone()))?;
+        project.spellbooks.retain(|b| b.id != collection_id);
+        if project.active.as_deref() == Some(collection_id.as_str()) {
+            project.active = None;
+        }
+        save_spellbooks(&file)
+    })
+    .await?
+}
+
+/// Enable every skill in the spellbook, and with `disable_others` put away every skill that
+/// isn't in it. A skill that can't be moved is reported and the rest still go ahead.
+#[tauri::command]
+#[tracing::instrument(err)]
+pub async fn equip_spellbook(
+    collection_id: String,
+    project_path: Option<String>,
+    disable_others: Option<bool>,
+) -> Result<EquipSpellbookResult, ArcadeError> {
+    run_blocking(move || {
+        let project_path = project_path.as_deref();
+        let mut file = load_spellbooks();
+        let project = file.projects.entry(project_key(project_path)).or_default();
+        let book = project
+            .spellbooks
+            .iter()
+            .find(|b| b.id == collection_id)
+            .cloned()
+            .ok_or_else(|| ArcadeError::not_found("Spellbook", collection_id.clone()))?;
+
+        let skills = scan_skills(project_path);
+        let mut errors: Vec<String> = book
+            .skills
+            .iter()
+            .filter(|id| !skills.iter().any(|s| &s.id == *id))
+            .map(|id| format!("Skill not found: {}", id))
+            .collect();
+        let (mut enabled, mut disabled) = (Vec::new(), Vec::new());
+        for (skill, enable) in plan_moves(&skills, &book, disable_others.unwrap_or(false)) {
+            match move_skill(Path::new(&skill.source_path), enable, project_path) {
+                Ok(()) if enable => enabled.push(skill.id.clone()),
+                Ok(()) => disabled.push(skill.id.clone()),
+                Err(e) => errors.push(format!("{}: {}", skill.id, e)),
+            }
+        }
+
+        project.active = Some(book.id);
+        save_spellbooks(&file)?;
+
+        let in_use = scan_skills(project_path).iter().filter(|s| s.enabled).map(|s| s.token_weight).sum();
+        Ok(EquipSpellbookResult { enabled, disabled, errors, context_stats: context_stats_for_tokens(in_use) })
+    })
+    .await?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{test_item, ItemSource, ItemType};
+
+    fn skill(id: &str, enabled: bool) -> InventoryItem {
+        InventoryItem { item_type: ItemType::Spell, source: ItemSource::Skill, token_weight: 1000, enabled, ..test_item(id) }
+    }
+
+    #[test]
+    fn plans_only_the_needed_moves() {
+        let skills = [skill("skill_user_pdf", false), skill("skill_user_lint", true), skill("skill_project_docs", true)];
+        let book = Spellbook { id: "docs".to_string(), name: "Docs".to_string(), skills: vec!["skill_user_pdf".to_string(), "skill_project_docs".to_string()] };
+
+        let ids = |moves: Vec<(&InventoryItem, bool)>| moves.into_iter().map(|(s, enable)| (s.id.clone(), enable)).collect::<Vec<_>>();
+        assert_eq!(ids(plan_moves(&skills, &book, false)), vec![("skill_user_pdf".to_string(), true)]);
+        assert_eq!(ids(plan_moves(&skills, &book, true)), vec![
+            ("skill_user_pdf".to_string(), true),
+            ("skill_user_lint".to_string(), false),
+        ]);
+    }
+}
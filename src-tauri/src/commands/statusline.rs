@@ -0,0 +1,203 @@
+//! Arcade stats in Claude Code's own status line
+//! The installed script only prints arcade_statusline.txt, which the app rewrites whenever
+//! analytics or the equipped load change, so Claude Code never waits on a scan.
+
+use chrono::{Duration, Local, NaiveDate};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::path::PathBuf;
+
+use super::analytics::AnalyticsData;
+use super::equipment::calculate_context_stats;
+use crate::blocking::run_blocking;
+use crate::error::ArcadeError;
+use crate::scanner::settings::{read_settings_full, update_settings_field};
+use crate::types::ContextStats;
+
+const SCRIPT_NAME: &str = "arcade_statusline.sh";
+
+/// Cells in the context load bar
+const BAR_WIDTH: usize = 10;
+
+/// What the status line shows, kept so each source can update its part
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct StatuslineData {
+    pub level: u32,
+    pub streak_days: u32,
+    pub load_percentage: f64,
+    /// healthy, heavy or dumbzone
+    pub status: String,
+}
+
+/// Result of `install_arcade_statusline`
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StatuslineInstall {
+    pub script_path: String,
+    pub command: String,
+    /// The line as Claude Code will show it now
+    pub preview: String,
+}
+
+fn script_path() -> Option<PathBuf> {
+    crate::paths::claude_dir().map(|d| d.join(SCRIPT_NAME))
+}
+
+fn data_path() -> Option<PathBuf> {
+    crate::paths::claude_dir().map(|d| d.join("arcade_statusline.json"))
+}
+
+fn line_path() -> Option<PathBuf> {
+    crate::paths::claude_dir().map(|d| d.join("arcade_statusline.txt"))
+}
+
+/// One level per sqrt step of activity: 10 messages or tool calls reach level 2, 40 level 3, 90 level 4
+fn level(analytics: &AnalyticsData) -> u32 {
+    let xp: u32 = analytics.daily_usage.iter().map(|d| d.messages + d.tools_used).sum();
+    ((xp / 10) as f64).sqrt() as u32 + 1
+}
+
+/// Consecutive days with activity, ending today (or yesterday, while today can still extend it)
+fn streak_days(analytics: &AnalyticsData, today: NaiveDate) -> u32 {
+    let active = |day: NaiveDate| {
+        let date = day.format("%Y-%m-%d").to_string();
+        analytics.daily_usage.iter().any(|d| d.date == date && (d.sessions > 0 || d.messages > 0))
+    };
+    let mut day = if active(today) { today } else { today - Duration::days(1) };
+    let mut streak = 0;
+    while active(day) {
+        streak += 1;
+        day -= Duration::days(1);
+    }
+    streak
+}
+
+fn render(data: &StatuslineData) -> String {
+    let filled = ((data.load_percentage * BAR_WIDTH as f64).round() as usize).min(BAR_WIDTH);
+    let bar = format!("{}{}", "█".repeat(filled), "░".repeat(BAR_WIDTH - filled));
+    let mut line = format!("⚔ Lv {} · [{}] {:.0}% {}", data.level, bar, data.load_percentage * 100.0, data.status);
+    if data.streak_days > 0 {
+        line.push_str(&format!(" · 🔥 {}-day streak", data.streak_days));
+    }
+    line
+}
+
+/// Update the status line's data and rendered line. Does nothing unless the status line is installed.
+fn update(f: impl FnOnce(&mut StatuslineData)) -> Result<String, String> {
+    let (Some(data_path), Some(line_path)) = (data_path(), line_path()) else {
+        return Err("Could not find home directory".to_string());
+    };
+    let mut data: StatuslineData = crate::store::STATUSLINE.load(&data_path).unwrap_or_default();
+    f(&mut data);
+
+    let line = render(&data);
+    let content = crate::store::STATUSLINE.to_json(&data)?;
+    crate::paths::atomic_write(&data_path, content).map_err(|e| format!("Failed to write {}: {}", data_path.display(), e))?;
+    crate::paths::atomic_write(&line_path, &line).map_err(|e| format!("Failed to write {}: {}", line_path.display(), e))?;
+    Ok(line)
+}
+
+fn installed() -> bool {
+    script_path().is_some_and(|p| p.exists())
+}
+
+/// Refresh level and streak after analytics change
+pub(crate) fn refresh_statusline_analytics(analytics: &AnalyticsData) {
+    if !installed() {
+        return;
+    }
+    if let Err(e) = update(|data| {
+        data.level = level(analytics);
+        data.streak_days = streak_days(analytics, Local::now().date_naive());
+    }) {
+        tracing::warn!("Failed to refresh the status line: {}", e);
+    }
+}
+
+/// Refresh the load bar after items are equipped or unequipped
+pub(crate) fn refresh_statusline_load(stats: &ContextStats) {
+    if !installed() {
+        return;
+    }
+    if let Err(e) = update(|data| {
+        data.load_percentage = stats.load_percentage;
+        data.status = stats.status.clone();
+    }) {
+        tracing::warn!("Failed to refresh the status line: {}", e);
+    }
+}
+
+fn script(line_path: &std::path::Path) -> String {
+    format!(
+        "#!/bin/sh\n# Installed by ClaudeArcade; prints the stats it keeps up to date.\n\
+         # Claude Code sends session details on stdin, which this line doesn't need.\n\
+         cat > /dev/null\ncat \"{}\" 2>/dev/null\n",
+        line_path.to_string_lossy().replace('\\', "/")
+    )
+}
+
+/// Write the arcade status line script and point settings.json's `statusLine` at it.
+/// A status line the user set up themselves is only replaced when `replace` is true.
+#[tauri::command]
+#[tracing::instrument(err)]
+pub async fn install_arcade_statusline(replace: Option<bool>) -> Result<StatuslineInstall, ArcadeError> {
+    run_blocking(move || {
+        let (Some(script_path), Some(line_path)) = (script_path(), line_path()) else {
+            return Err(ArcadeError::Other { message: "Could not find home directory".to_string() });
+        };
+        let command = format!("sh \"{}\"", script_path.to_string_lossy().replace('\\', "/"));
+
+        let existing = read_settings_full()?.status_line.and_then(|s| s.command);
+        if let Some(existing) = existing.filter(|c| *c != command) {
+            if !replace.unwrap_or(false) {
+                return Err(ArcadeError::Conflict {
+                    message: format!("A status line is already set up (\"{}\"). Replace it to install the arcade one.", existing),
+                });
+            }
+        }
+
+        let content = script(&line_path);
+        crate::watcher::write_expected(&script_path, content).map_err(|e| ArcadeError::io(&script_path, e))?;
+
+        // Fill in the data before Claude Code first runs the script
+        let analytics = super::analytics::load_analytics();
+        let stats = calculate_context_stats();
+        let preview = update(|data| {
+            data.level = level(&analytics);
+            data.streak_days = streak_days(&analytics, Local::now().date_naive());
+            data.load_percentage = stats.load_percentage;
+            data.status = stats.status.clone();
+        })?;
+
+        update_settings_field("statusLine", json!({ "type": "command", "command": command, "padding": 0 }))?;
+
+        Ok(StatuslineInstall { script_path: script_path.to_string_lossy().to_string(), command, preview })
+    })
+    .await?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::analytics::DailyUsage;
+
+    #[test]
+    fn renders_level_load_and_streak() {
+        let day = |date: &str, messages: u32| DailyUsage { date: date.to_string(), messages, sessions: 1, ..Default::default() };
+        let analytics = AnalyticsData {
+            daily_usage: vec![day("2026-03-01", 30), day("2026-03-03", 5), day("2026-03-04", 5)],
+            current_session: None,
+            hook_sessions: Default::default(),
+        };
+        let today = NaiveDate::from_ymd_opt(2026, 3, 5).unwrap();
+
+        let data = StatuslineData {
+            level: level(&analytics),
+            streak_days: streak_days(&analytics, today),
+            load_percentage: 0.3,
+            status: "heavy".to_string(),
+        };
+        assert_eq!(render(&data), "⚔ Lv 3 · [███░░░░░░░] 30% heavy · 🔥 2-day streak");
+    }
+}
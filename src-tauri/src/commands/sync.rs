@@ -0,0 +1,353 @@
+//! Opt-in git sync of the config ClaudeArcade manages (commands, skills, agents, CLAUDE.md)
+//! A clone in ~/.claude/arcade-sync mirrors those files; merges happen there, never in ~/.claude itself.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+use super::plugins::run_git;
+use crate::scanner::plugin::claude_config_dir;
+use crate::blocking::run_blocking;
+use crate::trash::move_to_trash;
+
+/// Folders and files under ~/.claude that are synced
+const SYNCED_PATHS: [&str; 4] = ["CLAUDE.md", "commands", "skills", "agents"];
+
+/// Sync settings, kept in ~/.claude/arcade_sync.json
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncConfig {
+    pub repo_url: String,
+    pub branch: String,
+    /// RFC 3339
+    pub last_push: Option<String>,
+    pub last_pull: Option<String>,
+}
+
+/// Whether sync is set up and what is waiting to go out
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncStatus {
+    pub enabled: bool,
+    pub config: Option<SyncConfig>,
+    /// Synced files changed locally since the last push, relative to ~/.claude
+    pub local_changes: Vec<String>,
+}
+
+/// Outcome of a push or pull
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncResult {
+    /// Local changes were committed to the sync repo
+    pub committed: bool,
+    pub pushed: bool,
+    /// Files written to or removed from ~/.claude by the merge
+    pub updated_files: Vec<String>,
+    /// Files both sides changed; nothing was merged or written when non-empty
+    pub conflicts: Vec<String>,
+    pub message: String,
+}
+
+fn sync_config_path() -> Option<PathBuf> {
+    claude_config_dir().map(|d| d.join("arcade_sync.json"))
+}
+
+fn sync_repo_dir() -> Option<PathBuf> {
+    claude_config_dir().map(|d| d.join("arcade-sync"))
+}
+
+fn load_sync_config() -> Option<SyncConfig> {
+    crate::store::SYNC.load(&sync_config_path()?)
+}
+
+fn save_sync_config(config: &SyncConfig) -> Result<(), String> {
+    let path = sync_config_path().ok_or("Could not find home directory")?;
+    let content = crate::store::SYNC.to_json(config)?;
+    crate::paths::atomic_write(&path, content).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+/// Config and clone, or an error when sync hasn't been set up
+fn sync_setup() -> Result<(SyncConfig, PathBuf, PathBuf), String> {
+    let config = load_sync_config().ok_or("Sync is not set up; run sync_init first")?;
+    let repo = sync_repo_dir().ok_or("Could not find home directory")?;
+    let claude_dir = claude_config_dir().ok_or("Could not find home directory")?;
+    if !repo.join(".git").is_dir() {
+        return Err("Sync repository is missing; run sync_init again".to_string());
+    }
+    Ok((config, repo, claude_dir))
+}
+
+/// Synced files under a root, relative to it
+fn synced_files(root: &Path) -> BTreeSet<String> {
+    let mut files = BTreeSet::new();
+    for synced in SYNCED_PATHS {
+        for entry in WalkDir::new(root.join(synced))
+            .into_iter()
+            .filter_entry(|e| e.file_name() != ".git")
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_type().is_file())
+        {
+            if let Ok(relative) = entry.path().strip_prefix(root) {
+                files.insert(relative.to_string_lossy().replace('\\', "/"));
+            }
+        }
+    }
+    files
+}
+
+/// Make `to` hold exactly the synced files of `from`, returning the paths that changed.
+/// Files it no longer holds go to the trash.
+fn mirror(from: &Path, to: &Path, announce_writes: bool) -> Result<Vec<String>, String> {
+    let source = synced_files(from);
+    let target = synced_files(to);
+    let mut changed = Vec::new();
+
+    for relative in target.difference(&source) {
+        move_to_trash(&to.join(relative), "sync").map_err(|e| format!("Failed to remove {}: {}", relative, e))?;
+        changed.push(relative.clone());
+    }
+    for relative in &source {
+        let content = fs::read(from.join(relative)).map_err(|e| format!("Failed to read {}: {}", relative, e))?;
+        let destination = to.join(relative);
+        if fs::read(&destination).is_ok_and(|existing| existing == content) {
+            continue;
+        }
+        if let Some(parent) = destination.parent() {
+            fs::create_dir_all(parent).map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+        }
+        let written = if announce_writes {
+            crate::watcher::write_expected(&destination, content)
+        } else {
+            crate::paths::atomic_write(&destination, content)
+        };
+        written.map_err(|e| format!("Failed to write {}: {}", relative, e))?;
+        changed.push(relative.clone());
+    }
+
+    Ok(changed)
+}
+
+/// Identity flags for commits and merges, since both fail on machines that never set one
+fn identity_args(repo: &Path) -> Vec<&'static str> {
+    if run_git(Some(repo), &["config", "user.email"]).is_ok_and(|e| !e.is_empty()) {
+        Vec::new()
+    } else {
+        vec!["-c", "user.name=ClaudeArcade", "-c", "user.email=sync@claudearcade.local"]
+    }
+}
+
+/// First sync on this machine: the clone holds what other machines pushed, not what this one
+/// last synced, so nothing missing locally is a deletion. Local-only files are added; local
+/// files that differ from the repo are backed up to ~/.claude/arcade-sync-backup and the repo
+/// version wins.
+fn adopt_local(claude_dir: &Path, repo: &Path) -> Result<(), String> {
+    let synced = synced_files(repo);
+    let backup_dir = claude_dir.join("arcade-sync-backup");
+    for relative in synced_files(claude_dir) {
+        let content = fs::read(claude_dir.join(&relative)).map_err(|e| format!("Failed to read {}: {}", relative, e))?;
+        let destination = if !synced.contains(&relative) {
+            repo.join(&relative)
+        } else if fs::read(repo.join(&relative)).is_ok_and(|existing| existing != content) {
+            backup_dir.join(&relative)
+        } else {
+            continue;
+        };
+        if let Some(parent) = destination.parent() {
+            fs::create_dir_all(parent).map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+        }
+        crate::paths::atomic_write(&destination, content).map_err(|e| format!("Failed to write {}: {}", destination.display(), e))?;
+    }
+    Ok(())
+}
+
+/// Copy local config into the clone and commit it if anything changed
+fn commit_local(repo: &Path, claude_dir: &Path, first_sync: bool) -> Result<bool, String> {
+    if first_sync {
+        adopt_local(claude_dir, repo)?;
+    } else {
+        mirror(claude_dir, repo, false)?;
+    }
+    run_git(Some(repo), &["add", "-A"])?;
+    if run_git(Some(repo), &["diff", "--cached", "--quiet"]).is_ok() {
+        return Ok(false);
+    }
+    let host = std::env::var("HOSTNAME").or_else(|_| std::env::var("COMPUTERNAME")).unwrap_or_else(|_| "this machine".to_string());
+    let message = format!("Sync Claude config from {}", host);
+    let mut args = identity_args(repo);
+    args.extend(["commit", "-q", "-m", message.as_str()]);
+    run_git(Some(repo), &args)?;
+    Ok(true)
+}
+
+/// Whether the remote has the branch yet (it doesn't until the first push to an empty repo)
+fn remote_has_branch(repo: &Path, branch: &str) -> bool {
+    run_git(Some(repo), &["ls-remote", "--heads", "origin", branch]).is_ok_and(|o| !o.is_empty())
+}
+
+/// Files git still reports as unmerged in the clone
+fn unmerged_files(repo: &Path) -> Vec<String> {
+    run_git(Some(repo), &["diff", "--name-only", "--diff-filter=U"])
+        .unwrap_or_default()
+        .lines()
+        .map(String::from)
+        .collect()
+}
+
+/// Merge the remote branch into the clone. On conflicts the merge is left in progress, with
+/// conflict markers in the clone only, and the conflicting files are returned.
+fn merge_remote(repo: &Path, branch: &str) -> Result<Vec<String>, String> {
+    if !remote_has_branch(repo, branch) {
+        return Ok(Vec::new());
+    }
+    run_git(Some(repo), &["fetch", "-q", "origin", branch])?;
+    let remote_ref = format!("origin/{}", branch);
+    if run_git(Some(repo), &["rev-parse", "--verify", "-q", "HEAD"]).is_err() {
+        // Fresh clone of an empty repo that has since been pushed to
+        run_git(Some(repo), &["reset", "-q", "--hard", &remote_ref])?;
+        return Ok(Vec::new());
+    }
+
+    let mut args = identity_args(repo);
+    args.extend(["merge", "-q", "--no-edit", remote_ref.as_str()]);
+    if run_git(Some(repo), &args).is_ok() {
+        return Ok(Vec::new());
+    }
+
+    let conflicts = unmerged_files(repo);
+    if conflicts.is_empty() {
+        let _ = run_git(Some(repo), &["merge", "--abort"]);
+        return Err("Merge with the sync repository failed".to_string());
+    }
+    Ok(conflicts)
+}
+
+/// Commit local changes, merge the remote and write the result back to ~/.claude.
+/// A merge left open by an earlier conflict is finished once its files are resolved.
+fn sync(push: bool) -> Result<SyncResult, String> {
+    let (mut config, repo, claude_dir) = sync_setup()?;
+    let mut result = SyncResult::default();
+
+    if repo.join(".git").join("MERGE_HEAD").exists() {
+        result.conflicts = unmerged_files(&repo);
+        if result.conflicts.is_empty() {
+            let mut args = identity_args(&repo);
+            args.extend(["commit", "-q", "--no-edit"]);
+            run_git(Some(&repo), &args)?;
+        }
+    } else {
+        result.committed = commit_local(&repo, &claude_dir, config.last_pull.is_none())?;
+        result.conflicts = merge_remote(&repo, &config.branch)?;
+    }
+    if !result.conflicts.is_empty() {
+        result.message = format!(
+            "{} file(s) changed both here and in the sync repository; fix the conflict markers in {}, `git add` them and sync again",
+            result.conflicts.len(),
+            repo.display()
+        );
+        return Ok(result);
+    }
+
+    result.updated_files = mirror(&repo, &claude_dir, true)?;
+    let now = chrono::Utc::now().to_rfc3339();
+    config.last_pull = Some(now.clone());
+
+    let has_commits = run_git(Some(&repo), &["rev-parse", "--verify", "-q", "HEAD"]).is_ok();
+    if push && has_commits {
+        run_git(Some(&repo), &["push", "-q", "-u", "origin", &format!("HEAD:{}", config.branch)])?;
+        result.pushed = true;
+        config.last_push = Some(now);
+    }
+    save_sync_config(&config)?;
+
+    result.message = match (result.pushed, result.updated_files.len()) {
+        (true, 0) => "Pushed; nothing new from other machines".to_string(),
+        (true, n) => format!("Pushed; {} file(s) updated from other machines", n),
+        (false, 0) => "Already up to date".to_string(),
+        (false, n) => format!("{} file(s) updated from other machines", n),
+    };
+    Ok(result)
+}
+
+/// Set up sync with a git repository (cloned into ~/.claude/arcade-sync). Nothing in
+/// ~/.claude changes until the first push or pull.
+#[tauri::command]
+#[tracing::instrument(skip(repo_url), err)]
+pub async fn sync_init(repo_url: String, branch: Option<String>) -> Result<SyncStatus, String> {
+    run_blocking(move || {
+        let repo_url = repo_url.trim().to_string();
+        if repo_url.is_empty() || repo_url.starts_with('-') {
+            return Err("Enter a git repository URL".to_string());
+        }
+        let branch = branch.filter(|b| !b.trim().is_empty()).unwrap_or_else(|| "main".to_string());
+        let repo = sync_repo_dir().ok_or("Could not find home directory")?;
+
+        let existing_remote = run_git(Some(&repo), &["remote", "get-url", "origin"]).ok();
+        if existing_remote.as_deref() != Some(repo_url.as_str()) {
+            if repo.exists() {
+                move_to_trash(&repo, "sync").map_err(|e| format!("Failed to replace {}: {}", repo.display(), e))?;
+            }
+            let target = repo.to_string_lossy().to_string();
+            run_git(None, &["clone", "-q", "--", &repo_url, &target])
+                .map_err(|e| format!("Failed to clone {}: {}", repo_url, e))?;
+        }
+        if remote_has_branch(&repo, &branch) {
+            run_git(Some(&repo), &["checkout", "-q", &branch])?;
+        } else {
+            // Empty repository, or a branch that doesn't exist yet
+            let _ = run_git(Some(&repo), &["checkout", "-q", "-b", &branch]);
+        }
+
+        // Sync history only carries over when the repository is unchanged
+        let previous = load_sync_config().filter(|c| c.repo_url == repo_url && c.branch == branch).unwrap_or_default();
+        save_sync_config(&SyncConfig { repo_url, branch, last_push: previous.last_push, last_pull: previous.last_pull })?;
+        sync_status()
+    })
+    .await?
+}
+
+/// Commit local commands, skills, agents and CLAUDE.md, merge what other machines pushed,
+/// and push the result
+#[tauri::command]
+#[tracing::instrument(err)]
+pub async fn sync_push() -> Result<SyncResult, String> {
+    run_blocking(move || sync(true)).await?
+}
+
+/// Merge what other machines pushed into ~/.claude. Local changes are committed to the
+/// sync clone first so a conflict can't lose them.
+#[tauri::command]
+#[tracing::instrument(err)]
+pub async fn sync_pull() -> Result<SyncResult, String> {
+    run_blocking(move || sync(false)).await?
+}
+
+/// Sync setup and the synced files changed since the last sync
+fn sync_status() -> Result<SyncStatus, String> {
+    let Ok((config, repo, claude_dir)) = sync_setup() else {
+        return Ok(SyncStatus { enabled: false, config: load_sync_config(), local_changes: Vec::new() });
+    };
+
+    let local = synced_files(&claude_dir);
+    let synced = synced_files(&repo);
+    // Before the first sync, files only in the repo are incoming, not local deletions
+    let mut local_changes: Vec<String> = match config.last_pull {
+        Some(_) => synced.difference(&local).cloned().collect(),
+        None => Vec::new(),
+    };
+    for relative in &local {
+        if fs::read(claude_dir.join(relative)).ok() != fs::read(repo.join(relative)).ok() {
+            local_changes.push(relative.clone());
+        }
+    }
+    local_changes.sort();
+
+    Ok(SyncStatus { enabled: true, config: Some(config), local_changes })
+}
+
+/// Sync setup and the synced files changed since the last sync
+#[tauri::command]
+pub async fn get_sync_status() -> Result<SyncStatus, String> {
+    run_blocking(sync_status).await?
+}
@@ -0,0 +1,256 @@
+//! Project config templates: save a project's Claude setup under a name and apply it elsewhere
+//! Templates live in ~/.claude/arcade_templates/<name>/ with a template.json manifest.
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+use crate::scanner::plugin::claude_config_dir;
+use crate::scanner::settings::{parse_settings_str, write_settings_at};
+use crate::blocking::run_blocking;
+use crate::trash::move_to_trash;
+
+/// Manifest stored alongside a template's files
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectTemplate {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    /// Project the template was saved from
+    #[serde(default)]
+    pub source_project: String,
+    pub created_at: String,
+    /// Files in the template, relative to the project root
+    pub files: Vec<String>,
+}
+
+/// Outcome of applying a template to a project
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApplyTemplateResult {
+    pub created: Vec<String>,
+    pub overwritten: Vec<String>,
+    pub skipped: Vec<String>,
+}
+
+const MANIFEST_FILE: &str = "template.json";
+
+/// Personal files that stay out of shared templates
+const PERSONAL_FILES: [&str; 2] = [".claude/settings.local.json", "CLAUDE.local.md"];
+
+/// Settings files, written like every other settings writer: backed up, then checked after writing
+const SETTINGS_FILES: [&str; 2] = [".claude/settings.json", ".mcp.json"];
+
+fn templates_dir() -> Result<PathBuf, String> {
+    claude_config_dir()
+        .map(|d| d.join("arcade_templates"))
+        .ok_or_else(|| "Could not find home directory".to_string())
+}
+
+fn validate_template_name(name: &str) -> Result<(), String> {
+    if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') {
+        return Err("Template name may only contain letters, numbers, '-' and '_'".to_string());
+    }
+    Ok(())
+}
+
+/// Relative paths of the Claude config files in a project
+fn collect_project_files(root: &Path) -> Vec<String> {
+    let mut files = Vec::new();
+
+    for name in ["CLAUDE.md", ".mcp.json"] {
+        if root.join(name).is_file() {
+            files.push(name.to_string());
+        }
+    }
+
+    let claude_dir = root.join(".claude");
+    for entry in WalkDir::new(&claude_dir).into_iter().filter_map(|e| e.ok()) {
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        if let Ok(relative) = entry.path().strip_prefix(root) {
+            let relative = relative.to_string_lossy().replace('\\', "/");
+            if !PERSONAL_FILES.contains(&relative.as_str()) && !relative.ends_with(".tmp") {
+                files.push(relative);
+            }
+        }
+    }
+
+    files
+}
+
+/// Save a project's `.claude/` folder, CLAUDE.md and .mcp.json as a named template
+#[tauri::command]
+pub async fn save_project_template(
+    project_path: String,
+    name: String,
+    description: Option<String>,
+) -> Result<ProjectTemplate, String> {
+    run_blocking(move || {
+        validate_template_name(&name)?;
+
+        let root = Path::new(&project_path);
+        let files = collect_project_files(root);
+        if files.is_empty() {
+            return Err("Project has no Claude configuration to save".to_string());
+        }
+
+        // Replace any previous template of the same name; the old one goes to the trash
+        let target = templates_dir()?.join(&name);
+        if target.exists() {
+            move_to_trash(&target, "template").map_err(|e| format!("Failed to replace template: {}", e))?;
+        }
+
+        for file in &files {
+            let destination = target.join("files").join(file);
+            if let Some(parent) = destination.parent() {
+                fs::create_dir_all(parent).map_err(|e| format!("Failed to create template folder: {}", e))?;
+            }
+            fs::copy(root.join(file), &destination).map_err(|e| format!("Failed to copy {}: {}", file, e))?;
+        }
+
+        let template = ProjectTemplate {
+            name,
+            description: description.unwrap_or_default(),
+            source_project: project_path,
+            created_at: chrono::Utc::now().to_rfc3339(),
+            files,
+        };
+        let manifest = serde_json::to_string_pretty(&template).map_err(|e| e.to_string())?;
+        crate::paths::atomic_write(target.join(MANIFEST_FILE), manifest).map_err(|e| format!("Failed to write template manifest: {}", e))?;
+
+        Ok(template)
+    })
+    .await?
+}
+
+/// List saved project templates
+#[tauri::command]
+pub async fn list_project_templates() -> Result<Vec<ProjectTemplate>, String> {
+    run_blocking(move || {
+        let dir = templates_dir()?;
+        let entries = match fs::read_dir(&dir) {
+            Ok(e) => e,
+            Err(_) => return Ok(Vec::new()),
+        };
+
+        let mut templates: Vec<ProjectTemplate> = entries
+            .filter_map(|e| e.ok())
+            .filter_map(|e| fs::read_to_string(e.path().join(MANIFEST_FILE)).ok())
+            .filter_map(|content| serde_json::from_str(&content).ok())
+            .collect();
+        templates.sort_by(|a, b| a.name.cmp(&b.name));
+
+        Ok(templates)
+    })
+    .await?
+}
+
+/// Delete a saved project template
+#[tauri::command]
+pub async fn delete_project_template(name: String) -> Result<(), String> {
+    run_blocking(move || {
+        validate_template_name(&name)?;
+        let target = templates_dir()?.join(&name);
+        if !target.exists() {
+            return Err(format!("Template not found: {}", name));
+        }
+        move_to_trash(&target, "template").map(|_| ()).map_err(|e| format!("Failed to delete template: {}", e))
+    })
+    .await?
+}
+
+/// Whether a manifest path could land outside the project. `..` is looked for between either
+/// separator, since a Windows manifest path may use backslashes.
+fn escapes_project(file: &str) -> bool {
+    file.split(['/', '\\']).any(|part| part == "..")
+        || Path::new(file).components().any(|c| !matches!(c, std::path::Component::Normal(_)))
+}
+
+/// Copy a saved template into a project. Existing files are kept unless `overwrite` is set; then
+/// settings files are backed up before being replaced and other files go to the trash.
+#[tauri::command]
+pub async fn apply_project_template(
+    name: String,
+    project_path: String,
+    overwrite: Option<bool>,
+) -> Result<ApplyTemplateResult, String> {
+    run_blocking(move || {
+        validate_template_name(&name)?;
+        let overwrite = overwrite.unwrap_or(false);
+
+        let template_dir = templates_dir()?.join(&name);
+        let manifest = fs::read_to_string(template_dir.join(MANIFEST_FILE))
+            .map_err(|_| format!("Template not found: {}", name))?;
+        let template: ProjectTemplate = serde_json::from_str(&manifest)
+            .map_err(|e| format!("Invalid template manifest: {}", e))?;
+
+        let root = Path::new(&project_path);
+        if !root.is_dir() {
+            return Err(format!("Project path does not exist: {}", project_path));
+        }
+
+        let mut result = ApplyTemplateResult {
+            created: Vec::new(),
+            overwritten: Vec::new(),
+            skipped: Vec::new(),
+        };
+
+        for file in &template.files {
+            // Manifests are user-editable; never write outside the project
+            if escapes_project(file) {
+                result.skipped.push(file.clone());
+                continue;
+            }
+
+            let source = template_dir.join("files").join(file);
+            let destination = root.join(file);
+            let existed = destination.exists();
+            if existed && !overwrite {
+                result.skipped.push(file.clone());
+                continue;
+            }
+
+            let content = fs::read(&source).map_err(|e| format!("Failed to read template file {}: {}", file, e))?;
+            if SETTINGS_FILES.contains(&file.as_str()) {
+                let settings = parse_settings_str(&String::from_utf8_lossy(&content))
+                    .map_err(|e| format!("Template file {} is not valid JSON: {}", file, e))?;
+                write_settings_at(&destination, &settings).map_err(|e| format!("Failed to write {}: {}", file, e))?;
+            } else {
+                if existed {
+                    move_to_trash(&destination, "template").map_err(|e| format!("Failed to replace {}: {}", file, e))?;
+                }
+                if let Some(parent) = destination.parent() {
+                    fs::create_dir_all(parent).map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+                }
+                crate::watcher::write_expected(&destination, content).map_err(|e| format!("Failed to write {}: {}", file, e))?;
+            }
+
+            if existed {
+                result.overwritten.push(file.clone());
+            } else {
+                result.created.push(file.clone());
+            }
+        }
+
+        Ok(result)
+    })
+    .await?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn refuses_paths_outside_the_project() {
+        assert!(!escapes_project(".claude/settings.json"));
+        assert!(escapes_project("../outside"));
+        assert!(escapes_project("..\\..\\outside"));
+        assert!(escapes_project("docs/..\\..\\outside"));
+        assert!(escapes_project("/etc/passwd"));
+    }
+}
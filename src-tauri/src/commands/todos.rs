@@ -0,0 +1,33 @@
+//! Claude's task lists for the quest log
+//! Lists change whenever Claude calls TodoWrite; the session tailer reports those calls as `toolCall` events.
+
+use crate::scanner::todos::{session_todo_lists, TodoList};
+use crate::scanner::transcripts::{is_valid_session_id, list_transcript_files, modified_time};
+use crate::blocking::run_blocking;
+
+/// Todo lists of a session: the main conversation's first, then its subagents'
+#[tauri::command]
+pub async fn get_session_todos(session_id: String) -> Result<Vec<TodoList>, String> {
+    run_blocking(move || {
+        if !is_valid_session_id(&session_id) {
+            return Err(format!("Invalid session id: {}", session_id));
+        }
+        Ok(session_todo_lists(&session_id))
+    })
+    .await?
+}
+
+/// Todo lists of the project's most recent session
+#[tauri::command]
+pub async fn get_active_todos(project_path: String) -> Result<Vec<TodoList>, String> {
+    run_blocking(move || {
+        let newest = list_transcript_files(Some(&project_path))
+            .into_iter()
+            .max_by_key(|f| modified_time(f));
+        let Some(session_id) = newest.and_then(|f| f.file_stem().map(|s| s.to_string_lossy().to_string())) else {
+            return Ok(Vec::new());
+        };
+        Ok(session_todo_lists(&session_id))
+    })
+    .await?
+}
@@ -0,0 +1,23 @@
+use crate::blocking::run_blocking;
+use crate::error::ArcadeError;
+use crate::trash::{empty_trash_entries, list_trash_entries, restore_entry, TrashEntry};
+
+/// Deleted agents, skills, commands, output styles and templates, most recent first
+#[tauri::command]
+pub async fn list_trash() -> Result<Vec<TrashEntry>, String> {
+    run_blocking(list_trash_entries).await
+}
+
+/// Move a deleted item back to where it was
+#[tauri::command]
+#[tracing::instrument(err)]
+pub async fn restore_from_trash(id: String) -> Result<TrashEntry, ArcadeError> {
+    run_blocking(move || restore_entry(&id)).await?
+}
+
+/// Delete trashed items for good, all of them or those older than `older_than_days`; returns how many
+#[tauri::command]
+#[tracing::instrument(err)]
+pub async fn empty_trash(older_than_days: Option<u32>) -> Result<usize, ArcadeError> {
+    run_blocking(move || empty_trash_entries(older_than_days)).await?
+}
@@ -0,0 +1,283 @@
+//! Subscription usage windows: the rolling 5 hours Claude plans meter messages over
+//! A window opens at the hour of the first message after the previous one closed and lasts
+//! five hours. Consumption comes from transcripts, topped up with the app's own session tracking.
+
+use chrono::{DateTime, Duration, DurationRound, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::sync::Mutex;
+use std::time::SystemTime;
+use tauri::{AppHandle, Emitter, State};
+
+use super::analytics::load_analytics;
+use crate::arcade_settings::{load_arcade_settings, save_arcade_settings};
+use crate::blocking::run_blocking;
+use crate::scanner::transcripts::{
+    entry_message_id, entry_timestamp, entry_type, entry_usage, list_transcript_files, modified_time, prompt_text,
+    read_transcript_entries,
+};
+
+const WINDOW_HOURS: i64 = 5;
+
+/// Closed windows this far back set the token estimate when no token limit is configured
+const HISTORY_DAYS: i64 = 7;
+
+/// Share of a limit at which `usage-window-warning` is emitted
+const WARN_AT: f64 = 0.8;
+
+/// What the user's plan allows per window. Anthropic publishes message counts per plan as
+/// approximations, so both limits can be set by hand.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct UsageLimits {
+    /// pro, max5 or max20; picks a message limit when none is set
+    pub plan: Option<String>,
+    pub message_limit: Option<u32>,
+    /// None estimates it from the busiest recent window
+    pub token_limit: Option<u64>,
+}
+
+impl UsageLimits {
+    fn messages(&self) -> Option<u32> {
+        self.message_limit.or(match self.plan.as_deref()? {
+            "pro" => Some(45),
+            "max5" => Some(225),
+            "max20" => Some(900),
+            _ => None,
+        })
+    }
+}
+
+/// The current window's consumption and what's left of it
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UsageWindowStatus {
+    /// False when nothing was sent in the last five hours; the counts are then zero
+    pub active: bool,
+    /// RFC3339
+    pub window_start: Option<String>,
+    pub window_end: Option<String>,
+    pub minutes_remaining: u32,
+    pub messages: u32,
+    pub tokens: u64,
+    pub message_limit: Option<u32>,
+    pub token_limit: Option<u64>,
+    /// Whether `token_limit` was estimated from past windows rather than configured
+    pub token_limit_estimated: bool,
+    pub remaining_messages: Option<u32>,
+    pub remaining_tokens: Option<u64>,
+    /// Share of the tighter limit used, 0..1
+    pub used_fraction: f64,
+    pub tokens_per_minute: f64,
+    /// Tokens by the window's end at the current rate
+    pub projected_tokens: u64,
+    /// ok, approaching or exceeded
+    pub status: String,
+}
+
+/// Window the last warning was emitted for, so it is emitted once per window
+pub struct UsageWindowState(pub Mutex<Option<DateTime<Utc>>>);
+
+/// One prompt or one API response, as far as the limits are concerned
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Activity {
+    at: DateTime<Utc>,
+    tokens: u64,
+    prompt: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Window {
+    start: DateTime<Utc>,
+    last: DateTime<Utc>,
+    messages: u32,
+    tokens: u64,
+}
+
+impl Window {
+    fn end(&self) -> DateTime<Utc> {
+        self.start + Duration::hours(WINDOW_HOURS)
+    }
+}
+
+/// Group activity into windows: each opens at the hour of the first activity after the previous closed
+fn windows(mut activity: Vec<Activity>) -> Vec<Window> {
+    activity.sort_by_key(|a| a.at);
+    let mut windows: Vec<Window> = Vec::new();
+    for a in activity {
+        let open = windows.last_mut().filter(|w| a.at < w.end());
+        let window = match open {
+            Some(window) => window,
+            None => {
+                let start = a.at.duration_trunc(Duration::hours(1)).unwrap_or(a.at);
+                windows.push(Window { start, last: a.at, messages: 0, tokens: 0 });
+                windows.last_mut().expect("just pushed")
+            }
+        };
+        window.last = a.at;
+        window.tokens += a.tokens;
+        window.messages += u32::from(a.prompt);
+    }
+    windows
+}
+
+/// Prompts and responses from transcripts written in the last `HISTORY_DAYS`
+fn recent_activity(now: DateTime<Utc>) -> Vec<Activity> {
+    let since = now - Duration::days(HISTORY_DAYS);
+    let mut seen = HashSet::new();
+    let mut activity = Vec::new();
+    for path in list_transcript_files(None) {
+        let recent = modified_time(&path).map(DateTime::<Utc>::from).is_some_and(|t| t >= since);
+        if !recent {
+            continue;
+        }
+        for entry in read_transcript_entries(&path) {
+            let Some(at) = entry_timestamp(&entry).filter(|t| *t >= since) else {
+                continue;
+            };
+            if prompt_text(&entry).is_some() {
+                activity.push(Activity { at, tokens: 0, prompt: true });
+            } else if entry_type(&entry) == "assistant" {
+                // A response is written once per content block with the same id and usage
+                if entry_message_id(&entry).is_some_and(|id| !seen.insert(id.to_string())) {
+                    continue;
+                }
+                if let Some(usage) = entry_usage(&entry) {
+                    let tokens = usage.input_tokens + usage.output_tokens + usage.cache_creation_input_tokens;
+                    activity.push(Activity { at, tokens, prompt: false });
+                }
+            }
+        }
+    }
+    activity
+}
+
+fn window_status(windows: &[Window], limits: &UsageLimits, now: DateTime<Utc>, recorded_messages: u32) -> UsageWindowStatus {
+    let current = windows.last().filter(|w| now < w.end());
+    let past = &windows[..windows.len() - usize::from(current.is_some())];
+    let estimated = past.iter().map(|w| w.tokens).max().filter(|t| *t > 0);
+    let token_limit = limits.token_limit.or(estimated);
+    let message_limit = limits.messages();
+
+    let messages = current.map_or(0, |w| w.messages.max(recorded_messages));
+    let tokens = current.map_or(0, |w| w.tokens);
+    let elapsed = current.map_or(0, |w| (now - w.start).num_minutes().max(1));
+    let minutes_remaining = current.map_or(0, |w| (w.end() - now).num_minutes().max(0)) as u32;
+    let tokens_per_minute = if elapsed > 0 { tokens as f64 / elapsed as f64 } else { 0.0 };
+
+    let fraction = |used: f64, limit: Option<f64>| limit.filter(|l| *l > 0.0).map_or(0.0, |l| used / l);
+    let used_fraction = fraction(messages as f64, message_limit.map(f64::from))
+        .max(fraction(tokens as f64, token_limit.map(|t| t as f64)));
+    let status = if used_fraction >= 1.0 {
+        "exceeded"
+    } else if used_fraction >= WARN_AT {
+        "approaching"
+    } else {
+        "ok"
+    };
+
+    UsageWindowStatus {
+        active: current.is_some(),
+        window_start: current.map(|w| w.start.to_rfc3339()),
+        window_end: current.map(|w| w.end().to_rfc3339()),
+        minutes_remaining,
+        messages,
+        tokens,
+        message_limit,
+        token_limit,
+        token_limit_estimated: limits.token_limit.is_none() && token_limit.is_some(),
+        remaining_messages: message_limit.map(|l| l.saturating_sub(messages)),
+        remaining_tokens: token_limit.map(|l| l.saturating_sub(tokens)),
+        used_fraction,
+        tokens_per_minute,
+        projected_tokens: tokens + (tokens_per_minute * f64::from(minutes_remaining)) as u64,
+        status: status.to_string(),
+    }
+}
+
+/// Messages the app counted itself in a session started inside the window
+fn recorded_messages(window_start: Option<DateTime<Utc>>) -> u32 {
+    let Some(start) = window_start else {
+        return 0;
+    };
+    load_analytics()
+        .current_session
+        .filter(|s| DateTime::<Utc>::from_timestamp(s.start_time, 0).is_some_and(|t| t >= start))
+        .map_or(0, |s| s.messages)
+}
+
+/// Consumption of the current 5-hour window and an estimate of what's left. Emits
+/// `usage-window-warning` (once per window) when the tighter limit is 80% used.
+#[tauri::command]
+pub async fn get_usage_window_status(
+    app_handle: AppHandle,
+    state: State<'_, UsageWindowState>,
+) -> Result<UsageWindowStatus, String> {
+    let (status, window_start) = run_blocking(|| {
+        let now = DateTime::<Utc>::from(SystemTime::now());
+        let windows = windows(recent_activity(now));
+        let window_start = windows.last().filter(|w| now < w.end()).map(|w| w.start);
+        let status = window_status(&windows, &load_arcade_settings().usage_limits, now, recorded_messages(window_start));
+        (status, window_start)
+    })
+    .await?;
+
+    if status.status != "ok" {
+        let mut warned = state.0.lock().map_err(|e| format!("Lock error: {}", e))?;
+        if *warned != window_start {
+            *warned = window_start;
+            let _ = app_handle.emit("usage-window-warning", &status);
+        }
+    }
+    Ok(status)
+}
+
+/// Set the plan or limits the window status is measured against
+#[tauri::command]
+pub async fn set_usage_limits(limits: UsageLimits) -> Result<(), String> {
+    run_blocking(move || {
+        let mut settings = load_arcade_settings();
+        settings.usage_limits = limits;
+        save_arcade_settings(&settings)
+    })
+    .await?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(hour: u32, minute: u32) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(&format!("2025-06-01T{:02}:{:02}:00Z", hour, minute)).unwrap().to_utc()
+    }
+
+    #[test]
+    fn splits_windows_and_estimates_what_is_left() {
+        let activity = vec![
+            Activity { at: at(9, 20), tokens: 0, prompt: true },
+            Activity { at: at(9, 21), tokens: 1000, prompt: false },
+            Activity { at: at(13, 59), tokens: 3000, prompt: false },
+            // 14:00 is the first window's end, so this opens the next one
+            Activity { at: at(15, 10), tokens: 0, prompt: true },
+            Activity { at: at(15, 11), tokens: 3200, prompt: false },
+        ];
+        let windows = windows(activity);
+        assert_eq!(windows.len(), 2);
+        assert_eq!(windows[0].start, at(9, 0));
+        assert_eq!(windows[0].tokens, 4000);
+        assert_eq!(windows[1].start, at(15, 0));
+
+        let limits = UsageLimits { plan: Some("pro".to_string()), ..Default::default() };
+        let status = window_status(&windows, &limits, at(16, 0), 0);
+        assert!(status.active);
+        assert_eq!(status.messages, 1);
+        assert_eq!(status.message_limit, Some(45));
+        assert_eq!(status.token_limit, Some(4000));
+        assert!(status.token_limit_estimated);
+        assert_eq!(status.remaining_tokens, Some(800));
+        assert_eq!(status.minutes_remaining, 240);
+        assert_eq!(status.status, "approaching");
+
+        assert!(!window_status(&windows, &limits, at(21, 0), 0).active);
+    }
+}
@@ -0,0 +1,31 @@
+use crate::watcher::ProjectWatchers;
+use std::sync::Mutex;
+use tauri::{AppHandle, State};
+
+pub struct ProjectWatcherState(pub Mutex<ProjectWatchers>);
+
+/// Start watching a project's Claude config (call when the project is activated)
+#[tauri::command]
+pub fn watch_project(
+    app_handle: AppHandle,
+    state: State<'_, ProjectWatcherState>,
+    project_path: String,
+) -> Result<(), String> {
+    let mut watchers = state.0.lock().map_err(|e| format!("Lock error: {}", e))?;
+    watchers.watch(app_handle, &project_path)
+}
+
+/// Stop watching a project's Claude config
+#[tauri::command]
+pub fn unwatch_project(state: State<'_, ProjectWatcherState>, project_path: String) -> Result<(), String> {
+    let mut watchers = state.0.lock().map_err(|e| format!("Lock error: {}", e))?;
+    watchers.unwatch(&project_path);
+    Ok(())
+}
+
+/// List projects with an active config watcher
+#[tauri::command]
+pub fn list_watched_projects(state: State<'_, ProjectWatcherState>) -> Result<Vec<String>, String> {
+    let watchers = state.0.lock().map_err(|e| format!("Lock error: {}", e))?;
+    Ok(watchers.watched())
+}
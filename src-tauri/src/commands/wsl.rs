@@ -0,0 +1,28 @@
+use crate::arcade_settings::{load_arcade_settings, save_arcade_settings};
+use crate::blocking::run_blocking;
+use crate::error::ArcadeError;
+use crate::wsl::{detect_environments, parse_wsl_dir, WslEnvironment};
+
+/// Claude config folders inside installed WSL distros; empty anywhere but Windows
+#[tauri::command]
+pub async fn detect_wsl_environments() -> Result<Vec<WslEnvironment>, String> {
+    run_blocking(detect_environments).await
+}
+
+/// Turn scanning a WSL config folder alongside this machine's on or off
+#[tauri::command]
+pub async fn set_wsl_scan(claude_dir: String, enabled: bool) -> Result<(), ArcadeError> {
+    if parse_wsl_dir(&claude_dir).is_none() {
+        return Err(ArcadeError::invalid_input(format!("{} is not a WSL folder", claude_dir)));
+    }
+    run_blocking(move || {
+        let mut settings = load_arcade_settings();
+        settings.wsl_config_dirs.retain(|dir| *dir != claude_dir);
+        if enabled {
+            settings.wsl_config_dirs.push(claude_dir);
+        }
+        save_arcade_settings(&settings)
+    })
+    .await??;
+    Ok(())
+}
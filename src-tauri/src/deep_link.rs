@@ -0,0 +1,130 @@
+//! `claudearcade://` links, e.g. `claudearcade://install-skill?source=repo&id=pdf`
+//! A link never installs anything by itself: it is checked here, then handed to the frontend,
+//! which asks for confirmation and runs the matching install command.
+
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Manager};
+use tauri_plugin_deep_link::DeepLinkExt;
+
+use crate::error::ArcadeError;
+use crate::paths::check_name;
+
+const SCHEME: &str = "claudearcade";
+
+/// Largest loadout or shared-item payload accepted in a link
+const MAX_PAYLOAD_LEN: usize = 64 * 1024;
+
+/// A checked link waiting for the user to confirm it
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeepLinkRequest {
+    /// install-skill, install-plugin, import-item or apply-loadout
+    pub action: String,
+    pub params: BTreeMap<String, String>,
+    pub url: String,
+}
+
+/// Links that arrived before the frontend was listening (the one that launched the app, say)
+pub struct DeepLinkState(pub Mutex<Vec<DeepLinkRequest>>);
+
+fn required<'a>(params: &'a BTreeMap<String, String>, key: &str) -> Result<&'a str, ArcadeError> {
+    params
+        .get(key)
+        .map(String::as_str)
+        .filter(|v| !v.is_empty())
+        .ok_or_else(|| ArcadeError::invalid_input(format!("Link is missing '{}'", key)))
+}
+
+/// Parse and check a link. Ids go through the same checks as the commands they end up in.
+pub fn parse_deep_link(url: &str) -> Result<DeepLinkRequest, ArcadeError> {
+    let parsed = reqwest::Url::parse(url).map_err(|e| ArcadeError::invalid_input(format!("Invalid link: {}", e)))?;
+    if parsed.scheme() != SCHEME {
+        return Err(ArcadeError::invalid_input(format!("Not a {}:// link", SCHEME)));
+    }
+    let action = parsed.host_str().unwrap_or_default().to_string();
+    let params: BTreeMap<String, String> = parsed.query_pairs().map(|(k, v)| (k.to_string(), v.to_string())).collect();
+
+    match action.as_str() {
+        "install-skill" => {
+            // Only the official skills repository can be installed from
+            let source = params.get("source").map(String::as_str).unwrap_or("repo");
+            if source != "repo" {
+                return Err(ArcadeError::invalid_input(format!("Unknown skill source '{}'", source)));
+            }
+            check_name(required(&params, "id")?)?;
+        }
+        "install-plugin" => {
+            let id = required(&params, "id")?;
+            let (name, marketplace) = id
+                .split_once('@')
+                .ok_or_else(|| ArcadeError::invalid_input("Plugin id must be name@marketplace"))?;
+            check_name(name)?;
+            check_name(marketplace)?;
+        }
+        "import-item" | "apply-loadout" => {
+            if required(&params, "payload")?.len() > MAX_PAYLOAD_LEN {
+                return Err(ArcadeError::invalid_input("Link payload is too large"));
+            }
+        }
+        _ => return Err(ArcadeError::invalid_input(format!("Unknown link action '{}'", action))),
+    }
+
+    Ok(DeepLinkRequest { action, params, url: url.to_string() })
+}
+
+fn handle_urls(app: &AppHandle, urls: impl IntoIterator<Item = String>) {
+    for url in urls {
+        match parse_deep_link(&url) {
+            Ok(request) => {
+                tracing::info!("Opened from link: {}", request.action);
+                if let Some(state) = app.try_state::<DeepLinkState>() {
+                    if let Ok(mut pending) = state.0.lock() {
+                        pending.push(request.clone());
+                    }
+                }
+                crate::tray::show_main_window(app);
+                let _ = app.emit("deep-link", &request);
+            }
+            Err(e) => {
+                tracing::warn!("Ignored link {}: {}", url, e);
+                let _ = app.emit("deep-link-rejected", &e);
+            }
+        }
+    }
+}
+
+/// Route links opened while the app runs, and the one it was launched with
+pub fn init(app: &AppHandle) -> Result<(), String> {
+    // Installed builds register the scheme through the bundle; dev builds on Windows and Linux have to do it at runtime
+    #[cfg(all(debug_assertions, any(windows, target_os = "linux")))]
+    app.deep_link().register_all().map_err(|e| e.to_string())?;
+
+    let handle = app.clone();
+    app.deep_link().on_open_url(move |event| {
+        handle_urls(&handle, event.urls().into_iter().map(|u| u.to_string()));
+    });
+    if let Some(urls) = app.deep_link().get_current().map_err(|e| e.to_string())? {
+        handle_urls(app, urls.into_iter().map(|u| u.to_string()));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accepts_known_actions_and_refuses_the_rest() {
+        let skill = parse_deep_link("claudearcade://install-skill?source=repo&id=pdf").unwrap();
+        assert_eq!(skill.action, "install-skill");
+        assert_eq!(skill.params["id"], "pdf");
+
+        assert!(parse_deep_link("claudearcade://install-plugin?id=review@official").is_ok());
+        assert!(parse_deep_link("claudearcade://install-skill?id=../../.ssh").is_err());
+        assert!(parse_deep_link("claudearcade://install-plugin?id=review").is_err());
+        assert!(parse_deep_link("claudearcade://run-command?cmd=rm").is_err());
+        assert!(parse_deep_link("https://install-skill?id=pdf").is_err());
+    }
+}
@@ -0,0 +1,197 @@
+//! Structured errors for commands, serialized with a stable `code` the frontend can match on
+//! Commands move to this from plain strings one at a time; `Other` carries the strings that remain.
+
+use serde::{Serialize, Serializer};
+use serde_json::json;
+use std::fmt;
+
+use crate::injection::InjectionReport;
+use crate::scanner::settings::{SettingsConflict, SettingsParseError};
+use crate::secrets::SecretFinding;
+
+/// Error returned to the frontend as `{ "code": "notFound", "message": ..., ...details }`.
+/// `message` is the English text, for places that don't translate the code yet.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ArcadeError {
+    /// A session, item, template or file that doesn't exist
+    NotFound { what: String, id: String },
+    /// A config file that doesn't parse
+    ParseError { path: String, line: Option<usize>, column: Option<usize>, message: String },
+    /// Reading or writing a file failed
+    Io { path: String, message: String },
+    /// A network request failed
+    Network { message: String },
+    /// GitHub (or another API) refused the request until its rate limit resets
+    NetworkRateLimited { retry_after_secs: Option<u64> },
+    /// The target already exists or was changed by something else
+    Conflict { message: String },
+    /// settings.json changed since it was read; carries both sides and the revision to retry against
+    SettingsConflict { conflict: Box<SettingsConflict> },
+    /// An argument the command can't work with
+    InvalidInput { message: String },
+    /// Content to be written looks like it holds credentials; retry with confirmation to write it anyway
+    SecretsDetected { findings: Vec<SecretFinding> },
+    /// Downloaded content scored high for prompt injection; retry accepting the risk to install it anyway
+    InjectionRisk { report: InjectionReport },
+    /// Anything not migrated to a specific variant yet
+    Other { message: String },
+}
+
+impl ArcadeError {
+    pub fn not_found(what: &str, id: impl Into<String>) -> Self {
+        ArcadeError::NotFound { what: what.to_string(), id: id.into() }
+    }
+
+    pub fn io(path: impl AsRef<std::path::Path>, error: impl fmt::Display) -> Self {
+        ArcadeError::Io { path: path.as_ref().to_string_lossy().to_string(), message: error.to_string() }
+    }
+
+    pub fn invalid_input(message: impl Into<String>) -> Self {
+        ArcadeError::InvalidInput { message: message.into() }
+    }
+
+    /// Stable code for the frontend to translate or branch on
+    pub fn code(&self) -> &'static str {
+        match self {
+            ArcadeError::NotFound { .. } => "notFound",
+            ArcadeError::ParseError { .. } => "parseError",
+            ArcadeError::Io { .. } => "io",
+            ArcadeError::Network { .. } => "network",
+            ArcadeError::NetworkRateLimited { .. } => "networkRateLimited",
+            ArcadeError::Conflict { .. } => "conflict",
+            ArcadeError::SettingsConflict { .. } => "settingsConflict",
+            ArcadeError::InvalidInput { .. } => "invalidInput",
+            ArcadeError::SecretsDetected { .. } => "secretsDetected",
+            ArcadeError::InjectionRisk { .. } => "injectionRisk",
+            ArcadeError::Other { .. } => "other",
+        }
+    }
+}
+
+impl Serialize for ArcadeError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut payload = match self {
+            ArcadeError::NotFound { what, id } => json!({ "what": what, "id": id }),
+            ArcadeError::ParseError { path, line, column, .. } => json!({ "path": path, "line": line, "column": column }),
+            ArcadeError::Io { path, .. } => json!({ "path": path }),
+            ArcadeError::NetworkRateLimited { retry_after_secs } => json!({ "retryAfterSecs": retry_after_secs }),
+            ArcadeError::SecretsDetected { findings } => json!({ "findings": findings }),
+            ArcadeError::InjectionRisk { report } => json!(report),
+            ArcadeError::SettingsConflict { conflict } => json!(conflict),
+            _ => json!({}),
+        };
+        payload["code"] = json!(self.code());
+        payload["message"] = json!(self.to_string());
+        payload.serialize(serializer)
+    }
+}
+
+impl fmt::Display for ArcadeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ArcadeError::NotFound { what, id } => write!(f, "{} not found: {}", what, id),
+            ArcadeError::ParseError { path, line: Some(line), column, message } => {
+                write!(f, "Failed to parse {} at line {}", path, line)?;
+                if let Some(column) = column {
+                    write!(f, ", column {}", column)?;
+                }
+                write!(f, ": {}", message)
+            }
+            ArcadeError::ParseError { path, message, .. } => write!(f, "Failed to parse {}: {}", path, message),
+            ArcadeError::Io { path, message } => write!(f, "{}: {}", path, message),
+            ArcadeError::Network { message } => write!(f, "Network error: {}", message),
+            ArcadeError::NetworkRateLimited { retry_after_secs: Some(secs) } => {
+                write!(f, "Rate limited; try again in {} minutes", secs.div_ceil(60))
+            }
+            ArcadeError::NetworkRateLimited { retry_after_secs: None } => write!(f, "Rate limited; try again later"),
+            ArcadeError::SecretsDetected { findings } => {
+                let kinds: Vec<String> = findings.iter().map(|s| format!("{} on line {}", s.kind, s.line)).collect();
+                write!(f, "This looks like it contains credentials ({})", kinds.join(", "))
+            }
+            ArcadeError::InjectionRisk { report } => {
+                let mut kinds: Vec<&str> = report.findings.iter().map(|f| f.kind.as_str()).collect();
+                kinds.sort();
+                kinds.dedup();
+                write!(f, "This looks like it contains prompt injection (score {}: {})", report.score, kinds.join(", "))
+            }
+            ArcadeError::SettingsConflict { conflict } if conflict.keys.is_empty() => {
+                write!(f, "{} changed since it was read; reload and try again", conflict.path)
+            }
+            ArcadeError::SettingsConflict { conflict } => {
+                write!(f, "{} was changed elsewhere ({})", conflict.path, conflict.keys.join(", "))
+            }
+            ArcadeError::Conflict { message } | ArcadeError::InvalidInput { message } | ArcadeError::Other { message } => {
+                write!(f, "{}", message)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ArcadeError {}
+
+impl From<String> for ArcadeError {
+    fn from(message: String) -> Self {
+        ArcadeError::Other { message }
+    }
+}
+
+impl From<&str> for ArcadeError {
+    fn from(message: &str) -> Self {
+        ArcadeError::Other { message: message.to_string() }
+    }
+}
+
+/// For helpers that still return `Result<_, String>`
+impl From<ArcadeError> for String {
+    fn from(error: ArcadeError) -> Self {
+        error.to_string()
+    }
+}
+
+impl From<SettingsParseError> for ArcadeError {
+    fn from(error: SettingsParseError) -> Self {
+        ArcadeError::ParseError {
+            path: error.path,
+            line: Some(error.line),
+            column: Some(error.column),
+            message: error.message,
+        }
+    }
+}
+
+impl From<reqwest::Error> for ArcadeError {
+    fn from(error: reqwest::Error) -> Self {
+        ArcadeError::Network { message: error.to_string() }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serializes_with_code() {
+        let error = ArcadeError::not_found("Session", "abc");
+        assert_eq!(
+            serde_json::to_value(&error).unwrap(),
+            json!({ "code": "notFound", "what": "Session", "id": "abc", "message": "Session not found: abc" })
+        );
+
+        let conflict = ArcadeError::SettingsConflict {
+            conflict: Box::new(SettingsConflict {
+                path: "settings.json".to_string(),
+                keys: Vec::new(),
+                ours: json!({ "model": "opus" }),
+                theirs: json!({}),
+                revision: "none".to_string(),
+            }),
+        };
+        let payload = serde_json::to_value(&conflict).unwrap();
+        assert_eq!(payload["code"], "settingsConflict");
+        assert_eq!((&payload["revision"], &payload["ours"]["model"]), (&json!("none"), &json!("opus")));
+
+        let limited = ArcadeError::NetworkRateLimited { retry_after_secs: Some(90) };
+        assert_eq!(serde_json::to_value(&limited).unwrap()["retryAfterSecs"], 90);
+        assert_eq!(limited.to_string(), "Rate limited; try again in 2 minutes");
+    }
+}
@@ -0,0 +1,345 @@
+//! Opt-in localhost endpoint for events from hooks and status line scripts running outside the app
+//! It listens on 127.0.0.1 on a random port. The port and a fresh token are written to
+//! ~/.claude/arcade_endpoint.json for scripts to read, and requests without the token are refused:
+//! `curl -H "Authorization: Bearer $TOKEN" -d '{"type":"toolUsed","tool":"Edit"}' http://127.0.0.1:$PORT/events`
+//! Claude Code's OpenTelemetry metrics arrive on the same port; see `otel`.
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::oneshot;
+
+use crate::arcade_settings::load_arcade_settings;
+use crate::blocking::run_blocking;
+use crate::commands::analytics::{get_or_create_day, load_analytics, lock_analytics, save_analytics};
+use crate::commands::arcade_hooks::apply_session_event;
+use crate::commands::context_guard::record_context_use;
+
+const MAX_HEAD_BYTES: usize = 8 * 1024;
+const MAX_BODY_BYTES: usize = 64 * 1024;
+/// A metrics export carries every series since the last one
+const MAX_METRICS_BODY_BYTES: usize = 1024 * 1024;
+
+/// A client that hasn't sent its whole request by then is dropped
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// An event from a Claude Code process the app didn't start
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase", rename_all_fields = "camelCase")]
+pub enum ExternalEvent {
+    SessionStarted { session_id: String },
+    SessionEnded { session_id: String },
+    ToolUsed { session_id: Option<String>, tool: String },
+    /// Live context use, 0-100, as a status line script sees it
+    ContextPercent { session_id: Option<String>, percent: f64 },
+}
+
+/// Where scripts find the endpoint
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct EndpointFile {
+    url: String,
+    port: u16,
+    token: String,
+}
+
+pub struct RunningListener {
+    pub port: u16,
+    shutdown: oneshot::Sender<()>,
+}
+
+/// The listener while it runs
+pub struct EventListenerState(pub Mutex<Option<RunningListener>>);
+
+#[derive(Debug, PartialEq)]
+struct RequestHead {
+    method: String,
+    path: String,
+    content_length: usize,
+    token: Option<String>,
+}
+
+fn endpoint_path() -> Option<PathBuf> {
+    crate::paths::claude_dir().map(|d| d.join("arcade_endpoint.json"))
+}
+
+/// The endpoint file holds a credential, so only the user may read it
+fn write_endpoint_file(port: u16, token: &str) -> Result<(), String> {
+    let path = endpoint_path().ok_or("Could not find home directory")?;
+    let endpoint = EndpointFile { url: format!("http://127.0.0.1:{}/events", port), port, token: token.to_string() };
+    let content = serde_json::to_string_pretty(&endpoint).map_err(|e| e.to_string())?;
+
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    let mut file = options.open(&path).map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+    std::io::Write::write_all(&mut file, content.as_bytes()).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+fn remove_endpoint_file() {
+    if let Some(path) = endpoint_path() {
+        let _ = fs::remove_file(path);
+    }
+}
+
+/// Request line and headers; errors are the HTTP status to answer with
+fn parse_head(head: &str) -> Result<RequestHead, u16> {
+    let mut lines = head.split("\r\n");
+    let mut request_line = lines.next().unwrap_or_default().split(' ');
+    let (Some(method), Some(path)) = (request_line.next(), request_line.next()) else {
+        return Err(400);
+    };
+
+    let mut content_length = 0;
+    let mut token = None;
+    for line in lines.filter(|l| !l.is_empty()) {
+        let (name, value) = line.split_once(':').ok_or(400u16)?;
+        let value = value.trim();
+        match name.trim().to_ascii_lowercase().as_str() {
+            "content-length" => content_length = value.parse().map_err(|_| 400u16)?,
+            "authorization" => token = value.strip_prefix("Bearer ").map(|t| t.trim().to_string()),
+            _ => {}
+        }
+    }
+    Ok(RequestHead { method: method.to_string(), path: path.to_string(), content_length, token })
+}
+
+/// Compare without stopping at the first difference, so timing says nothing about the token
+fn token_matches(given: &str, expected: &str) -> bool {
+    given.len() == expected.len() && given.bytes().zip(expected.bytes()).fold(0u8, |diff, (a, b)| diff | (a ^ b)) == 0
+}
+
+/// A request that passed the checks
+enum Request {
+    Event(ExternalEvent),
+    Metrics(serde_json::Value),
+}
+
+/// Read one request and check it; returns it or the status to answer with
+async fn read_request(stream: &mut TcpStream, token: &str) -> Result<Request, u16> {
+    let mut buffer = Vec::new();
+    let mut chunk = [0u8; 4096];
+    let head_end = loop {
+        if let Some(end) = buffer.windows(4).position(|w| w == b"\r\n\r\n") {
+            break end;
+        }
+        if buffer.len() > MAX_HEAD_BYTES {
+            return Err(431);
+        }
+        let read = stream.read(&mut chunk).await.map_err(|_| 400u16)?;
+        if read == 0 {
+            return Err(400);
+        }
+        buffer.extend_from_slice(&chunk[..read]);
+    };
+
+    let head = parse_head(&String::from_utf8_lossy(&buffer[..head_end]))?;
+    if !head.token.is_some_and(|t| token_matches(&t, token)) {
+        return Err(401);
+    }
+    let max_body = match head.path.as_str() {
+        "/events" => MAX_BODY_BYTES,
+        crate::otel::METRICS_PATH => MAX_METRICS_BODY_BYTES,
+        _ => return Err(404),
+    };
+    if head.method != "POST" {
+        return Err(405);
+    }
+    if head.content_length > max_body {
+        return Err(413);
+    }
+
+    let mut body = buffer.split_off(head_end + 4);
+    while body.len() < head.content_length {
+        let read = stream.read(&mut chunk).await.map_err(|_| 400u16)?;
+        if read == 0 {
+            return Err(400);
+        }
+        body.extend_from_slice(&chunk[..read]);
+    }
+    body.truncate(head.content_length);
+    if head.path == crate::otel::METRICS_PATH {
+        return serde_json::from_slice(&body).map(Request::Metrics).map_err(|_| 400);
+    }
+    serde_json::from_slice(&body).map(Request::Event).map_err(|_| 400)
+}
+
+/// Fold an event into analytics: session starts and ends as the session hooks record them, and
+/// tool calls into today's count
+fn record(event: &ExternalEvent) -> Result<(), String> {
+    // Live only; the context guard is the one thing that keeps it
+    if let ExternalEvent::ContextPercent { session_id, percent } = event {
+        if let Some(id) = session_id {
+            record_context_use(id, percent / 100.0);
+        }
+        return Ok(());
+    }
+    let now = chrono::Utc::now().timestamp();
+    let _lock = lock_analytics();
+    let mut data = load_analytics();
+    match event {
+        ExternalEvent::SessionStarted { session_id } => apply_session_event(&mut data, "start", session_id, now),
+        ExternalEvent::SessionEnded { session_id } => apply_session_event(&mut data, "end", session_id, now),
+        ExternalEvent::ToolUsed { session_id, .. } => {
+            get_or_create_day(&mut data, now).tools_used += 1;
+            if let Some(id) = session_id {
+                apply_session_event(&mut data, "activity", id, now);
+            }
+        }
+        ExternalEvent::ContextPercent { .. } => return Ok(()),
+    }
+    save_analytics(&data)
+}
+
+fn record_metrics(request: &serde_json::Value) -> Result<(), String> {
+    let _lock = lock_analytics();
+    let mut data = load_analytics();
+    if crate::otel::ingest_metrics(&mut data, request) {
+        save_analytics(&data)?;
+    }
+    Ok(())
+}
+
+async fn handle(mut stream: TcpStream, token: String, app: AppHandle) {
+    let status = match tokio::time::timeout(REQUEST_TIMEOUT, read_request(&mut stream, &token)).await {
+        Err(_) => 408,
+        Ok(Err(status)) => status,
+        Ok(Ok(Request::Metrics(request))) => match run_blocking(move || record_metrics(&request)).await.and_then(|r| r) {
+            Ok(()) => 200,
+            Err(e) => {
+                tracing::warn!("Failed to record metrics: {}", e);
+                500
+            }
+        },
+        Ok(Ok(Request::Event(event))) => {
+            let recorded = event.clone();
+            match run_blocking(move || record(&recorded)).await.and_then(|r| r) {
+                Ok(()) => {
+                    let _ = app.emit("external-event", event);
+                    204
+                }
+                Err(e) => {
+                    tracing::warn!("Failed to record an external event: {}", e);
+                    500
+                }
+            }
+        }
+    };
+    let reason = match status {
+        // Only metrics exports get a 200; OTLP expects an (empty) response message
+        200 => "OK",
+        204 => "No Content",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        408 => "Request Timeout",
+        413 => "Payload Too Large",
+        431 => "Request Header Fields Too Large",
+        _ => "Internal Server Error",
+    };
+    let body = if status == 200 { "{}" } else { "" };
+    let response = format!(
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        reason,
+        body.len(),
+        body
+    );
+    let _ = stream.write_all(response.as_bytes()).await;
+}
+
+/// Start listening (restarting if already running) and return the port
+pub async fn start(app: AppHandle) -> Result<u16, String> {
+    let listener = TcpListener::bind(("127.0.0.1", 0)).await.map_err(|e| format!("Failed to listen: {}", e))?;
+    let port = listener.local_addr().map_err(|e| e.to_string())?.port();
+    let token = uuid::Uuid::new_v4().simple().to_string();
+    run_blocking({
+        let token = token.clone();
+        move || {
+            write_endpoint_file(port, &token)?;
+            if load_arcade_settings().otel_metrics {
+                if let Err(e) = crate::otel::write_export_env(port, &token) {
+                    tracing::warn!("Failed to point metrics export at the listener: {}", e);
+                }
+            }
+            Ok::<(), String>(())
+        }
+    })
+    .await??;
+
+    let (shutdown, mut stopped) = oneshot::channel();
+    {
+        let state = app.state::<EventListenerState>();
+        let mut running = state.0.lock().map_err(|e| format!("Lock error: {}", e))?;
+        if let Some(previous) = running.replace(RunningListener { port, shutdown }) {
+            let _ = previous.shutdown.send(());
+        }
+    }
+
+    tauri::async_runtime::spawn(async move {
+        loop {
+            tokio::select! {
+                _ = &mut stopped => break,
+                accepted = listener.accept() => match accepted {
+                    Ok((stream, _)) => {
+                        tauri::async_runtime::spawn(handle(stream, token.clone(), app.clone()));
+                    }
+                    Err(e) => tracing::warn!("Event listener failed to accept: {}", e),
+                },
+            }
+        }
+    });
+    tracing::info!("Listening for external events on 127.0.0.1:{}", port);
+    Ok(port)
+}
+
+/// Stop listening and remove the endpoint file
+pub fn stop(state: &EventListenerState) -> Result<(), String> {
+    let mut running = state.0.lock().map_err(|e| format!("Lock error: {}", e))?;
+    if let Some(listener) = running.take() {
+        let _ = listener.shutdown.send(());
+    }
+    remove_endpoint_file();
+    Ok(())
+}
+
+/// Start the listener at launch when it is turned on in arcade settings
+pub fn init(app: &AppHandle) {
+    if !load_arcade_settings().event_listener {
+        return;
+    }
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        if let Err(e) = start(app).await {
+            tracing::error!("Failed to start the event listener: {}", e);
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_requests_and_events() {
+        let head = parse_head("POST /events HTTP/1.1\r\nHost: 127.0.0.1\r\nContent-Length: 42\r\nAuthorization: Bearer abc123").unwrap();
+        assert_eq!(head.path, "/events");
+        assert_eq!(head.content_length, 42);
+        assert_eq!(head.token.as_deref(), Some("abc123"));
+        assert_eq!(parse_head("POST /events HTTP/1.1\r\nContent-Length: lots"), Err(400));
+
+        assert!(token_matches("abc123", "abc123"));
+        assert!(!token_matches("abc124", "abc123"));
+        assert!(!token_matches("abc", "abc123"));
+
+        let event: ExternalEvent = serde_json::from_str(r#"{"type":"toolUsed","sessionId":"s1","tool":"Edit"}"#).unwrap();
+        assert_eq!(event, ExternalEvent::ToolUsed { session_id: Some("s1".to_string()), tool: "Edit".to_string() });
+    }
+}
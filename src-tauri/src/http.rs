@@ -0,0 +1,136 @@
+//! HTTP for network features: proxies, timeouts, retries and an offline cache
+//! Corporate networks need the proxy Claude Code itself is configured with, and a flight shouldn't turn
+//! a version check into an error, so JSON indexes are cached and served from disk when unreachable.
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use crate::arcade_settings::load_arcade_settings;
+use crate::error::ArcadeError;
+use crate::scanner::settings::read_settings_full;
+
+/// How the app reaches the network
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct NetworkSettings {
+    /// Proxy for every request, e.g. `http://proxy.corp:8080`. When unset, HTTPS_PROXY/HTTP_PROXY
+    /// from Claude Code's settings `env`, then from the environment, are used.
+    pub proxy: Option<String>,
+    pub timeout_secs: u64,
+    /// Further attempts after a connection failure, timeout or server error
+    pub retries: u32,
+    /// Never touch the network; cached data only
+    pub offline: bool,
+}
+
+impl Default for NetworkSettings {
+    fn default() -> Self {
+        NetworkSettings { proxy: None, timeout_secs: 20, retries: 2, offline: false }
+    }
+}
+
+/// Delay before the first retry, doubled for each one after
+const RETRY_DELAY: Duration = Duration::from_millis(500);
+
+/// A proxy variable from the settings.json `env` block, which Claude Code sessions run with
+fn claude_env(name: &str) -> Option<String> {
+    let env = read_settings_full().ok()?.env?;
+    env.get(name).or_else(|| env.get(&name.to_lowercase())).filter(|v| !v.is_empty()).cloned()
+}
+
+/// A client honouring the proxy and timeout settings. reqwest reads the process environment's
+/// proxy variables itself, so only the configured and Claude Code ones are added here.
+pub fn client() -> Result<reqwest::Client, ArcadeError> {
+    let settings = load_arcade_settings().network;
+    let mut builder = reqwest::Client::builder()
+        .user_agent("ClaudeArcade")
+        .timeout(Duration::from_secs(settings.timeout_secs.max(1)))
+        .connect_timeout(Duration::from_secs(settings.timeout_secs.clamp(1, 10)));
+
+    let no_proxy = claude_env("NO_PROXY").and_then(|list| reqwest::NoProxy::from_string(&list));
+    let proxy_for = |url: &str| reqwest::Proxy::all(url).map(|p| p.no_proxy(no_proxy.clone()));
+    if let Some(url) = settings.proxy.or_else(|| claude_env("HTTPS_PROXY")).or_else(|| claude_env("HTTP_PROXY")) {
+        let proxy = proxy_for(&url).map_err(|e| ArcadeError::invalid_input(format!("Invalid proxy {}: {}", url, e)))?;
+        builder = builder.proxy(proxy);
+    }
+    Ok(builder.build()?)
+}
+
+fn offline_error() -> ArcadeError {
+    ArcadeError::Network { message: "Offline mode is on".to_string() }
+}
+
+/// Send a request built by `request`, retrying connection failures, timeouts and 5xx responses
+pub async fn send_with_retry(
+    request: impl Fn() -> reqwest::RequestBuilder,
+) -> Result<reqwest::Response, ArcadeError> {
+    let settings = load_arcade_settings().network;
+    if settings.offline {
+        return Err(offline_error());
+    }
+    let mut attempt = 0;
+    loop {
+        let result = request().send().await;
+        let retryable = match &result {
+            Ok(response) => response.status().is_server_error(),
+            Err(e) => e.is_connect() || e.is_timeout(),
+        };
+        if !retryable || attempt >= settings.retries {
+            return match result {
+                Ok(response) => Ok(response),
+                Err(e) if e.is_connect() || e.is_timeout() => Err(ArcadeError::Network {
+                    message: format!("Could not reach the network ({}); check the connection or proxy settings", e),
+                }),
+                Err(e) => Err(e.into()),
+            };
+        }
+        tokio::time::sleep(RETRY_DELAY * 2u32.pow(attempt)).await;
+        attempt += 1;
+    }
+}
+
+fn cache_path(url: &str) -> Option<PathBuf> {
+    let key = format!("{:x}", Sha256::digest(url.as_bytes()));
+    crate::paths::claude_dir().map(|d| d.join("arcade_http_cache").join(format!("{}.json", &key[..16])))
+}
+
+/// JSON from `url`, refreshing the on-disk copy. When the request fails (or offline mode is on) the
+/// cached copy is returned instead, with `true` for stale. Errors only when there is no copy either.
+pub async fn get_json_cached<T: DeserializeOwned>(url: &str) -> Result<(T, bool), ArcadeError> {
+    let client = client()?;
+    let fetched = async {
+        let response = send_with_retry(|| client.get(url)).await?.error_for_status()?;
+        Ok::<_, ArcadeError>(response.bytes().await?)
+    }
+    .await;
+
+    let path = cache_path(url);
+    match fetched {
+        Ok(body) => {
+            let value = serde_json::from_slice(&body).map_err(|e| ArcadeError::Network {
+                message: format!("Unexpected response from {}: {}", url, e),
+            })?;
+            if let Some(path) = &path {
+                let _ = path.parent().map(fs::create_dir_all);
+                if let Err(e) = crate::paths::atomic_write(path, &body) {
+                    tracing::warn!("Failed to cache {}: {}", url, e);
+                }
+            }
+            Ok((value, false))
+        }
+        Err(e) => {
+            let cached = path.and_then(|p| fs::read(p).ok()).and_then(|body| serde_json::from_slice(&body).ok());
+            match cached {
+                Some(value) => {
+                    tracing::info!("Using cached {} ({})", url, e);
+                    Ok((value, true))
+                }
+                None => Err(e),
+            }
+        }
+    }
+}
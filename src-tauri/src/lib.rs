@@ -2,7 +2,29 @@ mod types;
 mod scanner;
 mod commands;
 mod watcher;
+mod tailer;
 mod pty;
+mod blocking;
+mod error;
+mod logging;
+mod paths;
+mod secrets;
+mod injection;
+mod audit;
+mod tray;
+mod arcade_settings;
+mod notifications;
+mod deep_link;
+mod wsl;
+mod http;
+mod trash;
+mod event_listener;
+mod otel;
+mod process;
+mod scheduler;
+mod store;
+#[cfg(test)]
+mod test_support;
 
 use commands::{
     scan_inventory, equip_item, unequip_item,
@@ -15,20 +37,107 @@ use commands::{
     start_session, record_message, record_activity, end_session,
     get_daily_usage, get_weekly_summary, get_monthly_summary, get_current_session,
     get_permissions, set_permissions,
+    set_permission_default_mode, add_additional_directory, remove_additional_directory,
+    set_bypass_permissions_disabled, test_permission, get_permission_audit,
+    get_effective_permissions,
     list_agents, get_agent, save_agent, delete_agent, get_agent_content, save_agent_content,
-    scan_project_claude_items,
+    scan_project_claude_items, scan_projects,
+    watch_project, unwatch_project, list_watched_projects, ProjectWatcherState,
+    list_settings_backups, restore_settings_backup,
+    get_settings, update_settings_field, get_settings_error, get_effective_settings,
+    get_git_info, init_project_claude,
+    save_project_template, list_project_templates, delete_project_template, apply_project_template,
+    record_project_opened, list_recent_projects, update_project_meta, remove_project, list_project_tags,
+    install_plugin, uninstall_plugin,
+    get_plugin_details,
+    check_plugin_updates, update_plugin,
+    link_local_plugin,
+    check_plugin_prerequisites,
+    create_plugin,
+    validate_marketplace,
+    list_session_transcripts,
+    get_session_transcript,
+    start_session_tail, stop_session_tail, get_session_tail_project, SessionTailerState,
+    pty_spawn_claude, list_resumable_sessions,
+    export_session,
+    get_session_context_timeline,
+    get_session_todos, get_active_todos,
+    get_companion_activity,
+    preview_cursor_import, apply_cursor_import,
+    import_from_tool, apply_tool_import,
+    export_character,
+    preview_character_import, import_character,
+    sync_init, sync_push, sync_pull, get_sync_status,
+    export_inventory,
+    convert_item,
+    export_agents_md,
+    detect_memory_imports,
+    share_item, preview_shared_item, import_shared_item,
+    get_cached_inventory, InventoryCache, InventoryCacheState,
+    get_recent_logs, set_log_level,
+    get_scan_metrics,
+    query_inventory,
+    verify_item_integrity,
+    get_audit_log,
+    get_notification_settings, set_notification_settings,
+    install_arcade_statusline,
+    list_output_styles, create_output_style, save_output_style_content, delete_output_style, set_active_output_style,
+    global_search,
+    run_doctor,
+    get_claude_version, ClaudeVersionState,
+    take_pending_deep_links,
+    detect_wsl_environments, set_wsl_scan,
+    list_profiles, create_profile, activate_profile,
+    get_slash_command,
+    get_usage_window_status, set_usage_limits, UsageWindowState,
+    get_default_model, set_default_model,
+    get_settings_env, set_settings_env,
+    get_network_settings, set_network_settings,
+    list_trash, restore_from_trash, empty_trash,
+    compare_items,
+    bulk_apply,
+    get_cleanup_suggestions, apply_cleanup_action,
+    get_context_breakdown,
+    install_arcade_hooks,
+    get_event_listener_status, set_event_listener_enabled, set_otel_metrics_enabled,
+    list_mcp_groups, create_mcp_group, delete_mcp_group, enable_mcp_group, disable_mcp_group,
+    list_spellbooks, save_spellbook, delete_spellbook, equip_spellbook,
+    recommend_for_prompt,
+    install_context_guard, uninstall_context_guard,
+    get_onboarding_status, complete_onboarding_step, set_onboarding_dismissed,
+    find_duplicates,
+    list_processes, cancel_process,
+    test_hook,
+    get_delegation_graph,
+    list_jobs, run_job_now, update_job,
+    export_arcade_data, import_arcade_data,
 };
 use pty::PtyManager;
+use watcher::ProjectWatchers;
+use tailer::SessionTailer;
 use std::sync::Mutex;
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
+    logging::init();
+
     tauri::Builder::default()
+        // Must come first: a second launch (from a link, say) hands its arguments to this one and exits
+        .plugin(tauri_plugin_single_instance::init(|app, _argv, _cwd| tray::show_main_window(app)))
+        .plugin(tauri_plugin_deep_link::init())
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_updater::Builder::new().build())
         .plugin(tauri_plugin_process::init())
+        .plugin(tauri_plugin_notification::init())
         .manage(PtyState(Mutex::new(PtyManager::new())))
+        .manage(ProjectWatcherState(Mutex::new(ProjectWatchers::new())))
+        .manage(SessionTailerState(Mutex::new(SessionTailer::new())))
+        .manage(InventoryCacheState(Mutex::new(InventoryCache::default())))
+        .manage(ClaudeVersionState(Mutex::new(None)))
+        .manage(UsageWindowState(Mutex::new(None)))
+        .manage(deep_link::DeepLinkState(Mutex::new(Vec::new())))
+        .manage(event_listener::EventListenerState(Mutex::new(None)))
         .invoke_handler(tauri::generate_handler![
             scan_inventory,
             equip_item,
@@ -60,6 +169,13 @@ pub fn run() {
             get_current_session,
             get_permissions,
             set_permissions,
+            set_permission_default_mode,
+            add_additional_directory,
+            remove_additional_directory,
+            set_bypass_permissions_disabled,
+            test_permission,
+            get_permission_audit,
+            get_effective_permissions,
             list_agents,
             get_agent,
             save_agent,
@@ -67,13 +183,152 @@ pub fn run() {
             get_agent_content,
             save_agent_content,
             scan_project_claude_items,
+            scan_projects,
+            watch_project,
+            unwatch_project,
+            list_watched_projects,
+            list_settings_backups,
+            restore_settings_backup,
+            get_settings,
+            update_settings_field,
+            get_settings_error,
+            get_effective_settings,
+            get_git_info,
+            init_project_claude,
+            save_project_template,
+            list_project_templates,
+            delete_project_template,
+            apply_project_template,
+            record_project_opened,
+            list_recent_projects,
+            update_project_meta,
+            remove_project,
+            list_project_tags,
+            install_plugin,
+            uninstall_plugin,
+            get_plugin_details,
+            check_plugin_updates,
+            update_plugin,
+            link_local_plugin,
+            check_plugin_prerequisites,
+            create_plugin,
+            validate_marketplace,
+            list_session_transcripts,
+            get_session_transcript,
+            start_session_tail,
+            stop_session_tail,
+            get_session_tail_project,
+            pty_spawn_claude,
+            list_resumable_sessions,
+            export_session,
+            get_session_context_timeline,
+            get_session_todos,
+            get_active_todos,
+            get_companion_activity,
+            preview_cursor_import,
+            apply_cursor_import,
+            import_from_tool,
+            apply_tool_import,
+            export_character,
+            preview_character_import,
+            import_character,
+            sync_init,
+            sync_push,
+            sync_pull,
+            get_sync_status,
+            export_inventory,
+            convert_item,
+            export_agents_md,
+            detect_memory_imports,
+            share_item,
+            preview_shared_item,
+            import_shared_item,
+            get_cached_inventory,
+            get_recent_logs,
+            set_log_level,
+            get_scan_metrics,
+            query_inventory,
+            verify_item_integrity,
+            get_audit_log,
+            get_notification_settings,
+            set_notification_settings,
+            install_arcade_statusline,
+            list_output_styles,
+            create_output_style,
+            save_output_style_content,
+            delete_output_style,
+            set_active_output_style,
+            global_search,
+            run_doctor,
+            get_claude_version,
+            take_pending_deep_links,
+            detect_wsl_environments,
+            set_wsl_scan,
+            list_profiles,
+            create_profile,
+            activate_profile,
+            get_slash_command,
+            get_usage_window_status,
+            set_usage_limits,
+            get_default_model,
+            set_default_model,
+            get_settings_env,
+            set_settings_env,
+            get_network_settings,
+            set_network_settings,
+            list_trash,
+            restore_from_trash,
+            empty_trash,
+            compare_items,
+            bulk_apply,
+            get_cleanup_suggestions,
+            apply_cleanup_action,
+            get_context_breakdown,
+            install_arcade_hooks,
+            get_event_listener_status,
+            set_event_listener_enabled,
+            set_otel_metrics_enabled,
+            list_mcp_groups,
+            create_mcp_group,
+            delete_mcp_group,
+            enable_mcp_group,
+            disable_mcp_group,
+            list_spellbooks,
+            save_spellbook,
+            delete_spellbook,
+            equip_spellbook,
+            recommend_for_prompt,
+            install_context_guard,
+            uninstall_context_guard,
+            get_onboarding_status,
+            complete_onboarding_step,
+            set_onboarding_dismissed,
+            find_duplicates,
+            list_processes,
+            cancel_process,
+            test_hook,
+            get_delegation_graph,
+            list_jobs,
+            run_job_now,
+            update_job,
+            export_arcade_data,
+            import_arcade_data,
         ])
         .setup(|app| {
+            commands::profiles::restore_active_profile();
             // Start file watcher for settings.json changes
             let handle = app.handle().clone();
             if let Err(e) = watcher::start_watcher(handle) {
-                eprintln!("Failed to start file watcher: {}", e);
+                tracing::error!("Failed to start file watcher: {}", e);
+            }
+            if let Err(e) = tray::init(app.handle()) {
+                tracing::error!("Failed to create tray icon: {}", e);
+            }
+            if let Err(e) = deep_link::init(app.handle()) {
+                tracing::error!("Failed to set up claudearcade:// links: {}", e);
             }
+            event_listener::init(app.handle());
+            scheduler::init(app.handle());
             Ok(())
         })
         .run(tauri::generate_context!())
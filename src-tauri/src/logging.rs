@@ -0,0 +1,71 @@
+//! Logging through `tracing`, to stderr and a daily log file under ~/.claude/arcade_logs/
+//! The level can be changed while the app runs; it starts at info, or RUST_LOG's level when set.
+
+use std::path::PathBuf;
+use std::sync::OnceLock;
+use tracing::level_filters::LevelFilter;
+use tracing_appender::rolling::{RollingFileAppender, Rotation};
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{fmt, reload, Registry};
+
+/// Log files kept before the oldest is removed, one per day
+const MAX_LOG_FILES: usize = 7;
+
+static LEVEL_HANDLE: OnceLock<reload::Handle<LevelFilter, Registry>> = OnceLock::new();
+
+/// ~/.claude/arcade_logs
+pub fn log_dir() -> Option<PathBuf> {
+    crate::paths::claude_dir().map(|d| d.join("arcade_logs"))
+}
+
+/// Parse "error", "warn", "info", "debug", "trace" or "off"
+pub fn parse_level(level: &str) -> Result<LevelFilter, String> {
+    level
+        .trim()
+        .parse::<LevelFilter>()
+        .map_err(|_| format!("Unknown log level: {}", level))
+}
+
+/// Install the global subscriber. Logging to the file is skipped when its folder can't be created.
+pub fn init() {
+    let initial = std::env::var("RUST_LOG")
+        .ok()
+        .and_then(|level| parse_level(&level).ok())
+        .unwrap_or(LevelFilter::INFO);
+    let (level_layer, handle) = reload::Layer::new(initial);
+
+    let file_appender = log_dir().and_then(|dir| {
+        RollingFileAppender::builder()
+            .rotation(Rotation::DAILY)
+            .filename_prefix("arcade")
+            .filename_suffix("log")
+            .max_log_files(MAX_LOG_FILES)
+            .build(dir)
+            .ok()
+    });
+    let file_layer = file_appender.map(|appender| fmt::layer().with_ansi(false).with_writer(appender));
+
+    let installed = tracing_subscriber::registry()
+        .with(level_layer)
+        .with(fmt::layer().with_writer(std::io::stderr))
+        .with(file_layer)
+        .try_init();
+    if installed.is_ok() {
+        let _ = LEVEL_HANDLE.set(handle);
+    }
+}
+
+/// Change the level of everything logged from now on
+pub fn set_level(level: LevelFilter) -> Result<(), String> {
+    let handle = LEVEL_HANDLE.get().ok_or("Logging isn't initialized")?;
+    handle.reload(level).map_err(|e| e.to_string())
+}
+
+/// The level currently logged
+pub fn current_level() -> LevelFilter {
+    LEVEL_HANDLE
+        .get()
+        .and_then(|handle| handle.clone_current())
+        .unwrap_or(LevelFilter::INFO)
+}
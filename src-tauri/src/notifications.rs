@@ -0,0 +1,173 @@
+//! OS notifications for things worth knowing about while the window is in the background
+//! The live context crossing into heavy or the dumbzone, an MCP server that stops responding,
+//! and a hook that keeps failing. Each kind can be turned off in arcade settings.
+
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::sync::{LazyLock, Mutex};
+use tauri::AppHandle;
+use tauri_plugin_notification::NotificationExt;
+
+use crate::arcade_settings::load_arcade_settings;
+
+/// Failures of one hook in a session before it is reported
+const HOOK_ERROR_THRESHOLD: u32 = 3;
+
+static NOTIFIER: LazyLock<Mutex<Notifier>> = LazyLock::new(|| Mutex::new(Notifier::default()));
+
+/// Which notifications are shown; all on by default
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct NotificationSettings {
+    /// Live context reaching heavy or the dumbzone
+    pub context_load: bool,
+    /// An MCP server's command can no longer be found
+    pub mcp_failures: bool,
+    /// A hook failing repeatedly in the tailed session
+    pub hook_errors: bool,
+}
+
+impl Default for NotificationSettings {
+    fn default() -> Self {
+        NotificationSettings { context_load: true, mcp_failures: true, hook_errors: true }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum NotificationKind {
+    ContextLoad,
+    McpFailure,
+    HookError,
+}
+
+impl NotificationKind {
+    fn enabled(self, settings: &NotificationSettings) -> bool {
+        match self {
+            NotificationKind::ContextLoad => settings.context_load,
+            NotificationKind::McpFailure => settings.mcp_failures,
+            NotificationKind::HookError => settings.hook_errors,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Notification {
+    kind: NotificationKind,
+    title: String,
+    body: String,
+}
+
+/// What has already been reported, so each problem notifies once rather than on every check
+#[derive(Debug, Default)]
+struct Notifier {
+    context_status: Option<String>,
+    failing_servers: HashSet<String>,
+    hook_errors: HashMap<String, u32>,
+}
+
+impl Notifier {
+    fn context(&mut self, status: &str, load_percentage: f64) -> Option<Notification> {
+        let previous = self.context_status.replace(status.to_string());
+        let rank = |s: &str| match s {
+            "heavy" => 1,
+            "dumbzone" => 2,
+            _ => 0,
+        };
+        // Only a step up is news; falling back (or the first reading) is not
+        if rank(status) <= previous.as_deref().map_or(0, rank) {
+            return None;
+        }
+        let title = if status == "dumbzone" { "Context in the dumbzone" } else { "Context getting heavy" };
+        Some(Notification {
+            kind: NotificationKind::ContextLoad,
+            title: title.to_string(),
+            body: format!("The session's context is {:.0}% full.", load_percentage * 100.0),
+        })
+    }
+
+    fn mcp_status(&mut self, server_id: &str, available: bool) -> Option<Notification> {
+        if available {
+            self.failing_servers.remove(server_id);
+            return None;
+        }
+        self.failing_servers.insert(server_id.to_string()).then(|| Notification {
+            kind: NotificationKind::McpFailure,
+            title: "MCP server unavailable".to_string(),
+            body: format!("{} could not be started; its command was not found.", server_id),
+        })
+    }
+
+    fn hook_error(&mut self, hook: &str) -> Option<Notification> {
+        let count = self.hook_errors.entry(hook.to_string()).or_default();
+        *count += 1;
+        (*count == HOOK_ERROR_THRESHOLD).then(|| Notification {
+            kind: NotificationKind::HookError,
+            title: "Hook keeps failing".to_string(),
+            body: format!("{} has failed {} times this session.", hook, HOOK_ERROR_THRESHOLD),
+        })
+    }
+}
+
+fn show(app: &AppHandle, notification: Option<Notification>) {
+    let Some(notification) = notification else {
+        return;
+    };
+    if !notification.kind.enabled(&load_arcade_settings().notifications) {
+        return;
+    }
+    let shown = app.notification().builder().title(&notification.title).body(&notification.body).show();
+    if let Err(e) = shown {
+        tracing::warn!("Failed to show notification: {}", e);
+    }
+}
+
+fn update(app: &AppHandle, f: impl FnOnce(&mut Notifier) -> Option<Notification>) {
+    let notification = NOTIFIER.lock().ok().and_then(|mut notifier| f(&mut notifier));
+    show(app, notification);
+}
+
+/// Report the live session's context size; notifies when it steps up into heavy or the dumbzone
+pub fn context_changed(app: &AppHandle, status: &str, load_percentage: f64) {
+    update(app, |n| n.context(status, load_percentage));
+}
+
+/// Report the result of probing an MCP server; notifies the first time it fails
+pub fn mcp_probed(app: &AppHandle, server_id: &str, available: bool) {
+    update(app, |n| n.mcp_status(server_id, available));
+}
+
+/// Report one failure of a hook; notifies when it has failed `HOOK_ERROR_THRESHOLD` times
+pub fn hook_failed(app: &AppHandle, hook: &str) {
+    update(app, |n| n.hook_error(hook));
+}
+
+/// Forget per-session state when a new session starts
+pub fn session_started() {
+    if let Ok(mut notifier) = NOTIFIER.lock() {
+        notifier.context_status = None;
+        notifier.hook_errors.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn notifies_once_per_problem() {
+        let mut notifier = Notifier::default();
+        assert!(notifier.context("healthy", 0.1).is_none());
+        assert_eq!(notifier.context("heavy", 0.3).unwrap().title, "Context getting heavy");
+        assert!(notifier.context("heavy", 0.35).is_none());
+        assert_eq!(notifier.context("dumbzone", 0.6).unwrap().title, "Context in the dumbzone");
+        assert!(notifier.context("heavy", 0.4).is_none());
+
+        assert!(notifier.mcp_status("github", false).is_some());
+        assert!(notifier.mcp_status("github", false).is_none());
+        assert!(notifier.mcp_status("github", true).is_none());
+        assert!(notifier.mcp_status("github", false).is_some());
+
+        let reported: Vec<bool> = (0..4).map(|_| notifier.hook_error("PostToolUse:Edit").is_some()).collect();
+        assert_eq!(reported, vec![false, false, true, false]);
+    }
+}
@@ -0,0 +1,180 @@
+//! Claude Code's OpenTelemetry metrics, received on the event listener's `/v1/metrics`
+//! Turning this on points Claude Code's OTLP exporter (http/json, delta temporality) at the
+//! listener through the `env` block of settings.json, so analytics gets the exact token counts,
+//! cost and edit decisions Claude Code reports instead of estimates. The env is rewritten each
+//! time the listener starts, since the port and token change; sessions already running keep
+//! exporting to the old port until they are restarted.
+
+use serde_json::{Map, Value};
+
+use crate::commands::analytics::{get_or_create_day, AnalyticsData};
+use crate::scanner::settings::{modify_settings, read_settings_full};
+
+pub const METRICS_PATH: &str = "/v1/metrics";
+
+/// OTLP `AggregationTemporality`; delta is asked for, cumulative points would be counted twice
+const TEMPORALITY_DELTA: i64 = 1;
+
+const INGESTED_METRICS: [&str; 3] = ["claude_code.token.usage", "claude_code.cost.usage", "claude_code.code_edit_tool.decision"];
+
+/// Settings env entries that send metrics to the listener
+fn export_env(port: u16, token: &str) -> Vec<(&'static str, String)> {
+    vec![
+        ("CLAUDE_CODE_ENABLE_TELEMETRY", "1".to_string()),
+        ("OTEL_METRICS_EXPORTER", "otlp".to_string()),
+        ("OTEL_EXPORTER_OTLP_METRICS_PROTOCOL", "http/json".to_string()),
+        ("OTEL_EXPORTER_OTLP_METRICS_ENDPOINT", format!("http://127.0.0.1:{}{}", port, METRICS_PATH)),
+        ("OTEL_EXPORTER_OTLP_METRICS_HEADERS", format!("Authorization=Bearer {}", token)),
+        ("OTEL_EXPORTER_OTLP_METRICS_TEMPORALITY_PREFERENCE", "delta".to_string()),
+    ]
+}
+
+fn is_arcade_endpoint(endpoint: &str) -> bool {
+    endpoint.starts_with("http://127.0.0.1:") && endpoint.ends_with(METRICS_PATH)
+}
+
+/// An OTLP endpoint in the settings env that isn't the listener's
+pub(crate) fn foreign_exporter() -> Result<Option<String>, String> {
+    let env = read_settings_full()?.env.unwrap_or_default();
+    let endpoint = env.get("OTEL_EXPORTER_OTLP_METRICS_ENDPOINT").or_else(|| env.get("OTEL_EXPORTER_OTLP_ENDPOINT"));
+    Ok(endpoint.filter(|e| !is_arcade_endpoint(e)).cloned())
+}
+
+/// Point Claude Code's metrics exporter at the listener. Refuses to replace a metrics exporter
+/// the user set up themselves.
+pub(crate) fn write_export_env(port: u16, token: &str) -> Result<(), String> {
+    if let Some(endpoint) = foreign_exporter()? {
+        return Err(format!("Claude Code already exports metrics to {}", endpoint));
+    }
+
+    Ok(modify_settings(|settings| {
+        let root = settings.as_object_mut().ok_or("settings.json is not an object")?;
+        let env = root.entry("env").or_insert_with(|| Value::Object(Map::new()));
+        let env = env.as_object_mut().ok_or("settings.json env is not an object")?;
+        for (key, value) in export_env(port, token) {
+            env.insert(key.to_string(), Value::String(value));
+        }
+        Ok(())
+    })?)
+}
+
+/// Take the listener's exporter settings back out of the env
+pub(crate) fn remove_export_env() -> Result<(), String> {
+    let env = read_settings_full()?.env.unwrap_or_default();
+    if !env.get("OTEL_EXPORTER_OTLP_METRICS_ENDPOINT").is_some_and(|e| is_arcade_endpoint(e)) {
+        return Ok(());
+    }
+    Ok(modify_settings(|settings| {
+        if let Some(env) = settings.get_mut("env").and_then(|e| e.as_object_mut()) {
+            for (key, _) in export_env(0, "") {
+                env.remove(key);
+            }
+        }
+        Ok(())
+    })?)
+}
+
+fn attribute<'a>(point: &'a Value, key: &str) -> Option<&'a str> {
+    point["attributes"]
+        .as_array()?
+        .iter()
+        .find(|a| a["key"] == key)
+        .and_then(|a| a["value"]["stringValue"].as_str())
+}
+
+/// OTLP JSON carries 64-bit integers as strings
+fn number(value: &Value) -> Option<f64> {
+    value.as_f64().or_else(|| value.as_str().and_then(|s| s.parse().ok()))
+}
+
+/// Fold an OTLP/JSON `ExportMetricsServiceRequest` into analytics, each point on the local day
+/// it was measured. Returns whether anything was recorded.
+pub(crate) fn ingest_metrics(data: &mut AnalyticsData, request: &Value) -> bool {
+    let metrics = request["resourceMetrics"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .flat_map(|r| r["scopeMetrics"].as_array().into_iter().flatten())
+        .flat_map(|s| s["metrics"].as_array().into_iter().flatten());
+
+    let mut recorded = false;
+    for metric in metrics {
+        let name = metric["name"].as_str().unwrap_or_default();
+        let sum = &metric["sum"];
+        if !INGESTED_METRICS.contains(&name) || sum["aggregationTemporality"].as_i64() != Some(TEMPORALITY_DELTA) {
+            continue;
+        }
+        for point in sum["dataPoints"].as_array().into_iter().flatten() {
+            let Some(value) = number(&point["asInt"]).or_else(|| number(&point["asDouble"])) else {
+                continue;
+            };
+            let at = number(&point["timeUnixNano"]).map_or_else(|| chrono::Utc::now().timestamp(), |n| (n / 1e9) as i64);
+            let day = get_or_create_day(data, at);
+            match name {
+                "claude_code.token.usage" => match attribute(point, "type") {
+                    Some("input") => day.input_tokens += value as u64,
+                    Some("output") => day.output_tokens += value as u64,
+                    Some("cacheRead") => day.cache_read_tokens += value as u64,
+                    Some("cacheCreation") => day.cache_creation_tokens += value as u64,
+                    _ => continue,
+                },
+                "claude_code.cost.usage" => day.cost_usd += value,
+                "claude_code.code_edit_tool.decision" => match attribute(point, "decision") {
+                    Some("accept") => day.edits_accepted += value as u32,
+                    Some("reject") => day.edits_rejected += value as u32,
+                    _ => continue,
+                },
+                _ => continue,
+            }
+            recorded = true;
+        }
+    }
+    recorded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn ingests_claude_code_metrics() {
+        let request = json!({
+            "resourceMetrics": [{
+                "scopeMetrics": [{
+                    "metrics": [
+                        {
+                            "name": "claude_code.token.usage",
+                            "sum": {
+                                "aggregationTemporality": 1,
+                                "dataPoints": [
+                                    { "asDouble": 1200, "timeUnixNano": "1767268800000000000",
+                                      "attributes": [{ "key": "type", "value": { "stringValue": "input" } }] },
+                                    { "asDouble": 300, "timeUnixNano": "1767268800000000000",
+                                      "attributes": [{ "key": "type", "value": { "stringValue": "output" } }] }
+                                ]
+                            }
+                        },
+                        {
+                            "name": "claude_code.cost.usage",
+                            "sum": { "aggregationTemporality": 1, "dataPoints": [{ "asDouble": 0.25, "timeUnixNano": "1767268800000000000" }] }
+                        },
+                        {
+                            "name": "claude_code.cost.usage",
+                            "sum": { "aggregationTemporality": 2, "dataPoints": [{ "asDouble": 9.0, "timeUnixNano": "1767268800000000000" }] }
+                        }
+                    ]
+                }]
+            }]
+        });
+
+        let mut data = AnalyticsData::default();
+        assert!(ingest_metrics(&mut data, &request));
+        let day = &data.daily_usage[0];
+        assert_eq!((day.input_tokens, day.output_tokens, day.cost_usd), (1200, 300, 0.25));
+        assert_eq!(day.total_tokens(), 1500);
+
+        assert!(is_arcade_endpoint("http://127.0.0.1:4242/v1/metrics"));
+        assert!(!is_arcade_endpoint("https://otel.example.com"));
+    }
+}
@@ -0,0 +1,301 @@
+//! Where the home folder, the Claude config folder and the open project are
+//! Real paths by default; CLAUDE_ARCADE_HOME and CLAUDE_CONFIG_DIR redirect them, an active config profile
+//! swaps the Claude folder for the whole process, and an override can be scoped to one thread (tests, and
+//! scans of a second config root such as WSL's).
+//! Commands that write check their targets here so an id like `../../.bashrc` can't leave these roots.
+
+use std::fs::File;
+use std::io::Write;
+use std::path::{Component, Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::RwLock;
+use std::time::Duration;
+
+use crate::error::ArcadeError;
+
+/// Roots every scanner and command resolves its files against
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfigPaths {
+    pub home: PathBuf,
+    /// ~/.claude, or CLAUDE_CONFIG_DIR as Claude Code itself honours it
+    pub claude_dir: PathBuf,
+    pub project: Option<PathBuf>,
+}
+
+/// Claude folder of the active config profile; None while the default one is active
+static PROFILE_DIR: RwLock<Option<PathBuf>> = RwLock::new(None);
+
+thread_local! {
+    static OVERRIDE: std::cell::RefCell<Option<ConfigPaths>> = const { std::cell::RefCell::new(None) };
+}
+
+impl ConfigPaths {
+    /// Paths for this thread: a scoped override, then the active profile's Claude folder over the defaults
+    pub fn resolve() -> Option<Self> {
+        if let Some(paths) = OVERRIDE.with(|o| o.borrow().clone()) {
+            return Some(paths);
+        }
+        let mut paths = Self::resolve_default()?;
+        if let Some(dir) = profile_dir() {
+            paths.claude_dir = dir;
+        }
+        Some(paths)
+    }
+
+    /// Paths whatever profile is active: a scoped override, then the environment, then the real home folder
+    pub fn resolve_default() -> Option<Self> {
+        if let Some(paths) = OVERRIDE.with(|o| o.borrow().clone()) {
+            return Some(paths);
+        }
+
+        let home = std::env::var_os("CLAUDE_ARCADE_HOME")
+            .filter(|v| !v.is_empty())
+            .map(PathBuf::from)
+            .or_else(default_home)?;
+        let claude_dir = std::env::var_os("CLAUDE_CONFIG_DIR")
+            .filter(|v| !v.is_empty())
+            .map(PathBuf::from)
+            .unwrap_or_else(|| home.join(".claude"));
+        Some(ConfigPaths { home, claude_dir, project: None })
+    }
+
+    /// Same roots with a project opened
+    pub fn with_project(self, project_path: Option<&str>) -> Self {
+        ConfigPaths { project: project_path.map(PathBuf::from), ..self }
+    }
+
+    /// <project>/.claude, when a project is open
+    pub fn project_claude_dir(&self) -> Option<PathBuf> {
+        self.project.as_ref().map(|p| p.join(".claude"))
+    }
+
+    /// Refuse `target` unless it is inside the Claude folder or the open project, after `..` and
+    /// symlinks in the part that already exists are resolved
+    pub fn check_writable(&self, target: &Path) -> Result<(), ArcadeError> {
+        let target = resolve_for_write(target);
+        let allowed = std::iter::once(&self.claude_dir)
+            .chain(self.project.as_ref())
+            .any(|root| target.starts_with(resolve_for_write(root)));
+        if allowed {
+            Ok(())
+        } else {
+            Err(ArcadeError::invalid_input(format!(
+                "Refusing to write {}: it is outside the Claude folder and the project",
+                target.display()
+            )))
+        }
+    }
+
+    /// Everything under `root`: home is `root`, the Claude folder `root/.claude`
+    #[cfg(test)]
+    pub fn under(root: &std::path::Path) -> Self {
+        ConfigPaths { home: root.to_path_buf(), claude_dir: root.join(".claude"), project: None }
+    }
+
+    /// Run `f` with these paths in place of the real ones on this thread
+    pub fn scoped<T>(self, f: impl FnOnce() -> T) -> T {
+        let previous = OVERRIDE.with(|o| o.replace(Some(self)));
+        let result = f();
+        OVERRIDE.with(|o| *o.borrow_mut() = previous);
+        result
+    }
+}
+
+/// Attempts at replacing a file that is held open, on Windows
+const RENAME_ATTEMPTS: u64 = 5;
+
+/// Device names Windows won't create a file under, with or without an extension
+const RESERVED_NAMES: [&str; 22] = [
+    "CON", "PRN", "AUX", "NUL", "COM1", "COM2", "COM3", "COM4", "COM5", "COM6", "COM7", "COM8", "COM9",
+    "LPT1", "LPT2", "LPT3", "LPT4", "LPT5", "LPT6", "LPT7", "LPT8", "LPT9",
+];
+
+/// Home folder as Claude Code sees it. Node takes USERPROFILE on Windows, which can differ from
+/// the known-folder path `dirs` returns (redirected or roaming profiles).
+fn default_home() -> Option<PathBuf> {
+    #[cfg(windows)]
+    if let Some(profile) = std::env::var_os("USERPROFILE").filter(|v| !v.is_empty()) {
+        return Some(PathBuf::from(profile));
+    }
+    dirs::home_dir()
+}
+
+/// The active profile's Claude folder, when it isn't the default one
+pub fn profile_dir() -> Option<PathBuf> {
+    PROFILE_DIR.read().ok().and_then(|dir| dir.clone())
+}
+
+/// Point every scanner and command at another Claude folder, as CLAUDE_CONFIG_DIR would; None goes back
+/// to the default one
+pub fn set_profile_dir(dir: Option<PathBuf>) {
+    if let Ok(mut current) = PROFILE_DIR.write() {
+        *current = dir;
+    }
+}
+
+/// The user's home folder, or its override
+pub fn home_dir() -> Option<PathBuf> {
+    ConfigPaths::resolve().map(|p| p.home)
+}
+
+/// The Claude config folder (~/.claude), or its override
+pub fn claude_dir() -> Option<PathBuf> {
+    ConfigPaths::resolve().map(|p| p.claude_dir)
+}
+
+/// A single file or folder name taken from an id, a download or the user, which must stay inside
+/// the folder it is joined onto
+pub fn check_name(name: &str) -> Result<(), ArcadeError> {
+    let single = matches!(Path::new(name).components().collect::<Vec<_>>().as_slice(), [Component::Normal(_)]);
+    if name.is_empty() || !single || name.contains(['/', '\\', '\0']) || name.starts_with('.') {
+        return Err(ArcadeError::invalid_input(format!("Invalid item name: {}", name)));
+    }
+    // Names that work here but not on Windows would break the item for anyone syncing it there
+    let stem = name.split('.').next().unwrap_or_default();
+    let reserved = RESERVED_NAMES.iter().any(|r| r.eq_ignore_ascii_case(stem.trim_end()));
+    if reserved || name.contains(['<', '>', ':', '"', '|', '?', '*']) || name.ends_with(['.', ' ']) {
+        return Err(ArcadeError::invalid_input(format!("Invalid item name (not allowed on Windows): {}", name)));
+    }
+    Ok(())
+}
+
+/// Move the temp file `from` over `to`. Windows refuses to replace a file another process has open
+/// (Claude Code reading it, an editor, a virus scanner) for a moment, so the rename is retried there.
+pub fn replace_file(from: &Path, to: &Path) -> std::io::Result<()> {
+    let mut attempt = 1;
+    loop {
+        match std::fs::rename(from, to) {
+            Err(e) if cfg!(windows) && e.kind() == std::io::ErrorKind::PermissionDenied && attempt < RENAME_ATTEMPTS => {
+                std::thread::sleep(Duration::from_millis(50 * attempt));
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
+}
+
+/// Write `contents` to `path` so that a crash leaves either the old file or the new one, never
+/// a torn mix: the data goes to a temp file in the same folder, is synced to disk, and is then
+/// renamed over `path`. An existing file's permissions are kept.
+pub fn atomic_write(path: impl AsRef<Path>, contents: impl AsRef<[u8]>) -> std::io::Result<()> {
+    static TEMP_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+    let path = path.as_ref();
+    let name = path.file_name().ok_or_else(|| std::io::Error::new(std::io::ErrorKind::InvalidInput, "no file name"))?;
+    let temp_path = path.with_file_name(format!(
+        ".{}.{}-{}.tmp",
+        name.to_string_lossy(),
+        std::process::id(),
+        TEMP_COUNTER.fetch_add(1, Ordering::Relaxed)
+    ));
+
+    let written = (|| {
+        let mut file = File::create(&temp_path)?;
+        file.write_all(contents.as_ref())?;
+        file.sync_all()?;
+        if let Ok(metadata) = std::fs::metadata(path) {
+            std::fs::set_permissions(&temp_path, metadata.permissions())?;
+        }
+        replace_file(&temp_path, path)
+    })();
+    if written.is_err() {
+        let _ = std::fs::remove_file(&temp_path);
+        return written;
+    }
+
+    // The rename itself only survives a crash once the folder is synced; Windows has no
+    // equivalent for folders and makes the rename durable on its own
+    #[cfg(unix)]
+    if let Some(dir) = path.parent().filter(|d| !d.as_os_str().is_empty()) {
+        File::open(dir)?.sync_all()?;
+    }
+    Ok(())
+}
+
+/// Refuse a write to `target` outside the Claude folder and `project_path`
+pub fn check_writable(target: &Path, project_path: Option<&str>) -> Result<(), ArcadeError> {
+    ConfigPaths::resolve()
+        .ok_or("Could not find home directory")?
+        .with_project(project_path)
+        .check_writable(target)
+}
+
+/// `path` with `.` and `..` removed, and symlinks resolved in the longest part that exists
+fn resolve_for_write(path: &Path) -> PathBuf {
+    let mut normal = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normal.pop();
+            }
+            other => normal.push(other),
+        }
+    }
+
+    let mut existing = normal.as_path();
+    let mut missing = Vec::new();
+    while !existing.exists() {
+        let (Some(parent), Some(name)) = (existing.parent(), existing.file_name()) else {
+            break;
+        };
+        missing.push(name);
+        existing = parent;
+    }
+    let mut resolved = existing.canonicalize().unwrap_or_else(|_| existing.to_path_buf());
+    resolved.extend(missing.iter().rev());
+    resolved
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TempDir;
+
+    #[test]
+    fn refuses_writes_that_escape_the_roots() {
+        let root = TempDir::new("paths");
+        std::fs::create_dir_all(root.join(".claude/agents")).unwrap();
+        let paths = ConfigPaths::under(&root);
+
+        let inside = paths.check_writable(&root.join(".claude/agents/new/reviewer.md"));
+        let escaped = paths.check_writable(&root.join(".claude/agents/../../.bashrc"));
+
+        assert!(inside.is_ok());
+        assert!(escaped.is_err());
+        assert!(check_name("reviewer").is_ok());
+        assert!(check_name("../reviewer").is_err() && check_name("..").is_err());
+        assert!(check_name("con").is_err() && check_name("NUL.md").is_err() && check_name("a:b").is_err());
+        assert!(check_name("console").is_ok());
+    }
+
+    #[test]
+    fn replaces_an_existing_file() {
+        let root = TempDir::new("replace");
+        std::fs::write(root.join("CLAUDE.md"), "old").unwrap();
+        std::fs::write(root.join("CLAUDE.md.tmp"), "new").unwrap();
+
+        replace_file(&root.join("CLAUDE.md.tmp"), &root.join("CLAUDE.md")).unwrap();
+        let content = std::fs::read_to_string(root.join("CLAUDE.md")).unwrap();
+        assert_eq!(content, "new");
+    }
+
+    #[test]
+    fn writes_atomically() {
+        let root = TempDir::new("atomic");
+        let path = root.join("settings.json");
+        atomic_write(&path, "{}").unwrap();
+        atomic_write(&path, "{\"model\": \"opus\"}").unwrap();
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        let files = std::fs::read_dir(&root).unwrap().count();
+        assert_eq!((content.as_str(), files), ("{\"model\": \"opus\"}", 1));
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn home_follows_userprofile() {
+        let profile = std::env::var_os("USERPROFILE").map(PathBuf::from);
+        assert_eq!(default_home(), profile.or_else(dirs::home_dir));
+    }
+}
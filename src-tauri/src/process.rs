@@ -0,0 +1,383 @@
+//! Running external programs: git, MCP server probes, doctor checks and hook tests
+//! Every run goes through here, so only a few run at once and each has a timeout, a cap on the
+//! output kept, and can be cancelled from the frontend when given an id. Runs block their thread;
+//! commands call them inside `run_blocking`.
+
+use std::ffi::{OsStr, OsString};
+use std::io::{self, Read, Write};
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+/// Runs allowed at once; the rest wait for a slot
+pub const MAX_CONCURRENT: usize = 4;
+
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Output kept per stream; the rest is read and dropped so the program never blocks on a full pipe
+const DEFAULT_MAX_OUTPUT: usize = 1024 * 1024;
+
+const POLL_INTERVAL: Duration = Duration::from_millis(20);
+
+/// How long to wait for the output of a killed program, whose children may hold the pipes open
+const DRAIN_GRACE: Duration = Duration::from_millis(500);
+
+/// A program to run, built up like `std::process::Command`
+#[derive(Debug, Clone)]
+pub struct ProcessRequest {
+    program: OsString,
+    args: Vec<OsString>,
+    current_dir: Option<PathBuf>,
+    env: Vec<(String, String)>,
+    stdin: Option<Vec<u8>>,
+    timeout: Duration,
+    max_output: usize,
+    until: Option<String>,
+    id: Option<String>,
+}
+
+/// What a run produced. Output is raw bytes; `stdout_text` and `stderr_text` decode it.
+#[derive(Debug, Clone, Default)]
+pub struct ProcessOutput {
+    /// None when the program was killed, or ended by a signal
+    pub exit_code: Option<i32>,
+    pub stdout: Vec<u8>,
+    pub stderr: Vec<u8>,
+    /// Output went past the cap and was cut
+    pub truncated: bool,
+    pub timed_out: bool,
+    pub cancelled: bool,
+    pub duration: Duration,
+}
+
+impl ProcessOutput {
+    pub fn success(&self) -> bool {
+        self.exit_code == Some(0)
+    }
+
+    pub fn stdout_text(&self) -> String {
+        String::from_utf8_lossy(&self.stdout).to_string()
+    }
+
+    pub fn stderr_text(&self) -> String {
+        String::from_utf8_lossy(&self.stderr).to_string()
+    }
+}
+
+/// A run with an id, as `running_processes` lists it
+#[derive(Debug, Clone)]
+pub struct RunningProcess {
+    pub id: String,
+    pub program: String,
+    pub started: Instant,
+    cancel: Arc<AtomicBool>,
+}
+
+static RUNNING: Mutex<Vec<RunningProcess>> = Mutex::new(Vec::new());
+
+static SLOTS_TAKEN: Mutex<usize> = Mutex::new(0);
+static SLOT_FREED: Condvar = Condvar::new();
+
+/// A slot among the `MAX_CONCURRENT`, given back on drop
+struct Slot;
+
+impl Drop for Slot {
+    fn drop(&mut self) {
+        if let Ok(mut taken) = SLOTS_TAKEN.lock() {
+            *taken -= 1;
+        }
+        SLOT_FREED.notify_one();
+    }
+}
+
+/// Wait for a slot; None once cancelled
+fn take_slot(cancel: &AtomicBool) -> Option<Slot> {
+    let mut taken = SLOTS_TAKEN.lock().unwrap_or_else(|e| e.into_inner());
+    loop {
+        if cancel.load(Ordering::Relaxed) {
+            return None;
+        }
+        if *taken < MAX_CONCURRENT {
+            *taken += 1;
+            return Some(Slot);
+        }
+        taken = SLOT_FREED.wait_timeout(taken, POLL_INTERVAL).unwrap_or_else(|e| e.into_inner()).0;
+    }
+}
+
+/// Lists a run with an id while it lasts
+struct Registration(Arc<AtomicBool>);
+
+impl Registration {
+    fn new(id: Option<&str>, program: &OsStr) -> Self {
+        let cancel = Arc::new(AtomicBool::new(false));
+        if let (Some(id), Ok(mut running)) = (id, RUNNING.lock()) {
+            running.push(RunningProcess {
+                id: id.to_string(),
+                program: program.to_string_lossy().to_string(),
+                started: Instant::now(),
+                cancel: cancel.clone(),
+            });
+        }
+        Registration(cancel)
+    }
+}
+
+impl Drop for Registration {
+    fn drop(&mut self) {
+        if let Ok(mut running) = RUNNING.lock() {
+            running.retain(|p| !Arc::ptr_eq(&p.cancel, &self.0));
+        }
+    }
+}
+
+/// Runs started with an id that haven't finished, waiting for a slot included
+pub fn running_processes() -> Vec<RunningProcess> {
+    RUNNING.lock().map(|running| running.clone()).unwrap_or_default()
+}
+
+/// Stop every run with this id; returns whether there was one
+pub fn cancel(id: &str) -> bool {
+    let Ok(running) = RUNNING.lock() else {
+        return false;
+    };
+    let mut found = false;
+    for process in running.iter().filter(|p| p.id == id) {
+        process.cancel.store(true, Ordering::Relaxed);
+        found = true;
+    }
+    found
+}
+
+#[derive(Default)]
+struct Captured {
+    bytes: Vec<u8>,
+    truncated: bool,
+}
+
+/// Read a stream to its end on its own thread, keeping the first `cap` bytes
+fn capture(mut stream: impl Read + Send + 'static, cap: usize) -> (Arc<Mutex<Captured>>, JoinHandle<()>) {
+    let captured = Arc::new(Mutex::new(Captured::default()));
+    let sink = captured.clone();
+    let handle = thread::spawn(move || {
+        let mut buf = [0u8; 8192];
+        while let Ok(n) = stream.read(&mut buf) {
+            if n == 0 {
+                break;
+            }
+            let Ok(mut captured) = sink.lock() else {
+                break;
+            };
+            let room = cap.saturating_sub(captured.bytes.len());
+            captured.bytes.extend_from_slice(&buf[..n.min(room)]);
+            captured.truncated |= n > room;
+        }
+    });
+    (captured, handle)
+}
+
+fn take_captured(captured: &Mutex<Captured>) -> (Vec<u8>, bool) {
+    captured.lock().map(|mut c| (std::mem::take(&mut c.bytes), c.truncated)).unwrap_or_default()
+}
+
+fn contains(haystack: &[u8], needle: &[u8]) -> bool {
+    needle.is_empty() || haystack.windows(needle.len()).any(|w| w == needle)
+}
+
+impl ProcessRequest {
+    pub fn new(program: impl AsRef<OsStr>) -> Self {
+        ProcessRequest {
+            program: program.as_ref().to_os_string(),
+            args: Vec::new(),
+            current_dir: None,
+            env: Vec::new(),
+            stdin: None,
+            timeout: DEFAULT_TIMEOUT,
+            max_output: DEFAULT_MAX_OUTPUT,
+            until: None,
+            id: None,
+        }
+    }
+
+    pub fn arg(mut self, arg: impl AsRef<OsStr>) -> Self {
+        self.args.push(arg.as_ref().to_os_string());
+        self
+    }
+
+    pub fn args<I, S>(mut self, args: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<OsStr>,
+    {
+        self.args.extend(args.into_iter().map(|a| a.as_ref().to_os_string()));
+        self
+    }
+
+    pub fn current_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.current_dir = Some(dir.into());
+        self
+    }
+
+    pub fn envs(mut self, env: impl IntoIterator<Item = (String, String)>) -> Self {
+        self.env.extend(env);
+        self
+    }
+
+    /// Written to the program's stdin, which is then closed. Without it stdin is empty.
+    pub fn stdin(mut self, input: impl Into<Vec<u8>>) -> Self {
+        self.stdin = Some(input.into());
+        self
+    }
+
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Bytes kept of stdout and of stderr
+    pub fn max_output(mut self, bytes: usize) -> Self {
+        self.max_output = bytes;
+        self
+    }
+
+    /// Stop the program as soon as its stdout contains `text`, for servers that never exit
+    pub fn until(mut self, text: impl Into<String>) -> Self {
+        self.until = Some(text.into());
+        self
+    }
+
+    /// List the run under `id` so it can be cancelled
+    pub fn id(mut self, id: impl Into<String>) -> Self {
+        self.id = Some(id.into());
+        self
+    }
+
+    fn command(&self) -> Command {
+        let mut command = Command::new(&self.program);
+        command
+            .args(&self.args)
+            .envs(self.env.iter().map(|(k, v)| (k, v)))
+            .stdin(if self.stdin.is_some() { Stdio::piped() } else { Stdio::null() })
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+        if let Some(dir) = &self.current_dir {
+            command.current_dir(dir);
+        }
+        #[cfg(windows)]
+        {
+            use std::os::windows::process::CommandExt;
+            const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+            command.creation_flags(CREATE_NO_WINDOW);
+        }
+        command
+    }
+
+    /// Run the program to its end, its timeout, or cancellation. Errors only when it can't be
+    /// started; how it ended is in the output.
+    pub fn run(self) -> io::Result<ProcessOutput> {
+        let registration = Registration::new(self.id.as_deref(), &self.program);
+        let cancelled = || registration.0.load(Ordering::Relaxed);
+        let Some(_slot) = take_slot(&registration.0) else {
+            return Ok(ProcessOutput { cancelled: true, ..Default::default() });
+        };
+
+        let started = Instant::now();
+        let mut child = self.command().spawn()?;
+        if let (Some(input), Some(mut stdin)) = (self.stdin.clone(), child.stdin.take()) {
+            // Its own thread, so a program that doesn't read its input can't stall this one
+            thread::spawn(move || {
+                let _ = stdin.write_all(&input);
+            });
+        }
+        let (stdout, stdout_reader) = capture(child.stdout.take().expect("stdout is piped"), self.max_output);
+        let (stderr, stderr_reader) = capture(child.stderr.take().expect("stderr is piped"), self.max_output);
+
+        let mut output = ProcessOutput::default();
+        let status = loop {
+            if let Some(status) = child.try_wait()? {
+                break Some(status);
+            }
+            if cancelled() {
+                output.cancelled = true;
+                break None;
+            }
+            if started.elapsed() >= self.timeout {
+                output.timed_out = true;
+                break None;
+            }
+            let done = self.until.as_ref().is_some_and(|text| {
+                stdout.lock().is_ok_and(|captured| contains(&captured.bytes, text.as_bytes()))
+            });
+            if done {
+                break None;
+            }
+            thread::sleep(POLL_INTERVAL);
+        };
+        if status.is_none() {
+            let _ = child.kill();
+            let _ = child.wait();
+        }
+
+        let drained = Instant::now();
+        while !(stdout_reader.is_finished() && stderr_reader.is_finished()) && drained.elapsed() < DRAIN_GRACE {
+            thread::sleep(POLL_INTERVAL);
+        }
+        let (stdout_bytes, stdout_cut) = take_captured(&stdout);
+        let (stderr_bytes, stderr_cut) = take_captured(&stderr);
+        output.exit_code = status.and_then(|s| s.code());
+        output.stdout = stdout_bytes;
+        output.stderr = stderr_bytes;
+        output.truncated = stdout_cut || stderr_cut;
+        output.duration = started.elapsed();
+        tracing::debug!(
+            program = %self.program.to_string_lossy(),
+            exit_code = ?output.exit_code,
+            timed_out = output.timed_out,
+            cancelled = output.cancelled,
+            ms = output.duration.as_millis() as u64,
+            "Process finished"
+        );
+        Ok(output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reports_programs_that_cannot_start() {
+        assert!(ProcessRequest::new("arcade-no-such-program").id("test-missing").run().is_err());
+        assert!(!running_processes().iter().any(|p| p.id == "test-missing"));
+        assert!(!cancel("test-missing"));
+    }
+
+    // Drives sh and sleep, which only Unix has
+    #[cfg(unix)]
+    #[test]
+    fn caps_output_and_stops_slow_programs() {
+        let output = ProcessRequest::new("sh").args(["-c", "cat; echo oops >&2; exit 3"]).stdin("hello").run().unwrap();
+        assert_eq!((output.exit_code, output.stdout_text(), output.stderr_text()), (Some(3), "hello".to_string(), "oops\n".to_string()));
+
+        let output = ProcessRequest::new("sh").args(["-c", "printf 0123456789"]).max_output(4).run().unwrap();
+        assert_eq!((output.stdout_text(), output.truncated), ("0123".to_string(), true));
+
+        let output = ProcessRequest::new("sleep").arg("5").timeout(Duration::from_millis(100)).run().unwrap();
+        assert!(output.timed_out && !output.success());
+        assert!(output.duration < Duration::from_secs(2));
+
+        let output = ProcessRequest::new("sh").args(["-c", "echo ready; sleep 5"]).until("ready").run().unwrap();
+        assert!(!output.timed_out && output.duration < Duration::from_secs(2));
+
+        let waiting = thread::spawn(|| ProcessRequest::new("sleep").arg("5").id("test-sleep").run().unwrap());
+        while !running_processes().iter().any(|p| p.id == "test-sleep") {
+            thread::sleep(POLL_INTERVAL);
+        }
+        assert!(cancel("test-sleep"));
+        assert!(waiting.join().unwrap().cancelled);
+        assert!(!cancel("test-sleep"));
+    }
+}
@@ -1,11 +1,14 @@
 use portable_pty::{native_pty_system, CommandBuilder, PtySize};
 use std::collections::HashMap;
 use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
 use std::thread;
 use tauri::{AppHandle, Emitter};
 use uuid::Uuid;
 
+use crate::scanner::prerequisites::find_binary;
+
 pub struct PtyInstance {
     writer: Box<dyn Write + Send>,
     master: Box<dyn portable_pty::MasterPty + Send>,
@@ -28,6 +31,47 @@ impl PtyManager {
         cols: u16,
         rows: u16,
         cwd: Option<String>,
+    ) -> Result<String, String> {
+        let mut cmd = CommandBuilder::new_default_prog();
+
+        // Set working directory if provided
+        if let Some(dir) = cwd {
+            cmd.cwd(dir);
+        }
+
+        self.spawn_command(app_handle, cols, rows, cmd)
+    }
+
+    /// Run the Claude Code CLI directly, optionally resuming a past session
+    pub fn spawn_claude(
+        &self,
+        app_handle: AppHandle,
+        cols: u16,
+        rows: u16,
+        cwd: Option<String>,
+        resume_session: Option<String>,
+    ) -> Result<String, String> {
+        let claude = find_claude_binary()
+            .ok_or("Claude Code CLI not found. Install it with: npm install -g @anthropic-ai/claude-code")?;
+        let mut cmd = command_for(&claude);
+
+        if let Some(session_id) = resume_session {
+            cmd.arg("--resume");
+            cmd.arg(session_id);
+        }
+        if let Some(dir) = cwd {
+            cmd.cwd(dir);
+        }
+
+        self.spawn_command(app_handle, cols, rows, cmd)
+    }
+
+    fn spawn_command(
+        &self,
+        app_handle: AppHandle,
+        cols: u16,
+        rows: u16,
+        mut cmd: CommandBuilder,
     ) -> Result<String, String> {
         let pty_system = native_pty_system();
 
@@ -40,16 +84,13 @@ impl PtyManager {
             })
             .map_err(|e| format!("Failed to open PTY: {}", e))?;
 
-        let mut cmd = CommandBuilder::new_default_prog();
-
-        // Set working directory if provided
-        if let Some(dir) = cwd {
-            cmd.cwd(dir);
-        }
-
         // Set up environment for interactive shell
         cmd.env("TERM", "xterm-256color");
         cmd.env("COLORTERM", "truecolor");
+        // So claude in the terminal uses the same config profile as the app
+        if let Some(dir) = crate::paths::profile_dir() {
+            cmd.env("CLAUDE_CONFIG_DIR", dir);
+        }
 
         let mut child = pair
             .slave
@@ -173,6 +214,30 @@ impl PtyManager {
     }
 }
 
+/// The `claude` binary, including the per-user install made by `claude migrate-installer`
+/// A command running `program`. npm installs `claude` as a `.cmd` shim on Windows, which
+/// CreateProcess can't start by itself, so batch files are run through cmd.exe.
+fn command_for(program: &Path) -> CommandBuilder {
+    let is_batch = program
+        .extension()
+        .is_some_and(|e| e.eq_ignore_ascii_case("cmd") || e.eq_ignore_ascii_case("bat"));
+    if cfg!(windows) && is_batch {
+        let mut cmd = CommandBuilder::new("cmd.exe");
+        cmd.arg("/C");
+        cmd.arg(program);
+        cmd
+    } else {
+        CommandBuilder::new(program)
+    }
+}
+
+fn find_claude_binary() -> Option<PathBuf> {
+    find_binary("claude").or_else(|| {
+        let local = crate::paths::claude_dir()?.join("local").join(if cfg!(windows) { "claude.cmd" } else { "claude" });
+        local.is_file().then_some(local)
+    })
+}
+
 impl Default for PtyManager {
     fn default() -> Self {
         Self::new()
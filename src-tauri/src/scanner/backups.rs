@@ -0,0 +1,141 @@
+//! Rotating backups of settings files, taken before every write ClaudeArcade makes.
+//! Backups live in ~/.claude/arcade_backups with an index recording where each came from.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+
+use super::plugin::claude_config_dir;
+
+/// Backups kept per settings file before the oldest are deleted
+const MAX_BACKUPS_PER_FILE: usize = 20;
+
+/// A single backup of a settings file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SettingsBackup {
+    pub id: String,
+    /// The settings file this is a copy of
+    pub source_path: String,
+    /// RFC3339 time the backup was taken
+    pub created_at: String,
+    pub size_bytes: u64,
+}
+
+/// Get the directory backups are stored in
+pub fn backups_dir() -> Option<PathBuf> {
+    claude_config_dir().map(|d| d.join("arcade_backups"))
+}
+
+fn index_path() -> Option<PathBuf> {
+    backups_dir().map(|d| d.join("index.json"))
+}
+
+fn backup_file_path(id: &str) -> Option<PathBuf> {
+    backups_dir().map(|d| d.join(format!("{}.json", id)))
+}
+
+/// Held across every load-change-save of the index: commands run side by side on the blocking
+/// pool, and two backups taken at once would otherwise drop one of their entries
+static INDEX_LOCK: Mutex<()> = Mutex::new(());
+
+fn lock_index() -> MutexGuard<'static, ()> {
+    INDEX_LOCK.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+fn load_index() -> Vec<SettingsBackup> {
+    index_path()
+        .and_then(|p| fs::read_to_string(p).ok())
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn save_index(index: &[SettingsBackup]) -> Result<(), String> {
+    let path = index_path().ok_or("Could not find home directory")?;
+    let content = serde_json::to_string_pretty(index).map_err(|e| e.to_string())?;
+    crate::paths::atomic_write(&path, content).map_err(|e| e.to_string())
+}
+
+/// Copy a settings file into the backup store, rotating out old backups of the same file.
+/// Returns None when the file doesn't exist yet (nothing to back up).
+pub fn backup_file(path: &Path) -> Result<Option<SettingsBackup>, String> {
+    if !path.is_file() {
+        return Ok(None);
+    }
+
+    let dir = backups_dir().ok_or("Could not find home directory")?;
+    fs::create_dir_all(&dir).map_err(|e| format!("Failed to create backup directory: {}", e))?;
+
+    let now = Utc::now();
+    let suffix = uuid::Uuid::new_v4().simple().to_string();
+    let id = format!("{}-{}", now.format("%Y%m%dT%H%M%S%3f"), &suffix[..8]);
+    let target = dir.join(format!("{}.json", id));
+
+    let size_bytes = fs::copy(path, &target).map_err(|e| format!("Failed to back up settings: {}", e))?;
+
+    let backup = SettingsBackup {
+        id,
+        source_path: path.to_string_lossy().to_string(),
+        created_at: now.to_rfc3339(),
+        size_bytes,
+    };
+
+    let _lock = lock_index();
+    let mut index = load_index();
+    index.push(backup.clone());
+
+    // Rotate: drop the oldest backups of this file beyond the limit
+    let source = backup.source_path.clone();
+    let same_source = index.iter().filter(|b| b.source_path == source).count();
+    if same_source > MAX_BACKUPS_PER_FILE {
+        let mut excess = same_source - MAX_BACKUPS_PER_FILE;
+        index.retain(|b| {
+            if excess > 0 && b.source_path == source {
+                excess -= 1;
+                if let Some(file) = backup_file_path(&b.id) {
+                    let _ = fs::remove_file(file);
+                }
+                return false;
+            }
+            true
+        });
+    }
+
+    save_index(&index)?;
+    Ok(Some(backup))
+}
+
+/// List backups, newest first, optionally only those of one settings file
+pub fn list_backups(source_path: Option<&Path>) -> Vec<SettingsBackup> {
+    let mut backups: Vec<SettingsBackup> = load_index()
+        .into_iter()
+        .filter(|b| source_path.is_none_or(|p| Path::new(&b.source_path) == p))
+        .collect();
+    backups.reverse();
+    backups
+}
+
+/// Restore a backup over its source file.
+/// The current file is backed up first so a restore can itself be undone.
+pub fn restore_backup(id: &str) -> Result<SettingsBackup, String> {
+    let backup = load_index()
+        .into_iter()
+        .find(|b| b.id == id)
+        .ok_or_else(|| format!("Backup not found: {}", id))?;
+
+    let file = backup_file_path(&backup.id).ok_or("Could not find home directory")?;
+    let content = fs::read_to_string(&file).map_err(|e| format!("Failed to read backup: {}", e))?;
+
+    let target = PathBuf::from(&backup.source_path);
+    backup_file(&target)?;
+
+    if let Some(parent) = target.parent() {
+        fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+
+    crate::watcher::write_expected(&target, &content).map_err(|e| format!("Failed to restore backup: {}", e))?;
+
+    Ok(backup)
+}
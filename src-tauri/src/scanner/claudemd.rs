@@ -1,10 +1,10 @@
 //! Scanner for CLAUDE.md memory files
 //! These files shape Claude's behavior and provide system-level context.
 
-use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use crate::types::{InventoryItem, ItemType, ItemRarity, ItemSource};
+use super::limits::read_capped;
 use super::plugin::claude_config_dir;
 
 /// Scope of the CLAUDE.md file
@@ -93,12 +93,12 @@ fn extract_description(content: &str) -> String {
 }
 
 /// Scan a specific CLAUDE.md location
-fn scan_claudemd_file(path: &PathBuf, scope: ClaudeMdScope) -> Option<InventoryItem> {
+fn scan_claudemd_file(path: &Path, scope: ClaudeMdScope) -> Option<InventoryItem> {
     if !path.exists() {
         return None;
     }
 
-    let content = fs::read_to_string(path).ok()?;
+    let content = read_capped(path).ok()?;
 
     if content.trim().is_empty() {
         return None;
@@ -142,6 +142,7 @@ fn scan_claudemd_file(path: &PathBuf, scope: ClaudeMdScope) -> Option<InventoryI
         version: None,
         author: None,
         status: None,
+        environment: None,
     })
 }
 
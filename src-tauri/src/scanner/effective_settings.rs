@@ -0,0 +1,179 @@
+//! Effective settings: every settings scope merged in Claude Code's precedence order
+//! (enterprise > local > project > user), with the file each value came from.
+
+use std::collections::BTreeMap;
+use serde::Serialize;
+use serde_json::Value;
+
+use super::permissions::PermissionScope;
+use super::settings::load_settings_file;
+
+/// Array settings that are combined across scopes rather than overridden
+const MERGED_ARRAYS: [&str; 4] = [
+    "permissions.allow",
+    "permissions.ask",
+    "permissions.deny",
+    "permissions.additionalDirectories",
+];
+
+/// A value a higher-precedence scope overrode
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ShadowedValue {
+    pub scope: PermissionScope,
+    pub value: Value,
+}
+
+/// Where the effective value of one (dotted) settings key comes from
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SettingProvenance {
+    pub key: String,
+    /// The scope whose value wins
+    pub scope: PermissionScope,
+    pub source_path: String,
+    /// Lower-precedence values for this key that were overridden
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub shadowed: Vec<ShadowedValue>,
+    /// For combined arrays, every scope that contributed entries
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub merged_from: Vec<PermissionScope>,
+}
+
+/// A settings file that took part in the merge
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SettingsSource {
+    pub scope: PermissionScope,
+    pub path: String,
+    pub exists: bool,
+    /// Parse error, when the file exists but was skipped
+    pub error: Option<String>,
+}
+
+/// The merged settings document with per-key provenance
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EffectiveSettings {
+    pub settings: Value,
+    pub provenance: Vec<SettingProvenance>,
+    pub sources: Vec<SettingsSource>,
+}
+
+/// Merge every settings scope for a project (user scope only when no project is given)
+pub fn effective_settings(project_path: Option<&str>) -> EffectiveSettings {
+    let mut merged = Value::Object(serde_json::Map::new());
+    let mut provenance: BTreeMap<String, SettingProvenance> = BTreeMap::new();
+    let mut sources = Vec::new();
+
+    // Apply lowest precedence first so later scopes override
+    for scope in PermissionScope::ALL.iter().rev().copied() {
+        let path = match scope.settings_path(project_path) {
+            Some(p) => p,
+            None => continue,
+        };
+        let source_path = path.to_string_lossy().to_string();
+
+        let (settings, error) = match load_settings_file(&path) {
+            Ok(settings) => (settings, None),
+            Err(e) => (None, Some(e.to_string())),
+        };
+        sources.push(SettingsSource {
+            scope,
+            path: source_path.clone(),
+            exists: path.exists(),
+            error,
+        });
+
+        if let Some(Value::Object(settings)) = settings {
+            let layer = Layer { scope, source_path: &source_path };
+            apply_object(&layer, &settings, "", &mut merged, &mut provenance);
+        }
+    }
+
+    // Report sources highest precedence first, like the permissions view
+    sources.reverse();
+
+    EffectiveSettings {
+        settings: merged,
+        provenance: provenance.into_values().collect(),
+        sources,
+    }
+}
+
+/// The scope currently being merged in
+struct Layer<'a> {
+    scope: PermissionScope,
+    source_path: &'a str,
+}
+
+fn apply_object(
+    layer: &Layer,
+    incoming: &serde_json::Map<String, Value>,
+    prefix: &str,
+    target: &mut Value,
+    provenance: &mut BTreeMap<String, SettingProvenance>,
+) {
+    if !target.is_object() {
+        *target = Value::Object(serde_json::Map::new());
+    }
+    let target = match target.as_object_mut() {
+        Some(t) => t,
+        None => return,
+    };
+
+    for (key, value) in incoming {
+        let full_key = if prefix.is_empty() { key.clone() } else { format!("{}.{}", prefix, key) };
+        let existing = target.get_mut(key);
+
+        match (value, existing) {
+            // Nested objects merge key by key (unless a lower scope set a scalar here)
+            (Value::Object(inner), Some(existing)) if existing.is_object() => {
+                apply_object(layer, inner, &full_key, existing, provenance);
+            }
+            (Value::Object(inner), None) if !inner.is_empty() => {
+                let slot = target.entry(key.clone()).or_insert(Value::Null);
+                apply_object(layer, inner, &full_key, slot, provenance);
+            }
+            // Rule lists are combined across scopes
+            (Value::Array(items), Some(Value::Array(existing))) if MERGED_ARRAYS.contains(&full_key.as_str()) => {
+                for item in items {
+                    if !existing.contains(item) {
+                        existing.push(item.clone());
+                    }
+                }
+                if let Some(entry) = provenance.get_mut(&full_key) {
+                    if entry.merged_from.is_empty() {
+                        entry.merged_from.push(entry.scope);
+                    }
+                    entry.merged_from.push(layer.scope);
+                    entry.scope = layer.scope;
+                    entry.source_path = layer.source_path.to_string();
+                }
+            }
+            // Anything else: the higher scope replaces the value outright
+            (_, existing) => {
+                let mut shadowed = Vec::new();
+                if let Some(previous) = existing {
+                    if let Some(old) = provenance.remove(&full_key) {
+                        shadowed = old.shadowed;
+                        shadowed.insert(0, ShadowedValue { scope: old.scope, value: previous.clone() });
+                    }
+                }
+
+                // An overridden object takes its children's provenance with it
+                let child_prefix = format!("{}.", full_key);
+                provenance.retain(|k, _| !k.starts_with(&child_prefix));
+
+                target.insert(key.clone(), value.clone());
+                provenance.insert(full_key.clone(), SettingProvenance {
+                    key: full_key,
+                    scope: layer.scope,
+                    source_path: layer.source_path.to_string(),
+                    shadowed,
+                    merged_from: Vec::new(),
+                });
+            }
+        }
+    }
+}
@@ -0,0 +1,202 @@
+//! YAML frontmatter of command, skill and agent markdown files, kept as written
+//! Fields are held as their original lines, so changing one key leaves every other key, comment,
+//! the key order and the body byte-for-byte as they were. A document nothing was changed in
+//! serializes back to exactly the input.
+
+use serde::de::DeserializeOwned;
+use serde_yaml::{Mapping, Value};
+
+/// One top-level key with its continuation lines (list items, indented values, blank lines)
+#[derive(Debug, Clone, PartialEq)]
+struct Field {
+    /// None for top-level comments and lines before the first key
+    key: Option<String>,
+    raw: String,
+}
+
+/// A markdown file split into its frontmatter fields and body
+#[derive(Debug, Clone, PartialEq)]
+pub struct Frontmatter {
+    /// Byte order mark and blank lines ahead of the opening `---`
+    prefix: String,
+    /// None when the file has no frontmatter
+    fences: Option<(String, String)>,
+    fields: Vec<Field>,
+    body: String,
+    /// Line ending new fields are written with: the file's own
+    newline: &'static str,
+}
+
+/// The key a top-level `key: value` line starts, if it starts one
+fn field_key(line: &str) -> Option<String> {
+    let first = line.chars().next()?;
+    if first.is_whitespace() || matches!(first, '#' | '-') {
+        return None;
+    }
+    let (key, _) = line.split_once(':')?;
+    Some(key.trim().trim_matches(['"', '\'']).to_string())
+}
+
+/// Blank lines at the end of a field, kept when its value is replaced
+fn trailing_blank_lines(raw: &str) -> &str {
+    let content_end = raw
+        .split_inclusive('\n')
+        .scan(0, |end, line| {
+            *end += line.len();
+            Some((*end, line))
+        })
+        .filter(|(_, line)| !line.trim().is_empty())
+        .last()
+        .map_or(0, |(end, _)| end);
+    &raw[content_end..]
+}
+
+fn is_fence(line: &str) -> bool {
+    line.trim_end() == "---"
+}
+
+impl Frontmatter {
+    /// Split `content`; without a closed `---` block at the top the whole content is the body
+    pub fn parse(content: &str) -> Self {
+        let newline = if content.contains("\r\n") { "\r\n" } else { "\n" };
+        let no_frontmatter = || Frontmatter {
+            prefix: String::new(),
+            fences: None,
+            fields: Vec::new(),
+            body: content.to_string(),
+            newline,
+        };
+
+        let mut lines = content.split_inclusive('\n');
+        let mut prefix = String::new();
+        let opening = loop {
+            match lines.next() {
+                Some(line) if is_fence(line.trim_start_matches('\u{feff}')) => break line,
+                Some(line) if line.trim_start_matches('\u{feff}').trim().is_empty() => prefix.push_str(line),
+                _ => return no_frontmatter(),
+            }
+        };
+
+        let mut fields: Vec<Field> = Vec::new();
+        let mut closing = None;
+        for line in lines.by_ref() {
+            if is_fence(line) {
+                closing = Some(line);
+                break;
+            }
+            let key = field_key(line);
+            match fields.last_mut() {
+                Some(field) if key.is_none() && !line.starts_with('#') => field.raw.push_str(line),
+                _ => fields.push(Field { key, raw: line.to_string() }),
+            }
+        }
+        let Some(closing) = closing else {
+            return no_frontmatter();
+        };
+
+        Frontmatter {
+            prefix,
+            fences: Some((opening.to_string(), closing.to_string())),
+            fields,
+            body: lines.collect(),
+            newline,
+        }
+    }
+
+    pub fn has_frontmatter(&self) -> bool {
+        self.fences.is_some()
+    }
+
+    /// The YAML between the fences, as written
+    pub fn yaml(&self) -> String {
+        self.fields.iter().map(|f| f.raw.as_str()).collect()
+    }
+
+    /// The fields as a typed struct; None without frontmatter or when it isn't valid YAML
+    pub fn deserialize<T: DeserializeOwned>(&self) -> Option<T> {
+        self.has_frontmatter().then(|| serde_yaml::from_str(&self.yaml()).ok()).flatten()
+    }
+
+    /// One field's value
+    pub fn get(&self, key: &str) -> Option<Value> {
+        let field = self.fields.iter().find(|f| f.key.as_deref() == Some(key))?;
+        let mapping: Mapping = serde_yaml::from_str(&field.raw).ok()?;
+        mapping.get(key).cloned()
+    }
+
+    /// Text after the closing fence, or all of it without frontmatter
+    pub fn body(&self) -> &str {
+        &self.body
+    }
+
+    pub fn set_body(&mut self, body: impl Into<String>) {
+        self.body = body.into();
+    }
+
+    /// Set a field in place, or add it after the others. Setting the value it already has changes nothing.
+    pub fn set(&mut self, key: &str, value: impl Into<Value>) -> Result<(), String> {
+        let value = value.into();
+        if self.get(key).as_ref() == Some(&value) {
+            return Ok(());
+        }
+        let mut mapping = Mapping::new();
+        mapping.insert(key.into(), value);
+        let raw = serde_yaml::to_string(&mapping).map_err(|e| e.to_string())?.replace('\n', self.newline);
+
+        if self.fences.is_none() {
+            self.fences = Some((format!("---{}", self.newline), format!("---{}", self.newline)));
+        }
+        match self.fields.iter_mut().find(|f| f.key.as_deref() == Some(key)) {
+            Some(field) => field.raw = raw + trailing_blank_lines(&field.raw),
+            None => self.fields.push(Field { key: Some(key.to_string()), raw }),
+        }
+        Ok(())
+    }
+
+    /// Drop a field; true if it was there
+    pub fn remove(&mut self, key: &str) -> bool {
+        let before = self.fields.len();
+        self.fields.retain(|f| f.key.as_deref() != Some(key));
+        self.fields.len() != before
+    }
+}
+
+impl std::fmt::Display for Frontmatter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.prefix)?;
+        if let Some((opening, closing)) = &self.fences {
+            f.write_str(opening)?;
+            f.write_str(&self.yaml())?;
+            f.write_str(closing)?;
+        }
+        f.write_str(&self.body)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_and_edits_in_place() {
+        let content = "\u{feff}---\r\nname: review\r\n\r\n# kept\r\ntools:\r\n  - Read\r\n  - Grep\r\nx-custom: {a: 1}\r\n---\r\n\r\n  Body  \r\n";
+        let mut doc = Frontmatter::parse(content);
+        assert_eq!(doc.to_string(), content);
+        assert_eq!(doc.get("tools"), Some(Value::Sequence(vec!["Read".into(), "Grep".into()])));
+
+        doc.set("name", "review").unwrap();
+        assert_eq!(doc.to_string(), content);
+
+        doc.set("name", "audit").unwrap();
+        doc.set("model", "sonnet").unwrap();
+        assert_eq!(
+            doc.to_string(),
+            "\u{feff}---\r\nname: audit\r\n\r\n# kept\r\ntools:\r\n  - Read\r\n  - Grep\r\nx-custom: {a: 1}\r\nmodel: sonnet\r\n---\r\n\r\n  Body  \r\n"
+        );
+
+        let plain = Frontmatter::parse("No frontmatter --- here\n");
+        assert!(!plain.has_frontmatter());
+        assert_eq!(plain.to_string(), "No frontmatter --- here\n");
+        assert!(!Frontmatter::parse("---\nname: open\n").has_frontmatter());
+    }
+}
@@ -201,7 +201,7 @@ fn generate_hook_name(event: &HookEvent, matcher: &Option<String>, command: &str
             .split_whitespace()
             .next()
             .unwrap_or("Hook")
-            .split('/')
+            .split(['/', '\\'])
             .last()
             .unwrap_or("Hook");
         format!("{}: {}", event.as_str(), first_word)
@@ -263,6 +263,7 @@ fn scan_hooks_from_settings(settings: &SettingsFile, scope: HookScope) -> Vec<In
                 version: None,
                 author: None,
                 status: None,
+                environment: None,
             });
         }
     }
@@ -0,0 +1,199 @@
+//! On-disk inventory index in ~/.claude/arcade_index.json
+//! Each scanner's items are stored with a fingerprint of the files it reads, so a scan only
+//! re-runs scanners whose files changed and startup can render the last inventory at once.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::path::PathBuf;
+use std::time::UNIX_EPOCH;
+use walkdir::WalkDir;
+
+use super::plugin::claude_config_dir;
+use crate::paths::ConfigPaths;
+use crate::types::{InventoryItem, ScanResult};
+
+/// Bump when the index layout changes; an index with another version is rebuilt from scratch
+const INDEX_SCHEMA_VERSION: u32 = 1;
+
+/// Projects whose last inventory is kept
+const MAX_INDEXED_PROJECTS: usize = 20;
+
+/// Sections in the order their items are listed
+pub const SECTIONS: [&str; 6] = ["plugins", "commands", "skills", "hooks", "agents", "claudeMd"];
+
+/// One scanner's output and the fingerprint of what it read
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IndexedSection {
+    pub fingerprint: u64,
+    pub items: Vec<InventoryItem>,
+    #[serde(default)]
+    pub errors: Vec<String>,
+}
+
+/// The last full scan for one project (or for no project)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IndexedInventory {
+    /// Milliseconds since the epoch
+    pub saved_at: u64,
+    pub scan_duration_ms: u64,
+    /// Keyed by section name
+    pub sections: HashMap<String, IndexedSection>,
+}
+
+impl IndexedInventory {
+    pub fn to_scan_result(&self) -> ScanResult {
+        let sections: Vec<&IndexedSection> = SECTIONS.iter().filter_map(|name| self.sections.get(*name)).collect();
+        ScanResult {
+            items: sections.iter().flat_map(|s| s.items.clone()).collect(),
+            errors: sections.iter().flat_map(|s| s.errors.clone()).collect(),
+            scan_duration_ms: self.scan_duration_ms,
+            metrics: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct InventoryIndex {
+    #[serde(default)]
+    schema_version: u32,
+    /// Scanners change between releases, so items from another version aren't reused
+    #[serde(default)]
+    app_version: String,
+    /// Keyed by project path, "" for the user-level inventory
+    projects: HashMap<String, IndexedInventory>,
+}
+
+fn index_path() -> Option<PathBuf> {
+    claude_config_dir().map(|d| d.join("arcade_index.json"))
+}
+
+fn load_index() -> InventoryIndex {
+    index_path()
+        .and_then(|path| crate::store::INDEX.load::<InventoryIndex>(&path))
+        .filter(|index| index.schema_version == INDEX_SCHEMA_VERSION && index.app_version == env!("CARGO_PKG_VERSION"))
+        .unwrap_or_default()
+}
+
+fn project_key(project_path: Option<&str>) -> String {
+    project_path.unwrap_or_default().to_string()
+}
+
+/// The indexed inventory for a project, if it has been scanned by this version
+pub fn load_indexed_inventory(project_path: Option<&str>) -> Option<IndexedInventory> {
+    load_index().projects.remove(&project_key(project_path))
+}
+
+/// Replace a project's indexed inventory, dropping the least recently scanned projects
+pub fn save_indexed_inventory(project_path: Option<&str>, inventory: IndexedInventory) -> Result<(), String> {
+    let path = index_path().ok_or("Could not find home directory")?;
+    let mut index = load_index();
+    index.schema_version = INDEX_SCHEMA_VERSION;
+    index.app_version = env!("CARGO_PKG_VERSION").to_string();
+    index.projects.insert(project_key(project_path), inventory);
+
+    if index.projects.len() > MAX_INDEXED_PROJECTS {
+        let mut by_age: Vec<(String, u64)> = index.projects.iter().map(|(k, v)| (k.clone(), v.saved_at)).collect();
+        by_age.sort_by_key(|(_, saved_at)| std::cmp::Reverse(*saved_at));
+        for (key, _) in by_age.into_iter().skip(MAX_INDEXED_PROJECTS) {
+            index.projects.remove(&key);
+        }
+    }
+
+    let content = crate::store::INDEX.to_json(&index)?;
+    crate::paths::atomic_write(&path, content).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+/// Files and folders (with how deep to look into them) a section's scanner reads
+fn section_sources(section: &str, paths: &ConfigPaths) -> Vec<(PathBuf, usize)> {
+    let user = &paths.claude_dir;
+    let project = paths.project.as_ref();
+    let mut sources = Vec::new();
+    let mut both = |relative: &str, depth: usize| {
+        sources.push((user.join(relative), depth));
+        sources.extend(paths.project_claude_dir().map(|d| (d.join(relative), depth)));
+    };
+
+    match section {
+        "commands" => both("commands", 8),
+        "skills" => {
+            both("skills", 8);
+            both(super::skills::DISABLED_SKILLS_DIR, 8);
+        }
+        "agents" => both("agents", 8),
+        "hooks" => both("settings.json", 0),
+        "claudeMd" => {
+            sources.push((user.join("CLAUDE.md"), 0));
+            if let Some(p) = project {
+                sources.push((p.join("CLAUDE.md"), 0));
+                sources.push((p.join(".claude").join("CLAUDE.md"), 0));
+                sources.push((p.join("CLAUDE.local.md"), 0));
+            }
+        }
+        "plugins" => {
+            let plugins = user.join("plugins");
+            sources.push((user.join("settings.json"), 0));
+            sources.push((plugins.join("installed_plugins.json"), 0));
+            sources.push((plugins.join("known_marketplaces.json"), 0));
+            sources.push((plugins.join("marketplaces"), 3));
+        }
+        _ => {}
+    }
+    sources
+}
+
+/// Hash of the path, size and modification time of everything a section reads, and how many
+/// files and folders that was. Plugins also depend on PATH, since it decides which
+/// prerequisites are missing.
+pub fn section_fingerprint(section: &str, paths: &ConfigPaths) -> (u64, usize) {
+    let mut file_count = 0;
+    let mut hasher = DefaultHasher::new();
+    section.hash(&mut hasher);
+    if section == "plugins" {
+        std::env::var_os("PATH").hash(&mut hasher);
+    }
+    for (root, depth) in section_sources(section, paths) {
+        root.hash(&mut hasher);
+        for entry in WalkDir::new(&root).max_depth(depth).sort_by_file_name().into_iter().filter_map(|e| e.ok()) {
+            let Ok(metadata) = entry.metadata() else {
+                continue;
+            };
+            let modified = metadata
+                .modified()
+                .ok()
+                .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+                .map(|d| d.as_nanos())
+                .unwrap_or(0);
+            entry.path().hash(&mut hasher);
+            metadata.len().hash(&mut hasher);
+            modified.hash(&mut hasher);
+            file_count += 1;
+        }
+    }
+    (hasher.finish(), file_count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TempDir;
+    use std::fs;
+
+    #[test]
+    fn fingerprint_changes_with_the_files_read() {
+        let dir = TempDir::new("index");
+        fs::create_dir_all(dir.join(".claude/commands")).unwrap();
+        let paths = ConfigPaths::under(&dir);
+
+        let (before, _) = section_fingerprint("commands", &paths);
+        assert_eq!(before, section_fingerprint("commands", &paths).0);
+        fs::write(dir.join(".claude/commands/review.md"), "Review").unwrap();
+        let (after, file_count) = section_fingerprint("commands", &paths);
+
+        assert_ne!(before, after);
+        assert_eq!(file_count, 2);
+    }
+}
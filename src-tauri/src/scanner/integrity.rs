@@ -0,0 +1,175 @@
+//! SHA-256 checksums of installed skills and plugins, in ~/.claude/arcade_integrity.json
+//! Recorded when ClaudeArcade installs an item, so later edits to its files can be told apart from the original.
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{LazyLock, Mutex};
+use walkdir::WalkDir;
+
+use super::plugin::claude_config_dir;
+
+/// Catalog entry digests as of the last time each marketplace plugin was listed
+static LISTED_DIGESTS: LazyLock<Mutex<HashMap<String, String>>> = LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Checksums of one installed item's files
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IntegrityRecord {
+    /// The skill's folder or the plugin's install path
+    pub root: String,
+    /// RFC3339 time the item was installed
+    pub recorded_at: String,
+    /// Relative path (with `/`) to SHA-256 hex
+    pub files: BTreeMap<String, String>,
+    /// Publisher signature from the marketplace, when it provides one
+    #[serde(default)]
+    pub signature: Option<String>,
+}
+
+/// Result of `verify_item_integrity`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IntegrityReport {
+    pub item_id: String,
+    pub root: String,
+    pub recorded_at: String,
+    /// True when every recorded file is unchanged and nothing was added
+    pub intact: bool,
+    pub modified: Vec<String>,
+    pub missing: Vec<String>,
+    pub added: Vec<String>,
+    pub signature: Option<String>,
+}
+
+pub fn sha256_hex(bytes: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(bytes))
+}
+
+fn store_path() -> Option<PathBuf> {
+    claude_config_dir().map(|d| d.join("arcade_integrity.json"))
+}
+
+/// Keyed by inventory item id
+fn load_records() -> HashMap<String, IntegrityRecord> {
+    store_path()
+        .and_then(|path| crate::store::INTEGRITY.load(&path))
+        .unwrap_or_default()
+}
+
+fn save_records(records: &HashMap<String, IntegrityRecord>) -> Result<(), String> {
+    let path = store_path().ok_or("Could not find home directory")?;
+    let content = crate::store::INTEGRITY.to_json(records)?;
+    crate::paths::atomic_write(&path, content).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+/// Checksum of every file under `root`, leaving out git metadata
+pub fn checksum_tree(root: &Path) -> BTreeMap<String, String> {
+    WalkDir::new(root)
+        .into_iter()
+        .filter_entry(|e| e.file_name() != ".git")
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .filter_map(|e| {
+            let content = fs::read(e.path()).ok()?;
+            let relative = e.path().strip_prefix(root).ok()?.to_string_lossy().replace('\\', "/");
+            Some((relative, sha256_hex(&content)))
+        })
+        .collect()
+}
+
+/// Record the checksums of an item just installed at `root`, replacing any earlier record
+pub fn record_install(item_id: &str, root: &Path, signature: Option<String>) -> Result<(), String> {
+    let mut records = load_records();
+    records.insert(
+        item_id.to_string(),
+        IntegrityRecord {
+            root: root.to_string_lossy().to_string(),
+            recorded_at: chrono::Utc::now().to_rfc3339(),
+            files: checksum_tree(root),
+            signature,
+        },
+    );
+    save_records(&records)
+}
+
+/// Drop the record of an uninstalled item
+pub fn forget_install(item_id: &str) -> Result<(), String> {
+    let mut records = load_records();
+    if records.remove(item_id).is_some() {
+        save_records(&records)?;
+    }
+    Ok(())
+}
+
+/// Compare an item's files with the checksums recorded when it was installed
+pub fn verify_install(item_id: &str) -> Option<IntegrityReport> {
+    let record = load_records().remove(item_id)?;
+    let current = checksum_tree(Path::new(&record.root));
+
+    let modified: Vec<String> = record
+        .files
+        .iter()
+        .filter(|(path, hash)| current.get(*path).is_some_and(|now| now != *hash))
+        .map(|(path, _)| path.clone())
+        .collect();
+    let missing: Vec<String> = record.files.keys().filter(|path| !current.contains_key(*path)).cloned().collect();
+    let added: Vec<String> = current.keys().filter(|path| !record.files.contains_key(*path)).cloned().collect();
+
+    Some(IntegrityReport {
+        item_id: item_id.to_string(),
+        intact: modified.is_empty() && missing.is_empty() && added.is_empty(),
+        root: record.root,
+        recorded_at: record.recorded_at,
+        modified,
+        missing,
+        added,
+        signature: record.signature,
+    })
+}
+
+/// Note the catalog entry a plugin was listed with
+pub fn remember_listing(plugin_id: &str, entry_digest: &str) {
+    if let Ok(mut listed) = LISTED_DIGESTS.lock() {
+        listed.insert(plugin_id.to_string(), entry_digest.to_string());
+    }
+}
+
+/// Whether the catalog entry about to be installed differs from the one last listed
+pub fn listing_changed(plugin_id: &str, entry_digest: &str) -> bool {
+    LISTED_DIGESTS
+        .lock()
+        .ok()
+        .and_then(|listed| listed.get(plugin_id).map(|digest| digest != entry_digest))
+        .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TempDir;
+    use crate::paths::ConfigPaths;
+
+    #[test]
+    fn detects_modified_and_added_files() {
+        let root = TempDir::new("integrity");
+        let skill = root.join(".claude/skills/review");
+        fs::create_dir_all(&skill).unwrap();
+        fs::write(skill.join("SKILL.md"), "Review the diff").unwrap();
+
+        let report = ConfigPaths::under(&root).scoped(|| {
+            record_install("skill_user_review", &skill, None).unwrap();
+            assert!(verify_install("skill_user_review").unwrap().intact);
+
+            fs::write(skill.join("SKILL.md"), "Review the diff, then push").unwrap();
+            fs::write(skill.join("run.sh"), "curl example.com").unwrap();
+            verify_install("skill_user_review").unwrap()
+        });
+
+        assert_eq!(report.modified, vec!["SKILL.md"]);
+        assert_eq!(report.added, vec!["run.sh"]);
+        assert!(!report.intact);
+    }
+}
@@ -0,0 +1,63 @@
+//! Caps that keep one huge folder or file from stalling a scan or filling memory
+//! Entries past a cap are skipped and logged rather than failing the scan.
+
+use std::fs::{self, DirEntry, File};
+use std::io::{self, Read};
+use std::path::Path;
+
+/// Entries read from any one folder (skills, commands, agents, plugin components)
+pub const MAX_DIR_ENTRIES: usize = 2000;
+
+/// Bytes read from any one file; far past anything Claude would load into context
+pub const MAX_FILE_BYTES: u64 = 1024 * 1024;
+
+/// Entries of `dir`, at most MAX_DIR_ENTRIES. A missing or unreadable folder has none.
+pub fn capped_read_dir(dir: &Path) -> Vec<DirEntry> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut entries: Vec<DirEntry> = entries.filter_map(|e| e.ok()).take(MAX_DIR_ENTRIES + 1).collect();
+    if entries.len() > MAX_DIR_ENTRIES {
+        tracing::warn!("{} has more than {} entries; the rest are skipped", dir.display(), MAX_DIR_ENTRIES);
+        entries.truncate(MAX_DIR_ENTRIES);
+    }
+    entries
+}
+
+/// Read a text file, keeping only its first MAX_FILE_BYTES
+pub fn read_capped(path: &Path) -> io::Result<String> {
+    let mut bytes = Vec::new();
+    File::open(path)?.take(MAX_FILE_BYTES).read_to_end(&mut bytes)?;
+    if bytes.len() as u64 == MAX_FILE_BYTES && fs::metadata(path).is_ok_and(|m| m.len() > MAX_FILE_BYTES) {
+        tracing::warn!("{} is larger than {} KB; only the start is read", path.display(), MAX_FILE_BYTES / 1024);
+    }
+    match String::from_utf8(bytes) {
+        Ok(text) => Ok(text),
+        // A cut inside a multi-byte character is dropped; anything else isn't text
+        Err(e) if e.utf8_error().error_len().is_none() => {
+            let valid = e.utf8_error().valid_up_to();
+            let mut bytes = e.into_bytes();
+            bytes.truncate(valid);
+            Ok(String::from_utf8(bytes).unwrap_or_default())
+        }
+        Err(e) => Err(io::Error::new(io::ErrorKind::InvalidData, e)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TempDir;
+
+    #[test]
+    fn caps_reads_at_a_character_boundary() {
+        let dir = TempDir::new("capped");
+        let path = dir.join("capped.md");
+        let mut content = "a".repeat(MAX_FILE_BYTES as usize - 1);
+        content.push('é');
+        fs::write(&path, &content).unwrap();
+        let read = read_capped(&path).unwrap();
+
+        assert_eq!(read.len(), MAX_FILE_BYTES as usize - 1);
+    }
+}
@@ -0,0 +1,186 @@
+//! Schema checks for marketplace catalogs (.claude-plugin/marketplace.json)
+//! A bad entry is reported and skipped instead of hiding the whole marketplace.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// One problem found in a marketplace catalog
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MarketplaceDiagnostic {
+    /// "error" entries are skipped; "warning" entries still load
+    pub severity: String,
+    /// Where in the catalog, e.g. "plugins[3].author"
+    pub location: String,
+    pub message: String,
+}
+
+impl MarketplaceDiagnostic {
+    pub fn error(location: impl Into<String>, message: impl Into<String>) -> Self {
+        MarketplaceDiagnostic { severity: "error".to_string(), location: location.into(), message: message.into() }
+    }
+
+    pub fn warning(location: impl Into<String>, message: impl Into<String>) -> Self {
+        MarketplaceDiagnostic { severity: "warning".to_string(), location: location.into(), message: message.into() }
+    }
+
+    pub fn is_error(&self) -> bool {
+        self.severity == "error"
+    }
+}
+
+impl std::fmt::Display for MarketplaceDiagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.location.is_empty() {
+            write!(f, "{}", self.message)
+        } else {
+            write!(f, "{}: {}", self.location, self.message)
+        }
+    }
+}
+
+/// Optional string fields of a plugin entry
+const OPTIONAL_STRING_FIELDS: [&str; 4] = ["description", "version", "category", "homepage"];
+
+fn validate_author(value: &Value, location: &str, diagnostics: &mut Vec<MarketplaceDiagnostic>) {
+    match value {
+        Value::String(_) => {}
+        Value::Object(author) if author.get("name").is_some_and(|n| n.is_string()) => {}
+        Value::Object(_) => diagnostics.push(MarketplaceDiagnostic::error(location, "author object needs a string \"name\"")),
+        _ => diagnostics.push(MarketplaceDiagnostic::error(location, "author must be a string or an object with a \"name\"")),
+    }
+}
+
+fn validate_source(value: &Value, location: &str, diagnostics: &mut Vec<MarketplaceDiagnostic>) {
+    match value {
+        Value::String(path) => {
+            if path.starts_with('/') || path.split(['/', '\\']).any(|part| part == "..") {
+                diagnostics.push(MarketplaceDiagnostic::error(location, "source path must stay inside the marketplace"));
+            } else if !path.starts_with("./") {
+                diagnostics.push(MarketplaceDiagnostic::warning(location, "relative source paths should start with \"./\""));
+            }
+        }
+        Value::Object(source) => {
+            let kind = source.get("source").and_then(|s| s.as_str());
+            let has = |key: &str| source.get(key).is_some_and(|v| v.is_string());
+            match kind {
+                Some("github") if !has("repo") => {
+                    diagnostics.push(MarketplaceDiagnostic::error(location, "github source needs a \"repo\" (owner/name)"));
+                }
+                Some("git") | Some("url") if !has("url") => {
+                    diagnostics.push(MarketplaceDiagnostic::error(location, "git source needs a \"url\""));
+                }
+                Some("github") | Some("git") | Some("url") => {}
+                Some(other) => diagnostics.push(MarketplaceDiagnostic::error(location, format!("unknown source type \"{}\"", other))),
+                None => diagnostics.push(MarketplaceDiagnostic::error(location, "source object needs a \"source\" type")),
+            }
+        }
+        _ => diagnostics.push(MarketplaceDiagnostic::error(location, "source must be a path or a source object")),
+    }
+}
+
+/// Check one plugin entry, pushing diagnostics located at `plugins[index]`
+fn validate_plugin(index: usize, plugin: &Value, diagnostics: &mut Vec<MarketplaceDiagnostic>) {
+    let location = format!("plugins[{}]", index);
+    let Some(entry) = plugin.as_object() else {
+        diagnostics.push(MarketplaceDiagnostic::error(location, "plugin entry must be an object"));
+        return;
+    };
+
+    match entry.get("name") {
+        Some(Value::String(name)) if name.is_empty() => {
+            diagnostics.push(MarketplaceDiagnostic::error(format!("{}.name", location), "name is empty"));
+        }
+        Some(Value::String(name)) => {
+            if !name.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-') {
+                diagnostics.push(MarketplaceDiagnostic::warning(format!("{}.name", location), format!("\"{}\" is not kebab-case", name)));
+            }
+        }
+        Some(_) => diagnostics.push(MarketplaceDiagnostic::error(format!("{}.name", location), "name must be a string")),
+        None => diagnostics.push(MarketplaceDiagnostic::error(&location, "missing required field \"name\"")),
+    }
+
+    for field in OPTIONAL_STRING_FIELDS {
+        if entry.get(field).is_some_and(|v| !v.is_string()) {
+            diagnostics.push(MarketplaceDiagnostic::error(format!("{}.{}", location, field), format!("{} must be a string", field)));
+        }
+    }
+    if !entry.contains_key("description") {
+        diagnostics.push(MarketplaceDiagnostic::warning(&location, "missing \"description\""));
+    }
+
+    match entry.get("source") {
+        Some(source) => validate_source(source, &format!("{}.source", location), diagnostics),
+        None => diagnostics.push(MarketplaceDiagnostic::warning(&location, "missing \"source\"; assuming ./plugins/<name>")),
+    }
+    if let Some(author) = entry.get("author") {
+        validate_author(author, &format!("{}.author", location), diagnostics);
+    }
+}
+
+/// Validate a parsed catalog against the marketplace.json schema
+pub fn validate_catalog(catalog: &Value) -> Vec<MarketplaceDiagnostic> {
+    let mut diagnostics = Vec::new();
+    let Some(root) = catalog.as_object() else {
+        diagnostics.push(MarketplaceDiagnostic::error("", "catalog must be a JSON object"));
+        return diagnostics;
+    };
+
+    if !root.get("name").is_some_and(|n| n.is_string()) {
+        diagnostics.push(MarketplaceDiagnostic::error("name", "missing required string field \"name\""));
+    }
+    if let Some(owner) = root.get("owner") {
+        validate_author(owner, "owner", &mut diagnostics);
+    }
+
+    let Some(plugins) = root.get("plugins").and_then(|p| p.as_array()) else {
+        diagnostics.push(MarketplaceDiagnostic::error("plugins", "missing required array \"plugins\""));
+        return diagnostics;
+    };
+
+    let mut seen: Vec<&str> = Vec::new();
+    for (index, plugin) in plugins.iter().enumerate() {
+        validate_plugin(index, plugin, &mut diagnostics);
+        if let Some(name) = plugin.get("name").and_then(|n| n.as_str()) {
+            if seen.contains(&name) {
+                diagnostics.push(MarketplaceDiagnostic::error(format!("plugins[{}].name", index), format!("duplicate plugin name \"{}\"", name)));
+            }
+            seen.push(name);
+        }
+    }
+
+    diagnostics
+}
+
+/// Parse and validate catalog text, reporting JSON syntax errors with their position
+pub fn parse_catalog(content: &str) -> Result<(Value, Vec<MarketplaceDiagnostic>), MarketplaceDiagnostic> {
+    let catalog: Value = serde_json::from_str(content).map_err(|e| {
+        MarketplaceDiagnostic::error(format!("line {}, column {}", e.line(), e.column()), format!("invalid JSON: {}", e))
+    })?;
+    let diagnostics = validate_catalog(&catalog);
+    Ok((catalog, diagnostics))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reports_bad_entries_without_failing_the_catalog() {
+        let catalog = serde_json::json!({
+            "name": "example",
+            "plugins": [
+                { "name": "good", "description": "fine", "source": "./plugins/good" },
+                { "description": "no name", "source": "./plugins/x" },
+                { "name": "bad-author", "description": "x", "source": "./plugins/y", "author": 42 },
+            ]
+        });
+
+        let errors: Vec<String> = validate_catalog(&catalog)
+            .into_iter()
+            .filter(|d| d.is_error())
+            .map(|d| d.location)
+            .collect();
+        assert_eq!(errors, vec!["plugins[1]".to_string(), "plugins[2].author".to_string()]);
+    }
+}
@@ -1,4 +1,7 @@
 pub mod plugin;
+pub mod plugin_contents;
+pub mod marketplace;
+pub mod prerequisites;
 pub mod settings;
 pub mod weight;
 pub mod slash_commands;
@@ -6,6 +9,16 @@ pub mod skills;
 pub mod hooks;
 pub mod subagents;
 pub mod claudemd;
+pub mod permissions;
+pub mod transcripts;
+pub mod usage;
+pub mod todos;
+pub mod backups;
+pub mod effective_settings;
+pub mod index;
+pub mod frontmatter;
+pub mod limits;
+pub mod integrity;
 
 pub use plugin::scan_plugins;
 pub use settings::{enable_plugin, disable_plugin};
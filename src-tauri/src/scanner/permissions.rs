@@ -0,0 +1,769 @@
+//! Permission rule evaluation for Claude Code settings
+//! Collects allow/ask/deny rules from each settings scope and matches tool calls against them.
+
+use std::path::{Path, PathBuf};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::plugin::claude_config_dir;
+use super::settings::{read_settings_at, PermissionsConfig};
+
+/// Settings scope a permission rule comes from, in precedence order (highest first)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum PermissionScope {
+    Enterprise, // managed-settings.json (set by IT, cannot be overridden)
+    Local,      // .claude/settings.local.json (personal, git-ignored)
+    Project,    // .claude/settings.json (shared with team)
+    User,       // ~/.claude/settings.json
+}
+
+impl PermissionScope {
+    pub const ALL: [PermissionScope; 4] = [
+        PermissionScope::Enterprise,
+        PermissionScope::Local,
+        PermissionScope::Project,
+        PermissionScope::User,
+    ];
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "enterprise" | "managed" => Some(PermissionScope::Enterprise),
+            "local" => Some(PermissionScope::Local),
+            "project" => Some(PermissionScope::Project),
+            "user" => Some(PermissionScope::User),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        match self {
+            PermissionScope::Enterprise => "enterprise",
+            PermissionScope::Local => "local",
+            PermissionScope::Project => "project",
+            PermissionScope::User => "user",
+        }
+    }
+
+    /// Path of the settings file backing this scope
+    pub fn settings_path(&self, project_path: Option<&str>) -> Option<PathBuf> {
+        match self {
+            PermissionScope::Enterprise => Some(managed_settings_path()),
+            PermissionScope::Local => project_path
+                .map(|p| PathBuf::from(p).join(".claude").join("settings.local.json")),
+            PermissionScope::Project => project_path
+                .map(|p| PathBuf::from(p).join(".claude").join("settings.json")),
+            PermissionScope::User => claude_config_dir().map(|d| d.join("settings.json")),
+        }
+    }
+}
+
+/// Location of the enterprise managed settings file for this platform
+pub fn managed_settings_path() -> PathBuf {
+    #[cfg(target_os = "macos")]
+    {
+        PathBuf::from("/Library/Application Support/ClaudeCode/managed-settings.json")
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        PathBuf::from(r"C:\ProgramData\ClaudeCode\managed-settings.json")
+    }
+
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    {
+        PathBuf::from("/etc/claude-code/managed-settings.json")
+    }
+}
+
+/// What a rule (or the default) does with a tool call
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum RuleBehavior {
+    Allow,
+    Ask,
+    Deny,
+}
+
+impl RuleBehavior {
+    pub fn as_str(&self) -> &str {
+        match self {
+            RuleBehavior::Allow => "allow",
+            RuleBehavior::Ask => "ask",
+            RuleBehavior::Deny => "deny",
+        }
+    }
+}
+
+/// A single permission rule together with where it was defined
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScopedRule {
+    pub rule: String,
+    pub behavior: RuleBehavior,
+    pub scope: PermissionScope,
+    pub source_path: String,
+}
+
+/// Result of evaluating a tool call against the merged rules
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PermissionDecision {
+    pub behavior: RuleBehavior,
+    pub matched_rule: Option<ScopedRule>,
+    pub reason: String,
+}
+
+/// Permission rules and mode gathered from one or more scopes
+#[derive(Debug, Clone, Default)]
+pub struct MergedPermissions {
+    pub rules: Vec<ScopedRule>,
+    pub default_mode: Option<String>,
+}
+
+/// Tools that never prompt unless a rule says otherwise
+const READ_ONLY_TOOLS: [&str; 6] = ["Read", "Glob", "Grep", "LS", "NotebookRead", "TodoWrite"];
+
+/// Tools covered by `Edit(...)` rules
+const EDIT_TOOLS: [&str; 4] = ["Edit", "MultiEdit", "Write", "NotebookEdit"];
+
+/// Read the permissions block of a single scope
+pub fn read_scope_permissions(scope: PermissionScope, project_path: Option<&str>) -> Option<(PathBuf, PermissionsConfig)> {
+    let path = scope.settings_path(project_path)?;
+    let settings = read_settings_at(&path)?;
+    let permissions = settings.get("permissions")?;
+    let config = serde_json::from_value(permissions.clone()).ok()?;
+    Some((path, config))
+}
+
+/// Collect rules from the given scopes, ordered by scope precedence
+pub fn collect_rules(scopes: &[PermissionScope], project_path: Option<&str>) -> MergedPermissions {
+    let mut merged = MergedPermissions::default();
+
+    for scope in PermissionScope::ALL.iter().filter(|s| scopes.contains(s)) {
+        let (path, config) = match read_scope_permissions(*scope, project_path) {
+            Some(found) => found,
+            None => continue,
+        };
+        let source_path = path.to_string_lossy().to_string();
+
+        if merged.default_mode.is_none() {
+            merged.default_mode = config.default_mode.clone();
+        }
+
+        let lists = [
+            (RuleBehavior::Deny, &config.deny),
+            (RuleBehavior::Ask, &config.ask),
+            (RuleBehavior::Allow, &config.allow),
+        ];
+        for (behavior, rules) in lists {
+            for rule in rules {
+                merged.rules.push(ScopedRule {
+                    rule: rule.clone(),
+                    behavior,
+                    scope: *scope,
+                    source_path: source_path.clone(),
+                });
+            }
+        }
+    }
+
+    merged
+}
+
+/// A scalar permissions setting together with the file that set it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScopedSetting {
+    pub value: String,
+    pub scope: PermissionScope,
+    pub source_path: String,
+}
+
+/// A merged rule plus whether a higher-priority rule makes it ineffective
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EffectiveRule {
+    #[serde(flatten)]
+    pub rule: ScopedRule,
+    /// Rule text of the deny/ask rule that takes precedence over this one
+    pub shadowed_by: Option<String>,
+}
+
+/// One settings file that contributed (or could contribute) permissions
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PermissionSource {
+    pub scope: PermissionScope,
+    pub path: String,
+    pub exists: bool,
+    pub rule_count: u32,
+}
+
+/// Permissions from every scope merged in Claude Code's precedence order
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EffectivePermissions {
+    pub rules: Vec<EffectiveRule>,
+    pub default_mode: Option<ScopedSetting>,
+    pub additional_directories: Vec<ScopedSetting>,
+    pub disable_bypass_permissions_mode: Option<ScopedSetting>,
+    pub sources: Vec<PermissionSource>,
+}
+
+/// Merge enterprise, local, project and user permission blocks.
+/// Rules are ordered deny → ask → allow, and by scope precedence within each list.
+pub fn effective_permissions(project_path: Option<&str>) -> EffectivePermissions {
+    let mut sources = Vec::new();
+    let mut default_mode = None;
+    let mut disable_bypass = None;
+    let mut additional_directories: Vec<ScopedSetting> = Vec::new();
+
+    for scope in PermissionScope::ALL {
+        let path = match scope.settings_path(project_path) {
+            Some(p) => p,
+            None => continue,
+        };
+        let source_path = path.to_string_lossy().to_string();
+        let config = read_scope_permissions(scope, project_path).map(|(_, c)| c);
+
+        sources.push(PermissionSource {
+            scope,
+            path: source_path.clone(),
+            exists: path.exists(),
+            rule_count: config.as_ref()
+                .map_or(0, |c| (c.allow.len() + c.ask.len() + c.deny.len()) as u32),
+        });
+
+        let config = match config {
+            Some(c) => c,
+            None => continue,
+        };
+
+        let scoped = |value: &str| ScopedSetting {
+            value: value.to_string(),
+            scope,
+            source_path: source_path.clone(),
+        };
+
+        // Scopes are visited highest precedence first, so the first value wins
+        if default_mode.is_none() {
+            default_mode = config.default_mode.as_deref().map(scoped);
+        }
+        if disable_bypass.is_none() {
+            disable_bypass = config.disable_bypass_permissions_mode.as_deref().map(scoped);
+        }
+        for dir in config.additional_directories.iter().flatten() {
+            if !additional_directories.iter().any(|d| &d.value == dir) {
+                additional_directories.push(scoped(dir));
+            }
+        }
+    }
+
+    let mut merged = collect_rules(&PermissionScope::ALL, project_path).rules;
+    let behavior_rank = |b: RuleBehavior| match b {
+        RuleBehavior::Deny => 0,
+        RuleBehavior::Ask => 1,
+        RuleBehavior::Allow => 2,
+    };
+    merged.sort_by_key(|r| behavior_rank(r.behavior));
+
+    let rules = merged.iter()
+        .map(|rule| {
+            let shadowed_by = merged.iter()
+                .filter(|other| behavior_rank(other.behavior) < behavior_rank(rule.behavior))
+                .find(|other| shadows(&other.rule, &rule.rule))
+                .map(|other| other.rule.clone());
+            EffectiveRule {
+                rule: rule.clone(),
+                shadowed_by,
+            }
+        })
+        .collect();
+
+    EffectivePermissions {
+        rules,
+        default_mode,
+        additional_directories,
+        disable_bypass_permissions_mode: disable_bypass,
+        sources,
+    }
+}
+
+/// Whether `stronger` covers everything `weaker` would match
+fn shadows(stronger: &str, weaker: &str) -> bool {
+    let (strong_tool, strong_spec) = parse_rule(stronger);
+    let (weak_tool, weak_spec) = parse_rule(weaker);
+
+    if strong_tool != weak_tool {
+        return false;
+    }
+
+    match (strong_spec, weak_spec) {
+        (None, _) | (Some("*"), _) => true,
+        (Some(strong), Some(weak)) => {
+            strong == weak
+                || strong.strip_suffix(":*").is_some_and(|prefix| weak.starts_with(prefix))
+                || (strong.contains('*') && wildcard_match(strong, weak))
+        }
+        (Some(_), None) => false,
+    }
+}
+
+/// Evaluate a tool call the way Claude Code does: deny beats ask beats allow,
+/// and anything unmatched falls back to the permission mode default.
+pub fn evaluate(
+    tool_name: &str,
+    tool_input: &Value,
+    merged: &MergedPermissions,
+    project_root: Option<&Path>,
+) -> PermissionDecision {
+    let ctx = MatchContext::new(project_root);
+
+    for behavior in [RuleBehavior::Deny, RuleBehavior::Ask] {
+        let hit = merged.rules.iter()
+            .filter(|r| r.behavior == behavior)
+            .find(|r| rule_matches(&r.rule, tool_name, tool_input, &ctx));
+
+        if let Some(rule) = hit {
+            return PermissionDecision {
+                behavior,
+                matched_rule: Some(rule.clone()),
+                reason: format!("Matched {} rule '{}' from {} settings", behavior.as_str(), rule.rule, rule.scope.as_str()),
+            };
+        }
+    }
+
+    let allow_rules: Vec<&ScopedRule> = merged.rules.iter()
+        .filter(|r| r.behavior == RuleBehavior::Allow)
+        .collect();
+
+    // Every part of a compound Bash command must be covered by some allow rule
+    let allow_hit = if tool_name == "Bash" {
+        let command = tool_input.get("command").and_then(|v| v.as_str()).unwrap_or_default();
+        let parts = split_compound_command(command);
+        let mut first_match = None;
+        let all_covered = !parts.is_empty() && parts.iter().all(|part| {
+            let part_input = serde_json::json!({ "command": part });
+            match allow_rules.iter().find(|r| rule_matches(&r.rule, tool_name, &part_input, &ctx)) {
+                Some(rule) => {
+                    first_match.get_or_insert(*rule);
+                    true
+                }
+                None => false,
+            }
+        });
+        if all_covered { first_match } else { None }
+    } else {
+        allow_rules.iter().find(|r| rule_matches(&r.rule, tool_name, tool_input, &ctx)).copied()
+    };
+
+    if let Some(rule) = allow_hit {
+        return PermissionDecision {
+            behavior: RuleBehavior::Allow,
+            matched_rule: Some(rule.clone()),
+            reason: format!("Matched allow rule '{}' from {} settings", rule.rule, rule.scope.as_str()),
+        };
+    }
+
+    default_decision(tool_name, merged.default_mode.as_deref())
+}
+
+/// Behavior when no rule matches
+fn default_decision(tool_name: &str, default_mode: Option<&str>) -> PermissionDecision {
+    let is_read_only = READ_ONLY_TOOLS.contains(&tool_name);
+    let is_edit = EDIT_TOOLS.contains(&tool_name);
+
+    let (behavior, reason) = match default_mode {
+        Some("bypassPermissions") => (RuleBehavior::Allow, "No rule matched; bypassPermissions mode allows everything not denied"),
+        Some("plan") if !is_read_only => (RuleBehavior::Deny, "No rule matched; plan mode blocks non-read-only tools"),
+        Some("dontAsk") if !is_read_only => (RuleBehavior::Deny, "No rule matched; dontAsk mode denies anything not allowed"),
+        Some("acceptEdits") if is_edit => (RuleBehavior::Allow, "No rule matched; acceptEdits mode auto-approves file edits"),
+        _ if is_read_only => (RuleBehavior::Allow, "No rule matched; read-only tools do not require approval"),
+        _ => (RuleBehavior::Ask, "No rule matched; Claude will prompt for approval"),
+    };
+
+    PermissionDecision {
+        behavior,
+        matched_rule: None,
+        reason: reason.to_string(),
+    }
+}
+
+/// Paths used to resolve file-based rule specifiers
+struct MatchContext {
+    project_root: Option<String>,
+    home: Option<String>,
+}
+
+impl MatchContext {
+    fn new(project_root: Option<&Path>) -> Self {
+        Self {
+            project_root: project_root.map(normalize_path),
+            home: crate::paths::home_dir().map(|h| normalize_path(&h)),
+        }
+    }
+}
+
+fn normalize_path(path: &Path) -> String {
+    path.to_string_lossy().replace('\\', "/").trim_end_matches('/').to_string()
+}
+
+/// Split "Tool(specifier)" into its parts
+pub fn parse_rule(rule: &str) -> (&str, Option<&str>) {
+    let rule = rule.trim();
+    match rule.find('(') {
+        Some(open) if rule.ends_with(')') => (&rule[..open], Some(&rule[open + 1..rule.len() - 1])),
+        _ => (rule, None),
+    }
+}
+
+/// The deny rule that blocks a tool an item declares it needs ("WebFetch", "Bash(git:*)",
+/// "mcp__github"), if any. Only rules denying the whole tool, or exactly that use of it, count;
+/// a narrower deny leaves the item mostly working.
+pub fn denying_rule<'a>(required: &str, rules: &'a [ScopedRule]) -> Option<&'a ScopedRule> {
+    let (tool, specifier) = parse_rule(required);
+    rules.iter().filter(|r| r.behavior == RuleBehavior::Deny).find(|r| {
+        let (denied_tool, denied_specifier) = parse_rule(&r.rule);
+        let same_tool =
+            denied_tool == tool || (denied_tool.starts_with("mcp__") && tool.starts_with(&format!("{}__", denied_tool)));
+        same_tool && (denied_specifier.is_none() || denied_specifier == specifier)
+    })
+}
+
+/// Check whether a rule covers a tool call.
+/// For Bash, a rule matches if it matches any part of a compound command.
+fn rule_matches(rule: &str, tool_name: &str, tool_input: &Value, ctx: &MatchContext) -> bool {
+    let (rule_tool, specifier) = parse_rule(rule);
+
+    // MCP rules: "mcp__server" covers every tool from that server
+    if rule_tool.starts_with("mcp__") {
+        return mcp_rule_matches(rule_tool, tool_name);
+    }
+
+    let tool_covered = rule_tool == tool_name
+        || (rule_tool == "Edit" && EDIT_TOOLS.contains(&tool_name))
+        || (rule_tool == "Read" && ["Glob", "Grep", "LS", "NotebookRead"].contains(&tool_name));
+    if !tool_covered {
+        return false;
+    }
+
+    let specifier = match specifier {
+        None | Some("") | Some("*") => return true,
+        Some(s) => s,
+    };
+
+    match tool_name {
+        "Bash" => {
+            let command = tool_input.get("command").and_then(|v| v.as_str()).unwrap_or_default();
+            split_compound_command(command)
+                .iter()
+                .any(|part| bash_specifier_matches(specifier, part))
+        }
+        "WebFetch" => {
+            let url = tool_input.get("url").and_then(|v| v.as_str()).unwrap_or_default();
+            match specifier.strip_prefix("domain:") {
+                Some(domain) => url_host(url).is_some_and(|host| {
+                    host == domain || host.ends_with(&format!(".{}", domain))
+                }),
+                None => wildcard_match(specifier, url),
+            }
+        }
+        _ => match tool_input_path(tool_input) {
+            Some(path) => path_specifier_matches(specifier, &path, ctx),
+            None => tool_input_text(tool_input).is_some_and(|text| wildcard_match(specifier, &text)),
+        },
+    }
+}
+
+fn mcp_rule_matches(rule_tool: &str, tool_name: &str) -> bool {
+    let rule_tool = rule_tool.trim_end_matches("__*").trim_end_matches('*');
+    tool_name == rule_tool || tool_name.starts_with(&format!("{}__", rule_tool))
+}
+
+/// Bash specifiers: "npm run test:*" is a prefix match, anything else is exact
+/// unless it contains a `*` wildcard.
+fn bash_specifier_matches(specifier: &str, command: &str) -> bool {
+    if let Some(prefix) = specifier.strip_suffix(":*") {
+        return command.starts_with(prefix);
+    }
+    if specifier.contains('*') {
+        return wildcard_match(specifier, command);
+    }
+    command == specifier
+}
+
+/// Split a shell command on control operators the way Claude Code checks each subcommand
+pub fn split_compound_command(command: &str) -> Vec<String> {
+    let mut parts = Vec::new();
+    let mut current = String::new();
+    let mut chars = command.chars().peekable();
+    let mut quote: Option<char> = None;
+
+    while let Some(c) = chars.next() {
+        if let Some(q) = quote {
+            current.push(c);
+            if c == q {
+                quote = None;
+            }
+            continue;
+        }
+
+        match c {
+            '\'' | '"' => {
+                quote = Some(c);
+                current.push(c);
+            }
+            ';' | '\n' => parts.push(std::mem::take(&mut current)),
+            '&' | '|' => {
+                if chars.peek() == Some(&c) {
+                    chars.next();
+                }
+                parts.push(std::mem::take(&mut current));
+            }
+            _ => current.push(c),
+        }
+    }
+    parts.push(current);
+
+    parts.into_iter()
+        .map(|p| p.trim().to_string())
+        .filter(|p| !p.is_empty())
+        .collect()
+}
+
+/// Extract the file path argument from file-based tool inputs
+fn tool_input_path(tool_input: &Value) -> Option<String> {
+    ["file_path", "notebook_path", "path"]
+        .iter()
+        .find_map(|key| tool_input.get(*key).and_then(|v| v.as_str()))
+        .map(|s| s.to_string())
+}
+
+/// Fallback text used for wildcard matching of other tools
+fn tool_input_text(tool_input: &Value) -> Option<String> {
+    match tool_input {
+        Value::String(s) => Some(s.clone()),
+        Value::Object(map) => map.values().find_map(|v| v.as_str()).map(|s| s.to_string()),
+        _ => None,
+    }
+}
+
+fn url_host(url: &str) -> Option<&str> {
+    let after_scheme = url.split("://").nth(1).unwrap_or(url);
+    let host = after_scheme.split(['/', '?', '#']).next()?;
+    let host = host.rsplit('@').next()?;
+    let host = host.split(':').next()?;
+    if host.is_empty() { None } else { Some(host) }
+}
+
+/// Match gitignore-style path specifiers:
+/// `//abs` is absolute, `~/x` is home-relative, `/x` and `./x` are project-relative,
+/// and bare patterns without a slash match at any depth.
+fn path_specifier_matches(specifier: &str, path: &str, ctx: &MatchContext) -> bool {
+    let path = path.replace('\\', "/");
+    let absolute_path = if path.starts_with('/') || path.chars().nth(1) == Some(':') {
+        path
+    } else {
+        match &ctx.project_root {
+            Some(root) => format!("{}/{}", root, path.trim_start_matches("./")),
+            None => path,
+        }
+    };
+
+    let pattern = if let Some(abs) = specifier.strip_prefix("//") {
+        format!("/{}", abs)
+    } else if let Some(rest) = specifier.strip_prefix("~/") {
+        match &ctx.home {
+            Some(home) => format!("{}/{}", home, rest),
+            None => return false,
+        }
+    } else {
+        let relative = specifier.trim_start_matches("./").trim_start_matches('/');
+        let anchored = specifier.starts_with('/') || relative.contains('/');
+        let relative = if anchored {
+            relative.to_string()
+        } else {
+            format!("**/{}", relative)
+        };
+        match &ctx.project_root {
+            Some(root) => format!("{}/{}", root, relative),
+            None => format!("**/{}", relative.trim_start_matches("**/")),
+        }
+    };
+
+    glob_match(&pattern, &absolute_path)
+        // A directory pattern also covers everything beneath it
+        || glob_match(&format!("{}/**", pattern.trim_end_matches('/')), &absolute_path)
+}
+
+/// Glob matching where `*` and `?` stop at `/` and `**` crosses directories
+pub fn glob_match(pattern: &str, text: &str) -> bool {
+    let p: Vec<char> = pattern.chars().collect();
+    let t: Vec<char> = text.chars().collect();
+    glob_match_from(&p, &t, true)
+}
+
+/// Wildcard matching where `*` matches any run of characters
+pub fn wildcard_match(pattern: &str, text: &str) -> bool {
+    let p: Vec<char> = pattern.chars().collect();
+    let t: Vec<char> = text.chars().collect();
+    glob_match_from(&p, &t, false)
+}
+
+fn glob_match_from(p: &[char], t: &[char], path_aware: bool) -> bool {
+    match p.first() {
+        None => t.is_empty(),
+        Some('*') if path_aware && p.get(1) == Some(&'*') => {
+            let rest = &p[2..];
+            // "**/" may also match zero directories
+            if rest.first() == Some(&'/') && glob_match_from(&rest[1..], t, path_aware) {
+                return true;
+            }
+            (0..=t.len()).any(|i| glob_match_from(rest, &t[i..], path_aware))
+        }
+        Some('*') => {
+            let rest = &p[1..];
+            for i in 0..=t.len() {
+                if glob_match_from(rest, &t[i..], path_aware) {
+                    return true;
+                }
+                if path_aware && i < t.len() && t[i] == '/' {
+                    break;
+                }
+            }
+            false
+        }
+        Some('?') => {
+            !(t.is_empty() || path_aware && t[0] == '/') && glob_match_from(&p[1..], &t[1..], path_aware)
+        }
+        Some(c) => t.first() == Some(c) && glob_match_from(&p[1..], &t[1..], path_aware),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn rules(list: &[(&str, RuleBehavior)]) -> MergedPermissions {
+        MergedPermissions {
+            rules: list.iter()
+                .map(|(rule, behavior)| ScopedRule {
+                    rule: rule.to_string(),
+                    behavior: *behavior,
+                    scope: PermissionScope::User,
+                    source_path: String::new(),
+                })
+                .collect(),
+            default_mode: None,
+        }
+    }
+
+    #[test]
+    fn test_deny_beats_allow() {
+        let merged = rules(&[
+            ("Bash(git:*)", RuleBehavior::Allow),
+            ("Bash(git push:*)", RuleBehavior::Deny),
+        ]);
+
+        let push = evaluate("Bash", &json!({"command": "git push origin main"}), &merged, None);
+        assert_eq!(push.behavior, RuleBehavior::Deny);
+        assert_eq!(push.matched_rule.unwrap().rule, "Bash(git push:*)");
+
+        let status = evaluate("Bash", &json!({"command": "git status"}), &merged, None);
+        assert_eq!(status.behavior, RuleBehavior::Allow);
+    }
+
+    #[test]
+    fn test_compound_bash_commands() {
+        let merged = rules(&[
+            ("Bash(npm test:*)", RuleBehavior::Allow),
+            ("Bash(rm:*)", RuleBehavior::Deny),
+        ]);
+
+        let sneaky = evaluate("Bash", &json!({"command": "npm test && rm -rf /"}), &merged, None);
+        assert_eq!(sneaky.behavior, RuleBehavior::Deny);
+
+        // Allow rules must cover every subcommand
+        let partial = evaluate("Bash", &json!({"command": "npm test; curl example.com"}), &merged, None);
+        assert_eq!(partial.behavior, RuleBehavior::Ask);
+
+        let merged = rules(&[
+            ("Bash(npm test:*)", RuleBehavior::Allow),
+            ("Bash(git status)", RuleBehavior::Allow),
+        ]);
+        let covered = evaluate("Bash", &json!({"command": "npm test | tee out.log"}), &merged, None);
+        assert_eq!(covered.behavior, RuleBehavior::Ask);
+        let covered = evaluate("Bash", &json!({"command": "git status && npm test"}), &merged, None);
+        assert_eq!(covered.behavior, RuleBehavior::Allow);
+    }
+
+    #[test]
+    fn test_path_rules() {
+        let merged = rules(&[
+            ("Read(.env)", RuleBehavior::Deny),
+            ("Edit(/src/**)", RuleBehavior::Allow),
+        ]);
+        let root = Path::new("/work/app");
+
+        let env = evaluate("Read", &json!({"file_path": "/work/app/config/.env"}), &merged, Some(root));
+        assert_eq!(env.behavior, RuleBehavior::Deny);
+
+        let src = evaluate("Write", &json!({"file_path": "/work/app/src/lib/main.ts"}), &merged, Some(root));
+        assert_eq!(src.behavior, RuleBehavior::Allow);
+
+        let outside = evaluate("Edit", &json!({"file_path": "/work/app/package.json"}), &merged, Some(root));
+        assert_eq!(outside.behavior, RuleBehavior::Ask);
+    }
+
+    #[test]
+    fn test_mcp_and_webfetch_rules() {
+        let merged = rules(&[
+            ("mcp__github", RuleBehavior::Allow),
+            ("WebFetch(domain:example.com)", RuleBehavior::Deny),
+        ]);
+
+        let mcp = evaluate("mcp__github__create_issue", &json!({}), &merged, None);
+        assert_eq!(mcp.behavior, RuleBehavior::Allow);
+
+        let fetch = evaluate("WebFetch", &json!({"url": "https://docs.example.com/page"}), &merged, None);
+        assert_eq!(fetch.behavior, RuleBehavior::Deny);
+    }
+
+    #[test]
+    fn test_default_modes() {
+        let mut merged = rules(&[("Bash(npm test:*)", RuleBehavior::Allow)]);
+        merged.default_mode = Some("dontAsk".to_string());
+
+        let unmatched = evaluate("Bash", &json!({"command": "curl example.com"}), &merged, None);
+        assert_eq!(unmatched.behavior, RuleBehavior::Deny);
+        assert_eq!(evaluate("Bash", &json!({"command": "npm test"}), &merged, None).behavior, RuleBehavior::Allow);
+        assert_eq!(evaluate("Read", &json!({"file_path": "/a.txt"}), &merged, None).behavior, RuleBehavior::Allow);
+
+        merged.default_mode = Some("acceptEdits".to_string());
+        assert_eq!(evaluate("Edit", &json!({"file_path": "/a.txt"}), &merged, None).behavior, RuleBehavior::Allow);
+        assert_eq!(evaluate("Bash", &json!({"command": "ls"}), &merged, None).behavior, RuleBehavior::Ask);
+    }
+
+    #[test]
+    fn test_denying_rule() {
+        let merged = rules(&[
+            ("WebFetch", RuleBehavior::Deny),
+            ("Bash(rm:*)", RuleBehavior::Deny),
+            ("mcp__github", RuleBehavior::Deny),
+        ]);
+
+        assert_eq!(denying_rule("WebFetch", &merged.rules).unwrap().rule, "WebFetch");
+        assert!(denying_rule("Bash(git:*)", &merged.rules).is_none());
+        assert!(denying_rule("mcp__github__create_issue", &merged.rules).is_some());
+    }
+
+    #[test]
+    fn test_glob_match() {
+        assert!(glob_match("/a/**/*.ts", "/a/b/c/d.ts"));
+        assert!(glob_match("/a/**/*.ts", "/a/d.ts"));
+        assert!(!glob_match("/a/*.ts", "/a/b/d.ts"));
+        assert!(wildcard_match("git * --force", "git push origin --force"));
+    }
+}
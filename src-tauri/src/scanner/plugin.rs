@@ -1,10 +1,15 @@
 use std::collections::HashMap;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use serde::Deserialize;
+use serde_json::Value;
 
-use crate::types::{InventoryItem, ItemType, ItemRarity, ItemSource, ScanResult};
+use crate::types::{InventoryItem, ItemStatus, ItemType, ItemRarity, ItemSource, ScanResult};
+use super::marketplace::{parse_catalog, MarketplaceDiagnostic};
+use super::plugin_contents::{measure_plugin_weight, read_plugin_manifest, PluginWeight};
+use super::prerequisites::check_prerequisites;
 use super::settings::read_settings;
+use super::integrity::{remember_listing, sha256_hex};
 
 /// Installed plugin entry from installed_plugins.json
 #[derive(Debug, Deserialize)]
@@ -28,15 +33,45 @@ struct InstalledPluginsFile {
 
 /// Plugin metadata from marketplace.json
 #[derive(Debug, Clone, Deserialize)]
-struct PluginMetadata {
-    name: String,
-    description: String,
+pub(crate) struct PluginMetadata {
+    pub(crate) name: String,
     #[serde(default)]
-    version: Option<String>,
+    pub(crate) description: String,
+    #[serde(default)]
+    pub(crate) version: Option<String>,
     #[serde(default)]
     category: Option<String>,
     #[serde(default)]
     author: Option<AuthorInfo>,
+    /// Where the plugin's files come from
+    #[serde(default)]
+    pub(crate) source: Option<PluginSource>,
+    /// LSP servers declared in the catalog entry rather than the plugin itself
+    #[serde(default, rename = "lspServers")]
+    pub(crate) lsp_servers: Option<Value>,
+    /// Publisher signature over the plugin's files, recorded with its checksums when installed
+    #[serde(default)]
+    pub(crate) signature: Option<String>,
+    /// SHA-256 of the catalog entry as read, to tell whether it changed since it was listed
+    #[serde(skip)]
+    pub(crate) entry_digest: String,
+}
+
+/// Source of a marketplace plugin: a path inside the marketplace checkout
+/// (e.g. "./plugins/foo") or a separate git repository
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub(crate) enum PluginSource {
+    Path(String),
+    Remote {
+        source: String,
+        #[serde(default)]
+        repo: Option<String>,
+        #[serde(default)]
+        url: Option<String>,
+        #[serde(default, rename = "ref")]
+        git_ref: Option<String>,
+    },
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -55,23 +90,46 @@ impl AuthorInfo {
     }
 }
 
-/// Marketplace catalog structure
-#[derive(Debug, Deserialize)]
-struct MarketplaceCatalog {
-    #[serde(rename = "name")]
-    _name: String,
-    plugins: Vec<PluginMetadata>,
-}
-
 /// Get Claude config directory
 pub fn claude_config_dir() -> Option<PathBuf> {
-    dirs::home_dir().map(|h| h.join(".claude"))
+    crate::paths::claude_dir()
+}
+
+/// Get ~/.claude/plugins
+pub(crate) fn plugins_dir() -> Option<PathBuf> {
+    claude_config_dir().map(|d| d.join("plugins"))
+}
+
+/// Get path to installed_plugins.json
+pub(crate) fn installed_plugins_path() -> Option<PathBuf> {
+    plugins_dir().map(|d| d.join("installed_plugins.json"))
+}
+
+/// Read installed_plugins.json as a JSON Value to preserve fields we don't model.
+/// Fails when the file exists but can't be parsed, so it is never replaced with an empty one.
+pub(crate) fn read_installed_plugins_raw() -> Result<Value, String> {
+    let path = installed_plugins_path().ok_or("Could not find home directory")?;
+    match fs::read_to_string(&path) {
+        Ok(content) => serde_json::from_str(&content)
+            .map_err(|e| format!("Failed to parse {}: {}", path.display(), e)),
+        Err(_) => Ok(serde_json::json!({ "version": 2, "plugins": {} })),
+    }
+}
+
+/// Write installed_plugins.json atomically
+pub(crate) fn write_installed_plugins(file: &Value) -> Result<(), String> {
+    let path = installed_plugins_path().ok_or("Could not find home directory")?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+
+    let content = serde_json::to_string_pretty(file).map_err(|e| e.to_string())?;
+    crate::watcher::write_expected(&path, &content).map_err(|e| format!("Failed to write installed plugins: {}", e))
 }
 
 /// Read installed plugins from installed_plugins.json
 fn read_installed_plugins() -> HashMap<String, InstalledPluginEntry> {
-    let path = claude_config_dir()
-        .map(|d| d.join("plugins").join("installed_plugins.json"));
+    let path = installed_plugins_path();
 
     let content = match path.and_then(|p| fs::read_to_string(p).ok()) {
         Some(c) => c,
@@ -92,43 +150,88 @@ fn read_installed_plugins() -> HashMap<String, InstalledPluginEntry> {
         .collect()
 }
 
+/// Install path of every plugin in installed_plugins.json
+pub(crate) fn installed_plugin_paths() -> HashMap<String, PathBuf> {
+    read_installed_plugins()
+        .into_iter()
+        .map(|(id, entry)| (id, PathBuf::from(entry.install_path)))
+        .collect()
+}
+
+/// Install path of an installed plugin
+pub(crate) fn installed_plugin_path(plugin_id: &str) -> Option<PathBuf> {
+    read_installed_plugins()
+        .remove(plugin_id)
+        .map(|entry| PathBuf::from(entry.install_path))
+}
+
+/// Load one marketplace's catalog. Entries that fail validation are skipped and reported
+/// in the returned diagnostics; an unreadable catalog is a single error.
+pub(crate) fn load_marketplace(marketplace_dir: &Path) -> Result<(Vec<PluginMetadata>, Vec<MarketplaceDiagnostic>), MarketplaceDiagnostic> {
+    let catalog_path = marketplace_dir.join(".claude-plugin").join("marketplace.json");
+    let content = fs::read_to_string(&catalog_path)
+        .map_err(|_| MarketplaceDiagnostic::error("", "missing .claude-plugin/marketplace.json"))?;
+    let (catalog, mut diagnostics) = parse_catalog(&content)?;
+
+    let mut plugins = Vec::new();
+    for (index, entry) in catalog.get("plugins").and_then(|p| p.as_array()).into_iter().flatten().enumerate() {
+        let location = format!("plugins[{}]", index);
+        if diagnostics.iter().any(|d| d.is_error() && (d.location == location || d.location.starts_with(&format!("{}.", location)))) {
+            continue;
+        }
+        match serde_json::from_value::<PluginMetadata>(entry.clone()) {
+            Ok(plugin) => plugins.push(PluginMetadata { entry_digest: sha256_hex(entry.to_string().as_bytes()), ..plugin }),
+            Err(e) => diagnostics.push(MarketplaceDiagnostic::error(location, e.to_string())),
+        }
+    }
+
+    Ok((plugins, diagnostics))
+}
+
 /// Read plugin metadata from marketplace catalogs
-fn read_marketplace_catalog() -> HashMap<String, PluginMetadata> {
+pub(crate) fn read_marketplace_catalog() -> HashMap<String, PluginMetadata> {
+    read_marketplace_catalog_with_errors().0
+}
+
+/// Read plugin metadata from marketplace catalogs, along with the catalog errors that made
+/// entries (or whole marketplaces) drop out
+fn read_marketplace_catalog_with_errors() -> (HashMap<String, PluginMetadata>, Vec<String>) {
     let mut catalog = HashMap::new();
+    let mut errors = Vec::new();
 
-    let marketplaces_dir = match claude_config_dir() {
-        Some(d) => d.join("plugins").join("marketplaces"),
-        None => return catalog,
+    let marketplaces_dir = match plugins_dir() {
+        Some(d) => d.join("marketplaces"),
+        None => return (catalog, errors),
     };
 
-    if !marketplaces_dir.exists() {
-        return catalog;
-    }
-
     // Scan each marketplace directory
-    if let Ok(entries) = fs::read_dir(&marketplaces_dir) {
-        for entry in entries.filter_map(|e| e.ok()) {
-            let marketplace_path = entry.path();
-            let marketplace_name = entry.file_name().to_string_lossy().to_string();
-
-            // Read the marketplace.json file
-            let catalog_path = marketplace_path
-                .join(".claude-plugin")
-                .join("marketplace.json");
-
-            if let Ok(content) = fs::read_to_string(&catalog_path) {
-                if let Ok(mc) = serde_json::from_str::<MarketplaceCatalog>(&content) {
-                    for plugin in mc.plugins {
-                        // Key is "plugin-name@marketplace-name"
-                        let key = format!("{}@{}", plugin.name, marketplace_name);
-                        catalog.insert(key, plugin);
-                    }
+    for entry in fs::read_dir(&marketplaces_dir).into_iter().flatten().filter_map(|e| e.ok()) {
+        if !entry.path().is_dir() {
+            continue;
+        }
+        let marketplace_name = entry.file_name().to_string_lossy().to_string();
+
+        let diagnostics = match load_marketplace(&entry.path()) {
+            Ok((plugins, diagnostics)) => {
+                for plugin in plugins {
+                    // Key is "plugin-name@marketplace-name"
+                    let key = format!("{}@{}", plugin.name, marketplace_name);
+                    catalog.insert(key, plugin);
                 }
+                diagnostics
             }
-        }
+            Err(diagnostic) => vec![diagnostic],
+        };
+
+        errors.extend(
+            diagnostics
+                .iter()
+                .filter(|d| d.is_error())
+                .map(|d| format!("Marketplace {}: {}", marketplace_name, d)),
+        );
     }
 
-    catalog
+    (catalog, errors)
 }
 
 /// Map category to ItemType
@@ -174,17 +277,21 @@ fn determine_rarity(metadata: Option<&PluginMetadata>, has_lsp: bool, has_mcp: b
 pub fn scan_plugins() -> ScanResult {
     let start = std::time::Instant::now();
     let mut items = Vec::new();
-    let errors = Vec::new();
+    let mut errors = Vec::new();
 
-    // Get enabled plugins from settings
-    let settings = read_settings();
+    // Get enabled plugins from settings; a broken file is reported rather than read as "nothing enabled"
+    let settings = read_settings().unwrap_or_else(|e| {
+        errors.push(e);
+        Default::default()
+    });
     let enabled_plugins = &settings.enabled_plugins;
 
     // Get installed plugins
     let installed = read_installed_plugins();
 
-    // Get marketplace metadata
-    let catalog = read_marketplace_catalog();
+    // Get marketplace metadata; broken catalog entries are reported rather than silently dropped
+    let (catalog, catalog_errors) = read_marketplace_catalog_with_errors();
+    errors.extend(catalog_errors);
 
     // Process each installed plugin
     for (plugin_id, entry) in &installed {
@@ -223,8 +330,11 @@ pub fn scan_plugins() -> ScanResult {
 
         let rarity = determine_rarity(metadata, has_lsp, has_mcp);
 
-        // Estimate token weight from install path content
-        let token_weight = estimate_plugin_weight(&entry.install_path);
+        // Equipping costs what's always loaded; bodies only arrive when a component is used
+        let weight = estimate_plugin_weight(&entry.install_path);
+
+        // Binaries the plugin needs but the host lacks would otherwise fail silently inside Claude
+        let missing = missing_prerequisites(&entry.install_path, metadata);
 
         // Check if enabled
         let enabled = enabled_plugins.get(plugin_id).copied().unwrap_or(false);
@@ -241,11 +351,18 @@ pub fn scan_plugins() -> ScanResult {
             rarity,
             source: ItemSource::Plugin,
             source_path: entry.install_path.clone(),
-            token_weight,
+            token_weight: weight.always_loaded,
             enabled,
             version: Some(entry.version.clone()),
             author,
-            status: None,
+            status: Some(ItemStatus {
+                base_tokens: Some(weight.always_loaded),
+                invoked_tokens: Some(weight.on_demand),
+                error_count: (!missing.is_empty()).then_some(missing.len() as u32),
+                last_error: (!missing.is_empty()).then(|| format!("Missing prerequisites: {}", missing.join(", "))),
+                ..Default::default()
+            }),
+            environment: None,
         });
     }
 
@@ -267,6 +384,7 @@ pub fn scan_plugins() -> ScanResult {
             let rarity = determine_rarity(Some(metadata), has_lsp, has_mcp);
 
             let author = metadata.author.as_ref().map(|a| a.name().to_string());
+            remember_listing(plugin_id, &metadata.entry_digest);
 
             items.push(InventoryItem {
                 id: plugin_id.clone(),
@@ -281,6 +399,7 @@ pub fn scan_plugins() -> ScanResult {
                 version: metadata.version.clone(),
                 author,
                 status: None,
+                environment: None,
             });
         }
     }
@@ -295,43 +414,37 @@ pub fn scan_plugins() -> ScanResult {
         items,
         errors,
         scan_duration_ms: start.elapsed().as_millis() as u64,
+        metrics: None,
     }
 }
 
-/// Estimate token weight for a plugin based on its install path
-fn estimate_plugin_weight(install_path: &str) -> u32 {
-    if install_path.is_empty() {
-        return 5000; // Base estimate for non-installed plugins
-    }
-
-    let path = PathBuf::from(install_path);
-    if !path.exists() {
-        return 5000;
+/// Declared binaries of an installed plugin that aren't on this machine
+fn missing_prerequisites(install_path: &str, metadata: Option<&PluginMetadata>) -> Vec<String> {
+    let root = PathBuf::from(install_path);
+    if install_path.is_empty() || !root.is_dir() {
+        return Vec::new();
     }
 
-    let mut total_chars = 0u64;
-
-    // Walk directory and sum up file sizes for relevant files
-    if let Ok(entries) = fs::read_dir(&path) {
-        for entry in entries.filter_map(|e| e.ok()) {
-            let entry_path = entry.path();
+    let manifest = read_plugin_manifest(&root, &mut Vec::new());
+    let catalog_lsp = metadata.and_then(|m| m.lsp_servers.as_ref());
+    check_prerequisites(Some(&root), &manifest, catalog_lsp)
+        .into_iter()
+        .filter(|p| !p.satisfied)
+        .map(|p| p.binary)
+        .collect()
+}
 
-            // Count markdown and JSON files
-            if let Some(ext) = entry_path.extension() {
-                let ext_str = ext.to_string_lossy().to_lowercase();
-                if ext_str == "md" || ext_str == "json" {
-                    if let Ok(content) = fs::read_to_string(&entry_path) {
-                        total_chars += content.len() as u64;
-                    }
-                }
-            }
-        }
+/// Measure a plugin's context cost from its install path: everything it contributes is
+/// weighed, split into what's loaded while enabled and what loads on use
+fn estimate_plugin_weight(install_path: &str) -> PluginWeight {
+    if install_path.is_empty() {
+        return PluginWeight { always_loaded: 5000, on_demand: 0 }; // Base estimate for non-installed plugins
     }
 
-    // Convert chars to tokens (rough estimate: 4 chars per token)
-    // Add base overhead for plugin infrastructure
-    let tokens = (total_chars / 4) as u32 + 1000;
+    let path = PathBuf::from(install_path);
+    if !path.is_dir() {
+        return PluginWeight { always_loaded: 5000, on_demand: 0 };
+    }
 
-    // Clamp to reasonable range
-    tokens.clamp(1000, 50000)
+    measure_plugin_weight(&path)
 }
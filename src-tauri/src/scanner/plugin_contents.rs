@@ -0,0 +1,258 @@
+//! What a plugin folder contributes to Claude and how much of it reaches the context window
+//! Listings (names and descriptions) are always loaded; command, agent and skill bodies only when used.
+
+use ignore::WalkBuilder;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::limits::{capped_read_dir, read_capped, MAX_DIR_ENTRIES};
+use super::weight::estimate_tokens;
+
+/// One thing a plugin contributes to Claude
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PluginComponent {
+    /// "command", "agent", "skill", "hook" or "mcpServer"
+    pub kind: String,
+    pub name: String,
+    pub description: Option<String>,
+    /// File the component is defined in, relative to the plugin root
+    pub path: Option<String>,
+    /// Tokens in context whenever the plugin is enabled
+    pub always_loaded_tokens: u32,
+    /// Tokens added when the component is invoked
+    pub on_demand_tokens: u32,
+    /// Tools it declares it uses (`allowed-tools`, an agent's `tools`, an MCP server's `mcp__<name>`)
+    #[serde(default)]
+    pub tools: Vec<String>,
+}
+
+/// Context cost of a plugin, split by when it is paid
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PluginWeight {
+    pub always_loaded: u32,
+    pub on_demand: u32,
+}
+
+/// Base weight for an MCP server's tool definitions; the real size is only known once it runs
+pub const MCP_SERVER_WEIGHT: u32 = 2000;
+
+/// Framing Claude Code adds around each listed command, agent or skill
+const LISTING_OVERHEAD: u32 = 15;
+
+/// Read `.claude-plugin/plugin.json`, noting why it couldn't be read
+pub fn read_plugin_manifest(root: &Path, warnings: &mut Vec<String>) -> Value {
+    match fs::read_to_string(root.join(".claude-plugin").join("plugin.json")) {
+        Ok(content) => serde_json::from_str(&content).unwrap_or_else(|e| {
+            warnings.push(format!("Failed to parse .claude-plugin/plugin.json: {}", e));
+            Value::Null
+        }),
+        Err(_) => {
+            warnings.push("Plugin has no .claude-plugin/plugin.json".to_string());
+            Value::Null
+        }
+    }
+}
+
+/// YAML frontmatter of a markdown file
+fn frontmatter(content: &str) -> Option<serde_yaml::Value> {
+    let rest = content.trim_start().strip_prefix("---")?;
+    let end = rest.find("\n---")?;
+    serde_yaml::from_str(&rest[..end]).ok()
+}
+
+/// The `description` field from markdown frontmatter
+fn frontmatter_description(content: &str) -> Option<String> {
+    frontmatter(content)?.get("description").and_then(|d| d.as_str()).map(String::from)
+}
+
+/// `allowed-tools` (commands, skills) or `tools` (agents), as a list or a comma-separated string
+pub(crate) fn frontmatter_tools(content: &str) -> Vec<String> {
+    let Some(frontmatter) = frontmatter(content) else {
+        return Vec::new();
+    };
+    match frontmatter.get("allowed-tools").or_else(|| frontmatter.get("tools")) {
+        Some(serde_yaml::Value::String(tools)) => {
+            tools.split(',').map(str::trim).filter(|t| !t.is_empty()).map(String::from).collect()
+        }
+        Some(serde_yaml::Value::Sequence(tools)) => tools.iter().filter_map(|t| t.as_str()).map(String::from).collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Tokens for a component's entry in Claude's listing of commands, agents or skills
+fn listing_tokens(name: &str, description: Option<&str>) -> u32 {
+    estimate_tokens(name) + description.map(estimate_tokens).unwrap_or(0) + LISTING_OVERHEAD
+}
+
+/// Component folders from the manifest (string or array of paths), plus the default folder
+fn component_dirs(root: &Path, manifest: &Value, key: &str) -> Vec<PathBuf> {
+    let mut dirs = vec![root.join(key)];
+    let declared: Vec<&str> = match manifest.get(key) {
+        Some(Value::String(path)) => vec![path.as_str()],
+        Some(Value::Array(paths)) => paths.iter().filter_map(|p| p.as_str()).collect(),
+        _ => Vec::new(),
+    };
+    for path in declared {
+        let dir = root.join(path.trim_start_matches("./"));
+        if !dirs.contains(&dir) {
+            dirs.push(dir);
+        }
+    }
+    dirs
+}
+
+fn relative_path(root: &Path, path: &Path) -> String {
+    path.strip_prefix(root).unwrap_or(path).to_string_lossy().replace('\\', "/")
+}
+
+/// Markdown files under a folder, honouring .gitignore/.ignore files in the plugin
+fn markdown_files(dir: &Path) -> Vec<PathBuf> {
+    if !dir.is_dir() {
+        return Vec::new();
+    }
+    WalkBuilder::new(dir)
+        .require_git(false)
+        .build()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_some_and(|t| t.is_file()))
+        .map(|e| e.into_path())
+        .filter(|p| p.extension().is_some_and(|ext| ext == "md"))
+        .take(MAX_DIR_ENTRIES)
+        .collect()
+}
+
+/// Markdown components (commands, agents): one per .md file, named after the file
+fn markdown_components(root: &Path, dirs: &[PathBuf], kind: &str) -> Vec<PluginComponent> {
+    let mut components = Vec::new();
+    for path in dirs.iter().flat_map(|d| markdown_files(d)) {
+        let content = read_capped(&path).unwrap_or_default();
+        let name = path.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default();
+        let description = frontmatter_description(&content);
+        components.push(PluginComponent {
+            kind: kind.to_string(),
+            always_loaded_tokens: listing_tokens(&name, description.as_deref()),
+            on_demand_tokens: estimate_tokens(&content),
+            tools: frontmatter_tools(&content),
+            name,
+            description,
+            path: Some(relative_path(root, &path)),
+        });
+    }
+    components
+}
+
+/// Skills: one per folder containing a SKILL.md; its other markdown loads on demand too
+fn skill_components(root: &Path, dirs: &[PathBuf]) -> Vec<PluginComponent> {
+    let mut components = Vec::new();
+    for dir in dirs {
+        for entry in capped_read_dir(dir) {
+            let skill_dir = entry.path();
+            let skill_md = skill_dir.join("SKILL.md");
+            let Ok(content) = read_capped(&skill_md) else {
+                continue;
+            };
+            let name = entry.file_name().to_string_lossy().to_string();
+            let description = frontmatter_description(&content);
+            let on_demand = markdown_files(&skill_dir)
+                .iter()
+                .filter_map(|p| read_capped(p).ok())
+                .map(|c| estimate_tokens(&c))
+                .sum();
+            components.push(PluginComponent {
+                kind: "skill".to_string(),
+                always_loaded_tokens: listing_tokens(&name, description.as_deref()),
+                on_demand_tokens: on_demand,
+                tools: frontmatter_tools(&content),
+                name,
+                description,
+                path: Some(relative_path(root, &skill_md)),
+            });
+        }
+    }
+    components
+}
+
+/// A JSON config that is either inline in the manifest or a path to a file
+fn inline_or_file(root: &Path, manifest: &Value, key: &str, default_file: &str, warnings: &mut Vec<String>) -> Option<(Value, String)> {
+    let file = match manifest.get(key) {
+        Some(value @ Value::Object(_)) => return Some((value.clone(), ".claude-plugin/plugin.json".to_string())),
+        Some(Value::String(path)) => path.trim_start_matches("./").to_string(),
+        _ => default_file.to_string(),
+    };
+    let content = fs::read_to_string(root.join(&file)).ok()?;
+    match serde_json::from_str(&content) {
+        Ok(value) => Some((value, file)),
+        Err(e) => {
+            warnings.push(format!("Failed to parse {}: {}", file, e));
+            None
+        }
+    }
+}
+
+/// Enumerate the commands, agents, skills, hooks and MCP servers in a plugin folder
+pub fn plugin_components(root: &Path, manifest: &Value, warnings: &mut Vec<String>) -> Vec<PluginComponent> {
+    let mut components = markdown_components(root, &component_dirs(root, manifest, "commands"), "command");
+    components.extend(markdown_components(root, &component_dirs(root, manifest, "agents"), "agent"));
+    components.extend(skill_components(root, &component_dirs(root, manifest, "skills")));
+
+    // hooks.json: { "hooks": { "<Event>": [ { matcher, hooks: [...] } ] } }
+    if let Some((hooks, file)) = inline_or_file(root, manifest, "hooks", "hooks/hooks.json", warnings) {
+        let events = hooks.get("hooks").unwrap_or(&hooks);
+        for (event, matchers) in events.as_object().into_iter().flatten() {
+            for (index, matcher) in matchers.as_array().into_iter().flatten().enumerate() {
+                let label = matcher.get("matcher").and_then(|m| m.as_str()).filter(|m| !m.is_empty());
+                components.push(PluginComponent {
+                    kind: "hook".to_string(),
+                    name: match label {
+                        Some(label) => format!("{} ({})", event, label),
+                        None => format!("{} #{}", event, index + 1),
+                    },
+                    description: None,
+                    path: Some(file.clone()),
+                    // Hooks run outside the context window; only their output is added
+                    always_loaded_tokens: 0,
+                    on_demand_tokens: 0,
+                    tools: Vec::new(),
+                });
+            }
+        }
+    }
+
+    // .mcp.json: { "mcpServers": { "<name>": {...} } }
+    if let Some((servers, file)) = inline_or_file(root, manifest, "mcpServers", ".mcp.json", warnings) {
+        let servers = servers.get("mcpServers").unwrap_or(&servers);
+        for (name, config) in servers.as_object().into_iter().flatten() {
+            components.push(PluginComponent {
+                kind: "mcpServer".to_string(),
+                name: name.clone(),
+                description: config.get("command").and_then(|c| c.as_str()).map(|c| format!("Runs `{}`", c)),
+                path: Some(file.clone()),
+                // Tool definitions are sent with every request
+                always_loaded_tokens: MCP_SERVER_WEIGHT,
+                on_demand_tokens: 0,
+                tools: vec![format!("mcp__{}", name)],
+            });
+        }
+    }
+
+    components
+}
+
+/// Total context cost of a set of components
+pub fn total_weight(components: &[PluginComponent]) -> PluginWeight {
+    components.iter().fold(PluginWeight::default(), |total, c| PluginWeight {
+        always_loaded: total.always_loaded + c.always_loaded_tokens,
+        on_demand: total.on_demand + c.on_demand_tokens,
+    })
+}
+
+/// Measure a plugin folder's context cost from everything it contributes
+pub fn measure_plugin_weight(root: &Path) -> PluginWeight {
+    let mut warnings = Vec::new();
+    let manifest = read_plugin_manifest(root, &mut warnings);
+    total_weight(&plugin_components(root, &manifest, &mut warnings))
+}
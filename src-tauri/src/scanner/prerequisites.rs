@@ -0,0 +1,189 @@
+//! Host binaries a plugin needs (LSP servers, MCP runtimes, hook interpreters)
+//! Found from the commands a plugin declares, then looked up on PATH and the usual install folders.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+
+/// A binary a plugin needs on the host
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PluginPrerequisite {
+    pub binary: String,
+    /// What needs it, e.g. "LSP server rust-analyzer"
+    pub required_by: String,
+    pub satisfied: bool,
+    pub resolved_path: Option<String>,
+    pub install_hint: Option<String>,
+}
+
+/// Install folders GUI apps often miss because they don't inherit the login shell's PATH
+fn extra_search_dirs() -> Vec<PathBuf> {
+    let mut dirs = vec![PathBuf::from("/usr/local/bin"), PathBuf::from("/opt/homebrew/bin")];
+    if let Some(home) = crate::paths::home_dir() {
+        dirs.push(home.join(".cargo").join("bin"));
+        dirs.push(home.join(".local").join("bin"));
+        dirs.push(home.join("go").join("bin"));
+        dirs.push(home.join(".bun").join("bin"));
+    }
+    dirs
+}
+
+/// Resolve a binary the way a shell would, plus the common install folders
+pub fn find_binary(name: &str) -> Option<PathBuf> {
+    if name.contains(['/', '\\']) {
+        let path = PathBuf::from(name);
+        return path.is_file().then_some(path);
+    }
+
+    let extensions: Vec<String> = if cfg!(windows) {
+        std::env::var("PATHEXT")
+            .unwrap_or_else(|_| ".EXE;.CMD;.BAT".to_string())
+            .split(';')
+            .map(|e| e.to_lowercase())
+            .chain(std::iter::once(String::new()))
+            .collect()
+    } else {
+        vec![String::new()]
+    };
+
+    let path_dirs = std::env::var_os("PATH")
+        .map(|p| std::env::split_paths(&p).collect::<Vec<_>>())
+        .unwrap_or_default();
+
+    path_dirs
+        .into_iter()
+        .chain(extra_search_dirs())
+        .flat_map(|dir| extensions.iter().map(move |ext| dir.join(format!("{}{}", name, ext))))
+        .find(|candidate| candidate.is_file())
+}
+
+/// How to get a well-known binary
+pub(crate) fn install_hint(binary: &str) -> Option<&'static str> {
+    Some(match binary {
+        "rust-analyzer" => "rustup component add rust-analyzer",
+        "node" | "npx" | "npm" => "Install Node.js from https://nodejs.org",
+        "uv" | "uvx" => "Install uv: https://docs.astral.sh/uv/getting-started/installation/",
+        "python" | "python3" => "Install Python from https://www.python.org/downloads/",
+        "docker" => "Install Docker Desktop: https://docs.docker.com/get-docker/",
+        "typescript-language-server" => "npm install -g typescript-language-server typescript",
+        "pyright" | "pyright-langserver" => "npm install -g pyright",
+        "gopls" => "go install golang.org/x/tools/gopls@latest",
+        "clangd" => "Install clangd from your LLVM distribution",
+        "jdtls" => "Install Eclipse JDT Language Server",
+        "bun" => "Install Bun: https://bun.sh",
+        "deno" => "Install Deno: https://deno.com",
+        "jq" => "Install jq: https://jqlang.github.io/jq/download/",
+        _ => return None,
+    })
+}
+
+/// The program a command line runs, skipping env assignments and plugin-relative scripts
+fn program_of(command: &str) -> Option<String> {
+    let program = command
+        .split_whitespace()
+        .find(|word| !word.contains('='))?
+        .trim_matches(['"', '\'']);
+
+    // Scripts shipped inside the plugin need no install
+    if program.starts_with("${CLAUDE_PLUGIN_ROOT}") || program.starts_with('.') || program.is_empty() {
+        return None;
+    }
+    Some(program.to_string())
+}
+
+/// Named server configs ({ "<name>": { "command": ... } }) under a key, or the value itself
+fn server_commands<'a>(config: Option<&'a Value>, key: &str) -> Vec<(&'a String, &'a str)> {
+    let Some(config) = config else {
+        return Vec::new();
+    };
+    let servers = config.get(key).unwrap_or(config);
+    servers
+        .as_object()
+        .into_iter()
+        .flatten()
+        .filter_map(|(name, server)| server.get("command").and_then(|c| c.as_str()).map(|c| (name, c)))
+        .collect()
+}
+
+/// Binaries declared by a plugin: LSP and MCP server commands and hook commands.
+/// `catalog_lsp` is the marketplace entry's `lspServers`, which LSP plugins often declare there.
+pub fn declared_binaries(root: Option<&Path>, manifest: &Value, catalog_lsp: Option<&Value>) -> Vec<(String, String)> {
+    let read_json = |relative: &str| -> Option<Value> {
+        let content = std::fs::read_to_string(root?.join(relative)).ok()?;
+        serde_json::from_str(&content).ok()
+    };
+    // Config inline in the manifest, in the file it names, or in the default file
+    let config = |key: &str, default_file: &str| -> Option<Value> {
+        match manifest.get(key) {
+            Some(value @ Value::Object(_)) => Some(value.clone()),
+            Some(Value::String(path)) => read_json(path.trim_start_matches("./")),
+            _ => read_json(default_file),
+        }
+    };
+
+    let mut binaries: Vec<(String, String)> = Vec::new();
+    let mut add = |command: &str, required_by: String| {
+        if let Some(program) = program_of(command) {
+            if !binaries.iter().any(|(b, _)| *b == program) {
+                binaries.push((program, required_by));
+            }
+        }
+    };
+
+    let lsp = config("lspServers", ".lsp.json");
+    for (name, command) in server_commands(lsp.as_ref().or(catalog_lsp), "lspServers") {
+        add(command, format!("LSP server {}", name));
+    }
+
+    let mcp = config("mcpServers", ".mcp.json");
+    for (name, command) in server_commands(mcp.as_ref(), "mcpServers") {
+        add(command, format!("MCP server {}", name));
+    }
+
+    let hooks = config("hooks", "hooks/hooks.json");
+    if let Some(hooks) = hooks.as_ref() {
+        let events = hooks.get("hooks").unwrap_or(hooks);
+        for (event, matchers) in events.as_object().into_iter().flatten() {
+            for matcher in matchers.as_array().into_iter().flatten() {
+                for hook in matcher.get("hooks").and_then(|h| h.as_array()).into_iter().flatten() {
+                    if let Some(command) = hook.get("command").and_then(|c| c.as_str()) {
+                        add(command, format!("{} hook", event));
+                    }
+                }
+            }
+        }
+    }
+
+    binaries
+}
+
+/// Probe the host for each declared binary
+pub fn check_prerequisites(root: Option<&Path>, manifest: &Value, catalog_lsp: Option<&Value>) -> Vec<PluginPrerequisite> {
+    declared_binaries(root, manifest, catalog_lsp)
+        .into_iter()
+        .map(|(binary, required_by)| {
+            let resolved = find_binary(&binary);
+            PluginPrerequisite {
+                satisfied: resolved.is_some(),
+                resolved_path: resolved.map(|p| p.to_string_lossy().to_string()),
+                install_hint: install_hint(&binary).map(String::from),
+                binary,
+                required_by,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn program_of_skips_env_and_plugin_scripts() {
+        assert_eq!(program_of("uvx mcp-server-git"), Some("uvx".to_string()));
+        assert_eq!(program_of("NODE_ENV=production node server.js"), Some("node".to_string()));
+        assert_eq!(program_of("${CLAUDE_PLUGIN_ROOT}/hooks/check.sh"), None);
+        assert_eq!(program_of("./bin/run"), None);
+    }
+}
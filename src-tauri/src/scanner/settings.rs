@@ -1,78 +1,126 @@
 use std::collections::HashMap;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
-/// Claude Code settings.json structure (partial - for reading enabled plugins)
+use super::backups::backup_file;
+use crate::error::ArcadeError;
+
+/// Claude Code settings.json structure (partial - for reading enabled plugins and the model)
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct ClaudeSettings {
     #[serde(default)]
     pub enabled_plugins: HashMap<String, bool>,
+    #[serde(default)]
+    pub model: Option<String>,
 }
 
 /// Get path to Claude settings.json
 pub fn settings_path() -> Option<PathBuf> {
-    dirs::home_dir().map(|h| h.join(".claude").join("settings.json"))
+    crate::paths::claude_dir().map(|d| d.join("settings.json"))
 }
 
-/// Read Claude Code settings
-pub fn read_settings() -> ClaudeSettings {
-    settings_path()
-        .and_then(|path| fs::read_to_string(path).ok())
-        .and_then(|content| serde_json::from_str(&content).ok())
-        .unwrap_or_default()
+/// Read Claude Code settings. A settings.json that can't be read is an error, not an empty config,
+/// so nothing acts as if no plugin were enabled.
+pub fn read_settings() -> Result<ClaudeSettings, String> {
+    serde_json::from_value(read_settings_raw()?).map_err(|e| format!("Failed to parse settings.json: {}", e))
 }
 
-/// Read the raw settings.json as a JSON Value to preserve all fields
-fn read_settings_raw() -> Value {
-    settings_path()
-        .and_then(|path| fs::read_to_string(path).ok())
-        .and_then(|content| serde_json::from_str(&content).ok())
-        .unwrap_or_else(|| Value::Object(serde_json::Map::new()))
+/// Where and why a settings file failed to parse
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SettingsParseError {
+    pub path: String,
+    pub message: String,
+    pub line: usize,
+    pub column: usize,
 }
 
-/// Update only the enabledPlugins field while preserving all other settings
-fn update_enabled_plugins(enabled_plugins: &HashMap<String, bool>) -> Result<(), String> {
-    let path = settings_path().ok_or("Could not find home directory")?;
-
-    // Ensure parent directory exists
-    if let Some(parent) = path.parent() {
-        fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+impl std::fmt::Display for SettingsParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Failed to parse {} at line {}, column {}: {}", self.path, self.line, self.column, self.message)
     }
+}
 
-    // Read existing settings to preserve other fields
-    let mut settings = read_settings_raw();
+/// Parse settings JSON, also accepting what Claude Code tolerates
+/// (comments, trailing commas) by falling back to JSON5
+pub fn parse_settings_str(content: &str) -> Result<Value, serde_json::Error> {
+    if content.trim().is_empty() {
+        return Ok(Value::Object(serde_json::Map::new()));
+    }
 
-    // Update only the enabledPlugins field
-    if let Value::Object(ref mut map) = settings {
-        let plugins_value = serde_json::to_value(enabled_plugins)
-            .map_err(|e| e.to_string())?;
-        map.insert("enabledPlugins".to_string(), plugins_value);
+    match serde_json::from_str(content) {
+        Ok(value) => Ok(value),
+        // Report the strict parser's error; its position is the more precise one
+        Err(strict) => json5::from_str::<Value>(content).map_err(|_| strict),
     }
+}
 
-    // Write to temp file first, then rename (atomic)
-    let temp_path = path.with_extension("json.tmp");
-    let content = serde_json::to_string_pretty(&settings)
-        .map_err(|e| e.to_string())?;
+/// Load a settings file: Ok(None) when it doesn't exist, an error with position when it doesn't
+/// parse, and an io error when it is there but can't be read
+pub fn load_settings_file(path: &Path) -> Result<Option<Value>, ArcadeError> {
+    let content = match fs::read_to_string(path) {
+        Ok(c) => c,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(ArcadeError::io(path, e)),
+    };
 
-    fs::write(&temp_path, content).map_err(|e| e.to_string())?;
-    fs::rename(&temp_path, &path).map_err(|e| e.to_string())?;
+    let parsed = parse_settings_str(&content).map_err(|e| SettingsParseError {
+        path: path.to_string_lossy().to_string(),
+        message: e.to_string(),
+        line: e.line(),
+        column: e.column(),
+    })?;
+    Ok(Some(parsed))
+}
 
-    Ok(())
+/// Read any Claude settings file (user, project, local, managed) as a JSON Value
+pub fn read_settings_at(path: &Path) -> Option<Value> {
+    load_settings_file(path).ok().flatten()
+}
+
+/// Why the user settings.json can't be loaded (a parse error or an unreadable file), if it can't
+pub fn settings_error() -> Option<ArcadeError> {
+    settings_path().and_then(|path| load_settings_file(&path).err())
+}
+
+/// Read the raw settings.json as a JSON Value to preserve all fields.
+/// Fails when the file exists but can't be parsed, so writers never replace
+/// a broken file with an empty one.
+fn read_settings_raw() -> Result<Value, String> {
+    let path = settings_path().ok_or("Could not find home directory")?;
+    match load_settings_file(&path) {
+        Ok(Some(settings)) => Ok(settings),
+        Ok(None) => Ok(Value::Object(serde_json::Map::new())),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+/// Update only the enabledPlugins field while preserving all other settings
+fn update_enabled_plugins(enabled_plugins: &HashMap<String, bool>) -> Result<(), String> {
+    Ok(modify_settings(|settings| {
+        // Update only the enabledPlugins field
+        if let Value::Object(ref mut map) = settings {
+            let plugins_value = serde_json::to_value(enabled_plugins)
+                .map_err(|e| e.to_string())?;
+            map.insert("enabledPlugins".to_string(), plugins_value);
+        }
+        Ok(())
+    })?)
 }
 
 /// Enable a plugin in settings
 pub fn enable_plugin(plugin_id: &str) -> Result<(), String> {
-    let mut settings = read_settings();
+    let mut settings = read_settings()?;
     settings.enabled_plugins.insert(plugin_id.to_string(), true);
     update_enabled_plugins(&settings.enabled_plugins)
 }
 
 /// Disable a plugin in settings
 pub fn disable_plugin(plugin_id: &str) -> Result<(), String> {
-    let mut settings = read_settings();
+    let mut settings = read_settings()?;
     settings.enabled_plugins.remove(plugin_id);
     update_enabled_plugins(&settings.enabled_plugins)
 }
@@ -81,81 +129,87 @@ pub fn disable_plugin(plugin_id: &str) -> Result<(), String> {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MCPServerConfig {
     pub command: String,
+    #[serde(default)]
     pub args: Vec<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub env: Option<HashMap<String, String>>,
 }
 
-/// Read MCP servers from settings
-pub fn read_mcp_servers() -> HashMap<String, MCPServerConfig> {
-    let settings = read_settings_raw();
-    if let Some(mcp_servers) = settings.get("mcpServers") {
-        serde_json::from_value(mcp_servers.clone()).unwrap_or_default()
-    } else {
-        HashMap::new()
+/// Read the command-line MCP servers from settings; remote (sse/http) servers have no command and
+/// are left out. Fails when settings.json can't be read.
+pub fn read_mcp_servers() -> Result<HashMap<String, MCPServerConfig>, String> {
+    let settings = read_settings_raw()?;
+    match settings.get("mcpServers") {
+        None => Ok(HashMap::new()),
+        Some(Value::Object(servers)) => Ok(servers
+            .iter()
+            .filter_map(|(name, config)| Some((name.clone(), serde_json::from_value(config.clone()).ok()?)))
+            .collect()),
+        Some(_) => Err("Failed to parse settings.json: mcpServers is not an object".to_string()),
     }
 }
 
 /// Add an MCP server to settings
 pub fn install_mcp_server(server_id: &str, command: &str, args: Vec<String>) -> Result<(), String> {
-    let path = settings_path().ok_or("Could not find home directory")?;
-
-    // Read existing settings to preserve other fields
-    let mut settings = read_settings_raw();
+    install_mcp_server_config(server_id, MCPServerConfig {
+        command: command.to_string(),
+        args,
+        env: None,
+    })
+}
 
-    // Get or create mcpServers object
-    let mcp_servers = if let Value::Object(ref mut map) = settings {
-        map.entry("mcpServers".to_string())
-            .or_insert_with(|| Value::Object(serde_json::Map::new()))
-    } else {
-        return Err("Settings is not an object".to_string());
-    };
+/// Add an MCP server with its full config (including env) to settings
+pub fn install_mcp_server_config(server_id: &str, config: MCPServerConfig) -> Result<(), String> {
+    let config_value = serde_json::to_value(&config).map_err(|e| e.to_string())?;
+    install_mcp_server_value(server_id, config_value)
+}
 
-    // Add the new server
-    if let Value::Object(ref mut servers) = mcp_servers {
-        let config = MCPServerConfig {
-            command: command.to_string(),
-            args,
-            env: None,
+/// Add an MCP server config as-is (stdio, sse or http) to settings
+pub fn install_mcp_server_value(server_id: &str, config_value: Value) -> Result<(), String> {
+    Ok(modify_settings(|settings| {
+        // Get or create mcpServers object
+        let mcp_servers = if let Value::Object(ref mut map) = settings {
+            map.entry("mcpServers".to_string())
+                .or_insert_with(|| Value::Object(serde_json::Map::new()))
+        } else {
+            return Err("Settings is not an object".to_string());
         };
-        let config_value = serde_json::to_value(&config).map_err(|e| e.to_string())?;
-        servers.insert(server_id.to_string(), config_value);
-    }
-
-    // Write atomically
-    let temp_path = path.with_extension("json.tmp");
-    let content = serde_json::to_string_pretty(&settings).map_err(|e| e.to_string())?;
-    fs::write(&temp_path, content).map_err(|e| e.to_string())?;
-    fs::rename(&temp_path, &path).map_err(|e| e.to_string())?;
 
-    Ok(())
+        // Add the new server
+        if let Value::Object(ref mut servers) = mcp_servers {
+            servers.insert(server_id.to_string(), config_value);
+        }
+        Ok(())
+    })?)
 }
 
 /// Remove an MCP server from settings
 pub fn remove_mcp_server(server_id: &str) -> Result<(), String> {
-    let path = settings_path().ok_or("Could not find home directory")?;
-
-    // Read existing settings
-    let mut settings = read_settings_raw();
-
-    // Remove the server from mcpServers
-    if let Value::Object(ref mut map) = settings {
-        if let Some(Value::Object(ref mut servers)) = map.get_mut("mcpServers") {
-            servers.remove(server_id);
+    Ok(modify_settings(|settings| {
+        // Remove the server from mcpServers
+        if let Value::Object(ref mut map) = settings {
+            if let Some(Value::Object(ref mut servers)) = map.get_mut("mcpServers") {
+                servers.remove(server_id);
+            }
         }
-    }
+        Ok(())
+    })?)
+}
 
-    // Write atomically
-    let temp_path = path.with_extension("json.tmp");
-    let content = serde_json::to_string_pretty(&settings).map_err(|e| e.to_string())?;
-    fs::write(&temp_path, content).map_err(|e| e.to_string())?;
-    fs::rename(&temp_path, &path).map_err(|e| e.to_string())?;
+/// Permission modes accepted by Claude Code for `permissions.defaultMode`
+pub const PERMISSION_MODES: [&str; 5] = ["default", "acceptEdits", "plan", "dontAsk", "bypassPermissions"];
 
-    Ok(())
+fn check_permission_mode(mode: &str) -> Result<(), String> {
+    if PERMISSION_MODES.contains(&mode) {
+        Ok(())
+    } else {
+        Err(format!("Unknown permission mode '{}'. Expected one of: {}", mode, PERMISSION_MODES.join(", ")))
+    }
 }
 
 /// Permissions configuration
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
 pub struct PermissionsConfig {
     #[serde(default)]
     pub allow: Vec<String>,
@@ -163,42 +217,453 @@ pub struct PermissionsConfig {
     pub ask: Vec<String>,
     #[serde(default)]
     pub deny: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_mode: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub additional_directories: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub disable_bypass_permissions_mode: Option<String>,
+    /// Any other keys in the permissions block, kept so they survive a save
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, Value>,
+}
+
+/// Read permissions from settings; fails when settings.json or its permissions block can't be read
+pub fn read_permissions() -> Result<PermissionsConfig, String> {
+    let settings = read_settings_raw()?;
+    match settings.get("permissions") {
+        Some(permissions) => {
+            serde_json::from_value(permissions.clone()).map_err(|e| format!("Failed to parse settings.json permissions: {}", e))
+        }
+        None => Ok(PermissionsConfig::default()),
+    }
+}
+
+/// Hash of a file's bytes, used to notice writes made behind our back
+fn file_hash(path: &Path) -> Option<u64> {
+    use std::hash::{Hash, Hasher};
+
+    let content = fs::read(path).ok()?;
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    content.hash(&mut hasher);
+    Some(hasher.finish())
+}
+
+fn revision_of(hash: Option<u64>) -> String {
+    hash.map_or_else(|| "none".to_string(), |h| format!("{:016x}", h))
+}
+
+/// Token for the current contents of settings.json. Take it before reading the settings it goes
+/// with: a write in between then shows up as a conflict instead of being overwritten.
+pub fn settings_revision() -> String {
+    revision_of(settings_path().and_then(|path| file_hash(&path)))
+}
+
+/// Something read from settings.json, with the revision to write changes to it against
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AtRevision<T> {
+    #[serde(flatten)]
+    pub value: T,
+    pub revision: String,
+}
+
+/// Both sides of a change that couldn't be made because settings.json was
+/// edited elsewhere (usually by Claude Code) since it was read
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SettingsConflict {
+    pub path: String,
+    /// Dotted keys both sides changed differently; empty when the caller's copy was simply out of date
+    pub keys: Vec<String>,
+    pub ours: Value,
+    pub theirs: Value,
+    /// Revision of `theirs`, to retry against
+    pub revision: String,
 }
 
-/// Read permissions from settings
-pub fn read_permissions() -> PermissionsConfig {
-    let settings = read_settings_raw();
-    if let Some(permissions) = settings.get("permissions") {
-        serde_json::from_value(permissions.clone()).unwrap_or_default()
+/// Three-way merge of settings objects: our changes relative to `base` are applied
+/// on top of `theirs`. Nested objects merge key by key; a key both sides changed
+/// to different values is a conflict.
+pub fn merge_settings(base: &Value, ours: &Value, theirs: &Value) -> Result<Value, Vec<String>> {
+    let mut conflicts = Vec::new();
+    let merged = merge_values(base, ours, theirs, "", &mut conflicts);
+    if conflicts.is_empty() {
+        Ok(merged)
     } else {
-        PermissionsConfig::default()
+        Err(conflicts)
     }
 }
 
-/// Write permissions to settings
-pub fn write_permissions(permissions: &PermissionsConfig) -> Result<(), String> {
+fn merge_values(base: &Value, ours: &Value, theirs: &Value, prefix: &str, conflicts: &mut Vec<String>) -> Value {
+    let (Value::Object(b), Value::Object(o), Value::Object(t)) = (base, ours, theirs) else {
+        if ours == base || ours == theirs {
+            return theirs.clone();
+        }
+        if theirs == base {
+            return ours.clone();
+        }
+        conflicts.push(prefix.to_string());
+        return ours.clone();
+    };
+
+    let mut merged = t.clone();
+    let mut keys: Vec<&String> = b.keys().chain(o.keys()).collect();
+    keys.sort();
+    keys.dedup();
+    for key in keys {
+        let (bv, ov, tv) = (b.get(key), o.get(key), t.get(key));
+        if ov == bv {
+            continue;
+        }
+
+        let path = if prefix.is_empty() { key.clone() } else { format!("{}.{}", prefix, key) };
+        match (bv, ov, tv) {
+            // We removed it; fine unless they changed it
+            (Some(bv), None, Some(tv)) if tv != bv => conflicts.push(path),
+            (_, None, _) => {
+                merged.remove(key);
+            }
+            (Some(bv), Some(ov), Some(tv)) => {
+                merged.insert(key.clone(), merge_values(bv, ov, tv, &path, conflicts));
+            }
+            // We added it; they removed it or added something different
+            (None, Some(ov), Some(tv)) if tv != ov => conflicts.push(path),
+            (Some(_), Some(_), None) => conflicts.push(path),
+            (_, Some(ov), _) => {
+                merged.insert(key.clone(), ov.clone());
+            }
+        }
+    }
+
+    Value::Object(merged)
+}
+
+/// Read-modify-write settings.json. If the file changed on disk between the read
+/// and the write, our edit is re-applied on top of the new contents, or a conflict
+/// error carrying both versions is returned when the same keys were changed.
+pub(crate) fn modify_settings<F>(edit: F) -> Result<(), ArcadeError>
+where
+    F: FnOnce(&mut Value) -> Result<(), String>,
+{
+    modify_settings_at(None, edit)
+}
+
+/// `modify_settings` for an edit made from an earlier read: when settings.json is no longer at
+/// `revision`, nothing is written and the conflict carries the current contents to redo it on.
+pub(crate) fn modify_settings_at<F>(revision: Option<&str>, edit: F) -> Result<(), ArcadeError>
+where
+    F: FnOnce(&mut Value) -> Result<(), String>,
+{
     let path = settings_path().ok_or("Could not find home directory")?;
+    let base_hash = file_hash(&path);
+    let base = read_settings_raw()?;
+
+    let mut ours = base.clone();
+    edit(&mut ours)?;
+
+    let conflict = |keys, ours, theirs| ArcadeError::SettingsConflict {
+        conflict: Box::new(SettingsConflict {
+            path: path.to_string_lossy().to_string(),
+            keys,
+            ours,
+            theirs,
+            revision: revision_of(file_hash(&path)),
+        }),
+    };
+    if revision.is_some_and(|r| r != revision_of(base_hash)) {
+        return Err(conflict(Vec::new(), ours, base));
+    }
 
+    if file_hash(&path) == base_hash {
+        return Ok(write_settings_at(&path, &ours)?);
+    }
+
+    // Someone else wrote settings.json while we were working
+    let theirs = read_settings_raw()?;
+    match merge_settings(&base, &ours, &theirs) {
+        Ok(merged) => Ok(write_settings_at(&path, &merged)?),
+        Err(keys) => Err(conflict(keys, ours, theirs)),
+    }
+}
+
+/// Write any settings file atomically, backing up the previous version first.
+/// The write is registered with the watcher so it isn't reported back as an external change.
+pub fn write_settings_at(path: &Path, settings: &Value) -> Result<(), String> {
     // Ensure parent directory exists
     if let Some(parent) = path.parent() {
         fs::create_dir_all(parent).map_err(|e| e.to_string())?;
     }
 
-    // Read existing settings to preserve other fields
-    let mut settings = read_settings_raw();
+    let backup = backup_file(path)?;
+
+    let content = serde_json::to_string_pretty(settings).map_err(|e| e.to_string())?;
+    crate::watcher::write_expected(path, content).map_err(|e| e.to_string())?;
+
+    // Verify what landed on disk parses, and point at the backup if it doesn't
+    if read_settings_at(path).is_none() {
+        return Err(match backup {
+            Some(b) => format!(
+                "{} is not valid JSON after writing. Restore backup {} to recover",
+                path.display(),
+                b.id
+            ),
+            None => format!("{} is not valid JSON after writing", path.display()),
+        });
+    }
 
-    // Update only the permissions field
-    if let Value::Object(ref mut map) = settings {
-        let permissions_value = serde_json::to_value(permissions)
-            .map_err(|e| e.to_string())?;
-        map.insert("permissions".to_string(), permissions_value);
+    Ok(())
+}
+
+/// Replace the permissions block as-is, without merging existing keys
+fn replace_permissions(permissions: &PermissionsConfig) -> Result<(), String> {
+    let permissions_value = serde_json::to_value(permissions)
+        .map_err(|e| e.to_string())?;
+
+    Ok(modify_settings(|settings| {
+        if let Value::Object(ref mut map) = settings {
+            map.insert("permissions".to_string(), permissions_value);
+            Ok(())
+        } else {
+            Err("Settings is not an object".to_string())
+        }
+    })?)
+}
+
+/// Write permissions to settings, refusing when settings.json no longer matches `revision`.
+/// Keys the caller left unset (e.g. `defaultMode` from a client that only
+/// edits allow/ask/deny) keep their current value in settings.json; `set_default_mode` clears the mode.
+pub fn write_permissions(permissions: &PermissionsConfig, revision: Option<&str>) -> Result<(), ArcadeError> {
+    if let Some(mode) = &permissions.default_mode {
+        check_permission_mode(mode)?;
+    }
+    let mut permissions_value = serde_json::to_value(permissions)
+        .map_err(|e| e.to_string())?;
+
+    modify_settings_at(revision, |settings| {
+        // Update only the permissions field
+        if let Value::Object(ref mut map) = settings {
+            if let (Some(Value::Object(existing)), Value::Object(ref mut updated)) =
+                (map.get("permissions"), &mut permissions_value)
+            {
+                for (key, value) in existing {
+                    updated.entry(key.clone()).or_insert_with(|| value.clone());
+                }
+            }
+
+            map.insert("permissions".to_string(), permissions_value);
+        }
+        Ok(())
+    })
+}
+
+/// Set or clear `permissions.defaultMode`
+pub fn set_default_mode(mode: Option<&str>) -> Result<(), String> {
+    if let Some(mode) = mode {
+        check_permission_mode(mode)?;
     }
 
-    // Write atomically
-    let temp_path = path.with_extension("json.tmp");
-    let content = serde_json::to_string_pretty(&settings).map_err(|e| e.to_string())?;
-    fs::write(&temp_path, content).map_err(|e| e.to_string())?;
-    fs::rename(&temp_path, &path).map_err(|e| e.to_string())?;
+    let mut permissions = read_permissions()?;
+    permissions.default_mode = mode.map(|m| m.to_string());
+    replace_permissions(&permissions)
+}
 
-    Ok(())
+/// Replace `permissions.additionalDirectories` (an empty list removes the key)
+pub fn set_additional_directories(directories: Vec<String>) -> Result<(), String> {
+    let mut permissions = read_permissions()?;
+    permissions.additional_directories = if directories.is_empty() {
+        None
+    } else {
+        Some(directories)
+    };
+    replace_permissions(&permissions)
+}
+
+/// Set or clear `permissions.disableBypassPermissionsMode`
+pub fn set_disable_bypass_permissions_mode(disabled: bool) -> Result<(), String> {
+    let mut permissions = read_permissions()?;
+    permissions.disable_bypass_permissions_mode = if disabled {
+        Some("disable".to_string())
+    } else {
+        None
+    };
+    replace_permissions(&permissions)
+}
+
+/// Status line configuration (`statusLine`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StatusLineConfig {
+    #[serde(rename = "type")]
+    pub line_type: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub command: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub padding: Option<u32>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, Value>,
+}
+
+/// The full settings.json document. Known keys are typed; everything else is
+/// kept in `extra` so a read-modify-write never drops settings we don't model.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ClaudeSettingsFull {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub env: Option<HashMap<String, String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub permissions: Option<PermissionsConfig>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hooks: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status_line: Option<StatusLineConfig>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output_style: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub include_co_authored_by: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cleanup_period_days: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub api_key_helper: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub force_login_method: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enable_all_project_mcp_servers: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enabled_mcpjson_servers: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub disabled_mcpjson_servers: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub always_thinking_enabled: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub disable_all_hooks: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enabled_plugins: Option<HashMap<String, bool>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub extra_known_marketplaces: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mcp_servers: Option<HashMap<String, Value>>,
+    /// Keys not modeled above
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, Value>,
+}
+
+/// Read the whole settings.json as a typed document
+pub fn read_settings_full() -> Result<ClaudeSettingsFull, String> {
+    serde_json::from_value(read_settings_raw()?)
+        .map_err(|e| format!("Failed to parse settings.json: {}", e))
+}
+
+/// The whole settings.json as a typed document, with its revision
+pub fn read_settings_at_revision() -> Result<AtRevision<ClaudeSettingsFull>, ArcadeError> {
+    let revision = settings_revision();
+    Ok(AtRevision { value: read_settings_full()?, revision })
+}
+
+/// Set a single settings field, addressed by a dotted path (e.g. `statusLine.command`).
+/// A null value removes the field. The result is type-checked before it is written.
+pub fn update_settings_field(key: &str, value: Value) -> Result<ClaudeSettingsFull, ArcadeError> {
+    update_settings_field_at(None, key, value)
+}
+
+/// `update_settings_field`, refused when settings.json is no longer at `revision`
+pub fn update_settings_field_at(revision: Option<&str>, key: &str, value: Value) -> Result<ClaudeSettingsFull, ArcadeError> {
+    let segments: Vec<&str> = key.split('.').collect();
+    if segments.iter().any(|s| s.is_empty()) {
+        return Err(ArcadeError::invalid_input(format!("Invalid settings key '{}'", key)));
+    }
+
+    let (last, parents) = segments.split_last().ok_or("Settings key cannot be empty")?;
+
+    modify_settings_at(revision, |settings| {
+        let mut current = &mut *settings;
+        for segment in parents {
+            let map = current.as_object_mut()
+                .ok_or_else(|| format!("'{}' is not an object in settings.json", segment))?;
+            current = map.entry(segment.to_string())
+                .or_insert_with(|| Value::Object(serde_json::Map::new()));
+        }
+
+        let map = current.as_object_mut()
+            .ok_or_else(|| format!("Cannot set '{}': parent is not an object", key))?;
+        if value.is_null() {
+            map.remove(*last);
+        } else {
+            map.insert(last.to_string(), value);
+        }
+
+        // Type-check before anything is written
+        serde_json::from_value::<ClaudeSettingsFull>(settings.clone())
+            .map(|_| ())
+            .map_err(|e| format!("Invalid value for '{}': {}", key, e))
+    })?;
+
+    Ok(read_settings_full()?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn merge_applies_our_change_on_top_of_theirs() {
+        let base = json!({ "model": "sonnet", "enabledPlugins": { "a@m": true } });
+        let ours = json!({ "model": "sonnet", "enabledPlugins": { "a@m": true, "b@m": true } });
+        let theirs = json!({ "model": "opus", "enabledPlugins": { "a@m": true } });
+
+        let merged = merge_settings(&base, &ours, &theirs).unwrap();
+        assert_eq!(merged, json!({ "model": "opus", "enabledPlugins": { "a@m": true, "b@m": true } }));
+    }
+
+    #[test]
+    fn merge_reports_keys_changed_on_both_sides() {
+        let base = json!({ "permissions": { "defaultMode": "default", "allow": [] } });
+        let ours = json!({ "permissions": { "defaultMode": "plan", "allow": [] } });
+        let theirs = json!({ "permissions": { "defaultMode": "acceptEdits", "allow": ["Bash"] } });
+
+        assert_eq!(merge_settings(&base, &ours, &theirs), Err(vec!["permissions.defaultMode".to_string()]));
+    }
+
+    #[test]
+    fn saving_permissions_without_a_mode_keeps_it() {
+        let root = crate::test_support::TempDir::new("settings-permissions");
+        fs::create_dir_all(root.join(".claude")).unwrap();
+        let path = root.join(".claude/settings.json");
+        fs::write(&path, r#"{"permissions":{"defaultMode":"plan","additionalDirectories":["/src"],"allow":[]}}"#).unwrap();
+
+        let saved = crate::paths::ConfigPaths::under(&root).scoped(|| {
+            let permissions = PermissionsConfig { allow: vec!["Bash(ls:*)".to_string()], ..Default::default() };
+            write_permissions(&permissions, None).unwrap();
+            assert_eq!(read_permissions().unwrap().default_mode.as_deref(), Some("plan"));
+            let unknown = PermissionsConfig { default_mode: Some("yolo".to_string()), ..Default::default() };
+            assert!(write_permissions(&unknown, None).is_err());
+            set_default_mode(None).unwrap();
+            assert_eq!(read_permissions().unwrap().default_mode, None);
+            set_default_mode(Some("dontAsk")).unwrap();
+            read_permissions().unwrap()
+        });
+
+        assert_eq!(saved.allow, vec!["Bash(ls:*)"]);
+        assert_eq!(saved.additional_directories, Some(vec!["/src".to_string()]));
+        assert_eq!(saved.default_mode.as_deref(), Some("dontAsk"));
+        let written: Value = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(written["permissions"]["defaultMode"], "dontAsk");
+    }
+
+    #[test]
+    fn unreadable_settings_are_an_error_not_empty_settings() {
+        let root = crate::test_support::TempDir::new("settings-unreadable");
+        fs::create_dir_all(root.join(".claude/settings.json")).unwrap();
+
+        assert_eq!(load_settings_file(&root.join(".claude/missing.json")), Ok(None));
+        crate::paths::ConfigPaths::under(&root).scoped(|| {
+            assert!(matches!(load_settings_file(&root.join(".claude/settings.json")), Err(ArcadeError::Io { .. })));
+            assert!(read_permissions().is_err());
+            assert!(set_default_mode(Some("plan")).is_err());
+        });
+        assert!(root.join(".claude/settings.json").is_dir());
+    }
 }
@@ -1,8 +1,9 @@
-use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use serde::Deserialize;
 
 use crate::types::{InventoryItem, ItemType, ItemRarity, ItemSource};
+use super::frontmatter::Frontmatter;
+use super::limits::{capped_read_dir, read_capped};
 use super::plugin::claude_config_dir;
 
 /// Skill metadata from YAML frontmatter in SKILL.md
@@ -30,6 +31,10 @@ impl SkillScope {
     }
 }
 
+/// Sibling of `skills/` holding skills a spellbook put away. Claude Code doesn't read it, so
+/// they cost no context until moved back.
+pub const DISABLED_SKILLS_DIR: &str = "skills-disabled";
+
 /// Get the user skills directory
 fn get_user_skills_dir() -> Option<PathBuf> {
     claude_config_dir().map(|d| d.join("skills"))
@@ -40,35 +45,22 @@ fn get_project_skills_dir(project_path: &str) -> PathBuf {
     PathBuf::from(project_path).join(".claude").join("skills")
 }
 
+/// The skills folder and the disabled skills folder for a skill at `skill_dir`
+pub(crate) fn skill_folders(skill_dir: &Path) -> Option<(PathBuf, PathBuf)> {
+    let claude_dir = skill_dir.parent()?.parent()?;
+    Some((claude_dir.join("skills"), claude_dir.join(DISABLED_SKILLS_DIR)))
+}
+
 /// Parse YAML frontmatter from markdown content
 fn parse_frontmatter(content: &str) -> Option<SkillFrontmatter> {
-    // Check if content starts with ---
-    let content = content.trim();
-    if !content.starts_with("---") {
-        return None;
-    }
-
-    // Find the closing ---
-    let after_first = &content[3..];
-    let end_pos = after_first.find("---")?;
-    let yaml_content = &after_first[..end_pos].trim();
-
-    // Parse the YAML
-    serde_yaml::from_str(yaml_content).ok()
+    Frontmatter::parse(content).deserialize()
 }
 
 /// Extract first non-empty, non-heading line as description
 fn extract_description_from_content(content: &str) -> Option<String> {
     // Skip frontmatter if present
-    let body = if content.trim().starts_with("---") {
-        if let Some(pos) = content[3..].find("---") {
-            &content[pos + 6..]
-        } else {
-            content
-        }
-    } else {
-        content
-    };
+    let doc = Frontmatter::parse(content);
+    let body = doc.body();
 
     // Find first meaningful line (skip headings)
     body.lines()
@@ -118,18 +110,14 @@ fn determine_skill_rarity(frontmatter: &Option<SkillFrontmatter>, scope: SkillSc
 }
 
 /// Estimate token weight for a skill
-fn estimate_skill_weight(skill_dir: &PathBuf) -> u32 {
+fn estimate_skill_weight(skill_dir: &Path) -> u32 {
     let mut total_chars = 0u64;
 
-    // Count all markdown files in the skill directory
-    if let Ok(entries) = fs::read_dir(skill_dir) {
-        for entry in entries.filter_map(|e| e.ok()) {
-            let path = entry.path();
-            if path.extension().map_or(false, |e| e == "md") {
-                if let Ok(content) = fs::read_to_string(&path) {
-                    total_chars += content.len() as u64;
-                }
-            }
+    // Count all markdown files in the skill directory; their size is enough, no need to read them
+    for entry in capped_read_dir(skill_dir) {
+        let path = entry.path();
+        if path.extension().map_or(false, |e| e == "md") {
+            total_chars += entry.metadata().map(|m| m.len()).unwrap_or(0);
         }
     }
 
@@ -158,19 +146,14 @@ fn format_skill_name(dir_name: &str) -> String {
 }
 
 /// Scan a directory for skill subdirectories
-fn scan_skills_dir(dir: &PathBuf, scope: SkillScope) -> Vec<InventoryItem> {
+fn scan_skills_dir(dir: &Path, scope: SkillScope, enabled: bool) -> Vec<InventoryItem> {
     let mut skills = Vec::new();
 
     if !dir.exists() {
         return skills;
     }
 
-    let entries = match fs::read_dir(dir) {
-        Ok(e) => e,
-        Err(_) => return skills,
-    };
-
-    for entry in entries.filter_map(|e| e.ok()) {
+    for entry in capped_read_dir(dir) {
         let path = entry.path();
 
         // Only process directories
@@ -187,7 +170,7 @@ fn scan_skills_dir(dir: &PathBuf, scope: SkillScope) -> Vec<InventoryItem> {
         let skill_md_path = find_skill_md(&path);
 
         let (frontmatter, content) = if let Some(md_path) = skill_md_path {
-            let content = fs::read_to_string(&md_path).unwrap_or_default();
+            let content = read_capped(&md_path).unwrap_or_default();
             let fm = parse_frontmatter(&content);
             (fm, content)
         } else {
@@ -225,10 +208,11 @@ fn scan_skills_dir(dir: &PathBuf, scope: SkillScope) -> Vec<InventoryItem> {
             source: ItemSource::Skill,
             source_path: path.to_string_lossy().to_string(),
             token_weight,
-            enabled: true, // Skills are always "enabled" (loaded on demand by Claude)
+            enabled, // Skills are loaded on demand by Claude unless put away in skills-disabled/
             version: None,
             author: None,
             status: None,
+            environment: None,
         });
     }
 
@@ -236,7 +220,7 @@ fn scan_skills_dir(dir: &PathBuf, scope: SkillScope) -> Vec<InventoryItem> {
 }
 
 /// Find SKILL.md file (case insensitive)
-fn find_skill_md(skill_dir: &PathBuf) -> Option<PathBuf> {
+pub(crate) fn find_skill_md(skill_dir: &Path) -> Option<PathBuf> {
     let candidates = ["SKILL.md", "skill.md", "Skill.md"];
 
     for name in candidates {
@@ -255,15 +239,17 @@ pub fn scan_skills(project_path: Option<&str>) -> Vec<InventoryItem> {
 
     // Scan user skills (~/.claude/skills/)
     if let Some(user_dir) = get_user_skills_dir() {
-        let user_skills = scan_skills_dir(&user_dir, SkillScope::User);
+        let user_skills = scan_skills_dir(&user_dir, SkillScope::User, true);
         all_skills.extend(user_skills);
+        all_skills.extend(scan_skills_dir(&user_dir.with_file_name(DISABLED_SKILLS_DIR), SkillScope::User, false));
     }
 
     // Scan project skills (.claude/skills/) if project path provided
     if let Some(path) = project_path {
         let project_dir = get_project_skills_dir(path);
-        let project_skills = scan_skills_dir(&project_dir, SkillScope::Project);
+        let project_skills = scan_skills_dir(&project_dir, SkillScope::Project, true);
         all_skills.extend(project_skills);
+        all_skills.extend(scan_skills_dir(&project_dir.with_file_name(DISABLED_SKILLS_DIR), SkillScope::Project, false));
     }
 
     // Sort by name
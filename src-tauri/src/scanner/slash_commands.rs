@@ -1,8 +1,10 @@
-use std::fs;
-use std::path::PathBuf;
-use serde::Deserialize;
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use serde::{Deserialize, Serialize};
 
 use crate::types::{InventoryItem, ItemType, ItemRarity, ItemSource};
+use super::frontmatter::Frontmatter;
+use super::limits::{capped_read_dir, read_capped};
 use super::plugin::claude_config_dir;
 
 /// Slash command metadata from YAML frontmatter
@@ -10,7 +12,125 @@ use super::plugin::claude_config_dir;
 #[serde(rename_all = "kebab-case")]
 struct CommandFrontmatter {
     description: Option<String>,
-    allowed_tools: Option<Vec<String>>,
+    allowed_tools: Option<ToolList>,
+    /// Usually `[message]`, which YAML reads as a list
+    argument_hint: Option<serde_yaml::Value>,
+    model: Option<String>,
+    #[serde(default)]
+    disable_model_invocation: bool,
+}
+
+/// `allowed-tools` as a list or a comma-separated line, e.g. `Bash(git add:*), Read`
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum ToolList {
+    List(Vec<String>),
+    Line(String),
+}
+
+impl ToolList {
+    fn tools(&self) -> Vec<String> {
+        match self {
+            ToolList::List(tools) => tools.clone(),
+            ToolList::Line(line) => split_tools(line),
+        }
+    }
+}
+
+/// Split on commas outside parentheses, so `Bash(git add:*, git commit:*)` stays one entry
+fn split_tools(line: &str) -> Vec<String> {
+    let mut tools = Vec::new();
+    let mut depth = 0usize;
+    let mut current = String::new();
+    for c in line.chars() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth = depth.saturating_sub(1),
+            ',' if depth == 0 => {
+                tools.push(std::mem::take(&mut current));
+                continue;
+            }
+            _ => {}
+        }
+        current.push(c);
+    }
+    tools.push(current);
+    tools.into_iter().map(|t| t.trim().to_string()).filter(|t| !t.is_empty()).collect()
+}
+
+/// `argument-hint` as written: a string as is, a YAML list back in its `[a] [b]` form
+fn argument_hint_text(hint: &serde_yaml::Value) -> Option<String> {
+    match hint {
+        serde_yaml::Value::String(s) => Some(s.clone()),
+        serde_yaml::Value::Sequence(items) => Some(
+            items
+                .iter()
+                .map(|item| match item {
+                    serde_yaml::Value::String(s) => format!("[{}]", s),
+                    other => format!("[{}]", serde_yaml::to_string(other).unwrap_or_default().trim()),
+                })
+                .collect::<Vec<_>>()
+                .join(" "),
+        ),
+        serde_yaml::Value::Null => None,
+        other => serde_yaml::to_string(other).ok().map(|s| s.trim().to_string()),
+    }
+}
+
+/// Everything a slash command file declares, for the command detail view
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SlashCommandDetails {
+    pub name: String,
+    pub description: Option<String>,
+    pub argument_hint: Option<String>,
+    pub allowed_tools: Vec<String>,
+    /// Model the command runs with instead of the session's
+    pub model: Option<String>,
+    /// Set when Claude may not run the command itself through the SlashCommand tool
+    pub disable_model_invocation: bool,
+    /// The body takes all arguments through `$ARGUMENTS`
+    pub uses_arguments: bool,
+    /// Positional arguments the body uses (`$1`, `$2`, ...), ascending
+    pub positional_arguments: Vec<u32>,
+    pub file_path: String,
+    pub content: String,
+}
+
+/// Positional arguments (`$1`, `$2`, ...) referenced in a command body
+fn positional_arguments(body: &str) -> Vec<u32> {
+    let mut found = BTreeSet::new();
+    let mut rest = body;
+    while let Some(pos) = rest.find('$') {
+        rest = &rest[pos + 1..];
+        let digits: String = rest.chars().take_while(|c| c.is_ascii_digit()).collect();
+        if let Ok(n) = digits.parse::<u32>() {
+            if n > 0 {
+                found.insert(n);
+            }
+        }
+    }
+    found.into_iter().collect()
+}
+
+/// Details of the command file at `path`, None when it can't be read
+pub fn read_command_details(path: &Path) -> Option<SlashCommandDetails> {
+    let content = read_capped(path).ok()?;
+    let doc = Frontmatter::parse(&content);
+    let frontmatter: CommandFrontmatter = doc.deserialize().unwrap_or_default();
+    let body = doc.body();
+    Some(SlashCommandDetails {
+        name: path.file_stem()?.to_string_lossy().to_string(),
+        description: frontmatter.description.clone().or_else(|| extract_description_from_content(&content)),
+        argument_hint: frontmatter.argument_hint.as_ref().and_then(argument_hint_text),
+        allowed_tools: frontmatter.allowed_tools.as_ref().map(ToolList::tools).unwrap_or_default(),
+        model: frontmatter.model,
+        disable_model_invocation: frontmatter.disable_model_invocation,
+        uses_arguments: body.contains("$ARGUMENTS"),
+        positional_arguments: positional_arguments(body),
+        file_path: path.to_string_lossy().to_string(),
+        content,
+    })
 }
 
 /// Scope of the slash command
@@ -32,47 +152,24 @@ impl CommandScope {
 }
 
 /// Get the user commands directory
-fn get_user_commands_dir() -> Option<PathBuf> {
+pub(crate) fn get_user_commands_dir() -> Option<PathBuf> {
     claude_config_dir().map(|d| d.join("commands"))
 }
 
 /// Get the project commands directory for a given project path
-fn get_project_commands_dir(project_path: &str) -> PathBuf {
+pub(crate) fn get_project_commands_dir(project_path: &str) -> PathBuf {
     PathBuf::from(project_path).join(".claude").join("commands")
 }
 
 /// Parse YAML frontmatter from markdown content
 fn parse_frontmatter(content: &str) -> Option<CommandFrontmatter> {
-    // Check if content starts with ---
-    let content = content.trim();
-    if !content.starts_with("---") {
-        return None;
-    }
-
-    // Find the closing ---
-    let after_first = &content[3..];
-    let end_pos = after_first.find("---")?;
-    let yaml_content = &after_first[..end_pos].trim();
-
-    // Parse the YAML
-    serde_yaml::from_str(yaml_content).ok()
+    Frontmatter::parse(content).deserialize()
 }
 
 /// Extract first non-empty, non-heading line as description
 fn extract_description_from_content(content: &str) -> Option<String> {
-    // Skip frontmatter if present
-    let body = if content.trim().starts_with("---") {
-        if let Some(pos) = content[3..].find("---") {
-            &content[pos + 6..]
-        } else {
-            content
-        }
-    } else {
-        content
-    };
-
     // Find first meaningful line
-    body.lines()
+    Frontmatter::parse(content).body().lines()
         .map(|line| line.trim())
         .find(|line| !line.is_empty() && !line.starts_with('#'))
         .map(|s| {
@@ -94,7 +191,7 @@ fn determine_command_rarity(frontmatter: &Option<CommandFrontmatter>, scope: Com
 
     // Commands with allowed-tools are more powerful
     if let Some(fm) = frontmatter {
-        if let Some(tools) = &fm.allowed_tools {
+        if let Some(tools) = fm.allowed_tools.as_ref().map(ToolList::tools) {
             if tools.len() > 3 {
                 return ItemRarity::Epic;
             }
@@ -143,19 +240,14 @@ fn format_command_name(filename: &str) -> String {
 }
 
 /// Scan a directory for slash command .md files
-fn scan_commands_dir(dir: &PathBuf, scope: CommandScope) -> Vec<InventoryItem> {
+fn scan_commands_dir(dir: &Path, scope: CommandScope) -> Vec<InventoryItem> {
     let mut commands = Vec::new();
 
     if !dir.exists() {
         return commands;
     }
 
-    let entries = match fs::read_dir(dir) {
-        Ok(e) => e,
-        Err(_) => return commands,
-    };
-
-    for entry in entries.filter_map(|e| e.ok()) {
+    for entry in capped_read_dir(dir) {
         let path = entry.path();
 
         // Only process .md files
@@ -169,7 +261,7 @@ fn scan_commands_dir(dir: &PathBuf, scope: CommandScope) -> Vec<InventoryItem> {
         };
 
         // Read file content
-        let content = match fs::read_to_string(&path) {
+        let content = match read_capped(&path) {
             Ok(c) => c,
             Err(_) => continue,
         };
@@ -210,6 +302,7 @@ fn scan_commands_dir(dir: &PathBuf, scope: CommandScope) -> Vec<InventoryItem> {
             version: None,
             author: None,
             status: None,
+            environment: None,
         });
     }
 
@@ -276,6 +369,26 @@ pub fn get_builtin_commands() -> Vec<InventoryItem> {
             version: None,
             author: Some("Anthropic".to_string()),
             status: None,
+            environment: None,
         })
         .collect()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_usage_and_overrides_from_frontmatter() {
+        let content = "---\nallowed-tools: Bash(git add:*, git commit:*), Read\nargument-hint: [message]\nmodel: claude-3-5-haiku-20241022\ndisable-model-invocation: true\n---\nCommit with $ARGUMENTS, then tag $2 and $1, or $2 again.\n";
+        let frontmatter = parse_frontmatter(content).unwrap();
+        assert_eq!(
+            frontmatter.allowed_tools.as_ref().map(ToolList::tools).unwrap(),
+            vec!["Bash(git add:*, git commit:*)", "Read"]
+        );
+        assert_eq!(frontmatter.argument_hint.as_ref().and_then(argument_hint_text).as_deref(), Some("[message]"));
+        assert_eq!(frontmatter.model.as_deref(), Some("claude-3-5-haiku-20241022"));
+        assert!(frontmatter.disable_model_invocation);
+        assert_eq!(positional_arguments(Frontmatter::parse(content).body()), vec![1, 2]);
+    }
+}
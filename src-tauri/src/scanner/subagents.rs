@@ -2,10 +2,12 @@
 //! Subagents are specialized AI assistants with isolated context windows.
 
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use serde::Deserialize;
 
 use crate::types::{InventoryItem, ItemType, ItemRarity, ItemSource};
+use super::frontmatter::Frontmatter;
+use super::limits::{capped_read_dir, read_capped};
 use super::plugin::claude_config_dir;
 
 /// Subagent metadata from YAML frontmatter
@@ -48,16 +50,7 @@ fn get_project_agents_dir(project_path: &str) -> PathBuf {
 
 /// Parse YAML frontmatter from markdown content
 fn parse_frontmatter(content: &str) -> Option<SubagentFrontmatter> {
-    let content = content.trim();
-    if !content.starts_with("---") {
-        return None;
-    }
-
-    let after_first = &content[3..];
-    let end_pos = after_first.find("---")?;
-    let yaml_content = &after_first[..end_pos].trim();
-
-    serde_yaml::from_str(yaml_content).ok()
+    Frontmatter::parse(content).deserialize()
 }
 
 /// Extract first non-empty, non-heading line as description
@@ -128,8 +121,8 @@ fn determine_subagent_rarity(frontmatter: &Option<SubagentFrontmatter>, scope: S
 fn estimate_subagent_weight(agent_file: &PathBuf) -> u32 {
     // Subagents don't consume main context!
     // But we show a small "management overhead" cost
-    if let Ok(content) = fs::read_to_string(agent_file) {
-        let chars = content.len() as u32;
+    if let Ok(metadata) = fs::metadata(agent_file) {
+        let chars = metadata.len().min(u32::MAX as u64) as u32;
         // Very small overhead - subagents are efficient
         (chars / 10).clamp(100, 500)
     } else {
@@ -154,19 +147,14 @@ fn format_agent_name(file_name: &str) -> String {
 }
 
 /// Scan a directory for subagent files
-fn scan_agents_dir(dir: &PathBuf, scope: SubagentScope) -> Vec<InventoryItem> {
+fn scan_agents_dir(dir: &Path, scope: SubagentScope) -> Vec<InventoryItem> {
     let mut agents = Vec::new();
 
     if !dir.exists() {
         return agents;
     }
 
-    let entries = match fs::read_dir(dir) {
-        Ok(e) => e,
-        Err(_) => return agents,
-    };
-
-    for entry in entries.filter_map(|e| e.ok()) {
+    for entry in capped_read_dir(dir) {
         let path = entry.path();
 
         // Only process .md files
@@ -186,7 +174,7 @@ fn scan_agents_dir(dir: &PathBuf, scope: SubagentScope) -> Vec<InventoryItem> {
 
         let agent_id = file_name.trim_end_matches(".md").to_string();
 
-        let content = fs::read_to_string(&path).unwrap_or_default();
+        let content = read_capped(&path).unwrap_or_default();
         let frontmatter = parse_frontmatter(&content);
 
         // Get name from frontmatter or file name
@@ -224,6 +212,7 @@ fn scan_agents_dir(dir: &PathBuf, scope: SubagentScope) -> Vec<InventoryItem> {
             version: None,
             author: None,
             status: None,
+            environment: None,
         });
     }
 
@@ -0,0 +1,82 @@
+//! Reader for the todo lists Claude Code keeps per session (~/.claude/todos/<session>-agent-<agent>.json)
+//! The main conversation's list is the file whose agent id equals the session id.
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::plugin::claude_config_dir;
+
+/// One item of Claude's task list
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TodoItem {
+    pub content: String,
+    /// pending, in_progress or completed
+    pub status: String,
+    /// Present-tense label shown while the item is in progress
+    #[serde(default, alias = "active_form")]
+    pub active_form: Option<String>,
+    #[serde(default)]
+    pub priority: Option<String>,
+}
+
+/// A todo list written by the main conversation or one of its subagents
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TodoList {
+    pub session_id: String,
+    pub agent_id: String,
+    /// Written by a subagent rather than the main conversation
+    pub is_subagent: bool,
+    pub path: String,
+    pub todos: Vec<TodoItem>,
+}
+
+/// Get the directory Claude Code stores todo lists in
+pub fn todos_dir() -> Option<PathBuf> {
+    claude_config_dir().map(|d| d.join("todos"))
+}
+
+/// Split a todo file name into session and agent ids
+fn parse_todo_file_name(path: &Path) -> Option<(String, String)> {
+    let stem = path.file_stem()?.to_str()?;
+    if path.extension().is_none_or(|e| e != "json") {
+        return None;
+    }
+    let (session, agent) = stem.split_once("-agent-")?;
+    Some((session.to_string(), agent.to_string()))
+}
+
+/// Read one todo file; an empty or unreadable file is an empty list
+pub fn read_todo_list(path: &Path) -> Option<TodoList> {
+    let (session_id, agent_id) = parse_todo_file_name(path)?;
+    let todos = fs::read_to_string(path)
+        .ok()
+        .and_then(|content| serde_json::from_str::<Vec<TodoItem>>(&content).ok())
+        .unwrap_or_default();
+    Some(TodoList {
+        is_subagent: agent_id != session_id,
+        session_id,
+        agent_id,
+        path: path.to_string_lossy().to_string(),
+        todos,
+    })
+}
+
+/// Todo lists of a session, main conversation first
+pub fn session_todo_lists(session_id: &str) -> Vec<TodoList> {
+    let Some(dir) = todos_dir() else {
+        return Vec::new();
+    };
+    let prefix = format!("{}-agent-", session_id);
+    let mut lists: Vec<TodoList> = fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_name().to_string_lossy().starts_with(&prefix))
+        .filter_map(|e| read_todo_list(&e.path()))
+        .collect();
+    lists.sort_by(|a, b| a.is_subagent.cmp(&b.is_subagent).then_with(|| a.agent_id.cmp(&b.agent_id)));
+    lists
+}
@@ -0,0 +1,340 @@
+//! Reader for Claude Code session transcripts (~/.claude/projects/<encoded-path>/*.jsonl)
+//! Transcript lines are loosely typed JSON, so everything here works on serde_json::Value.
+
+use std::collections::HashMap;
+use std::fs;
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::plugin::claude_config_dir;
+
+/// Get the directory Claude Code stores per-project transcripts in
+pub fn claude_projects_dir() -> Option<PathBuf> {
+    claude_config_dir().map(|d| d.join("projects"))
+}
+
+/// Encode a project path the way Claude Code names its transcript folders
+/// (every character that isn't alphanumeric becomes `-`)
+pub fn encode_project_path(project_path: &str) -> String {
+    project_path
+        .trim_end_matches(['/', '\\'])
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '-' { c } else { '-' })
+        .collect()
+}
+
+/// Get the transcript folder for a project
+pub fn project_transcripts_dir(project_path: &str) -> Option<PathBuf> {
+    claude_projects_dir().map(|d| d.join(encode_project_path(project_path)))
+}
+
+/// List transcript files for one project, or for every project when None
+pub fn list_transcript_files(project_path: Option<&str>) -> Vec<PathBuf> {
+    let dirs: Vec<PathBuf> = match project_path {
+        Some(path) => project_transcripts_dir(path).into_iter().collect(),
+        None => claude_projects_dir()
+            .and_then(|d| fs::read_dir(d).ok())
+            .map(|entries| {
+                entries.filter_map(|e| e.ok())
+                    .map(|e| e.path())
+                    .filter(|p| p.is_dir())
+                    .collect()
+            })
+            .unwrap_or_default(),
+    };
+
+    let mut files = Vec::new();
+    for dir in dirs {
+        if let Ok(entries) = fs::read_dir(&dir) {
+            for entry in entries.filter_map(|e| e.ok()) {
+                let path = entry.path();
+                if path.is_file() && path.extension().is_some_and(|e| e == "jsonl") {
+                    files.push(path);
+                }
+            }
+        }
+    }
+
+    files
+}
+
+/// Whether `session_id` is made only of the characters Claude Code uses in session ids, so it is
+/// safe to use as a file name
+pub fn is_valid_session_id(session_id: &str) -> bool {
+    !session_id.is_empty() && session_id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+/// Find a session's transcript in any project folder by its session id
+pub fn find_transcript(session_id: &str) -> Option<PathBuf> {
+    if !is_valid_session_id(session_id) {
+        return None;
+    }
+    list_transcript_files(None)
+        .into_iter()
+        .find(|path| path.file_stem().is_some_and(|stem| stem == session_id))
+}
+
+/// Last modification time of a file
+pub fn modified_time(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+/// Read all parseable lines of a transcript, skipping malformed ones
+pub fn read_transcript_entries(path: &Path) -> Vec<Value> {
+    let file = match fs::File::open(path) {
+        Ok(f) => f,
+        Err(_) => return Vec::new(),
+    };
+
+    BufReader::new(file)
+        .lines()
+        .map_while(Result::ok)
+        .filter(|line| !line.trim().is_empty())
+        .filter_map(|line| serde_json::from_str(&line).ok())
+        .collect()
+}
+
+/// Entry type ("user", "assistant", "summary", "system", ...)
+pub fn entry_type(entry: &Value) -> &str {
+    entry.get("type").and_then(|v| v.as_str()).unwrap_or_default()
+}
+
+/// Parse the ISO-8601 timestamp of an entry
+pub fn entry_timestamp(entry: &Value) -> Option<DateTime<Utc>> {
+    let raw = entry.get("timestamp")?.as_str()?;
+    DateTime::parse_from_rfc3339(raw).ok().map(|t| t.with_timezone(&Utc))
+}
+
+/// Working directory recorded on an entry
+pub fn entry_cwd(entry: &Value) -> Option<&str> {
+    entry.get("cwd").and_then(|v| v.as_str())
+}
+
+/// Content blocks of an entry's message (string content becomes a single text block)
+pub fn content_blocks(entry: &Value) -> Vec<Value> {
+    match entry.get("message").and_then(|m| m.get("content")) {
+        Some(Value::Array(blocks)) => blocks.clone(),
+        Some(Value::String(text)) => vec![serde_json::json!({ "type": "text", "text": text })],
+        _ => Vec::new(),
+    }
+}
+
+/// A tool invocation made by the assistant
+#[derive(Debug, Clone)]
+pub struct ToolUse {
+    pub id: String,
+    pub name: String,
+    pub input: Value,
+    pub timestamp: Option<DateTime<Utc>>,
+    pub cwd: Option<String>,
+}
+
+/// Outcome of a tool invocation as reported back in the next user turn
+#[derive(Debug, Clone)]
+pub struct ToolResult {
+    pub is_error: bool,
+    pub text: String,
+}
+
+impl ToolResult {
+    /// Whether the user rejected the tool call at the permission prompt
+    pub fn was_rejected(&self) -> bool {
+        self.is_error
+            && (self.text.contains("doesn't want to proceed")
+                || self.text.contains("tool use was rejected"))
+    }
+}
+
+/// Collect every tool_use block from assistant entries
+pub fn tool_uses(entries: &[Value]) -> Vec<ToolUse> {
+    let mut uses = Vec::new();
+
+    for entry in entries.iter().filter(|e| entry_type(e) == "assistant") {
+        for block in content_blocks(entry) {
+            if block.get("type").and_then(|v| v.as_str()) != Some("tool_use") {
+                continue;
+            }
+            let id = block.get("id").and_then(|v| v.as_str()).unwrap_or_default();
+            let name = block.get("name").and_then(|v| v.as_str()).unwrap_or_default();
+            if name.is_empty() {
+                continue;
+            }
+            uses.push(ToolUse {
+                id: id.to_string(),
+                name: name.to_string(),
+                input: block.get("input").cloned().unwrap_or(Value::Null),
+                timestamp: entry_timestamp(entry),
+                cwd: entry_cwd(entry).map(|s| s.to_string()),
+            });
+        }
+    }
+
+    uses
+}
+
+/// Map tool_use ids to their results
+pub fn tool_results(entries: &[Value]) -> HashMap<String, ToolResult> {
+    let mut results = HashMap::new();
+
+    for entry in entries.iter().filter(|e| entry_type(e) == "user") {
+        for block in content_blocks(entry) {
+            if block.get("type").and_then(|v| v.as_str()) != Some("tool_result") {
+                continue;
+            }
+            let id = match block.get("tool_use_id").and_then(|v| v.as_str()) {
+                Some(id) => id.to_string(),
+                None => continue,
+            };
+            let is_error = block.get("is_error").and_then(|v| v.as_bool()).unwrap_or(false);
+            results.insert(id, ToolResult {
+                is_error,
+                text: block_text(block.get("content")),
+            });
+        }
+    }
+
+    results
+}
+
+/// Flatten a content value (string or array of text blocks) into plain text
+pub fn block_text(content: Option<&Value>) -> String {
+    match content {
+        Some(Value::String(s)) => s.clone(),
+        Some(Value::Array(parts)) => parts
+            .iter()
+            .filter_map(|p| p.get("text").and_then(|t| t.as_str()))
+            .collect::<Vec<_>>()
+            .join("\n"),
+        _ => String::new(),
+    }
+}
+
+/// Token usage reported on assistant messages
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenUsage {
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub cache_creation_input_tokens: u64,
+    pub cache_read_input_tokens: u64,
+}
+
+impl TokenUsage {
+    pub fn add(&mut self, other: &TokenUsage) {
+        self.input_tokens += other.input_tokens;
+        self.output_tokens += other.output_tokens;
+        self.cache_creation_input_tokens += other.cache_creation_input_tokens;
+        self.cache_read_input_tokens += other.cache_read_input_tokens;
+    }
+
+    /// Tokens the model read for this request, i.e. the context window in use
+    pub fn context_tokens(&self) -> u64 {
+        self.input_tokens + self.cache_creation_input_tokens + self.cache_read_input_tokens
+    }
+}
+
+/// Usage block of an assistant entry
+pub fn entry_usage(entry: &Value) -> Option<TokenUsage> {
+    let usage = entry.get("message")?.get("usage")?;
+    let field = |key: &str| usage.get(key).and_then(|v| v.as_u64()).unwrap_or(0);
+    Some(TokenUsage {
+        input_tokens: field("input_tokens"),
+        output_tokens: field("output_tokens"),
+        cache_creation_input_tokens: field("cache_creation_input_tokens"),
+        cache_read_input_tokens: field("cache_read_input_tokens"),
+    })
+}
+
+/// API message id of an assistant entry. One response is written as several entries
+/// (one per content block) that repeat the same id and usage.
+pub fn entry_message_id(entry: &Value) -> Option<&str> {
+    entry.get("message")?.get("id")?.as_str()
+}
+
+/// Model that produced an assistant entry
+pub fn entry_model(entry: &Value) -> Option<&str> {
+    entry
+        .get("message")?
+        .get("model")?
+        .as_str()
+        .filter(|m| *m != "<synthetic>")
+}
+
+/// Text a user typed, or None for entries that only carry tool results
+pub fn prompt_text(entry: &Value) -> Option<String> {
+    if entry_type(entry) != "user" {
+        return None;
+    }
+    let text = content_blocks(entry)
+        .iter()
+        .filter(|b| b.get("type").and_then(|t| t.as_str()) == Some("text"))
+        .filter_map(|b| b.get("text").and_then(|t| t.as_str()))
+        .collect::<Vec<_>>()
+        .join("\n");
+    (!text.trim().is_empty()).then_some(text)
+}
+
+/// Map tool_use ids to the structured result Claude Code stores next to the tool_result
+/// (`toolUseResult`, e.g. a subagent's token count and duration)
+pub fn structured_tool_results(entries: &[Value]) -> HashMap<String, Value> {
+    let mut results = HashMap::new();
+
+    for entry in entries.iter().filter(|e| entry_type(e) == "user") {
+        let Some(structured) = entry.get("toolUseResult") else {
+            continue;
+        };
+        for block in content_blocks(entry) {
+            if let Some(id) = block.get("tool_use_id").and_then(|v| v.as_str()) {
+                results.insert(id.to_string(), structured.clone());
+            }
+        }
+    }
+
+    results
+}
+
+/// How a Task (subagent) call ended and what it cost in the subagent's own context
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskOutcome {
+    /// completed, error, rejected or pending (no result yet)
+    pub status: String,
+    pub total_tokens: u64,
+    pub duration_ms: Option<u64>,
+    pub tool_use_count: Option<u32>,
+}
+
+/// Outcome of a Task call from its tool_result and structured result
+pub fn task_outcome(result: Option<&ToolResult>, structured: Option<&Value>) -> TaskOutcome {
+    let number = |key: &str| structured.and_then(|s| s.get(key)).and_then(|v| v.as_u64());
+    let status = match result {
+        None => "pending",
+        Some(r) if r.was_rejected() => "rejected",
+        Some(r) if r.is_error => "error",
+        Some(_) => structured
+            .and_then(|s| s.get("status"))
+            .and_then(|s| s.as_str())
+            .unwrap_or("completed"),
+    };
+    // Older results only carry the subagent's last usage block
+    let total_tokens = number("totalTokens").unwrap_or_else(|| {
+        structured
+            .and_then(|s| s.get("usage"))
+            .map(|u| {
+                let field = |key: &str| u.get(key).and_then(|v| v.as_u64()).unwrap_or(0);
+                field("input_tokens") + field("cache_creation_input_tokens") + field("cache_read_input_tokens") + field("output_tokens")
+            })
+            .unwrap_or(0)
+    });
+
+    TaskOutcome {
+        status: status.to_string(),
+        total_tokens,
+        duration_ms: number("totalDurationMs"),
+        tool_use_count: number("totalToolUseCount").map(|n| n as u32),
+    }
+}
@@ -0,0 +1,280 @@
+//! How often inventory items are actually used, attributed from session transcripts
+//! Counts are kept per transcript in ~/.claude/arcade_usage.json so history survives Claude Code pruning old sessions.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use super::plugin::claude_config_dir;
+use super::transcripts::{
+    content_blocks, entry_timestamp, entry_type, list_transcript_files, modified_time, read_transcript_entries,
+    structured_tool_results, task_outcome, tool_results,
+};
+use crate::types::{ContextStats, InventoryItem, ItemSource, ItemStatus};
+
+/// Bump when attribution changes so every transcript is read again
+const STORE_VERSION: u32 = 2;
+
+/// Items used this recently count as active
+const ACTIVE_WINDOW_MS: u64 = 10 * 60 * 1000;
+
+/// Use of one item: a skill, command, subagent, MCP server or plugin
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ItemUsage {
+    pub run_count: u32,
+    /// Milliseconds since the Unix epoch
+    pub last_used: Option<u64>,
+    /// Subagent tasks that finished successfully
+    #[serde(default)]
+    pub tasks_completed: u32,
+    /// Tokens those tasks used in the subagent's isolated context
+    #[serde(default)]
+    pub isolated_tokens: u64,
+}
+
+impl ItemUsage {
+    fn record(&mut self, timestamp: Option<u64>) {
+        self.run_count += 1;
+        self.last_used = self.last_used.max(timestamp);
+    }
+
+    fn merge(&mut self, other: &ItemUsage) {
+        self.run_count += other.run_count;
+        self.last_used = self.last_used.max(other.last_used);
+        self.tasks_completed += other.tasks_completed;
+        self.isolated_tokens += other.isolated_tokens;
+    }
+}
+
+/// Usage found in one transcript, with the file state it was read at
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TranscriptUsage {
+    size_bytes: u64,
+    modified_ms: u64,
+    items: HashMap<String, ItemUsage>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct UsageStore {
+    /// Stores from an older version are re-ingested from scratch
+    #[serde(default)]
+    version: u32,
+    /// Keyed by transcript path
+    transcripts: HashMap<String, TranscriptUsage>,
+}
+
+fn usage_store_path() -> Option<PathBuf> {
+    claude_config_dir().map(|d| d.join("arcade_usage.json"))
+}
+
+fn load_store() -> UsageStore {
+    usage_store_path()
+        .and_then(|path| crate::store::USAGE.load::<UsageStore>(&path))
+        .filter(|store| store.version == STORE_VERSION)
+        .unwrap_or(UsageStore { version: STORE_VERSION, transcripts: HashMap::new() })
+}
+
+fn save_store(store: &UsageStore) -> Result<(), String> {
+    let path = usage_store_path().ok_or("Could not find home directory")?;
+    let content = crate::store::USAGE.to_json(store)?;
+    crate::paths::atomic_write(&path, content).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+fn to_millis(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH).map(|d| d.as_millis() as u64).unwrap_or(0)
+}
+
+/// Usage key for a name that may be namespaced by a plugin ("plugin:name")
+fn namespaced_key(kind: &str, name: &str) -> String {
+    let name = name.trim_start_matches('/');
+    match name.split_once(':') {
+        Some((plugin, _)) => format!("plugin:{}", plugin),
+        None => format!("{}:{}", kind, name),
+    }
+}
+
+/// Usage key for an MCP tool name (mcp__<server>__<tool>); plugin servers are mcp__plugin_<plugin>_<server>__<tool>
+fn mcp_key(tool_name: &str) -> Option<String> {
+    let server = tool_name.strip_prefix("mcp__")?.split("__").next()?;
+    match server.strip_prefix("plugin_") {
+        Some(rest) => Some(format!("plugin:{}", rest.split('_').next().unwrap_or(rest))),
+        None => Some(format!("mcp:{}", server)),
+    }
+}
+
+/// Slash command typed by the user, from the <command-name> tag Claude Code records
+fn slash_command(entry: &Value) -> Option<String> {
+    let text = match entry.get("message").and_then(|m| m.get("content")) {
+        Some(Value::String(text)) => text.clone(),
+        _ => content_blocks(entry)
+            .iter()
+            .filter_map(|b| b.get("text").and_then(|t| t.as_str()))
+            .collect::<Vec<_>>()
+            .join("\n"),
+    };
+    let start = text.find("<command-name>")? + "<command-name>".len();
+    let end = start + text[start..].find("</command-name>")?;
+    Some(text[start..end].trim().to_string())
+}
+
+/// Tools that hand work to a subagent
+pub fn is_task_tool(name: &str) -> bool {
+    name == "Task" || name == "Agent"
+}
+
+/// Usage keys for one tool call
+fn tool_keys(name: &str, input: &Value) -> Vec<String> {
+    let field = |key: &str| input.get(key).and_then(|v| v.as_str());
+    match name {
+        "Skill" => field("skill").or(field("command")).map(|s| namespaced_key("skill", s)).into_iter().collect(),
+        "Task" | "Agent" => field("subagent_type").map(|a| namespaced_key("subagent", a)).into_iter().collect(),
+        "SlashCommand" => field("command")
+            .and_then(|c| c.split_whitespace().next())
+            .map(|c| namespaced_key("command", c))
+            .into_iter()
+            .collect(),
+        _ => mcp_key(name).into_iter().collect(),
+    }
+}
+
+/// Attribute the skill, subagent, command and MCP uses in a transcript to usage keys
+fn transcript_usage(entries: &[Value]) -> HashMap<String, ItemUsage> {
+    let mut usage: HashMap<String, ItemUsage> = HashMap::new();
+    let results = tool_results(entries);
+    let structured = structured_tool_results(entries);
+
+    for entry in entries {
+        let timestamp = entry_timestamp(entry).map(|t| t.timestamp_millis().max(0) as u64);
+        match entry_type(entry) {
+            "user" => {
+                if let Some(command) = slash_command(entry) {
+                    usage.entry(namespaced_key("command", &command)).or_default().record(timestamp);
+                }
+            }
+            "assistant" => {
+                for block in content_blocks(entry) {
+                    if block.get("type").and_then(|t| t.as_str()) != Some("tool_use") {
+                        continue;
+                    }
+                    let name = block.get("name").and_then(|n| n.as_str()).unwrap_or_default();
+                    let keys = tool_keys(name, block.get("input").unwrap_or(&Value::Null));
+                    let outcome = is_task_tool(name).then(|| {
+                        let id = block.get("id").and_then(|v| v.as_str()).unwrap_or_default();
+                        task_outcome(results.get(id), structured.get(id))
+                    });
+                    for key in keys {
+                        let item = usage.entry(key).or_default();
+                        item.record(timestamp);
+                        if let Some(outcome) = outcome.as_ref().filter(|o| o.status == "completed") {
+                            item.tasks_completed += 1;
+                            item.isolated_tokens += outcome.total_tokens;
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    usage
+}
+
+/// Re-read transcripts that changed since the last ingestion and persist the result.
+/// Returns usage totals per key.
+pub fn ingest_usage() -> HashMap<String, ItemUsage> {
+    let mut store = load_store();
+    let mut changed = false;
+
+    for path in list_transcript_files(None) {
+        let key = path.to_string_lossy().to_string();
+        let size_bytes = fs::metadata(&path).map(|m| m.len()).unwrap_or(0);
+        let modified_ms = modified_time(&path).map(to_millis).unwrap_or(0);
+        if store.transcripts.get(&key).is_some_and(|t| t.size_bytes == size_bytes && t.modified_ms == modified_ms) {
+            continue;
+        }
+
+        let items = transcript_usage(&read_transcript_entries(&path));
+        store.transcripts.insert(key, TranscriptUsage { size_bytes, modified_ms, items });
+        changed = true;
+    }
+
+    if changed {
+        if let Err(e) = save_store(&store) {
+            tracing::error!("Failed to save usage stats: {}", e);
+        }
+    }
+
+    let mut totals: HashMap<String, ItemUsage> = HashMap::new();
+    for transcript in store.transcripts.values() {
+        for (key, usage) in &transcript.items {
+            totals.entry(key.clone()).or_default().merge(usage);
+        }
+    }
+    totals
+}
+
+fn file_stem(path: &str) -> String {
+    Path::new(path).file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default()
+}
+
+/// Usage key an inventory item is tracked under
+fn item_usage_key(item: &InventoryItem) -> Option<String> {
+    match item.source {
+        ItemSource::Skill => {
+            let dir = Path::new(&item.source_path).file_name()?.to_string_lossy().to_string();
+            Some(format!("skill:{}", dir))
+        }
+        ItemSource::Subagent => Some(format!("subagent:{}", file_stem(&item.source_path))),
+        ItemSource::Command => match item.id.strip_prefix("builtin_") {
+            Some(name) => Some(format!("command:{}", name)),
+            None => Some(format!("command:{}", file_stem(&item.source_path))),
+        },
+        ItemSource::Plugin => Some(format!("plugin:{}", item.id.split('@').next().unwrap_or(&item.id))),
+        ItemSource::Mcp => Some(format!("mcp:{}", item.name)),
+        _ => None,
+    }
+}
+
+/// Fill run count, last use and activity on items from transcript usage
+pub fn apply_item_usage(items: &mut [InventoryItem], usage: &HashMap<String, ItemUsage>) {
+    let now = to_millis(SystemTime::now());
+
+    for item in items.iter_mut() {
+        let Some(key) = item_usage_key(item) else {
+            continue;
+        };
+        let used = usage.get(&key).copied().unwrap_or_default();
+        let status = item.status.get_or_insert_with(ItemStatus::default);
+        status.run_count = Some(used.run_count);
+        status.last_used = used.last_used;
+        status.is_active = Some(used.last_used.is_some_and(|t| now.saturating_sub(t) < ACTIVE_WINDOW_MS));
+
+        if matches!(item.source, ItemSource::Subagent) {
+            // Average per task: how much of its own window a companion typically fills
+            status.tasks_completed = Some(used.tasks_completed);
+            status.isolated_context_usage = (used.tasks_completed > 0)
+                .then(|| (used.isolated_tokens / used.tasks_completed as u64).min(u32::MAX as u64) as u32);
+            status.isolated_context_budget = Some(ContextStats::default().total_budget);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn attributes_tool_calls_to_items() {
+        assert_eq!(tool_keys("Skill", &serde_json::json!({ "skill": "pdf" })), vec!["skill:pdf"]);
+        assert_eq!(tool_keys("Task", &serde_json::json!({ "subagent_type": "review:critic" })), vec!["plugin:review"]);
+        assert_eq!(tool_keys("mcp__github__create_issue", &Value::Null), vec!["mcp:github"]);
+        assert_eq!(tool_keys("mcp__plugin_linear_linear__list", &Value::Null), vec!["plugin:linear"]);
+        assert!(tool_keys("Read", &Value::Null).is_empty());
+    }
+}
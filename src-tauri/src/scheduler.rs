@@ -0,0 +1,277 @@
+//! Background jobs run on a schedule: update checks, marketplace refreshes, analytics compaction
+//! What each job does is defined here; arcade_jobs.json keeps whether it is on, how often it
+//! runs and how its last run went. A tick every minute runs the jobs that are due, one at a time,
+//! and each run is emitted as `job-finished` with the job's updated info.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter};
+
+use crate::blocking::run_blocking;
+use crate::commands::analytics::compact_analytics;
+use crate::commands::cli_version::refresh_claude_version;
+use crate::commands::plugins::refresh_marketplaces;
+use crate::error::ArcadeError;
+
+const TICK: Duration = Duration::from_secs(60);
+
+/// Give launch, the first scan and the watchers the machine before any job runs
+const STARTUP_DELAY: Duration = Duration::from_secs(2 * 60);
+
+/// Shortest interval a job can be set to
+pub const MIN_INTERVAL_MINUTES: u32 = 5;
+
+/// A job the scheduler knows how to run
+pub struct JobSpec {
+    pub id: &'static str,
+    pub name: &'static str,
+    pub description: &'static str,
+    pub default_interval_minutes: u32,
+}
+
+pub const JOBS: [JobSpec; 3] = [
+    JobSpec {
+        id: "claude-update-check",
+        name: "Claude Code update check",
+        description: "Looks up the latest Claude Code release and tells you when yours is behind",
+        default_interval_minutes: 24 * 60,
+    },
+    JobSpec {
+        id: "marketplace-refresh",
+        name: "Marketplace refresh",
+        description: "Pulls git-backed plugin marketplaces so their catalogs stay current",
+        default_interval_minutes: 24 * 60,
+    },
+    JobSpec {
+        id: "analytics-compaction",
+        name: "Analytics compaction",
+        description: "Closes sessions gone quiet and drops usage days older than the retention window",
+        default_interval_minutes: 24 * 60,
+    },
+];
+
+/// A job's schedule and last run, as saved
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JobRecord {
+    pub id: String,
+    pub enabled: bool,
+    pub interval_minutes: u32,
+    /// RFC3339
+    pub last_run: Option<String>,
+    pub last_success: Option<bool>,
+    /// What the last run did, or why it failed
+    pub last_message: Option<String>,
+    pub last_duration_ms: Option<u64>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+struct JobsFile {
+    jobs: Vec<JobRecord>,
+}
+
+/// A job as the frontend shows it
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JobInfo {
+    #[serde(flatten)]
+    pub record: JobRecord,
+    pub name: String,
+    pub description: String,
+    /// RFC3339; None while the job is off
+    pub next_run: Option<String>,
+    pub running: bool,
+}
+
+/// Jobs running right now, so a manual run can't overlap a scheduled one
+static RUNNING: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+fn jobs_path() -> Option<PathBuf> {
+    crate::paths::claude_dir().map(|d| d.join("arcade_jobs.json"))
+}
+
+fn default_record(spec: &JobSpec) -> JobRecord {
+    JobRecord {
+        id: spec.id.to_string(),
+        enabled: true,
+        interval_minutes: spec.default_interval_minutes,
+        last_run: None,
+        last_success: None,
+        last_message: None,
+        last_duration_ms: None,
+    }
+}
+
+/// One record per known job, in `JOBS` order: saved ones where there are, defaults otherwise.
+/// Records of jobs that no longer exist are dropped.
+fn with_defaults(file: JobsFile) -> Vec<JobRecord> {
+    JOBS.iter()
+        .map(|spec| file.jobs.iter().find(|r| r.id == spec.id).cloned().unwrap_or_else(|| default_record(spec)))
+        .collect()
+}
+
+pub fn load_jobs() -> Vec<JobRecord> {
+    let file = jobs_path()
+        .and_then(|path| crate::store::JOBS.load(&path))
+        .unwrap_or_default();
+    with_defaults(file)
+}
+
+fn save_jobs(jobs: Vec<JobRecord>) -> Result<(), ArcadeError> {
+    let path = jobs_path().ok_or("Could not find home directory")?;
+    let content = crate::store::JOBS.to_json(&JobsFile { jobs })?;
+    crate::paths::atomic_write(&path, content).map_err(|e| ArcadeError::io(&path, e))
+}
+
+/// Change one job's saved record
+pub fn update_record(id: &str, change: impl FnOnce(&mut JobRecord)) -> Result<JobRecord, ArcadeError> {
+    let mut jobs = load_jobs();
+    let record = jobs.iter_mut().find(|r| r.id == id).ok_or_else(|| ArcadeError::not_found("Job", id))?;
+    change(record);
+    let updated = record.clone();
+    save_jobs(jobs)?;
+    Ok(updated)
+}
+
+fn next_run(record: &JobRecord) -> Option<DateTime<Utc>> {
+    if !record.enabled {
+        return None;
+    }
+    let last = record.last_run.as_deref().and_then(|t| DateTime::parse_from_rfc3339(t).ok());
+    Some(match last {
+        Some(last) => last.with_timezone(&Utc) + chrono::Duration::minutes(record.interval_minutes.into()),
+        // Never run: due now
+        None => Utc::now(),
+    })
+}
+
+fn is_due(record: &JobRecord, now: DateTime<Utc>) -> bool {
+    record.enabled && (record.last_run.is_none() || next_run(record).is_some_and(|at| at <= now))
+}
+
+fn is_running(id: &str) -> bool {
+    RUNNING.lock().is_ok_and(|running| running.iter().any(|r| r == id))
+}
+
+pub fn job_info(record: JobRecord) -> JobInfo {
+    let spec = JOBS.iter().find(|s| s.id == record.id);
+    JobInfo {
+        name: spec.map_or(record.id.clone(), |s| s.name.to_string()),
+        description: spec.map(|s| s.description.to_string()).unwrap_or_default(),
+        next_run: next_run(&record).map(|t| t.to_rfc3339()),
+        running: is_running(&record.id),
+        record,
+    }
+}
+
+/// What the job does; Ok carries a summary for its record
+async fn execute(app: &AppHandle, id: &str) -> Result<String, String> {
+    match id {
+        "claude-update-check" => {
+            let info = refresh_claude_version(app).await.map_err(|e| e.to_string())?;
+            let installed = info.installed.unwrap_or_else(|| "not installed".to_string());
+            match (info.latest, info.error) {
+                (Some(latest), _) => Ok(format!("Installed {}, latest {}", installed, latest)),
+                (None, error) => Err(error.unwrap_or_else(|| "The latest release is unknown".to_string())),
+            }
+        }
+        "marketplace-refresh" => {
+            let warnings = run_blocking(|| {
+                let mut warnings = Vec::new();
+                refresh_marketplaces(&mut warnings);
+                warnings
+            })
+            .await?;
+            if warnings.is_empty() {
+                Ok("Marketplaces are up to date".to_string())
+            } else {
+                Err(warnings.join("; "))
+            }
+        }
+        "analytics-compaction" => run_blocking(compact_analytics).await?,
+        _ => Err(format!("Unknown job: {}", id)),
+    }
+}
+
+/// Run a job now and record how it went. Fails without running when it is already running.
+pub async fn run_job(app: &AppHandle, id: &str) -> Result<JobInfo, ArcadeError> {
+    if !JOBS.iter().any(|s| s.id == id) {
+        return Err(ArcadeError::not_found("Job", id));
+    }
+    {
+        let mut running = RUNNING.lock().map_err(|e| format!("Lock error: {}", e))?;
+        if running.iter().any(|r| r == id) {
+            return Err(ArcadeError::Conflict { message: format!("{} is already running", id) });
+        }
+        running.push(id.to_string());
+    }
+
+    let started_at = Utc::now();
+    let started = Instant::now();
+    let outcome = execute(app, id).await;
+    if let Ok(mut running) = RUNNING.lock() {
+        running.retain(|r| r != id);
+    }
+    match &outcome {
+        Ok(message) => tracing::info!("Job {} finished: {}", id, message),
+        Err(e) => tracing::warn!("Job {} failed: {}", id, e),
+    }
+
+    let id_owned = id.to_string();
+    let record = run_blocking(move || {
+        update_record(&id_owned, |record| {
+            record.last_run = Some(started_at.to_rfc3339());
+            record.last_success = Some(outcome.is_ok());
+            record.last_message = Some(outcome.unwrap_or_else(|e| e));
+            record.last_duration_ms = Some(started.elapsed().as_millis() as u64);
+        })
+    })
+    .await??;
+    let info = job_info(record);
+    let _ = app.emit("job-finished", &info);
+    Ok(info)
+}
+
+/// Start the scheduler; it runs for the life of the app
+pub fn init(app: &AppHandle) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(STARTUP_DELAY).await;
+        loop {
+            let jobs = run_blocking(load_jobs).await.unwrap_or_default();
+            let now = Utc::now();
+            for record in jobs.iter().filter(|r| is_due(r, now) && !is_running(&r.id)) {
+                if let Err(e) = run_job(&app, &record.id).await {
+                    tracing::warn!("Scheduled job {} could not run: {}", record.id, e);
+                }
+            }
+            tokio::time::sleep(TICK).await;
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn schedules_from_the_last_run() {
+        let mut saved = default_record(&JOBS[1]);
+        saved.interval_minutes = 60;
+        saved.last_run = Some("2026-01-01T10:00:00Z".to_string());
+        let stale = JobRecord { id: "gone".to_string(), ..saved.clone() };
+        let jobs = with_defaults(JobsFile { jobs: vec![stale, saved.clone()] });
+        assert_eq!(jobs.iter().map(|r| r.id.as_str()).collect::<Vec<_>>(), JOBS.map(|s| s.id).to_vec());
+        assert_eq!(jobs[1], saved);
+
+        let at = |t: &str| DateTime::parse_from_rfc3339(t).unwrap().with_timezone(&Utc);
+        assert!(!is_due(&saved, at("2026-01-01T10:59:00Z")));
+        assert!(is_due(&saved, at("2026-01-01T11:00:00Z")));
+        assert!(is_due(&jobs[0], Utc::now()));
+        assert!(!is_due(&JobRecord { enabled: false, ..saved }, at("2026-01-02T00:00:00Z")));
+    }
+}
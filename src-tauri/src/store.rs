@@ -0,0 +1,161 @@
+//! Schema versions for the arcade's own data files
+//! Each file is written as `{"schemaVersion": N, "data": ...}`. On load, the file's data is
+//! brought up to the current version one migration at a time, so a format change only needs a
+//! migration added here. Files from before the envelope are read as version 1.
+//! A file that can't be read as this version's data, such as one written by a newer app, is
+//! copied to `<name>.bak` before anything can overwrite it; caches are just rebuilt.
+//! arcade_endpoint.json isn't listed: scripts outside the app read it as it is.
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fs;
+use std::path::Path;
+
+/// Turns one schema version's data into the next one's
+pub type Migration = fn(Value) -> Result<Value, String>;
+
+/// An arcade-owned data file
+pub struct DataFile {
+    pub name: &'static str,
+    /// `migrations[i]` turns version `i + 1` into `i + 2`
+    pub migrations: &'static [Migration],
+    /// Caches are written compact, and rebuilt rather than backed up when unreadable
+    pub cache: bool,
+}
+
+pub const ANALYTICS: DataFile = DataFile::new("arcade_analytics.json");
+pub const USAGE: DataFile = DataFile::cache("arcade_usage.json");
+pub const INDEX: DataFile = DataFile::cache("arcade_index.json");
+pub const INTEGRITY: DataFile = DataFile::new("arcade_integrity.json");
+pub const PROJECTS: DataFile = DataFile::new("arcade_projects.json");
+pub const SETTINGS: DataFile = DataFile::new("arcade_settings.json");
+pub const MCP_GROUPS: DataFile = DataFile::new("arcade_mcp_groups.json");
+pub const MCP_PARKED: DataFile = DataFile::new("arcade_mcp_parked.json");
+pub const SPELLBOOKS: DataFile = DataFile::new("arcade_spellbooks.json");
+pub const STATUSLINE: DataFile = DataFile::new("arcade_statusline.json");
+pub const AGENTS_MD: DataFile = DataFile::new("arcade_agents_md.json");
+pub const JOBS: DataFile = DataFile::new("arcade_jobs.json");
+pub const ONBOARDING: DataFile = DataFile::new("arcade_onboarding.json");
+pub const PROFILES: DataFile = DataFile::new("arcade_profiles.json");
+pub const SYNC: DataFile = DataFile::new("arcade_sync.json");
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Envelope<T> {
+    schema_version: u32,
+    data: T,
+}
+
+/// The file's schema version and data; anything that isn't an envelope is version 1
+fn open_envelope(value: Value) -> (u32, Value) {
+    match value {
+        Value::Object(mut map) if map.len() == 2 && map.contains_key("data") => {
+            match map.get("schemaVersion").and_then(Value::as_u64).and_then(|v| u32::try_from(v).ok()) {
+                Some(version) => (version, map.remove("data").unwrap_or_default()),
+                None => (1, Value::Object(map)),
+            }
+        }
+        value => (1, value),
+    }
+}
+
+impl DataFile {
+    const fn new(name: &'static str) -> Self {
+        DataFile { name, migrations: &[], cache: false }
+    }
+
+    const fn cache(name: &'static str) -> Self {
+        DataFile { name, migrations: &[], cache: true }
+    }
+
+    /// The version this app writes
+    pub const fn version(&self) -> u32 {
+        self.migrations.len() as u32 + 1
+    }
+
+    /// Bring `data` from `version` up to the current version. Data from a newer version is
+    /// returned as it is, for a best-effort read.
+    pub fn migrate(&self, version: u32, mut data: Value) -> Result<Value, String> {
+        if version == 0 {
+            return Err(format!("{} has no schema version 0", self.name));
+        }
+        for (from, migration) in self.migrations.iter().enumerate().skip(version as usize - 1) {
+            data = migration(data).map_err(|e| format!("Migrating {} from version {}: {}", self.name, from + 1, e))?;
+        }
+        Ok(data)
+    }
+
+    /// The file's data at the current version, and the version it was written as
+    pub fn parse<T: DeserializeOwned>(&self, content: &str) -> Result<(T, u32), String> {
+        let value: Value = serde_json::from_str(content).map_err(|e| e.to_string())?;
+        let (version, data) = open_envelope(value);
+        let data = self.migrate(version, data)?;
+        let data = serde_json::from_value(data).map_err(|e| format!("{} (schema version {})", e, version))?;
+        Ok((data, version))
+    }
+
+    /// Read the file; None when it is missing or can't be read
+    pub fn load<T: DeserializeOwned>(&self, path: &Path) -> Option<T> {
+        let content = fs::read_to_string(path).ok()?;
+        let parsed = self.parse(&content);
+        let newer = parsed.as_ref().is_ok_and(|(_, version)| *version > self.version());
+        if let Err(e) = &parsed {
+            tracing::warn!("Could not read {}: {}", path.display(), e);
+        } else if newer {
+            tracing::warn!("{} is from a newer version of ClaudeArcade; reading what this version understands", path.display());
+        }
+        if (parsed.is_err() || newer) && !self.cache {
+            let backup = path.with_file_name(format!("{}.bak", self.name));
+            if let Err(e) = fs::copy(path, &backup) {
+                tracing::warn!("Failed to back up {}: {}", path.display(), e);
+            }
+        }
+        parsed.ok().map(|(data, _)| data)
+    }
+
+    /// `data` in its envelope at the current version, ready to write
+    pub fn to_json<T: Serialize>(&self, data: &T) -> Result<String, String> {
+        let envelope = Envelope { schema_version: self.version(), data };
+        if self.cache {
+            serde_json::to_string(&envelope)
+        } else {
+            serde_json::to_string_pretty(&envelope)
+        }
+        .map_err(|e| e.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn rename_days(mut data: Value) -> Result<Value, String> {
+        let days = data.as_object_mut().and_then(|d| d.remove("days")).ok_or("no days")?;
+        data["dailyUsage"] = days;
+        Ok(data)
+    }
+
+    fn count_days(mut data: Value) -> Result<Value, String> {
+        data["dayCount"] = json!(data["dailyUsage"].as_array().map_or(0, Vec::len));
+        Ok(data)
+    }
+
+    #[test]
+    fn migrates_old_files_to_the_current_version() {
+        let file = DataFile { name: "test.json", migrations: &[rename_days, count_days], cache: false };
+        assert_eq!(file.version(), 3);
+
+        let legacy: (Value, u32) = file.parse(r#"{"days":[1,2]}"#).unwrap();
+        assert_eq!(legacy, (json!({ "dailyUsage": [1, 2], "dayCount": 2 }), 1));
+        let v2: (Value, u32) = file.parse(r#"{"schemaVersion":2,"data":{"dailyUsage":[1]}}"#).unwrap();
+        assert_eq!(v2, (json!({ "dailyUsage": [1], "dayCount": 1 }), 2));
+        let saved = file.to_json(&v2.0).unwrap();
+        assert_eq!(file.parse::<Value>(&saved).unwrap(), (v2.0, 3));
+
+        assert!(file.parse::<Value>(r#"{"schemaVersion":1,"data":{}}"#).is_err());
+        let newer: (Value, u32) = file.parse(r#"{"schemaVersion":9,"data":{"x":1}}"#).unwrap();
+        assert_eq!(newer.1, 9);
+    }
+}
@@ -0,0 +1,389 @@
+//! Live tail of the active project's newest session transcript
+//! New transcript lines become `session-event` messages, so the UI never has to poll.
+
+use notify::{Config, RecommendedWatcher, RecursiveMode, Watcher};
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::HashSet;
+use std::fs;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::channel;
+use std::sync::Arc;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+
+use crate::commands::context_guard::{clear_context_use, record_context_use};
+use crate::commands::context_stats_for_tokens;
+use crate::scanner::transcripts::{
+    block_text, content_blocks, entry_message_id, entry_model, entry_timestamp, entry_type, entry_usage,
+    modified_time, project_transcripts_dir, prompt_text, TokenUsage,
+};
+
+/// Hook events whose failures Claude Code logs as `<Event>:<matcher> ... failed`
+const HOOK_EVENTS: [&str; 8] = [
+    "PreToolUse", "PostToolUse", "SessionStart", "SessionEnd", "Stop", "SubagentStop", "UserPromptSubmit", "Notification",
+];
+
+/// How often the transcript is checked when no file events arrive
+const POLL_INTERVAL: Duration = Duration::from_millis(750);
+
+/// Payload of the `session-event` event
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionEvent {
+    pub project_path: String,
+    pub session_id: String,
+    /// sessionStarted, userPrompt, assistantTurn (first part of a response, with usage),
+    /// assistantText (later text of the same response), toolCall, toolResult, compaction
+    /// or hookError (with the hook, e.g. "PostToolUse:Edit", as `tool_name`)
+    pub kind: String,
+    pub timestamp: Option<String>,
+    pub text: Option<String>,
+    pub tool_name: Option<String>,
+    pub tool_use_id: Option<String>,
+    pub tool_input: Option<Value>,
+    pub is_error: bool,
+    pub usage: Option<TokenUsage>,
+    pub model: Option<String>,
+    pub is_sidechain: bool,
+}
+
+impl SessionEvent {
+    fn new(project_path: &str, session_id: &str, kind: &str, entry: Option<&Value>) -> Self {
+        SessionEvent {
+            project_path: project_path.to_string(),
+            session_id: session_id.to_string(),
+            kind: kind.to_string(),
+            timestamp: entry.and_then(entry_timestamp).map(|t| t.to_rfc3339()),
+            text: None,
+            tool_name: None,
+            tool_use_id: None,
+            tool_input: None,
+            is_error: false,
+            usage: None,
+            model: None,
+            is_sidechain: entry
+                .and_then(|e| e.get("isSidechain"))
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false),
+        }
+    }
+}
+
+/// Handle to the running tail thread
+struct ActiveTail {
+    project_path: String,
+    stop: Arc<AtomicBool>,
+}
+
+/// Tails the newest transcript of one project at a time
+pub struct SessionTailer {
+    active: Option<ActiveTail>,
+}
+
+impl SessionTailer {
+    pub fn new() -> Self {
+        Self { active: None }
+    }
+
+    /// Start tailing a project, replacing any previous tail
+    pub fn start(&mut self, app_handle: AppHandle, project_path: &str) -> Result<(), String> {
+        if self.active.as_ref().is_some_and(|a| a.project_path == project_path) {
+            return Ok(());
+        }
+        self.stop();
+
+        let dir = project_transcripts_dir(project_path).ok_or("Could not find home directory")?;
+        let stop = Arc::new(AtomicBool::new(false));
+        let stop_flag = stop.clone();
+        let project = project_path.to_string();
+
+        std::thread::spawn(move || {
+            if let Err(e) = run_tailer(app_handle, project, dir, stop_flag) {
+                tracing::error!("Session tailer error: {}", e);
+            }
+        });
+
+        self.active = Some(ActiveTail { project_path: project_path.to_string(), stop });
+        Ok(())
+    }
+
+    /// Stop tailing
+    pub fn stop(&mut self) {
+        if let Some(active) = self.active.take() {
+            active.stop.store(true, Ordering::Relaxed);
+        }
+    }
+
+    /// Project currently being tailed
+    pub fn active_project(&self) -> Option<String> {
+        self.active.as_ref().map(|a| a.project_path.clone())
+    }
+}
+
+impl Default for SessionTailer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for SessionTailer {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+/// Read position in the transcript being followed
+struct TailPosition {
+    path: PathBuf,
+    session_id: String,
+    offset: u64,
+    /// Bytes after the last newline, completed by the next read. Kept undecoded, as a read can
+    /// end inside a multi-byte character.
+    partial: Vec<u8>,
+    /// Assistant message ids already reported (responses span several lines)
+    seen_messages: HashSet<String>,
+}
+
+impl TailPosition {
+    fn new(path: PathBuf, offset: u64) -> Self {
+        TailPosition {
+            session_id: path.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default(),
+            path,
+            offset,
+            partial: Vec::new(),
+            seen_messages: HashSet::new(),
+        }
+    }
+}
+
+fn newest_transcript(dir: &Path) -> Option<PathBuf> {
+    fs::read_dir(dir)
+        .ok()?
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| p.is_file() && p.extension().is_some_and(|e| e == "jsonl"))
+        .max_by_key(|p| modified_time(p))
+}
+
+fn run_tailer(app_handle: AppHandle, project_path: String, dir: PathBuf, stop: Arc<AtomicBool>) -> Result<(), String> {
+    let (tx, rx) = channel();
+    let mut watcher: RecommendedWatcher = Watcher::new(tx, Config::default())
+        .map_err(|e| e.to_string())?;
+    let mut dir_watched = dir.is_dir() && watcher.watch(&dir, RecursiveMode::NonRecursive).is_ok();
+
+    // Start at the end of the current session; only new activity is reported
+    let mut position = newest_transcript(&dir).map(|path| {
+        let length = fs::metadata(&path).map(|m| m.len()).unwrap_or(0);
+        TailPosition::new(path, length)
+    });
+
+    tracing::info!("Tailing sessions for {}", project_path);
+
+    while !stop.load(Ordering::Relaxed) {
+        // Events only wake the loop early; the transcript is re-checked either way
+        let _ = rx.recv_timeout(POLL_INTERVAL);
+        while rx.try_recv().is_ok() {}
+
+        if !dir_watched && dir.is_dir() {
+            dir_watched = watcher.watch(&dir, RecursiveMode::NonRecursive).is_ok();
+        }
+
+        // A newer transcript means a new (or resumed) session; follow it from the start
+        if let Some(newest) = newest_transcript(&dir) {
+            if position.as_ref().is_none_or(|p| p.path != newest) {
+                let next = TailPosition::new(newest, 0);
+                crate::notifications::session_started();
+                let _ = app_handle.emit("session-event", SessionEvent::new(&project_path, &next.session_id, "sessionStarted", None));
+                position = Some(next);
+            }
+        }
+
+        if let Some(position) = position.as_mut() {
+            for event in read_new_events(&project_path, position) {
+                notify_for(&app_handle, &event);
+                let _ = app_handle.emit("session-event", event);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Pass context load and hook failures on to the notifier, and context load to the context guard
+fn notify_for(app_handle: &AppHandle, event: &SessionEvent) {
+    match (event.kind.as_str(), &event.usage, &event.tool_name) {
+        ("assistantTurn", Some(usage), _) if !event.is_sidechain => {
+            let stats = context_stats_for_tokens(usage.context_tokens().min(u32::MAX as u64) as u32);
+            crate::notifications::context_changed(app_handle, &stats.status, stats.load_percentage);
+            record_context_use(&event.session_id, stats.load_percentage);
+        }
+        ("compaction", _, _) => clear_context_use(&event.session_id),
+        ("hookError", _, Some(hook)) => crate::notifications::hook_failed(app_handle, hook),
+        _ => {}
+    }
+}
+
+/// The hook named by a logged hook failure, either a `hook_*error` attachment or a warning like
+/// "PostToolUse:Edit [prettier --write .] failed with non-blocking status code 1"
+fn failed_hook(entry: &Value) -> Option<String> {
+    if let Some(attachment) = entry.get("attachment") {
+        let kind = attachment.get("type").and_then(|t| t.as_str())?;
+        if kind.starts_with("hook_") && kind.contains("error") {
+            return attachment.get("hookName").and_then(|h| h.as_str()).map(String::from);
+        }
+        return None;
+    }
+    if entry_type(entry) != "system" {
+        return None;
+    }
+    let content = entry.get("content").and_then(|c| c.as_str())?;
+    let hook = content.split_whitespace().next()?;
+    let event = hook.split(':').next()?;
+    let lower = content.to_lowercase();
+    (HOOK_EVENTS.contains(&event) && (lower.contains("failed") || lower.contains("hook error"))).then(|| hook.to_string())
+}
+
+/// Read lines appended since the last call and turn them into events
+fn read_new_events(project_path: &str, position: &mut TailPosition) -> Vec<SessionEvent> {
+    let Ok(mut file) = fs::File::open(&position.path) else {
+        return Vec::new();
+    };
+    let length = file.metadata().map(|m| m.len()).unwrap_or(0);
+    if length < position.offset {
+        // Truncated or rewritten; start over
+        position.offset = 0;
+        position.partial.clear();
+    }
+    if length == position.offset || file.seek(SeekFrom::Start(position.offset)).is_err() {
+        return Vec::new();
+    }
+
+    let mut bytes = Vec::new();
+    if file.read_to_end(&mut bytes).is_err() {
+        return Vec::new();
+    }
+    position.offset += bytes.len() as u64;
+    position.partial.extend_from_slice(&bytes);
+
+    let mut events = Vec::new();
+    while let Some(newline) = position.partial.iter().position(|&b| b == b'\n') {
+        let line: Vec<u8> = position.partial.drain(..=newline).collect();
+        if let Ok(entry) = serde_json::from_str::<Value>(String::from_utf8_lossy(&line).trim()) {
+            events.extend(entry_events(project_path, position, &entry));
+        }
+    }
+    events
+}
+
+/// Events for one transcript entry
+fn entry_events(project_path: &str, position: &mut TailPosition, entry: &Value) -> Vec<SessionEvent> {
+    let session_id = position.session_id.clone();
+    let event = |kind: &str| SessionEvent::new(project_path, &session_id, kind, Some(entry));
+    let mut events = Vec::new();
+
+    let is_compaction = entry.get("subtype").and_then(|s| s.as_str()) == Some("compact_boundary")
+        || entry.get("isCompactSummary").and_then(|v| v.as_bool()).unwrap_or(false);
+    if is_compaction {
+        // The boundary and the summary that follows are one compaction
+        if entry_type(entry) == "system" {
+            let mut compaction = event("compaction");
+            compaction.text = entry.get("content").and_then(|c| c.as_str()).map(String::from);
+            events.push(compaction);
+        }
+        return events;
+    }
+
+    if let Some(hook) = failed_hook(entry) {
+        let mut failure = event("hookError");
+        failure.tool_name = Some(hook);
+        failure.text = entry.get("content").and_then(|c| c.as_str()).map(String::from);
+        failure.is_error = true;
+        events.push(failure);
+        return events;
+    }
+
+    match entry_type(entry) {
+        "user" => {
+            for block in content_blocks(entry) {
+                if block.get("type").and_then(|t| t.as_str()) != Some("tool_result") {
+                    continue;
+                }
+                let mut result = event("toolResult");
+                result.tool_use_id = block.get("tool_use_id").and_then(|v| v.as_str()).map(String::from);
+                result.is_error = block.get("is_error").and_then(|v| v.as_bool()).unwrap_or(false);
+                result.text = Some(block_text(block.get("content")));
+                events.push(result);
+            }
+            if let Some(text) = prompt_text(entry) {
+                let mut prompt = event("userPrompt");
+                prompt.text = Some(text);
+                events.push(prompt);
+            }
+        }
+        "assistant" => {
+            // Usage is repeated on every line of a response; report it once
+            let is_new = entry_message_id(entry).is_none_or(|id| position.seen_messages.insert(id.to_string()));
+            if is_new {
+                let mut turn = event("assistantTurn");
+                turn.usage = entry_usage(entry);
+                turn.model = entry_model(entry).map(String::from);
+                events.push(turn);
+            }
+            for block in content_blocks(entry) {
+                match block.get("type").and_then(|t| t.as_str()) {
+                    Some("tool_use") => {
+                        let mut call = event("toolCall");
+                        call.tool_name = block.get("name").and_then(|v| v.as_str()).map(String::from);
+                        call.tool_use_id = block.get("id").and_then(|v| v.as_str()).map(String::from);
+                        call.tool_input = block.get("input").cloned();
+                        events.push(call);
+                    }
+                    Some("text") => {
+                        let text = block.get("text").and_then(|t| t.as_str()).map(String::from);
+                        match events.iter_mut().find(|e| e.kind == "assistantTurn" && e.text.is_none()) {
+                            Some(turn) => turn.text = text,
+                            None => {
+                                let mut more = event("assistantText");
+                                more.text = text;
+                                events.push(more);
+                            }
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+        _ => {}
+    }
+
+    events
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TempDir;
+    use std::io::Write;
+
+    #[test]
+    fn keeps_characters_split_across_reads() {
+        let dir = TempDir::new("tailer");
+        let path = dir.join("tailer.jsonl");
+        let line = "{\"type\":\"user\",\"message\":{\"role\":\"user\",\"content\":\"caf\u{e9} \u{2713}\"}}\n";
+        // Inside the three bytes of the check mark
+        let split = line.find('\u{2713}').unwrap() + 1;
+        fs::write(&path, &line.as_bytes()[..split]).unwrap();
+
+        let mut position = TailPosition::new(path.clone(), 0);
+        let first = read_new_events("/project", &mut position);
+        fs::OpenOptions::new().append(true).open(&path).unwrap().write_all(&line.as_bytes()[split..]).unwrap();
+        let second = read_new_events("/project", &mut position);
+
+        assert!(first.is_empty());
+        assert_eq!(second.len(), 1);
+        assert_eq!(second[0].text.as_deref(), Some("caf\u{e9} \u{2713}"));
+    }
+}
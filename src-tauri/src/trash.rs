@@ -0,0 +1,166 @@
+//! Deleted items go to ~/.claude/arcade_trash instead of disappearing
+//! Each entry is a folder holding the removed file or folder and a meta.json saying where it came from.
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+use crate::error::ArcadeError;
+
+const META_FILE: &str = "meta.json";
+
+/// A removed file or folder
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrashEntry {
+    pub id: String,
+    /// agent, skill, command, outputStyle or template
+    pub kind: String,
+    pub name: String,
+    pub original_path: String,
+    /// RFC3339
+    pub deleted_at: String,
+    pub is_dir: bool,
+}
+
+pub fn trash_dir() -> Option<PathBuf> {
+    crate::paths::claude_dir().map(|d| d.join("arcade_trash"))
+}
+
+/// Rename, or copy and delete when `from` and `to` are on different volumes (a project on another disk)
+fn move_path(from: &Path, to: &Path) -> std::io::Result<()> {
+    if fs::rename(from, to).is_ok() {
+        return Ok(());
+    }
+    if from.is_dir() {
+        for entry in WalkDir::new(from).into_iter().filter_map(|e| e.ok()) {
+            let target = to.join(entry.path().strip_prefix(from).unwrap_or(entry.path()));
+            if entry.file_type().is_dir() {
+                fs::create_dir_all(&target)?;
+            } else {
+                fs::copy(entry.path(), &target)?;
+            }
+        }
+        fs::remove_dir_all(from)
+    } else {
+        fs::copy(from, to)?;
+        fs::remove_file(from)
+    }
+}
+
+/// Move `path` into the trash, recording the removal in the audit log
+pub fn move_to_trash(path: &Path, kind: &str) -> Result<TrashEntry, ArcadeError> {
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .ok_or_else(|| ArcadeError::invalid_input(format!("Nothing to delete at {}", path.display())))?;
+    let now = chrono::Utc::now();
+    let id = format!("{}-{}", now.format("%Y%m%d%H%M%S"), &uuid::Uuid::new_v4().simple().to_string()[..8]);
+    let entry_dir = trash_dir().ok_or("Could not find home directory")?.join(&id);
+    fs::create_dir_all(&entry_dir).map_err(|e| ArcadeError::io(&entry_dir, e))?;
+
+    let entry = TrashEntry {
+        id,
+        kind: kind.to_string(),
+        original_path: path.to_string_lossy().to_string(),
+        deleted_at: now.to_rfc3339(),
+        is_dir: path.is_dir(),
+        name: name.clone(),
+    };
+    let meta = serde_json::to_string_pretty(&entry).map_err(|e| e.to_string())?;
+    crate::paths::atomic_write(entry_dir.join(META_FILE), meta).map_err(|e| ArcadeError::io(&entry_dir, e))?;
+
+    crate::audit::record_removal(path);
+    move_path(path, &entry_dir.join(&name)).map_err(|e| ArcadeError::io(path, e))?;
+    Ok(entry)
+}
+
+/// Everything in the trash, most recently deleted first
+pub fn list_trash_entries() -> Vec<TrashEntry> {
+    let Some(dir) = trash_dir() else {
+        return Vec::new();
+    };
+    let mut entries: Vec<TrashEntry> = fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter_map(|e| fs::read_to_string(e.path().join(META_FILE)).ok())
+        .filter_map(|meta| serde_json::from_str(&meta).ok())
+        .collect();
+    entries.sort_by(|a, b| b.deleted_at.cmp(&a.deleted_at));
+    entries
+}
+
+fn entry_dir(id: &str) -> Result<PathBuf, ArcadeError> {
+    crate::paths::check_name(id)?;
+    let dir = trash_dir().ok_or("Could not find home directory")?.join(id);
+    if dir.join(META_FILE).is_file() {
+        Ok(dir)
+    } else {
+        Err(ArcadeError::not_found("Trash entry", id))
+    }
+}
+
+/// Put an entry back where it was deleted from; refuses to overwrite something created there since
+pub fn restore_entry(id: &str) -> Result<TrashEntry, ArcadeError> {
+    let dir = entry_dir(id)?;
+    let meta = fs::read_to_string(dir.join(META_FILE)).map_err(|e| ArcadeError::io(&dir, e))?;
+    let entry: TrashEntry = serde_json::from_str(&meta).map_err(|e| e.to_string())?;
+    let original = PathBuf::from(&entry.original_path);
+    if original.exists() {
+        return Err(ArcadeError::Conflict { message: format!("{} exists again; rename or remove it first", original.display()) });
+    }
+    if let Some(parent) = original.parent() {
+        fs::create_dir_all(parent).map_err(|e| ArcadeError::io(parent, e))?;
+    }
+    move_path(&dir.join(&entry.name), &original).map_err(|e| ArcadeError::io(&original, e))?;
+    fs::remove_dir_all(&dir).map_err(|e| ArcadeError::io(&dir, e))?;
+    Ok(entry)
+}
+
+/// Delete entries for good: all of them, or those deleted more than `older_than_days` ago
+pub fn empty_trash_entries(older_than_days: Option<u32>) -> Result<usize, ArcadeError> {
+    let cutoff = older_than_days.map(|days| chrono::Utc::now() - chrono::Duration::days(i64::from(days)));
+    let mut removed = 0;
+    for entry in list_trash_entries() {
+        let expired = cutoff.is_none_or(|cutoff| {
+            chrono::DateTime::parse_from_rfc3339(&entry.deleted_at).is_ok_and(|at| at < cutoff)
+        });
+        if expired {
+            let dir = entry_dir(&entry.id)?;
+            fs::remove_dir_all(&dir).map_err(|e| ArcadeError::io(&dir, e))?;
+            removed += 1;
+        }
+    }
+    Ok(removed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TempDir;
+    use crate::paths::ConfigPaths;
+
+    #[test]
+    fn trashes_and_restores_an_item() {
+        let root = TempDir::new("trash");
+        let agent = root.join(".claude/agents/reviewer.md");
+        fs::create_dir_all(agent.parent().unwrap()).unwrap();
+        fs::write(&agent, "---\nname: reviewer\n---\n").unwrap();
+
+        let (listed, restored) = ConfigPaths::under(&root).scoped(|| {
+            let entry = move_to_trash(&agent, "agent").unwrap();
+            assert!(!agent.exists());
+            let listed = list_trash_entries();
+            restore_entry(&entry.id).unwrap();
+            (listed, list_trash_entries())
+        });
+        let content = fs::read_to_string(&agent).unwrap();
+
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].name, "reviewer.md");
+        assert!(restored.is_empty());
+        assert_eq!(content, "---\nname: reviewer\n---\n");
+    }
+}
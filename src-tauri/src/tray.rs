@@ -0,0 +1,92 @@
+//! Tray icon with the current context load and session status, for while the window is minimized
+//! Its menu opens the window, asks the frontend to apply a loadout, or pauses the config watchers.
+
+use std::time::Duration;
+use tauri::menu::{CheckMenuItem, Menu, MenuEvent, MenuItem, PredefinedMenuItem};
+use tauri::tray::{MouseButton, MouseButtonState, TrayIcon, TrayIconBuilder, TrayIconEvent};
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::commands::{context_stats_for_tokens, calculate_context_stats, load_analytics, InventoryCacheState};
+
+const TRAY_ID: &str = "arcade";
+
+/// How often the stats line and tooltip are refreshed
+const REFRESH_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Context load from the last scan (or a plugin scan before the first one) and whether a session is running
+fn quick_stats(app: &AppHandle) -> String {
+    let cached_tokens = app.try_state::<InventoryCacheState>().and_then(|state| {
+        let cache = state.0.lock().ok()?;
+        let result = cache.result.as_ref()?;
+        Some(result.items.iter().filter(|i| i.enabled).map(|i| i.token_weight).sum::<u32>())
+    });
+    let stats = cached_tokens.map(context_stats_for_tokens).unwrap_or_else(calculate_context_stats);
+
+    let session = match load_analytics().current_session {
+        Some(session) => format!("session: {} messages", session.messages),
+        None => "no session".to_string(),
+    };
+    format!("Context {:.0}% ({}) · {}", stats.load_percentage * 100.0, stats.status, session)
+}
+
+pub(crate) fn show_main_window(app: &AppHandle) {
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.unminimize();
+        let _ = window.show();
+        let _ = window.set_focus();
+    }
+}
+
+fn on_menu_event(app: &AppHandle, event: MenuEvent, pause: &CheckMenuItem) {
+    match event.id().as_ref() {
+        "open" => show_main_window(app),
+        "apply-loadout" => {
+            // Loadouts live in the frontend's store; it shows its picker on this event
+            show_main_window(app);
+            let _ = app.emit("tray-apply-loadout", ());
+        }
+        "pause-watchers" => {
+            let paused = pause.is_checked().unwrap_or(!crate::watcher::is_paused());
+            crate::watcher::set_paused(paused);
+            let _ = app.emit("watchers-paused", paused);
+        }
+        "quit" => app.exit(0),
+        _ => {}
+    }
+}
+
+/// Add the tray icon and keep its stats current
+pub fn init(app: &AppHandle) -> tauri::Result<()> {
+    let stats = MenuItem::with_id(app, "stats", "Context: checking…", false, None::<&str>)?;
+    let open = MenuItem::with_id(app, "open", "Open Arcade", true, None::<&str>)?;
+    let loadout = MenuItem::with_id(app, "apply-loadout", "Apply Loadout…", true, None::<&str>)?;
+    let pause = CheckMenuItem::with_id(app, "pause-watchers", "Pause Watchers", true, false, None::<&str>)?;
+    let quit = MenuItem::with_id(app, "quit", "Quit", true, None::<&str>)?;
+    let separator = PredefinedMenuItem::separator(app)?;
+    let menu = Menu::with_items(app, &[&stats, &separator, &open, &loadout, &pause, &quit])?;
+
+    let mut builder = TrayIconBuilder::with_id(TRAY_ID)
+        .tooltip("ClaudeArcade")
+        .menu(&menu)
+        .show_menu_on_left_click(false)
+        .on_menu_event(move |app, event| on_menu_event(app, event, &pause))
+        .on_tray_icon_event(|tray: &TrayIcon, event| {
+            if let TrayIconEvent::Click { button: MouseButton::Left, button_state: MouseButtonState::Up, .. } = event {
+                show_main_window(tray.app_handle());
+            }
+        });
+    if let Some(icon) = app.default_window_icon() {
+        builder = builder.icon(icon.clone());
+    }
+    let tray = builder.build(app)?;
+
+    let app = app.clone();
+    // Stats can take a plugin scan, so they're filled in off the setup thread
+    std::thread::spawn(move || loop {
+        let line = quick_stats(&app);
+        let _ = stats.set_text(&line);
+        let _ = tray.set_tooltip(Some(format!("ClaudeArcade — {}", line)));
+        std::thread::sleep(REFRESH_INTERVAL);
+    });
+    Ok(())
+}
@@ -85,6 +85,29 @@ pub struct InventoryItem {
     pub author: Option<String>,
     // Live status tracking
     pub status: Option<ItemStatus>,
+    // Where the item lives when it isn't this machine's own config, e.g. "wsl:Ubuntu"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub environment: Option<String>,
+}
+
+/// An enabled, weightless user command named after its id, for tests to adjust with `..test_item(id)`
+#[cfg(test)]
+pub(crate) fn test_item(id: &str) -> InventoryItem {
+    InventoryItem {
+        id: id.to_string(),
+        name: id.to_string(),
+        description: String::new(),
+        item_type: ItemType::Ring,
+        rarity: ItemRarity::Common,
+        source: ItemSource::Command,
+        source_path: String::new(),
+        token_weight: 0,
+        enabled: true,
+        version: None,
+        author: None,
+        status: None,
+        environment: None,
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -93,6 +116,36 @@ pub struct ScanResult {
     pub items: Vec<InventoryItem>,
     pub errors: Vec<String>,
     pub scan_duration_ms: u64,
+    // Where the time went, for full inventory scans
+    #[serde(default)]
+    pub metrics: Option<ScanMetrics>,
+}
+
+// Timing of one scanner within a scan
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SectionMetrics {
+    pub section: String,
+    pub duration_ms: u64,
+    pub item_count: usize,
+    // Files and folders checked for changes
+    pub file_count: usize,
+    // Reused from the on-disk index because nothing changed
+    pub cache_hit: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScanMetrics {
+    // Milliseconds since the epoch
+    pub scanned_at: u64,
+    pub total_ms: u64,
+    // Reading transcripts for run counts
+    pub usage_ms: u64,
+    pub sections: Vec<SectionMetrics>,
+    pub cache_hit_rate: f32,
+    // Sections that took longer than their budget
+    pub over_budget: Vec<String>,
 }
 
 // Simplified equipment slot types
@@ -177,6 +230,12 @@ pub struct EquipResult {
     pub success: bool,
     pub new_context_stats: ContextStats,
     pub warnings: Vec<String>,
+    // Commands, agents, skills or MCP servers another enabled plugin or the user's config already has
+    #[serde(default)]
+    pub conflicts: Vec<String>,
+    // Nothing was written; the stats and warnings are what equipping would lead to
+    #[serde(default)]
+    pub dry_run: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -1,12 +1,35 @@
-use notify::{Config, Event, RecommendedWatcher, RecursiveMode, Watcher};
-use std::path::PathBuf;
-use std::sync::mpsc::channel;
-use std::time::Duration;
+use notify::{Config, Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::mpsc::{channel, RecvTimeoutError};
+use std::sync::{Arc, LazyLock, Mutex};
+use std::time::{Duration, Instant};
 use tauri::{AppHandle, Emitter};
 
 use crate::scanner::plugin::claude_config_dir;
+use crate::scanner::settings::{load_settings_file, read_settings_at};
 
-/// Start watching Claude config directory for changes
+/// Set while watching is paused from the tray; changes collected meanwhile are reported on resume
+static PAUSED: AtomicBool = AtomicBool::new(false);
+
+/// Hold back change events (for the user and project watchers) until unpaused
+pub fn set_paused(paused: bool) {
+    PAUSED.store(paused, Ordering::Relaxed);
+}
+
+pub fn is_paused() -> bool {
+    PAUSED.load(Ordering::Relaxed)
+}
+
+/// Bumped on every `start_watcher`, so a watcher left on another profile's folder stops
+static GENERATION: AtomicU64 = AtomicU64::new(0);
+
+/// Start watching Claude config directory for changes, replacing the watcher started before
 pub fn start_watcher(app_handle: AppHandle) -> Result<(), String> {
     let settings_path = claude_config_dir()
         .map(|d| d.join("settings.json"))
@@ -16,16 +39,68 @@ pub fn start_watcher(app_handle: AppHandle) -> Result<(), String> {
         .ok_or("Could not get settings directory")?
         .to_path_buf();
 
+    let generation = GENERATION.fetch_add(1, Ordering::Relaxed) + 1;
     std::thread::spawn(move || {
-        if let Err(e) = run_watcher(app_handle, watch_dir, settings_path) {
-            eprintln!("File watcher error: {}", e);
+        if let Err(e) = run_watcher(app_handle, watch_dir, settings_path, generation) {
+            tracing::error!("File watcher error: {}", e);
         }
     });
 
     Ok(())
 }
 
-fn run_watcher(app_handle: AppHandle, watch_dir: PathBuf, settings_path: PathBuf) -> Result<(), String> {
+/// What kind of config a change touched; each maps to its own event
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ChangeSource {
+    Plugins,
+    Hooks,
+    Permissions,
+    Mcp,
+    Commands,
+    Skills,
+    Agents,
+    Marketplaces,
+}
+
+impl ChangeSource {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ChangeSource::Plugins => "plugins",
+            ChangeSource::Hooks => "hooks",
+            ChangeSource::Permissions => "permissions",
+            ChangeSource::Mcp => "mcp",
+            ChangeSource::Commands => "commands",
+            ChangeSource::Skills => "skills",
+            ChangeSource::Agents => "agents",
+            ChangeSource::Marketplaces => "marketplaces",
+        }
+    }
+
+    /// Name of the event emitted for this source
+    pub fn event_name(&self) -> &'static str {
+        match self {
+            ChangeSource::Plugins => "plugins-changed",
+            ChangeSource::Hooks => "hooks-changed",
+            ChangeSource::Permissions => "permissions-changed",
+            ChangeSource::Mcp => "mcp-changed",
+            ChangeSource::Commands => "commands-changed",
+            ChangeSource::Skills => "skills-changed",
+            ChangeSource::Agents => "agents-changed",
+            ChangeSource::Marketplaces => "marketplaces-changed",
+        }
+    }
+}
+
+/// Payload of the typed `*-changed` events
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigChange {
+    pub source: String,
+    /// Inventory item ids (or plugin/server ids, permission rules) that changed
+    pub changed_ids: Vec<String>,
+}
+
+fn run_watcher(app_handle: AppHandle, watch_dir: PathBuf, settings_path: PathBuf, generation: u64) -> Result<(), String> {
     let (tx, rx) = channel();
 
     let config = Config::default()
@@ -37,37 +112,626 @@ fn run_watcher(app_handle: AppHandle, watch_dir: PathBuf, settings_path: PathBuf
     watcher.watch(&watch_dir, RecursiveMode::NonRecursive)
         .map_err(|e| e.to_string())?;
 
-    println!("Watching for changes: {:?}", watch_dir);
+    // Item directories are watched recursively; ones that don't exist yet are retried on later events
+    let mut unwatched: Vec<PathBuf> = USER_SOURCE_DIRS.iter().map(|d| watch_dir.join(d)).collect();
+    unwatched.retain(|dir| !(dir.is_dir() && watcher.watch(dir, RecursiveMode::Recursive).is_ok()));
+
+    tracing::info!("Watching for changes: {:?}", watch_dir);
+
+    // Last known settings and installed plugins, diffed against on every change
+    let mut cache = WatchCache {
+        settings: read_settings_at(&settings_path).unwrap_or(Value::Null),
+        installed_plugins: read_settings_at(&watch_dir.join(INSTALLED_PLUGINS)).unwrap_or(Value::Null),
+    };
+
+    // Locally linked plugins live outside ~/.claude and get their own watches
+    let mut local_plugins: HashMap<PathBuf, String> = HashMap::new();
+    sync_local_plugin_watches(&mut watcher, &cache.installed_plugins, &mut local_plugins);
+
+    let mut pending = PendingChanges::new();
 
     loop {
-        match rx.recv() {
+        match rx.recv_timeout(pending.wait()) {
             Ok(Ok(event)) => {
-                handle_event(&app_handle, &event, &settings_path);
+                if !unwatched.is_empty() {
+                    unwatched.retain(|dir| !(dir.is_dir() && watcher.watch(dir, RecursiveMode::Recursive).is_ok()));
+                }
+                pending.add(&event);
             }
             Ok(Err(e)) => {
-                eprintln!("Watch error: {:?}", e);
+                tracing::error!("Watch error: {:?}", e);
             }
-            Err(e) => {
-                eprintln!("Channel error: {:?}", e);
+            Err(RecvTimeoutError::Timeout) => {
+                if GENERATION.load(Ordering::Relaxed) != generation {
+                    tracing::info!("Stopped watching {:?}", watch_dir);
+                    break;
+                }
+            }
+            Err(RecvTimeoutError::Disconnected) => {
+                tracing::error!("Watch channel closed");
                 break;
             }
         }
+        if pending.is_due() && !is_paused() {
+            handle_changes(&app_handle, pending.take(), &watch_dir, &settings_path, &mut cache, &local_plugins);
+            sync_local_plugin_watches(&mut watcher, &cache.installed_plugins, &mut local_plugins);
+        }
     }
 
     Ok(())
 }
 
-fn handle_event(app_handle: &AppHandle, event: &Event, settings_path: &PathBuf) {
-    // Check if the event affects settings.json
-    let affects_settings = event.paths.iter().any(|p| p == settings_path);
+/// Subdirectories of ~/.claude watched recursively
+const USER_SOURCE_DIRS: [&str; 4] = ["commands", "skills", "agents", "plugins"];
+
+/// Plugin install registry, relative to ~/.claude
+const INSTALLED_PLUGINS: &str = "plugins/installed_plugins.json";
+
+/// Install paths of locally linked plugins (isLocal entries), mapped to their plugin ids
+fn local_plugin_paths(installed_plugins: &Value) -> HashMap<PathBuf, String> {
+    let mut paths = HashMap::new();
+    let Some(plugins) = installed_plugins.get("plugins").and_then(|p| p.as_object()) else {
+        return paths;
+    };
+
+    for (plugin_id, entries) in plugins {
+        for entry in entries.as_array().into_iter().flatten() {
+            let is_local = entry.get("isLocal").and_then(|v| v.as_bool()).unwrap_or(false);
+            if let Some(path) = entry.get("installPath").and_then(|p| p.as_str()).filter(|_| is_local) {
+                paths.insert(PathBuf::from(path), plugin_id.clone());
+            }
+        }
+    }
+    paths
+}
+
+/// Watch newly linked local plugin folders and drop watches for unlinked ones
+fn sync_local_plugin_watches(
+    watcher: &mut RecommendedWatcher,
+    installed_plugins: &Value,
+    watched: &mut HashMap<PathBuf, String>,
+) {
+    let wanted = local_plugin_paths(installed_plugins);
+
+    watched.retain(|path, _| {
+        let keep = wanted.contains_key(path);
+        if !keep {
+            let _ = watcher.unwatch(path);
+        }
+        keep
+    });
+
+    for (path, plugin_id) in wanted {
+        if !watched.contains_key(&path) && path.is_dir() && watcher.watch(&path, RecursiveMode::Recursive).is_ok() {
+            watched.insert(path, plugin_id);
+        }
+    }
+}
+
+/// Last seen contents of the files the watcher diffs
+struct WatchCache {
+    settings: Value,
+    installed_plugins: Value,
+}
+
+fn handle_changes(
+    app_handle: &AppHandle,
+    changed: HashMap<PathBuf, &'static str>,
+    watch_dir: &Path,
+    settings_path: &Path,
+    cache: &mut WatchCache,
+    local_plugins: &HashMap<PathBuf, String>,
+) {
+    let mut changes: HashMap<ChangeSource, Vec<String>> = HashMap::new();
+    let installed_path = watch_dir.join(INSTALLED_PLUGINS);
+
+    if changed.contains_key(settings_path) {
+        let loaded = match load_settings_file(settings_path) {
+            Ok(loaded) => loaded,
+            Err(e) => {
+                // Surface the position instead of letting the UI read it as empty settings
+                let _ = app_handle.emit("settings-error", e);
+                None
+            }
+        };
+
+        if let Some(current) = loaded {
+            let diff = diff_settings(&cache.settings, &current);
+            let modified = current != cache.settings;
+            cache.settings = current;
 
-    if affects_settings {
-        match event.kind {
-            notify::EventKind::Modify(_) | notify::EventKind::Create(_) => {
-                println!("Settings changed externally, emitting refresh event");
+            // Our own writes only refresh the cache
+            if modified && !is_expected_write(settings_path) {
+                // Untyped keys (model, env, ...) only get the generic event
+                tracing::info!("Settings changed externally, emitting refresh event");
                 let _ = app_handle.emit("settings-changed", ());
+
+                for (source, ids) in diff {
+                    changes.entry(source).or_default().extend(ids);
+                }
+            }
+        }
+    }
+
+    if changed.contains_key(&installed_path) {
+        if let Some(current) = read_settings_at(&installed_path) {
+            let ids = changed_keys(cache.installed_plugins.get("plugins"), current.get("plugins"));
+            cache.installed_plugins = current;
+            if !ids.is_empty() && !is_expected_write(&installed_path) {
+                changes.entry(ChangeSource::Plugins).or_default().extend(ids);
             }
-            _ => {}
         }
     }
+
+    for path in changed.keys() {
+        if path == settings_path || path == &installed_path || is_expected_write(path) {
+            continue;
+        }
+        if let Some((source, id)) = classify_user_item_path(watch_dir, path) {
+            changes.entry(source).or_default().push(id);
+        } else if let Some(plugin_id) = local_plugin_for_path(local_plugins, path) {
+            changes.entry(ChangeSource::Plugins).or_default().push(plugin_id);
+        }
+    }
+
+    for (source, mut ids) in changes {
+        ids.sort();
+        ids.dedup();
+        let _ = app_handle.emit(source.event_name(), ConfigChange {
+            source: source.as_str().to_string(),
+            changed_ids: ids,
+        });
+    }
+}
+
+/// Keys of a JSON object whose values differ between two versions (including added/removed keys)
+fn changed_keys(old: Option<&Value>, new: Option<&Value>) -> Vec<String> {
+    let empty = serde_json::Map::new();
+    let old = old.and_then(|v| v.as_object()).unwrap_or(&empty);
+    let new = new.and_then(|v| v.as_object()).unwrap_or(&empty);
+
+    let mut keys: Vec<String> = old.keys()
+        .chain(new.keys())
+        .filter(|k| old.get(*k) != new.get(*k))
+        .cloned()
+        .collect();
+    keys.sort();
+    keys.dedup();
+    keys
+}
+
+/// Hook item ids (matching the hooks scanner) whose config changed
+fn changed_hook_ids(old: Option<&Value>, new: Option<&Value>) -> Vec<String> {
+    let mut ids = Vec::new();
+
+    for event_name in changed_keys(old, new) {
+        let entries = |v: Option<&Value>| {
+            v.and_then(|h| h.get(&event_name))
+                .and_then(|e| e.as_array())
+                .cloned()
+                .unwrap_or_default()
+        };
+        let old_entries = entries(old);
+        let new_entries = entries(new);
+
+        for index in 0..old_entries.len().max(new_entries.len()) {
+            if old_entries.get(index) != new_entries.get(index) {
+                ids.push(format!("hook_user_{}_{}", event_name.to_lowercase(), index));
+            }
+        }
+    }
+
+    ids
+}
+
+/// Permission rules added or removed, plus any other permission keys that changed
+fn changed_permission_ids(old: Option<&Value>, new: Option<&Value>) -> Vec<String> {
+    let mut ids = Vec::new();
+
+    for key in changed_keys(old, new) {
+        let rules = |v: Option<&Value>| -> Vec<String> {
+            v.and_then(|p| p.get(&key))
+                .and_then(|r| r.as_array())
+                .map(|r| r.iter().filter_map(|s| s.as_str().map(String::from)).collect())
+                .unwrap_or_default()
+        };
+
+        if matches!(key.as_str(), "allow" | "ask" | "deny") {
+            let old_rules = rules(old);
+            let new_rules = rules(new);
+            ids.extend(old_rules.iter().filter(|r| !new_rules.contains(r)).cloned());
+            ids.extend(new_rules.iter().filter(|r| !old_rules.contains(r)).cloned());
+        } else {
+            ids.push(key);
+        }
+    }
+
+    ids
+}
+
+/// Classify the differences between two versions of settings.json by source
+pub fn diff_settings(old: &Value, new: &Value) -> Vec<(ChangeSource, Vec<String>)> {
+    let sections = [
+        (ChangeSource::Plugins, "enabledPlugins"),
+        (ChangeSource::Hooks, "hooks"),
+        (ChangeSource::Permissions, "permissions"),
+        (ChangeSource::Mcp, "mcpServers"),
+    ];
+
+    let mut diff = Vec::new();
+    for (source, key) in sections {
+        let (old_section, new_section) = (old.get(key), new.get(key));
+        if old_section == new_section {
+            continue;
+        }
+
+        let ids = match source {
+            ChangeSource::Hooks => changed_hook_ids(old_section, new_section),
+            ChangeSource::Permissions => changed_permission_ids(old_section, new_section),
+            _ => changed_keys(old_section, new_section),
+        };
+        diff.push((source, ids));
+    }
+
+    diff
+}
+
+/// Map a path under ~/.claude to the user-scope inventory item it belongs to
+fn classify_user_item_path(claude_dir: &Path, path: &Path) -> Option<(ChangeSource, String)> {
+    let relative = path.strip_prefix(claude_dir).ok()?;
+    let parts: Vec<String> = relative.components()
+        .map(|c| c.as_os_str().to_string_lossy().to_string())
+        .collect();
+
+    // Marketplace refreshes are git pulls; their object churn isn't a config change
+    if parts.iter().any(|p| p == ".git") || path.extension().is_some_and(|e| e == "tmp") {
+        return None;
+    }
+
+    match parts.as_slice() {
+        [dir, file, ..] if dir == "commands" => {
+            let name = file.strip_suffix(".md")?;
+            Some((ChangeSource::Commands, format!("cmd_user_{}", name)))
+        }
+        [dir, skill, ..] if dir == "skills" => {
+            Some((ChangeSource::Skills, format!("skill_user_{}", skill)))
+        }
+        [dir, file, ..] if dir == "agents" => {
+            let name = file.strip_suffix(".md")?;
+            Some((ChangeSource::Agents, format!("subagent_user_{}", name)))
+        }
+        // plugins/cache/<marketplace>/<plugin>/... holds installed plugin files
+        [dir, cache, marketplace, plugin, ..] if dir == "plugins" && cache == "cache" => {
+            Some((ChangeSource::Plugins, format!("{}@{}", plugin, marketplace)))
+        }
+        [dir, marketplaces, marketplace, ..] if dir == "plugins" && marketplaces == "marketplaces" => {
+            Some((ChangeSource::Marketplaces, marketplace.clone()))
+        }
+        _ => None,
+    }
+}
+
+/// Plugin id of the locally linked plugin a path belongs to
+fn local_plugin_for_path(local_plugins: &HashMap<PathBuf, String>, path: &Path) -> Option<String> {
+    let (root, plugin_id) = local_plugins.iter().find(|(root, _)| path.starts_with(root))?;
+    let relative = path.strip_prefix(root).ok()?;
+    if relative.components().any(|c| c.as_os_str() == ".git") || path.extension().is_some_and(|e| e == "tmp") {
+        return None;
+    }
+    Some(plugin_id.clone())
+}
+
+/// Payload of the `project-config-changed` event
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectConfigChange {
+    pub project_path: String,
+    /// Which part of the project config changed (settings, commands, skills, agents, hooks, claudeMd, mcp, claudeDir)
+    pub area: String,
+    /// created, modified or removed
+    pub kind: String,
+    pub paths: Vec<String>,
+}
+
+/// Handle to a running per-project watcher thread
+struct ProjectWatch {
+    stop: Arc<AtomicBool>,
+}
+
+/// Watches the `.claude/` folder, CLAUDE.md files and `.mcp.json` of active projects
+pub struct ProjectWatchers {
+    watches: HashMap<String, ProjectWatch>,
+}
+
+impl ProjectWatchers {
+    pub fn new() -> Self {
+        Self {
+            watches: HashMap::new(),
+        }
+    }
+
+    /// Start watching a project (no-op if it is already watched)
+    pub fn watch(&mut self, app_handle: AppHandle, project_path: &str) -> Result<(), String> {
+        if self.watches.contains_key(project_path) {
+            return Ok(());
+        }
+
+        let root = PathBuf::from(project_path);
+        if !root.is_dir() {
+            return Err(format!("Project path does not exist: {}", project_path));
+        }
+
+        let stop = Arc::new(AtomicBool::new(false));
+        let stop_flag = stop.clone();
+        let project = project_path.to_string();
+
+        std::thread::spawn(move || {
+            if let Err(e) = run_project_watcher(app_handle, project, root, stop_flag) {
+                tracing::error!("Project watcher error: {}", e);
+            }
+        });
+
+        self.watches.insert(project_path.to_string(), ProjectWatch { stop });
+        Ok(())
+    }
+
+    /// Stop watching a project
+    pub fn unwatch(&mut self, project_path: &str) {
+        if let Some(watch) = self.watches.remove(project_path) {
+            watch.stop.store(true, Ordering::Relaxed);
+        }
+    }
+
+    /// Projects currently being watched
+    pub fn watched(&self) -> Vec<String> {
+        self.watches.keys().cloned().collect()
+    }
+}
+
+impl Default for ProjectWatchers {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for ProjectWatchers {
+    fn drop(&mut self) {
+        for watch in self.watches.values() {
+            watch.stop.store(true, Ordering::Relaxed);
+        }
+    }
+}
+
+fn run_project_watcher(
+    app_handle: AppHandle,
+    project_path: String,
+    root: PathBuf,
+    stop: Arc<AtomicBool>,
+) -> Result<(), String> {
+    let (tx, rx) = channel();
+
+    let config = Config::default()
+        .with_poll_interval(Duration::from_secs(2));
+
+    let mut watcher: RecommendedWatcher = Watcher::new(tx, config)
+        .map_err(|e| e.to_string())?;
+
+    // Root non-recursively for CLAUDE.md / .mcp.json, .claude/ recursively
+    watcher.watch(&root, RecursiveMode::NonRecursive)
+        .map_err(|e| e.to_string())?;
+
+    let claude_dir = root.join(".claude");
+    let mut claude_dir_watched = claude_dir.is_dir()
+        && watcher.watch(&claude_dir, RecursiveMode::Recursive).is_ok();
+
+    tracing::info!("Watching project config: {:?}", root);
+
+    let mut pending = PendingChanges::new();
+
+    while !stop.load(Ordering::Relaxed) {
+        match rx.recv_timeout(pending.wait()) {
+            Ok(Ok(event)) => {
+                // Pick up a .claude/ folder created after the watch started
+                if !claude_dir_watched && claude_dir.is_dir() {
+                    claude_dir_watched = watcher.watch(&claude_dir, RecursiveMode::Recursive).is_ok();
+                }
+                pending.add(&event);
+            }
+            Ok(Err(e)) => {
+                tracing::error!("Project watch error: {:?}", e);
+            }
+            Err(RecvTimeoutError::Timeout) => {}
+            Err(RecvTimeoutError::Disconnected) => break,
+        }
+        if pending.is_due() && !is_paused() {
+            handle_project_changes(&app_handle, &project_path, &root, pending.take());
+        }
+    }
+
+    Ok(())
+}
+
+/// Map a changed path to the project config area it belongs to
+fn classify_project_path(root: &Path, path: &Path) -> Option<&'static str> {
+    let relative = path.strip_prefix(root).ok()?;
+    let mut components = relative.components().map(|c| c.as_os_str().to_string_lossy().to_string());
+
+    match components.next()?.as_str() {
+        "CLAUDE.md" | "CLAUDE.local.md" => Some("claudeMd"),
+        ".mcp.json" => Some("mcp"),
+        ".claude" => match components.next().as_deref() {
+            Some("settings.json") | Some("settings.local.json") => Some("settings"),
+            Some("commands") => Some("commands"),
+            Some("skills") => Some("skills"),
+            Some("agents") => Some("agents"),
+            Some("hooks") => Some("hooks"),
+            Some("CLAUDE.md") => Some("claudeMd"),
+            _ => Some("claudeDir"),
+        },
+        _ => None,
+    }
+}
+
+fn event_kind_name(kind: &EventKind) -> Option<&'static str> {
+    match kind {
+        EventKind::Create(_) => Some("created"),
+        EventKind::Modify(_) => Some("modified"),
+        EventKind::Remove(_) => Some("removed"),
+        _ => None,
+    }
+}
+
+fn handle_project_changes(
+    app_handle: &AppHandle,
+    project_path: &str,
+    root: &Path,
+    changed: HashMap<PathBuf, &'static str>,
+) {
+    // Group paths by area and kind so one emit covers each
+    let mut grouped: HashMap<(&str, &str), Vec<String>> = HashMap::new();
+    for (path, kind) in changed {
+        // Ignore our own atomic-write temp files and writes
+        if path.extension().is_some_and(|e| e == "tmp") || is_expected_write(&path) {
+            continue;
+        }
+        if let Some(area) = classify_project_path(root, &path) {
+            grouped.entry((area, kind)).or_default().push(path.to_string_lossy().to_string());
+        }
+    }
+
+    // Projects that opted into AGENTS.md sync get it regenerated from the new memory
+    if grouped.keys().any(|(area, _)| *area == "claudeMd") {
+        crate::commands::agents_md::refresh_synced_agents_md(project_path);
+    }
+
+    for ((area, kind), paths) in grouped {
+        let _ = app_handle.emit("project-config-changed", ProjectConfigChange {
+            project_path: project_path.to_string(),
+            area: area.to_string(),
+            kind: kind.to_string(),
+            paths,
+        });
+    }
+}
+
+/// Events collected during the debounce window, keyed by path with the latest kind
+struct PendingChanges {
+    paths: HashMap<PathBuf, &'static str>,
+    /// When the first and the latest of the pending events arrived
+    first: Option<Instant>,
+    latest: Option<Instant>,
+}
+
+impl PendingChanges {
+    fn new() -> Self {
+        Self { paths: HashMap::new(), first: None, latest: None }
+    }
+
+    fn add(&mut self, event: &Event) {
+        if let Some(kind) = event_kind_name(&event.kind) {
+            for path in &event.paths {
+                self.paths.insert(path.clone(), kind);
+            }
+            let now = Instant::now();
+            self.first.get_or_insert(now);
+            self.latest = Some(now);
+        }
+    }
+
+    /// Quiet for the debounce period, or waiting since the first event for the longest delay allowed
+    fn is_due(&self) -> bool {
+        !self.paths.is_empty()
+            && (self.latest.is_some_and(|t| t.elapsed() >= DEBOUNCE) || self.first.is_some_and(|t| t.elapsed() >= MAX_DEBOUNCE))
+    }
+
+    /// How long to wait for the next event before checking whether the batch is due
+    fn wait(&self) -> Duration {
+        match (self.first, self.latest) {
+            (Some(first), Some(latest)) if !self.is_due() => {
+                DEBOUNCE.saturating_sub(latest.elapsed()).min(MAX_DEBOUNCE.saturating_sub(first.elapsed()))
+            }
+            _ => DEBOUNCE,
+        }
+    }
+
+    fn take(&mut self) -> HashMap<PathBuf, &'static str> {
+        self.first = None;
+        self.latest = None;
+        std::mem::take(&mut self.paths)
+    }
+}
+
+/// Quiet period before a batch of file events is processed
+const DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// Longest a batch waits after its first event, so a steady stream (a build writing into a
+/// watched project) still gets reported
+const MAX_DEBOUNCE: Duration = Duration::from_secs(2);
+
+/// How long a registered write stays expected
+const EXPECTED_WRITE_TTL: Duration = Duration::from_secs(10);
+
+/// Writes made by ClaudeArcade itself: path -> (content hash, registered at)
+static EXPECTED_WRITES: LazyLock<Mutex<HashMap<PathBuf, (u64, Instant)>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+fn content_hash(content: &[u8]) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    content.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Register a write the app is about to make so the watcher doesn't report it back.
+/// Call with the final path and the exact content being written.
+fn expect_write(path: &Path, content: &[u8]) {
+    if let Ok(mut writes) = EXPECTED_WRITES.lock() {
+        writes.retain(|_, (_, at)| at.elapsed() < EXPECTED_WRITE_TTL);
+        writes.insert(path.to_path_buf(), (content_hash(content), Instant::now()));
+    }
+}
+
+/// Atomically write one of the app's own changes: registered first so the watcher doesn't
+/// report it back, and recorded in the audit log once it has landed
+pub fn write_expected(path: impl AsRef<Path>, content: impl AsRef<[u8]>) -> std::io::Result<()> {
+    let (path, content) = (path.as_ref(), content.as_ref());
+    let before_hash = crate::audit::current_hash(path);
+    expect_write(path, content);
+    crate::paths::atomic_write(path, content)?;
+    crate::audit::record_write(path, before_hash, content);
+    Ok(())
+}
+
+/// Whether the file currently holds content the app registered as its own write.
+/// Matching on content means an external edit right after ours still gets through.
+fn is_expected_write(path: &Path) -> bool {
+    let expected = match EXPECTED_WRITES.lock() {
+        Ok(writes) => writes.get(path).filter(|(_, at)| at.elapsed() < EXPECTED_WRITE_TTL).map(|(hash, _)| *hash),
+        Err(_) => None,
+    };
+
+    match (expected, std::fs::read(path)) {
+        (Some(hash), Ok(content)) => content_hash(&content) == hash,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use notify::event::{CreateKind, EventKind};
+
+    #[test]
+    fn steady_events_are_flushed_after_the_longest_delay() {
+        let mut pending = PendingChanges::new();
+        assert!(!pending.is_due());
+        pending.add(&Event::new(EventKind::Create(CreateKind::File)).add_path(PathBuf::from("/a")));
+        assert!(!pending.is_due());
+        assert!(pending.wait() <= DEBOUNCE);
+
+        // Events keep arriving, but the first one is old enough
+        pending.first = Instant::now().checked_sub(MAX_DEBOUNCE);
+        assert!(pending.is_due());
+        assert_eq!(pending.take().len(), 1);
+        assert!(!pending.is_due());
+    }
 }
@@ -0,0 +1,124 @@
+//! Claude Code installs inside WSL, seen from Windows through `\\wsl$`
+//! People often run the CLI in a distro while the app runs on Windows, so those config folders can be
+//! scanned as a second root. Elsewhere there is nothing to detect.
+
+use serde::Serialize;
+use std::path::PathBuf;
+
+use crate::arcade_settings::load_arcade_settings;
+use crate::paths::ConfigPaths;
+
+/// Prefixes Windows exposes WSL filesystems under
+const WSL_PREFIXES: [&str; 2] = [r"\\wsl$\", r"\\wsl.localhost\"];
+
+/// A `.claude` folder found inside a WSL distro
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WslEnvironment {
+    pub distro: String,
+    pub user: String,
+    pub claude_dir: String,
+    /// Whether it is scanned with the inventory
+    pub enabled: bool,
+}
+
+/// Distro names from `wsl.exe --list --quiet`, which writes UTF-16LE
+#[cfg_attr(not(windows), allow(dead_code))]
+fn parse_distro_list(output: &[u8]) -> Vec<String> {
+    let units: Vec<u16> = output.chunks_exact(2).map(|pair| u16::from_le_bytes([pair[0], pair[1]])).collect();
+    String::from_utf16_lossy(&units)
+        .lines()
+        .map(|line| line.trim_matches(|c: char| c.is_whitespace() || c == '\u{feff}' || c == '\0'))
+        .filter(|line| !line.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+/// Distro and home folder of a `\\wsl$\<distro>\...\.claude` path
+pub(crate) fn parse_wsl_dir(claude_dir: &str) -> Option<(String, PathBuf)> {
+    let rest = WSL_PREFIXES.iter().find_map(|prefix| {
+        claude_dir.get(..prefix.len()).filter(|head| head.eq_ignore_ascii_case(prefix)).map(|_| &claude_dir[prefix.len()..])
+    })?;
+    let distro = rest.split('\\').next().filter(|d| !d.is_empty())?;
+    let (home, _) = claude_dir.trim_end_matches('\\').rsplit_once('\\')?;
+    Some((distro.to_string(), PathBuf::from(home)))
+}
+
+#[cfg(windows)]
+fn installed_distros() -> Vec<String> {
+    crate::process::ProcessRequest::new("wsl.exe")
+        .args(["--list", "--quiet"])
+        .timeout(std::time::Duration::from_secs(10))
+        .run()
+        .ok()
+        .filter(|output| output.success())
+        .map(|output| parse_distro_list(&output.stdout))
+        .unwrap_or_default()
+}
+
+#[cfg(not(windows))]
+fn installed_distros() -> Vec<String> {
+    Vec::new()
+}
+
+/// `.claude` folders of every user in every installed distro
+pub fn detect_environments() -> Vec<WslEnvironment> {
+    let enabled = load_arcade_settings().wsl_config_dirs;
+    let mut found = Vec::new();
+    for distro in installed_distros() {
+        let root = PathBuf::from(format!(r"\\wsl$\{}", distro));
+        let mut homes: Vec<(String, PathBuf)> = std::fs::read_dir(root.join("home"))
+            .into_iter()
+            .flatten()
+            .flatten()
+            .map(|entry| (entry.file_name().to_string_lossy().to_string(), entry.path()))
+            .collect();
+        homes.sort();
+        homes.push(("root".to_string(), root.join("root")));
+
+        for (user, home) in homes {
+            let claude_dir = home.join(".claude");
+            if !claude_dir.is_dir() {
+                continue;
+            }
+            let claude_dir = claude_dir.to_string_lossy().to_string();
+            found.push(WslEnvironment {
+                distro: distro.clone(),
+                user,
+                enabled: enabled.contains(&claude_dir),
+                claude_dir,
+            });
+        }
+    }
+    found
+}
+
+/// Environment tag (`wsl:<distro>`) and roots of each WSL folder turned on in arcade settings
+pub fn enabled_roots() -> Vec<(String, ConfigPaths)> {
+    load_arcade_settings()
+        .wsl_config_dirs
+        .iter()
+        .filter_map(|dir| {
+            let (distro, home) = parse_wsl_dir(dir)?;
+            let paths = ConfigPaths { home, claude_dir: PathBuf::from(dir), project: None };
+            Some((format!("wsl:{}", distro), paths))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_distros_and_wsl_paths() {
+        let output: Vec<u8> = "\u{feff}Ubuntu\r\nDebian\r\n\r\n".encode_utf16().flat_map(u16::to_le_bytes).collect();
+        assert_eq!(parse_distro_list(&output), vec!["Ubuntu", "Debian"]);
+
+        let (distro, home) = parse_wsl_dir(r"\\wsl$\Ubuntu\home\sam\.claude").unwrap();
+        assert_eq!(distro, "Ubuntu");
+        assert_eq!(home, PathBuf::from(r"\\wsl$\Ubuntu\home\sam"));
+        assert_eq!(parse_wsl_dir(r"\\WSL.localhost\Debian\root\.claude").unwrap().0, "Debian");
+        assert!(parse_wsl_dir(r"C:\Users\sam\.claude").is_none());
+    }
+}
@@ -1,5 +1,6 @@
 import { useState, useEffect, useCallback } from 'react';
 import { invoke } from '@tauri-apps/api/core';
+import { errorMessage, withSecretsConfirmation } from '../../utils/errors';
 
 interface ClaudeMdEditorProps {
   isOpen: boolean;
@@ -28,7 +29,7 @@ export function ClaudeMdEditor({ isOpen, onClose }: ClaudeMdEditorProps) {
       setGlobalContent(global);
     } catch (err) {
       console.error('Failed to load global CLAUDE.md:', err);
-      setError(err instanceof Error ? err.message : String(err));
+      setError(errorMessage(err));
     }
 
     setIsLoading(false);
@@ -47,16 +48,21 @@ export function ClaudeMdEditor({ isOpen, onClose }: ClaudeMdEditorProps) {
 
     try {
       if (activeTab === 'global') {
-        await invoke('write_global_claude_md', { content: globalContent });
+        await withSecretsConfirmation((allowSecrets) =>
+          invoke('write_global_claude_md', { content: globalContent, allowSecrets })
+        );
       } else if (projectPath) {
-        await invoke('write_project_claude_md', {
-          projectPath,
-          content: projectContent,
-        });
+        await withSecretsConfirmation((allowSecrets) =>
+          invoke('write_project_claude_md', {
+            projectPath,
+            content: projectContent,
+            allowSecrets,
+          })
+        );
       }
       setHasChanges(false);
     } catch (err) {
-      setError(err instanceof Error ? err.message : String(err));
+      setError(errorMessage(err));
     }
 
     setIsSaving(false);
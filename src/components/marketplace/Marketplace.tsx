@@ -11,6 +11,7 @@ import {
 } from '../../services/github';
 import type { MarketplaceItem } from '../../types/mcp';
 import mcpCatalogData from '../../data/mcp-catalog.json';
+import { errorMessage, withInjectionConfirmation } from '../../utils/errors';
 
 interface MarketplaceProps {
   isOpen: boolean;
@@ -191,12 +192,15 @@ export function Marketplace({ isOpen, onClose, installedServers, onRefresh }: Ma
     try {
       if (item.type === 'skill') {
         // Install skill via download_skill command
-        await invoke('download_skill', {
-          skillId: item.id,
-          skillName: item.name,
-          isGlobal: true,
-          projectPath: null,
-        });
+        await withInjectionConfirmation((acceptRisk) =>
+          invoke('download_skill', {
+            skillId: item.id,
+            skillName: item.name,
+            isGlobal: true,
+            projectPath: null,
+            acceptRisk,
+          })
+        );
         await fetchInstalledSkills();
       } else if (item.type === 'mcp') {
         // Install MCP server
@@ -213,7 +217,7 @@ export function Marketplace({ isOpen, onClose, installedServers, onRefresh }: Ma
         onRefresh();
       }
     } catch (err) {
-      setError(`Failed to install ${item.name}: ${err}`);
+      setError(`Failed to install ${item.name}: ${errorMessage(err)}`);
     } finally {
       setInstalling(null);
     }
@@ -238,7 +242,7 @@ export function Marketplace({ isOpen, onClose, installedServers, onRefresh }: Ma
         onRefresh();
       }
     } catch (err) {
-      setError(`Failed to remove ${item.name}: ${err}`);
+      setError(`Failed to remove ${item.name}: ${errorMessage(err)}`);
     } finally {
       setInstalling(null);
     }
@@ -14,6 +14,11 @@ interface PermissionsConfig {
   deny: string[];
 }
 
+interface PermissionsAtRevision extends PermissionsConfig {
+  /** settings.json revision the permissions were read at */
+  revision: string;
+}
+
 // Common permission patterns
 const COMMON_PATTERNS = [
   { pattern: 'Bash(*)', description: 'All bash commands' },
@@ -126,6 +131,7 @@ export function PermissionsEditor({ onClose }: PermissionsEditorProps) {
   const [isLoading, setIsLoading] = useState(true);
   const [isSaving, setIsSaving] = useState(false);
   const [error, setError] = useState<string | null>(null);
+  const [revision, setRevision] = useState<string | null>(null);
   const [newPattern, setNewPattern] = useState('');
   const [showSuggestions, setShowSuggestions] = useState(false);
 
@@ -138,7 +144,8 @@ export function PermissionsEditor({ onClose }: PermissionsEditorProps) {
     setIsLoading(true);
     setError(null);
     try {
-      const config = await invoke<PermissionsConfig>('get_permissions');
+      const config = await invoke<PermissionsAtRevision>('get_permissions');
+      setRevision(config.revision);
       const allPermissions: Permission[] = [
         ...config.allow.map((p) => ({ pattern: p, level: 'allow' as PermissionLevel })),
         ...config.ask.map((p) => ({ pattern: p, level: 'ask' as PermissionLevel })),
@@ -164,10 +171,11 @@ export function PermissionsEditor({ onClose }: PermissionsEditorProps) {
         ask: permissions.filter((p) => p.level === 'ask').map((p) => p.pattern),
         deny: permissions.filter((p) => p.level === 'deny').map((p) => p.pattern),
       };
-      await invoke('set_permissions', { permissions: config });
+      setRevision(await invoke<string>('set_permissions', { permissions: config, revision }));
     } catch (e) {
       console.error('Failed to save permissions:', e);
-      setError('Failed to save permissions');
+      const conflict = (e as { code?: string } | null)?.code === 'settingsConflict';
+      setError(conflict ? 'settings.json changed since it was loaded; reload and try again' : 'Failed to save permissions');
     }
     setIsSaving(false);
   };
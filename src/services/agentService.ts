@@ -4,6 +4,7 @@
  */
 
 import { invoke } from '@tauri-apps/api/core';
+import { withSecretsConfirmation } from '../utils/errors';
 
 /** Agent configuration (from YAML frontmatter + body) */
 export interface AgentConfig {
@@ -109,7 +110,7 @@ export async function getAgentContent(
 }
 
 /**
- * Save raw agent content
+ * Save raw agent content, asking before writing anything that looks like a credential
  */
 export async function saveAgentContent(
   agentId: string,
@@ -117,7 +118,9 @@ export async function saveAgentContent(
   isGlobal: boolean,
   projectPath?: string
 ): Promise<void> {
-  return invoke<void>('save_agent_content', { agentId, content, isGlobal, projectPath });
+  return withSecretsConfirmation((allowSecrets) =>
+    invoke<void>('save_agent_content', { agentId, content, isGlobal, projectPath, allowSecrets })
+  );
 }
 
 /**
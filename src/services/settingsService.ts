@@ -0,0 +1,40 @@
+/**
+ * Settings Service - Writes to ~/.claude/settings.json
+ * Writes that look like they contain credentials are confirmed with the user first
+ */
+
+import { invoke } from '@tauri-apps/api/core';
+import { withSecretsConfirmation } from '../utils/errors';
+
+/** A settings.json value with the revision to write it back against */
+export type AtRevision<T> = T & { revision: string };
+
+/** One entry of the settings `env` block */
+export interface EnvVar {
+  name: string;
+  value: string;
+  isSecret: boolean;
+  placeholder: boolean;
+}
+
+/**
+ * Set one settings field by dotted key (null removes it)
+ */
+export async function updateSettingsField(
+  key: string,
+  value: unknown,
+  revision?: string
+): Promise<AtRevision<Record<string, unknown>>> {
+  return withSecretsConfirmation((allowSecrets) =>
+    invoke<AtRevision<Record<string, unknown>>>('update_settings_field', { key, value, allowSecrets, revision })
+  );
+}
+
+/**
+ * Replace the settings `env` block (an empty one removes it)
+ */
+export async function setSettingsEnv(vars: Record<string, string>, revision?: string): Promise<EnvVar[]> {
+  return withSecretsConfirmation((allowSecrets) =>
+    invoke<EnvVar[]>('set_settings_env', { vars, allowSecrets, revision })
+  );
+}
@@ -0,0 +1,67 @@
+/** What a command returning `ArcadeError` (src-tauri/src/error.rs) rejects with */
+export interface ArcadeError {
+  code: string;
+  message: string;
+  [detail: string]: unknown;
+}
+
+export function isArcadeError(err: unknown): err is ArcadeError {
+  return typeof err === 'object' && err !== null && typeof (err as ArcadeError).code === 'string';
+}
+
+/** Readable text for a rejected invoke, whether the command returns a string or an ArcadeError */
+export function errorMessage(err: unknown): string {
+  if (isArcadeError(err)) return err.message;
+  if (err instanceof Error) return err.message;
+  return String(err);
+}
+
+/** A likely credential the backend found in content about to be written */
+export interface SecretFinding {
+  kind: string;
+  line: number;
+  preview: string;
+}
+
+/**
+ * Run a write that the backend refuses when the content looks like it holds credentials. On that
+ * refusal, list what was found and, once the user confirms, retry with `allowSecrets`.
+ */
+export async function withSecretsConfirmation<T>(write: (allowSecrets: boolean) => Promise<T>): Promise<T> {
+  try {
+    return await write(false);
+  } catch (err) {
+    if (!isArcadeError(err) || err.code !== 'secretsDetected') throw err;
+    const findings = (err.findings as SecretFinding[])
+      .map((f) => `- ${f.kind} on line ${f.line}: ${f.preview}`)
+      .join('\n');
+    if (!confirm(`This looks like it contains credentials:\n\n${findings}\n\nSave it anyway?`)) throw err;
+    return write(true);
+  }
+}
+
+/** Something in downloaded content that looks like prompt injection */
+export interface InjectionFinding {
+  kind: string;
+  file: string;
+  line: number;
+  excerpt: string;
+}
+
+/**
+ * Run an install that the backend refuses when the content scores high for prompt injection. On
+ * that refusal, list what was found and, once the user accepts the risk, retry with `acceptRisk`.
+ */
+export async function withInjectionConfirmation<T>(install: (acceptRisk: boolean) => Promise<T>): Promise<T> {
+  try {
+    return await install(false);
+  } catch (err) {
+    if (!isArcadeError(err) || err.code !== 'injectionRisk') throw err;
+    const findings = (err.findings as InjectionFinding[])
+      .map((f) => `- ${f.kind} in ${f.file} line ${f.line}: ${f.excerpt}`)
+      .join('\n');
+    const prompt = `This may try to hijack Claude's instructions (risk score ${err.score}):\n\n${findings}\n\nInstall it anyway?`;
+    if (!confirm(prompt)) throw err;
+    return install(true);
+  }
+}